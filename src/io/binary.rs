@@ -0,0 +1,189 @@
+//! A compact binary format for checkpoints.
+//!
+//! Reducing the boundary matrix of a very large complex can take hours.  The functions in this
+//! module save intermediate results, so that a computation can be resumed after it is
+//! interrupted: any type that implements serde's `Serialize` and `Deserialize` can be saved,
+//! including [`VecOfVec`](crate::matrices::implementors::vec_of_vec::VecOfVec),
+//! [`VecCsv`](crate::matrices::implementors::vec_of_csvec::VecCsv),
+//! [`BiMapSequential`](crate::utilities::sequences_and_ordinals::BiMapSequential) and the output
+//! of a factorization, [`RightReduction`](crate::matrix_factorization::factors::RightReduction).
+//! This module is available with the `binary` feature.
+//!
+//! A checkpoint consists of
+//!
+//! - the four bytes `SOLR`,
+//! - the format version, [`FORMAT_VERSION`], as a little-endian `u32`, and
+//! - the value, encoded with [bincode](https://docs.rs/bincode): integers are little-endian and
+//!   have fixed width, and sequences are prefixed with their length as a `u64`.
+//!
+//! The format records neither the type of the value nor its coefficient ring, so a checkpoint
+//! must be loaded as the type it was saved as.  Loading fails with an error of kind
+//! [`InvalidData`](std::io::ErrorKind::InvalidData) if the header is wrong or the data cannot be
+//! decoded.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::io::binary::{load, save};
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::MajorDimension;
+//!
+//! let matrix  =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (0, 1.) ], vec![], vec![ (1, -2.), (2, 0.5) ] ] );
+//!
+//! let mut file                                =   Vec::new();
+//! save( &mut file, &matrix ).unwrap();
+//! let loaded: VecOfVec< (usize, f64) >        =   load( file.as_slice() ).unwrap();
+//!
+//! assert_eq!( loaded.major_dimension, MajorDimension::Col );
+//! assert_eq!( loaded.vec_of_vec, matrix.vec_of_vec );
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+
+/// The first four bytes of every checkpoint.
+pub const MAGIC: [ u8; 4 ] = *b"SOLR";
+
+/// The version of the format written by [`save`]; [`load`] rejects any other version.
+pub const FORMAT_VERSION: u32 = 1;
+
+
+/// Convert a bincode error to an I/O error, passing I/O errors through unchanged.
+fn to_io_error( error: bincode::ErrorKind ) -> io::Error {
+    match error {
+        bincode::ErrorKind::Io( error ) =>  error,
+        error                           =>  io::Error::new( io::ErrorKind::InvalidData, error ),
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  READERS AND WRITERS
+//  ---------------------------------------------------------------------------
+
+
+/// Write `value` to `writer` as a checkpoint.
+///
+/// The writer is not buffered by this function; wrap it in a [`BufWriter`] if it is a file.
+pub fn save< Writer, T >( mut writer: Writer, value: &T ) -> io::Result< () >
+    where   Writer:     Write,
+            T:          Serialize + ?Sized,
+{
+    writer.write_all( &MAGIC )?;
+    writer.write_all( &FORMAT_VERSION.to_le_bytes() )?;
+    bincode::serialize_into( &mut writer, value ).map_err( |error| to_io_error( *error ) )?;
+    writer.flush()
+}
+
+/// Read a checkpoint written by [`save`] from `reader`.
+///
+/// The reader is not buffered by this function; wrap it in a [`BufReader`] if it is a file.
+pub fn load< Reader, T >( mut reader: Reader ) -> io::Result< T >
+    where   Reader:     Read,
+            T:          DeserializeOwned,
+{
+    let mut header      =   [ 0u8; 8 ];
+    reader.read_exact( &mut header )?;
+    if header[ .. 4 ] != MAGIC {
+        return Err( io::Error::new( io::ErrorKind::InvalidData, "not a checkpoint: the file does not start with `SOLR`" ) )
+    }
+    let version         =   u32::from_le_bytes( [ header[ 4 ], header[ 5 ], header[ 6 ], header[ 7 ] ] );
+    if version != FORMAT_VERSION {
+        return Err( io::Error::new( io::ErrorKind::InvalidData, format!( "unsupported checkpoint format version {} (expected {})", version, FORMAT_VERSION ) ) )
+    }
+    bincode::deserialize_from( reader ).map_err( |error| to_io_error( *error ) )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  FILES
+//  ---------------------------------------------------------------------------
+
+
+/// Save `value` to the file at `path`, replacing it if it exists.
+///
+/// The checkpoint is first written to a temporary file in the same directory (`path` with
+/// `.tmp` appended), which is then renamed to `path`; so if the computation is interrupted while
+/// saving, the previous checkpoint at `path` is left intact.
+pub fn save_to_path< T, P >( path: P, value: &T ) -> io::Result< () >
+    where   T:          Serialize + ?Sized,
+            P:          AsRef< Path >,
+{
+    let path            =   path.as_ref();
+    let mut temporary   =   path.as_os_str().to_owned();
+    temporary.push( ".tmp" );
+    {
+        let file        =   File::create( &temporary )?;
+        save( BufWriter::new( &file ), value )?;
+        file.sync_all()?;
+    }
+    fs::rename( &temporary, path )
+}
+
+/// Load a checkpoint from the file at `path`.
+pub fn load_from_path< T, P >( path: P ) -> io::Result< T >
+    where   T:          DeserializeOwned,
+            P:          AsRef< Path >,
+{
+    load( BufReader::new( File::open( path )? ) )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_csvec::VecCsv;
+    use crate::matrices::matrix_oracle::MajorDimension;
+    use crate::matrix_factorization::factors::{right_reduce_factored, RightReduction};
+    use crate::rings::ring_native::NativeDivisionRing;
+    use crate::utilities::sequences_and_ordinals::BiMapSequential;
+
+    /// Save `value`, and load it back.
+    fn round_trip< T: Serialize + DeserializeOwned >( value: &T ) -> T {
+        let mut bytes   =   Vec::new();
+        save( &mut bytes, value ).unwrap();
+        load( bytes.as_slice() ).unwrap()
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let csv         =   VecCsv::from_triplets( MajorDimension::Row, 3, vec![ (0, 2, 1i64), (2, 0, -4), (2, 1, 7) ] );
+        assert_eq!( round_trip( &csv ), csv );
+
+        let bimap       =   BiMapSequential::from_vec( vec![ vec![ 0, 1 ], vec![ 0, 2 ], vec![ 1, 2 ] ] );
+        assert_eq!( round_trip( &bimap ), bimap );
+
+        let boundary    =   vec![ vec![], vec![], vec![], vec![ (0, -1.), (1, 1.) ], vec![ (0, -1.), (2, 1.) ], vec![ (1, -1.), (2, 1.) ] ];
+        let factors     =   right_reduce_factored( &boundary, NativeDivisionRing::< f64 >::new() );
+        let loaded: RightReduction< f64 >   =   round_trip( &factors );
+        assert_eq!( loaded, factors );
+    }
+
+    #[test]
+    fn test_files_and_malformed_checkpoints() {
+        let path        =   std::env::temp_dir().join( format!( "solar_binary_test_{}.bin", std::process::id() ) );
+        save_to_path( &path, &vec![ 1u8, 2, 3 ] ).unwrap();
+        save_to_path( &path, &vec![ 4u8, 5 ] ).unwrap();           // overwrite
+        assert_eq!( load_from_path::< Vec< u8 >, _ >( &path ).unwrap(), vec![ 4, 5 ] );
+        fs::remove_file( &path ).unwrap();
+
+        let mut bytes   =   Vec::new();
+        save( &mut bytes, &( 1u64, 2u64 ) ).unwrap();
+        assert_eq!( bytes.len(), 8 + 16 );
+
+        // a wrong magic number, a future version, and a truncated value
+        let mut wrong   =   bytes.clone();
+        wrong[ 0 ]      =   b'X';
+        assert_eq!( load::< _, ( u64, u64 ) >( wrong.as_slice() ).unwrap_err().kind(), io::ErrorKind::InvalidData );
+        let mut wrong   =   bytes.clone();
+        wrong[ 4 ]      =   2;
+        assert_eq!( load::< _, ( u64, u64 ) >( wrong.as_slice() ).unwrap_err().kind(), io::ErrorKind::InvalidData );
+        assert_eq!( load::< _, ( u64, u64 ) >( &bytes[ .. 20 ] ).unwrap_err().kind(), io::ErrorKind::UnexpectedEof );
+    }
+}
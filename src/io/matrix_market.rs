@@ -0,0 +1,327 @@
+//! The Matrix Market exchange format for sparse matrices.
+//!
+//! A Matrix Market file in coordinate format (the usual `.mtx` file) looks like this:
+//!
+//! ```text
+//! %%MatrixMarket matrix coordinate integer general
+//! % comments start with a percent sign
+//! 2 3 2
+//! 1 1 5
+//! 2 3 -1
+//! ```
+//!
+//! The header names the field of the coefficients (`real`, `integer` or `pattern`) and the
+//! symmetry of the matrix (`general`, `symmetric` or `skew-symmetric`).  The first line after the
+//! comments gives the number of rows, the number of columns and the number of stored entries; the
+//! remaining lines list the entries as `row col value`, with rows and columns numbered from 1.
+//! Complex and hermitian matrices, and the dense `array` format, are not supported.
+//!
+//! - [`read_matrix_market`] reads a file into a [`Coo`], with coefficients in a ring chosen by
+//!   the caller; call [`Coo::into_vec_of_vec`] or [`Coo::into_vec_csv`] to choose a storage format.
+//! - [`write_matrix_market`] writes the major views of any matrix oracle.
+//!
+//! Rows and columns are numbered from 0 in memory, and from 1 in the file.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::io::matrix_market::{read_matrix_market, write_matrix_market, Field};
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend};
+//! use solar::rings::ring_native::NativeRing;
+//!
+//! let file    =   "%%MatrixMarket matrix coordinate integer symmetric\n\
+//!                  3 3 2\n\
+//!                  1 1 5\n\
+//!                  3 1 -1\n";
+//! let ring    =   NativeRing::<i64>::new();
+//! let coo     =   read_matrix_market( file.as_bytes(), ring.clone() ).unwrap();
+//! let matrix  =   coo.into_vec_of_vec( MajorDimension::Row, ring );
+//! assert_eq!( matrix.view_major_ascend( 0 ).collect::<Vec<_>>(), vec![ (0, 5), (2, -1) ] );
+//!
+//! let mut out =   Vec::new();
+//! write_matrix_market( &mut out, &matrix, 0 .. 3, 3, 3, Field::Integer ).unwrap();
+//! assert_eq!( String::from_utf8( out ).unwrap(),
+//!             "%%MatrixMarket matrix coordinate integer general\n3 3 3\n1 1 5\n1 3 -1\n3 1 -1\n" );
+//! ```
+
+use crate::matrices::implementors::coo::Coo;
+use crate::matrices::matrix_oracle::{OracleMajor, WhichMajor, MajorDimension};
+use crate::rings::ring::Ring;
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+
+/// The field of the coefficients in a Matrix Market file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    /// Floating point coefficients.
+    Real,
+    /// Integer coefficients.
+    Integer,
+    /// No coefficients: every stored entry equals one.
+    Pattern,
+}
+
+impl Field {
+    /// The name of the field in a Matrix Market header.
+    pub fn name( &self ) -> &'static str {
+        match self { Field::Real => "real", Field::Integer => "integer", Field::Pattern => "pattern" }
+    }
+}
+
+/// The symmetry of a Matrix Market file; only the lower triangle of a symmetric or
+/// skew-symmetric matrix is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+}
+
+/// The largest number of entries the reader allocates for before it has read them.
+const MAX_PREALLOCATED_ENTRIES: usize = 1 << 20;
+
+/// An error for malformed input, tagged with the line number (counting from 1).
+fn invalid( line: usize, message: impl Display ) -> io::Error {
+    io::Error::new( io::ErrorKind::InvalidData, format!( "line {}: {}", line, message ) )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  READ
+//  ---------------------------------------------------------------------------
+
+
+/// Read a matrix in Matrix Market coordinate format, parsing coefficients with `FromStr`.
+///
+/// See [`read_matrix_market_with`] for details.
+pub fn read_matrix_market< Reader, Val, RingOperator >( reader: Reader, ring: RingOperator ) -> io::Result< Coo< Val > >
+    where   Reader:         BufRead,
+            Val:            Clone + FromStr,
+            RingOperator:   Ring< Val >,
+{
+    read_matrix_market_with( reader, ring, |text| text.parse().ok() )
+}
+
+/// Read a matrix in Matrix Market coordinate format, parsing coefficients with `parse`.
+///
+/// - Entries of a `pattern` matrix have coefficient `RingOperator::one()`.
+/// - The lower triangle of a `symmetric` matrix is mirrored across the diagonal; the lower
+///   triangle of a `skew-symmetric` matrix is mirrored and negated with `ring`.
+/// - Entries are returned in the order they appear in the file.  Repeated positions are kept as
+///   separate triplets; they are summed when the [`Coo`] is converted to another format.
+///
+/// Returns an error of kind `InvalidData` if the file is malformed, if `parse` returns `None`, or
+/// if the number of entries does not match the size line.
+///
+/// # Examples
+///
+/// ```
+/// use solar::io::matrix_market::read_matrix_market_with;
+/// use solar::rings::field_prime::GF2;
+///
+/// // read integer coefficients into GF(2)
+/// let file    =   "%%MatrixMarket matrix coordinate integer general\n2 2 2\n1 2 3\n2 1 4\n";
+/// let coo     =   read_matrix_market_with( file.as_bytes(), GF2::new(), |text| text.parse::<i64>().ok().map( |n| n % 2 != 0 ) ).unwrap();
+/// assert_eq!( coo.triplets(), &vec![ (0, 1, true), (1, 0, false) ] );
+/// ```
+pub fn read_matrix_market_with< Reader, Val, RingOperator, Parse >(
+            reader:         Reader,
+            ring:           RingOperator,
+            mut parse:      Parse,
+        )
+        ->
+        io::Result< Coo< Val > >
+
+    where   Reader:         BufRead,
+            Val:            Clone,
+            RingOperator:   Ring< Val >,
+            Parse:          FnMut( &str ) -> Option< Val >,
+{
+    let mut lines       =   reader.lines().enumerate().map( |( i, line )| line.map( |line| ( i + 1, line ) ) );
+
+    // header
+    let ( _, header )   =   lines.next().ok_or_else( || invalid( 1, "missing header" ) )??;
+    let words: Vec< _ > =   header.split_whitespace().map( |word| word.to_lowercase() ).collect();
+    if words.len() != 5 || words[0] != "%%matrixmarket" || words[1] != "matrix" {
+        return Err( invalid( 1, "expected a header of the form `%%MatrixMarket matrix coordinate <field> <symmetry>`" ) )
+    }
+    if words[2] != "coordinate" { return Err( invalid( 1, format!( "unsupported format `{}`", words[2] ) ) ) }
+    let field           =   match words[3].as_str() {
+                                "real"      =>  Field::Real,
+                                "integer"   =>  Field::Integer,
+                                "pattern"   =>  Field::Pattern,
+                                other       =>  return Err( invalid( 1, format!( "unsupported field `{}`", other ) ) ),
+                            };
+    let symmetry        =   match words[4].as_str() {
+                                "general"           =>  Symmetry::General,
+                                "symmetric"         =>  Symmetry::Symmetric,
+                                "skew-symmetric"    =>  Symmetry::SkewSymmetric,
+                                other               =>  return Err( invalid( 1, format!( "unsupported symmetry `{}`", other ) ) ),
+                            };
+
+    // skip comments and blank lines
+    let mut lines       =   lines.filter( |line| match line {
+                                Ok( ( _, line ) )   =>  ! ( line.trim().is_empty() || line.starts_with( '%' ) ),
+                                Err( _ )            =>  true,
+                            } );
+    let parse_index     =   |number: usize, word: Option< &str >| -> io::Result< usize > {
+                                word.and_then( |word| word.parse().ok() ).ok_or_else( || invalid( number, "expected a nonnegative integer" ) )
+                            };
+
+    // size line
+    let ( number, size ) =   lines.next().ok_or_else( || invalid( 2, "missing size line" ) )??;
+    let mut words       =   size.split_whitespace();
+    let num_rows        =   parse_index( number, words.next() )?;
+    let num_cols        =   parse_index( number, words.next() )?;
+    let num_entries     =   parse_index( number, words.next() )?;
+
+    // entries; the entry count comes from the file, so preallocate at most a bounded amount and
+    // let the storage grow as entries arrive
+    let capacity        =   num_entries.min( num_rows.saturating_mul( num_cols ) ).min( MAX_PREALLOCATED_ENTRIES );
+    let mut coo         =   Coo::with_capacity( num_rows, num_cols, capacity );
+    let mut num_read    =   0;
+    let mut last_line   =   number;
+    for line in lines {
+        let ( number, line )    =   line?;
+        last_line       =   number;
+        num_read        +=  1;
+        if num_read > num_entries { return Err( invalid( number, format!( "expected {} entries", num_entries ) ) ) }
+
+        let mut words   =   line.split_whitespace();
+        let row         =   parse_index( number, words.next() )?;
+        let col         =   parse_index( number, words.next() )?;
+        if row == 0 || row > num_rows || col == 0 || col > num_cols {
+            return Err( invalid( number, format!( "position ({}, {}) lies outside a {} x {} matrix", row, col, num_rows, num_cols ) ) )
+        }
+        let val         =   match field {
+                                Field::Pattern  =>  RingOperator::one(),
+                                _               =>  words.next()
+                                                        .and_then( &mut parse )
+                                                        .ok_or_else( || invalid( number, "could not parse the coefficient" ) )?,
+                            };
+        let ( row, col )    =   ( row - 1, col - 1 );
+        if row != col {
+            match symmetry {
+                Symmetry::General       =>  {},
+                Symmetry::Symmetric     =>  coo.push( col, row, val.clone() ),
+                Symmetry::SkewSymmetric =>  coo.push( col, row, ring.negate( val.clone() ) ),
+            }
+        }
+        coo.push( row, col, val );
+    }
+    if num_read < num_entries {
+        return Err( invalid( last_line, format!( "expected {} entries, found {}", num_entries, num_read ) ) )
+    }
+    Ok( coo )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  WRITE
+//  ---------------------------------------------------------------------------
+
+
+/// Write the major views of `matrix` with the given keys, in Matrix Market coordinate format.
+///
+/// The matrix is written as a `general` matrix with `num_rows` rows and `num_cols` columns.  If
+/// the matrix is row-major, major views are rows; if it is column-major, major views are columns.
+/// Coefficients are written with `Display`, except that no coefficients are written if `field` is
+/// [`Field::Pattern`].  Entries are written in the order of `major_keys`, and then in the order of
+/// each major view; explicitly stored zeros are written like any other entry.
+pub fn write_matrix_market< 'a, Writer, Matrix, MajKeys, Val >(
+            mut writer:     Writer,
+            matrix:         &'a Matrix,
+            major_keys:     MajKeys,
+            num_rows:       usize,
+            num_cols:       usize,
+            field:          Field,
+        )
+        ->
+        io::Result<()>
+
+    where   Writer:         Write,
+            Matrix:         OracleMajor< 'a, usize, usize, Val > + WhichMajor,
+            MajKeys:        IntoIterator< Item = usize >,
+            Val:            Display,
+{
+    // the size line needs the number of entries, so collect them first
+    let transpose       =   matrix.major_dimension() == MajorDimension::Col;
+    let entries: Vec< _ >   =   major_keys.into_iter()
+                                    .flat_map(  |major| matrix.view_major( major ).into_iter().map(
+                                                    move |entry| {
+                                                        let ( row, col )    =   if transpose { ( entry.key(), major ) } else { ( major, entry.key() ) };
+                                                        ( row, col, entry.val() )
+                                                    }
+                                                )
+                                            )
+                                    .collect();
+
+    writeln!( writer, "%%MatrixMarket matrix coordinate {} general", field.name() )?;
+    writeln!( writer, "{} {} {}", num_rows, num_cols, entries.len() )?;
+    for ( row, col, val ) in entries {
+        match field {
+            Field::Pattern  =>  writeln!( writer, "{} {}", row + 1, col + 1 )?,
+            _               =>  writeln!( writer, "{} {} {}", row + 1, col + 1, val )?,
+        }
+    }
+    Ok(())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::matrices::matrix_oracle::OracleMajorAscend;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_write_then_read_column_major() {
+        let ring        =   NativeRing::<i64>::new();
+        let matrix      =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (1, 2) ], vec![], vec![ (0, -3), (1, 4) ] ] );
+
+        let mut out     =   Vec::new();
+        write_matrix_market( &mut out, &matrix, 0 .. 3, 2, 3, Field::Integer ).unwrap();
+        let text        =   String::from_utf8( out ).unwrap();
+        assert_eq!( text, "%%MatrixMarket matrix coordinate integer general\n2 3 3\n2 1 2\n1 3 -3\n2 3 4\n" );
+
+        let coo         =   read_matrix_market( text.as_bytes(), ring.clone() ).unwrap();
+        assert_eq!( ( coo.num_rows(), coo.num_cols() ), ( 2, 3 ) );
+        let read        =   coo.into_vec_of_vec( MajorDimension::Col, ring );
+        assert!( read.equal_modulo_zeros( &matrix, NativeRing::<i64>::new() ) );
+    }
+
+    #[test]
+    fn test_read_pattern_and_skew_symmetric() {
+        let ring        =   NativeRing::<i64>::new();
+        let pattern     =   "%%MatrixMarket matrix coordinate pattern general\n% a comment\n\n2 2 2\n1 2\n2 2\n";
+        let coo         =   read_matrix_market::< _, i64, _ >( pattern.as_bytes(), ring.clone() ).unwrap();
+        assert_eq!( coo.triplets(), &vec![ (0, 1, 1), (1, 1, 1) ] );
+
+        let skew        =   "%%MatrixMarket matrix coordinate real skew-symmetric\n2 2 1\n2 1 1.5\n";
+        let matrix      =   read_matrix_market( skew.as_bytes(), NativeRing::<f64>::new() ).unwrap()
+                                .into_vec_of_vec( MajorDimension::Row, NativeRing::<f64>::new() );
+        assert_eq!( matrix.view_major_ascend( 0 ).collect::< Vec< _ > >(), vec![ (1, -1.5) ] );
+        assert_eq!( matrix.view_major_ascend( 1 ).collect::< Vec< _ > >(), vec![ (0, 1.5) ] );
+    }
+
+    #[test]
+    fn test_malformed_input_is_an_error() {
+        let ring        =   NativeRing::<i64>::new();
+        let read        =   |text: &str| read_matrix_market::< _, i64, _ >( text.as_bytes(), ring.clone() ).unwrap_err().to_string();
+        assert!( read( "" ).contains( "missing header" ) );
+        assert!( read( "%%MatrixMarket matrix array real general\n1 1\n1\n" ).contains( "unsupported format" ) );
+        assert!( read( "%%MatrixMarket matrix coordinate complex general\n" ).contains( "unsupported field" ) );
+        assert!( read( "%%MatrixMarket matrix coordinate integer general\n2 2 1\n3 1 1\n" ).starts_with( "line 3" ) );
+        assert!( read( "%%MatrixMarket matrix coordinate integer general\n2 2 1\n1 1 x\n" ).contains( "coefficient" ) );
+        assert!( read( "%%MatrixMarket matrix coordinate integer general\n2 2 2\n1 1 1\n" ).contains( "found 1" ) );
+        assert!( read( "%%MatrixMarket matrix coordinate integer general\n2 2 0\n1 1 1\n" ).contains( "expected 0 entries" ) );
+        assert!( read( "%%MatrixMarket matrix coordinate integer general\n1 1 4000000000000000000\n1 1 1\n" ).contains( "found 1" ) );
+        assert!( read( "%%MatrixMarket matrix coordinate integer general\n4000000000 4000000000 4000000000\n1 1 1\n" ).contains( "found 1" ) );
+    }
+}
@@ -0,0 +1,6 @@
+//! Reading and writing matrices, in file formats shared with other libraries and in a compact
+//! binary format for checkpoints.
+
+pub mod matrix_market;
+#[cfg(feature = "binary")]
+pub mod binary;
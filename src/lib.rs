@@ -176,7 +176,6 @@
 //!     // Example: convert [(1,1.), (1,0.5), (2,0.), (1,0.)] into [(1,1.5), (2,0.), (1,0.)]
 //!     let gathered : Vec<_> = iter_c
 //!                             .clone() // this makes a copy of the iterator, so the original stays unchanged
-//!                             .peekable() // this puts the iterator in a slightly different form, which is compatible with gather
 //!                             .gather( ring.clone() )
 //!                             .collect(); // this collects the entries of the iterator into a standard Rust vector
 //!     assert_eq!( gathered, vec![ (1, 1.), (2, 2.), (3, 6.), (4, 0.) ]);   
@@ -217,15 +216,29 @@
 //!     For advanced usage (eg matrix reduction), we also provide a
 //!     customized merge process in the [hit_merge](utilities::iterators::hit_merge) module.
 //! 
-//! * **Add**
+//! * **Add and subtract**
 //! 
-//!     We can add the vectors represented by `iter_a` and `iter_b` by 
-//!     first combining (e.g., with the `merge` function discussed above), 
-//!     then applying the `gather` method.
+//!     We can add or subtract the vectors represented by two sorted iterators with the `add`
+//!     and `subtract` methods, which merge the iterators, gather terms, and drop zeros in a 
+//!     single pass.  The last argument is an [order comparator](utilities::order), which 
+//!     describes the order in which the entries are sorted.
 //! 
-//! * **Subtract**
+//!     ```
+//!     use solar::vectors::vector_transforms::*;
+//!     use solar::rings::ring_native::*;
+//!     use solar::utilities::order::OrderNatural;
+//! 
+//!     # let entries_a   =   vec![ (1, 1.), (4, 4.) ];
+//!     # let entries_b   =   vec![ (2, 2.), (3, 3.) ];
+//!     # let iter_a      =   entries_a.iter().cloned(); 
+//!     # let iter_b      =   entries_b.iter().cloned();
+//!     # let ring = NativeDivisionRing::<f64>::new();        
+//!     let sum : Vec<_>    =   iter_a.clone().add( iter_b.clone(), ring.clone(), OrderNatural ).collect();
+//!     assert_eq!( sum, vec![ (1, 1.), (2, 2.), (3, 3.), (4, 4.) ]);
 //! 
-//!     We can subtract  `iter_a` from `iter_b` by first scaling `iter_a` by `-1`, then adding.
+//!     let difference : Vec<_>     =   iter_a.clone().subtract( iter_a, ring.clone(), OrderNatural ).collect();
+//!     assert_eq!( difference, vec![] );
+//!     ```
 //!̦
 //! 
 //! # Where to learn more
@@ -242,5 +255,6 @@ pub mod matrices;
 pub mod matrix_factorization;
 pub mod utilities;
 pub mod vector_entries;
+pub mod io;
 //pub mod iterators::itertools_kmerge_impl;
 //pub mod itertools_kmerge_impl;
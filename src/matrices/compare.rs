@@ -0,0 +1,128 @@
+//! Compare matrix oracles.
+//!
+//! Hand-written oracles are easiest to test against a reference implementation, such as a
+//! [`VecOfVec`](crate::matrices::implementors::vec_of_vec::VecOfVec) built by hand.  The function
+//! [`oracles_equal`] compares the ascending major views of two oracles on a list of major keys,
+//! and reports the first entry where they differ.
+
+use crate::matrices::matrix_oracle::OracleMajorAscend;
+use crate::rings::ring::Semiring;
+use crate::vector_entries::vector_entries::KeyValGet;
+use crate::vectors::operations::{diff_sparse, SparseDiffEntry};
+use crate::vectors::vector_transforms::Transforms;
+
+
+/// An entry where two matrix oracles differ; see [`oracles_equal`].
+///
+/// A coefficient that is not explicitly stored is reported as zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleDiscrepancy< MajKey, MinKey, Val > {
+    /// The major key of the entry.
+    pub major_key:  MajKey,
+    /// The minor key of the entry, and its coefficient in each oracle.
+    pub entry:      SparseDiffEntry< MinKey, Val >,
+}
+
+/// Check that two matrix oracles have the same ascending major views, for each key in
+/// `major_keys`.
+///
+/// Each view is simplified before comparison: consecutive entries with equal minor keys are
+/// summed, and explicitly stored zeros are ignored.  So two oracles agree if they represent the
+/// same matrix, even if one stores zeros or repeated keys that the other does not.  The views of
+/// both oracles should be sorted in ascending order of minor key (repeated keys allowed).
+///
+/// Returns the first discrepancy found, in the order of `major_keys` and then of minor keys.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::compare::oracles_equal;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let ring        =   NativeRing::<i64>::new();
+/// let reference   =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 2) ], vec![ (1, 1) ] ] );
+/// let candidate   =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (0, 1), (1, 0) ], vec![ (1, 2) ] ] );
+///
+/// // the first rows agree after simplification; the second rows do not
+/// assert!( oracles_equal( &reference, &candidate, vec![ 0 ], ring.clone() ).is_ok() );
+///
+/// let discrepancy =   oracles_equal( &reference, &candidate, vec![ 0, 1 ], ring ).unwrap_err();
+/// assert_eq!( discrepancy.major_key, 1 );
+/// assert_eq!( ( discrepancy.entry.key, discrepancy.entry.val_a, discrepancy.entry.val_b ), ( 1, 1, 2 ) );
+/// ```
+pub fn oracles_equal< 'a, MatrixA, MatrixB, MajKeys, MajKey, MinKey, Val, RingOperator >(
+        a:              &'a MatrixA,
+        b:              &'a MatrixB,
+        major_keys:     MajKeys,
+        ring:           RingOperator,
+    )
+    ->
+    Result< (), OracleDiscrepancy< MajKey, MinKey, Val > >
+
+    where   MatrixA:            OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+            MatrixB:            OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+            MajKeys:            IntoIterator< Item = MajKey >,
+            MajKey:             Clone,
+            MinKey:             Clone + PartialOrd,
+            Val:                Clone + PartialEq,
+            RingOperator:       Clone + Semiring< Val >,
+{
+    for major_key in major_keys {
+        let view_a      =   simplify( a.view_major_ascend( major_key.clone() ), ring.clone() );
+        let view_b      =   simplify( b.view_major_ascend( major_key.clone() ), ring.clone() );
+        if let Some( entry ) = diff_sparse( view_a, view_b, ring.clone() ).into_iter().next() {
+            return Err( OracleDiscrepancy{ major_key, entry } )
+        }
+    }
+    Ok(())
+}
+
+/// Sum consecutive entries with equal keys.
+fn simplify< View, MinKey, Val, RingOperator >( view: View, ring: RingOperator )
+        -> impl Iterator< Item = ( MinKey, Val ) >
+
+    where   View:               IntoIterator,
+            View::Item:         KeyValGet< Key = MinKey, Val = Val >,
+            MinKey:             Clone + PartialEq,
+            Val:                Clone,
+            RingOperator:       Semiring< Val >,
+{
+    view.into_iter().map( |entry| ( entry.key(), entry.val() ) ).gather( ring )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::hash_of_hash::HashOfHash;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::matrices::matrix_oracle::MajorDimension;
+    use crate::rings::field_prime::GF2;
+
+    #[test]
+    fn test_oracles_equal_across_implementors() {
+        let ring            =   GF2::new();
+        let reference       =   VecOfVec::new(  MajorDimension::Row,
+                                                vec![ vec![ (0, true), (2, true) ], vec![], vec![ (1, true) ] ] );
+        let mut candidate   =   HashOfHash::new( MajorDimension::Row );
+        candidate.extend( [ (0, 0, true), (0, 2, true), (1, 1, false), (2, 1, true) ] );
+        assert_eq!( oracles_equal( &reference, &candidate, 0 .. 3, ring.clone() ), Ok(()) );
+
+        // entries that cancel over GF(2) are simplified away
+        let cancelling      =   VecOfVec::new(  MajorDimension::Row,
+                                                vec![ vec![ (0, true), (1, true), (1, true), (2, true) ] ] );
+        assert_eq!( oracles_equal( &reference, &cancelling, vec![ 0 ], ring.clone() ), Ok(()) );
+
+        candidate.set_entry( 2, 0, true );
+        candidate.set_entry( 1, 2, true );
+        assert_eq!(     oracles_equal( &reference, &candidate, 0 .. 3, ring ),
+                        Err( OracleDiscrepancy{
+                            major_key:  1,
+                            entry:      SparseDiffEntry{ key: 2, val_a: false, val_b: true },
+                        } )     );
+    }
+}
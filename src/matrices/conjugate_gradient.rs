@@ -0,0 +1,174 @@
+//! The (preconditioned) conjugate gradient method.
+//!
+//! The function [`conjugate_gradient`] solves `A x = b` for a symmetric positive definite matrix
+//! `A` with floating point coefficients, given as a [`LinearOperator`].  It only ever multiplies
+//! by `A`, so `A` can be a matrix oracle paired with a ring operator, a closure, or any other
+//! linear operator.
+//!
+//! The method converges slowly when `A` is ill-conditioned, as combinatorial Laplacians of large
+//! complexes often are.  A *preconditioner* `M^{-1}` (also a linear operator) speeds up
+//! convergence; it should be symmetric positive definite, and cheap to apply.  The
+//! [preconditioners](crate::matrices::preconditioners) module provides Jacobi, block Jacobi and
+//! symmetric Gauss-Seidel preconditioners; pass [`identity`] to run the method without one.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::conjugate_gradient::{conjugate_gradient, identity};
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::MajorDimension;
+//! use solar::matrices::preconditioners::JacobiPreconditioner;
+//! use solar::rings::ring_native::NativeDivisionRing;
+//!
+//! let ring        =   NativeDivisionRing::<f64>::new();
+//! let columns     =   vec![ vec![ (0, 4.), (1, 1.) ], vec![ (0, 1.), (1, 3.) ] ];
+//! let matrix      =   VecOfVec::new( MajorDimension::Col, columns.clone() );
+//! let rhs         =   vec![ (0, 1.), (1, 2.) ];
+//!
+//! // without a preconditioner ...
+//! let plain       =   conjugate_gradient( &( &matrix, ring.clone() ), &identity, &rhs, 1e-12, 10 );
+//! assert!( plain.converged );
+//!
+//! // ... and with one
+//! let jacobi      =   JacobiPreconditioner::new( &columns, ring.clone() );
+//! let solved      =   conjugate_gradient( &( &matrix, ring ), &jacobi, &rhs, 1e-12, 10 );
+//! assert!( solved.converged );
+//! assert!( ( solved.solution[0].1 - 1. / 11. ).abs() < 1e-12 );
+//! assert!( ( solved.solution[1].1 - 7. / 11. ).abs() < 1e-12 );
+//! ```
+
+use crate::matrices::linear_operator::LinearOperator;
+use crate::rings::ring_native::NativeDivisionRing;
+use crate::utilities::order::OrderNatural;
+use crate::vectors::operations::{add_assign_sorted_with_buffer, dot};
+use num::Float;
+
+
+/// The identity operator, for running [`conjugate_gradient`] without a preconditioner.
+pub fn identity< Val: Clone >( vector: &[ (usize, Val) ] ) -> Vec< (usize, Val) > { vector.to_vec() }
+
+/// The result of [`conjugate_gradient`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConjugateGradientResult< Val > {
+    /// The approximate solution `x`, sorted in ascending order of key.
+    pub solution:       Vec< (usize, Val) >,
+    /// The number of iterations performed.
+    pub iterations:     usize,
+    /// The Euclidean norm of the final residual `b - A x`.
+    pub residual_norm:  Val,
+    /// Returns `true` if `residual_norm <= tolerance * |b|`.
+    pub converged:      bool,
+}
+
+/// Approximately solve `A x = b` by the preconditioned conjugate gradient method.
+///
+/// Here `operator` sends `v` to `A v`, and `preconditioner` sends `r` to `M^{-1} r`; both should
+/// be symmetric positive definite, and follow the conventions of [`LinearOperator`].  Iteration
+/// stops once the norm of the residual `b - A x` is at most `tolerance` times the norm of `b`,
+/// or after `max_iterations` iterations, or if the search direction `p` satisfies `p^T A p <= 0`
+/// (which means that `A` is not positive definite).  Check
+/// [`converged`](ConjugateGradientResult::converged) to see which.
+///
+/// See the [module documentation](self) for an example.
+pub fn  conjugate_gradient< Val, Operator, Preconditioner >(
+            operator:           &Operator,
+            preconditioner:     &Preconditioner,
+            rhs:                &[ (usize, Val) ],
+            tolerance:          Val,
+            max_iterations:     usize,
+        )
+        ->
+        ConjugateGradientResult< Val >
+
+        where   Operator:           LinearOperator< usize, usize, Val >,
+                Preconditioner:     LinearOperator< usize, usize, Val > + ?Sized,
+                Val:                Float,
+{
+    let ring                =   NativeDivisionRing::< Val >::new();
+    let inner               =   | a: &[ (usize, Val) ], b: &[ (usize, Val) ] | dot( a.iter().cloned(), b.iter().cloned(), ring.clone(), OrderNatural );
+    let mut buffer          =   Vec::new();
+
+    let threshold           =   tolerance * inner( rhs, rhs ).sqrt();
+    let mut solution        =   Vec::new();
+    let mut residual        =   rhs.to_vec();
+    let mut preconditioned  =   preconditioner.apply( &residual );
+    let mut direction       =   preconditioned.clone();
+    let mut inner_rz        =   inner( &residual, &preconditioned );
+    let mut iterations      =   0;
+
+    while iterations < max_iterations && inner( &residual, &residual ).sqrt() > threshold {
+        let image           =   operator.apply( &direction );
+        let curvature       =   inner( &direction, &image );
+        if curvature <= Val::zero() { break }                   // breakdown: A is not positive definite
+        let step            =   inner_rz / curvature;
+
+        add_assign_sorted_with_buffer( &mut solution, &direction, step, ring.clone(), &mut buffer );
+        add_assign_sorted_with_buffer( &mut residual, &image, - step, ring.clone(), &mut buffer );
+        preconditioned      =   preconditioner.apply( &residual );
+
+        // the new direction is z + beta p
+        let inner_rz_next   =   inner( &residual, &preconditioned );
+        let beta            =   inner_rz_next / inner_rz;
+        inner_rz            =   inner_rz_next;
+        std::mem::swap( &mut direction, &mut preconditioned );
+        add_assign_sorted_with_buffer( &mut direction, &preconditioned, beta, ring.clone(), &mut buffer );
+        iterations          +=  1;
+    }
+
+    let residual_norm       =   inner( &residual, &residual ).sqrt();
+    ConjugateGradientResult{ solution, iterations, residual_norm, converged: residual_norm <= threshold }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::matrices::matrix_oracle::MajorDimension;
+    use crate::matrices::preconditioners::{JacobiPreconditioner, GaussSeidelPreconditioner};
+
+    #[test]
+    fn test_preconditioning_speeds_up_an_ill_conditioned_system() {
+        // a tridiagonal matrix whose diagonal spans several orders of magnitude
+        let size: usize     =   40;
+        let columns: Vec< Vec< (usize, f64) > >
+                            =   ( 0 .. size )
+                                    .map(   |j|
+                                            ( j.saturating_sub( 1 ) ..= ( j + 1 ).min( size - 1 ) )
+                                                .map( |i| ( i, if i == j { 2. + 10f64.powi( ( j % 5 ) as i32 ) } else { -1. } ) )
+                                                .collect()
+                                        )
+                                    .collect();
+        let ring            =   NativeDivisionRing::< f64 >::new();
+        let matrix          =   VecOfVec::new( MajorDimension::Col, columns.clone() );
+        let operator        =   ( &matrix, ring.clone() );
+        let rhs: Vec< _ >   =   ( 0 .. size ).map( |i| ( i, 1. ) ).collect();
+
+        let plain           =   conjugate_gradient( &operator, &identity, &rhs, 1e-10, 1000 );
+        let jacobi          =   conjugate_gradient( &operator, &JacobiPreconditioner::new( &columns, ring.clone() ), &rhs, 1e-10, 1000 );
+        let gauss_seidel    =   conjugate_gradient( &operator, &GaussSeidelPreconditioner::symmetric( &columns, ring.clone() ), &rhs, 1e-10, 1000 );
+
+        for result in [ &plain, &jacobi, &gauss_seidel ].iter() {
+            assert!( result.converged );
+            // check the residual directly
+            let mut residual    =   rhs.clone();
+            add_assign_sorted_with_buffer( &mut residual, &operator.apply( &result.solution ), -1., ring.clone(), &mut Vec::new() );
+            assert!( dot( residual.iter().cloned(), residual.iter().cloned(), ring.clone(), OrderNatural ).sqrt() <= 1e-10 * ( size as f64 ).sqrt() * 1.01 );
+        }
+        assert!( jacobi.iterations < plain.iterations );
+        assert!( gauss_seidel.iterations < plain.iterations );
+    }
+
+    #[test]
+    fn test_breakdown_on_indefinite_matrices() {
+        let matrix          =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (0, 1.) ], vec![ (1, -1.) ] ] );
+        let result          =   conjugate_gradient( &( &matrix, NativeDivisionRing::< f64 >::new() ), &identity, &[ (0, 1.), (1, 1.) ], 1e-12, 10 );
+        assert!( ! result.converged );
+
+        // a zero right-hand side is solved by zero, without iterating
+        let result          =   conjugate_gradient( &( &matrix, NativeDivisionRing::< f64 >::new() ), &identity, &[], 1e-12, 10 );
+        assert!( result.converged && result.solution.is_empty() && result.iterations == 0 );
+    }
+}
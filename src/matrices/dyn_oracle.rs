@@ -0,0 +1,205 @@
+//! Object-safe variants of the major oracle traits.
+//!
+//! The traits in [`matrix_oracle`](crate::matrices::matrix_oracle) have associated view types, so
+//! they cannot be used as trait objects: a `Vec< Box< dyn OracleMajor< .. > > >` would have to
+//! name the type of view returned by every matrix in the vector.  The traits in this module
+//! return boxed iterators instead, with entries converted to `(MinKey, Val)` tuples, so matrices
+//! of different types can be stored side by side; for example, the boundary matrices of a chain
+//! complex, each stored in whatever format suits its dimension.
+//!
+//! Each trait has a blanket implementation for every matrix that implements the corresponding
+//! generic trait *for every lifetime*; this holds for types such as
+//! [`VecOfVecSimple`](crate::matrices::implementors::vec_of_vec_simple::VecOfVecSimple) and
+//! [`VecCsv`](crate::matrices::implementors::vec_of_csvec::VecCsv).  It does not hold for a
+//! [`VecOfVec`](crate::matrices::implementors::vec_of_vec::VecOfVec), whose views are tied to its
+//! lifetime parameter; convert it to a `VecOfVecSimple` first.  Conversely, the trait objects
+//! implement the generic traits, so a boxed matrix can be passed to generic code as `&*matrix`.
+//!
+//! Every entry is produced through a virtual call, so these traits are slower than the generic
+//! ones; use them to store matrices, not in inner loops.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::dyn_oracle::DynOracleMajorAscend;
+//! use solar::matrices::implementors::vec_of_csvec::VecCsv;
+//! use solar::matrices::implementors::vec_of_vec_simple::VecOfVecSimple;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend};
+//!
+//! // the boundary matrices of a filled triangle, stored in two different formats
+//! let edges       =   VecOfVecSimple::new( MajorDimension::Col, vec![ vec![ (0, -1), (1, 1) ], vec![ (0, -1), (2, 1) ], vec![ (1, -1), (2, 1) ] ] );
+//! let triangle    =   VecCsv::from_triplets( MajorDimension::Col, 1, vec![ (0, 0, 1), (0, 1, -1), (0, 2, 1) ] );
+//!
+//! let boundaries: Vec< Box< dyn DynOracleMajorAscend< usize, usize, i64 > > >  =   vec![ Box::new( edges ), Box::new( triangle ) ];
+//!
+//! assert_eq!( boundaries[1].view_major_ascend_dyn( 0 ).collect::<Vec<_>>(), vec![ (0, 1), (1, -1), (2, 1) ] );
+//! assert_eq!( ( &*boundaries[0] ).view_major_ascend( 2 ).collect::<Vec<_>>(), vec![ (1, -1), (2, 1) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend};
+use crate::vector_entries::vector_entries::KeyValGet;
+
+
+/// A boxed major view, with entries of type `(MinKey, Val)`.
+pub type DynView< 'a, MinKey, Val > = Box< dyn Iterator< Item = ( MinKey, Val ) > + 'a >;
+
+
+//  ---------------------------------------------------------------------------
+//  TRAITS
+//  ---------------------------------------------------------------------------
+
+
+/// An object-safe [`OracleMajor`]: entries may not appear in sorted order.
+pub trait DynOracleMajor< MajKey, MinKey, Val >
+{
+    /// Get a major vector, as a boxed iterator.
+    fn view_major_dyn< 'b >( &'b self, index: MajKey ) -> DynView< 'b, MinKey, Val >;
+}
+
+/// An object-safe [`OracleMajorAscend`]: entries appear in ascending order, according to index.
+pub trait DynOracleMajorAscend< MajKey, MinKey, Val >
+{
+    /// Get a major vector with entries sorted in ascending order of index, as a boxed iterator.
+    fn view_major_ascend_dyn< 'b >( &'b self, index: MajKey ) -> DynView< 'b, MinKey, Val >;
+}
+
+/// An object-safe [`OracleMajorDescend`]: entries appear in descending order, according to index.
+pub trait DynOracleMajorDescend< MajKey, MinKey, Val >
+{
+    /// Get a major vector with entries sorted in descending order of index, as a boxed iterator.
+    fn view_major_descend_dyn< 'b >( &'b self, index: MajKey ) -> DynView< 'b, MinKey, Val >;
+}
+
+
+//  ---------------------------------------------------------------------------
+//  BLANKET IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+impl     < Matrix, MajKey, MinKey, Val >
+        DynOracleMajor < MajKey, MinKey, Val >
+        for
+        Matrix
+
+        where   Matrix:                                                                 for < 'b > OracleMajor< 'b, MajKey, MinKey, Val >,
+                for < 'b > < < Matrix as OracleMajor< 'b, MajKey, MinKey, Val > >::ViewMajor as IntoIterator >::IntoIter: 'b,
+{
+    fn view_major_dyn< 'b >( &'b self, index: MajKey ) -> DynView< 'b, MinKey, Val > {
+        let view    =   < Matrix as OracleMajor< 'b, MajKey, MinKey, Val > >::view_major( self, index );
+        Box::new( view.into_iter().map( |entry| ( entry.key(), entry.val() ) ) )
+    }
+}
+
+impl     < Matrix, MajKey, MinKey, Val >
+        DynOracleMajorAscend < MajKey, MinKey, Val >
+        for
+        Matrix
+
+        where   Matrix:                                                                 for < 'b > OracleMajorAscend< 'b, MajKey, MinKey, Val >,
+                for < 'b > < < Matrix as OracleMajorAscend< 'b, MajKey, MinKey, Val > >::ViewMajorAscend as IntoIterator >::IntoIter: 'b,
+{
+    fn view_major_ascend_dyn< 'b >( &'b self, index: MajKey ) -> DynView< 'b, MinKey, Val > {
+        let view    =   < Matrix as OracleMajorAscend< 'b, MajKey, MinKey, Val > >::view_major_ascend( self, index );
+        Box::new( view.into_iter().map( |entry| ( entry.key(), entry.val() ) ) )
+    }
+}
+
+impl     < Matrix, MajKey, MinKey, Val >
+        DynOracleMajorDescend < MajKey, MinKey, Val >
+        for
+        Matrix
+
+        where   Matrix:                                                                 for < 'b > OracleMajorDescend< 'b, MajKey, MinKey, Val >,
+                for < 'b > < < Matrix as OracleMajorDescend< 'b, MajKey, MinKey, Val > >::ViewMajorDescend as IntoIterator >::IntoIter: 'b,
+{
+    fn view_major_descend_dyn< 'b >( &'b self, index: MajKey ) -> DynView< 'b, MinKey, Val > {
+        let view    =   < Matrix as OracleMajorDescend< 'b, MajKey, MinKey, Val > >::view_major_descend( self, index );
+        Box::new( view.into_iter().map( |entry| ( entry.key(), entry.val() ) ) )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  GENERIC TRAITS FOR TRAIT OBJECTS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, 'c, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        dyn DynOracleMajor < MajKey, MinKey, Val > + 'c
+
+        where   MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   DynView< 'a, MinKey, Val >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { self.view_major_dyn( index ) }
+}
+
+impl     < 'a, 'c, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        dyn DynOracleMajorAscend < MajKey, MinKey, Val > + 'c
+
+        where   MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   DynView< 'a, MinKey, Val >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend { self.view_major_ascend_dyn( index ) }
+}
+
+impl     < 'a, 'c, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        dyn DynOracleMajorDescend < MajKey, MinKey, Val > + 'c
+
+        where   MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   DynView< 'a, MinKey, Val >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend { self.view_major_descend_dyn( index ) }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::compressed_sparse::CompressedSparse;
+    use crate::matrices::implementors::vec_of_csvec::VecCsv;
+    use crate::matrices::implementors::vec_of_vec_simple::VecOfVecSimple;
+    use crate::matrices::matrix_oracle::MajorDimension;
+
+    #[test]
+    fn test_boxed_matrices_agree_with_generic_views() {
+        let views       =   vec![ vec![ (1, 2.), (4, -1.) ], vec![], vec![ (0, 3.) ] ];
+        let simple      =   VecOfVecSimple::new( MajorDimension::Row, views.clone() );
+        let csv         =   VecCsv::from_triplets( MajorDimension::Row, 3, vec![ (0, 1, 2.), (0, 4, -1.), (2, 0, 3.) ] );
+        let mut hashed  =   CompressedSparse::new( MajorDimension::Row );
+        for ( key, view ) in views.iter().cloned().enumerate() { hashed.push_view( key, view ); }
+
+        let ascend: Vec< Box< dyn DynOracleMajorAscend< usize, usize, f64 > > >     =   vec![ Box::new( simple.clone() ), Box::new( csv.clone() ), Box::new( hashed.clone() ) ];
+        let descend: Vec< Box< dyn DynOracleMajorDescend< usize, usize, f64 > > >   =   vec![ Box::new( simple.clone() ), Box::new( csv ), Box::new( hashed ) ];
+        let unsorted: Box< dyn DynOracleMajor< usize, usize, f64 > >                =   Box::new( simple );
+        for ( major, view ) in views.iter().enumerate() {
+            for matrix in ascend.iter() {
+                assert_eq!( &matrix.view_major_ascend_dyn( major ).collect::< Vec< _ > >(), view );
+                assert!( ( **matrix ).view_major_ascend( major ).eq( view.iter().cloned() ) );
+            }
+            for matrix in descend.iter() {
+                assert!( matrix.view_major_descend_dyn( major ).eq( view.iter().rev().cloned() ) );
+                assert!( ( **matrix ).view_major_descend( major ).eq( view.iter().rev().cloned() ) );
+            }
+            assert!( unsorted.view_major_dyn( major ).eq( ( *unsorted ).view_major( major ) ) );
+        }
+    }
+}
@@ -0,0 +1,439 @@
+//! Adaptors that supply descending views from ascending ones, and vice versa.
+//!
+//! Some algorithms (e.g. the U-match decomposition) need to access the same matrix in both
+//! ascending and descending order.  If a user-defined oracle only implements one direction, it
+//! can be wrapped in one of the adaptors below:
+//!
+//! - [`AscendToDescend`] implements `OracleMajorDescend` and `OracleMinorDescend` by collecting
+//!   an ascending view into a buffer and reversing it, and
+//! - [`DescendToAscend`] does the opposite.
+//!
+//! In both cases the views the wrapped matrix already supplies are passed through unchanged.
+//! Buffering costs time and memory proportional to the length of the view, so a native
+//! implementation should be preferred when one is available.
+//!
+//! When the views of the wrapped matrix are double-ended iterators (as views of stored matrices
+//! usually are) no buffer is needed: [`ReverseViews`] supplies descending views by running
+//! ascending views backwards, and ascending views by running descending views backwards.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::ascend_descend::AscendToDescend;
+//! use solar::matrices::matrix_oracle::{OracleMajorAscend, OracleMajorDescend};
+//!
+//! // a user-defined oracle that only supplies ascending views
+//! struct Diagonal;
+//! impl < 'a > OracleMajorAscend< 'a, usize, usize, f64 > for Diagonal {
+//!     type PairMajorAscend = (usize, f64);
+//!     type ViewMajorAscend = Vec< (usize, f64) >;
+//!     fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend {
+//!         vec![ (index, 1.), (index + 1, 2.) ]
+//!     }
+//! }
+//!
+//! let matrix  =   AscendToDescend::new( Diagonal );
+//! assert_eq!( matrix.view_major_descend( 3 ).collect::<Vec<_>>(), vec![ (4, 2.), (3, 1.) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use std::iter::Rev;
+use std::vec::IntoIter;
+
+
+//  ---------------------------------------------------------------------------
+//  ASCEND TO DESCEND
+//  ---------------------------------------------------------------------------
+
+
+/// Wraps a matrix that supplies ascending views, and supplies descending views by reversing them.
+#[derive(Clone, Debug)]
+pub struct AscendToDescend< Matrix > { matrix: Matrix }
+
+impl < Matrix > AscendToDescend< Matrix > {
+    /// Wrap `matrix`.
+    pub fn new( matrix: Matrix ) -> Self { AscendToDescend{ matrix } }
+
+    /// A reference to the wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// Return the wrapped matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+}
+
+impl     < Matrix: WhichMajor >
+        WhichMajor
+        for
+        AscendToDescend < Matrix >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajor < 'a, MajKey, MinKey, SnzVal >
+        for
+        AscendToDescend < Matrix >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   Matrix::ViewMajor;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { self.matrix.view_major( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        AscendToDescend < Matrix >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajorAscend =   Matrix::PairMajorAscend;
+    type ViewMajorAscend =   Matrix::ViewMajorAscend;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend { self.matrix.view_major_ascend( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        AscendToDescend < Matrix >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajorDescend =   Matrix::PairMajorAscend;
+    type ViewMajorDescend =   Rev< IntoIter< Matrix::PairMajorAscend > >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        self.matrix.view_major_ascend( index ).into_iter().collect::< Vec< _ > >().into_iter().rev()
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinor < 'a, MajKey, MinKey, SnzVal >
+        for
+        AscendToDescend < Matrix >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   Matrix::ViewMinor;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor { self.matrix.view_minor( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        AscendToDescend < Matrix >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMinorAscend =   Matrix::PairMinorAscend;
+    type ViewMinorAscend =   Matrix::ViewMinorAscend;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend { self.matrix.view_minor_ascend( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        AscendToDescend < Matrix >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMinorDescend =   Matrix::PairMinorAscend;
+    type ViewMinorDescend =   Rev< IntoIter< Matrix::PairMinorAscend > >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        self.matrix.view_minor_ascend( index ).into_iter().collect::< Vec< _ > >().into_iter().rev()
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  DESCEND TO ASCEND
+//  ---------------------------------------------------------------------------
+
+
+/// Wraps a matrix that supplies descending views, and supplies ascending views by reversing them.
+#[derive(Clone, Debug)]
+pub struct DescendToAscend< Matrix > { matrix: Matrix }
+
+impl < Matrix > DescendToAscend< Matrix > {
+    /// Wrap `matrix`.
+    pub fn new( matrix: Matrix ) -> Self { DescendToAscend{ matrix } }
+
+    /// A reference to the wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// Return the wrapped matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+}
+
+impl     < Matrix: WhichMajor >
+        WhichMajor
+        for
+        DescendToAscend < Matrix >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajor < 'a, MajKey, MinKey, SnzVal >
+        for
+        DescendToAscend < Matrix >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   Matrix::ViewMajor;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { self.matrix.view_major( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        DescendToAscend < Matrix >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajorAscend =   Matrix::PairMajorDescend;
+    type ViewMajorAscend =   Rev< IntoIter< Matrix::PairMajorDescend > >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        self.matrix.view_major_descend( index ).into_iter().collect::< Vec< _ > >().into_iter().rev()
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        DescendToAscend < Matrix >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajorDescend =   Matrix::PairMajorDescend;
+    type ViewMajorDescend =   Matrix::ViewMajorDescend;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend { self.matrix.view_major_descend( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinor < 'a, MajKey, MinKey, SnzVal >
+        for
+        DescendToAscend < Matrix >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   Matrix::ViewMinor;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor { self.matrix.view_minor( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        DescendToAscend < Matrix >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMinorAscend =   Matrix::PairMinorDescend;
+    type ViewMinorAscend =   Rev< IntoIter< Matrix::PairMinorDescend > >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        self.matrix.view_minor_descend( index ).into_iter().collect::< Vec< _ > >().into_iter().rev()
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        DescendToAscend < Matrix >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMinorDescend =   Matrix::PairMinorDescend;
+    type ViewMinorDescend =   Matrix::ViewMinorDescend;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend { self.matrix.view_minor_descend( index ) }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  REVERSE VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// Wraps a matrix, and exchanges the order of its views without buffering them.
+///
+/// The ascending views of the wrapper are the descending views of the wrapped matrix, read back to
+/// front, and vice versa; this requires the views to be double-ended iterators.  Unordered views
+/// are passed through unchanged.  So wrapping a matrix that only supplies ascending views yields a
+/// matrix that only supplies descending ones; to keep both directions, use [`AscendToDescend`] or
+/// [`DescendToAscend`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::ascend_descend::ReverseViews;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMajorDescend};
+///
+/// let matrix      =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1.), (2, 3.) ] ] );
+/// let reversed    =   ReverseViews::new( &matrix );
+///
+/// assert!( reversed.view_major_descend( 0 ).eq( matrix.view_major_descend( 0 ) ) );
+/// assert!( reversed.view_major_ascend( 0 ).eq( matrix.view_major_ascend( 0 ) ) );
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReverseViews< Matrix > { matrix: Matrix }
+
+impl < Matrix > ReverseViews< Matrix > {
+    /// Wrap `matrix`.
+    pub fn new( matrix: Matrix ) -> Self { ReverseViews{ matrix } }
+
+    /// A reference to the wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// Return the wrapped matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+}
+
+impl     < Matrix: WhichMajor >
+        WhichMajor
+        for
+        ReverseViews < Matrix >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajor < 'a, MajKey, MinKey, SnzVal >
+        for
+        ReverseViews < Matrix >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   Matrix::ViewMajor;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { self.matrix.view_major( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ReverseViews < Matrix >
+
+        where   Matrix:                                                         OracleMajorDescend< 'a, MajKey, MinKey, SnzVal >,
+                < Matrix::ViewMajorDescend as IntoIterator >::IntoIter:         DoubleEndedIterator,
+{
+    type PairMajorAscend =   Matrix::PairMajorDescend;
+    type ViewMajorAscend =   Rev< < Matrix::ViewMajorDescend as IntoIterator >::IntoIter >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        self.matrix.view_major_descend( index ).into_iter().rev()
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ReverseViews < Matrix >
+
+        where   Matrix:                                                         OracleMajorAscend< 'a, MajKey, MinKey, SnzVal >,
+                < Matrix::ViewMajorAscend as IntoIterator >::IntoIter:          DoubleEndedIterator,
+{
+    type PairMajorDescend =   Matrix::PairMajorAscend;
+    type ViewMajorDescend =   Rev< < Matrix::ViewMajorAscend as IntoIterator >::IntoIter >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        self.matrix.view_major_ascend( index ).into_iter().rev()
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinor < 'a, MajKey, MinKey, SnzVal >
+        for
+        ReverseViews < Matrix >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   Matrix::ViewMinor;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor { self.matrix.view_minor( index ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ReverseViews < Matrix >
+
+        where   Matrix:                                                         OracleMinorDescend< 'a, MajKey, MinKey, SnzVal >,
+                < Matrix::ViewMinorDescend as IntoIterator >::IntoIter:         DoubleEndedIterator,
+{
+    type PairMinorAscend =   Matrix::PairMinorDescend;
+    type ViewMinorAscend =   Rev< < Matrix::ViewMinorDescend as IntoIterator >::IntoIter >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        self.matrix.view_minor_descend( index ).into_iter().rev()
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ReverseViews < Matrix >
+
+        where   Matrix:                                                         OracleMinorAscend< 'a, MajKey, MinKey, SnzVal >,
+                < Matrix::ViewMinorAscend as IntoIterator >::IntoIter:          DoubleEndedIterator,
+{
+    type PairMinorDescend =   Matrix::PairMinorAscend;
+    type ViewMinorDescend =   Rev< < Matrix::ViewMinorAscend as IntoIterator >::IntoIter >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        self.matrix.view_minor_ascend( index ).into_iter().rev()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::csc::Csc;
+
+    #[test]
+    fn test_round_trip_agrees_with_native_views() {
+        let matrix      =   Csc::from_triplets( 3, 3, vec![ (0, 0, 1), (2, 0, 2), (1, 1, 3), (0, 2, 4), (2, 2, 5) ] );
+        let descend     =   AscendToDescend::new( &matrix );
+        let ascend      =   DescendToAscend::new( &matrix );
+
+        for i in 0 .. 3 {
+            assert!( descend.view_major_descend( i ).eq( matrix.view_major_descend( i ) ) );
+            assert!( descend.view_minor_descend( i ).eq( matrix.view_minor_descend( i ) ) );
+            assert!( ascend.view_major_ascend( i ).eq( matrix.view_major_ascend( i ) ) );
+            assert!( ascend.view_minor_ascend( i ).eq( matrix.view_minor_ascend( i ) ) );
+        }
+    }
+
+    #[test]
+    fn test_reverse_views_agree_with_native_views() {
+        let matrix      =   Csc::from_triplets( 3, 3, vec![ (0, 0, 1), (2, 0, 2), (1, 1, 3), (0, 2, 4), (2, 2, 5) ] );
+        let reversed    =   ReverseViews::new( &matrix );
+
+        for i in 0 .. 3 {
+            assert!( reversed.view_major_ascend( i ).eq( matrix.view_major_ascend( i ) ) );
+            assert!( reversed.view_major_descend( i ).eq( matrix.view_major_descend( i ) ) );
+            assert!( reversed.view_minor_ascend( i ).eq( matrix.view_minor_ascend( i ) ) );
+            assert!( reversed.view_minor_descend( i ).eq( matrix.view_minor_descend( i ) ) );
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Bit-packed matrices with coefficients in the two element field.
+//!
+//! Boundary matrices with coefficients in GF(2) dominate computations in topology.  Stored as
+//! `(usize, bool)` pairs, each entry costs 16 bytes; a [`BitMatrix`] stores each major view as a
+//! bitset instead, packed into `u64` words, so each *position* costs one bit.  This is much
+//! smaller whenever views are not extremely sparse (at least one entry per 128 positions), and
+//! adding one view to another is a word-by-word XOR.
+//!
+//! Coefficients are `bool`, as for the ring [`GF2`](crate::rings::field_prime::GF2); views list
+//! the positions of the set bits, each with coefficient `true`.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::bit_matrix::BitMatrix;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMajorDescend};
+//!
+//! // the boundary of a triangle, column-major: columns are edges, rows are vertices
+//! let mut boundary    =   BitMatrix::from_views( MajorDimension::Col, 3, vec![ vec![ 0, 1 ], vec![ 0, 2 ], vec![ 1, 2 ] ] );
+//! assert_eq!( boundary.view_major_ascend( 1 ).collect::<Vec<_>>(), vec![ (0, true), (2, true) ] );
+//!
+//! // column 2 + column 1 + column 0 = 0
+//! boundary.add_view_in_place( 2, 1 );
+//! assert_eq!( boundary.view_major_descend( 2 ).collect::<Vec<_>>(), vec![ (1, true), (0, true) ] );
+//! boundary.add_view_in_place( 2, 0 );
+//! assert_eq!( boundary.view_major_ascend( 2 ).count(), 0 );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMajorAscendScoped,
+                                        OracleSet,
+                                        MajorKeys,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use std::ops::Range;
+
+
+/// The number of bits in a word.
+const WORD_BITS: usize = 64;
+
+/// The number of words needed to store `num_bits` bits.
+fn num_words( num_bits: usize ) -> usize { num_bits.div_ceil( WORD_BITS ) }
+
+
+/// A matrix with coefficients in GF(2), in which each major view is stored as a bitset.
+///
+/// Every major view has the same length, `num_minor`; minor keys are `0 .. num_minor`, and major
+/// keys are `0 .. num_major`.  Bits beyond position `num_minor` in the last word of a view are
+/// always zero.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    major_dimension:    MajorDimension,
+    num_minor:          usize,
+    views:              Vec< Vec< u64 > >,
+}
+
+impl BitMatrix {
+
+    /// A matrix of zeros, with `num_major` major views of length `num_minor`.
+    pub fn new( major_dimension: MajorDimension, num_major: usize, num_minor: usize ) -> Self {
+        BitMatrix{ major_dimension, num_minor, views: vec![ vec![ 0; num_words( num_minor ) ]; num_major ] }
+    }
+
+    /// The matrix whose `i`th major view has entries in the positions listed by `views[ i ]`.
+    ///
+    /// Positions may appear in any order; a position listed twice is set twice, not toggled.
+    /// Panics if a position is `>= num_minor`.
+    pub fn from_views( major_dimension: MajorDimension, num_minor: usize, views: Vec< Vec< usize > > ) -> Self {
+        let mut matrix  =   BitMatrix::new( major_dimension, views.len(), num_minor );
+        for ( major, view ) in views.into_iter().enumerate() {
+            for minor in view { matrix.set( major, minor, true ) }
+        }
+        matrix
+    }
+
+    /// The number of major views.
+    pub fn num_major( &self ) -> usize { self.views.len() }
+
+    /// The length of each major view.
+    pub fn num_minor( &self ) -> usize { self.num_minor }
+
+    /// The number of nonzero entries.
+    pub fn num_entries( &self ) -> usize {
+        self.views.iter().flatten().map( |word| word.count_ones() as usize ).sum()
+    }
+
+    /// The words that store major view `major`; bit `b` of word `w` is position `64 * w + b`.
+    pub fn words( &self, major: usize ) -> &[ u64 ] { &self.views[ major ] }
+
+    /// The coefficient in position `(major, minor)`.
+    ///
+    /// Panics if `major >= num_major` or `minor >= num_minor`.
+    pub fn get( &self, major: usize, minor: usize ) -> bool {
+        self.check_minor( minor );
+        self.views[ major ][ minor / WORD_BITS ] >> ( minor % WORD_BITS ) & 1 == 1
+    }
+
+    /// Set the coefficient in position `(major, minor)` to `val`.
+    ///
+    /// Panics if `major >= num_major` or `minor >= num_minor`.
+    pub fn set( &mut self, major: usize, minor: usize, val: bool ) {
+        self.check_minor( minor );
+        let mask    =   1u64 << ( minor % WORD_BITS );
+        let word    =   &mut self.views[ major ][ minor / WORD_BITS ];
+        if val { *word |= mask } else { *word &= ! mask }
+    }
+
+    /// Add one to the coefficient in position `(major, minor)`, i.e. toggle it.
+    ///
+    /// Panics if `major >= num_major` or `minor >= num_minor`.
+    pub fn flip( &mut self, major: usize, minor: usize ) {
+        self.check_minor( minor );
+        self.views[ major ][ minor / WORD_BITS ] ^= 1u64 << ( minor % WORD_BITS );
+    }
+
+    /// Replace major view `target` with `target + source`, one word at a time.
+    ///
+    /// If `target == source` the view becomes zero.  Panics if either key is `>= num_major`.
+    pub fn add_view_in_place( &mut self, target: usize, source: usize ) {
+        if target == source {
+            self.views[ target ].iter_mut().for_each( |word| *word = 0 );
+            return
+        }
+        let ( target_words, source_words )  =   if target < source {
+                                                    let ( head, tail )  =   self.views.split_at_mut( source );
+                                                    ( &mut head[ target ], &tail[ 0 ] )
+                                                } else {
+                                                    let ( head, tail )  =   self.views.split_at_mut( target );
+                                                    ( &mut tail[ 0 ], &head[ source ] )
+                                                };
+        for ( target_word, source_word ) in target_words.iter_mut().zip( source_words.iter() ) { *target_word ^= source_word }
+    }
+
+    /// Append a major view of zeros, and return its key.
+    pub fn push_zero_view( &mut self ) -> usize {
+        self.views.push( vec![ 0; num_words( self.num_minor ) ] );
+        self.views.len() - 1
+    }
+
+    /// The largest minor key of an entry in major view `major`, if any: the pivot of the view,
+    /// found by scanning words from the end.
+    pub fn last_entry( &self, major: usize ) -> Option< usize > { self.view( major ).next_back().map( |entry| entry.0 ) }
+
+    /// Panics if `minor` is not a valid minor key.
+    fn check_minor( &self, minor: usize ) {
+        assert!( minor < self.num_minor, "minor key {} is out of bounds for views of length {}", minor, self.num_minor );
+    }
+
+    /// The view with key `major`.
+    fn view( &self, major: usize ) -> BitView< '_ > { BitView{ words: &self.views[ major ], front: 0, back: self.num_minor } }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A major view of a [`BitMatrix`]: the positions of the set bits of a bitset, each with
+/// coefficient `true`.
+///
+/// Iterates in ascending order from the front and descending order from the back, skipping whole
+/// words of zeros at a time.
+#[derive(Clone, Debug)]
+pub struct BitView< 'a > {
+    words:  &'a [ u64 ],
+    front:  usize,  // the first position not yet visited from the front
+    back:   usize,  // one past the last position not yet visited from the back
+}
+
+impl < 'a > Iterator for BitView< 'a > {
+    type Item = ( usize, bool );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        while self.front < self.back {
+            let word    =   self.words[ self.front / WORD_BITS ] >> ( self.front % WORD_BITS );
+            if word == 0 {
+                self.front  =   ( self.front / WORD_BITS + 1 ) * WORD_BITS;
+                continue
+            }
+            let position    =   self.front + word.trailing_zeros() as usize;
+            if position >= self.back { break }
+            self.front      =   position + 1;
+            return Some( ( position, true ) )
+        }
+        self.front  =   self.back;
+        None
+    }
+}
+
+impl < 'a > DoubleEndedIterator for BitView< 'a > {
+    fn next_back( &mut self ) -> Option< Self::Item > {
+        while self.back > self.front {
+            let index       =   ( self.back - 1 ) / WORD_BITS;
+            let num_bits    =   self.back - index * WORD_BITS;     // between 1 and 64
+            let mask        =   if num_bits == WORD_BITS { ! 0 } else { ( 1u64 << num_bits ) - 1 };
+            let word        =   self.words[ index ] & mask;
+            if word == 0 {
+                self.back   =   index * WORD_BITS;
+                continue
+            }
+            let position    =   index * WORD_BITS + ( WORD_BITS - 1 - word.leading_zeros() as usize );
+            if position < self.front { break }
+            self.back       =   position;
+            return Some( ( position, true ) )
+        }
+        self.back   =   self.front;
+        None
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl WhichMajor for BitMatrix { fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJOR KEYS
+//
+
+impl < 'a > MajorKeys< 'a, usize > for BitMatrix {
+    type MajorKeysIter = Range< usize >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { 0 .. self.num_major() }
+
+    fn num_major_keys( &self ) -> usize { self.num_major() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a >
+        OracleMajor < 'a, usize, usize, bool >
+        for
+        BitMatrix
+{
+    type PairMajor =   ( usize, bool );
+    type ViewMajor =   BitView< 'a >;
+
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor { self.view( index ) }
+}
+
+impl     < 'a >
+        OracleMajorAscend < 'a, usize, usize, bool >
+        for
+        BitMatrix
+{
+    type PairMajorAscend =   ( usize, bool );
+    type ViewMajorAscend =   BitView< 'a >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend { self.view( index ) }
+}
+
+/// Only the words that overlap the range are read.
+impl     < 'a >
+        OracleMajorAscendScoped < 'a, usize, usize, bool >
+        for
+        BitMatrix
+{
+    type PairMajorAscendScoped =   ( usize, bool );
+    type ViewMajorAscendScoped =   BitView< 'a >;
+
+    fn view_major_ascend_scoped<'b: 'a>( &'b self, index: usize, min: usize, max: usize ) -> Self::ViewMajorAscendScoped {
+        let back    =   max.min( self.num_minor );
+        BitView{ words: &self.views[ index ], front: min.min( back ), back }
+    }
+}
+
+impl     < 'a >
+        OracleMajorDescend < 'a, usize, usize, bool >
+        for
+        BitMatrix
+{
+    type PairMajorDescend =   ( usize, bool );
+    type ViewMajorDescend =   std::iter::Rev< BitView< 'a > >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend { self.view( index ).rev() }
+}
+
+
+//  WRITING
+//  ---------------------------------------------------------------------------
+
+
+/// Panics if a key is out of bounds; unlike a [`VecOfVec`](crate::matrices::implementors::vec_of_vec::VecOfVec),
+/// a `BitMatrix` does not grow.
+impl OracleSet< usize, usize, bool > for BitMatrix {
+    fn set_entry( &mut self, major: usize, minor: usize, val: bool ) -> Option< bool > {
+        let previous    =   self.get( major, minor );
+        self.set( major, minor, val );
+        if previous { Some( true ) } else { None }
+    }
+
+    fn add_to_entry< RingOperator: Semiring< bool > >( &mut self, major: usize, minor: usize, val: bool, ring: &RingOperator ) {
+        let sum     =   ring.add( self.get( major, minor ), val );
+        self.set( major, minor, sum );
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GF2;
+
+    #[test]
+    fn test_views_cross_word_boundaries() {
+        let positions   =   vec![ 0, 5, 63, 64, 65, 127, 128, 199 ];
+        let mut matrix  =   BitMatrix::from_views( MajorDimension::Row, 200, vec![ positions.clone(), vec![], vec![ 199 ] ] );
+        assert_eq!( matrix.num_entries(), 9 );
+        assert_eq!( matrix.view_major_ascend( 0 ).map( |entry| entry.0 ).collect::< Vec< _ > >(), positions );
+        assert_eq!( matrix.view_major_descend( 0 ).map( |entry| entry.0 ).collect::< Vec< _ > >(), positions.iter().rev().cloned().collect::< Vec< _ > >() );
+        assert_eq!( matrix.view_major( 1 ).count(), 0 );
+        assert_eq!( matrix.view_major_ascend_scoped( 0, 6, 128 ).map( |entry| entry.0 ).collect::< Vec< _ > >(), vec![ 63, 64, 65, 127 ] );
+        assert_eq!( matrix.view_major_ascend_scoped( 0, 150, 1000 ).map( |entry| entry.0 ).collect::< Vec< _ > >(), vec![ 199 ] );
+        assert_eq!( matrix.view_major_ascend_scoped( 0, 70, 10 ).count(), 0 );
+        assert_eq!( matrix.last_entry( 0 ), Some( 199 ) );
+        assert_eq!( matrix.last_entry( 1 ), None );
+
+        // reading from both ends at once
+        let mut view    =   matrix.view_major( 0 );
+        assert_eq!( ( view.next(), view.next_back(), view.next_back() ), ( Some( (0, true) ), Some( (199, true) ), Some( (128, true) ) ) );
+        assert_eq!( view.map( |entry| entry.0 ).collect::< Vec< _ > >(), vec![ 5, 63, 64, 65, 127 ] );
+
+        // adding views, and writing through OracleSet
+        matrix.add_view_in_place( 0, 2 );
+        assert_eq!( matrix.last_entry( 0 ), Some( 128 ) );
+        matrix.add_to_entry( 1, 64, true, &GF2::new() );
+        matrix.add_to_entry( 0, 64, true, &GF2::new() );
+        assert!( matrix.get( 1, 64 ) && ! matrix.get( 0, 64 ) );
+        assert_eq!( OracleSet::set_entry( &mut matrix, 1, 64, false ), Some( true ) );
+        assert_eq!( matrix.push_zero_view(), 3 );
+        assert_eq!( matrix.num_major_keys(), 4 );
+    }
+
+    #[test]
+    #[should_panic( expected = "out of bounds" )]
+    fn test_minor_keys_are_checked() {
+        BitMatrix::new( MajorDimension::Row, 1, 64 ).set( 0, 64, true );
+    }
+}
@@ -0,0 +1,308 @@
+//! Block matrices assembled from a grid of matrix oracles.
+//!
+//! A [`BlockMatrixOracle`] arranges oracles of the same type in a rectangular grid, with missing
+//! blocks standing for zero.  As with a [`DirectSumOracle`](crate::matrices::implementors::direct_sum::DirectSumOracle),
+//! major and minor keys of the block matrix are pairs `(block_id, key)`: major view `(i, k)` is
+//! the concatenation, over the blocks `j` in block row `i`, of major view `k` of block `(i, j)`,
+//! with each minor key `l` relabeled as `(j, l)`.  Minor views are assembled in the same way from
+//! the blocks in one block column.  Nothing is copied.
+//!
+//! Here "rows" and "columns" of the grid refer to the major and minor dimensions of the blocks:
+//! if the blocks are column-major, then block row `i` of the grid is a row of blocks that share
+//! their major keys.  Since the block id increases along a view, views are sorted
+//! (lexicographically, with respect to pairs) whenever the views of the blocks are.
+//!
+//! Block matrices express mapping cones and relative boundary matrices; for example, the
+//! boundary matrix of the mapping cone of a chain map `f: C -> D` has the form
+//! `[ [ -d_C, 0 ], [ -f, d_D ] ]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::block_matrix::BlockMatrixOracle;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMajorDescend};
+//!
+//! let a       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1) ] ] );
+//! let b       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (1, 2) ] ] );
+//! let c       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 3), (1, 4) ] ] );
+//!
+//! // the block matrix [ [ a, b ], [ 0, c ] ]
+//! let matrix  =   BlockMatrixOracle::new( vec![ vec![ Some( &a ), Some( &b ) ], vec![ None, Some( &c ) ] ] );
+//!
+//! assert_eq!( matrix.view_major_ascend( (0, 0) ).collect::<Vec<_>>(), vec![ ((0, 0), 1), ((1, 1), 2) ] );
+//! assert_eq!( matrix.view_major_descend( (1, 0) ).collect::<Vec<_>>(), vec![ ((1, 1), 4), ((1, 0), 3) ] );
+//! ```
+
+use crate::matrices::implementors::direct_sum::DirectSumView;
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::iter::Flatten;
+use std::vec;
+
+
+/// A matrix assembled from a rectangular grid of matrix oracles of the same type.
+///
+/// Entry `blocks[ i ][ j ]` of the grid is the block in block row `i` and block column `j`, or
+/// `None` for a zero block.  Major and minor keys are pairs `(block_id, key)`.  Requesting a view
+/// with a block id that is out of range panics.  See the [module documentation](self) for
+/// details.
+#[derive(Clone, Debug)]
+pub struct BlockMatrixOracle< Matrix > {
+    blocks:             Vec< Vec< Option< Matrix > > >,
+    num_block_minor:    usize,
+}
+
+impl    < Matrix >
+        BlockMatrixOracle
+        < Matrix >
+{
+    /// Create the block matrix with the given grid of blocks.
+    ///
+    /// Panics if the rows of the grid have different lengths.
+    pub fn new( blocks: Vec< Vec< Option< Matrix > > > ) -> Self {
+        let num_block_minor     =   blocks.first().map( |row| row.len() ).unwrap_or( 0 );
+        assert!( blocks.iter().all( |row| row.len() == num_block_minor ), "every row of blocks must have the same length" );
+        BlockMatrixOracle{ blocks, num_block_minor }
+    }
+
+    /// The number of block rows (i.e. of distinct major block ids).
+    pub fn num_block_major( &self ) -> usize { self.blocks.len() }
+
+    /// The number of block columns (i.e. of distinct minor block ids).
+    pub fn num_block_minor( &self ) -> usize { self.num_block_minor }
+
+    /// The block in block row `i` and block column `j`, or `None` if it is zero.
+    pub fn block( &self, i: usize, j: usize ) -> Option< &Matrix > { self.blocks[ i ][ j ].as_ref() }
+
+    /// Unwrap the grid of blocks.
+    pub fn into_blocks( self ) -> Vec< Vec< Option< Matrix > > > { self.blocks }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A view of a [`BlockMatrixOracle`]: the concatenation of relabeled views of the blocks in one
+/// block row or block column.
+pub type BlockMatrixView< View >   =   Flatten< vec::IntoIter< DirectSumView< View > > >;
+
+/// Concatenate the views of the nonzero blocks in `blocks`, relabeling each by its block id.
+fn concatenate< 'b, Matrix, Blocks, View, F >( blocks: Blocks, view: F ) -> BlockMatrixView< View::IntoIter >
+    where   Matrix:     'b,
+            Blocks:     Iterator< Item = ( usize, &'b Option< Matrix > ) >,
+            View:       IntoIterator,
+            View::Item: KeyValGet,
+            F:          Fn( &'b Matrix ) -> View,
+{
+    blocks
+        .filter_map( |( block_id, block )| block.as_ref().map( |block| DirectSumView::new( block_id, view( block ).into_iter() ) ) )
+        .collect::< Vec< _ > >()
+        .into_iter()
+        .flatten()
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Matrix >
+        WhichMajor
+        for
+        BlockMatrixOracle < Matrix >
+
+        where   Matrix:     WhichMajor,
+{
+    /// Panics if every block is zero.
+    fn major_dimension( &self ) -> MajorDimension {
+        self.blocks.iter()
+            .flatten()
+            .flatten()
+            .next()
+            .expect( "a block matrix with no nonzero blocks has no major dimension" )
+            .major_dimension()
+    }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajor < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        BlockMatrixOracle < Matrix >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+                MinKey:     Clone,
+                Val:        Clone,
+                < Matrix::ViewMajor as IntoIterator >::IntoIter:    'a,
+                MinKey:     'a,
+                Val:        'a,
+{
+    type PairMajor =   ( ( usize, MinKey ), Val );
+    type ViewMajor =   BlockMatrixView< < Matrix::ViewMajor as IntoIterator >::IntoIter >;
+
+    fn view_major<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMajor {
+        let ( block_id, key )   =   index;
+        concatenate( self.blocks[ block_id ].iter().enumerate(), |block| block.view_major( key.clone() ) )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        BlockMatrixOracle < Matrix >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMajorAscend =   ( ( usize, MinKey ), Val );
+    type ViewMajorAscend =   BlockMatrixView< < Matrix::ViewMajorAscend as IntoIterator >::IntoIter >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMajorAscend {
+        let ( block_id, key )   =   index;
+        concatenate( self.blocks[ block_id ].iter().enumerate(), |block| block.view_major_ascend( key.clone() ) )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        BlockMatrixOracle < Matrix >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMajorDescend =   ( ( usize, MinKey ), Val );
+    type ViewMajorDescend =   BlockMatrixView< < Matrix::ViewMajorDescend as IntoIterator >::IntoIter >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMajorDescend {
+        let ( block_id, key )   =   index;
+        concatenate( self.blocks[ block_id ].iter().enumerate().rev(), |block| block.view_major_descend( key.clone() ) )
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMinor < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        BlockMatrixOracle < Matrix >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMinor =   ( ( usize, MinKey ), Val );
+    type ViewMinor =   BlockMatrixView< < Matrix::ViewMinor as IntoIterator >::IntoIter >;
+
+    fn view_minor<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMinor {
+        let ( block_id, key )   =   index;
+        concatenate( self.blocks.iter().map( |row| &row[ block_id ] ).enumerate(), |block| block.view_minor( key.clone() ) )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        BlockMatrixOracle < Matrix >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMinorAscend =   ( ( usize, MinKey ), Val );
+    type ViewMinorAscend =   BlockMatrixView< < Matrix::ViewMinorAscend as IntoIterator >::IntoIter >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMinorAscend {
+        let ( block_id, key )   =   index;
+        concatenate( self.blocks.iter().map( |row| &row[ block_id ] ).enumerate(), |block| block.view_minor_ascend( key.clone() ) )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        BlockMatrixOracle < Matrix >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMinorDescend =   ( ( usize, MinKey ), Val );
+    type ViewMinorDescend =   BlockMatrixView< < Matrix::ViewMinorDescend as IntoIterator >::IntoIter >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMinorDescend {
+        let ( block_id, key )   =   index;
+        concatenate( self.blocks.iter().map( |row| &row[ block_id ] ).enumerate().rev(), |block| block.view_minor_descend( key.clone() ) )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::dense::DenseMatrix;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_block_matrix_views_agree_with_assembled_dense_matrix() {
+        let ring        =   NativeRing::<i64>::new();
+        let dense       =   |cols: Vec< Vec< i64 > >| DenseMatrix::from_vec_of_vecs( MajorDimension::Col, cols, ring.clone() );
+
+        // the grid [ [ a, 0 ], [ b, c ] ], where the first block row has 2 major keys and the
+        // second has 1, and the first block column has 2 minor keys and the second has 3
+        let a           =   dense( vec![ vec![ 1, 0 ], vec![ 2, 3 ] ] );
+        let b           =   dense( vec![ vec![ 0, 4 ] ] );
+        let c           =   dense( vec![ vec![ 5, 0, 6 ] ] );
+        let block       =   BlockMatrixOracle::new( vec![ vec![ Some( a ), None ], vec![ Some( b ), Some( c ) ] ] );
+        let assembled   =   dense( vec![ vec![ 1, 0, 0, 0, 0 ], vec![ 2, 3, 0, 0, 0 ], vec![ 0, 4, 5, 0, 6 ] ] );
+        assert_eq!( block.major_dimension(), MajorDimension::Col );
+
+        let major_keys  =   [ (0, 0), (0, 1), (1, 0) ];
+        let minor_keys  =   [ (0, 0), (0, 1), (1, 0), (1, 1), (1, 2) ];
+        let flat        =   |( block_id, key ): ( usize, usize ), offsets: &[ usize ]| offsets[ block_id ] + key;
+        let relabel     =   |view: Vec< ( ( usize, usize ), i64 ) >, offsets: &[ usize ]| view.into_iter().map( |( key, val )| ( flat( key, offsets ), val ) ).collect::< Vec< _ > >();
+        for key in major_keys {
+            let i       =   flat( key, &[ 0, 2 ] );
+            assert_eq!( relabel( block.view_major( key ).collect(), &[ 0, 2 ] ), assembled.view_major( i ).collect::< Vec< _ > >() );
+            assert_eq!( relabel( block.view_major_ascend( key ).collect(), &[ 0, 2 ] ), assembled.view_major_ascend( i ).collect::< Vec< _ > >() );
+            assert_eq!( relabel( block.view_major_descend( key ).collect(), &[ 0, 2 ] ), assembled.view_major_descend( i ).collect::< Vec< _ > >() );
+        }
+        for key in minor_keys {
+            let j       =   flat( key, &[ 0, 2 ] );
+            assert_eq!( relabel( block.view_minor( key ).collect(), &[ 0, 2 ] ), assembled.view_minor( j ).collect::< Vec< _ > >() );
+            assert_eq!( relabel( block.view_minor_ascend( key ).collect(), &[ 0, 2 ] ), assembled.view_minor_ascend( j ).collect::< Vec< _ > >() );
+            assert_eq!( relabel( block.view_minor_descend( key ).collect(), &[ 0, 2 ] ), assembled.view_minor_descend( j ).collect::< Vec< _ > >() );
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! A wrapper that computes each major view of a matrix oracle at most once.
+//!
+//! Some oracles generate their views lazily, at real cost: a row of the boundary matrix of a
+//! Vietoris-Rips complex, for example, is found by searching for the cofacets of a simplex.  A
+//! reduction may request the same view many times.  Wrapping the oracle in a [`CachedOracle`]
+//! stores each ascending major view the first time it is requested, in a `HashMap` keyed by major
+//! key; later requests (ascending, descending or unordered) read the stored copy.
+//!
+//! The cache grows without bound until it is cleared with [`CachedOracle::clear_cache`], so it is
+//! best suited to views that are requested repeatedly within a single computation.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+
+/// A stored major view, shared between the cache and the views read from it.
+type Stored< MinKey, Val > = Rc< Vec< ( MinKey, Val ) > >;
+
+/// Wraps a matrix oracle, and stores each ascending major view the first time it is requested.
+///
+/// The wrapper implements `OracleMajor`, `OracleMajorAscend` and `OracleMajorDescend` whenever the
+/// wrapped matrix implements `OracleMajorAscend`; all three views are read from the stored
+/// ascending view (the unordered view is the ascending view).  Stored views are shared, so reading
+/// a view from the cache does not copy it.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::cached::CachedOracle;
+/// use solar::matrices::implementors::function::FunctionOracle;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMajorDescend};
+/// use std::cell::Cell;
+///
+/// // an oracle that counts how often it computes a view
+/// let calls       =   Cell::new( 0 );
+/// let expensive   =   FunctionOracle::new(    MajorDimension::Row,
+///                                             |i: usize| { calls.set( calls.get() + 1 ); vec![ (i, 1.), (i + 1, -1.) ] } );
+/// let cached      =   CachedOracle::new( expensive );
+///
+/// assert_eq!( cached.view_major_ascend( 4 ).collect::<Vec<_>>(), vec![ (4, 1.), (5, -1.) ] );
+/// assert_eq!( cached.view_major_descend( 4 ).collect::<Vec<_>>(), vec![ (5, -1.), (4, 1.) ] );
+/// assert_eq!( calls.get(), 1 );
+/// ```
+#[derive(Debug)]
+pub struct CachedOracle< Matrix, MajKey, MinKey, Val > {
+    matrix:     Matrix,
+    cache:      RefCell< HashMap< MajKey, Stored< MinKey, Val > > >,
+}
+
+impl    < Matrix, MajKey, MinKey, Val >
+        CachedOracle
+        < Matrix, MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+{
+    /// Wrap `matrix`, with an empty cache.
+    pub fn new( matrix: Matrix ) -> Self { CachedOracle{ matrix, cache: RefCell::new( HashMap::new() ) } }
+
+    /// A reference to the wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// The number of stored views.
+    pub fn num_cached( &self ) -> usize { self.cache.borrow().len() }
+
+    /// Delete every stored view.
+    pub fn clear_cache( &self ) { self.cache.borrow_mut().clear() }
+
+    /// Discard the cache, and return the wrapped matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+
+    /// The ascending major view with key `index`, computed and stored if it is not stored yet.
+    fn cached< 'a, 'b: 'a >( &'b self, index: MajKey ) -> Stored< MinKey, Val >
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+    {
+        if let Some( view ) = self.cache.borrow().get( &index ) { return view.clone() }
+        let view: Stored< _, _ >    =   Rc::new(    self.matrix.view_major_ascend( index.clone() )
+                                                        .into_iter()
+                                                        .map( |entry| ( entry.key(), entry.val() ) )
+                                                        .collect()
+                                                );
+        self.cache.borrow_mut().insert( index, view.clone() );
+        view
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A major view of a [`CachedOracle`]: an iterator over (clones of) the entries of a stored view.
+#[derive(Clone, Debug)]
+pub struct CachedView< MinKey, Val > {
+    entries:    Stored< MinKey, Val >,
+    front:      usize,
+    back:       usize,
+}
+
+impl    < MinKey, Val >
+        CachedView
+        < MinKey, Val >
+{
+    fn new( entries: Stored< MinKey, Val > ) -> Self {
+        let back    =   entries.len();
+        CachedView{ entries, front: 0, back }
+    }
+}
+
+impl    < MinKey: Clone, Val: Clone >
+        Iterator
+        for
+        CachedView < MinKey, Val >
+{
+    type Item = ( MinKey, Val );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        if self.front == self.back { return None }
+        self.front  +=  1;
+        Some( self.entries[ self.front - 1 ].clone() )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { ( self.back - self.front, Some( self.back - self.front ) ) }
+}
+
+impl    < MinKey: Clone, Val: Clone >
+        DoubleEndedIterator
+        for
+        CachedView < MinKey, Val >
+{
+    fn next_back( &mut self ) -> Option< Self::Item > {
+        if self.front == self.back { return None }
+        self.back   -=  1;
+        Some( self.entries[ self.back ].clone() )
+    }
+}
+
+impl < MinKey: Clone, Val: Clone > ExactSizeIterator for CachedView< MinKey, Val > {}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+impl     < Matrix: WhichMajor, MajKey, MinKey, Val >
+        WhichMajor
+        for
+        CachedOracle < Matrix, MajKey, MinKey, Val >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        CachedOracle < Matrix, MajKey, MinKey, Val >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   CachedView< MinKey, Val >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { CachedView::new( self.cached( index ) ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        CachedOracle < Matrix, MajKey, MinKey, Val >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   CachedView< MinKey, Val >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend { CachedView::new( self.cached( index ) ) }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        CachedOracle < Matrix, MajKey, MinKey, Val >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   std::iter::Rev< CachedView< MinKey, Val > >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend { CachedView::new( self.cached( index ) ).rev() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::matrices::implementors::view_recorder::ViewRecorder;
+
+    #[test]
+    fn test_each_view_is_computed_once() {
+        let cols        =   vec![ vec![ (0, 1), (2, 1) ], vec![ (1, 5) ], vec![] ];
+        let matrix      =   VecOfVec::new( MajorDimension::Col, cols.clone() );
+        let cached      =   CachedOracle::new( ViewRecorder::new( VecOfVec::new( MajorDimension::Col, cols ) ) );
+        assert_eq!( cached.major_dimension(), MajorDimension::Col );
+
+        for _ in 0 .. 3 {
+            for j in 0 .. 3 {
+                assert!( cached.view_major( j ).eq( matrix.view_major( j ) ) );
+                assert!( cached.view_major_ascend( j ).eq( matrix.view_major_ascend( j ) ) );
+                assert!( cached.view_major_descend( j ).eq( matrix.view_major_descend( j ) ) );
+            }
+        }
+        assert_eq!( cached.num_cached(), 3 );
+        assert_eq!( cached.matrix().log().len(), 3 );
+
+        // views can be read from both ends
+        let mut view    =   cached.view_major( 0 );
+        assert_eq!( view.len(), 2 );
+        assert_eq!( ( view.next_back(), view.next(), view.next() ), ( Some( (2, 1) ), Some( (0, 1) ), None ) );
+
+        cached.clear_cache();
+        assert_eq!( cached.num_cached(), 0 );
+        let _           =   cached.view_major_descend( 1 ).count();
+        assert_eq!( cached.into_inner().log().len(), 4 );
+    }
+}
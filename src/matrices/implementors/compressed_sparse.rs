@@ -1,11 +1,252 @@
+//! Compressed sparse matrices whose major keys need not be integers.
+//!
+//! A [`VecCsv`] stores its major views one after another in flat vectors, and locates major view
+//! `i` through an array of offsets; this requires major keys to be the integers `0 .. n`.
+//! Sometimes there is a natural bijection between *one* set of keys and `{0, .., n}`, but not the
+//! other.  This occurs in the persistent cohomology algorithm, where rows are naturally indexed by
+//! integers, but assigning integers to column keys (simplices, say) can be quite laborious: the
+//! columns are generated one at a time, and their keys are only known once they are generated.
+//!
+//! A [`CompressedSparse`] uses the same flat layout, and in addition records a
+//! [`BiMapSequential`] between major keys of any hashable type and the positions of their views.
+//! Views are appended one at a time with [`CompressedSparse::push_view`], and looked up by key.
+//! Minor keys can have any type, as in a [`VecCsv`].
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::compressed_sparse::CompressedSparse;
+//! use solar::matrices::matrix_oracle::{MajorDimension, MajorKeys, OracleMajorAscend};
+//!
+//! // columns indexed by edges, rows indexed by (integer) vertices
+//! let mut boundary    =   CompressedSparse::new( MajorDimension::Col );
+//! boundary.push_view( vec![ 0, 2 ], vec![ (0, -1), (2, 1) ] );
+//! boundary.push_view( vec![ 1, 2 ], vec![ (1, -1), (2, 1) ] );
+//!
+//! assert_eq!( boundary.view_major_ascend( vec![ 1, 2 ] ).collect::<Vec<_>>(), vec![ (1, -1), (2, 1) ] );
+//! assert_eq!( boundary.view_major_ascend( vec![ 0, 1 ] ).count(), 0 );   // never pushed: zero
+//! assert_eq!( boundary.major_keys().collect::<Vec<_>>(), vec![ vec![ 0, 2 ], vec![ 1, 2 ] ] );
+//! ```
 
+use crate::matrices::implementors::vec_of_csvec::VecCsv;
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        MajorKeys,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::utilities::sequences_and_ordinals::BiMapSequential;
+use std::hash::Hash;
+use std::iter::{Cloned, Rev, Zip};
+use std::ops::Range;
+use std::slice::Iter;
 
-//  NOTES
+
+/// A compressed sparse row/column matrix, whose major keys can have any hashable type.
+///
+/// Major views are stored in the order they were pushed; the `i`th view pushed has *ordinal*
+/// `i`.  The entries of each major view should be sorted in strictly ascending order of minor key.
+/// A major view that was never pushed is empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompressedSparse< MajKey, MinKey, Val >
+    where   MajKey:     Hash + Eq,
+{
+    major_dimension:    MajorDimension,
+    major_keys:         BiMapSequential< MajKey >,
+    major_ptr:          Vec< usize >,
+    min_ind:            Vec< MinKey >,
+    snz_val:            Vec< Val >,
+}
+
+impl    < MajKey, MinKey, Val >
+        CompressedSparse
+        < MajKey, MinKey, Val >
+
+        where   MajKey:     Clone + Hash + Eq,
+{
+    /// An empty matrix.
+    pub fn new( major_dimension: MajorDimension ) -> Self {
+        CompressedSparse{   major_dimension,
+                            major_keys:     BiMapSequential::from_vec( Vec::new() ),
+                            major_ptr:      vec![ 0 ],
+                            min_ind:        Vec::new(),
+                            snz_val:        Vec::new(),
+                        }
+    }
+
+    /// Append a major view with key `key`, and return its ordinal.
+    ///
+    /// Panics if a view with key `key` has already been pushed.
+    pub fn push_view< I >( &mut self, key: MajKey, entries: I ) -> usize
+        where   I:      IntoIterator< Item = ( MinKey, Val ) >,
+    {
+        assert!( self.major_keys.ord( &key ).is_none(), "a major view with this key has already been pushed" );
+        let ordinal     =   self.major_keys.ord_to_val.len();
+        self.major_keys.val_to_ord.insert( key.clone(), ordinal );
+        self.major_keys.ord_to_val.push( key );
+        for ( minor, val ) in entries {
+            self.min_ind.push( minor );
+            self.snz_val.push( val );
+        }
+        self.major_ptr.push( self.min_ind.len() );
+        ordinal
+    }
+
+    /// The ordinal of the major view with key `key`, if it has been pushed.
+    pub fn ordinal( &self, key: &MajKey ) -> Option< usize > { self.major_keys.ord( key ) }
+
+    /// The bijection between major keys and ordinals.
+    pub fn major_key_bimap( &self ) -> &BiMapSequential< MajKey > { &self.major_keys }
+
+    /// The number of major views.
+    pub fn num_major( &self ) -> usize { self.major_keys.ord_to_val.len() }
+
+    /// The number of structural nonzeros.
+    pub fn num_snz( &self ) -> usize { self.min_ind.len() }
+
+    /// Forget the major keys: return the matrix whose `i`th major view is the view with ordinal
+    /// `i`, together with the bijection between major keys and ordinals.
+    pub fn into_vec_csv( self ) -> ( VecCsv< MinKey, Val >, BiMapSequential< MajKey > ) {
+        ( VecCsv::new( self.major_dimension, self.major_ptr, self.min_ind, self.snz_val ), self.major_keys )
+    }
+
+    /// The positions in the flat vectors of the view with key `key`; empty if it was never pushed.
+    fn range( &self, key: &MajKey ) -> Range< usize > {
+        match self.ordinal( key ) {
+            Some( ordinal ) =>  self.major_ptr[ ordinal ] .. self.major_ptr[ ordinal + 1 ],
+            None            =>  0 .. 0,
+        }
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < MajKey, MinKey, Val >
+        WhichMajor
+        for
+        CompressedSparse < MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJOR KEYS
 //
-//  Why have this data type?
-//  * this overall structure for data is pretty common/standard
-//  * why have this instead of just using standard csc/csr
-//      * sometimes there is a natural bijection between *one* set of indices and {0, ..., n}, but
-//      not the other set of indices.  This occurs naturally in the persistent cohomology
-//      algorithm, where rows are naturally indexed by integers, but assigning integers to column
-//      indices can be quite laborious.
+
+/// Lists keys in the order their views were pushed.
+impl     < 'a, MajKey, MinKey, Val >
+        MajorKeys < 'a, MajKey >
+        for
+        CompressedSparse < MajKey, MinKey, Val >
+
+        where   MajKey:     'a + Clone + Hash + Eq,
+{
+    type MajorKeysIter = Cloned< Iter< 'a, MajKey > >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { self.major_keys.ord_to_val.iter().cloned() }
+
+    fn num_major_keys( &self ) -> usize { self.num_major() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        CompressedSparse < MajKey, MinKey, Val >
+
+        where   MajKey:     Clone + Hash + Eq,
+                MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   Zip< Cloned< Iter< 'a, MinKey > >, Cloned< Iter< 'a, Val > > >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        let range   =   self.range( &index );
+        self.min_ind[ range.clone() ].iter().cloned().zip( self.snz_val[ range ].iter().cloned() )
+    }
+}
+
+impl     < 'a, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        CompressedSparse < MajKey, MinKey, Val >
+
+        where   MajKey:     Clone + Hash + Eq,
+                MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   Zip< Cloned< Iter< 'a, MinKey > >, Cloned< Iter< 'a, Val > > >;
+
+    /// Assumes that entries in each major view are sorted in ascending order.
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend { self.view_major( index ) }
+}
+
+impl     < 'a, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        CompressedSparse < MajKey, MinKey, Val >
+
+        where   MajKey:     Clone + Hash + Eq,
+                MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   Zip< Cloned< Rev< Iter< 'a, MinKey > > >, Cloned< Rev< Iter< 'a, Val > > > >;
+
+    /// Assumes that entries in each major view are sorted in ascending order.
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        let range   =   self.range( &index );
+        self.min_ind[ range.clone() ].iter().rev().cloned().zip( self.snz_val[ range ].iter().rev().cloned() )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_views_agree_with_vec_csv() {
+        let views       =   vec![   ( "c", vec![ (0, 1.), (3, 2.) ] ),
+                                    ( "a", vec![] ),
+                                    ( "b", vec![ (1, 5.), (2, 6.), (4, 7.) ] ),     ];
+        let mut matrix  =   CompressedSparse::new( MajorDimension::Row );
+        for ( ordinal, ( key, view ) ) in views.iter().cloned().enumerate() {
+            assert_eq!( matrix.push_view( key, view ), ordinal );
+        }
+        assert_eq!( ( matrix.num_major(), matrix.num_snz(), matrix.ordinal( &"b" ) ), ( 3, 5, Some( 2 ) ) );
+        assert_eq!( matrix.major_keys().collect::< Vec< _ > >(), vec![ "c", "a", "b" ] );
+        assert_eq!( matrix.view_major_descend( "z" ).count(), 0 );
+
+        let ( csv, bimap )  =   matrix.clone().into_vec_csv();
+        for ( key, view ) in views {
+            let ordinal     =   bimap.ord( &key ).unwrap();
+            assert_eq!( matrix.view_major( key ).collect::< Vec< _ > >(), view );
+            assert!( matrix.view_major_ascend( key ).eq( csv.view_major_ascend( ordinal ) ) );
+            assert!( matrix.view_major_descend( key ).eq( csv.view_major_descend( ordinal ) ) );
+        }
+    }
+
+    #[test]
+    #[should_panic( expected = "already been pushed" )]
+    fn test_keys_are_unique() {
+        let mut matrix  =   CompressedSparse::new( MajorDimension::Col );
+        matrix.push_view( 'x', vec![ (0, 1) ] );
+        matrix.push_view( 'x', vec![] );
+    }
+}
@@ -0,0 +1,231 @@
+//! Conversions between matrix implementors.
+//!
+//! Different phases of a computation favor different storage formats: a [`HashOfHash`] is easy
+//! to build one entry at a time, a [`VecOfVec`] is easy to modify one view at a time, and a
+//! [`VecCsv`] is compact and fast to read.  This module implements `From` for the following
+//! conversions:
+//!
+//! | from                            | to                              |
+//! |---------------------------------|---------------------------------|
+//! | `VecOfVec< (MinKey, Val) >`     | `VecCsv< MinKey, Val >`         |
+//! | `VecCsv< MinKey, Val >`         | `VecOfVec< (MinKey, Val) >`     |
+//! | `VecOfVec< (MinKey, Val) >`     | `HashOfHash< usize, MinKey, Val >` |
+//! | `HashOfHash< usize, MinKey, Val >` | `VecOfVec< (MinKey, Val) >`  |
+//! | `Csc< Val >`                    | `VecOfVec< (usize, Val) >`      |
+//! | `Csc< Val >`                    | `VecCsv< usize, Val >`          |
+//!
+//! Every conversion preserves the major dimension, and sorts the entries of each major view in
+//! ascending order of minor key.  A [`Csc`] converts to its column-major copy.
+//!
+//! Conversions that need more information than the matrix itself are methods instead:
+//! [`Coo::into_vec_of_vec`](crate::matrices::implementors::coo::Coo::into_vec_of_vec) and
+//! [`Coo::into_vec_csv`](crate::matrices::implementors::coo::Coo::into_vec_csv) take a ring
+//! operator (to sum repeated entries), and
+//! [`Csc::from_vec_of_vec`] takes the number of minor views.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::hash_of_hash::HashOfHash;
+//! use solar::matrices::implementors::vec_of_csvec::VecCsv;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend};
+//!
+//! // build the matrix one entry at a time
+//! let mut hash    =   HashOfHash::new( MajorDimension::Row );
+//! hash.set_entry( 1, 2, 5. );
+//! hash.set_entry( 1, 0, 4. );
+//! hash.set_entry( 0, 1, 3. );
+//!
+//! // then store it compactly
+//! let vov         =   VecOfVec::from( hash );
+//! let csr         =   VecCsv::from( vov );
+//! assert_eq!( csr.major_ptr(), &vec![ 0, 1, 3 ] );
+//! assert_eq!( csr.view_major_ascend( 1 ).collect::<Vec<_>>(), vec![ (0, 4.), (2, 5.) ] );
+//! ```
+
+use crate::matrices::implementors::csc::Csc;
+use crate::matrices::implementors::hash_of_hash::HashOfHash;
+use crate::matrices::implementors::vec_of_csvec::VecCsv;
+use crate::matrices::implementors::vec_of_vec::VecOfVec;
+use crate::matrices::matrix_oracle::WhichMajor;
+use std::hash::Hash;
+
+
+/// Sort a major view in ascending order of minor key.
+fn sort_view< MinKey: PartialOrd, Val >( view: &mut [ (MinKey, Val) ] ) {
+    view.sort_by( |a, b| a.0.partial_cmp( &b.0 ).expect( "minor keys must be comparable" ) );
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VEC OF VEC <-> COMPRESSED SPARSE
+//  ---------------------------------------------------------------------------
+
+
+impl    < 'a, MinKey, Val >
+        From < VecOfVec< 'a, (MinKey, Val) > >
+        for
+        VecCsv < MinKey, Val >
+
+        where   MinKey:     Clone + PartialOrd,
+                Val:        Clone,
+{
+    fn from( matrix: VecOfVec< 'a, (MinKey, Val) > ) -> Self {
+        let mut major_ptr   =   Vec::with_capacity( matrix.vec_of_vec.len() + 1 );
+        let mut min_ind     =   Vec::new();
+        let mut snz_val     =   Vec::new();
+        major_ptr.push( 0 );
+        for mut view in matrix.vec_of_vec {
+            sort_view( &mut view );
+            for ( key, val ) in view {
+                min_ind.push( key );
+                snz_val.push( val );
+            }
+            major_ptr.push( min_ind.len() );
+        }
+        VecCsv::new( matrix.major_dimension, major_ptr, min_ind, snz_val )
+    }
+}
+
+impl    < 'a, MinKey, Val >
+        From < VecCsv< MinKey, Val > >
+        for
+        VecOfVec < 'a, (MinKey, Val) >
+
+        where   MinKey:     'a + Clone + PartialOrd,
+                Val:        'a + Clone,
+{
+    fn from( matrix: VecCsv< MinKey, Val > ) -> Self {
+        let ( major_dimension, major_ptr, min_ind, snz_val )  =   matrix.into_raw_parts();
+        let mut entries     =   min_ind.into_iter().zip( snz_val );
+        let vec_of_vec      =   major_ptr.windows( 2 )
+                                    .map(   |w| {
+                                                let mut view: Vec< _ >  =   entries.by_ref().take( w[1] - w[0] ).collect();
+                                                sort_view( &mut view );
+                                                view
+                                            }
+                                        )
+                                    .collect();
+        VecOfVec::new( major_dimension, vec_of_vec )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VEC OF VEC <-> HASH OF HASH
+//  ---------------------------------------------------------------------------
+
+
+impl    < 'a, MinKey, Val >
+        From < VecOfVec< 'a, (MinKey, Val) > >
+        for
+        HashOfHash < usize, MinKey, Val >
+
+        where   MinKey:     Clone + Hash + Eq,
+                Val:        Clone,
+{
+    /// Major view `i` of the `VecOfVec` becomes the view with major key `i`.
+    fn from( matrix: VecOfVec< 'a, (MinKey, Val) > ) -> Self {
+        let mut hash    =   HashOfHash::new( matrix.major_dimension );
+        hash.extend(
+            matrix.vec_of_vec.into_iter()
+                .enumerate()
+                .flat_map( |( major, view )| view.into_iter().map( move |( minor, val )| ( major, minor, val ) ) )
+        );
+        hash
+    }
+}
+
+impl    < 'a, MinKey, Val >
+        From < HashOfHash< usize, MinKey, Val > >
+        for
+        VecOfVec < 'a, (MinKey, Val) >
+
+        where   MinKey:     'a + Clone + Hash + Eq + PartialOrd,
+                Val:        'a + Clone,
+{
+    /// The view with major key `i` becomes major view `i` of the `VecOfVec`; the number of
+    /// major views is one more than the largest major key of a nonempty view.
+    fn from( matrix: HashOfHash< usize, MinKey, Val > ) -> Self {
+        let major_dimension =   matrix.major_dimension();
+        let num_major       =   matrix.major_keys().map( |key| key + 1 ).max().unwrap_or( 0 );
+        let mut vec_of_vec  =   vec![ Vec::new(); num_major ];
+        for ( major, view ) in matrix.into_hash_of_hash() {
+            vec_of_vec[ major ].extend( view );
+            sort_view( &mut vec_of_vec[ major ] );
+        }
+        VecOfVec::new( major_dimension, vec_of_vec )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  CSC
+//  ---------------------------------------------------------------------------
+
+
+impl    < SnzVal >
+        From < Csc< SnzVal > >
+        for
+        VecCsv < usize, SnzVal >
+
+        where   SnzVal:     Clone,
+{
+    /// The columns of the matrix, in compressed sparse column format.
+    fn from( matrix: Csc< SnzVal > ) -> Self { matrix.into_columns() }
+}
+
+impl    < 'a, SnzVal >
+        From < Csc< SnzVal > >
+        for
+        VecOfVec < 'a, (usize, SnzVal) >
+
+        where   SnzVal:     'a + Clone,
+{
+    /// The columns of the matrix, as a column-major `VecOfVec`.
+    fn from( matrix: Csc< SnzVal > ) -> Self { VecOfVec::from( matrix.into_columns() ) }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend};
+
+    #[test]
+    fn test_round_trips_preserve_major_dimension_and_sort_views() {
+        for major_dimension in [ MajorDimension::Row, MajorDimension::Col ] {
+            let unsorted    =   vec![ vec![ (3, 1), (0, 2) ], vec![], vec![ (2, 3), (1, 4), (4, 5) ] ];
+            let mut sorted  =   unsorted.clone();
+            for view in sorted.iter_mut() { view.sort() }
+
+            let csv         =   VecCsv::from( VecOfVec::new( major_dimension.clone(), unsorted.clone() ) );
+            assert_eq!( csv.major_dimension(), major_dimension );
+            assert_eq!( csv.major_ptr(), &vec![ 0, 2, 2, 5 ] );
+            assert_eq!( csv.view_major_ascend( 2 ).collect::< Vec< _ > >(), sorted[ 2 ] );
+
+            let vov         =   VecOfVec::from( csv );
+            assert_eq!( vov.major_dimension, major_dimension );
+            assert_eq!( vov.vec_of_vec, sorted );
+
+            let hash        =   HashOfHash::from( VecOfVec::new( major_dimension.clone(), unsorted ) );
+            assert_eq!( hash.major_dimension(), major_dimension );
+            assert_eq!( hash.num_entries(), 5 );
+            let vov         =   VecOfVec::from( hash );
+            assert_eq!( vov.major_dimension, major_dimension );
+            assert_eq!( vov.vec_of_vec, sorted );
+        }
+    }
+
+    #[test]
+    fn test_csc_converts_to_its_columns() {
+        let matrix      =   Csc::from_triplets( 2, 3, vec![ (0, 2, 1), (1, 0, 2), (0, 0, 3) ] );
+        let vov         =   VecOfVec::from( matrix.clone() );
+        assert_eq!( vov.major_dimension, MajorDimension::Col );
+        assert_eq!( vov.vec_of_vec, vec![ vec![ (0, 3), (1, 2) ], vec![], vec![ (0, 1) ] ] );
+        assert_eq!( VecCsv::from( matrix.clone() ), matrix.columns().clone() );
+    }
+}
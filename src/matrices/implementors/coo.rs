@@ -0,0 +1,182 @@
+//! Coordinate (triplet) format matrices.
+//!
+//! A [`Coo`] matrix is a list of triplets `(row, col, coefficient)`, in no particular order.
+//! It's a convenient format for *building* a matrix one entry at a time; once the matrix is
+//! complete, it can be converted to a [`VecOfVec`] or a compressed sparse [`VecCsv`], which are
+//! better suited to computation.
+
+use crate::matrices::implementors::vec_of_vec::VecOfVec;
+use crate::matrices::implementors::vec_of_csvec::VecCsv;
+use crate::matrices::matrix_oracle::MajorDimension;
+use crate::rings::ring::Semiring;
+
+
+/// A matrix stored as a list of `(row, col, coefficient)` triplets.
+///
+/// Triplets may appear in any order, and the same position may appear more than once; when the
+/// matrix is finalized, coefficients at the same position are summed.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::coo::Coo;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let mut matrix  =   Coo::new( 2, 3 );
+/// matrix.push( 1, 2, 5 );
+/// matrix.push( 0, 1, 1 );
+/// matrix.push( 1, 2, -2 );    // duplicate position: summed with the first entry
+/// matrix.push( 0, 0, 4 );
+/// matrix.push( 0, 0, -4 );    // sums to zero: dropped
+///
+/// let rows        =   matrix.into_vec_of_vec( MajorDimension::Row, NativeRing::<i64>::new() );
+/// assert_eq!( rows.vec_of_vec, vec![ vec![ (1, 1) ], vec![ (2, 3) ] ] );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coo< Val > {
+    num_rows:   usize,
+    num_cols:   usize,
+    triplets:   Vec< ( usize, usize, Val ) >,
+}
+
+impl < Val > Coo< Val > {
+
+    /// Create an empty `num_rows x num_cols` matrix.
+    pub fn new( num_rows: usize, num_cols: usize ) -> Self {
+        Coo{ num_rows, num_cols, triplets: Vec::new() }
+    }
+
+    /// Create an empty `num_rows x num_cols` matrix, with space for `capacity` triplets.
+    pub fn with_capacity( num_rows: usize, num_cols: usize, capacity: usize ) -> Self {
+        Coo{ num_rows, num_cols, triplets: Vec::with_capacity( capacity ) }
+    }
+
+    /// Add `val` to the entry in position `(row, col)`.
+    ///
+    /// Panics if the position lies outside the matrix.
+    pub fn push( &mut self, row: usize, col: usize, val: Val ) {
+        assert!( row < self.num_rows && col < self.num_cols,
+                 "position ({}, {}) lies outside a {} x {} matrix", row, col, self.num_rows, self.num_cols );
+        self.triplets.push( ( row, col, val ) );
+    }
+
+    /// The number of rows.
+    pub fn num_rows( &self ) -> usize { self.num_rows }
+
+    /// The number of columns.
+    pub fn num_cols( &self ) -> usize { self.num_cols }
+
+    /// The triplets pushed so far, in the order they were pushed.
+    pub fn triplets( &self ) -> &Vec< ( usize, usize, Val ) > { &self.triplets }
+
+    /// Unwrap the triplets, in the order they were pushed.
+    pub fn into_triplets( self ) -> Vec< ( usize, usize, Val ) > { self.triplets }
+
+    /// The number of triplets pushed so far (counting duplicates).
+    pub fn len( &self ) -> usize { self.triplets.len() }
+
+    /// Returns `true` if no triplets have been pushed.
+    pub fn is_empty( &self ) -> bool { self.triplets.is_empty() }
+
+    /// Sort the triplets, sum the coefficients of triplets with the same position, and drop zeros.
+    ///
+    /// Returns `(major_key, minor_key, coefficient)` triplets, sorted in ascending order.
+    fn finalize< RingOperator >( self, major_dimension: &MajorDimension, ring: RingOperator ) -> Vec< ( usize, usize, Val ) >
+        where   RingOperator:   Semiring< Val >,
+                Val:            Clone,
+    {
+        let mut triplets    =   self.triplets;
+        if let MajorDimension::Col = major_dimension {
+            for triplet in triplets.iter_mut() { std::mem::swap( &mut triplet.0, &mut triplet.1 ) }
+        }
+        triplets.sort_by_key( |x| ( x.0, x.1 ) );
+
+        let mut gathered: Vec< ( usize, usize, Val ) >  =   Vec::with_capacity( triplets.len() );
+        for (major, minor, val) in triplets {
+            if let Some( last ) = gathered.last_mut() {
+                if last.0 == major && last.1 == minor { last.2 = ring.add( last.2.clone(), val ); continue }
+            }
+            gathered.push( ( major, minor, val ) );
+        }
+        gathered.retain( |x| ! ring.is_0( x.2.clone() ) );
+        gathered
+    }
+
+    /// Convert to a [`VecOfVec`] with the given major dimension.
+    ///
+    /// Coefficients at the same position are summed, and entries equal to zero are dropped.
+    pub fn into_vec_of_vec< 'a, RingOperator >( self, major_dimension: MajorDimension, ring: RingOperator ) -> VecOfVec< 'a, (usize, Val) >
+        where   RingOperator:   Semiring< Val >,
+                Val:            Clone,
+    {
+        let num_major       =   match major_dimension { MajorDimension::Row => self.num_rows, MajorDimension::Col => self.num_cols };
+        let mut vec_of_vec  =   vec![ Vec::new(); num_major ];
+        for (major, minor, val) in self.finalize( &major_dimension, ring ) {
+            vec_of_vec[ major ].push( ( minor, val ) );
+        }
+        VecOfVec::new( major_dimension, vec_of_vec )
+    }
+
+    /// Convert to a compressed sparse matrix with the given major dimension (CSR if the major
+    /// dimension is `Row`, CSC if it is `Col`).
+    ///
+    /// Coefficients at the same position are summed, and entries equal to zero are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::coo::Coo;
+    /// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+    /// use solar::rings::field_prime::GF2;
+    ///
+    /// let mut matrix  =   Coo::new( 2, 2 );
+    /// matrix.push( 1, 0, true );
+    /// matrix.push( 0, 1, true );
+    /// matrix.push( 1, 0, true );
+    ///
+    /// let csr         =   matrix.into_vec_csv( MajorDimension::Row, GF2::new() );
+    /// assert_eq!( csr.major_ptr(), &vec![ 0, 1, 1 ] );
+    /// assert_eq!( csr.view_major( 0 ).collect::<Vec<_>>(), vec![ (1, true) ] );
+    /// ```
+    pub fn into_vec_csv< RingOperator >( self, major_dimension: MajorDimension, ring: RingOperator ) -> VecCsv< usize, Val >
+        where   RingOperator:   Semiring< Val >,
+                Val:            Clone,
+    {
+        let num_major       =   match major_dimension { MajorDimension::Row => self.num_rows, MajorDimension::Col => self.num_cols };
+        let triplets        =   self.finalize( &major_dimension, ring );
+        VecCsv::from_triplets( major_dimension, num_major, triplets )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::matrix_oracle::OracleMajor;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_coo_conversions_agree() {
+        let mut coo     =   Coo::with_capacity( 3, 2, 6 );
+        for (row, col, val) in [ (2, 1, 1), (0, 0, 2), (2, 1, 1), (1, 1, 3), (0, 0, -2), (2, 0, 7) ] {
+            coo.push( row, col, val );
+        }
+        assert_eq!( coo.len(), 6 );
+
+        let ring        =   NativeRing::<i64>::new();
+        for major_dimension in [ MajorDimension::Row, MajorDimension::Col ] {
+            let vov     =   coo.clone().into_vec_of_vec( major_dimension.clone(), ring.clone() );
+            let csv     =   coo.clone().into_vec_csv( major_dimension.clone(), ring.clone() );
+            assert_eq!( vov.vec_of_vec.len(), csv.num_major() );
+            for i in 0 .. csv.num_major() {
+                assert!( csv.view_major( i ).eq( vov.view_major( i ) ) );
+            }
+        }
+
+        let cols        =   coo.into_vec_of_vec( MajorDimension::Col, ring );
+        assert_eq!( cols.vec_of_vec, vec![ vec![ (2, 7) ], vec![ (1, 3), (2, 2) ] ] );
+    }
+}
@@ -0,0 +1,275 @@
+//! Compressed sparse column matrices with fast access to both rows and columns.
+//!
+//! A [`Csc`] stores two copies of the same matrix: one in compressed sparse column format (used
+//! for major views) and one in compressed sparse row format (used for minor views).  This doubles
+//! the memory footprint, but allows efficient access to the columns of a matrix that was built
+//! row-major, and vice versa.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMajorAscendScoped,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        MajorKeys,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::matrices::implementors::vec_of_vec::VecOfVec;
+use crate::matrices::implementors::vec_of_csvec::VecCsv;
+use std::iter::{Cloned, Rev, Zip};
+use std::ops::Range;
+use std::slice::Iter;
+
+
+/// A column-major compressed sparse matrix, which also stores its rows.
+///
+/// Major views are columns and minor views are rows.  Both are indexed by integers.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::csc::Csc;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMinorAscend};
+///
+/// // a matrix built row-major
+/// //  [ 1  0  2 ]
+/// //  [ 0  3  0 ]
+/// let rows    =   VecOfVec::new(
+///                     MajorDimension::Row,
+///                     vec![ vec![ (0, 1), (2, 2) ], vec![ (1, 3) ] ],
+///                 );
+/// let matrix  =   Csc::from_vec_of_vec( &rows, 3 );
+///
+/// // columns are major views
+/// assert_eq!( matrix.view_major_ascend( 2 ).collect::<Vec<_>>(), vec![ (0, 2) ] );
+/// // rows are minor views
+/// assert_eq!( matrix.view_minor_ascend( 0 ).collect::<Vec<_>>(), vec![ (0, 1), (2, 2) ] );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Csc< SnzVal > {
+    columns:    VecCsv< usize, SnzVal >,
+    rows:       VecCsv< usize, SnzVal >,
+}
+
+impl    < SnzVal: Clone >
+        Csc
+        < SnzVal >
+{
+    /// Create a new matrix from a list of triplets `(row, col, coefficient)`.
+    ///
+    /// Triplets can appear in any order, but no two triplets should have the same row and
+    /// column.  Panics if a row index is `>= num_rows` or a column index is `>= num_cols`.
+    pub fn from_triplets< I >( num_rows: usize, num_cols: usize, triplets: I ) -> Self
+        where   I:  IntoIterator< Item = ( usize, usize, SnzVal ) >
+    {
+        let triplets: Vec< _ >  =   triplets.into_iter().collect();
+        let columns     =   VecCsv::from_triplets(
+                                MajorDimension::Col,
+                                num_cols,
+                                triplets.iter().map( |(row, col, val)| ( *col, *row, val.clone() ) ),
+                            );
+        let rows        =   VecCsv::from_triplets( MajorDimension::Row, num_rows, triplets );
+        Csc{ columns, rows }
+    }
+
+    /// Convert a [`VecOfVec`] (either row-major or column-major) to a `Csc`.
+    ///
+    /// The argument `num_minor` is the number of minor views of `matrix` (i.e. the number of
+    /// columns, if `matrix` is row-major); every minor key must be strictly less than `num_minor`.
+    pub fn from_vec_of_vec< 'a >( matrix: &VecOfVec< 'a, (usize, SnzVal) >, num_minor: usize ) -> Self {
+        let num_major   =   matrix.vec_of_vec.len();
+        let triplets    =   matrix.vec_of_vec.iter()
+                                .enumerate()
+                                .flat_map( |(i, vec)| vec.iter().map( move |(j, val)| ( i, *j, val.clone() ) ) );
+        match matrix.major_dimension {
+            MajorDimension::Row     =>  Csc::from_triplets( num_major, num_minor, triplets ),
+            MajorDimension::Col     =>  Csc::from_triplets( num_minor, num_major, triplets.map( |(i, j, val)| ( j, i, val ) ) ),
+        }
+    }
+
+    /// The number of rows.
+    pub fn num_rows( &self ) -> usize { self.rows.num_major() }
+
+    /// The number of columns.
+    pub fn num_cols( &self ) -> usize { self.columns.num_major() }
+
+    /// The columns of the matrix, in compressed sparse column format.
+    pub fn columns( &self ) -> &VecCsv< usize, SnzVal > { &self.columns }
+
+    /// The rows of the matrix, in compressed sparse row format.
+    pub fn rows( &self ) -> &VecCsv< usize, SnzVal > { &self.rows }
+
+    /// Discard the rows, and return the columns in compressed sparse column format.
+    pub fn into_columns( self ) -> VecCsv< usize, SnzVal > { self.columns }
+}
+
+
+//  ---------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < SnzVal >
+        WhichMajor
+        for
+        Csc < SnzVal >
+{ fn major_dimension( &self ) -> MajorDimension { MajorDimension::Col } }
+
+
+//  MAJOR KEYS
+//
+
+impl     < 'a, SnzVal >
+        MajorKeys < 'a, usize >
+        for
+        Csc < SnzVal >
+{
+    type MajorKeysIter = Range< usize >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { 0 .. self.columns.num_major() }
+
+    fn num_major_keys( &self ) -> usize { self.columns.num_major() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, SnzVal >
+        OracleMajor < 'a, usize, usize, SnzVal >
+        for
+        Csc < SnzVal >
+
+        where   SnzVal: 'a + Clone,
+{
+    type PairMajor =   ( usize, SnzVal );
+    type ViewMajor =   Zip< Cloned< Iter< 'a, usize > >, Cloned< Iter< 'a, SnzVal > > >;
+
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor { self.columns.view_major( index ) }
+}
+
+impl     < 'a, SnzVal >
+        OracleMajorAscend < 'a, usize, usize, SnzVal >
+        for
+        Csc < SnzVal >
+
+        where   SnzVal: 'a + Clone,
+{
+    type PairMajorAscend =   ( usize, SnzVal );
+    type ViewMajorAscend =   Zip< Cloned< Iter< 'a, usize > >, Cloned< Iter< 'a, SnzVal > > >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend { self.columns.view_major_ascend( index ) }
+}
+
+impl     < 'a, SnzVal >
+        OracleMajorAscendScoped < 'a, usize, usize, SnzVal >
+        for
+        Csc < SnzVal >
+
+        where   SnzVal: 'a + Clone,
+{
+    type PairMajorAscendScoped =   ( usize, SnzVal );
+    type ViewMajorAscendScoped =   Zip< Cloned< Iter< 'a, usize > >, Cloned< Iter< 'a, SnzVal > > >;
+
+    fn view_major_ascend_scoped<'b: 'a>( &'b self, index: usize, min: usize, max: usize ) -> Self::ViewMajorAscendScoped { self.columns.view_major_ascend_scoped( index, min, max ) }
+}
+
+impl     < 'a, SnzVal >
+        OracleMajorDescend < 'a, usize, usize, SnzVal >
+        for
+        Csc < SnzVal >
+
+        where   SnzVal: 'a + Clone,
+{
+    type PairMajorDescend =   ( usize, SnzVal );
+    type ViewMajorDescend =   Zip< Cloned< Rev< Iter< 'a, usize > > >, Cloned< Rev< Iter< 'a, SnzVal > > > >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend { self.columns.view_major_descend( index ) }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, SnzVal >
+        OracleMinor < 'a, usize, usize, SnzVal >
+        for
+        Csc < SnzVal >
+
+        where   SnzVal: 'a + Clone,
+{
+    type PairMinor =   ( usize, SnzVal );
+    type ViewMinor =   Zip< Cloned< Iter< 'a, usize > >, Cloned< Iter< 'a, SnzVal > > >;
+
+    fn view_minor<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinor { self.rows.view_major( index ) }
+}
+
+impl     < 'a, SnzVal >
+        OracleMinorAscend < 'a, usize, usize, SnzVal >
+        for
+        Csc < SnzVal >
+
+        where   SnzVal: 'a + Clone,
+{
+    type PairMinorAscend =   ( usize, SnzVal );
+    type ViewMinorAscend =   Zip< Cloned< Iter< 'a, usize > >, Cloned< Iter< 'a, SnzVal > > >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorAscend { self.rows.view_major_ascend( index ) }
+}
+
+impl     < 'a, SnzVal >
+        OracleMinorDescend < 'a, usize, usize, SnzVal >
+        for
+        Csc < SnzVal >
+
+        where   SnzVal: 'a + Clone,
+{
+    type PairMinorDescend =   ( usize, SnzVal );
+    type ViewMinorDescend =   Zip< Cloned< Rev< Iter< 'a, usize > > >, Cloned< Rev< Iter< 'a, SnzVal > > > >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorDescend { self.rows.view_major_descend( index ) }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_csc_from_row_and_column_major() {
+
+        let rows        =   vec![
+                                vec![ (0, 1), (3, 4)         ],
+                                vec![                        ],
+                                vec![ (1, 2), (2, 3), (3, 5) ],
+                            ];
+        let cols        =   vec![
+                                vec![ (0, 1)         ],
+                                vec![ (2, 2)         ],
+                                vec![ (2, 3)         ],
+                                vec![ (0, 4), (2, 5) ],
+                            ];
+        let from_rows   =   Csc::from_vec_of_vec( &VecOfVec::new( MajorDimension::Row, rows.clone() ), 4 );
+        let from_cols   =   Csc::from_vec_of_vec( &VecOfVec::new( MajorDimension::Col, cols.clone() ), 3 );
+
+        assert_eq!( from_rows, from_cols );
+        assert_eq!( ( from_rows.num_rows(), from_rows.num_cols() ), ( 3, 4 ) );
+        for (i, row) in rows.iter().enumerate() {
+            assert!( from_rows.view_minor( i ).eq( row.iter().cloned() ) );
+            assert!( from_rows.view_minor_descend( i ).eq( row.iter().rev().cloned() ) );
+        }
+        for (j, col) in cols.iter().enumerate() {
+            assert!( from_rows.view_major_ascend( j ).eq( col.iter().cloned() ) );
+            assert!( from_rows.view_major_descend( j ).eq( col.iter().rev().cloned() ) );
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Dense matrices.
+//!
+//! A [`DenseMatrix`] stores every entry of a matrix, zero or not, in a flat buffer.  Its major
+//! and minor views skip entries that the ring operator identifies as zero, so it behaves like
+//! any other sparse oracle.  This is wasteful for large matrices, but convenient for writing
+//! small reference examples to test sparse algorithms against.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        MajorKeys,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use std::iter::Rev;
+use std::ops::Range;
+
+
+/// A dense matrix, stored as a flat buffer of major views.
+///
+/// Major view `i` occupies positions `i * num_minor .. (i + 1) * num_minor` of the buffer.
+/// Major and minor keys are integers, and views skip the entries that `ring` identifies as zero.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::dense::DenseMatrix;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMinorDescend};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let matrix  =   DenseMatrix::from_vec_of_vecs(
+///                     MajorDimension::Row,
+///                     vec![ vec![ 1, 0, 2 ],
+///                           vec![ 0, 3, 4 ], ],
+///                     NativeRing::<i64>::new(),
+///                 );
+///
+/// assert_eq!( matrix.view_major_ascend( 0 ).collect::<Vec<_>>(),  vec![ (0, 1), (2, 2) ] );
+/// assert_eq!( matrix.view_minor_descend( 2 ).collect::<Vec<_>>(), vec![ (1, 4), (0, 2) ] );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseMatrix< Val, RingOperator > {
+    major_dimension:    MajorDimension,
+    num_major:          usize,
+    num_minor:          usize,
+    entries:            Vec< Val >,
+    ring:               RingOperator,
+}
+
+impl    < Val, RingOperator >
+        DenseMatrix
+        < Val, RingOperator >
+{
+    /// Create a new matrix from a flat buffer of `num_major * num_minor` entries, listed one
+    /// major view after another.
+    ///
+    /// Panics if the buffer has the wrong length.
+    pub fn new(
+                major_dimension:    MajorDimension,
+                num_major:          usize,
+                num_minor:          usize,
+                entries:            Vec< Val >,
+                ring:               RingOperator,
+            )
+            -> Self
+    {
+        assert_eq!( entries.len(), num_major * num_minor, "a {} x {} buffer must have {} entries", num_major, num_minor, num_major * num_minor );
+        DenseMatrix{ major_dimension, num_major, num_minor, entries, ring }
+    }
+
+    /// Create a new matrix from a vector of major views.
+    ///
+    /// Panics if the major views have different lengths.  An empty vector gives a `0 x 0` matrix.
+    pub fn from_vec_of_vecs( major_dimension: MajorDimension, vec_of_vec: Vec< Vec< Val > >, ring: RingOperator ) -> Self {
+        let num_major   =   vec_of_vec.len();
+        let num_minor   =   vec_of_vec.first().map( |x| x.len() ).unwrap_or( 0 );
+        assert!( vec_of_vec.iter().all( |x| x.len() == num_minor ), "every major view must have the same length" );
+        let entries     =   vec_of_vec.into_iter().flatten().collect();
+        DenseMatrix{ major_dimension, num_major, num_minor, entries, ring }
+    }
+
+    /// The number of major views.
+    pub fn num_major( &self ) -> usize { self.num_major }
+
+    /// The number of minor views.
+    pub fn num_minor( &self ) -> usize { self.num_minor }
+
+    /// The entry in major view `major` and minor view `minor`.  Panics if either is out of bounds.
+    pub fn entry( &self, major: usize, minor: usize ) -> &Val {
+        assert!( major < self.num_major && minor < self.num_minor, "position ({}, {}) lies outside the matrix", major, minor );
+        &self.entries[ major * self.num_minor + minor ]
+    }
+
+    /// The flat buffer of entries.
+    pub fn entries( &self ) -> &Vec< Val > { &self.entries }
+
+    /// The ring operator used to identify zeros.
+    pub fn ring( &self ) -> &RingOperator { &self.ring }
+
+    /// A view of the entries at positions `offset + k * stride` of the buffer, for `k in 0 .. len`.
+    fn view( &self, offset: usize, stride: usize, len: usize ) -> DenseView< '_, Val, RingOperator > {
+        DenseView{ entries: &self.entries, ring: &self.ring, offset, stride, front: 0, back: len }
+    }
+
+    fn major_view( &self, index: usize ) -> DenseView< '_, Val, RingOperator > {
+        assert!( index < self.num_major, "major key {} exceeds the number of major views", index );
+        self.view( index * self.num_minor, 1, self.num_minor )
+    }
+
+    fn minor_view( &self, index: usize ) -> DenseView< '_, Val, RingOperator > {
+        assert!( index < self.num_minor, "minor key {} exceeds the number of minor views", index );
+        self.view( index, self.num_minor, self.num_major )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A (major or minor) view of a [`DenseMatrix`], which skips zero entries.
+///
+/// Iterates over `(key, coefficient)` pairs in ascending order of key; call `.rev()` to iterate
+/// in descending order.
+#[derive(Clone, Debug)]
+pub struct DenseView< 'a, Val, RingOperator > {
+    entries:    &'a [ Val ],
+    ring:       &'a RingOperator,
+    offset:     usize,
+    stride:     usize,
+    front:      usize,
+    back:       usize,
+}
+
+impl    < 'a, Val, RingOperator >
+        Iterator
+        for
+        DenseView< 'a, Val, RingOperator >
+
+        where   Val:            Clone,
+                RingOperator:   Semiring< Val >,
+{
+    type Item = ( usize, Val );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        while self.front < self.back {
+            let key     =   self.front;
+            self.front  +=  1;
+            let val     =   &self.entries[ self.offset + key * self.stride ];
+            if ! self.ring.is_0( val.clone() ) { return Some( ( key, val.clone() ) ) }
+        }
+        None
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { ( 0, Some( self.back - self.front ) ) }
+}
+
+impl    < 'a, Val, RingOperator >
+        DoubleEndedIterator
+        for
+        DenseView< 'a, Val, RingOperator >
+
+        where   Val:            Clone,
+                RingOperator:   Semiring< Val >,
+{
+    fn next_back( &mut self ) -> Option< Self::Item > {
+        while self.front < self.back {
+            self.back   -=  1;
+            let val     =   &self.entries[ self.offset + self.back * self.stride ];
+            if ! self.ring.is_0( val.clone() ) { return Some( ( self.back, val.clone() ) ) }
+        }
+        None
+    }
+}
+
+
+//  ---------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Val, RingOperator >
+        WhichMajor
+        for
+        DenseMatrix < Val, RingOperator >
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJOR KEYS
+//
+
+impl     < 'a, Val, RingOperator >
+        MajorKeys < 'a, usize >
+        for
+        DenseMatrix < Val, RingOperator >
+{
+    type MajorKeysIter = Range< usize >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { 0 .. self.num_major }
+
+    fn num_major_keys( &self ) -> usize { self.num_major }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Val, RingOperator >
+        OracleMajor < 'a, usize, usize, Val >
+        for
+        DenseMatrix < Val, RingOperator >
+
+        where   Val:            'a + Clone,
+                RingOperator:   'a + Semiring< Val >,
+{
+    type PairMajor =   ( usize, Val );
+    type ViewMajor =   DenseView< 'a, Val, RingOperator >;
+
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor { self.major_view( index ) }
+}
+
+impl     < 'a, Val, RingOperator >
+        OracleMajorAscend < 'a, usize, usize, Val >
+        for
+        DenseMatrix < Val, RingOperator >
+
+        where   Val:            'a + Clone,
+                RingOperator:   'a + Semiring< Val >,
+{
+    type PairMajorAscend =   ( usize, Val );
+    type ViewMajorAscend =   DenseView< 'a, Val, RingOperator >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend { self.major_view( index ) }
+}
+
+impl     < 'a, Val, RingOperator >
+        OracleMajorDescend < 'a, usize, usize, Val >
+        for
+        DenseMatrix < Val, RingOperator >
+
+        where   Val:            'a + Clone,
+                RingOperator:   'a + Semiring< Val >,
+{
+    type PairMajorDescend =   ( usize, Val );
+    type ViewMajorDescend =   Rev< DenseView< 'a, Val, RingOperator > >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend { self.major_view( index ).rev() }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Val, RingOperator >
+        OracleMinor < 'a, usize, usize, Val >
+        for
+        DenseMatrix < Val, RingOperator >
+
+        where   Val:            'a + Clone,
+                RingOperator:   'a + Semiring< Val >,
+{
+    type PairMinor =   ( usize, Val );
+    type ViewMinor =   DenseView< 'a, Val, RingOperator >;
+
+    fn view_minor<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinor { self.minor_view( index ) }
+}
+
+impl     < 'a, Val, RingOperator >
+        OracleMinorAscend < 'a, usize, usize, Val >
+        for
+        DenseMatrix < Val, RingOperator >
+
+        where   Val:            'a + Clone,
+                RingOperator:   'a + Semiring< Val >,
+{
+    type PairMinorAscend =   ( usize, Val );
+    type ViewMinorAscend =   DenseView< 'a, Val, RingOperator >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorAscend { self.minor_view( index ) }
+}
+
+impl     < 'a, Val, RingOperator >
+        OracleMinorDescend < 'a, usize, usize, Val >
+        for
+        DenseMatrix < Val, RingOperator >
+
+        where   Val:            'a + Clone,
+                RingOperator:   'a + Semiring< Val >,
+{
+    type PairMinorDescend =   ( usize, Val );
+    type ViewMinorDescend =   Rev< DenseView< 'a, Val, RingOperator > >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorDescend { self.minor_view( index ).rev() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::csc::Csc;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_dense_views_agree_with_csc() {
+        let dense       =   DenseMatrix::new(
+                                MajorDimension::Col,
+                                3,
+                                2,
+                                vec![ 1, 0, 0, 2, 5, 0 ],
+                                NativeRing::<i64>::new(),
+                            );
+        let csc         =   Csc::from_triplets( 2, 3, vec![ (0, 0, 1), (1, 1, 2), (0, 2, 5) ] );
+
+        assert_eq!( *dense.entry( 2, 0 ), 5 );
+        for j in 0 .. 3 {
+            assert!( dense.view_major( j ).eq( csc.view_major( j ) ) );
+            assert!( dense.view_major_descend( j ).eq( csc.view_major_descend( j ) ) );
+        }
+        for i in 0 .. 2 {
+            assert!( dense.view_minor_ascend( i ).eq( csc.view_minor_ascend( i ) ) );
+            assert!( dense.view_minor_descend( i ).eq( csc.view_minor_descend( i ) ) );
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Diagonal matrices.
+//!
+//! A [`DiagonalMatrixOracle`] stores the nonzero diagonal entries of a matrix as a sparse vector
+//! `Vec< (Key, Val) >`.  Every major or minor view contains at most one entry.  Diagonal
+//! matrices are a common building block for weighting (e.g. rescaling the rows or columns of) a
+//! boundary matrix.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use std::option;
+
+
+/// A diagonal matrix whose nonzero entries are given by a sparse vector.
+///
+/// The view with key `k` (major or minor) is `[ (k, val) ]` if `(k, val)` is an entry of the
+/// sparse vector, and empty otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::diagonal::DiagonalMatrixOracle;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor, OracleMinor};
+///
+/// let matrix  =   DiagonalMatrixOracle::new(
+///                     MajorDimension::Row,
+///                     vec![ ( "b", 2. ), ( "a", 1. ) ],
+///                 );
+///
+/// assert_eq!( matrix.view_major( "a" ).collect::<Vec<_>>(), vec![ ( "a", 1. ) ] );
+/// assert_eq!( matrix.view_minor( "b" ).collect::<Vec<_>>(), vec![ ( "b", 2. ) ] );
+/// assert_eq!( matrix.view_major( "c" ).count(), 0 );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagonalMatrixOracle< Key, Val > {
+    major_dimension:    MajorDimension,
+    diagonal:           Vec< ( Key, Val ) >,
+}
+
+impl    < Key, Val >
+        DiagonalMatrixOracle
+        < Key, Val >
+
+        where   Key:    Ord,
+{
+    /// Create a new diagonal matrix from its nonzero diagonal entries.
+    ///
+    /// The entries can appear in any order.  Panics if two entries have the same key.
+    pub fn new( major_dimension: MajorDimension, mut diagonal: Vec< ( Key, Val ) > ) -> Self {
+        diagonal.sort_by( |a, b| a.0.cmp( &b.0 ) );
+        assert!( diagonal.windows( 2 ).all( |w| w[0].0 < w[1].0 ), "each key may appear at most once on the diagonal" );
+        DiagonalMatrixOracle{ major_dimension, diagonal }
+    }
+
+    /// The diagonal entry with key `key`, if it is stored.
+    pub fn get( &self, key: &Key ) -> Option< &Val > {
+        self.diagonal
+            .binary_search_by( |x| x.0.cmp( key ) )
+            .ok()
+            .map( |i| &self.diagonal[ i ].1 )
+    }
+
+    /// The stored diagonal entries, sorted in ascending order of key.
+    pub fn diagonal( &self ) -> &Vec< ( Key, Val ) > { &self.diagonal }
+
+    /// The view with key `key`; every major and minor view has this form.
+    fn view( &self, key: Key ) -> option::IntoIter< ( Key, Val ) >
+        where   Val:    Clone,
+    {
+        self.get( &key ).cloned().map( |val| ( key, val ) ).into_iter()
+    }
+}
+
+
+//  ---------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Key, Val >
+        WhichMajor
+        for
+        DiagonalMatrixOracle < Key, Val >
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Key, Val >
+        OracleMajor < 'a, Key, Key, Val >
+        for
+        DiagonalMatrixOracle < Key, Val >
+
+        where   Key:    'a + Ord + Clone,
+                Val:    'a + Clone,
+{
+    type PairMajor =   ( Key, Val );
+    type ViewMajor =   option::IntoIter< ( Key, Val ) >;
+
+    fn view_major<'b: 'a>( &'b self, index: Key ) -> Self::ViewMajor { self.view( index ) }
+}
+
+impl     < 'a, Key, Val >
+        OracleMajorAscend < 'a, Key, Key, Val >
+        for
+        DiagonalMatrixOracle < Key, Val >
+
+        where   Key:    'a + Ord + Clone,
+                Val:    'a + Clone,
+{
+    type PairMajorAscend =   ( Key, Val );
+    type ViewMajorAscend =   option::IntoIter< ( Key, Val ) >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: Key ) -> Self::ViewMajorAscend { self.view( index ) }
+}
+
+impl     < 'a, Key, Val >
+        OracleMajorDescend < 'a, Key, Key, Val >
+        for
+        DiagonalMatrixOracle < Key, Val >
+
+        where   Key:    'a + Ord + Clone,
+                Val:    'a + Clone,
+{
+    type PairMajorDescend =   ( Key, Val );
+    type ViewMajorDescend =   option::IntoIter< ( Key, Val ) >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: Key ) -> Self::ViewMajorDescend { self.view( index ) }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Key, Val >
+        OracleMinor < 'a, Key, Key, Val >
+        for
+        DiagonalMatrixOracle < Key, Val >
+
+        where   Key:    'a + Ord + Clone,
+                Val:    'a + Clone,
+{
+    type PairMinor =   ( Key, Val );
+    type ViewMinor =   option::IntoIter< ( Key, Val ) >;
+
+    fn view_minor<'b: 'a>( &'b self, index: Key ) -> Self::ViewMinor { self.view( index ) }
+}
+
+impl     < 'a, Key, Val >
+        OracleMinorAscend < 'a, Key, Key, Val >
+        for
+        DiagonalMatrixOracle < Key, Val >
+
+        where   Key:    'a + Ord + Clone,
+                Val:    'a + Clone,
+{
+    type PairMinorAscend =   ( Key, Val );
+    type ViewMinorAscend =   option::IntoIter< ( Key, Val ) >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: Key ) -> Self::ViewMinorAscend { self.view( index ) }
+}
+
+impl     < 'a, Key, Val >
+        OracleMinorDescend < 'a, Key, Key, Val >
+        for
+        DiagonalMatrixOracle < Key, Val >
+
+        where   Key:    'a + Ord + Clone,
+                Val:    'a + Clone,
+{
+    type PairMinorDescend =   ( Key, Val );
+    type ViewMinorDescend =   option::IntoIter< ( Key, Val ) >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: Key ) -> Self::ViewMinorDescend { self.view( index ) }
+}
@@ -0,0 +1,273 @@
+//! Direct sums (block-diagonal matrices) of matrix oracles.
+//!
+//! A [`DirectSumOracle`] combines a list of oracles ("blocks") of the same type into a single
+//! block-diagonal matrix.  Rows and columns of the direct sum are indexed by pairs
+//! `(block_id, key)`, where `key` indexes a row or column of block `block_id`; view
+//! `(block_id, key)` of the direct sum is view `key` of block `block_id`, with each minor key
+//! `k` replaced by `(block_id, k)`.  Nothing is copied.
+//!
+//! Since the block id is the same for every entry of a view, views are sorted (lexicographically,
+//! with respect to pairs) whenever the views of the blocks are.  This makes the direct sum a
+//! convenient way to assemble the boundary matrix of a chain complex from its disjoint components.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::direct_sum::DirectSumOracle;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend};
+//!
+//! let a       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (1, 1) ] ] );
+//! let b       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 2) ], vec![ (1, 3) ] ] );
+//! let sum     =   DirectSumOracle::new( vec![ &a, &b ] );
+//!
+//! assert_eq!( sum.view_major_ascend( (0, 0) ).collect::<Vec<_>>(), vec![ ((0, 0), 1), ((0, 1), 1) ] );
+//! assert_eq!( sum.view_major_ascend( (1, 1) ).collect::<Vec<_>>(), vec![ ((1, 1), 3) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::vector_entries::vector_entries::KeyValGet;
+
+
+/// A block-diagonal matrix, whose diagonal blocks are matrix oracles of the same type.
+///
+/// Major and minor keys are pairs `(block_id, key)`.  Requesting a view with a block id that is
+/// out of range panics.  See the [module documentation](self) for details.
+#[derive(Clone, Debug)]
+pub struct DirectSumOracle< Matrix > {
+    blocks:     Vec< Matrix >,
+}
+
+impl    < Matrix >
+        DirectSumOracle
+        < Matrix >
+{
+    /// Create the direct sum of `blocks`; block `i` has block id `i`.
+    pub fn new( blocks: Vec< Matrix > ) -> Self { DirectSumOracle{ blocks } }
+
+    /// The blocks.
+    pub fn blocks( &self ) -> &[ Matrix ] { &self.blocks }
+
+    /// The number of blocks.
+    pub fn num_blocks( &self ) -> usize { self.blocks.len() }
+
+    /// Append a block, and return its block id.
+    pub fn push( &mut self, block: Matrix ) -> usize { self.blocks.push( block ); self.blocks.len() - 1 }
+
+    /// Unwrap the blocks.
+    pub fn into_blocks( self ) -> Vec< Matrix > { self.blocks }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A view of a [`DirectSumOracle`]: a view of one block, with each key `k` replaced by
+/// `(block_id, k)`.
+#[derive(Clone, Debug)]
+pub struct DirectSumView< View > {
+    block:  usize,
+    view:   View,
+}
+
+impl < View > DirectSumView< View > {
+    /// Relabel the entries of `view`, replacing each key `k` with `(block, k)`.
+    pub fn new( block: usize, view: View ) -> Self { DirectSumView{ block, view } }
+}
+
+impl    < View >
+        Iterator
+        for
+        DirectSumView < View >
+
+        where   View:           Iterator,
+                View::Item:     KeyValGet,
+{
+    type Item = ( ( usize, < View::Item as KeyValGet >::Key ), < View::Item as KeyValGet >::Val );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        let block   =   self.block;
+        self.view.next().map( |entry| ( ( block, entry.key() ), entry.val() ) )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.view.size_hint() }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Matrix >
+        WhichMajor
+        for
+        DirectSumOracle < Matrix >
+
+        where   Matrix:     WhichMajor,
+{
+    /// Panics if there are no blocks.
+    fn major_dimension( &self ) -> MajorDimension { self.blocks.first().expect( "a direct sum of zero blocks has no major dimension" ).major_dimension() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajor < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        DirectSumOracle < Matrix >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, Val >,
+                MinKey:     Clone,
+                Val:        Clone,
+                < Matrix::ViewMajor as IntoIterator >::IntoIter:    'a,
+                MinKey:     'a,
+                Val:        'a,
+{
+    type PairMajor =   ( ( usize, MinKey ), Val );
+    type ViewMajor =   DirectSumView< < Matrix::ViewMajor as IntoIterator >::IntoIter >;
+
+    fn view_major<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMajor {
+        let ( block, key )  =   index;
+        DirectSumView{ block, view: self.blocks[ block ].view_major( key ).into_iter() }
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        DirectSumOracle < Matrix >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMajorAscend =   ( ( usize, MinKey ), Val );
+    type ViewMajorAscend =   DirectSumView< < Matrix::ViewMajorAscend as IntoIterator >::IntoIter >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMajorAscend {
+        let ( block, key )  =   index;
+        DirectSumView{ block, view: self.blocks[ block ].view_major_ascend( key ).into_iter() }
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        DirectSumOracle < Matrix >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMajorDescend =   ( ( usize, MinKey ), Val );
+    type ViewMajorDescend =   DirectSumView< < Matrix::ViewMajorDescend as IntoIterator >::IntoIter >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMajorDescend {
+        let ( block, key )  =   index;
+        DirectSumView{ block, view: self.blocks[ block ].view_major_descend( key ).into_iter() }
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMinor < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        DirectSumOracle < Matrix >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, Val >,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMinor =   ( ( usize, MinKey ), Val );
+    type ViewMinor =   DirectSumView< < Matrix::ViewMinor as IntoIterator >::IntoIter >;
+
+    fn view_minor<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMinor {
+        let ( block, key )  =   index;
+        DirectSumView{ block, view: self.blocks[ block ].view_minor( key ).into_iter() }
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        DirectSumOracle < Matrix >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMinorAscend =   ( ( usize, MinKey ), Val );
+    type ViewMinorAscend =   DirectSumView< < Matrix::ViewMinorAscend as IntoIterator >::IntoIter >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMinorAscend {
+        let ( block, key )  =   index;
+        DirectSumView{ block, view: self.blocks[ block ].view_minor_ascend( key ).into_iter() }
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, ( usize, MajKey ), ( usize, MinKey ), Val >
+        for
+        DirectSumOracle < Matrix >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                MinKey:     Clone,
+                Val:        Clone,
+{
+    type PairMinorDescend =   ( ( usize, MinKey ), Val );
+    type ViewMinorDescend =   DirectSumView< < Matrix::ViewMinorDescend as IntoIterator >::IntoIter >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: ( usize, MajKey ) ) -> Self::ViewMinorDescend {
+        let ( block, key )  =   index;
+        DirectSumView{ block, view: self.blocks[ block ].view_minor_descend( key ).into_iter() }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::dense::DenseMatrix;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_direct_sum_views_come_from_one_block() {
+        let ring        =   NativeRing::<i64>::new();
+        let a           =   DenseMatrix::from_vec_of_vecs( MajorDimension::Col, vec![ vec![ 1, 0 ], vec![ 2, 3 ] ], ring.clone() );
+        let b           =   DenseMatrix::from_vec_of_vecs( MajorDimension::Col, vec![ vec![ 4, 5, 0 ] ], ring );
+        let sum         =   DirectSumOracle::new( vec![ a, b ] );
+        assert_eq!( sum.major_dimension(), MajorDimension::Col );
+        assert_eq!( sum.num_blocks(), 2 );
+
+        assert_eq!( sum.view_major( (0, 1) ).collect::<Vec<_>>(), vec![ ((0, 0), 2), ((0, 1), 3) ] );
+        assert_eq!( sum.view_major_ascend( (1, 0) ).collect::<Vec<_>>(), vec![ ((1, 0), 4), ((1, 1), 5) ] );
+        assert_eq!( sum.view_major_descend( (1, 0) ).collect::<Vec<_>>(), vec![ ((1, 1), 5), ((1, 0), 4) ] );
+
+        assert_eq!( sum.view_minor( (0, 0) ).collect::<Vec<_>>(), vec![ ((0, 0), 1), ((0, 1), 2) ] );
+        assert_eq!( sum.view_minor_ascend( (1, 1) ).collect::<Vec<_>>(), vec![ ((1, 0), 5) ] );
+        assert_eq!( sum.view_minor_descend( (0, 1) ).collect::<Vec<_>>(), vec![ ((0, 1), 3) ] );
+        assert_eq!( sum.view_minor( (1, 2) ).count(), 0 );
+    }
+}
@@ -0,0 +1,292 @@
+//! Drop the entries of a matrix oracle that fail a predicate.
+//!
+//! A [`FilteredOracle`] wraps a matrix oracle together with a predicate on entries, and filters
+//! every view accordingly.  The predicate receives the major key, the minor key and the
+//! coefficient of each entry, so it can e.g. threshold the coefficients of a weighted matrix, or
+//! keep only the entries in a band around the diagonal.  Nothing is copied.  To restrict a matrix
+//! to sets of keys, [`Submatrix`](crate::matrices::implementors::submatrix::Submatrix) is
+//! simpler.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::filtered::FilteredOracle;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+//!
+//! let weights     =   VecOfVec::new(
+//!                         MajorDimension::Row,
+//!                         vec![   vec![ (0, 0.9), (1, 0.1), (2, 0.5) ],
+//!                                 vec![ (0, 0.2), (2, 0.7) ], ],
+//!                     );
+//!
+//! // keep the entries of weight at least one half
+//! let heavy       =   FilteredOracle::new( &weights, |_: &usize, _: &usize, val: &f64| *val >= 0.5 );
+//! assert_eq!( heavy.view_major( 0 ).collect::<Vec<_>>(), vec![ (0, 0.9), (2, 0.5) ] );
+//!
+//! // keep the entries on or above the diagonal
+//! let upper       =   FilteredOracle::new( &weights, |row: &usize, col: &usize, _: &f64| row <= col );
+//! assert_eq!( upper.view_major( 1 ).collect::<Vec<_>>(), vec![ (2, 0.7) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::vector_entries::vector_entries::KeyValGet;
+
+
+/// The matrix obtained from `matrix` by dropping every entry for which `predicate` returns
+/// `false`.
+///
+/// The predicate is called as `predicate( &major_key, &minor_key, &val )` in every view, major or
+/// minor, so that the same entry is kept or dropped in both; for a row-major matrix the major key
+/// is the row, and for a column-major matrix it is the column.  It should not depend on anything
+/// but its arguments.  Entries are returned in the same order as the wrapped matrix returns them,
+/// so ascending (respectively, descending) views remain ascending (respectively, descending).
+#[derive(Clone, Debug)]
+pub struct FilteredOracle< Matrix, Predicate > {
+    matrix:         Matrix,
+    predicate:      Predicate,
+}
+
+impl    < Matrix, Predicate >
+        FilteredOracle
+        < Matrix, Predicate >
+{
+    /// Keep the entries of `matrix` that satisfy `predicate`.
+    pub fn new( matrix: Matrix, predicate: Predicate ) -> Self { FilteredOracle{ matrix, predicate } }
+
+    /// The wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// The predicate.
+    pub fn predicate( &self ) -> &Predicate { &self.predicate }
+
+    /// Unwrap the matrix and the predicate.
+    pub fn into_parts( self ) -> ( Matrix, Predicate ) { ( self.matrix, self.predicate ) }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A major view of a [`FilteredOracle`]: the entries of a major view of the wrapped matrix that
+/// satisfy the predicate.
+#[derive(Clone, Debug)]
+pub struct FilteredMajorView< 'a, View, MajKey, Predicate > {
+    view:           View,
+    major_key:      MajKey,
+    predicate:      &'a Predicate,
+}
+
+impl    < 'a, View, MajKey, Predicate >
+        Iterator
+        for
+        FilteredMajorView < 'a, View, MajKey, Predicate >
+
+        where   View:           Iterator,
+                View::Item:     KeyValGet,
+                Predicate:      Fn( &MajKey, &< View::Item as KeyValGet >::Key, &< View::Item as KeyValGet >::Val ) -> bool,
+{
+    type Item = View::Item;
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        let ( major_key, predicate )    =   ( &self.major_key, self.predicate );
+        self.view.find( |entry| predicate( major_key, &entry.key(), &entry.val() ) )
+    }
+}
+
+/// A minor view of a [`FilteredOracle`]: the entries of a minor view of the wrapped matrix that
+/// satisfy the predicate.
+#[derive(Clone, Debug)]
+pub struct FilteredMinorView< 'a, View, MinKey, Predicate > {
+    view:           View,
+    minor_key:      MinKey,
+    predicate:      &'a Predicate,
+}
+
+impl    < 'a, View, MinKey, Predicate >
+        Iterator
+        for
+        FilteredMinorView < 'a, View, MinKey, Predicate >
+
+        where   View:           Iterator,
+                View::Item:     KeyValGet,
+                Predicate:      Fn( &< View::Item as KeyValGet >::Key, &MinKey, &< View::Item as KeyValGet >::Val ) -> bool,
+{
+    type Item = View::Item;
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        let ( minor_key, predicate )    =   ( &self.minor_key, self.predicate );
+        self.view.find( |entry| predicate( &entry.key(), minor_key, &entry.val() ) )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Matrix, Predicate >
+        WhichMajor
+        for
+        FilteredOracle < Matrix, Predicate >
+
+        where   Matrix:     WhichMajor,
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, Predicate, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        FilteredOracle < Matrix, Predicate >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, Val >,
+                MajKey:     'a + Clone,
+                Predicate:  'a + Fn( &MajKey, &MinKey, &Val ) -> bool,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   FilteredMajorView< 'a, < Matrix::ViewMajor as IntoIterator >::IntoIter, MajKey, Predicate >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        FilteredMajorView{ view: self.matrix.view_major( index.clone() ).into_iter(), major_key: index, predicate: &self.predicate }
+    }
+}
+
+impl     < 'a, Matrix, Predicate, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        FilteredOracle < Matrix, Predicate >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     'a + Clone,
+                Predicate:  'a + Fn( &MajKey, &MinKey, &Val ) -> bool,
+{
+    type PairMajorAscend =   Matrix::PairMajorAscend;
+    type ViewMajorAscend =   FilteredMajorView< 'a, < Matrix::ViewMajorAscend as IntoIterator >::IntoIter, MajKey, Predicate >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        FilteredMajorView{ view: self.matrix.view_major_ascend( index.clone() ).into_iter(), major_key: index, predicate: &self.predicate }
+    }
+}
+
+impl     < 'a, Matrix, Predicate, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        FilteredOracle < Matrix, Predicate >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:     'a + Clone,
+                Predicate:  'a + Fn( &MajKey, &MinKey, &Val ) -> bool,
+{
+    type PairMajorDescend =   Matrix::PairMajorDescend;
+    type ViewMajorDescend =   FilteredMajorView< 'a, < Matrix::ViewMajorDescend as IntoIterator >::IntoIter, MajKey, Predicate >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        FilteredMajorView{ view: self.matrix.view_major_descend( index.clone() ).into_iter(), major_key: index, predicate: &self.predicate }
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, Predicate, MajKey, MinKey, Val >
+        OracleMinor < 'a, MajKey, MinKey, Val >
+        for
+        FilteredOracle < Matrix, Predicate >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, Val >,
+                MajKey:     'a + Clone,
+                Predicate:  'a + Fn( &MinKey, &MajKey, &Val ) -> bool,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   FilteredMinorView< 'a, < Matrix::ViewMinor as IntoIterator >::IntoIter, MajKey, Predicate >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        FilteredMinorView{ view: self.matrix.view_minor( index.clone() ).into_iter(), minor_key: index, predicate: &self.predicate }
+    }
+}
+
+impl     < 'a, Matrix, Predicate, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, MajKey, MinKey, Val >
+        for
+        FilteredOracle < Matrix, Predicate >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     'a + Clone,
+                Predicate:  'a + Fn( &MinKey, &MajKey, &Val ) -> bool,
+{
+    type PairMinorAscend =   Matrix::PairMinorAscend;
+    type ViewMinorAscend =   FilteredMinorView< 'a, < Matrix::ViewMinorAscend as IntoIterator >::IntoIter, MajKey, Predicate >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        FilteredMinorView{ view: self.matrix.view_minor_ascend( index.clone() ).into_iter(), minor_key: index, predicate: &self.predicate }
+    }
+}
+
+impl     < 'a, Matrix, Predicate, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, MajKey, MinKey, Val >
+        for
+        FilteredOracle < Matrix, Predicate >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:     'a + Clone,
+                Predicate:  'a + Fn( &MinKey, &MajKey, &Val ) -> bool,
+{
+    type PairMinorDescend =   Matrix::PairMinorDescend;
+    type ViewMinorDescend =   FilteredMinorView< 'a, < Matrix::ViewMinorDescend as IntoIterator >::IntoIter, MajKey, Predicate >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        FilteredMinorView{ view: self.matrix.view_minor_descend( index.clone() ).into_iter(), minor_key: index, predicate: &self.predicate }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::csc::Csc;
+
+    #[test]
+    fn test_filtered_views_agree_with_filtered_triplets() {
+        let triplets        =   vec![ (0, 0, 1), (2, 0, -2), (1, 1, 3), (0, 2, -4), (1, 2, 5), (2, 2, 6) ];
+        let matrix          =   Csc::from_triplets( 3, 3, triplets.clone() );
+        // a Csc is column-major, so major keys are columns and minor keys are rows; keep the
+        // positive entries on or below the diagonal
+        let keep            =   |col: &usize, row: &usize, val: &i32| row >= col && *val > 0;
+        let filtered        =   FilteredOracle::new( &matrix, keep );
+
+        for col in 0 .. 3 {
+            let expected: Vec< _ >  =   triplets.iter().filter( |&&( r, c, v )| c == col && keep( &c, &r, &v ) ).map( |&( r, _, v )| ( r, v ) ).collect();
+            assert_eq!( filtered.view_major( col ).collect::< Vec< _ > >(), expected );
+            assert_eq!( filtered.view_major_ascend( col ).collect::< Vec< _ > >(), expected );
+            assert_eq!( filtered.view_major_descend( col ).collect::< Vec< _ > >(), expected.into_iter().rev().collect::< Vec< _ > >() );
+        }
+        for row in 0 .. 3 {
+            let mut expected: Vec< _ >  =   triplets.iter().filter( |&&( r, c, v )| r == row && keep( &c, &r, &v ) ).map( |&( _, c, v )| ( c, v ) ).collect();
+            expected.sort();
+            assert_eq!( filtered.view_minor( row ).collect::< Vec< _ > >(), expected );
+            assert_eq!( filtered.view_minor_ascend( row ).collect::< Vec< _ > >(), expected );
+            assert_eq!( filtered.view_minor_descend( row ).collect::< Vec< _ > >(), expected.into_iter().rev().collect::< Vec< _ > >() );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Matrix oracles defined by a closure.
+//!
+//! Writing a matrix oracle by hand means defining a struct and implementing several traits.  For
+//! a throwaway oracle (e.g. in a test, or to try out an idea) it is usually enough to say what
+//! each major view is: a [`FunctionOracle`] wraps a closure that maps a major key to its major
+//! view, returned as a `Vec< (Key, Val) >`.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use std::iter::Rev;
+use std::marker::PhantomData;
+use std::vec;
+
+
+/// A matrix oracle whose major view with key `k` is `function( k )`.
+///
+/// Major and minor keys have the same type.  The closure should return the entries of each view
+/// sorted in ascending order of minor key; the ascending view returns them in the order given by
+/// the closure, and the descending view reverses them.  The closure is called once per view.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::function::FunctionOracle;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMajorDescend};
+///
+/// // the boundary matrix of an infinite path graph 0 - 1 - 2 - ..., in which the edge {i, i+1}
+/// // has key i; one line defines the whole (infinite) matrix
+/// let boundary    =   FunctionOracle::new( MajorDimension::Col, |i: usize| vec![ (i, -1), (i + 1, 1) ] );
+///
+/// assert_eq!( boundary.view_major_ascend( 3 ).collect::<Vec<_>>(), vec![ (3, -1), (4, 1) ] );
+/// assert_eq!( boundary.view_major_descend( 3 ).collect::<Vec<_>>(), vec![ (4, 1), (3, -1) ] );
+/// ```
+#[derive(Clone, Debug)]
+pub struct FunctionOracle< F, Key, Val > {
+    major_dimension:    MajorDimension,
+    function:           F,
+    phantom:            PhantomData< ( Key, Val ) >,
+}
+
+impl    < F, Key, Val >
+        FunctionOracle
+        < F, Key, Val >
+
+        where   F:      Fn( Key ) -> Vec< ( Key, Val ) >,
+{
+    /// Wrap `function`, which maps each major key to its major view.
+    pub fn new( major_dimension: MajorDimension, function: F ) -> Self {
+        FunctionOracle{ major_dimension, function, phantom: PhantomData }
+    }
+
+    /// The wrapped closure.
+    pub fn function( &self ) -> &F { &self.function }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < F, Key, Val >
+        WhichMajor
+        for
+        FunctionOracle < F, Key, Val >
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, F, Key, Val >
+        OracleMajor < 'a, Key, Key, Val >
+        for
+        FunctionOracle < F, Key, Val >
+
+        where   F:      Fn( Key ) -> Vec< ( Key, Val ) >,
+                Key:    'a + Clone,
+                Val:    'a + Clone,
+{
+    type PairMajor =   ( Key, Val );
+    type ViewMajor =   vec::IntoIter< ( Key, Val ) >;
+
+    fn view_major<'b: 'a>( &'b self, index: Key ) -> Self::ViewMajor { ( self.function )( index ).into_iter() }
+}
+
+impl     < 'a, F, Key, Val >
+        OracleMajorAscend < 'a, Key, Key, Val >
+        for
+        FunctionOracle < F, Key, Val >
+
+        where   F:      Fn( Key ) -> Vec< ( Key, Val ) >,
+                Key:    'a + Clone,
+                Val:    'a + Clone,
+{
+    type PairMajorAscend =   ( Key, Val );
+    type ViewMajorAscend =   vec::IntoIter< ( Key, Val ) >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: Key ) -> Self::ViewMajorAscend { ( self.function )( index ).into_iter() }
+}
+
+impl     < 'a, F, Key, Val >
+        OracleMajorDescend < 'a, Key, Key, Val >
+        for
+        FunctionOracle < F, Key, Val >
+
+        where   F:      Fn( Key ) -> Vec< ( Key, Val ) >,
+                Key:    'a + Clone,
+                Val:    'a + Clone,
+{
+    type PairMajorDescend =   ( Key, Val );
+    type ViewMajorDescend =   Rev< vec::IntoIter< ( Key, Val ) > >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: Key ) -> Self::ViewMajorDescend { ( self.function )( index ).into_iter().rev() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::compare::oracles_equal;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_function_oracle_agrees_with_stored_matrix() {
+        let rows        =   vec![ vec![ (0, 1), (2, 3) ], vec![], vec![ (1, -1) ] ];
+        let stored      =   VecOfVec::new( MajorDimension::Row, rows.clone() );
+        let function    =   FunctionOracle::new( MajorDimension::Row, |i: usize| rows[ i ].clone() );
+
+        assert_eq!( function.major_dimension(), MajorDimension::Row );
+        assert_eq!( oracles_equal( &stored, &function, 0 .. 3, NativeRing::<i64>::new() ), Ok(()) );
+        for i in 0 .. 3 {
+            assert!( function.view_major( i ).eq( stored.view_major( i ) ) );
+            assert!( function.view_major_descend( i ).eq( stored.view_major_descend( i ) ) );
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Dictionary-of-keys matrices, stored as hash maps of hash maps.
+//!
+//! A [`HashOfHash`] stores each major view as a `HashMap< MinKey, Val >`, inside a
+//! `HashMap< MajKey, HashMap< MinKey, Val > >`.  Unlike the other implementors in this module, it
+//! supports reading, writing and removing individual entries in (expected) constant time, and
+//! keys may be of any hashable type, so it is well suited to building a matrix incrementally.
+//! Sorted views are computed on demand, by sorting the entries of a major view, so if the matrix
+//! will be read many times once it is complete, it is usually worth converting it to a format
+//! that stores views in sorted order.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleSet,
+                                        MajorKeys,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use std::collections::HashMap;
+use std::collections::hash_map::{self, Entry};
+use std::hash::Hash;
+use std::iter::Cloned;
+use std::vec;
+
+
+/// A mutable sparse matrix, stored as a hash map from major keys to hash maps from minor keys to
+/// coefficients.
+///
+/// Explicit zeros are stored if they are set with [`set_entry`](HashOfHash::set_entry); use
+/// [`remove_entry`](HashOfHash::remove_entry) to delete an entry, or
+/// [`add_to_entry`](HashOfHash::add_to_entry), which removes entries that sum to zero.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::hash_of_hash::HashOfHash;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let ring        =   NativeRing::<i64>::new();
+/// let mut matrix  =   HashOfHash::new( MajorDimension::Row );
+/// matrix.set_entry( "b", 3, 1 );
+/// matrix.set_entry( "b", 1, 2 );
+/// matrix.add_to_entry( "b", 3, -1, &ring );    // sums to zero: removed
+/// matrix.add_to_entry( "a", 0, 5, &ring );
+///
+/// assert_eq!( matrix.get_entry( &"b", &1 ), Some( &2 ) );
+/// assert_eq!( matrix.get_entry( &"b", &3 ), None );
+/// assert_eq!( matrix.num_entries(), 2 );
+/// assert_eq!( matrix.view_major_ascend( "b" ).collect::<Vec<_>>(), vec![ (1, 2) ] );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HashOfHash< MajKey, MinKey, Val >
+    where   MajKey:     Hash + Eq,
+            MinKey:     Hash + Eq,
+{
+    major_dimension:    MajorDimension,
+    hash_of_hash:       HashMap< MajKey, HashMap< MinKey, Val > >,
+}
+
+impl    < MajKey, MinKey, Val >
+        HashOfHash
+        < MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+                MinKey:     Hash + Eq,
+{
+    /// Create an empty matrix.
+    pub fn new( major_dimension: MajorDimension ) -> Self {
+        HashOfHash{ major_dimension, hash_of_hash: HashMap::new() }
+    }
+
+    /// The coefficient in position `(major, minor)`, if an entry is stored there.
+    pub fn get_entry( &self, major: &MajKey, minor: &MinKey ) -> Option< &Val > {
+        self.hash_of_hash.get( major ).and_then( |view| view.get( minor ) )
+    }
+
+    /// Store `val` in position `(major, minor)`, and return the coefficient previously stored
+    /// there, if any.
+    pub fn set_entry( &mut self, major: MajKey, minor: MinKey, val: Val ) -> Option< Val > {
+        self.hash_of_hash.entry( major ).or_default().insert( minor, val )
+    }
+
+    /// Remove the entry in position `(major, minor)`, and return its coefficient, if any.
+    pub fn remove_entry( &mut self, major: &MajKey, minor: &MinKey ) -> Option< Val > {
+        let view        =   self.hash_of_hash.get_mut( major )?;
+        let removed     =   view.remove( minor );
+        if view.is_empty() { self.hash_of_hash.remove( major ); }
+        removed
+    }
+
+    /// Add `val` to the coefficient in position `(major, minor)`; if the sum is zero, the entry
+    /// is removed.
+    pub fn add_to_entry< RingOperator >( &mut self, major: MajKey, minor: MinKey, val: Val, ring: &RingOperator )
+        where   RingOperator:   Semiring< Val >,
+                MajKey:         Clone,
+                Val:            Clone,
+    {
+        let view        =   self.hash_of_hash.entry( major.clone() ).or_default();
+        match view.entry( minor ) {
+            Entry::Occupied( mut entry ) => {
+                let sum     =   ring.add( entry.get().clone(), val );
+                if ring.is_0( sum.clone() ) { entry.remove(); } else { entry.insert( sum ); }
+            }
+            Entry::Vacant( entry ) => {
+                if ! ring.is_0( val.clone() ) { entry.insert( val ); }
+            }
+        }
+        if view.is_empty() { self.hash_of_hash.remove( &major ); }
+    }
+
+    /// The number of stored entries.
+    pub fn num_entries( &self ) -> usize { self.hash_of_hash.values().map( |view| view.len() ).sum() }
+
+    /// Returns `true` if no entries are stored.
+    pub fn is_empty( &self ) -> bool { self.hash_of_hash.is_empty() }
+
+    /// The major keys of the nonempty major views, in no particular order.
+    pub fn major_keys( &self ) -> impl Iterator< Item = &MajKey > { self.hash_of_hash.keys() }
+
+    /// The underlying hash map.
+    pub fn hash_of_hash( &self ) -> &HashMap< MajKey, HashMap< MinKey, Val > > { &self.hash_of_hash }
+
+    /// Unwrap the underlying hash map.
+    pub fn into_hash_of_hash( self ) -> HashMap< MajKey, HashMap< MinKey, Val > > { self.hash_of_hash }
+
+    /// The entries of the major view with key `major`, in no particular order.
+    fn entries( &self, major: &MajKey ) -> Vec< ( MinKey, Val ) >
+        where   MinKey:     Clone,
+                Val:        Clone,
+    {
+        self.hash_of_hash.get( major )
+            .map( |view| view.iter().map( |( key, val )| ( key.clone(), val.clone() ) ).collect() )
+            .unwrap_or_default()
+    }
+}
+
+impl    < MajKey, MinKey, Val >
+        Extend < ( MajKey, MinKey, Val ) >
+        for
+        HashOfHash < MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+                MinKey:     Hash + Eq,
+{
+    /// Store each triplet `(major, minor, val)`, overwriting any coefficient already stored in
+    /// the same position.
+    fn extend< I: IntoIterator< Item = ( MajKey, MinKey, Val ) > >( &mut self, triplets: I ) {
+        for ( major, minor, val ) in triplets { self.set_entry( major, minor, val ); }
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < MajKey, MinKey, Val >
+        WhichMajor
+        for
+        HashOfHash < MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+                MinKey:     Hash + Eq,
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJOR KEYS
+//
+
+/// Lists the keys of the nonempty major views, in no particular order.
+impl     < 'a, MajKey, MinKey, Val >
+        MajorKeys < 'a, MajKey >
+        for
+        HashOfHash < MajKey, MinKey, Val >
+
+        where   MajKey:     'a + Clone + Hash + Eq,
+                MinKey:     'a + Hash + Eq,
+                Val:        'a,
+{
+    type MajorKeysIter = Cloned< hash_map::Keys< 'a, MajKey, HashMap< MinKey, Val > > >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { self.hash_of_hash.keys().cloned() }
+
+    fn num_major_keys( &self ) -> usize { self.hash_of_hash.len() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        HashOfHash < MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+                MinKey:     'a + Hash + Eq + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   vec::IntoIter< ( MinKey, Val ) >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { self.entries( &index ).into_iter() }
+}
+
+impl     < 'a, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        HashOfHash < MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+                MinKey:     Hash + Eq + Clone + Ord,
+                Val:        Clone,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   vec::IntoIter< ( MinKey, Val ) >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        let mut entries     =   self.entries( &index );
+        entries.sort_unstable_by( |a, b| a.0.cmp( &b.0 ) );
+        entries.into_iter()
+    }
+}
+
+impl     < 'a, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        HashOfHash < MajKey, MinKey, Val >
+
+        where   MajKey:     Hash + Eq,
+                MinKey:     Hash + Eq + Clone + Ord,
+                Val:        Clone,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   vec::IntoIter< ( MinKey, Val ) >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        let mut entries     =   self.entries( &index );
+        entries.sort_unstable_by( |a, b| b.0.cmp( &a.0 ) );
+        entries.into_iter()
+    }
+}
+
+
+
+//  WRITING
+//  ---------------------------------------------------------------------------
+
+
+impl     < MajKey, MinKey, Val >
+        OracleSet < MajKey, MinKey, Val >
+        for
+        HashOfHash < MajKey, MinKey, Val >
+
+        where   MajKey:     Clone + Hash + Eq,
+                MinKey:     Hash + Eq,
+                Val:        Clone,
+{
+    fn set_entry( &mut self, major: MajKey, minor: MinKey, val: Val ) -> Option< Val > {
+        HashOfHash::set_entry( self, major, minor, val )
+    }
+
+    fn add_to_entry< RingOperator: Semiring< Val > >( &mut self, major: MajKey, minor: MinKey, val: Val, ring: &RingOperator ) {
+        HashOfHash::add_to_entry( self, major, minor, val, ring )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::coo::Coo;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_incremental_construction_agrees_with_coo() {
+        let ring        =   NativeRing::<i64>::new();
+        let triplets    =   [ (2, 1, 1), (0, 0, 2), (2, 1, 1), (1, 1, 3), (0, 0, -2), (2, 0, 7), (1, 2, 4) ];
+
+        let mut coo     =   Coo::new( 3, 3 );
+        let mut hash    =   HashOfHash::new( MajorDimension::Row );
+        for ( row, col, val ) in triplets {
+            coo.push( row, col, val );
+            hash.add_to_entry( row, col, val, &ring );
+        }
+        let vov         =   coo.into_vec_of_vec( MajorDimension::Row, ring );
+        assert_eq!( hash.num_entries(), 4 );
+        for i in 0 .. 3 {
+            assert!( hash.view_major_ascend( i ).eq( vov.view_major_ascend( i ) ) );
+            assert!( hash.view_major_descend( i ).eq( vov.view_major_descend( i ) ) );
+            assert_eq!( hash.view_major( i ).count(), vov.view_major( i ).count() );
+        }
+
+        // overwriting and removing entries
+        assert_eq!( hash.set_entry( 2, 1, 9 ), Some( 2 ) );
+        assert_eq!( hash.remove_entry( &1, &1 ), Some( 3 ) );
+        assert_eq!( hash.remove_entry( &1, &1 ), None );
+        hash.extend( [ (0, 2, 8) ] );
+        assert_eq!( hash.view_major_ascend( 2 ).collect::< Vec< _ > >(), vec![ (0, 7), (1, 9) ] );
+        assert_eq!( hash.view_major_ascend( 0 ).collect::< Vec< _ > >(), vec![ (2, 8) ] );
+        assert_eq!( hash.major_keys().count(), 3 );
+    }
+
+    #[test]
+    fn test_major_keys_lists_nonempty_views() {
+        let mut matrix  =   HashOfHash::new( MajorDimension::Col );
+        matrix.set_entry( "x", 0, 1 );
+        matrix.set_entry( "y", 0, 1 );
+        matrix.remove_entry( &"y", &0 );
+        matrix.set_entry( "z", 2, 1 );
+
+        let mut keys: Vec< _ >  =   MajorKeys::major_keys( &matrix ).collect();
+        keys.sort();
+        assert_eq!( keys, vec![ "x", "z" ] );
+        assert_eq!( matrix.num_major_keys(), 2 );
+    }
+
+}
@@ -0,0 +1,579 @@
+//! Matrices with infinitely many rows and columns, and adaptors that make them finite.
+//!
+//! An oracle never has to store its matrix, so nothing prevents its key space from being
+//! unbounded: the identity on `0, 1, 2, ..`, the shift operator `e_i -> e_{i+1}`, or a matrix whose
+//! views are defined by a recurrence (see [`FunctionOracle`](crate::matrices::implementors::function::FunctionOracle))
+//! are all perfectly good oracles.  This module provides
+//!
+//! - [`InfiniteScalarOracle`], a scalar multiple of the identity or of a power of the shift
+//!   operator, indexed by all of `usize`;
+//! - [`Truncated`], which restricts any oracle indexed by integers to the keys `0 .. n`, reading
+//!   ascending views only as far as necessary, so that infinite ascending views can be truncated;
+//! - [`EitherOracle`], which holds one of two oracles of different types with the same keys and
+//!   coefficients, so that finite and (truncated) infinite pieces can be placed side by side in
+//!   constructions that require blocks of a single type, such as a
+//!   [`BlockMatrixOracle`](crate::matrices::implementors::block_matrix::BlockMatrixOracle).
+//!
+//! Infinite views should only be read lazily; collecting one never terminates.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::block_matrix::BlockMatrixOracle;
+//! use solar::matrices::implementors::infinite::{EitherOracle, InfiniteScalarOracle, Truncated};
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMinorAscend};
+//!
+//! // the shift operator, read lazily
+//! let shift       =   InfiniteScalarOracle::shift( 1, 1, MajorDimension::Row );
+//! assert_eq!( shift.view_major_ascend( 1_000_000 ).collect::<Vec<_>>(), vec![ (1_000_001, 1) ] );
+//! assert_eq!( shift.view_minor_ascend( 0 ).count(), 0 );
+//!
+//! // the block matrix [ [ a, I ] ], where `a` is stored and I is a truncated identity
+//! let a           =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (1, 5) ], vec![ (0, 7) ] ] );
+//! let identity    =   Truncated::new( InfiniteScalarOracle::new( 1, MajorDimension::Row ), 2, 2 );
+//! let matrix      =   BlockMatrixOracle::new( vec![ vec![ Some( EitherOracle::Left( &a ) ), Some( EitherOracle::Right( identity ) ) ] ] );
+//! assert_eq!( matrix.view_major_ascend( (0, 1) ).collect::<Vec<_>>(), vec![ ((0, 0), 7), ((1, 1), 1) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        MajorKeys,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::vector_entries::vector_entries::KeyValGet;
+use itertools::Either;
+use std::ops::Range;
+use std::option;
+
+
+//  ---------------------------------------------------------------------------
+//  INFINITE SCALAR MATRICES
+//  ---------------------------------------------------------------------------
+
+
+/// A scalar multiple of the identity, or of a power of the shift operator, with keys `0, 1, 2, ..`.
+///
+/// Major view `i` has a single entry `(i + offset, scalar)`, and minor view `j` has a single entry
+/// `(j - offset, scalar)` if `j >= offset` (and no entries otherwise).  If `offset == 0` this is
+/// the scalar matrix [`ScalarMatrixOracleUsize`](crate::matrices::implementors::scalar_matrices::ScalarMatrixOracleUsize);
+/// if `offset == 1` it is a multiple of the shift operator.  Keys that would exceed `usize::MAX`
+/// are omitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InfiniteScalarOracle< Val > {
+    scalar:             Val,
+    offset:             usize,
+    major_dimension:    MajorDimension,
+}
+
+impl < Val > InfiniteScalarOracle< Val > {
+    /// The scalar matrix with diagonal entries `scalar`.
+    pub fn new( scalar: Val, major_dimension: MajorDimension ) -> Self { InfiniteScalarOracle{ scalar, offset: 0, major_dimension } }
+
+    /// The matrix with entries `scalar` in positions `(i, i + offset)`, for every `i`.
+    pub fn shift( scalar: Val, offset: usize, major_dimension: MajorDimension ) -> Self { InfiniteScalarOracle{ scalar, offset, major_dimension } }
+
+    /// The nonzero coefficient.
+    pub fn scalar( &self ) -> &Val { &self.scalar }
+
+    /// The distance between the diagonal and the nonzero entries.
+    pub fn offset( &self ) -> usize { self.offset }
+}
+
+impl < Val: Clone > InfiniteScalarOracle< Val > {
+    /// The unique entry of major view `index`, if any.
+    fn major_entry( &self, index: usize ) -> option::IntoIter< ( usize, Val ) > {
+        index.checked_add( self.offset ).map( |minor| ( minor, self.scalar.clone() ) ).into_iter()
+    }
+
+    /// The unique entry of minor view `index`, if any.
+    fn minor_entry( &self, index: usize ) -> option::IntoIter< ( usize, Val ) > {
+        index.checked_sub( self.offset ).map( |major| ( major, self.scalar.clone() ) ).into_iter()
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRUNCATION
+//  ---------------------------------------------------------------------------
+
+
+/// The restriction of a matrix indexed by integers to the major keys `0 .. num_major` and the
+/// minor keys `0 .. num_minor`.
+///
+/// A view whose key is out of range is empty; other views drop the entries whose keys are out of
+/// range.  Ascending views stop at the first entry whose key is out of range, so the ascending
+/// views of the wrapped matrix may be infinite; unordered and descending views are read in full,
+/// so they must be finite.  Keys are not renumbered.  Unlike the wrapped matrix, a `Truncated`
+/// matrix can list its major keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Truncated< Matrix > {
+    matrix:     Matrix,
+    num_major:  usize,
+    num_minor:  usize,
+}
+
+impl < Matrix > Truncated< Matrix > {
+    /// Restrict `matrix` to the major keys `0 .. num_major` and the minor keys `0 .. num_minor`.
+    pub fn new( matrix: Matrix, num_major: usize, num_minor: usize ) -> Self { Truncated{ matrix, num_major, num_minor } }
+
+    /// The wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// The number of major keys.
+    pub fn num_major( &self ) -> usize { self.num_major }
+
+    /// The number of minor keys.
+    pub fn num_minor( &self ) -> usize { self.num_minor }
+
+    /// Unwrap the matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+
+    /// A view of the wrapped matrix, restricted to entries with keys below `bound`.
+    fn truncate< View: IntoIterator >( view: Option< View >, bound: usize, ascending: bool ) -> TruncatedView< View::IntoIter > {
+        TruncatedView{ view: view.map( |view| view.into_iter() ), bound, ascending }
+    }
+}
+
+/// A view of a [`Truncated`] matrix.
+#[derive(Clone, Debug)]
+pub struct TruncatedView< View > {
+    view:       Option< View >,
+    bound:      usize,
+    ascending:  bool,   // if true, the first entry with key >= bound ends the view
+}
+
+impl    < View >
+        Iterator
+        for
+        TruncatedView < View >
+
+        where   View:           Iterator,
+                View::Item:     KeyValGet< Key = usize >,
+{
+    type Item = View::Item;
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        loop {
+            let entry   =   self.view.as_mut()?.next()?;
+            if entry.key() < self.bound { return Some( entry ) }
+            if self.ascending { self.view = None; return None }
+        }
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  EITHER OF TWO ORACLES
+//  ---------------------------------------------------------------------------
+
+
+/// One of two matrix oracles, with the same keys and coefficients but possibly of different types.
+///
+/// Views are [`itertools::Either`] iterators.  This lets oracles of different types (e.g. a
+/// stored matrix and a truncated infinite one) serve as blocks of a single block matrix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EitherOracle< Left, Right > {
+    Left( Left ),
+    Right( Right ),
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  INFINITE SCALAR MATRICES
+//  ---------------------------------------------------------------------------
+
+
+impl < Val > WhichMajor for InfiniteScalarOracle< Val > { fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+impl     < 'a, Val >
+        OracleMajor < 'a, usize, usize, Val >
+        for
+        InfiniteScalarOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMajor =   ( usize, Val );
+    type ViewMajor =   option::IntoIter< ( usize, Val ) >;
+
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor { self.major_entry( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMajorAscend < 'a, usize, usize, Val >
+        for
+        InfiniteScalarOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMajorAscend =   ( usize, Val );
+    type ViewMajorAscend =   option::IntoIter< ( usize, Val ) >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend { self.major_entry( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMajorDescend < 'a, usize, usize, Val >
+        for
+        InfiniteScalarOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMajorDescend =   ( usize, Val );
+    type ViewMajorDescend =   option::IntoIter< ( usize, Val ) >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend { self.major_entry( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMinor < 'a, usize, usize, Val >
+        for
+        InfiniteScalarOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMinor =   ( usize, Val );
+    type ViewMinor =   option::IntoIter< ( usize, Val ) >;
+
+    fn view_minor<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinor { self.minor_entry( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMinorAscend < 'a, usize, usize, Val >
+        for
+        InfiniteScalarOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMinorAscend =   ( usize, Val );
+    type ViewMinorAscend =   option::IntoIter< ( usize, Val ) >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorAscend { self.minor_entry( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMinorDescend < 'a, usize, usize, Val >
+        for
+        InfiniteScalarOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMinorDescend =   ( usize, Val );
+    type ViewMinorDescend =   option::IntoIter< ( usize, Val ) >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorDescend { self.minor_entry( index ) }
+}
+
+
+//  TRUNCATION
+//  ---------------------------------------------------------------------------
+
+
+impl     < Matrix: WhichMajor >
+        WhichMajor
+        for
+        Truncated < Matrix >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix >
+        MajorKeys < 'a, usize >
+        for
+        Truncated < Matrix >
+{
+    type MajorKeysIter = Range< usize >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { 0 .. self.num_major }
+
+    fn num_major_keys( &self ) -> usize { self.num_major }
+}
+
+impl     < 'a, Matrix, Val >
+        OracleMajor < 'a, usize, usize, Val >
+        for
+        Truncated < Matrix >
+
+        where   Matrix:     OracleMajor< 'a, usize, usize, Val >,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   TruncatedView< < Matrix::ViewMajor as IntoIterator >::IntoIter >;
+
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor {
+        let view    =   if index < self.num_major { Some( self.matrix.view_major( index ) ) } else { None };
+        Self::truncate( view, self.num_minor, false )
+    }
+}
+
+impl     < 'a, Matrix, Val >
+        OracleMajorAscend < 'a, usize, usize, Val >
+        for
+        Truncated < Matrix >
+
+        where   Matrix:     OracleMajorAscend< 'a, usize, usize, Val >,
+{
+    type PairMajorAscend =   Matrix::PairMajorAscend;
+    type ViewMajorAscend =   TruncatedView< < Matrix::ViewMajorAscend as IntoIterator >::IntoIter >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend {
+        let view    =   if index < self.num_major { Some( self.matrix.view_major_ascend( index ) ) } else { None };
+        Self::truncate( view, self.num_minor, true )
+    }
+}
+
+impl     < 'a, Matrix, Val >
+        OracleMajorDescend < 'a, usize, usize, Val >
+        for
+        Truncated < Matrix >
+
+        where   Matrix:     OracleMajorDescend< 'a, usize, usize, Val >,
+{
+    type PairMajorDescend =   Matrix::PairMajorDescend;
+    type ViewMajorDescend =   TruncatedView< < Matrix::ViewMajorDescend as IntoIterator >::IntoIter >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend {
+        let view    =   if index < self.num_major { Some( self.matrix.view_major_descend( index ) ) } else { None };
+        Self::truncate( view, self.num_minor, false )
+    }
+}
+
+impl     < 'a, Matrix, Val >
+        OracleMinor < 'a, usize, usize, Val >
+        for
+        Truncated < Matrix >
+
+        where   Matrix:     OracleMinor< 'a, usize, usize, Val >,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   TruncatedView< < Matrix::ViewMinor as IntoIterator >::IntoIter >;
+
+    fn view_minor<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinor {
+        let view    =   if index < self.num_minor { Some( self.matrix.view_minor( index ) ) } else { None };
+        Self::truncate( view, self.num_major, false )
+    }
+}
+
+impl     < 'a, Matrix, Val >
+        OracleMinorAscend < 'a, usize, usize, Val >
+        for
+        Truncated < Matrix >
+
+        where   Matrix:     OracleMinorAscend< 'a, usize, usize, Val >,
+{
+    type PairMinorAscend =   Matrix::PairMinorAscend;
+    type ViewMinorAscend =   TruncatedView< < Matrix::ViewMinorAscend as IntoIterator >::IntoIter >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorAscend {
+        let view    =   if index < self.num_minor { Some( self.matrix.view_minor_ascend( index ) ) } else { None };
+        Self::truncate( view, self.num_major, true )
+    }
+}
+
+impl     < 'a, Matrix, Val >
+        OracleMinorDescend < 'a, usize, usize, Val >
+        for
+        Truncated < Matrix >
+
+        where   Matrix:     OracleMinorDescend< 'a, usize, usize, Val >,
+{
+    type PairMinorDescend =   Matrix::PairMinorDescend;
+    type ViewMinorDescend =   TruncatedView< < Matrix::ViewMinorDescend as IntoIterator >::IntoIter >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorDescend {
+        let view    =   if index < self.num_minor { Some( self.matrix.view_minor_descend( index ) ) } else { None };
+        Self::truncate( view, self.num_major, false )
+    }
+}
+
+
+//  EITHER OF TWO ORACLES
+//  ---------------------------------------------------------------------------
+
+
+impl     < Left: WhichMajor, Right: WhichMajor >
+        WhichMajor
+        for
+        EitherOracle < Left, Right >
+{
+    fn major_dimension( &self ) -> MajorDimension {
+        match self { EitherOracle::Left( matrix ) => matrix.major_dimension(), EitherOracle::Right( matrix ) => matrix.major_dimension() }
+    }
+}
+
+impl     < 'a, Left, Right, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        EitherOracle < Left, Right >
+
+        where   Left:       OracleMajor< 'a, MajKey, MinKey, Val >,
+                Right:      OracleMajor< 'a, MajKey, MinKey, Val, PairMajor = Left::PairMajor >,
+{
+    type PairMajor =   Left::PairMajor;
+    type ViewMajor =   Either< < Left::ViewMajor as IntoIterator >::IntoIter, < Right::ViewMajor as IntoIterator >::IntoIter >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        match self {
+            EitherOracle::Left( matrix )    =>  Either::Left( matrix.view_major( index ).into_iter() ),
+            EitherOracle::Right( matrix )   =>  Either::Right( matrix.view_major( index ).into_iter() ),
+        }
+    }
+}
+
+impl     < 'a, Left, Right, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        EitherOracle < Left, Right >
+
+        where   Left:       OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                Right:      OracleMajorAscend< 'a, MajKey, MinKey, Val, PairMajorAscend = Left::PairMajorAscend >,
+{
+    type PairMajorAscend =   Left::PairMajorAscend;
+    type ViewMajorAscend =   Either< < Left::ViewMajorAscend as IntoIterator >::IntoIter, < Right::ViewMajorAscend as IntoIterator >::IntoIter >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        match self {
+            EitherOracle::Left( matrix )    =>  Either::Left( matrix.view_major_ascend( index ).into_iter() ),
+            EitherOracle::Right( matrix )   =>  Either::Right( matrix.view_major_ascend( index ).into_iter() ),
+        }
+    }
+}
+
+impl     < 'a, Left, Right, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        EitherOracle < Left, Right >
+
+        where   Left:       OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                Right:      OracleMajorDescend< 'a, MajKey, MinKey, Val, PairMajorDescend = Left::PairMajorDescend >,
+{
+    type PairMajorDescend =   Left::PairMajorDescend;
+    type ViewMajorDescend =   Either< < Left::ViewMajorDescend as IntoIterator >::IntoIter, < Right::ViewMajorDescend as IntoIterator >::IntoIter >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        match self {
+            EitherOracle::Left( matrix )    =>  Either::Left( matrix.view_major_descend( index ).into_iter() ),
+            EitherOracle::Right( matrix )   =>  Either::Right( matrix.view_major_descend( index ).into_iter() ),
+        }
+    }
+}
+
+impl     < 'a, Left, Right, MajKey, MinKey, Val >
+        OracleMinor < 'a, MajKey, MinKey, Val >
+        for
+        EitherOracle < Left, Right >
+
+        where   Left:       OracleMinor< 'a, MajKey, MinKey, Val >,
+                Right:      OracleMinor< 'a, MajKey, MinKey, Val, PairMinor = Left::PairMinor >,
+{
+    type PairMinor =   Left::PairMinor;
+    type ViewMinor =   Either< < Left::ViewMinor as IntoIterator >::IntoIter, < Right::ViewMinor as IntoIterator >::IntoIter >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        match self {
+            EitherOracle::Left( matrix )    =>  Either::Left( matrix.view_minor( index ).into_iter() ),
+            EitherOracle::Right( matrix )   =>  Either::Right( matrix.view_minor( index ).into_iter() ),
+        }
+    }
+}
+
+impl     < 'a, Left, Right, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, MajKey, MinKey, Val >
+        for
+        EitherOracle < Left, Right >
+
+        where   Left:       OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                Right:      OracleMinorAscend< 'a, MajKey, MinKey, Val, PairMinorAscend = Left::PairMinorAscend >,
+{
+    type PairMinorAscend =   Left::PairMinorAscend;
+    type ViewMinorAscend =   Either< < Left::ViewMinorAscend as IntoIterator >::IntoIter, < Right::ViewMinorAscend as IntoIterator >::IntoIter >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        match self {
+            EitherOracle::Left( matrix )    =>  Either::Left( matrix.view_minor_ascend( index ).into_iter() ),
+            EitherOracle::Right( matrix )   =>  Either::Right( matrix.view_minor_ascend( index ).into_iter() ),
+        }
+    }
+}
+
+impl     < 'a, Left, Right, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, MajKey, MinKey, Val >
+        for
+        EitherOracle < Left, Right >
+
+        where   Left:       OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                Right:      OracleMinorDescend< 'a, MajKey, MinKey, Val, PairMinorDescend = Left::PairMinorDescend >,
+{
+    type PairMinorDescend =   Left::PairMinorDescend;
+    type ViewMinorDescend =   Either< < Left::ViewMinorDescend as IntoIterator >::IntoIter, < Right::ViewMinorDescend as IntoIterator >::IntoIter >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        match self {
+            EitherOracle::Left( matrix )    =>  Either::Left( matrix.view_minor_descend( index ).into_iter() ),
+            EitherOracle::Right( matrix )   =>  Either::Right( matrix.view_minor_descend( index ).into_iter() ),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use std::iter::{Repeat, Zip};
+    use std::ops::RangeFrom;
+
+    /// The upper triangular matrix of ones, whose ascending major views are infinite.
+    struct Ones;
+
+    impl < 'a > OracleMajorAscend< 'a, usize, usize, i64 > for Ones {
+        type PairMajorAscend = ( usize, i64 );
+        type ViewMajorAscend = Zip< RangeFrom< usize >, Repeat< i64 > >;
+        fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend { ( index .. ).zip( std::iter::repeat( 1 ) ) }
+    }
+
+    #[test]
+    fn test_truncated_infinite_views() {
+        let truncated   =   Truncated::new( Ones, 3, 4 );
+        assert_eq!( truncated.view_major_ascend( 1 ).collect::< Vec< _ > >(), vec![ (1, 1), (2, 1), (3, 1) ] );
+        assert_eq!( truncated.view_major_ascend( 3 ).count(), 0 );
+        assert_eq!( truncated.major_keys().map( |i| truncated.view_major_ascend( i ).count() ).sum::< usize >(), 4 + 3 + 2 );
+
+        // both dimensions of the shift operator, truncated to a 3 x 3 matrix
+        let shift       =   Truncated::new( InfiniteScalarOracle::shift( -1, 1, MajorDimension::Col ), 3, 3 );
+        let stored      =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (1, -1) ], vec![ (2, -1) ], vec![] ] );
+        assert_eq!( shift.major_dimension(), MajorDimension::Col );
+        for i in 0 .. 4 {
+            let expected: Vec< _ >  =   if i < 3 { stored.view_major( i ).collect() } else { vec![] };
+            assert_eq!( shift.view_major( i ).collect::< Vec< _ > >(), expected );
+            assert_eq!( shift.view_major_descend( i ).collect::< Vec< _ > >(), expected );
+            let expected: Vec< _ >  =   if i < 3 { stored.view_minor( i ).collect() } else { vec![] };
+            assert_eq!( shift.view_minor( i ).collect::< Vec< _ > >(), expected );
+            assert_eq!( shift.view_minor_ascend( i ).collect::< Vec< _ > >(), expected );
+            assert_eq!( shift.view_minor_descend( i ).collect::< Vec< _ > >(), expected );
+        }
+
+        // keys that would overflow are omitted
+        let far         =   InfiniteScalarOracle::shift( 2., 5, MajorDimension::Row );
+        assert_eq!( far.view_major( usize::MAX - 1 ).count(), 0 );
+        assert_eq!( far.view_minor_descend( usize::MAX ).collect::< Vec< _ > >(), vec![ ( usize::MAX - 5, 2. ) ] );
+    }
+
+    #[test]
+    fn test_either_oracle() {
+        let stored      =   || VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 3), (2, 4) ] ] );
+        let matrices    =   [ EitherOracle::Left( stored() ), EitherOracle::Right( Truncated::new( stored(), 1, 3 ) ) ];
+        for matrix in matrices.iter() {
+            assert_eq!( matrix.major_dimension(), MajorDimension::Row );
+            assert_eq!( matrix.view_major_descend( 0 ).collect::< Vec< _ > >(), vec![ (2, 4), (0, 3) ] );
+            assert_eq!( matrix.view_minor_ascend( 2 ).collect::< Vec< _ > >(), vec![ (0, 4) ] );
+        }
+    }
+}
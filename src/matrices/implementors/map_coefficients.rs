@@ -0,0 +1,287 @@
+//! Change the coefficients of a matrix oracle, one entry at a time.
+//!
+//! A [`MapCoefficients`] wraps a matrix oracle together with a function on coefficients, and
+//! applies the function to every coefficient it returns.  The function is usually a ring
+//! homomorphism: reducing an integer boundary matrix modulo a prime, for example, or embedding
+//! integers in the rationals.  Any [`RingHomomorphism`] can be used, including the ones in
+//! [`rings::homomorphism`](crate::rings::homomorphism) and any closure.  Nothing is copied.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::map_coefficients::MapCoefficients;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+//!
+//! // an integer matrix, reduced modulo 2
+//! let integer     =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (0, -1i64), (1, 2) ], vec![ (1, 3) ] ] );
+//! let mod_two     =   MapCoefficients::new( &integer, |val: i64| val.rem_euclid( 2 ) == 1 );
+//!
+//! assert_eq!( mod_two.view_major( 0 ).collect::<Vec<_>>(), vec![ (0, true), (1, false) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::homomorphism::RingHomomorphism;
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::marker::PhantomData;
+
+
+/// The matrix obtained from `matrix` by replacing each coefficient `val` with `function.apply( val )`.
+///
+/// Views have the same keys, in the same order, as the views of the wrapped matrix, so ascending
+/// (respectively, descending) views remain ascending (respectively, descending).  Zeros are not
+/// dropped: if `function` sends a nonzero coefficient to zero (as reduction modulo a prime often
+/// does), views contain an explicit zero entry in its place (use
+/// [`drop_zeros`](crate::vectors::vector_transforms::Transforms::drop_zeros) to remove them).
+///
+/// The type parameter `ValIn` is the type of the coefficients of the wrapped matrix; it is usually
+/// inferred.
+pub struct MapCoefficients< Matrix, F, ValIn > {
+    matrix:     Matrix,
+    function:   F,
+    phantom:    PhantomData< ValIn >,
+}
+
+impl    < Matrix, F, ValIn >
+        MapCoefficients
+        < Matrix, F, ValIn >
+{
+    /// Apply `function` to the coefficients of `matrix`.
+    pub fn new( matrix: Matrix, function: F ) -> Self { MapCoefficients{ matrix, function, phantom: PhantomData } }
+
+    /// The wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// The function applied to coefficients.
+    pub fn function( &self ) -> &F { &self.function }
+
+    /// Unwrap the matrix and the function.
+    pub fn into_parts( self ) -> ( Matrix, F ) { ( self.matrix, self.function ) }
+}
+
+// implemented by hand, since the derived implementations would require `ValIn: Clone + Debug`
+impl < Matrix: Clone, F: Clone, ValIn > Clone for MapCoefficients< Matrix, F, ValIn > {
+    fn clone( &self ) -> Self { MapCoefficients::new( self.matrix.clone(), self.function.clone() ) }
+}
+
+impl < Matrix: std::fmt::Debug, F, ValIn > std::fmt::Debug for MapCoefficients< Matrix, F, ValIn > {
+    fn fmt( &self, f: &mut std::fmt::Formatter< '_ > ) -> std::fmt::Result {
+        f.debug_struct( "MapCoefficients" ).field( "matrix", &self.matrix ).finish_non_exhaustive()
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A view of a [`MapCoefficients`]: the entries of a view of the wrapped matrix, with `function`
+/// applied to each coefficient.
+#[derive(Clone, Debug)]
+pub struct MapCoefficientsView< 'a, View, F, ValOut > {
+    view:       View,
+    function:   &'a F,
+    phantom:    PhantomData< ValOut >,
+}
+
+impl    < 'a, View, F, ValOut >
+        MapCoefficientsView
+        < 'a, View, F, ValOut >
+{
+    fn new( view: View, function: &'a F ) -> Self { MapCoefficientsView{ view, function, phantom: PhantomData } }
+}
+
+impl    < 'a, View, F, ValOut >
+        Iterator
+        for
+        MapCoefficientsView < 'a, View, F, ValOut >
+
+        where   View:           Iterator,
+                View::Item:     KeyValGet,
+                F:              RingHomomorphism< < View::Item as KeyValGet >::Val, ValOut >,
+{
+    type Item = ( < View::Item as KeyValGet >::Key, ValOut );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        self.view.next().map( |entry| ( entry.key(), self.function.apply( entry.val() ) ) )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.view.size_hint() }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Matrix, F, ValIn >
+        WhichMajor
+        for
+        MapCoefficients < Matrix, F, ValIn >
+
+        where   Matrix:     WhichMajor,
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, F, MajKey, MinKey, ValIn, ValOut >
+        OracleMajor < 'a, MajKey, MinKey, ValOut >
+        for
+        MapCoefficients < Matrix, F, ValIn >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, ValIn >,
+                MinKey:     Clone,
+                ValOut:     'a + Clone,
+                F:          'a + RingHomomorphism< ValIn, ValOut >,
+{
+    type PairMajor =   ( MinKey, ValOut );
+    type ViewMajor =   MapCoefficientsView< 'a, < Matrix::ViewMajor as IntoIterator >::IntoIter, F, ValOut >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        MapCoefficientsView::new( self.matrix.view_major( index ).into_iter(), &self.function )
+    }
+}
+
+impl     < 'a, Matrix, F, MajKey, MinKey, ValIn, ValOut >
+        OracleMajorAscend < 'a, MajKey, MinKey, ValOut >
+        for
+        MapCoefficients < Matrix, F, ValIn >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, ValIn >,
+                MinKey:     Clone,
+                ValOut:     'a + Clone,
+                F:          'a + RingHomomorphism< ValIn, ValOut >,
+{
+    type PairMajorAscend =   ( MinKey, ValOut );
+    type ViewMajorAscend =   MapCoefficientsView< 'a, < Matrix::ViewMajorAscend as IntoIterator >::IntoIter, F, ValOut >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        MapCoefficientsView::new( self.matrix.view_major_ascend( index ).into_iter(), &self.function )
+    }
+}
+
+impl     < 'a, Matrix, F, MajKey, MinKey, ValIn, ValOut >
+        OracleMajorDescend < 'a, MajKey, MinKey, ValOut >
+        for
+        MapCoefficients < Matrix, F, ValIn >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, ValIn >,
+                MinKey:     Clone,
+                ValOut:     'a + Clone,
+                F:          'a + RingHomomorphism< ValIn, ValOut >,
+{
+    type PairMajorDescend =   ( MinKey, ValOut );
+    type ViewMajorDescend =   MapCoefficientsView< 'a, < Matrix::ViewMajorDescend as IntoIterator >::IntoIter, F, ValOut >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        MapCoefficientsView::new( self.matrix.view_major_descend( index ).into_iter(), &self.function )
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, F, MajKey, MinKey, ValIn, ValOut >
+        OracleMinor < 'a, MajKey, MinKey, ValOut >
+        for
+        MapCoefficients < Matrix, F, ValIn >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, ValIn >,
+                MinKey:     Clone,
+                ValOut:     'a + Clone,
+                F:          'a + RingHomomorphism< ValIn, ValOut >,
+{
+    type PairMinor =   ( MinKey, ValOut );
+    type ViewMinor =   MapCoefficientsView< 'a, < Matrix::ViewMinor as IntoIterator >::IntoIter, F, ValOut >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        MapCoefficientsView::new( self.matrix.view_minor( index ).into_iter(), &self.function )
+    }
+}
+
+impl     < 'a, Matrix, F, MajKey, MinKey, ValIn, ValOut >
+        OracleMinorAscend < 'a, MajKey, MinKey, ValOut >
+        for
+        MapCoefficients < Matrix, F, ValIn >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, ValIn >,
+                MinKey:     Clone,
+                ValOut:     'a + Clone,
+                F:          'a + RingHomomorphism< ValIn, ValOut >,
+{
+    type PairMinorAscend =   ( MinKey, ValOut );
+    type ViewMinorAscend =   MapCoefficientsView< 'a, < Matrix::ViewMinorAscend as IntoIterator >::IntoIter, F, ValOut >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        MapCoefficientsView::new( self.matrix.view_minor_ascend( index ).into_iter(), &self.function )
+    }
+}
+
+impl     < 'a, Matrix, F, MajKey, MinKey, ValIn, ValOut >
+        OracleMinorDescend < 'a, MajKey, MinKey, ValOut >
+        for
+        MapCoefficients < Matrix, F, ValIn >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, ValIn >,
+                MinKey:     Clone,
+                ValOut:     'a + Clone,
+                F:          'a + RingHomomorphism< ValIn, ValOut >,
+{
+    type PairMinorDescend =   ( MinKey, ValOut );
+    type ViewMinorDescend =   MapCoefficientsView< 'a, < Matrix::ViewMinorDescend as IntoIterator >::IntoIter, F, ValOut >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        MapCoefficientsView::new( self.matrix.view_minor_descend( index ).into_iter(), &self.function )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::csc::Csc;
+    use crate::rings::field_prime::GF2;
+    use crate::rings::homomorphism::ReduceModPrime;
+    use crate::vectors::vector_transforms::Transforms;
+
+    #[test]
+    fn test_reduction_mod_two() {
+        let matrix      =   Csc::from_triplets( 2, 3, vec![ (0, 0, 1i64), (1, 0, -2), (0, 1, 4), (1, 2, -3) ] );
+        let mod_two     =   MapCoefficients::new( matrix, |val: i64| val.rem_euclid( 2 ) == 1 );
+        assert_eq!( mod_two.major_dimension(), MajorDimension::Col );
+
+        assert_eq!( mod_two.view_major( 0 ).collect::< Vec< _ > >(), vec![ (0, true), (1, false) ] );
+        assert_eq!( mod_two.view_major_ascend( 2 ).collect::< Vec< _ > >(), vec![ (1, true) ] );
+        assert_eq!( mod_two.view_major_descend( 0 ).collect::< Vec< _ > >(), vec![ (1, false), (0, true) ] );
+        assert_eq!( mod_two.view_minor( 1 ).collect::< Vec< _ > >(), vec![ (0, false), (2, true) ] );
+        assert_eq!( mod_two.view_minor_ascend( 0 ).collect::< Vec< _ > >(), vec![ (0, true), (1, false) ] );
+        assert_eq!( mod_two.view_minor_descend( 0 ).collect::< Vec< _ > >(), vec![ (1, false), (0, true) ] );
+
+        // explicit zeros can be dropped afterwards
+        assert_eq!( mod_two.view_major( 0 ).drop_zeros( GF2::new() ).collect::< Vec< _ > >(), vec![ (0, true) ] );
+
+        // named homomorphisms work as well as closures
+        let mod_three   =   MapCoefficients::new( mod_two.into_parts().0, ReduceModPrime::new( 3 ) );
+        assert_eq!( mod_three.view_major( 0 ).collect::< Vec< _ > >(), vec![ (0, 1u64), (1, 1) ] );
+        assert_eq!( mod_three.view_minor_descend( 1 ).collect::< Vec< _ > >(), vec![ (2, 0u64), (0, 1) ] );
+    }
+}
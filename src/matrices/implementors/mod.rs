@@ -1,4 +1,33 @@
 //! Some objects that implement matrix oracle traits.
 
 pub mod scalar_matrices;
+pub mod infinite;
 pub mod vec_of_vec;
+pub mod vec_of_vec_simple;
+pub mod vec_of_csvec;
+pub mod compressed_sparse;
+pub mod csc;
+pub mod coo;
+pub mod hash_of_hash;
+pub mod function;
+pub mod view_recorder;
+pub mod cached;
+pub mod ascend_descend;
+pub mod sorted_views;
+pub mod dense;
+pub mod bit_matrix;
+pub mod diagonal;
+pub mod product;
+pub mod permutation;
+pub mod reindexed;
+pub mod submatrix;
+pub mod filtered;
+pub mod sum;
+pub mod scaled;
+pub mod map_coefficients;
+pub mod direct_sum;
+pub mod block_matrix;
+pub mod conversions;
+pub mod row_col;
+#[cfg(feature = "nalgebra-sparse")]
+pub mod nalgebra_interop;
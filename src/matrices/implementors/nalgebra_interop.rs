@@ -0,0 +1,150 @@
+//! Conversions to and from the sparse matrices of the `nalgebra-sparse` crate.
+//!
+//! Available with the `nalgebra-sparse` feature.  Coordinate matrices convert both ways with
+//! `From`, since both [`Coo`] and `CooMatrix` store their shape and allow repeated positions:
+//!
+//! | from                    | to                      |
+//! |-------------------------|-------------------------|
+//! | `Coo< T >`              | `CooMatrix< T >`        |
+//! | `CooMatrix< T >`        | `Coo< T >`              |
+//! | `CsrMatrix< T >`        | `VecCsv< usize, T >` (row-major)    |
+//! | `CscMatrix< T >`        | `VecCsv< usize, T >` (column-major) |
+//!
+//! A [`VecCsv`] does not store its number of minor views, and its views need not be sorted, so
+//! [`VecCsv::into_csr_matrix`] and [`VecCsv::into_csc_matrix`] take the missing dimension and
+//! return the error reported by `nalgebra-sparse` if the data are not a valid matrix.  To convert
+//! a [`VecOfVec`](crate::matrices::implementors::vec_of_vec::VecOfVec), first convert it to a
+//! `VecCsv` (see the [conversions](crate::matrices::implementors::conversions) module).
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::vec_of_csvec::VecCsv;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+//! use nalgebra_sparse::CsrMatrix;
+//!
+//! let matrix  =   VecCsv::from_triplets( MajorDimension::Row, 2, vec![ (0, 1, 2.), (1, 0, 3.) ] );
+//! let csr     =   matrix.clone().into_csr_matrix( 2 ).unwrap();
+//! assert_eq!( csr.get_entry( 1, 0 ).unwrap().into_value(), 3. );
+//!
+//! let back    =   VecCsv::from( csr );
+//! assert_eq!( back, matrix );
+//! ```
+
+use crate::matrices::implementors::coo::Coo;
+use crate::matrices::implementors::vec_of_csvec::VecCsv;
+use crate::matrices::matrix_oracle::{MajorDimension, WhichMajor};
+use ::nalgebra_sparse::{CooMatrix, CscMatrix, CsrMatrix, SparseFormatError};
+
+
+//  ---------------------------------------------------------------------------
+//  COORDINATE MATRICES
+//  ---------------------------------------------------------------------------
+
+
+impl    < T >
+        From < Coo< T > >
+        for
+        CooMatrix < T >
+{
+    fn from( matrix: Coo< T > ) -> Self {
+        let mut coo     =   CooMatrix::new( matrix.num_rows(), matrix.num_cols() );
+        for ( row, col, val ) in matrix.into_triplets() { coo.push( row, col, val ) }
+        coo
+    }
+}
+
+impl    < T >
+        From < CooMatrix< T > >
+        for
+        Coo < T >
+{
+    fn from( matrix: CooMatrix< T > ) -> Self {
+        let mut coo     =   Coo::with_capacity( matrix.nrows(), matrix.ncols(), matrix.nnz() );
+        let ( rows, cols, vals )    =   matrix.disassemble();
+        for ( ( row, col ), val ) in rows.into_iter().zip( cols ).zip( vals ) { coo.push( row, col, val ) }
+        coo
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  COMPRESSED MATRICES
+//  ---------------------------------------------------------------------------
+
+
+impl    < T >
+        From < CsrMatrix< T > >
+        for
+        VecCsv < usize, T >
+{
+    fn from( matrix: CsrMatrix< T > ) -> Self {
+        let ( row_offsets, col_indices, values )    =   matrix.disassemble();
+        VecCsv::new( MajorDimension::Row, row_offsets, col_indices, values )
+    }
+}
+
+impl    < T >
+        From < CscMatrix< T > >
+        for
+        VecCsv < usize, T >
+{
+    fn from( matrix: CscMatrix< T > ) -> Self {
+        let ( col_offsets, row_indices, values )    =   matrix.disassemble();
+        VecCsv::new( MajorDimension::Col, col_offsets, row_indices, values )
+    }
+}
+
+impl < T > VecCsv< usize, T > {
+
+    /// Convert a row-major matrix with `num_cols` columns to a `CsrMatrix`.
+    ///
+    /// Returns an error if a column index is `>= num_cols`, or if the entries of some row are not
+    /// sorted in strictly ascending order of column.  Panics if the matrix is column-major.
+    pub fn into_csr_matrix( self, num_cols: usize ) -> Result< CsrMatrix< T >, SparseFormatError > {
+        assert!( self.major_dimension() == MajorDimension::Row, "only a row-major matrix converts to a CsrMatrix" );
+        let num_rows    =   self.num_major();
+        let ( _, major_ptr, min_ind, snz_val )  =   self.into_raw_parts();
+        CsrMatrix::try_from_csr_data( num_rows, num_cols, major_ptr, min_ind, snz_val )
+    }
+
+    /// Convert a column-major matrix with `num_rows` rows to a `CscMatrix`.
+    ///
+    /// Returns an error if a row index is `>= num_rows`, or if the entries of some column are not
+    /// sorted in strictly ascending order of row.  Panics if the matrix is row-major.
+    pub fn into_csc_matrix( self, num_rows: usize ) -> Result< CscMatrix< T >, SparseFormatError > {
+        assert!( self.major_dimension() == MajorDimension::Col, "only a column-major matrix converts to a CscMatrix" );
+        let num_cols    =   self.num_major();
+        let ( _, major_ptr, min_ind, snz_val )  =   self.into_raw_parts();
+        CscMatrix::try_from_csc_data( num_rows, num_cols, major_ptr, min_ind, snz_val )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_round_trips() {
+        let mut coo     =   Coo::new( 3, 2 );
+        for ( row, col, val ) in [ (2, 1, 1), (0, 0, 2), (2, 1, 4), (1, 0, -3) ] { coo.push( row, col, val ) }
+        let back        =   Coo::from( CooMatrix::from( coo.clone() ) );
+        assert_eq!( back, coo );
+
+        // compressed formats, in both major dimensions
+        let ring        =   NativeRing::<i64>::new();
+        let csr         =   coo.clone().into_vec_csv( MajorDimension::Row, ring.clone() );
+        assert_eq!( VecCsv::from( csr.clone().into_csr_matrix( 2 ).unwrap() ), csr );
+        let csc         =   coo.into_vec_csv( MajorDimension::Col, ring );
+        let nalgebra    =   csc.clone().into_csc_matrix( 3 ).unwrap();
+        assert_eq!( nalgebra.get_entry( 2, 1 ).unwrap().into_value(), 5 );
+        assert_eq!( VecCsv::from( nalgebra ), csc );
+
+        // the missing dimension is checked
+        assert!( csr.into_csr_matrix( 1 ).is_err() );
+    }
+}
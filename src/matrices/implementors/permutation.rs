@@ -0,0 +1,251 @@
+//! Permutation matrices.
+//!
+//! A [`PermutationMatrixOracle`] is built from an old-to-new index vector, in the same format
+//! used by [`sort_perm`](crate::utilities::indexing_and_bijection::sort_perm) and
+//! [`inverse_perm`](crate::utilities::indexing_and_bijection::inverse_perm).  Every view contains
+//! exactly one entry, so the matrix can be composed lazily with other oracles (e.g. with a
+//! [`ProductMatrixOracle`](crate::matrices::implementors::product::ProductMatrixOracle)) without
+//! ever being stored as a sparse matrix.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use crate::utilities::indexing_and_bijection::inverse_perm;
+use std::iter;
+
+
+/// The permutation matrix `P` with `P[ old_to_new[ j ], j ] = 1` for every `j`.
+///
+/// Multiplying a column vector by `P` moves the entry in position `j` to position
+/// `old_to_new[ j ]`.  Row `i` of `P` is `[ (new_to_old[ i ], 1) ]` and column `j` is
+/// `[ (old_to_new[ j ], 1) ]`; whether these are major or minor views depends on the major
+/// dimension.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::permutation::PermutationMatrixOracle;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor, OracleMinor};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// // send 0 -> 2, 1 -> 0, 2 -> 1
+/// let perm    =   PermutationMatrixOracle::new( MajorDimension::Col, vec![ 2, 0, 1 ], NativeRing::<i64>::new() );
+///
+/// assert_eq!( perm.view_major( 0 ).collect::<Vec<_>>(), vec![ (2, 1) ] ); // column 0
+/// assert_eq!( perm.view_minor( 0 ).collect::<Vec<_>>(), vec![ (1, 1) ] ); // row 0
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PermutationMatrixOracle< Val > {
+    major_dimension:    MajorDimension,
+    old_to_new:         Vec< usize >,
+    new_to_old:         Vec< usize >,
+    one:                Val,
+}
+
+impl    < Val >
+        PermutationMatrixOracle
+        < Val >
+{
+    /// Create the permutation matrix of `old_to_new`; the ring operator supplies the coefficient
+    /// `1`.
+    ///
+    /// Panics if `old_to_new` is not a permutation of `0 .. old_to_new.len()`.
+    pub fn new< RingOperator >( major_dimension: MajorDimension, old_to_new: Vec< usize >, _ring: RingOperator ) -> Self
+        where   RingOperator:   Semiring< Val >,
+    {
+        let mut seen    =   vec![ false; old_to_new.len() ];
+        for new in old_to_new.iter() {
+            assert!( *new < seen.len() && ! seen[ *new ], "the index vector must be a permutation of 0 .. n" );
+            seen[ *new ] = true;
+        }
+        let new_to_old  =   inverse_perm( &old_to_new );
+        PermutationMatrixOracle{ major_dimension, old_to_new, new_to_old, one: RingOperator::one() }
+    }
+
+    /// The number of rows (equivalently, of columns).
+    pub fn dim( &self ) -> usize { self.old_to_new.len() }
+
+    /// The old-to-new index vector.
+    pub fn old_to_new( &self ) -> &Vec< usize > { &self.old_to_new }
+
+    /// The new-to-old index vector (the inverse permutation).
+    pub fn new_to_old( &self ) -> &Vec< usize > { &self.new_to_old }
+
+    /// The inverse permutation matrix, which is also the transpose.  The major dimension is
+    /// unchanged.
+    pub fn inverse( &self ) -> Self
+        where   Val:    Clone,
+    {
+        PermutationMatrixOracle{
+            major_dimension:    self.major_dimension.clone(),
+            old_to_new:         self.new_to_old.clone(),
+            new_to_old:         self.old_to_new.clone(),
+            one:                self.one.clone(),
+        }
+    }
+
+    /// Row `index`.
+    fn row( &self, index: usize ) -> iter::Once< (usize, Val) >
+        where   Val:    Clone,
+    { iter::once( ( self.new_to_old[ index ], self.one.clone() ) ) }
+
+    /// Column `index`.
+    fn col( &self, index: usize ) -> iter::Once< (usize, Val) >
+        where   Val:    Clone,
+    { iter::once( ( self.old_to_new[ index ], self.one.clone() ) ) }
+
+    fn major( &self, index: usize ) -> iter::Once< (usize, Val) >
+        where   Val:    Clone,
+    {
+        match self.major_dimension { MajorDimension::Row => self.row( index ), MajorDimension::Col => self.col( index ) }
+    }
+
+    fn minor( &self, index: usize ) -> iter::Once< (usize, Val) >
+        where   Val:    Clone,
+    {
+        match self.major_dimension { MajorDimension::Row => self.col( index ), MajorDimension::Col => self.row( index ) }
+    }
+}
+
+
+//  ---------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Val >
+        WhichMajor
+        for
+        PermutationMatrixOracle < Val >
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Val >
+        OracleMajor < 'a, usize, usize, Val >
+        for
+        PermutationMatrixOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMajor =   ( usize, Val );
+    type ViewMajor =   iter::Once< ( usize, Val ) >;
+
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor { self.major( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMajorAscend < 'a, usize, usize, Val >
+        for
+        PermutationMatrixOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMajorAscend =   ( usize, Val );
+    type ViewMajorAscend =   iter::Once< ( usize, Val ) >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend { self.major( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMajorDescend < 'a, usize, usize, Val >
+        for
+        PermutationMatrixOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMajorDescend =   ( usize, Val );
+    type ViewMajorDescend =   iter::Once< ( usize, Val ) >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend { self.major( index ) }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Val >
+        OracleMinor < 'a, usize, usize, Val >
+        for
+        PermutationMatrixOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMinor =   ( usize, Val );
+    type ViewMinor =   iter::Once< ( usize, Val ) >;
+
+    fn view_minor<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinor { self.minor( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMinorAscend < 'a, usize, usize, Val >
+        for
+        PermutationMatrixOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMinorAscend =   ( usize, Val );
+    type ViewMinorAscend =   iter::Once< ( usize, Val ) >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorAscend { self.minor( index ) }
+}
+
+impl     < 'a, Val >
+        OracleMinorDescend < 'a, usize, usize, Val >
+        for
+        PermutationMatrixOracle < Val >
+
+        where   Val:    'a + Clone,
+{
+    type PairMinorDescend =   ( usize, Val );
+    type ViewMinorDescend =   iter::Once< ( usize, Val ) >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMinorDescend { self.minor( index ) }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::product::ProductMatrixOracle;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_permuting_rows_lazily() {
+        let ring        =   NativeRing::<i64>::new();
+        let matrix      =   VecOfVec::new(
+                                MajorDimension::Row,
+                                vec![ vec![ (0, 1) ], vec![ (1, 2) ], vec![ (0, 3), (2, 3) ] ],
+                            );
+        // P * M moves row j of M to row old_to_new[ j ]
+        let perm        =   PermutationMatrixOracle::new( MajorDimension::Row, vec![ 1, 2, 0 ], ring.clone() );
+        let product     =   ProductMatrixOracle::new( &perm, &matrix, ring.clone() );
+        for old in 0 .. 3 {
+            let new     =   perm.old_to_new()[ old ];
+            assert!( product.view_major( new ).eq( matrix.view_major( old ) ) );
+        }
+
+        // the inverse undoes the permutation
+        let inverse     =   perm.inverse();
+        let identity    =   ProductMatrixOracle::new( &perm, &inverse, ring );
+        for i in 0 .. 3 {
+            assert_eq!( identity.view_major( i ).collect::< Vec< _ > >(), vec![ (i, 1) ] );
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Lazy products of matrix oracles.
+//!
+//! A [`ProductMatrixOracle`] wraps two oracles and computes the major views of their product on
+//! demand; nothing is stored except the two factors.
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use crate::utilities::workspace::Workspace;
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::marker::PhantomData;
+
+
+/// The product of two matrix oracles, computed lazily one major view at a time.
+///
+/// Major view `i` of the product is the linear combination
+///
+/// `sum over (k, c) in first.view_major( i ) of c * second.view_major( k )`.
+///
+/// If both factors are row-major this is the matrix product `first * second`; if both are
+/// column-major it is `second * first`.  Views of the product are sorted in ascending order of
+/// key, have no repeated keys, and contain no zero entries.
+///
+/// The type parameter `MidKey` is the type of the keys shared by the two factors (the minor keys
+/// of `first` and the major keys of `second`).
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::product::ProductMatrixOracle;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// // [ 1  1 ]   [ 1  0 ]   [ 2  -1 ]
+/// // [ 0  1 ] * [ 1 -1 ] = [ 1  -1 ]
+/// let a       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (1, 1) ], vec![ (1, 1) ] ] );
+/// let b       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1) ], vec![ (0, 1), (1, -1) ] ] );
+/// let product =   ProductMatrixOracle::new( &a, &b, NativeRing::<i64>::new() );
+///
+/// assert_eq!( product.view_major( 0 ).collect::<Vec<_>>(), vec![ (0, 2), (1, -1) ] );
+/// assert_eq!( product.view_major( 1 ).collect::<Vec<_>>(), vec![ (0, 1), (1, -1) ] );
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProductMatrixOracle< First, Second, MidKey, RingOperator > {
+    first:      First,
+    second:     Second,
+    ring:       RingOperator,
+    phantom:    PhantomData< MidKey >,
+}
+
+impl    < First, Second, MidKey, RingOperator >
+        ProductMatrixOracle
+        < First, Second, MidKey, RingOperator >
+{
+    /// Create the lazy product of `first` and `second`.
+    pub fn new( first: First, second: Second, ring: RingOperator ) -> Self {
+        ProductMatrixOracle{ first, second, ring, phantom: PhantomData }
+    }
+
+    /// The first factor.
+    pub fn first( &self ) -> &First { &self.first }
+
+    /// The second factor.
+    pub fn second( &self ) -> &Second { &self.second }
+}
+
+/// Compute the linear combination `sum over (k, c) in first.view_major( index ) of c * second.view_major( k )`.
+///
+/// This is major view `index` of the [`ProductMatrixOracle`] of `first` and `second`.  It is
+/// exposed as a free function so that the product can be computed without constructing (and
+/// borrowing) a product oracle, e.g. inside a function that only holds references to the factors.
+/// The output is sorted in ascending order of key, has no repeated keys, and contains no zeros.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::product::multiply_major_view;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use solar::rings::field_prime::GF2;
+///
+/// let a       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, true), (1, true) ] ] );
+/// let b       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, true), (1, true) ], vec![ (1, true) ] ] );
+///
+/// assert_eq!( multiply_major_view( &a, &b, 0, &GF2::new() ), vec![ (0, true) ] );
+/// ```
+pub fn multiply_major_view< 'a, First, Second, MajKey, MidKey, MinKey, Val, RingOperator >(
+            first:      &'a First,
+            second:     &'a Second,
+            index:      MajKey,
+            ring:       &RingOperator,
+        )
+        ->
+        Vec< (MinKey, Val) >
+
+    where   First:          OracleMajor< 'a, MajKey, MidKey, Val >,
+            Second:         OracleMajor< 'a, MidKey, MinKey, Val >,
+            RingOperator:   Semiring< Val >,
+            MinKey:         PartialOrd,
+            Val:            Clone,
+{
+    multiply_major_view_with_workspace( first, second, index, ring, &mut Workspace::new() )
+}
+
+/// Same as [`multiply_major_view`], but uses the scratch space in `workspace` rather than
+/// allocating its own.
+///
+/// Only the output vector is allocated, so computing many views with the same workspace (see
+/// [`with_thread_workspace`](crate::utilities::workspace::with_thread_workspace)) avoids most
+/// allocation.
+pub fn multiply_major_view_with_workspace< 'a, First, Second, MajKey, MidKey, MinKey, Val, RingOperator >(
+            first:      &'a First,
+            second:     &'a Second,
+            index:      MajKey,
+            ring:       &RingOperator,
+            workspace:  &mut Workspace< MinKey, Val >,
+        )
+        ->
+        Vec< (MinKey, Val) >
+
+    where   First:          OracleMajor< 'a, MajKey, MidKey, Val >,
+            Second:         OracleMajor< 'a, MidKey, MinKey, Val >,
+            RingOperator:   Semiring< Val >,
+            MinKey:         PartialOrd,
+            Val:            Clone,
+{
+    let summands    =   &mut workspace.entries;
+    summands.clear();
+    for outer in first.view_major( index ) {
+        let scalar      =   outer.val();
+        for inner in second.view_major( outer.key() ) {
+            summands.push( ( inner.key(), ring.multiply( inner.val(), scalar.clone() ) ) );
+        }
+    }
+    summands.sort_by( |a, b| a.0.partial_cmp( &b.0 ).expect( "minor keys must be comparable" ) );
+
+    let mut gathered: Vec< (MinKey, Val) >  =   Vec::with_capacity( summands.len() );
+    for (key, val) in summands.drain( .. ) {
+        if let Some( last ) = gathered.last_mut() {
+            if last.0 == key { last.1 = ring.add( last.1.clone(), val ); continue }
+        }
+        gathered.push( (key, val) );
+    }
+    gathered.retain( |x| ! ring.is_0( x.1.clone() ) );
+    gathered
+}
+
+
+//  ---------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------
+
+
+impl     < First, Second, MidKey, RingOperator >
+        WhichMajor
+        for
+        ProductMatrixOracle < First, Second, MidKey, RingOperator >
+
+        where   First:  WhichMajor,
+{ fn major_dimension( &self ) -> MajorDimension { self.first.major_dimension() } }
+
+impl     < 'a, First, Second, MajKey, MidKey, MinKey, Val, RingOperator >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        ProductMatrixOracle < First, Second, MidKey, RingOperator >
+
+        where   First:          OracleMajor< 'a, MajKey, MidKey, Val >,
+                Second:         OracleMajor< 'a, MidKey, MinKey, Val >,
+                RingOperator:   Semiring< Val >,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   std::vec::IntoIter< ( MinKey, Val ) >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { multiply_major_view( &self.first, &self.second, index, &self.ring ).into_iter() }
+}
+
+impl     < 'a, First, Second, MajKey, MidKey, MinKey, Val, RingOperator >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        ProductMatrixOracle < First, Second, MidKey, RingOperator >
+
+        where   First:          OracleMajor< 'a, MajKey, MidKey, Val >,
+                Second:         OracleMajor< 'a, MidKey, MinKey, Val >,
+                RingOperator:   Semiring< Val >,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   std::vec::IntoIter< ( MinKey, Val ) >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend { multiply_major_view( &self.first, &self.second, index, &self.ring ).into_iter() }
+}
@@ -0,0 +1,305 @@
+//! Matrix oracles indexed by arbitrary keys, via bijections with integer ordinals.
+//!
+//! Many algorithms store matrices with integer keys `0, 1, 2, ..`, while users think in terms of
+//! other keys (e.g. simplices).  A [`ReindexedOracle`] wraps an oracle with integer keys, together
+//! with a [`BiMapSequential`] for each dimension, and translates in both directions: views are
+//! requested with user-level keys, and the entries of each view carry user-level keys.  Nothing
+//! is copied.
+//!
+//! Sorted views stay sorted with respect to ordinals; that is, entries appear in the order in
+//! which their keys appear in the bimap, which need not agree with any `Ord` implementation of
+//! the keys themselves.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::reindexed::ReindexedOracle;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMinorAscend};
+//! use solar::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets;
+//! use solar::utilities::sequences_and_ordinals::BiMapSequential;
+//! use solar::rings::ring_native::NativeRing;
+//!
+//! let bimap       =   BiMapSequential::from_vec( vec![ vec![0], vec![1], vec![2], vec![0,1], vec![0,2], vec![1,2] ] );
+//! let boundary    =   VecOfVec::new(
+//!                         MajorDimension::Col,
+//!                         boundary_matrix_from_complex_facets( &bimap, NativeRing::<i64>::new() ),
+//!                     );
+//! let boundary    =   ReindexedOracle::new( &boundary, &bimap, &bimap );
+//!
+//! // the boundary of an edge, and the coboundary of a vertex, looked up by simplex
+//! assert_eq!( boundary.view_major_ascend( vec![0,2] ).collect::<Vec<_>>(), vec![ (vec![0], -1), (vec![2], 1) ] );
+//! assert_eq!( boundary.view_minor_ascend( vec![0] ).collect::<Vec<_>>(), vec![ (vec![0,1], -1), (vec![0,2], -1) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::utilities::sequences_and_ordinals::BiMapSequential;
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::hash::Hash;
+
+
+/// A matrix oracle with integer keys, reindexed by user-level keys.
+///
+/// Major keys are translated by `major_keys`, and minor keys by `minor_keys`.  Requesting a view
+/// with a key that does not appear in the corresponding bimap panics.  See the
+/// [module documentation](self) for details.
+#[derive(Clone, Debug)]
+pub struct ReindexedOracle< 'k, Matrix, MajKey, MinKey >
+    where   MajKey:     Hash + Eq,
+            MinKey:     Hash + Eq,
+{
+    matrix:         Matrix,
+    major_keys:     &'k BiMapSequential< MajKey >,
+    minor_keys:     &'k BiMapSequential< MinKey >,
+}
+
+impl    < 'k, Matrix, MajKey, MinKey >
+        ReindexedOracle
+        < 'k, Matrix, MajKey, MinKey >
+
+        where   MajKey:     Hash + Eq,
+                MinKey:     Hash + Eq,
+{
+    /// Reindex `matrix`: major ordinal `i` corresponds to the key `major_keys.ord_to_val[ i ]`,
+    /// and minor ordinal `j` to the key `minor_keys.ord_to_val[ j ]`.
+    pub fn new( matrix: Matrix, major_keys: &'k BiMapSequential< MajKey >, minor_keys: &'k BiMapSequential< MinKey > ) -> Self {
+        ReindexedOracle{ matrix, major_keys, minor_keys }
+    }
+
+    /// The wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// The bijection between major keys and major ordinals.
+    pub fn major_keys( &self ) -> &'k BiMapSequential< MajKey > { self.major_keys }
+
+    /// The bijection between minor keys and minor ordinals.
+    pub fn minor_keys( &self ) -> &'k BiMapSequential< MinKey > { self.minor_keys }
+
+    /// Unwrap the matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+}
+
+/// The ordinal of `key` in `keys`; panics if there is none.
+fn ordinal< Key: Hash + Eq >( keys: &BiMapSequential< Key >, key: &Key ) -> usize {
+    *keys.val_to_ord.get( key ).expect( "the requested key does not appear in the bimap" )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A view of a [`ReindexedOracle`]: a view of the wrapped matrix, with each integer key replaced
+/// by the corresponding user-level key.
+#[derive(Clone, Debug)]
+pub struct ReindexedView< 'k, View, Key >
+    where   Key:    Hash + Eq,
+{
+    view:   View,
+    keys:   &'k BiMapSequential< Key >,
+}
+
+impl    < 'k, View, Key >
+        Iterator
+        for
+        ReindexedView < 'k, View, Key >
+
+        where   View:           Iterator,
+                View::Item:     KeyValGet< Key = usize >,
+                Key:            Clone + Hash + Eq,
+{
+    type Item = ( Key, < View::Item as KeyValGet >::Val );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        let keys    =   self.keys;
+        self.view.next().map( |entry| ( keys.ord_to_val[ entry.key() ].clone(), entry.val() ) )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.view.size_hint() }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < 'k, Matrix, MajKey, MinKey >
+        WhichMajor
+        for
+        ReindexedOracle < 'k, Matrix, MajKey, MinKey >
+
+        where   Matrix:     WhichMajor,
+                MajKey:     Hash + Eq,
+                MinKey:     Hash + Eq,
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, 'k, Matrix, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        ReindexedOracle < 'k, Matrix, MajKey, MinKey >
+
+        where   Matrix:     OracleMajor< 'a, usize, usize, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     Clone + Hash + Eq,
+                Val:        Clone,
+                < Matrix::ViewMajor as IntoIterator >::IntoIter:    'a,
+                'k:         'a,
+                MinKey:     'a,
+                Val:        'a,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   ReindexedView< 'k, < Matrix::ViewMajor as IntoIterator >::IntoIter, MinKey >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        ReindexedView{ view: self.matrix.view_major( ordinal( self.major_keys, &index ) ).into_iter(), keys: self.minor_keys }
+    }
+}
+
+impl     < 'a, 'k, Matrix, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        ReindexedOracle < 'k, Matrix, MajKey, MinKey >
+
+        where   Matrix:     OracleMajorAscend< 'a, usize, usize, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     Clone + Hash + Eq,
+                Val:        Clone,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   ReindexedView< 'k, < Matrix::ViewMajorAscend as IntoIterator >::IntoIter, MinKey >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        ReindexedView{ view: self.matrix.view_major_ascend( ordinal( self.major_keys, &index ) ).into_iter(), keys: self.minor_keys }
+    }
+}
+
+impl     < 'a, 'k, Matrix, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        ReindexedOracle < 'k, Matrix, MajKey, MinKey >
+
+        where   Matrix:     OracleMajorDescend< 'a, usize, usize, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     Clone + Hash + Eq,
+                Val:        Clone,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   ReindexedView< 'k, < Matrix::ViewMajorDescend as IntoIterator >::IntoIter, MinKey >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        ReindexedView{ view: self.matrix.view_major_descend( ordinal( self.major_keys, &index ) ).into_iter(), keys: self.minor_keys }
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, 'k, Matrix, MajKey, MinKey, Val >
+        OracleMinor < 'a, MinKey, MajKey, Val >
+        for
+        ReindexedOracle < 'k, Matrix, MajKey, MinKey >
+
+        where   Matrix:     OracleMinor< 'a, usize, usize, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     Clone + Hash + Eq,
+                Val:        Clone,
+{
+    type PairMinor =   ( MajKey, Val );
+    type ViewMinor =   ReindexedView< 'k, < Matrix::ViewMinor as IntoIterator >::IntoIter, MajKey >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MinKey ) -> Self::ViewMinor {
+        ReindexedView{ view: self.matrix.view_minor( ordinal( self.minor_keys, &index ) ).into_iter(), keys: self.major_keys }
+    }
+}
+
+impl     < 'a, 'k, Matrix, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, MinKey, MajKey, Val >
+        for
+        ReindexedOracle < 'k, Matrix, MajKey, MinKey >
+
+        where   Matrix:     OracleMinorAscend< 'a, usize, usize, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     Clone + Hash + Eq,
+                Val:        Clone,
+{
+    type PairMinorAscend =   ( MajKey, Val );
+    type ViewMinorAscend =   ReindexedView< 'k, < Matrix::ViewMinorAscend as IntoIterator >::IntoIter, MajKey >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MinKey ) -> Self::ViewMinorAscend {
+        ReindexedView{ view: self.matrix.view_minor_ascend( ordinal( self.minor_keys, &index ) ).into_iter(), keys: self.major_keys }
+    }
+}
+
+impl     < 'a, 'k, Matrix, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, MinKey, MajKey, Val >
+        for
+        ReindexedOracle < 'k, Matrix, MajKey, MinKey >
+
+        where   Matrix:     OracleMinorDescend< 'a, usize, usize, Val >,
+                MajKey:     Clone + Hash + Eq,
+                MinKey:     Clone + Hash + Eq,
+                Val:        Clone,
+{
+    type PairMinorDescend =   ( MajKey, Val );
+    type ViewMinorDescend =   ReindexedView< 'k, < Matrix::ViewMinorDescend as IntoIterator >::IntoIter, MajKey >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MinKey ) -> Self::ViewMinorDescend {
+        ReindexedView{ view: self.matrix.view_minor_descend( ordinal( self.minor_keys, &index ) ).into_iter(), keys: self.major_keys }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::dense::DenseMatrix;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_reindexed_views_translate_both_dimensions() {
+        let ring        =   NativeRing::<i64>::new();
+        let matrix      =   DenseMatrix::from_vec_of_vecs( MajorDimension::Row, vec![ vec![ 1, 0, 2 ], vec![ 0, 3, 4 ] ], ring );
+        let rows        =   BiMapSequential::from_vec( vec![ "r", "s" ] );
+        let cols        =   BiMapSequential::from_vec( vec![ 'c', 'b', 'a' ] );
+        let reindexed   =   ReindexedOracle::new( matrix, &rows, &cols );
+        assert_eq!( reindexed.major_dimension(), MajorDimension::Row );
+
+        // entries follow the order of the bimap, not the order of the keys
+        assert_eq!( reindexed.view_major( "s" ).collect::< Vec< _ > >(), vec![ ('b', 3), ('a', 4) ] );
+        assert_eq!( reindexed.view_major_ascend( "r" ).collect::< Vec< _ > >(), vec![ ('c', 1), ('a', 2) ] );
+        assert_eq!( reindexed.view_major_descend( "r" ).collect::< Vec< _ > >(), vec![ ('a', 2), ('c', 1) ] );
+        assert_eq!( reindexed.view_minor( 'a' ).collect::< Vec< _ > >(), vec![ ("r", 2), ("s", 4) ] );
+        assert_eq!( reindexed.view_minor_ascend( 'b' ).collect::< Vec< _ > >(), vec![ ("s", 3) ] );
+        assert_eq!( reindexed.view_minor_descend( 'a' ).collect::< Vec< _ > >(), vec![ ("s", 4), ("r", 2) ] );
+    }
+
+    #[test]
+    #[should_panic( expected = "does not appear in the bimap" )]
+    fn test_unknown_key_panics() {
+        let ring        =   NativeRing::<i64>::new();
+        let matrix      =   DenseMatrix::from_vec_of_vecs( MajorDimension::Row, vec![ vec![ 1 ] ], ring );
+        let keys        =   BiMapSequential::from_vec( vec![ 7 ] );
+        ReindexedOracle::new( matrix, &keys, &keys ).view_major( 8 );
+    }
+}
@@ -0,0 +1,180 @@
+//! Rows and columns, instead of major and minor views.
+//!
+//! The oracle traits speak of *major* and *minor* views, so that the same code serves row-major
+//! and column-major matrices alike.  When a computation is naturally phrased in terms of rows and
+//! columns, translating between the two vocabularies by hand is error-prone.  A [`RowColOracle`]
+//! wraps a matrix and does the translation: `view_row` returns a major view if the matrix is
+//! row-major and a minor view if it is column-major, and `view_col` does the reverse.
+//!
+//! Since the choice is made at run time, from [`WhichMajor`], both kinds of view are returned as
+//! an [`itertools::Either`], and the wrapped matrix must use the same type for major and minor
+//! keys and for the entries of major and minor views.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::row_col::RowColOracle;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::MajorDimension;
+//!
+//! // the matrix [ [ 1, 2 ], [ 0, 3 ] ], stored by column
+//! let matrix  =   RowColOracle::new( VecOfVec::new( MajorDimension::Col, vec![ vec![ (0, 1) ], vec![ (0, 2), (1, 3) ] ] ) );
+//!
+//! assert_eq!( matrix.view_row( 0 ).collect::<Vec<_>>(), vec![ (0, 1), (1, 2) ] );
+//! assert_eq!( matrix.view_col_descend( 1 ).collect::<Vec<_>>(), vec![ (1, 3), (0, 2) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use itertools::Either;
+
+
+/// A row or column of a [`RowColOracle`], in no particular order: a major view of the wrapped
+/// matrix (`Left`) or a minor view (`Right`).
+pub type RowColView< 'a, Matrix, Key, Val > = Either<
+        < < Matrix as OracleMajor< 'a, Key, Key, Val > >::ViewMajor as IntoIterator >::IntoIter,
+        < < Matrix as OracleMinor< 'a, Key, Key, Val > >::ViewMinor as IntoIterator >::IntoIter,
+    >;
+
+/// A row or column of a [`RowColOracle`], in ascending order.
+pub type RowColViewAscend< 'a, Matrix, Key, Val > = Either<
+        < < Matrix as OracleMajorAscend< 'a, Key, Key, Val > >::ViewMajorAscend as IntoIterator >::IntoIter,
+        < < Matrix as OracleMinorAscend< 'a, Key, Key, Val > >::ViewMinorAscend as IntoIterator >::IntoIter,
+    >;
+
+/// A row or column of a [`RowColOracle`], in descending order.
+pub type RowColViewDescend< 'a, Matrix, Key, Val > = Either<
+        < < Matrix as OracleMajorDescend< 'a, Key, Key, Val > >::ViewMajorDescend as IntoIterator >::IntoIter,
+        < < Matrix as OracleMinorDescend< 'a, Key, Key, Val > >::ViewMinorDescend as IntoIterator >::IntoIter,
+    >;
+
+
+/// Wraps a matrix oracle, and exposes its rows and columns.
+///
+/// Row `i` is major view `i` if the wrapped matrix is row-major, and minor view `i` if it is
+/// column-major; symmetrically for columns.  Each method requires the wrapped matrix to implement
+/// the corresponding major and minor traits.  The wrapper also passes [`WhichMajor`] through, so
+/// it can be handed back to code that expects major and minor views.
+#[derive(Clone, Debug)]
+pub struct RowColOracle< Matrix > {
+    matrix:     Matrix,
+}
+
+impl    < Matrix >
+        RowColOracle
+        < Matrix >
+{
+    /// Wrap `matrix`.
+    pub fn new( matrix: Matrix ) -> Self { RowColOracle{ matrix } }
+
+    /// The wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// Unwrap the matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+}
+
+impl    < Matrix: WhichMajor >
+        RowColOracle
+        < Matrix >
+{
+    /// `true` if rows are major views.
+    pub fn rows_are_major( &self ) -> bool { self.matrix.major_dimension() == MajorDimension::Row }
+
+    /// Row `index`, in no particular order; entries are `(column, coefficient)` pairs.
+    pub fn view_row< 'a, 'b: 'a, Key, Val >( &'b self, index: Key ) -> RowColView< 'a, Matrix, Key, Val >
+        where   Matrix:     OracleMajor< 'a, Key, Key, Val > + OracleMinor< 'a, Key, Key, Val >,
+    {
+        if self.rows_are_major() { Either::Left( self.matrix.view_major( index ).into_iter() ) }
+        else { Either::Right( self.matrix.view_minor( index ).into_iter() ) }
+    }
+
+    /// Column `index`, in no particular order; entries are `(row, coefficient)` pairs.
+    pub fn view_col< 'a, 'b: 'a, Key, Val >( &'b self, index: Key ) -> RowColView< 'a, Matrix, Key, Val >
+        where   Matrix:     OracleMajor< 'a, Key, Key, Val > + OracleMinor< 'a, Key, Key, Val >,
+    {
+        if self.rows_are_major() { Either::Right( self.matrix.view_minor( index ).into_iter() ) }
+        else { Either::Left( self.matrix.view_major( index ).into_iter() ) }
+    }
+
+    /// Row `index`, in ascending order of column.
+    pub fn view_row_ascend< 'a, 'b: 'a, Key, Val >( &'b self, index: Key ) -> RowColViewAscend< 'a, Matrix, Key, Val >
+        where   Matrix:     OracleMajorAscend< 'a, Key, Key, Val > + OracleMinorAscend< 'a, Key, Key, Val >,
+    {
+        if self.rows_are_major() { Either::Left( self.matrix.view_major_ascend( index ).into_iter() ) }
+        else { Either::Right( self.matrix.view_minor_ascend( index ).into_iter() ) }
+    }
+
+    /// Column `index`, in ascending order of row.
+    pub fn view_col_ascend< 'a, 'b: 'a, Key, Val >( &'b self, index: Key ) -> RowColViewAscend< 'a, Matrix, Key, Val >
+        where   Matrix:     OracleMajorAscend< 'a, Key, Key, Val > + OracleMinorAscend< 'a, Key, Key, Val >,
+    {
+        if self.rows_are_major() { Either::Right( self.matrix.view_minor_ascend( index ).into_iter() ) }
+        else { Either::Left( self.matrix.view_major_ascend( index ).into_iter() ) }
+    }
+
+    /// Row `index`, in descending order of column.
+    pub fn view_row_descend< 'a, 'b: 'a, Key, Val >( &'b self, index: Key ) -> RowColViewDescend< 'a, Matrix, Key, Val >
+        where   Matrix:     OracleMajorDescend< 'a, Key, Key, Val > + OracleMinorDescend< 'a, Key, Key, Val >,
+    {
+        if self.rows_are_major() { Either::Left( self.matrix.view_major_descend( index ).into_iter() ) }
+        else { Either::Right( self.matrix.view_minor_descend( index ).into_iter() ) }
+    }
+
+    /// Column `index`, in descending order of row.
+    pub fn view_col_descend< 'a, 'b: 'a, Key, Val >( &'b self, index: Key ) -> RowColViewDescend< 'a, Matrix, Key, Val >
+        where   Matrix:     OracleMajorDescend< 'a, Key, Key, Val > + OracleMinorDescend< 'a, Key, Key, Val >,
+    {
+        if self.rows_are_major() { Either::Right( self.matrix.view_minor_descend( index ).into_iter() ) }
+        else { Either::Left( self.matrix.view_major_descend( index ).into_iter() ) }
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+impl     < Matrix: WhichMajor >
+        WhichMajor
+        for
+        RowColOracle < Matrix >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+
+    #[test]
+    fn test_rows_and_cols_agree_across_storage_orders() {
+        // the matrix [ [ 1, 0, 2 ], [ 0, 3, 4 ] ], stored both ways
+        let by_row  =   RowColOracle::new( VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (2, 2) ], vec![ (1, 3), (2, 4) ] ] ) );
+        let by_col  =   RowColOracle::new( VecOfVec::new( MajorDimension::Col, vec![ vec![ (0, 1) ], vec![ (1, 3) ], vec![ (0, 2), (1, 4) ] ] ) );
+        assert!( by_row.rows_are_major() && ! by_col.rows_are_major() );
+
+        for i in 0 .. 2 {
+            assert!( by_row.view_row( i ).eq( by_col.view_row( i ) ) );
+            assert!( by_row.view_row_ascend( i ).eq( by_col.view_row_ascend( i ) ) );
+            assert!( by_row.view_row_descend( i ).eq( by_col.view_row_descend( i ) ) );
+        }
+        for j in 0 .. 3 {
+            assert!( by_row.view_col( j ).eq( by_col.view_col( j ) ) );
+            assert!( by_row.view_col_ascend( j ).eq( by_col.view_col_ascend( j ) ) );
+            assert!( by_row.view_col_descend( j ).eq( by_col.view_col_descend( j ) ) );
+        }
+        assert_eq!( by_col.view_col_ascend( 2 ).collect::< Vec< _ > >(), vec![ (0, 2), (1, 4) ] );
+        assert_eq!( by_row.view_col_descend( 2 ).collect::< Vec< _ > >(), vec![ (1, 4), (0, 2) ] );
+        assert_eq!( by_row.into_inner().major_dimension(), MajorDimension::Row );
+    }
+}
@@ -0,0 +1,234 @@
+//! Scalar multiples of matrix oracles.
+//!
+//! A [`ScaledOracle`] wraps an oracle and multiplies every coefficient it returns by a fixed ring
+//! element, using the [`Scale`] vector transform.  Nothing is copied.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::scaled::ScaledOracle;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor, OracleMajorDescend};
+//! use solar::rings::ring_native::NativeRing;
+//!
+//! let matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (2, -3) ] ] );
+//! let scaled  =   ScaledOracle::new( &matrix, 2, NativeRing::<i64>::new() );
+//!
+//! assert_eq!( scaled.view_major( 0 ).collect::<Vec<_>>(), vec![ (0, 2), (2, -6) ] );
+//! assert_eq!( scaled.view_major_descend( 0 ).collect::<Vec<_>>(), vec![ (2, -6), (0, 2) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use crate::vector_entries::vector_entries::KeyValSet;
+use crate::vectors::vector_transforms::{Scale, Transforms};
+use std::fmt::Debug;
+
+
+/// The product `scalar * matrix`, computed lazily one view at a time.
+///
+/// Views have the same keys, in the same order, as the views of the wrapped matrix.  Zeros are not
+/// dropped: if `scalar` is zero, or a zero divisor, views may contain explicit zero entries (use
+/// [`drop_zeros`](crate::vectors::vector_transforms::Transforms::drop_zeros) to remove them).
+#[derive(Clone, Debug)]
+pub struct ScaledOracle< Matrix, Val, RingOperator > {
+    matrix:     Matrix,
+    scalar:     Val,
+    ring:       RingOperator,
+}
+
+impl    < Matrix, Val, RingOperator >
+        ScaledOracle
+        < Matrix, Val, RingOperator >
+{
+    /// Create the lazy product `scalar * matrix`.
+    pub fn new( matrix: Matrix, scalar: Val, ring: RingOperator ) -> Self { ScaledOracle{ matrix, scalar, ring } }
+
+    /// The wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// The scalar.
+    pub fn scalar( &self ) -> &Val { &self.scalar }
+
+    /// Unwrap the matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Matrix, Val, RingOperator >
+        WhichMajor
+        for
+        ScaledOracle < Matrix, Val, RingOperator >
+
+        where   Matrix:     WhichMajor,
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, Val, RingOperator, MajKey, MinKey >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        ScaledOracle < Matrix, Val, RingOperator >
+
+        where   Matrix:             OracleMajor< 'a, MajKey, MinKey, Val >,
+                Matrix::PairMajor:  KeyValSet,
+                MinKey:             Clone + Debug,
+                Val:                Clone + Debug,
+                RingOperator:       Clone + Semiring< Val >,
+                < Matrix::ViewMajor as IntoIterator >::IntoIter:    'a,
+                Val:                'a,
+                RingOperator:       'a,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   Scale< < Matrix::ViewMajor as IntoIterator >::IntoIter, RingOperator >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        self.matrix.view_major( index ).into_iter().scale( self.ring.clone(), self.scalar.clone() )
+    }
+}
+
+impl     < 'a, Matrix, Val, RingOperator, MajKey, MinKey >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        ScaledOracle < Matrix, Val, RingOperator >
+
+        where   Matrix:             OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                Matrix::PairMajorAscend:  KeyValSet,
+                MinKey:             Clone + Debug,
+                Val:                Clone + Debug,
+                RingOperator:       Clone + Semiring< Val >,
+{
+    type PairMajorAscend =   Matrix::PairMajorAscend;
+    type ViewMajorAscend =   Scale< < Matrix::ViewMajorAscend as IntoIterator >::IntoIter, RingOperator >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        self.matrix.view_major_ascend( index ).into_iter().scale( self.ring.clone(), self.scalar.clone() )
+    }
+}
+
+impl     < 'a, Matrix, Val, RingOperator, MajKey, MinKey >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        ScaledOracle < Matrix, Val, RingOperator >
+
+        where   Matrix:             OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                Matrix::PairMajorDescend:  KeyValSet,
+                MinKey:             Clone + Debug,
+                Val:                Clone + Debug,
+                RingOperator:       Clone + Semiring< Val >,
+{
+    type PairMajorDescend =   Matrix::PairMajorDescend;
+    type ViewMajorDescend =   Scale< < Matrix::ViewMajorDescend as IntoIterator >::IntoIter, RingOperator >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        self.matrix.view_major_descend( index ).into_iter().scale( self.ring.clone(), self.scalar.clone() )
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, Val, RingOperator, MajKey, MinKey >
+        OracleMinor < 'a, MajKey, MinKey, Val >
+        for
+        ScaledOracle < Matrix, Val, RingOperator >
+
+        where   Matrix:             OracleMinor< 'a, MajKey, MinKey, Val >,
+                Matrix::PairMinor:  KeyValSet,
+                MinKey:             Clone + Debug,
+                Val:                Clone + Debug,
+                RingOperator:       Clone + Semiring< Val >,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   Scale< < Matrix::ViewMinor as IntoIterator >::IntoIter, RingOperator >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        self.matrix.view_minor( index ).into_iter().scale( self.ring.clone(), self.scalar.clone() )
+    }
+}
+
+impl     < 'a, Matrix, Val, RingOperator, MajKey, MinKey >
+        OracleMinorAscend < 'a, MajKey, MinKey, Val >
+        for
+        ScaledOracle < Matrix, Val, RingOperator >
+
+        where   Matrix:             OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                Matrix::PairMinorAscend:  KeyValSet,
+                MinKey:             Clone + Debug,
+                Val:                Clone + Debug,
+                RingOperator:       Clone + Semiring< Val >,
+{
+    type PairMinorAscend =   Matrix::PairMinorAscend;
+    type ViewMinorAscend =   Scale< < Matrix::ViewMinorAscend as IntoIterator >::IntoIter, RingOperator >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        self.matrix.view_minor_ascend( index ).into_iter().scale( self.ring.clone(), self.scalar.clone() )
+    }
+}
+
+impl     < 'a, Matrix, Val, RingOperator, MajKey, MinKey >
+        OracleMinorDescend < 'a, MajKey, MinKey, Val >
+        for
+        ScaledOracle < Matrix, Val, RingOperator >
+
+        where   Matrix:             OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                Matrix::PairMinorDescend:  KeyValSet,
+                MinKey:             Clone + Debug,
+                Val:                Clone + Debug,
+                RingOperator:       Clone + Semiring< Val >,
+{
+    type PairMinorDescend =   Matrix::PairMinorDescend;
+    type ViewMinorDescend =   Scale< < Matrix::ViewMinorDescend as IntoIterator >::IntoIter, RingOperator >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        self.matrix.view_minor_descend( index ).into_iter().scale( self.ring.clone(), self.scalar.clone() )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::dense::DenseMatrix;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_scaled_views_agree_with_dense_multiple() {
+        let ring        =   NativeRing::<i64>::new();
+        let matrix      =   DenseMatrix::from_vec_of_vecs( MajorDimension::Col, vec![ vec![ 1, 0, 2 ], vec![ 0, -3, 4 ] ], ring.clone() );
+        let tripled     =   DenseMatrix::from_vec_of_vecs( MajorDimension::Col, vec![ vec![ 3, 0, 6 ], vec![ 0, -9, 12 ] ], ring.clone() );
+        let scaled      =   ScaledOracle::new( &matrix, 3, ring );
+        for i in 0 .. 2 {
+            assert!( scaled.view_major( i ).eq( tripled.view_major( i ) ) );
+            assert!( scaled.view_major_ascend( i ).eq( tripled.view_major_ascend( i ) ) );
+            assert!( scaled.view_major_descend( i ).eq( tripled.view_major_descend( i ) ) );
+        }
+        for j in 0 .. 3 {
+            assert!( scaled.view_minor( j ).eq( tripled.view_minor( j ) ) );
+            assert!( scaled.view_minor_ascend( j ).eq( tripled.view_minor_ascend( j ) ) );
+            assert!( scaled.view_minor_descend( j ).eq( tripled.view_minor_descend( j ) ) );
+        }
+    }
+}
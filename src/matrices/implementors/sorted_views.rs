@@ -0,0 +1,154 @@
+//! An adaptor that supplies sorted views of a matrix whose views are unsorted.
+//!
+//! Many user-defined oracles only implement `OracleMajor`, whose views may list their entries in
+//! any order; reduction algorithms, on the other hand, usually require `OracleMajorAscend` or
+//! `OracleMajorDescend`.  A [`SortedViews`] wraps such an oracle together with an
+//! [order comparator](crate::utilities::order) on minor keys, and sorts each view when it is
+//! requested.  Sorting costs `O(n log n)` time and `O(n)` memory for a view with `n` entries, so
+//! a native implementation should be preferred when one is available.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::sorted_views::SortedViews;
+//! use solar::matrices::matrix_oracle::{OracleMajor, OracleMajorAscend, OracleMajorDescend};
+//! use solar::utilities::order::OrderNatural;
+//!
+//! // a user-defined oracle whose views are unsorted
+//! struct Scrambled;
+//! impl < 'a > OracleMajor< 'a, usize, usize, f64 > for Scrambled {
+//!     type PairMajor = (usize, f64);
+//!     type ViewMajor = Vec< (usize, f64) >;
+//!     fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor {
+//!         vec![ (index + 2, 3.), (index, 1.), (index + 1, 2.) ]
+//!     }
+//! }
+//!
+//! let matrix  =   SortedViews::new( Scrambled, OrderNatural );
+//! assert_eq!( matrix.view_major_ascend( 0 ).collect::<Vec<_>>(), vec![ (0, 1.), (1, 2.), (2, 3.) ] );
+//! assert_eq!( matrix.view_major_descend( 0 ).collect::<Vec<_>>(), vec![ (2, 3.), (1, 2.), (0, 1.) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::utilities::order::OrderComparator;
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::iter::Rev;
+use std::vec::IntoIter;
+
+
+/// Wraps a matrix oracle, and supplies ascending and descending major views by sorting its
+/// unordered major views by minor key.
+///
+/// Keys are compared with `order`; the ascending view lists entries in ascending order, and the
+/// descending view is the ascending view in reverse.  Unordered views are passed through unchanged.
+#[derive(Clone, Debug)]
+pub struct SortedViews< Matrix, Order > {
+    matrix:     Matrix,
+    order:      Order,
+}
+
+impl < Matrix, Order > SortedViews< Matrix, Order > {
+    /// Wrap `matrix`, sorting views by the order on minor keys represented by `order`.
+    pub fn new( matrix: Matrix, order: Order ) -> Self { SortedViews{ matrix, order } }
+
+    /// A reference to the wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// A reference to the order comparator.
+    pub fn order( &self ) -> &Order { &self.order }
+
+    /// Return the wrapped matrix and the order comparator.
+    pub fn into_parts( self ) -> ( Matrix, Order ) { ( self.matrix, self.order ) }
+
+    /// The major view with key `index`, sorted in ascending order.
+    fn sorted< 'a, 'b: 'a, MajKey, MinKey, SnzVal >( &'b self, index: MajKey ) -> Vec< Matrix::PairMajor >
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+                Order:      OrderComparator< MinKey >,
+    {
+        let mut view: Vec< _ >  =   self.matrix.view_major( index ).into_iter().collect();
+        view.sort_by( |a, b| self.order.compare( &a.key(), &b.key() ) );
+        view
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+impl     < Matrix: WhichMajor, Order >
+        WhichMajor
+        for
+        SortedViews < Matrix, Order >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix, Order, MajKey, MinKey, SnzVal >
+        OracleMajor < 'a, MajKey, MinKey, SnzVal >
+        for
+        SortedViews < Matrix, Order >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   Matrix::ViewMajor;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor { self.matrix.view_major( index ) }
+}
+
+impl     < 'a, Matrix, Order, MajKey, MinKey, SnzVal >
+        OracleMajorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        SortedViews < Matrix, Order >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+                Order:      OrderComparator< MinKey >,
+{
+    type PairMajorAscend =   Matrix::PairMajor;
+    type ViewMajorAscend =   IntoIter< Matrix::PairMajor >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend { self.sorted( index ).into_iter() }
+}
+
+impl     < 'a, Matrix, Order, MajKey, MinKey, SnzVal >
+        OracleMajorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        SortedViews < Matrix, Order >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+                Order:      OrderComparator< MinKey >,
+{
+    type PairMajorDescend =   Matrix::PairMajor;
+    type ViewMajorDescend =   Rev< IntoIter< Matrix::PairMajor > >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend { self.sorted( index ).into_iter().rev() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::utilities::order::{OrderNatural, OrderReverse};
+
+    #[test]
+    fn test_sorted_views() {
+        let rows        =   vec![ vec![ (2, 'a'), (0, 'b'), (1, 'c') ], vec![], vec![ (3, 'd') ] ];
+        let sorted      =   SortedViews::new( VecOfVec::new( MajorDimension::Row, rows.clone() ), OrderNatural );
+        assert_eq!( sorted.view_major_ascend( 0 ).collect::< Vec< _ > >(), vec![ (0, 'b'), (1, 'c'), (2, 'a') ] );
+        assert_eq!( sorted.view_major_descend( 0 ).collect::< Vec< _ > >(), vec![ (2, 'a'), (1, 'c'), (0, 'b') ] );
+        assert_eq!( sorted.view_major_ascend( 1 ).count(), 0 );
+        assert!( sorted.view_major( 0 ).eq( rows[ 0 ].iter().cloned() ) );
+        assert_eq!( sorted.major_dimension(), MajorDimension::Row );
+
+        // a custom order
+        let reversed    =   SortedViews::new( sorted.into_parts().0, OrderReverse::new( OrderNatural ) );
+        assert_eq!( reversed.view_major_ascend( 0 ).map( |entry| entry.0 ).collect::< Vec< _ > >(), vec![ 2, 1, 0 ] );
+    }
+}
@@ -0,0 +1,301 @@
+//! Restrict a matrix oracle to subsets of its major and minor keys.
+//!
+//! A [`Submatrix`] wraps a matrix oracle together with a set of allowed major keys and a set of
+//! allowed minor keys (anything that implements [`KeySet`], including predicates), and filters
+//! every view accordingly.  Nothing is copied, so this is a cheap way to restrict e.g. a boundary
+//! matrix to a sub-filtration without rebuilding it.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::submatrix::Submatrix;
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+//! use solar::utilities::index_sets::IndexRangeSet;
+//!
+//! // boundary of a triangle: vertices 0, 1, 2, edges 3, 4, 5, and the 2-cell 6
+//! let boundary    =   VecOfVec::new(
+//!                         MajorDimension::Col,
+//!                         vec![   vec![], vec![], vec![],
+//!                                 vec![ (0, -1), (1, 1) ],
+//!                                 vec![ (0, -1), (2, 1) ],
+//!                                 vec![ (1, -1), (2, 1) ],
+//!                                 vec![ (3, 1), (4, -1), (5, 1) ], ],
+//!                     );
+//!
+//! // the sub-filtration consisting of the first five cells
+//! let keys        =   IndexRangeSet::from_range( 0 .. 5 );
+//! let restricted  =   Submatrix::new( &boundary, keys.clone(), keys );
+//!
+//! assert_eq!( restricted.view_major( 4 ).collect::<Vec<_>>(), vec![ (0, -1), (2, 1) ] );
+//! assert_eq!( restricted.view_major( 6 ).count(), 0 );
+//!
+//! // predicates work too: keep only the edges as rows
+//! let edges_only  =   Submatrix::new( &boundary, |_: &usize| true, |k: &usize| ( 3 .. 6 ).contains( k ) );
+//! assert_eq!( edges_only.view_major( 6 ).collect::<Vec<_>>(), vec![ (3, 1), (4, -1), (5, 1) ] );
+//! assert_eq!( edges_only.view_major( 3 ).count(), 0 );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::utilities::index_sets::KeySet;
+use crate::vector_entries::vector_entries::KeyValGet;
+
+
+/// The submatrix of `matrix` on the rows and columns indexed by `major_keys` and `minor_keys`.
+///
+/// - A major view whose key is not in `major_keys` is empty; otherwise it contains the entries
+///   of the original view whose keys lie in `minor_keys`.
+/// - Symmetrically, a minor view whose key is not in `minor_keys` is empty; otherwise it contains
+///   the entries of the original view whose keys lie in `major_keys`.
+///
+/// Entries are returned in the same order as the wrapped matrix returns them, so ascending
+/// (respectively, descending) views remain ascending (respectively, descending).  Use
+/// [`AllKeys`](crate::utilities::index_sets::AllKeys) to restrict only one of the two
+/// dimensions.
+#[derive(Clone, Debug)]
+pub struct Submatrix< Matrix, MajorKeys, MinorKeys > {
+    matrix:         Matrix,
+    major_keys:     MajorKeys,
+    minor_keys:     MinorKeys,
+}
+
+impl    < Matrix, MajorKeys, MinorKeys >
+        Submatrix
+        < Matrix, MajorKeys, MinorKeys >
+{
+    /// Restrict `matrix` to the given major and minor keys.
+    pub fn new( matrix: Matrix, major_keys: MajorKeys, minor_keys: MinorKeys ) -> Self {
+        Submatrix{ matrix, major_keys, minor_keys }
+    }
+
+    /// The wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// The set of allowed major keys.
+    pub fn major_keys( &self ) -> &MajorKeys { &self.major_keys }
+
+    /// The set of allowed minor keys.
+    pub fn minor_keys( &self ) -> &MinorKeys { &self.minor_keys }
+
+    /// Unwrap the matrix and the two key sets.
+    pub fn into_parts( self ) -> ( Matrix, MajorKeys, MinorKeys ) { ( self.matrix, self.major_keys, self.minor_keys ) }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// A view of a [`Submatrix`]: the entries of a view of the wrapped matrix whose keys lie in a
+/// given key set.
+#[derive(Clone, Debug)]
+pub struct SubmatrixView< 'a, View, Keys > {
+    view:   Option< View >,
+    keys:   &'a Keys,
+}
+
+impl    < 'a, View, Keys >
+        Iterator
+        for
+        SubmatrixView < 'a, View, Keys >
+
+        where   View:           Iterator,
+                View::Item:     KeyValGet,
+                Keys:           KeySet< < View::Item as KeyValGet >::Key >,
+{
+    type Item = View::Item;
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        let keys    =   self.keys;
+        self.view.as_mut()?.find( |entry| keys.contains_key( &entry.key() ) )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < Matrix, MajorKeys, MinorKeys >
+        WhichMajor
+        for
+        Submatrix < Matrix, MajorKeys, MinorKeys >
+
+        where   Matrix:     WhichMajor,
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, MajorKeys, MinorKeys, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        Submatrix < Matrix, MajorKeys, MinorKeys >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, Val >,
+                MajorKeys:  KeySet< MajKey >,
+                MinorKeys:  'a + KeySet< MinKey >,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   SubmatrixView< 'a, < Matrix::ViewMajor as IntoIterator >::IntoIter, MinorKeys >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        let view    =   if self.major_keys.contains_key( &index ) { Some( self.matrix.view_major( index ).into_iter() ) } else { None };
+        SubmatrixView{ view, keys: &self.minor_keys }
+    }
+}
+
+impl     < 'a, Matrix, MajorKeys, MinorKeys, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        Submatrix < Matrix, MajorKeys, MinorKeys >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajorKeys:  KeySet< MajKey >,
+                MinorKeys:  'a + KeySet< MinKey >,
+{
+    type PairMajorAscend =   Matrix::PairMajorAscend;
+    type ViewMajorAscend =   SubmatrixView< 'a, < Matrix::ViewMajorAscend as IntoIterator >::IntoIter, MinorKeys >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        let view    =   if self.major_keys.contains_key( &index ) { Some( self.matrix.view_major_ascend( index ).into_iter() ) } else { None };
+        SubmatrixView{ view, keys: &self.minor_keys }
+    }
+}
+
+impl     < 'a, Matrix, MajorKeys, MinorKeys, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        Submatrix < Matrix, MajorKeys, MinorKeys >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                MajorKeys:  KeySet< MajKey >,
+                MinorKeys:  'a + KeySet< MinKey >,
+{
+    type PairMajorDescend =   Matrix::PairMajorDescend;
+    type ViewMajorDescend =   SubmatrixView< 'a, < Matrix::ViewMajorDescend as IntoIterator >::IntoIter, MinorKeys >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        let view    =   if self.major_keys.contains_key( &index ) { Some( self.matrix.view_major_descend( index ).into_iter() ) } else { None };
+        SubmatrixView{ view, keys: &self.minor_keys }
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, MajorKeys, MinorKeys, MajKey, MinKey, Val >
+        OracleMinor < 'a, MajKey, MinKey, Val >
+        for
+        Submatrix < Matrix, MajorKeys, MinorKeys >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, Val >,
+                MinorKeys:  KeySet< MajKey >,
+                MajorKeys:  'a + KeySet< MinKey >,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   SubmatrixView< 'a, < Matrix::ViewMinor as IntoIterator >::IntoIter, MajorKeys >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        let view    =   if self.minor_keys.contains_key( &index ) { Some( self.matrix.view_minor( index ).into_iter() ) } else { None };
+        SubmatrixView{ view, keys: &self.major_keys }
+    }
+}
+
+impl     < 'a, Matrix, MajorKeys, MinorKeys, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, MajKey, MinKey, Val >
+        for
+        Submatrix < Matrix, MajorKeys, MinorKeys >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MinorKeys:  KeySet< MajKey >,
+                MajorKeys:  'a + KeySet< MinKey >,
+{
+    type PairMinorAscend =   Matrix::PairMinorAscend;
+    type ViewMinorAscend =   SubmatrixView< 'a, < Matrix::ViewMinorAscend as IntoIterator >::IntoIter, MajorKeys >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        let view    =   if self.minor_keys.contains_key( &index ) { Some( self.matrix.view_minor_ascend( index ).into_iter() ) } else { None };
+        SubmatrixView{ view, keys: &self.major_keys }
+    }
+}
+
+impl     < 'a, Matrix, MajorKeys, MinorKeys, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, MajKey, MinKey, Val >
+        for
+        Submatrix < Matrix, MajorKeys, MinorKeys >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                MinorKeys:  KeySet< MajKey >,
+                MajorKeys:  'a + KeySet< MinKey >,
+{
+    type PairMinorDescend =   Matrix::PairMinorDescend;
+    type ViewMinorDescend =   SubmatrixView< 'a, < Matrix::ViewMinorDescend as IntoIterator >::IntoIter, MajorKeys >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        let view    =   if self.minor_keys.contains_key( &index ) { Some( self.matrix.view_minor_descend( index ).into_iter() ) } else { None };
+        SubmatrixView{ view, keys: &self.major_keys }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::dense::DenseMatrix;
+    use crate::rings::ring_native::NativeRing;
+    use crate::utilities::index_sets::{AllKeys, IndexRangeSet};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_submatrix_views_agree_with_dense_restriction() {
+        let rows            =   vec![   vec![ 1, 2, 0, 4 ],
+                                        vec![ 0, 5, 6, 0 ],
+                                        vec![ 7, 0, 8, 9 ], ];
+        let matrix          =   DenseMatrix::from_vec_of_vecs( MajorDimension::Row, rows, NativeRing::<i64>::new() );
+        let major_keys: HashSet< usize >    =   vec![ 0, 2 ].into_iter().collect();
+        let minor_keys      =   IndexRangeSet::from_ranges( vec![ 0 .. 1, 2 .. 4 ] );
+        let submatrix       =   Submatrix::new( &matrix, major_keys.clone(), minor_keys.clone() );
+
+        for i in 0 .. 3 {
+            let expected: Vec< (usize, i64) >   =   ( 0 .. 4 )
+                                                        .filter( |j| major_keys.contains( &i ) && minor_keys.contains( *j ) && *matrix.entry( i, *j ) != 0 )
+                                                        .map( |j| ( j, *matrix.entry( i, j ) ) )
+                                                        .collect();
+            assert_eq!( submatrix.view_major( i ).collect::< Vec< _ > >(), expected );
+            assert_eq!( submatrix.view_major_ascend( i ).collect::< Vec< _ > >(), expected );
+            assert_eq!( submatrix.view_major_descend( i ).collect::< Vec< _ > >(), expected.into_iter().rev().collect::< Vec< _ > >() );
+        }
+        for j in 0 .. 4 {
+            let expected: Vec< (usize, i64) >   =   ( 0 .. 3 )
+                                                        .filter( |i| major_keys.contains( i ) && minor_keys.contains( j ) && *matrix.entry( *i, j ) != 0 )
+                                                        .map( |i| ( i, *matrix.entry( i, j ) ) )
+                                                        .collect();
+            assert_eq!( submatrix.view_minor( j ).collect::< Vec< _ > >(), expected );
+            assert_eq!( submatrix.view_minor_ascend( j ).collect::< Vec< _ > >(), expected );
+            assert_eq!( submatrix.view_minor_descend( j ).collect::< Vec< _ > >(), expected.into_iter().rev().collect::< Vec< _ > >() );
+        }
+
+        // restricting only the minor keys leaves every major view nonempty
+        let columns_only    =   Submatrix::new( &matrix, AllKeys, minor_keys );
+        assert_eq!( columns_only.view_major( 1 ).collect::< Vec< _ > >(), vec![ (2, 6) ] );
+    }
+}
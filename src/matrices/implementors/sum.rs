@@ -0,0 +1,518 @@
+//! Lazy sums of matrix oracles.
+//!
+//! A [`SumOracle`] wraps two oracles (possibly of different types), and a [`SumOracleMany`] wraps
+//! any number of oracles of the same type.  In both cases view `i` of the sum is computed on
+//! demand by merging view `i` of each addend, summing the coefficients of entries with equal
+//! keys, and dropping zeros; nothing is stored except the addends.
+//!
+//! Since entries are gathered as they stream past, the sum is built from sorted views: it
+//! implements `OracleMajorAscend` (respectively `OracleMajorDescend`, etc.) whenever every addend
+//! does.  Unsorted views (`OracleMajor`, `OracleMinor`) are computed from the ascending views of
+//! the addends.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::sum::{SumOracle, SumOracleMany};
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMajorDescend};
+//! use solar::rings::ring_native::NativeRing;
+//!
+//! let ring    =   NativeRing::<i64>::new();
+//! let a       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (2, 1) ] ] );
+//! let b       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (1, 5), (2, -1) ] ] );
+//! let c       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 2) ] ] );
+//!
+//! let sum     =   SumOracle::new( &a, &b, ring.clone() );
+//! assert_eq!( sum.view_major_ascend( 0 ).collect::<Vec<_>>(), vec![ (0, 1), (1, 5) ] );
+//! assert_eq!( sum.view_major_descend( 0 ).collect::<Vec<_>>(), vec![ (1, 5), (0, 1) ] );
+//!
+//! let addends =   vec![ a, b, c ];
+//! let sum     =   SumOracleMany::from_slice( &addends, ring );
+//! assert_eq!( sum.view_major_ascend( 0 ).collect::<Vec<_>>(), vec![ (0, 3), (1, 5) ] );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use crate::utilities::iterators::hit_merge::{hit_merge_by, HitMerge};
+use crate::vector_entries::vector_entries::KeyValGet;
+use crate::vectors::vector_transforms::{Simplify, Transforms};
+use itertools::Itertools;
+use itertools::structs::MergeBy;
+use std::iter::Map;
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// Converts an entry of an addend to a `(key, val)` tuple.
+pub type ToTuple< Pair, Key, Val >          =   fn( Pair ) -> ( Key, Val );
+
+/// Returns `true` if the first entry should be emitted before the second.
+pub type EntryOrder< Key, Val >             =   fn( &( Key, Val ), &( Key, Val ) ) -> bool;
+
+/// A view of an addend, with entries converted to tuples.
+pub type TupleView< View, Key, Val >        =   Map< View, ToTuple< < View as Iterator >::Item, Key, Val > >;
+
+/// Gathers the entries of a merged, sorted stream and drops zeros.
+pub type SumView< Merged, RingOperator >    =   Simplify< Merged, RingOperator >;
+
+/// A view of a [`SumOracle`].
+pub type SumOracleView< ViewA, ViewB, Key, Val, RingOperator >
+    =   SumView< MergeBy< TupleView< ViewA, Key, Val >, TupleView< ViewB, Key, Val >, EntryOrder< Key, Val > >, RingOperator >;
+
+/// A view of a [`SumOracleMany`].
+pub type SumOracleManyView< View, Key, Val, RingOperator >
+    =   SumView< HitMerge< TupleView< View, Key, Val >, EntryOrder< Key, Val > >, RingOperator >;
+
+fn to_tuple< Pair: KeyValGet >( entry: Pair ) -> ( Pair::Key, Pair::Val ) { ( entry.key(), entry.val() ) }
+
+// `merge_by` takes from the first iterator when the predicate holds; `hit_merge_by` expects a
+// strict "less than"
+fn ascending< Key: PartialOrd, Val >( a: &( Key, Val ), b: &( Key, Val ) ) -> bool { a.0 <= b.0 }
+
+fn descending< Key: PartialOrd, Val >( a: &( Key, Val ), b: &( Key, Val ) ) -> bool { a.0 >= b.0 }
+
+fn strictly_ascending< Key: PartialOrd, Val >( a: &( Key, Val ), b: &( Key, Val ) ) -> bool { a.0 < b.0 }
+
+fn strictly_descending< Key: PartialOrd, Val >( a: &( Key, Val ), b: &( Key, Val ) ) -> bool { a.0 > b.0 }
+
+/// Merge two sorted views, gather, and drop zeros.
+fn sum_two< A, B, Key, Val, RingOperator >( a: A, b: B, order: EntryOrder< Key, Val >, ring: &RingOperator )
+        -> SumOracleView< A::IntoIter, B::IntoIter, Key, Val, RingOperator >
+
+    where   A:              IntoIterator,
+            B:              IntoIterator,
+            A::Item:        KeyValGet< Key = Key, Val = Val >,
+            B::Item:        KeyValGet< Key = Key, Val = Val >,
+            RingOperator:   Clone + Semiring< Val >,
+            Key:            Clone + PartialEq,
+            Val:            Clone,
+{
+    let a   =   a.into_iter().map( to_tuple as ToTuple< A::Item, Key, Val > );
+    let b   =   b.into_iter().map( to_tuple as ToTuple< B::Item, Key, Val > );
+    a.merge_by( b, order ).simplify( ring.clone() )
+}
+
+/// Merge any number of sorted views, gather, and drop zeros.
+fn sum_many< I, Key, Val, RingOperator >( views: I, order: EntryOrder< Key, Val >, ring: &RingOperator )
+        -> SumOracleManyView< < I::Item as IntoIterator >::IntoIter, Key, Val, RingOperator >
+
+    where   I:                                  IntoIterator,
+            I::Item:                            IntoIterator,
+            < I::Item as IntoIterator >::Item:  KeyValGet< Key = Key, Val = Val >,
+            RingOperator:                       Clone + Semiring< Val >,
+            Key:                                Clone + PartialEq,
+            Val:                                Clone,
+{
+    let views   =   views.into_iter()
+                        .map( |view| view.into_iter().map( to_tuple as ToTuple< _, Key, Val > ) );
+    hit_merge_by( views, order ).simplify( ring.clone() )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  SUM OF TWO ORACLES
+//  ---------------------------------------------------------------------------
+
+
+/// The sum of two matrix oracles, computed lazily one view at a time.
+///
+/// The addends may have different types, but must have the same key and coefficient types.  See
+/// the [module documentation](self) for details.
+#[derive(Clone, Debug)]
+pub struct SumOracle< A, B, RingOperator > {
+    a:      A,
+    b:      B,
+    ring:   RingOperator,
+}
+
+impl    < A, B, RingOperator >
+        SumOracle
+        < A, B, RingOperator >
+{
+    /// Create the lazy sum `a + b`.
+    pub fn new( a: A, b: B, ring: RingOperator ) -> Self { SumOracle{ a, b, ring } }
+
+    /// The first addend.
+    pub fn a( &self ) -> &A { &self.a }
+
+    /// The second addend.
+    pub fn b( &self ) -> &B { &self.b }
+}
+
+impl     < A, B, RingOperator >
+        WhichMajor
+        for
+        SumOracle < A, B, RingOperator >
+
+        where   A:  WhichMajor,
+{ fn major_dimension( &self ) -> MajorDimension { self.a.major_dimension() } }
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, A, B, RingOperator, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        SumOracle < A, B, RingOperator >
+
+        where   A:              OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                B:              OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+                < A::ViewMajorAscend as IntoIterator >::IntoIter:    'a,
+                A::PairMajorAscend:    'a,
+                < B::ViewMajorAscend as IntoIterator >::IntoIter:    'a,
+                B::PairMajorAscend:    'a,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   SumOracleView< < A::ViewMajorAscend as IntoIterator >::IntoIter, < B::ViewMajorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        sum_two( self.a.view_major_ascend( index.clone() ), self.b.view_major_ascend( index ), ascending, &self.ring )
+    }
+}
+
+impl     < 'a, A, B, RingOperator, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracle < A, B, RingOperator >
+
+        where   A:              OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                B:              OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   SumOracleView< < A::ViewMajorAscend as IntoIterator >::IntoIter, < B::ViewMajorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        sum_two( self.a.view_major_ascend( index.clone() ), self.b.view_major_ascend( index ), ascending, &self.ring )
+    }
+}
+
+impl     < 'a, A, B, RingOperator, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracle < A, B, RingOperator >
+
+        where   A:              OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                B:              OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   SumOracleView< < A::ViewMajorDescend as IntoIterator >::IntoIter, < B::ViewMajorDescend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        sum_two( self.a.view_major_descend( index.clone() ), self.b.view_major_descend( index ), descending, &self.ring )
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, A, B, RingOperator, MajKey, MinKey, Val >
+        OracleMinor < 'a, MajKey, MinKey, Val >
+        for
+        SumOracle < A, B, RingOperator >
+
+        where   A:              OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                B:              OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMinor =   ( MinKey, Val );
+    type ViewMinor =   SumOracleView< < A::ViewMinorAscend as IntoIterator >::IntoIter, < B::ViewMinorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        sum_two( self.a.view_minor_ascend( index.clone() ), self.b.view_minor_ascend( index ), ascending, &self.ring )
+    }
+}
+
+impl     < 'a, A, B, RingOperator, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracle < A, B, RingOperator >
+
+        where   A:              OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                B:              OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMinorAscend =   ( MinKey, Val );
+    type ViewMinorAscend =   SumOracleView< < A::ViewMinorAscend as IntoIterator >::IntoIter, < B::ViewMinorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        sum_two( self.a.view_minor_ascend( index.clone() ), self.b.view_minor_ascend( index ), ascending, &self.ring )
+    }
+}
+
+impl     < 'a, A, B, RingOperator, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracle < A, B, RingOperator >
+
+        where   A:              OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                B:              OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMinorDescend =   ( MinKey, Val );
+    type ViewMinorDescend =   SumOracleView< < A::ViewMinorDescend as IntoIterator >::IntoIter, < B::ViewMinorDescend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        sum_two( self.a.view_minor_descend( index.clone() ), self.b.view_minor_descend( index ), descending, &self.ring )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  SUM OF MANY ORACLES
+//  ---------------------------------------------------------------------------
+
+
+/// The sum of any number of matrix oracles of the same type, computed lazily one view at a time.
+///
+/// Views of the addends are merged with a heap (see
+/// [`hit_merge_by`](crate::utilities::iterators::hit_merge::hit_merge_by)).  See the
+/// [module documentation](self) for details.  The sum of zero addends is the zero matrix.
+#[derive(Clone, Debug)]
+pub struct SumOracleMany< Matrix, RingOperator > {
+    addends:    Vec< Matrix >,
+    ring:       RingOperator,
+}
+
+impl    < Matrix, RingOperator >
+        SumOracleMany
+        < Matrix, RingOperator >
+{
+    /// Create the lazy sum of `addends`.
+    pub fn new( addends: Vec< Matrix >, ring: RingOperator ) -> Self { SumOracleMany{ addends, ring } }
+
+    /// The addends.
+    pub fn addends( &self ) -> &[ Matrix ] { &self.addends }
+}
+
+impl    < 's, Matrix, RingOperator >
+        SumOracleMany
+        < &'s Matrix, RingOperator >
+{
+    /// Create the lazy sum of the oracles in a slice.
+    pub fn from_slice( addends: &'s [ Matrix ], ring: RingOperator ) -> Self { SumOracleMany{ addends: addends.iter().collect(), ring } }
+}
+
+impl     < Matrix, RingOperator >
+        WhichMajor
+        for
+        SumOracleMany < Matrix, RingOperator >
+
+        where   Matrix:     WhichMajor,
+{
+    /// Panics if there are no addends.
+    fn major_dimension( &self ) -> MajorDimension { self.addends.first().expect( "a sum of zero addends has no major dimension" ).major_dimension() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, RingOperator, MajKey, MinKey, Val >
+        OracleMajor < 'a, MajKey, MinKey, Val >
+        for
+        SumOracleMany < Matrix, RingOperator >
+
+        where   Matrix:         OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+                < Matrix::ViewMajorAscend as IntoIterator >::IntoIter:    'a,
+                Matrix::PairMajorAscend:    'a,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   SumOracleManyView< < Matrix::ViewMajorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        sum_many( self.addends.iter().map( |addend| addend.view_major_ascend( index.clone() ) ), strictly_ascending, &self.ring )
+    }
+}
+
+impl     < 'a, Matrix, RingOperator, MajKey, MinKey, Val >
+        OracleMajorAscend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracleMany < Matrix, RingOperator >
+
+        where   Matrix:         OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   SumOracleManyView< < Matrix::ViewMajorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        sum_many( self.addends.iter().map( |addend| addend.view_major_ascend( index.clone() ) ), strictly_ascending, &self.ring )
+    }
+}
+
+impl     < 'a, Matrix, RingOperator, MajKey, MinKey, Val >
+        OracleMajorDescend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracleMany < Matrix, RingOperator >
+
+        where   Matrix:         OracleMajorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   SumOracleManyView< < Matrix::ViewMajorDescend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        sum_many( self.addends.iter().map( |addend| addend.view_major_descend( index.clone() ) ), strictly_descending, &self.ring )
+    }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, Matrix, RingOperator, MajKey, MinKey, Val >
+        OracleMinor < 'a, MajKey, MinKey, Val >
+        for
+        SumOracleMany < Matrix, RingOperator >
+
+        where   Matrix:         OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMinor =   ( MinKey, Val );
+    type ViewMinor =   SumOracleManyView< < Matrix::ViewMinorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        sum_many( self.addends.iter().map( |addend| addend.view_minor_ascend( index.clone() ) ), strictly_ascending, &self.ring )
+    }
+}
+
+impl     < 'a, Matrix, RingOperator, MajKey, MinKey, Val >
+        OracleMinorAscend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracleMany < Matrix, RingOperator >
+
+        where   Matrix:         OracleMinorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMinorAscend =   ( MinKey, Val );
+    type ViewMinorAscend =   SumOracleManyView< < Matrix::ViewMinorAscend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        sum_many( self.addends.iter().map( |addend| addend.view_minor_ascend( index.clone() ) ), strictly_ascending, &self.ring )
+    }
+}
+
+impl     < 'a, Matrix, RingOperator, MajKey, MinKey, Val >
+        OracleMinorDescend < 'a, MajKey, MinKey, Val >
+        for
+        SumOracleMany < Matrix, RingOperator >
+
+        where   Matrix:         OracleMinorDescend< 'a, MajKey, MinKey, Val >,
+                MajKey:         Clone,
+                MinKey:         'a + Clone + PartialOrd,
+                Val:            'a + Clone,
+                RingOperator:   'a + Clone + Semiring< Val >,
+{
+    type PairMinorDescend =   ( MinKey, Val );
+    type ViewMinorDescend =   SumOracleManyView< < Matrix::ViewMinorDescend as IntoIterator >::IntoIter, MinKey, Val, RingOperator >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        sum_many( self.addends.iter().map( |addend| addend.view_minor_descend( index.clone() ) ), strictly_descending, &self.ring )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::dense::DenseMatrix;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_sum_views_agree_with_dense_sum() {
+        let ring        =   NativeRing::<i64>::new();
+        let entries     =   [       vec![ vec![ 1, 0, 2 ], vec![ 0, 3, 0 ] ],
+                                    vec![ vec![ -1, 4, 0 ], vec![ 5, -3, 0 ] ],
+                                    vec![ vec![ 0, 1, -2 ], vec![ 0, 0, 7 ] ], ];
+        let addends: Vec< _ >   =   entries.iter()
+                                        .map( |x| DenseMatrix::from_vec_of_vecs( MajorDimension::Row, x.clone(), ring.clone() ) )
+                                        .collect();
+        let total       =   DenseMatrix::from_vec_of_vecs(
+                                MajorDimension::Row,
+                                vec![ vec![ 0, 5, 0 ], vec![ 5, 0, 7 ] ],
+                                ring.clone(),
+                            );
+        let pair        =   DenseMatrix::from_vec_of_vecs(
+                                MajorDimension::Row,
+                                vec![ vec![ 0, 4, 2 ], vec![ 5, 0, 0 ] ],
+                                ring.clone(),
+                            );
+
+        let sum_two     =   SumOracle::new( &addends[ 0 ], &addends[ 1 ], ring.clone() );
+        let sum_all     =   SumOracleMany::from_slice( &addends, ring.clone() );
+        let sum_none    =   SumOracleMany::< &DenseMatrix< i64, NativeRing< i64 > >, _ >::new( vec![], ring );
+        for i in 0 .. 2 {
+            assert!( sum_two.view_major( i ).eq( pair.view_major( i ) ) );
+            assert!( sum_two.view_major_ascend( i ).eq( pair.view_major_ascend( i ) ) );
+            assert!( sum_two.view_major_descend( i ).eq( pair.view_major_descend( i ) ) );
+            assert!( sum_all.view_major( i ).eq( total.view_major( i ) ) );
+            assert!( sum_all.view_major_ascend( i ).eq( total.view_major_ascend( i ) ) );
+            assert!( sum_all.view_major_descend( i ).eq( total.view_major_descend( i ) ) );
+            assert_eq!( sum_none.view_major_ascend( i ).count(), 0 );
+        }
+        for j in 0 .. 3 {
+            assert!( sum_two.view_minor( j ).eq( pair.view_minor( j ) ) );
+            assert!( sum_two.view_minor_ascend( j ).eq( pair.view_minor_ascend( j ) ) );
+            assert!( sum_two.view_minor_descend( j ).eq( pair.view_minor_descend( j ) ) );
+            assert!( sum_all.view_minor( j ).eq( total.view_minor( j ) ) );
+            assert!( sum_all.view_minor_ascend( j ).eq( total.view_minor_ascend( j ) ) );
+            assert!( sum_all.view_minor_descend( j ).eq( total.view_minor_descend( j ) ) );
+        }
+    }
+}
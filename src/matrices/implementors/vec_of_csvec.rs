@@ -1,36 +1,451 @@
+//! Compressed sparse row (or column) matrices.
+//!
+//! A [`VecCsv`] stores the nonzero entries of a matrix in three flat vectors:
+//!
+//! - `min_ind` and `snz_val` hold the minor keys and coefficients of every structural nonzero,
+//!   listed one major view after another, and
+//! - `major_ptr` holds the offsets of the major views, so that major view `i` consists of the
+//!   entries in positions `major_ptr[i] .. major_ptr[i+1]`.
+//!
+//! If the major dimension is `Row` this is the usual compressed sparse row (CSR) format; if it
+//! is `Col` it is the compressed sparse column (CSC) format.  Minor keys can have any type, while
+//! major keys are always integers `0 .. num_major`.
 
 use crate::matrices::matrix_oracle::{   OracleMajor,
                                         OracleMajorAscend,
                                         OracleMajorDescend,
-                                        OracleMinor, 
-                                        OracleMinorAscend,
-                                        OracleMinorDescend,
+                                        OracleMajorAscendScoped,
+                                        MajorKeys,
                                         WhichMajor,
                                         MajorDimension};
-use std::iter;
+use crate::rings::ring_native::NativeDivisionRing;
+use crate::vectors::operations::{add_assign_sorted_with_buffer, axpy_f64};
+use std::iter::{Cloned, Rev, Zip};
+use std::ops::Range;
+use std::slice::Iter;
 
 
+/// A matrix in compressed sparse row/column format.
+///
+/// The entries of each major view should be sorted in strictly ascending order of minor key.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::vec_of_csvec::VecCsv;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMajorDescend};
+///
+/// // the matrix
+/// //  [ 1  0  2 ]
+/// //  [ 0  0  0 ]
+/// //  [ 0  3  0 ]
+/// let matrix  =   VecCsv::new(
+///                     MajorDimension::Row,
+///                     vec![ 0, 2, 2, 3 ],     // major_ptr
+///                     vec![ 0, 2, 1 ],        // min_ind
+///                     vec![ 1, 2, 3 ],        // snz_val
+///                 );
+///
+/// assert_eq!( matrix.view_major_ascend( 0 ).collect::<Vec<_>>(),  vec![ (0, 1), (2, 2) ] );
+/// assert_eq!( matrix.view_major_descend( 0 ).collect::<Vec<_>>(), vec![ (2, 2), (0, 1) ] );
+/// assert_eq!( matrix.view_major_ascend( 1 ).count(), 0 );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct VecCsv< MinKey, SnzVal >
 {
-    major_dimension: MajorDimension, 
-    min_ind: Vec< Vec< MinKey > > ,
-    snz_val: Vec< Vec< SnzVal > >
+    major_dimension:    MajorDimension,
+    major_ptr:          Vec< usize >,
+    min_ind:            Vec< MinKey >,
+    snz_val:            Vec< SnzVal >,
 }
 
 
 impl    < MinKey, SnzVal >
-        VecOfVec 
-        < MinKey, SnzVal > 
+        VecCsv
+        < MinKey, SnzVal >
 {
-    // Make new (empty) VecOfVec. 
-    pub fn new( major_dimension: MajorDimension ) -> Self  
+    /// Create a new matrix from its raw parts.
+    ///
+    /// Panics if `major_ptr` is empty, does not start with 0, is not monotone, or does not end
+    /// with the number of structural nonzeros; or if `min_ind` and `snz_val` have different lengths.
+    pub fn new(
+                major_dimension:    MajorDimension,
+                major_ptr:          Vec< usize >,
+                min_ind:            Vec< MinKey >,
+                snz_val:            Vec< SnzVal >,
+            )
+            -> Self
     {
-        atrixOracle { scalar: scalar,
-                             major_dimension: major_dimension,
-                             phantom: PhantomData 
-                            }
+        assert_eq!( min_ind.len(), snz_val.len(), "min_ind and snz_val must have the same length" );
+        assert_eq!( major_ptr.first(), Some( &0 ), "major_ptr must start with 0" );
+        assert_eq!( major_ptr.last(), Some( &min_ind.len() ), "major_ptr must end with the number of structural nonzeros" );
+        assert!( major_ptr.windows( 2 ).all( |w| w[0] <= w[1] ), "major_ptr must be monotone" );
+        VecCsv{ major_dimension, major_ptr, min_ind, snz_val }
+    }
+
+    /// Create a new matrix from a list of triplets `(major_key, minor_key, coefficient)`.
+    ///
+    /// For a row-major matrix each triplet has the form `(row, col, coefficient)`; for a
+    /// column-major matrix it has the form `(col, row, coefficient)`.  Triplets can appear in
+    /// any order, but no two triplets should have the same pair of keys.  Panics if a major key
+    /// is `>= num_major`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_csvec::VecCsv;
+    /// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+    ///
+    /// let matrix  =   VecCsv::from_triplets(
+    ///                     MajorDimension::Row,
+    ///                     3,
+    ///                     vec![ (2, 1, 3.), (0, 2, 2.), (0, 0, 1.) ],
+    ///                 );
+    ///
+    /// assert_eq!( matrix.major_ptr(), &vec![ 0, 2, 2, 3 ] );
+    /// assert_eq!( matrix.view_major( 0 ).collect::<Vec<_>>(), vec![ (0, 1.), (2, 2.) ] );
+    /// ```
+    pub fn from_triplets< I >(
+                major_dimension:    MajorDimension,
+                num_major:          usize,
+                triplets:           I,
+            )
+            -> Self
+
+        where   I:          IntoIterator< Item = ( usize, MinKey, SnzVal ) >,
+                MinKey:     PartialOrd,
+    {
+        let mut triplets: Vec< _ >  =   triplets.into_iter().collect();
+        triplets.sort_by(   |a, b|
+                            a.0.cmp( &b.0 ).then_with(
+                                || a.1.partial_cmp( &b.1 ).expect( "minor keys must be comparable" )
+                            )
+                        );
+
+        let mut major_ptr   =   vec![ 0; num_major + 1 ];
+        for triplet in triplets.iter() {
+            assert!( triplet.0 < num_major, "major key {} exceeds the number of major views", triplet.0 );
+            major_ptr[ triplet.0 + 1 ] += 1;
+        }
+        for i in 0 .. num_major { major_ptr[ i + 1 ] += major_ptr[ i ] }
+
+        let mut min_ind     =   Vec::with_capacity( triplets.len() );
+        let mut snz_val     =   Vec::with_capacity( triplets.len() );
+        for ( _, key, val ) in triplets {
+            min_ind.push( key );
+            snz_val.push( val );
+        }
+        VecCsv{ major_dimension, major_ptr, min_ind, snz_val }
+    }
+
+    /// The offsets of the major views.
+    pub fn major_ptr( &self ) -> &Vec< usize > { &self.major_ptr }
+
+    /// The minor keys of all structural nonzeros.
+    pub fn min_ind( &self ) -> &Vec< MinKey > { &self.min_ind }
+
+    /// The coefficients of all structural nonzeros.
+    pub fn snz_val( &self ) -> &Vec< SnzVal > { &self.snz_val }
+
+    /// The number of major views.
+    pub fn num_major( &self ) -> usize { self.major_ptr.len() - 1 }
+
+    /// The number of structural nonzeros.
+    pub fn num_snz( &self ) -> usize { self.min_ind.len() }
+
+    /// Decompose the matrix into `(major_dimension, major_ptr, min_ind, snz_val)`.
+    pub fn into_raw_parts( self ) -> ( MajorDimension, Vec< usize >, Vec< MinKey >, Vec< SnzVal > ) {
+        ( self.major_dimension, self.major_ptr, self.min_ind, self.snz_val )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  ROW OPERATIONS ON FLOATING POINT MATRICES
+//  ---------------------------------------------------------------------------
+
+
+impl < MinKey > VecCsv< MinKey, f64 >
+    where   MinKey:     Clone + PartialOrd,
+{
+    /// Replace major view `target` with `target + alpha * source`.
+    ///
+    /// If the two views have the same minor keys (as happens inside dense blocks, which dominate
+    /// the cost of reducing large floating point matrices) the coefficients are updated in place
+    /// with the vectorized kernel [`axpy_f64`].  Otherwise the views are merged as sparse vectors,
+    /// exactly as over any other ring, and the new view is spliced into the flat arrays, which
+    /// costs time proportional to the number of structural nonzeros that follow it.  In both cases
+    /// coefficients that become exactly `0.` are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_csvec::VecCsv;
+    /// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+    ///
+    /// let mut matrix  =   VecCsv::from_triplets(
+    ///                         MajorDimension::Row,
+    ///                         3,
+    ///                         vec![ (0, 0, 1.), (0, 1, 2.), (1, 0, 2.), (1, 1, 1.), (2, 2, 5.) ],
+    ///                     );
+    /// matrix.add_scaled_view( 1, 0, -2. );       // same pattern: updated in place
+    /// assert_eq!( matrix.view_major( 1 ).collect::<Vec<_>>(), vec![ (1, -3.) ] );
+    /// matrix.add_scaled_view( 2, 0, 1. );        // different patterns: merged
+    /// assert_eq!( matrix.view_major( 2 ).collect::<Vec<_>>(), vec![ (0, 1.), (1, 2.), (2, 5.) ] );
+    /// ```
+    pub fn add_scaled_view( &mut self, target: usize, source: usize, alpha: f64 ) {
+        let target_range    =   self.major_ptr[ target ] .. self.major_ptr[ target + 1 ];
+        let source_range    =   self.major_ptr[ source ] .. self.major_ptr[ source + 1 ];
+
+        if target == source {
+            self.snz_val[ target_range ].iter_mut().for_each( |val| *val *= 1. + alpha );
+        } else if self.min_ind[ target_range.clone() ] == self.min_ind[ source_range.clone() ] {
+            // fast path: the patterns agree, so only coefficients change
+            let ( target_vals, source_vals )    =   if target < source {
+                                                        let ( head, tail )  =   self.snz_val.split_at_mut( source_range.start );
+                                                        ( &mut head[ target_range ], &tail[ .. source_range.len() ] )
+                                                    } else {
+                                                        let ( head, tail )  =   self.snz_val.split_at_mut( target_range.start );
+                                                        ( &mut tail[ .. target_range.len() ], &head[ source_range ] )
+                                                    };
+            axpy_f64( target_vals, alpha, source_vals );
+        } else {
+            let mut merged: Vec< _ >    =   self.view( target_range ).collect();
+            let source_view: Vec< _ >   =   self.view( source_range ).collect();
+            add_assign_sorted_with_buffer( &mut merged, &source_view, alpha, NativeDivisionRing::< f64 >::new(), &mut Vec::new() );
+            self.replace_view( target, merged );
+            return
+        }
+
+        // drop coefficients that cancelled
+        let target_range    =   self.major_ptr[ target ] .. self.major_ptr[ target + 1 ];
+        if self.snz_val[ target_range.clone() ].contains( &0. ) {
+            let entries     =   self.view( target_range ).filter( |entry| entry.1 != 0. ).collect();
+            self.replace_view( target, entries );
+        }
+    }
+
+    /// The entries in positions `range` of the flat arrays.
+    fn view( &self, range: Range< usize > ) -> impl Iterator< Item = ( MinKey, f64 ) > + '_ {
+        self.min_ind[ range.clone() ].iter().cloned().zip( self.snz_val[ range ].iter().cloned() )
+    }
+
+    /// Replace the entries of major view `major` with `entries`, shifting the views that follow.
+    fn replace_view( &mut self, major: usize, entries: Vec< ( MinKey, f64 ) > ) {
+        let range           =   self.major_ptr[ major ] .. self.major_ptr[ major + 1 ];
+        let old_len         =   range.len();
+        let new_len         =   entries.len();
+        let ( keys, vals ): ( Vec< _ >, Vec< _ > )  =   entries.into_iter().unzip();
+        self.min_ind.splice( range.clone(), keys );
+        self.snz_val.splice( range, vals );
+        for ptr in self.major_ptr[ major + 1 .. ].iter_mut() { *ptr = *ptr + new_len - old_len }
     }
 }
 
 
+//  ---------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------
+
+
+//  WHICH MAJOR
+//
+
+impl     < MinKey, SnzVal >
+        WhichMajor
+        for
+        VecCsv < MinKey, SnzVal >
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+
+//  MAJOR KEYS
+//
+
+impl     < 'a, MinKey, SnzVal >
+        MajorKeys < 'a, usize >
+        for
+        VecCsv < MinKey, SnzVal >
+{
+    type MajorKeysIter = Range< usize >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { 0 .. self.num_major() }
+
+    fn num_major_keys( &self ) -> usize { self.num_major() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+//  OracleMajor
+//
+impl     < 'a, MinKey, SnzVal >
+        OracleMajor < 'a, usize, MinKey, SnzVal >
+        for
+        VecCsv < MinKey, SnzVal >
+
+        where   MinKey: 'a + Clone,
+                SnzVal: 'a + Clone,
+{
+    type PairMajor =   ( MinKey, SnzVal );
+    type ViewMajor =   Zip< Cloned< Iter< 'a, MinKey > >, Cloned< Iter< 'a, SnzVal > > >;
 
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor
+    {
+        let range   =   self.major_ptr[ index ] .. self.major_ptr[ index + 1 ];
+        self.min_ind[ range.clone() ].iter().cloned().zip( self.snz_val[ range ].iter().cloned() )
+    }
+}
+
+//  OracleMajorAscend
+//
+impl     < 'a, MinKey, SnzVal >
+        OracleMajorAscend < 'a, usize, MinKey, SnzVal >
+        for
+        VecCsv < MinKey, SnzVal >
+
+        where   MinKey: 'a + Clone,
+                SnzVal: 'a + Clone,
+{
+    type PairMajorAscend =   ( MinKey, SnzVal );
+    type ViewMajorAscend =   Zip< Cloned< Iter< 'a, MinKey > >, Cloned< Iter< 'a, SnzVal > > >;
+
+    /// Assumes that entries in each major view are sorted in ascending order.
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend
+    {
+        self.view_major( index )
+    }
+}
+
+//  OracleMajorAscendScoped
+//
+impl     < 'a, MinKey, SnzVal >
+        OracleMajorAscendScoped < 'a, usize, MinKey, SnzVal >
+        for
+        VecCsv < MinKey, SnzVal >
+
+        where   MinKey: 'a + Clone + PartialOrd,
+                SnzVal: 'a + Clone,
+{
+    type PairMajorAscendScoped =   ( MinKey, SnzVal );
+    type ViewMajorAscendScoped =   Zip< Cloned< Iter< 'a, MinKey > >, Cloned< Iter< 'a, SnzVal > > >;
+
+    /// Assumes that entries in each major view are sorted in ascending order; the ends of the
+    /// range are found by binary search.
+    fn view_major_ascend_scoped<'b: 'a>( &'b self, index: usize, min: MinKey, max: MinKey ) -> Self::ViewMajorAscendScoped
+    {
+        let offset  =   self.major_ptr[ index ];
+        let keys    =   &self.min_ind[ offset .. self.major_ptr[ index + 1 ] ];
+        let start   =   offset + keys.partition_point( |key| *key < min );
+        let end     =   ( offset + keys.partition_point( |key| *key < max ) ).max( start );
+        self.min_ind[ start .. end ].iter().cloned().zip( self.snz_val[ start .. end ].iter().cloned() )
+    }
+}
+
+//  OracleMajorDescend
+//
+impl     < 'a, MinKey, SnzVal >
+        OracleMajorDescend < 'a, usize, MinKey, SnzVal >
+        for
+        VecCsv < MinKey, SnzVal >
+
+        where   MinKey: 'a + Clone,
+                SnzVal: 'a + Clone,
+{
+    type PairMajorDescend =   ( MinKey, SnzVal );
+    type ViewMajorDescend =   Zip< Cloned< Rev< Iter< 'a, MinKey > > >, Cloned< Rev< Iter< 'a, SnzVal > > > >;
+
+    /// Assumes that entries in each major view are sorted in ascending order.
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend
+    {
+        let range   =   self.major_ptr[ index ] .. self.major_ptr[ index + 1 ];
+        self.min_ind[ range.clone() ].iter().rev().cloned().zip( self.snz_val[ range ].iter().rev().cloned() )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+
+    #[test]
+    fn test_vec_csv_views_agree_with_vec_of_vec() {
+
+        let vec_of_vec  =   vec![
+                                vec![ (0, 1.), (3, 4.)          ],
+                                vec![                           ],
+                                vec![ (1, 2.), (2, 3.), (3, 5.) ],
+                                vec![ (2, 6.)                   ],
+                            ];
+        let triplets    =   vec_of_vec.iter()
+                                .enumerate()
+                                .flat_map( |(i, vec)| vec.iter().map( move |(j, x)| ( i, *j, *x ) ) )
+                                .rev(); // put the triplets out of order
+        let csv         =   VecCsv::from_triplets( MajorDimension::Row, 4, triplets );
+        let vov         =   VecOfVec::new( MajorDimension::Row, vec_of_vec );
+
+        assert_eq!( csv.num_major(), 4 );
+        assert_eq!( csv.num_snz(), 6 );
+        for i in 0 .. 4 {
+            assert!( csv.view_major( i ).eq( vov.view_major( i ) ) );
+            assert!( csv.view_major_ascend( i ).eq( vov.view_major_ascend( i ) ) );
+            assert!( csv.view_major_descend( i ).eq( vov.view_major_descend( i ) ) );
+            for min in 0 .. 5 {
+                for max in 0 .. 5 {
+                    let clipped     =   vov.view_major_ascend( i ).filter( |entry| min <= entry.0 && entry.0 < max );
+                    assert!( csv.view_major_ascend_scoped( i, min, max ).eq( clipped.clone() ) );
+                    assert!( vov.view_major_ascend_scoped( i, min, max ).eq( clipped ) );
+                }
+            }
+        }
+
+        // reassembling the raw parts gives the same matrix
+        let (major_dimension, major_ptr, min_ind, snz_val)  =   csv.clone().into_raw_parts();
+        assert_eq!( VecCsv::new( major_dimension, major_ptr, min_ind, snz_val ), csv );
+    }
+
+    #[test]
+    fn test_add_scaled_view() {
+        // a dense block of width 19, which spans several chunks of the vectorized kernel
+        let width       =   19;
+        let triplets    =   ( 0 .. 3 ).flat_map( |i| ( 0 .. width ).map( move |j| ( i, j, ( i * width + j ) as f64 ) ) )
+                                .chain( vec![ ( 3, 0, 1. ), ( 3, 25, 1. ) ] );
+        let mut csv     =   VecCsv::from_triplets( MajorDimension::Row, 4, triplets );
+
+        // same pattern, source after target and before target
+        csv.add_scaled_view( 0, 1, 0.5 );
+        csv.add_scaled_view( 2, 1, -1. );
+        assert!( csv.view_major( 0 ).eq( ( 0 .. width ).map( |j| ( j, j as f64 + 0.5 * ( width + j ) as f64 ) ) ) );
+        assert!( csv.view_major( 2 ).eq( ( 0 .. width ).map( |j| ( j, width as f64 ) ) ) );
+
+        // cancellation drops entries, and shifts the views that follow
+        csv.add_scaled_view( 1, 1, -1. );
+        assert_eq!( csv.view_major( 1 ).count(), 0 );
+        assert_eq!( csv.major_ptr(), &vec![ 0, width, width, 2 * width, 2 * width + 2 ] );
+
+        // different patterns
+        csv.add_scaled_view( 3, 2, -1. / width as f64 );
+        let expected: Vec< _ >  =   ( 1 .. width ).map( |j| ( j, -1. ) ).chain( vec![ ( 25, 1. ) ] ).collect();
+        assert!( csv.view_major( 3 ).eq( expected ) );
+        assert_eq!( csv.num_snz(), 3 * width );
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn test_serde_round_trip() {
+        let vov         =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (0, 1), (2, 3) ], vec![], vec![ (1, 2) ] ] );
+        let json        =   serde_json::to_string( &vov ).unwrap();
+        let read: VecOfVec< (usize, i64) >  =   serde_json::from_str( &json ).unwrap();
+        assert_eq!( read.major_dimension, MajorDimension::Col );
+        assert_eq!( read.vec_of_vec, vov.vec_of_vec );
+
+        let csv         =   VecCsv::new( MajorDimension::Row, vec![ 0, 2, 2, 3 ], vec![ 0, 2, 1 ], vec![ 1., 2., 3. ] );
+        let json        =   serde_json::to_string( &csv ).unwrap();
+        assert_eq!( serde_json::from_str::< VecCsv< usize, f64 > >( &json ).unwrap(), csv );
+    }
+}
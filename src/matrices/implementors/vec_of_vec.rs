@@ -2,14 +2,24 @@
 use crate::matrices::matrix_oracle::{   OracleMajor,
                                         OracleMajorAscend,
                                         OracleMajorDescend,
+                                        OracleMajorAscendScoped,
                                         OracleMinor, 
                                         OracleMinorAscend,
                                         OracleMinorDescend,
+                                        OracleSet,
+                                        MajorKeys,
                                         WhichMajor,
                                         MajorDimension};
 use crate::vector_entries::vector_entries::KeyValGet;
+use crate::utilities::sequences_and_ordinals::BiMapSequential;
+use crate::utilities::reordering::reverse_cuthill_mckee;
+use crate::rings::ring::Semiring;
+use crate::vectors::operations::{add_assign_sorted_with_buffer, equal_modulo_zeros, scale_in_place};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
 use std::marker::PhantomData;
 use std::iter::{Rev, Cloned};
+use std::ops::Range;
 
 
 /// A vector of vectors, representing a sparse matrix.  
@@ -38,6 +48,7 @@ use std::iter::{Rev, Cloned};
 /// 
 /// 
 /// ```
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct VecOfVec
 
     < 'a, IndexCoeffPair >
@@ -48,6 +59,7 @@ pub struct VecOfVec
 {
     pub major_dimension: MajorDimension, 
     pub vec_of_vec: Vec< Vec< IndexCoeffPair > >,
+    #[cfg_attr(feature = "serialize", serde(skip))]
     pub phantom: PhantomData<&'a IndexCoeffPair >
 }
 
@@ -67,6 +79,500 @@ impl    < 'a, IndexCoeffPair >
                     phantom: PhantomData 
                 }
     }
+
+    /// The number of stored major views.
+    pub fn num_views( &self ) -> usize { self.vec_of_vec.len() }
+
+    /// Delete the major views with the given keys.
+    /// 
+    /// If `renumber == false` then each deleted view is replaced by an empty vector, so the keys
+    /// of the remaining views do not change; in this case the function returns `None`.
+    /// 
+    /// If `renumber == true` then the deleted views are removed, and the remaining views are
+    /// renumbered `0, 1, 2, ..` (preserving their relative order).  In this case the function
+    /// returns `Some( bimap )`, where `bimap` is the bijection `new_key <-> old_key`.
+    /// 
+    /// Keys that exceed the number of major views are ignored.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    /// 
+    /// let mut matrix  =   VecOfVec::new(
+    ///                         MajorDimension::Row,
+    ///                         vec![ vec![(0,1.)], vec![(1,1.)], vec![(2,1.)] ],
+    ///                     );
+    /// let bimap       =   matrix.delete_major_views( vec![1], true ).unwrap();
+    /// 
+    /// assert_eq!( matrix.vec_of_vec, vec![ vec![(0,1.)], vec![(2,1.)] ] );
+    /// assert_eq!( bimap.val( 1 ), Some( 2 ) ); // new key 1 corresponds to old key 2
+    /// ```
+    pub fn delete_major_views< I: IntoIterator< Item = usize > >( &mut self, keys: I, renumber: bool ) 
+        -> Option< BiMapSequential< usize > > 
+    {
+        let mut delete      =   vec![ false; self.vec_of_vec.len() ];
+        for key in keys { if key < delete.len() { delete[ key ] = true } }
+
+        if ! renumber {
+            for (key, deleted) in delete.iter().enumerate() {
+                if *deleted { self.vec_of_vec[ key ].clear() }
+            }
+            return None
+        }
+
+        let old_keys: Vec< usize >  =   ( 0 .. delete.len() ).filter( |x| ! delete[ *x ] ).collect();
+        let mut key                 =   0;
+        self.vec_of_vec.retain( |_| { key += 1; ! delete[ key - 1 ] } );
+        Some( BiMapSequential::from_vec( old_keys ) )
+    }
+
+    /// Remove every empty major view, and renumber the remaining views `0, 1, 2, ..`
+    /// (preserving their relative order).
+    /// 
+    /// Returns the bijection `new_key <-> old_key`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    /// 
+    /// let mut matrix  =   VecOfVec::new(
+    ///                         MajorDimension::Row,
+    ///                         vec![ vec![], vec![(1,1.)], vec![], vec![(2,1.)] ],
+    ///                     );
+    /// let bimap       =   matrix.compact();
+    /// 
+    /// assert_eq!( matrix.vec_of_vec, vec![ vec![(1,1.)], vec![(2,1.)] ] );
+    /// assert_eq!( bimap.ord_to_val, vec![ 1, 3 ] );
+    /// ```
+    pub fn compact( &mut self ) -> BiMapSequential< usize > {
+        let empty: Vec< usize >     =   ( 0 .. self.vec_of_vec.len() )
+                                            .filter( |x| self.vec_of_vec[ *x ].is_empty() )
+                                            .collect();
+        self.delete_major_views( empty, true ).unwrap()
+    }
+}
+
+impl    < 'a, MinKey, Val >
+        VecOfVec 
+        < 'a, (MinKey, Val) > 
+        
+        where   MinKey:     Clone + PartialEq,
+                Val:        Clone,
+
+{
+    /// Append a major view, and return its key.
+    ///
+    /// The view should be sorted in strictly ascending order of minor key; this is checked in
+    /// debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    ///
+    /// let mut matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![(0,1.)] ] );
+    /// assert_eq!( matrix.push_vec( vec![(1,2.), (3,1.)] ), 1 );
+    /// assert_eq!( matrix.num_views(), 2 );
+    /// ```
+    pub fn push_vec( &mut self, vec: Vec< (MinKey, Val) > ) -> usize
+        where   MinKey:     PartialOrd,
+    {
+        debug_assert!( is_strictly_ascending( &vec ), "major views must be sorted in strictly ascending order of minor key" );
+        self.vec_of_vec.push( vec );
+        self.vec_of_vec.len() - 1
+    }
+
+    /// Store `val` in position `(major, minor)`, keeping major view `major` sorted, and return
+    /// the coefficient previously stored there, if any.
+    ///
+    /// Panics if `major` is not the key of a stored view.  The view should be sorted in strictly
+    /// ascending order of minor key; this is checked in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    ///
+    /// let mut matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![(0,1), (3,1)] ] );
+    /// assert_eq!( matrix.insert_entry( 0, 2, 5 ), None );
+    /// assert_eq!( matrix.insert_entry( 0, 3, 7 ), Some( 1 ) );
+    /// assert_eq!( matrix.vec_of_vec, vec![ vec![(0,1), (2,5), (3,7)] ] );
+    /// ```
+    pub fn insert_entry( &mut self, major: usize, minor: MinKey, val: Val ) -> Option< Val >
+        where   MinKey:     PartialOrd,
+    {
+        let view        =   &mut self.vec_of_vec[ major ];
+        debug_assert!( is_strictly_ascending( view ), "major view {} is not sorted in strictly ascending order of minor key", major );
+        let position    =   view.partition_point( |entry| entry.0 < minor );
+        match view.get_mut( position ) {
+            Some( entry ) if entry.0 == minor   =>  Some( std::mem::replace( &mut entry.1, val ) ),
+            _                                   =>  { view.insert( position, ( minor, val ) ); None }
+        }
+    }
+
+    /// Remove the entry in position `(major, minor)`, and return its coefficient, if any.
+    ///
+    /// Panics if `major` is not the key of a stored view.  The view should be sorted in strictly
+    /// ascending order of minor key; this is checked in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    ///
+    /// let mut matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![(0,1), (3,1)] ] );
+    /// assert_eq!( matrix.remove_entry( 0, &3 ), Some( 1 ) );
+    /// assert_eq!( matrix.remove_entry( 0, &3 ), None );
+    /// assert_eq!( matrix.vec_of_vec, vec![ vec![(0,1)] ] );
+    /// ```
+    pub fn remove_entry( &mut self, major: usize, minor: &MinKey ) -> Option< Val >
+        where   MinKey:     PartialOrd,
+    {
+        let view        =   &mut self.vec_of_vec[ major ];
+        debug_assert!( is_strictly_ascending( view ), "major view {} is not sorted in strictly ascending order of minor key", major );
+        let position    =   view.partition_point( |entry| entry.0 < *minor );
+        match view.get( position ) {
+            Some( entry ) if entry.0 == *minor  =>  Some( view.remove( position ).1 ),
+            _                                   =>  None,
+        }
+    }
+
+    /// Delete every entry whose minor key belongs to `keys`.
+    /// 
+    /// If the matrix is row-major, this deletes columns; if the matrix is column-major, this 
+    /// deletes rows.  Keys of the remaining entries are unchanged.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    /// 
+    /// let mut matrix  =   VecOfVec::new(
+    ///                         MajorDimension::Row,
+    ///                         vec![ vec![(0,1.), (1,1.)], vec![(1,1.), (2,1.)] ],
+    ///                     );
+    /// matrix.delete_minor_keys( &[1] );
+    /// 
+    /// assert_eq!( matrix.vec_of_vec, vec![ vec![(0,1.)], vec![(2,1.)] ] );
+    /// ```
+    pub fn delete_minor_keys( &mut self, keys: &[ MinKey ] ) {
+        for vec in self.vec_of_vec.iter_mut() {
+            vec.retain( |x| ! keys.contains( &x.0 ) );
+        }
+    }
+
+    /// Multiply every entry of major view `index` by `scalar`, dropping entries that become zero.
+    ///
+    /// Operates directly on the stored vector, without constructing a view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    /// use solar::rings::ring_native::NativeRing;
+    ///
+    /// let mut matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![(0,1), (2,-3)] ] );
+    /// matrix.scale_view_in_place( 0, 2, NativeRing::<i64>::new() );
+    ///
+    /// assert_eq!( matrix.vec_of_vec, vec![ vec![(0,2), (2,-6)] ] );
+    /// ```
+    pub fn scale_view_in_place< RingOperator >( &mut self, index: usize, scalar: Val, ring: RingOperator )
+        where   RingOperator:   Semiring< Val >,
+    {
+        scale_in_place( &mut self.vec_of_vec[ index ], scalar, ring );
+    }
+
+    /// Replace major view `target` with `target + scalar * source`, using `buffer` as scratch space.
+    ///
+    /// Operates directly on the stored vectors, without constructing views; see
+    /// [`add_assign_sorted_with_buffer`] for details.  Entries that become zero are dropped.  The
+    /// `buffer` can be reused across calls to avoid repeated allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    /// use solar::rings::field_prime::GF2;
+    ///
+    /// let mut matrix  =   VecOfVec::new(
+    ///                         MajorDimension::Col,
+    ///                         vec![ vec![(0,true), (1,true)], vec![(1,true), (2,true)] ],
+    ///                     );
+    /// let mut buffer  =   Vec::new();
+    /// matrix.add_view_in_place( 1, 0, true, GF2::new(), &mut buffer );
+    ///
+    /// assert_eq!( matrix.vec_of_vec[1], vec![(0,true), (2,true)] );
+    /// ```
+    pub fn add_view_in_place< RingOperator >(
+                &mut self,
+                target:     usize,
+                source:     usize,
+                scalar:     Val,
+                ring:       RingOperator,
+                buffer:     &mut Vec< (MinKey, Val) >,
+            )
+        where   RingOperator:   Semiring< Val >,
+                MinKey:         PartialOrd,
+    {
+        if target == source {
+            // target + scalar * target = ( 1 + scalar ) * target
+            let factor  =   ring.add( RingOperator::one(), scalar );
+            scale_in_place( &mut self.vec_of_vec[ target ], factor, ring );
+            return
+        }
+        let ( target_vec, source_vec )  =   if target < source {
+                                                let ( head, tail )  =   self.vec_of_vec.split_at_mut( source );
+                                                ( &mut head[ target ], &tail[ 0 ] )
+                                            } else {
+                                                let ( head, tail )  =   self.vec_of_vec.split_at_mut( target );
+                                                ( &mut tail[ 0 ], &head[ source ] )
+                                            };
+        add_assign_sorted_with_buffer( target_vec, source_vec, scalar, ring, buffer );
+    }
+
+    /// Returns `true` if `self` and `other` represent the same matrix once explicitly stored
+    /// zeros are ignored.
+    ///
+    /// The two matrices must have the same major dimension.  A missing major view (when one
+    /// matrix stores fewer views than the other) is treated as an empty view.  Each view should
+    /// be sorted in strictly ascending order of key; see
+    /// [`equal_modulo_zeros`](crate::vectors::operations::equal_modulo_zeros).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    /// use solar::rings::ring_native::NativeRing;
+    ///
+    /// let a   =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (1, 0) ], vec![ (2, 0) ] ] );
+    /// let b   =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1) ] ] );
+    ///
+    /// assert!( a.equal_modulo_zeros( &b, NativeRing::<i64>::new() ) );
+    /// ```
+    pub fn equal_modulo_zeros< RingOperator >( &self, other: &Self, ring: RingOperator ) -> bool
+        where   RingOperator:   Clone + Semiring< Val >,
+                MinKey:         PartialOrd,
+                Val:            PartialEq,
+    {
+        if self.major_dimension != other.major_dimension { return false }
+        let empty   =   Vec::new();
+        let len     =   self.vec_of_vec.len().max( other.vec_of_vec.len() );
+        ( 0 .. len ).all(   |i|
+                            equal_modulo_zeros(
+                                self.vec_of_vec.get( i ).unwrap_or( &empty ).iter().cloned(),
+                                other.vec_of_vec.get( i ).unwrap_or( &empty ).iter().cloned(),
+                                ring.clone(),
+                            )
+                        )
+    }
+
+    /// Check that every major view is sorted in strictly ascending order of minor key and, unless
+    /// `allow_explicit_zeros` is `true`, that no stored coefficient is zero.
+    ///
+    /// The oracle traits assume these invariants without checking them.  Returns the first
+    /// violation found, scanning views in order of major key and each view from left to right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::{VecOfVec, VecOfVecViolation};
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    /// use solar::rings::ring_native::NativeRing;
+    ///
+    /// let ring    =   NativeRing::<i64>::new();
+    /// let matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (2, 0) ], vec![ (3, 1), (1, 1) ] ] );
+    ///
+    /// assert_eq!( matrix.validate( ring.clone(), false ), Err( VecOfVecViolation::ExplicitZero{ major: 0, position: 1, key: 2 } ) );
+    /// assert_eq!( matrix.validate( ring, true ),  Err( VecOfVecViolation::Unsorted{ major: 1, position: 1, previous: 3, key: 1 } ) );
+    /// ```
+    pub fn validate< RingOperator >( &self, ring: RingOperator, allow_explicit_zeros: bool ) -> Result< (), VecOfVecViolation< MinKey > >
+        where   RingOperator:   Semiring< Val >,
+                MinKey:         PartialOrd,
+    {
+        for ( major, view ) in self.vec_of_vec.iter().enumerate() {
+            for ( position, ( key, val ) ) in view.iter().enumerate() {
+                if position > 0 {
+                    let previous    =   &view[ position - 1 ].0;
+                    match previous.partial_cmp( key ) {
+                        Some( Ordering::Less )  =>  {},
+                        Some( Ordering::Equal ) =>  return Err( VecOfVecViolation::DuplicateKey{ major, position, key: key.clone() } ),
+                        _                       =>  return Err( VecOfVecViolation::Unsorted{ major, position, previous: previous.clone(), key: key.clone() } ),
+                    }
+                }
+                if ! allow_explicit_zeros && ring.is_0( val.clone() ) {
+                    return Err( VecOfVecViolation::ExplicitZero{ major, position, key: key.clone() } )
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  REORDERING
+//  ---------------------------------------------------------------------------
+
+
+impl    < 'a, Val >
+        VecOfVec
+        < 'a, (usize, Val) >
+
+        where   Val:        Clone,
+{
+    /// The number of keys of the square matrix that contains `self`: one more than the largest
+    /// major or minor key.
+    fn num_square_keys( &self ) -> usize {
+        let max_minor   =   self.vec_of_vec.iter().filter_map( |view| view.last().map( |entry| entry.0 + 1 ) ).max();
+        max_minor.unwrap_or( 0 ).max( self.vec_of_vec.len() )
+    }
+
+    /// The sparsity pattern of `self + self^T`, as an undirected graph on the keys
+    /// `0 .. max( num_views, 1 + largest minor key )`.
+    ///
+    /// Returns adjacency lists, sorted and without loops, in the format used by
+    /// [`reordering`](crate::utilities::reordering).
+    pub fn symmetric_pattern( &self ) -> Vec< Vec< usize > > {
+        let mut neighbors   =   vec![ Vec::new(); self.num_square_keys() ];
+        for ( major, view ) in self.vec_of_vec.iter().enumerate() {
+            for ( minor, _ ) in view.iter() {
+                if *minor == major { continue }
+                neighbors[ major ].push( *minor );
+                neighbors[ *minor ].push( major );
+            }
+        }
+        for adjacent in neighbors.iter_mut() { adjacent.sort(); adjacent.dedup() }
+        neighbors
+    }
+
+    /// Permute the major and minor keys of `self` simultaneously, so that key `new_to_old[ i ]`
+    /// becomes key `i`.
+    ///
+    /// This is the permutation `P M P^T` of a square matrix, e.g. a boundary matrix whose rows
+    /// and columns are both indexed by cells.  The new matrix has one major view for each element
+    /// of `new_to_old` (views that do not exist in `self` are empty), and its views are sorted.
+    /// Returns the new matrix together with the bijection `new_key <-> old_key`.
+    ///
+    /// Panics if `new_to_old` is not a permutation of `0 .. n` for some `n` that exceeds every
+    /// major and minor key of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::MajorDimension;
+    ///
+    /// // the boundary matrix of an edge 2 with vertices 0 and 1, stored by column
+    /// let matrix          =   VecOfVec::new( MajorDimension::Col, vec![ vec![], vec![], vec![ (0, 1), (1, -1) ] ] );
+    /// let ( permuted, bimap ) =   matrix.reordered( &[ 2, 0, 1 ] );
+    ///
+    /// assert_eq!( permuted.vec_of_vec, vec![ vec![ (1, 1), (2, -1) ], vec![], vec![] ] );
+    /// assert_eq!( bimap.val( 0 ), Some( 2 ) );   // new key 0 corresponds to old key 2
+    /// ```
+    pub fn reordered( &self, new_to_old: &[ usize ] ) -> ( Self, BiMapSequential< usize > ) {
+        let num_keys        =   new_to_old.len();
+        assert!( num_keys >= self.num_square_keys(), "the permutation must include every major and minor key" );
+        let mut old_to_new  =   vec![ usize::MAX; num_keys ];
+        for ( new, old ) in new_to_old.iter().enumerate() {
+            assert!( *old < num_keys && old_to_new[ *old ] == usize::MAX, "`new_to_old` must be a permutation" );
+            old_to_new[ *old ]  =   new;
+        }
+
+        let vec_of_vec      =   new_to_old.iter()
+                                    .map(   |old| {
+                                                let mut view: Vec< _ >  =   self.vec_of_vec.get( *old )
+                                                                                .into_iter()
+                                                                                .flatten()
+                                                                                .map( |( minor, val )| ( old_to_new[ *minor ], val.clone() ) )
+                                                                                .collect();
+                                                view.sort_by_key( |entry| entry.0 );
+                                                view
+                                            }
+                                    )
+                                    .collect();
+        ( VecOfVec::new( self.major_dimension.clone(), vec_of_vec ), BiMapSequential::from_vec( new_to_old.to_vec() ) )
+    }
+
+    /// Permute the major and minor keys of `self` simultaneously, by the reverse Cuthill-McKee
+    /// ordering of [`symmetric_pattern`](VecOfVec::symmetric_pattern).
+    ///
+    /// Keys that interact are moved close to one another, which reduces cache misses when
+    /// reducing very large matrices.  Returns the new matrix together with the bijection
+    /// `new_key <-> old_key`; see [`reordered`](VecOfVec::reordered).
+    pub fn reordered_by_reverse_cuthill_mckee( &self ) -> ( Self, BiMapSequential< usize > ) {
+        self.reordered( &reverse_cuthill_mckee( &self.symmetric_pattern() ) )
+    }
+}
+
+
+/// A violation of the invariants of a [`VecOfVec`]; see [`VecOfVec::validate`].
+///
+/// In each variant, `major` is the key of the offending major view and `position` is the index of
+/// the offending entry within that view.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VecOfVecViolation< MinKey > {
+    /// The entry's minor key is not greater than the key of the entry before it.
+    Unsorted{ major: usize, position: usize, previous: MinKey, key: MinKey },
+    /// The entry's minor key equals the key of the entry before it.
+    DuplicateKey{ major: usize, position: usize, key: MinKey },
+    /// The entry's coefficient is zero.
+    ExplicitZero{ major: usize, position: usize, key: MinKey },
+}
+
+impl < MinKey: Debug > fmt::Display for VecOfVecViolation< MinKey > {
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        match self {
+            VecOfVecViolation::Unsorted{ major, position, previous, key }   =>
+                write!( f, "major view {}: key {:?} in position {} follows key {:?}", major, key, position, previous ),
+            VecOfVecViolation::DuplicateKey{ major, position, key }         =>
+                write!( f, "major view {}: key {:?} is repeated in position {}", major, key, position ),
+            VecOfVecViolation::ExplicitZero{ major, position, key }         =>
+                write!( f, "major view {}: key {:?} in position {} has coefficient zero", major, key, position ),
+        }
+    }
+}
+
+impl < MinKey: Debug > std::error::Error for VecOfVecViolation< MinKey > {}
+
+
+/// Returns `true` if the minor keys of `view` are strictly ascending.
+fn is_strictly_ascending< MinKey: PartialOrd, Val >( view: &[ (MinKey, Val) ] ) -> bool {
+    view.windows( 2 ).all( |pair| pair[0].0 < pair[1].0 )
+}
+
+
+impl < 'a, IndexCoeffPair >
+    WhichMajor
+    for
+    VecOfVec < 'a, IndexCoeffPair >
+
+    where   IndexCoeffPair:    KeyValGet,
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+impl < 'a, 'k, IndexCoeffPair >
+    MajorKeys< 'k, usize >
+    for
+    VecOfVec < 'a, IndexCoeffPair >
+
+    where   IndexCoeffPair:    KeyValGet,
+{
+    type MajorKeysIter = Range< usize >;
+
+    fn major_keys<'b: 'k>( &'b self ) -> Self::MajorKeysIter { 0 .. self.vec_of_vec.len() }
+
+    fn num_major_keys( &self ) -> usize { self.vec_of_vec.len() }
 }
 
 
@@ -147,6 +653,256 @@ impl < 'a, IndexCoeffPair >
     } 
 }
 
+impl < 'a, IndexCoeffPair > 
+    
+    OracleMajorAscendScoped
+    <   
+        'a,
+        usize, 
+        < IndexCoeffPair as KeyValGet >::Key, 
+        < IndexCoeffPair as KeyValGet >::Val, 
+    > 
+    
+    for 
+    
+    VecOfVec < 'a, IndexCoeffPair > 
+
+    where   IndexCoeffPair:         KeyValGet + Clone + 'a,
+            IndexCoeffPair::Key:    PartialOrd,
+            Self: 'a
+{
+    type PairMajorAscendScoped = IndexCoeffPair;
+    type ViewMajorAscendScoped = Cloned<std::slice::Iter<'a, IndexCoeffPair>>; 
+        
+    /// Assumes that entries in each vector are sorted in ascending order; the ends of the range
+    /// are found by binary search.
+    fn view_major_ascend_scoped<'b: 'a>( &'b self, index: usize, min: IndexCoeffPair::Key, max: IndexCoeffPair::Key ) -> Self::ViewMajorAscendScoped {
+        let view    =   &self.vec_of_vec[ index ];
+        let start   =   view.partition_point( |entry| entry.key() < min );
+        let end     =   view.partition_point( |entry| entry.key() < max ).max( start );
+        view[ start .. end ].iter().cloned()
+    } 
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+/// A minor view of a [`VecOfVec`], computed by searching each major view for the minor key.
+///
+/// Each major view is searched with a binary search, so iterating over a minor view of a matrix
+/// with `m` major views costs `O( m log n )`, where `n` is the length of the longest major view.
+/// Entries are `(major_key, coefficient)` pairs, in ascending order of major key (or descending,
+/// if the iterator is reversed).
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMinor, OracleMinorDescend};
+///
+/// let matrix  =   VecOfVec::new(
+///                     MajorDimension::Row,
+///                     vec![ vec![(0,1), (1,2)], vec![], vec![(1,3)] ],
+///                 );
+///
+/// // column 1 of a row-major matrix
+/// assert_eq!( matrix.view_minor( 1 ).collect::<Vec<_>>(), vec![ (0,2), (2,3) ] );
+/// assert_eq!( matrix.view_minor_descend( 1 ).collect::<Vec<_>>(), vec![ (2,3), (0,2) ] );
+/// ```
+pub struct VecOfVecMinorView< 'a, IndexCoeffPair >
+    where   IndexCoeffPair:     KeyValGet,
+{
+    vec_of_vec:     &'a [ Vec< IndexCoeffPair > ],
+    key:            IndexCoeffPair::Key,
+    front:          usize,
+    back:           usize,
+}
+
+impl < 'a, IndexCoeffPair > VecOfVecMinorView< 'a, IndexCoeffPair >
+    where   IndexCoeffPair:         KeyValGet,
+            IndexCoeffPair::Key:    PartialOrd,
+{
+    /// The coefficient of major view `major` in position `self.key`, if any.
+    fn search( &self, major: usize ) -> Option< ( usize, IndexCoeffPair::Val ) > {
+        let view        =   &self.vec_of_vec[ major ];
+        let position    =   view.partition_point( |entry| entry.key() < self.key );
+        view.get( position )
+            .filter( |entry| entry.key() == self.key )
+            .map( |entry| ( major, entry.val() ) )
+    }
+}
+
+impl < 'a, IndexCoeffPair > Iterator for VecOfVecMinorView< 'a, IndexCoeffPair >
+    where   IndexCoeffPair:         KeyValGet,
+            IndexCoeffPair::Key:    PartialOrd,
+{
+    type Item = ( usize, IndexCoeffPair::Val );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        while self.front < self.back {
+            self.front += 1;
+            if let Some( entry ) = self.search( self.front - 1 ) { return Some( entry ) }
+        }
+        None
+    }
+}
+
+impl < 'a, IndexCoeffPair > DoubleEndedIterator for VecOfVecMinorView< 'a, IndexCoeffPair >
+    where   IndexCoeffPair:         KeyValGet,
+            IndexCoeffPair::Key:    PartialOrd,
+{
+    fn next_back( &mut self ) -> Option< Self::Item > {
+        while self.front < self.back {
+            self.back -= 1;
+            if let Some( entry ) = self.search( self.back ) { return Some( entry ) }
+        }
+        None
+    }
+}
+
+impl < 'a, IndexCoeffPair > VecOfVecMinorView< 'a, IndexCoeffPair >
+    where   IndexCoeffPair:     KeyValGet,
+{
+    /// The minor view with key `key` of the matrix whose major views are `vec_of_vec`.
+    pub(crate) fn new( vec_of_vec: &'a [ Vec< IndexCoeffPair > ], key: IndexCoeffPair::Key ) -> Self {
+        VecOfVecMinorView{ vec_of_vec, key, front: 0, back: vec_of_vec.len() }
+    }
+}
+
+impl < 'a, IndexCoeffPair > VecOfVec< 'a, IndexCoeffPair >
+    where   IndexCoeffPair:     KeyValGet,
+{
+    /// The minor view with key `key`.
+    fn minor_view( &'a self, key: IndexCoeffPair::Key ) -> VecOfVecMinorView< 'a, IndexCoeffPair > {
+        VecOfVecMinorView::new( &self.vec_of_vec, key )
+    }
+}
+
+impl < 'a, IndexCoeffPair > 
+    
+    OracleMinor
+    <   
+        'a,
+        < IndexCoeffPair as KeyValGet >::Key, 
+        usize, 
+        < IndexCoeffPair as KeyValGet >::Val, 
+    > 
+    
+    for 
+    
+    VecOfVec < 'a, IndexCoeffPair > 
+
+    where   IndexCoeffPair:         KeyValGet + 'a,
+            IndexCoeffPair::Key:    PartialOrd,
+            IndexCoeffPair::Val:    Clone,
+            Self: 'a
+{
+    type PairMinor = ( usize, IndexCoeffPair::Val );
+    type ViewMinor = VecOfVecMinorView< 'a, IndexCoeffPair >;
+        
+    fn view_minor<'b: 'a>( &'b self, index: IndexCoeffPair::Key ) -> Self::ViewMinor {
+        self.minor_view( index )
+    } 
+}
+
+impl < 'a, IndexCoeffPair > 
+    
+    OracleMinorAscend
+    <   
+        'a,
+        < IndexCoeffPair as KeyValGet >::Key, 
+        usize, 
+        < IndexCoeffPair as KeyValGet >::Val, 
+    > 
+    
+    for 
+    
+    VecOfVec < 'a, IndexCoeffPair > 
+
+    where   IndexCoeffPair:         KeyValGet + 'a,
+            IndexCoeffPair::Key:    PartialOrd,
+            IndexCoeffPair::Val:    Clone,
+            Self: 'a
+{
+    type PairMinorAscend = ( usize, IndexCoeffPair::Val );
+    type ViewMinorAscend = VecOfVecMinorView< 'a, IndexCoeffPair >;
+        
+    /// Assumes that entries in each vector are sorted in ascending order.
+    fn view_minor_ascend<'b: 'a>( &'b self, index: IndexCoeffPair::Key ) -> Self::ViewMinorAscend {
+        self.minor_view( index )
+    } 
+}
+
+impl < 'a, IndexCoeffPair > 
+    
+    OracleMinorDescend
+    <   
+        'a,
+        < IndexCoeffPair as KeyValGet >::Key, 
+        usize, 
+        < IndexCoeffPair as KeyValGet >::Val, 
+    > 
+    
+    for 
+    
+    VecOfVec < 'a, IndexCoeffPair > 
+
+    where   IndexCoeffPair:         KeyValGet + 'a,
+            IndexCoeffPair::Key:    PartialOrd,
+            IndexCoeffPair::Val:    Clone,
+            Self: 'a
+{
+    type PairMinorDescend = ( usize, IndexCoeffPair::Val );
+    type ViewMinorDescend = Rev< VecOfVecMinorView< 'a, IndexCoeffPair > >;
+        
+    /// Assumes that entries in each vector are sorted in ascending order.
+    fn view_minor_descend<'b: 'a>( &'b self, index: IndexCoeffPair::Key ) -> Self::ViewMinorDescend {
+        self.minor_view( index ).rev()
+    } 
+}
+
+
+
+//  WRITING
+//  ---------------------------------------------------------------------------
+
+
+/// Keeps every major view sorted.  Unlike [`VecOfVec::insert_entry`], writing to a major key that
+/// exceeds the number of stored views appends empty views, rather than panicking.
+impl < 'a, MinKey, Val >
+
+    OracleSet< usize, MinKey, Val >
+
+    for
+
+    VecOfVec < 'a, (MinKey, Val) >
+
+    where   MinKey:     Clone + PartialOrd,
+            Val:        Clone,
+{
+    fn set_entry( &mut self, major: usize, minor: MinKey, val: Val ) -> Option< Val > {
+        if major >= self.vec_of_vec.len() { self.vec_of_vec.resize_with( major + 1, Vec::new ) }
+        self.insert_entry( major, minor, val )
+    }
+
+    fn add_to_entry< RingOperator: Semiring< Val > >( &mut self, major: usize, minor: MinKey, val: Val, ring: &RingOperator ) {
+        if major >= self.vec_of_vec.len() { self.vec_of_vec.resize_with( major + 1, Vec::new ) }
+        let view        =   &mut self.vec_of_vec[ major ];
+        debug_assert!( is_strictly_ascending( view ), "major view {} is not sorted in strictly ascending order of minor key", major );
+        let position    =   view.partition_point( |entry| entry.0 < minor );
+        match view.get_mut( position ) {
+            Some( entry ) if entry.0 == minor   =>  {
+                let sum     =   ring.add( entry.1.clone(), val );
+                if ring.is_0( sum.clone() ) { view.remove( position ); } else { entry.1 = sum; }
+            }
+            _                                   =>  {
+                if ! ring.is_0( val.clone() ) { view.insert( position, ( minor, val ) ) }
+            }
+        }
+    }
+}
 
 
 
@@ -171,5 +927,162 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_vec_of_vec_deletion() {
+
+        let mut matrix  =   VecOfVec::new(
+                                MajorDimension::Row,
+                                vec![ vec![(0,1)], vec![(0,2), (3,2)], vec![], vec![(1,4)] ],
+                            );
+
+        // deleting without renumbering leaves an empty view in place
+        assert_eq!( matrix.delete_major_views( vec![1, 10], false ), None );
+        assert_eq!( matrix.vec_of_vec, vec![ vec![(0,1)], vec![], vec![], vec![(1,4)] ] );
+
+        // compacting removes every empty view
+        let bimap       =   matrix.compact();
+        assert_eq!( matrix.vec_of_vec, vec![ vec![(0,1)], vec![(1,4)] ] );
+        assert_eq!( bimap.ord( &3 ), Some( 1 ) );
+        assert_eq!( bimap.ord( &1 ), None );
+    }
+
+    #[test]
+    fn test_bulk_view_operations() {
+        use crate::rings::ring_native::NativeRing;
+
+        let ring        =   NativeRing::<i64>::new();
+        let mut matrix  =   VecOfVec::new(
+                                MajorDimension::Row,
+                                vec![ vec![(0,1), (2,1)], vec![(1,3)], vec![(0,-1), (1,1)] ],
+                            );
+        let mut buffer  =   Vec::new();
+
+        // target after source, and target before source
+        matrix.add_view_in_place( 2, 0, 1, ring.clone(), &mut buffer );
+        assert_eq!( matrix.vec_of_vec[2], vec![(1,1), (2,1)] );
+        matrix.add_view_in_place( 1, 2, -3, ring.clone(), &mut buffer );
+        assert_eq!( matrix.vec_of_vec[1], vec![(2,-3)] );
+
+        // adding a view to itself scales it
+        matrix.add_view_in_place( 0, 0, 2, ring.clone(), &mut buffer );
+        assert_eq!( matrix.vec_of_vec[0], vec![(0,3), (2,3)] );
+        matrix.add_view_in_place( 0, 0, -1, ring.clone(), &mut buffer );
+        assert!( matrix.vec_of_vec[0].is_empty() );
+
+        matrix.scale_view_in_place( 2, 0, ring );
+        assert!( matrix.vec_of_vec[2].is_empty() );
+    }
+
+    #[test]
+    fn test_entry_mutation_keeps_views_sorted() {
+        let mut matrix  =   VecOfVec::new( MajorDimension::Col, vec![] );
+        assert_eq!( matrix.push_vec( vec![] ), 0 );
+        for row in [ 4, 0, 2, 3, 1 ] {
+            assert_eq!( matrix.insert_entry( 0, row, row as i64 ), None );
+        }
+        assert_eq!( matrix.vec_of_vec[0], vec![ (0,0), (1,1), (2,2), (3,3), (4,4) ] );
+
+        assert_eq!( matrix.remove_entry( 0, &0 ), Some( 0 ) );
+        assert_eq!( matrix.remove_entry( 0, &4 ), Some( 4 ) );
+        assert_eq!( matrix.remove_entry( 0, &5 ), None );
+        assert_eq!( matrix.vec_of_vec[0], vec![ (1,1), (2,2), (3,3) ] );
+    }
+
+    #[test]
+    fn test_minor_views_agree_with_transpose() {
+        let rows        =   vec![ vec![(0,1), (2,5)], vec![], vec![(1,2), (2,3)], vec![(0,4)] ];
+        let row_major   =   VecOfVec::new( MajorDimension::Row, rows.clone() );
+
+        // transpose by hand
+        let mut cols    =   vec![ vec![]; 3 ];
+        for ( i, row ) in rows.iter().enumerate() {
+            for ( j, val ) in row.iter() { cols[ *j ].push( ( i, *val ) ) }
+        }
+        let col_major   =   VecOfVec::new( MajorDimension::Col, cols );
+        for j in 0 .. 3 {
+            assert!( row_major.view_minor( j ).eq( col_major.view_major( j ) ) );
+            assert!( row_major.view_minor_ascend( j ).eq( col_major.view_major_ascend( j ) ) );
+            assert!( row_major.view_minor_descend( j ).eq( col_major.view_major_descend( j ) ) );
+        }
+        assert_eq!( row_major.view_minor( 7 ).count(), 0 );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic( expected = "strictly ascending" )]
+    fn test_push_vec_checks_sorting_in_debug_builds() {
+        let mut matrix  =   VecOfVec::new( MajorDimension::Row, vec![] );
+        matrix.push_vec( vec![ (1,1), (0,1) ] );
+    }
+
+    #[test]
+    fn test_validate_reports_first_violation() {
+        use crate::rings::field_prime::GF2;
+
+        let ring        =   GF2::new();
+        let mut matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, true), (3, true) ], vec![] ] );
+        assert_eq!( matrix.validate( ring.clone(), false ), Ok(()) );
+
+        matrix.vec_of_vec[1]    =   vec![ (1, true), (1, true), (0, false) ];
+        let violation   =   matrix.validate( ring.clone(), false ).unwrap_err();
+        assert_eq!( violation, VecOfVecViolation::DuplicateKey{ major: 1, position: 1, key: 1 } );
+        assert_eq!( violation.to_string(), "major view 1: key 1 is repeated in position 1" );
+
+        matrix.vec_of_vec[1].remove( 0 );
+        assert_eq!( matrix.validate( ring.clone(), true ), Err( VecOfVecViolation::Unsorted{ major: 1, position: 1, previous: 1, key: 0 } ) );
+        matrix.vec_of_vec[1].remove( 0 );
+        assert_eq!( matrix.validate( ring.clone(), true ), Ok(()) );
+        assert_eq!( matrix.validate( ring, false ), Err( VecOfVecViolation::ExplicitZero{ major: 1, position: 0, key: 0 } ) );
+    }
+
+    #[test]
+    fn test_reordering_preserves_the_matrix() {
+        use crate::rings::ring_native::NativeRing;
+
+        // the boundary matrix of a path of 4 edges, with cells numbered badly
+        let ( vertices, edges )     =   ( vec![ 0, 5, 2, 7, 4 ], vec![ 3, 8, 1, 6 ] );
+        let mut cols                =   vec![ vec![]; 9 ];
+        for ( e, edge ) in edges.iter().enumerate() {
+            let mut col             =   vec![ ( vertices[ e ], 1 ), ( vertices[ e + 1 ], -1 ) ];
+            col.sort();
+            cols[ *edge ]           =   col;
+        }
+        let matrix                  =   VecOfVec::new( MajorDimension::Col, cols );
+        let ( permuted, bimap )     =   matrix.reordered_by_reverse_cuthill_mckee();
+
+        // every entry is moved to the position given by the bijection, and views stay sorted
+        assert_eq!( permuted.num_views(), 9 );
+        for ( new_major, view ) in permuted.vec_of_vec.iter().enumerate() {
+            let old_major           =   bimap.val( new_major ).unwrap();
+            let expected: Vec< _ >  =   matrix.vec_of_vec[ old_major ].iter().map( |( minor, val )| ( bimap.ord( minor ).unwrap(), *val ) ).collect();
+            let mut sorted          =   expected.clone();
+            sorted.sort();
+            assert_eq!( view, &sorted );
+        }
+        assert_eq!( permuted.validate( NativeRing::< i64 >::new(), false ), Ok(()) );
+
+        // interacting cells are adjacent after reordering
+        let bandwidth               =   | m: &VecOfVec< (usize, i64) > | m.vec_of_vec.iter().enumerate().flat_map( |( j, col )| col.iter().map( move |( i, _ )| i.abs_diff( j ) ) ).max();
+        assert_eq!( bandwidth( &permuted ), Some( 1 ) );
+        assert!( bandwidth( &matrix ) > Some( 1 ) );
+    }
+
+    #[test]
+    fn test_oracle_set_keeps_views_sorted() {
+        use crate::rings::ring_native::NativeRing;
+
+        let ring        =   NativeRing::<i64>::new();
+        let mut matrix  =   VecOfVec::new( MajorDimension::Col, vec![] );
+        assert_eq!( OracleSet::set_entry( &mut matrix, 2, 5, 1 ), None );
+        matrix.add_to_entry( 2, 1, 4, &ring );
+        matrix.add_to_entry( 2, 3, 0, &ring );          // zero: not stored
+        matrix.add_to_entry( 0, 0, 2, &ring );
+        assert_eq!( matrix.vec_of_vec, vec![ vec![ (0, 2) ], vec![], vec![ (1, 4), (5, 1) ] ] );
+
+        matrix.add_to_entry( 2, 1, -4, &ring );         // sums to zero: removed
+        assert_eq!( OracleSet::set_entry( &mut matrix, 2, 5, 7 ), Some( 1 ) );
+        assert_eq!( matrix.vec_of_vec[ 2 ], vec![ (5, 7) ] );
+    }
+
 }
 
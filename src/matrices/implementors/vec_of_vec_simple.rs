@@ -0,0 +1,251 @@
+//! A vector of vectors, without a lifetime parameter.
+//!
+//! A [`VecOfVec`] carries a lifetime parameter `'a`, which appears in every trait bound that
+//! mentions it, and in the type of every struct that stores one.  A [`VecOfVecSimple`] stores
+//! the same data, but has no lifetime parameter: each view borrows the matrix for the lifetime
+//! chosen by the caller of the oracle method.  This makes it the natural type to store inside
+//! other structs, and to return from functions that produce a matrix, such as
+//! [`RightReduction::into_vec_of_vecs`](crate::matrix_factorization::factors::RightReduction::into_vec_of_vecs).
+//!
+//! The two types convert into one another with [`From`], without copying the stored entries.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::vec_of_vec_simple::VecOfVecSimple;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscend, OracleMinorDescend};
+//!
+//! struct Holder { matrix: VecOfVecSimple< usize, f64 > }  // no lifetime to thread through
+//!
+//! let holder  =   Holder{ matrix: VecOfVecSimple::new( MajorDimension::Row, vec![ vec![ (0, 1.), (2, 2.) ], vec![ (2, 3.) ] ] ) };
+//!
+//! assert_eq!( holder.matrix.view_major_ascend( 0 ).collect::<Vec<_>>(), vec![ (0, 1.), (2, 2.) ] );
+//! assert_eq!( holder.matrix.view_minor_descend( 2 ).collect::<Vec<_>>(), vec![ (1, 3.), (0, 2.) ] );
+//! ```
+
+use crate::matrices::implementors::vec_of_vec::{VecOfVec, VecOfVecMinorView};
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        MajorKeys,
+                                        WhichMajor,
+                                        MajorDimension};
+use std::iter::{Cloned, Rev};
+use std::ops::Range;
+use std::slice::Iter;
+
+
+/// A vector of vectors, representing a sparse matrix; the same as a [`VecOfVec`] with entries of
+/// type `(MinKey, Val)`, but without a lifetime parameter.
+///
+/// Each of the internal vectors should have entries sorted in strictly ascending order of minor
+/// key.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct VecOfVecSimple< MinKey, Val > {
+    pub major_dimension:    MajorDimension,
+    pub vec_of_vec:         Vec< Vec< (MinKey, Val) > >,
+}
+
+impl    < MinKey, Val >
+        VecOfVecSimple
+        < MinKey, Val >
+{
+    /// A matrix with the given major views.
+    pub fn new( major_dimension: MajorDimension, vec_of_vec: Vec< Vec< (MinKey, Val) > > ) -> Self {
+        VecOfVecSimple{ major_dimension, vec_of_vec }
+    }
+
+    /// The number of stored major views.
+    pub fn num_views( &self ) -> usize { self.vec_of_vec.len() }
+
+    /// Append a major view, and return its key.
+    ///
+    /// The view should be sorted in strictly ascending order of minor key; this is checked in
+    /// debug builds.
+    pub fn push_vec( &mut self, vec: Vec< (MinKey, Val) > ) -> usize
+        where   MinKey:     PartialOrd,
+    {
+        debug_assert!( vec.windows( 2 ).all( |pair| pair[0].0 < pair[1].0 ), "major views must be sorted in strictly ascending order of minor key" );
+        self.vec_of_vec.push( vec );
+        self.vec_of_vec.len() - 1
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  CONVERSIONS
+//  ---------------------------------------------------------------------------
+
+
+impl < 'a, MinKey, Val > From< VecOfVec< 'a, (MinKey, Val) > > for VecOfVecSimple< MinKey, Val >
+    where   MinKey:     Clone,
+            Val:        Clone,
+{
+    fn from( matrix: VecOfVec< 'a, (MinKey, Val) > ) -> Self { VecOfVecSimple::new( matrix.major_dimension, matrix.vec_of_vec ) }
+}
+
+impl < 'a, MinKey, Val > From< VecOfVecSimple< MinKey, Val > > for VecOfVec< 'a, (MinKey, Val) >
+    where   MinKey:     Clone,
+            Val:        Clone,
+{
+    fn from( matrix: VecOfVecSimple< MinKey, Val > ) -> Self { VecOfVec::new( matrix.major_dimension, matrix.vec_of_vec ) }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+impl     < MinKey, Val >
+        WhichMajor
+        for
+        VecOfVecSimple < MinKey, Val >
+{ fn major_dimension( &self ) -> MajorDimension { self.major_dimension.clone() } }
+
+impl     < 'a, MinKey, Val >
+        MajorKeys < 'a, usize >
+        for
+        VecOfVecSimple < MinKey, Val >
+{
+    type MajorKeysIter = Range< usize >;
+
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter { 0 .. self.vec_of_vec.len() }
+
+    fn num_major_keys( &self ) -> usize { self.vec_of_vec.len() }
+}
+
+
+//  MAJORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, MinKey, Val >
+        OracleMajor < 'a, usize, MinKey, Val >
+        for
+        VecOfVecSimple < MinKey, Val >
+
+        where   MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajor =   ( MinKey, Val );
+    type ViewMajor =   Cloned< Iter< 'a, (MinKey, Val) > >;
+
+    fn view_major<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajor { self.vec_of_vec[ index ].iter().cloned() }
+}
+
+impl     < 'a, MinKey, Val >
+        OracleMajorAscend < 'a, usize, MinKey, Val >
+        for
+        VecOfVecSimple < MinKey, Val >
+
+        where   MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorAscend =   ( MinKey, Val );
+    type ViewMajorAscend =   Cloned< Iter< 'a, (MinKey, Val) > >;
+
+    /// Assumes that entries in each vector are sorted in ascending order.
+    fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend { self.view_major( index ) }
+}
+
+impl     < 'a, MinKey, Val >
+        OracleMajorDescend < 'a, usize, MinKey, Val >
+        for
+        VecOfVecSimple < MinKey, Val >
+
+        where   MinKey:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorDescend =   ( MinKey, Val );
+    type ViewMajorDescend =   Cloned< Rev< Iter< 'a, (MinKey, Val) > > >;
+
+    /// Assumes that entries in each vector are sorted in ascending order.
+    fn view_major_descend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorDescend { self.vec_of_vec[ index ].iter().rev().cloned() }
+}
+
+
+//  MINORS
+//  ---------------------------------------------------------------------------
+
+
+impl     < 'a, MinKey, Val >
+        OracleMinor < 'a, MinKey, usize, Val >
+        for
+        VecOfVecSimple < MinKey, Val >
+
+        where   MinKey:     'a + Clone + PartialOrd,
+                Val:        'a + Clone,
+{
+    type PairMinor =   ( usize, Val );
+    type ViewMinor =   VecOfVecMinorView< 'a, (MinKey, Val) >;
+
+    /// Searches each major view for `index`; see [`VecOfVecMinorView`].
+    fn view_minor<'b: 'a>( &'b self, index: MinKey ) -> Self::ViewMinor { VecOfVecMinorView::new( &self.vec_of_vec, index ) }
+}
+
+impl     < 'a, MinKey, Val >
+        OracleMinorAscend < 'a, MinKey, usize, Val >
+        for
+        VecOfVecSimple < MinKey, Val >
+
+        where   MinKey:     'a + Clone + PartialOrd,
+                Val:        'a + Clone,
+{
+    type PairMinorAscend =   ( usize, Val );
+    type ViewMinorAscend =   VecOfVecMinorView< 'a, (MinKey, Val) >;
+
+    /// Assumes that entries in each vector are sorted in ascending order.
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MinKey ) -> Self::ViewMinorAscend { self.view_minor( index ) }
+}
+
+impl     < 'a, MinKey, Val >
+        OracleMinorDescend < 'a, MinKey, usize, Val >
+        for
+        VecOfVecSimple < MinKey, Val >
+
+        where   MinKey:     'a + Clone + PartialOrd,
+                Val:        'a + Clone,
+{
+    type PairMinorDescend =   ( usize, Val );
+    type ViewMinorDescend =   Rev< VecOfVecMinorView< 'a, (MinKey, Val) > >;
+
+    /// Assumes that entries in each vector are sorted in ascending order.
+    fn view_minor_descend<'b: 'a>( &'b self, index: MinKey ) -> Self::ViewMinorDescend { self.view_minor( index ).rev() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_views_agree_with_vec_of_vec() {
+        let views       =   vec![ vec![ (0, 1), (3, 2) ], vec![], vec![ (1, 5), (3, 6) ] ];
+        let mut simple  =   VecOfVecSimple::new( MajorDimension::Col, views.clone() );
+        let matrix      =   VecOfVec::new( MajorDimension::Col, views );
+
+        assert_eq!( ( simple.num_views(), simple.major_keys().collect::< Vec< _ > >() ), ( 3, vec![ 0, 1, 2 ] ) );
+        for major in 0 .. 3 {
+            assert!( simple.view_major( major ).eq( matrix.view_major( major ) ) );
+            assert!( simple.view_major_ascend( major ).eq( matrix.view_major_ascend( major ) ) );
+            assert!( simple.view_major_descend( major ).eq( matrix.view_major_descend( major ) ) );
+        }
+        for minor in 0 .. 4 {
+            assert!( simple.view_minor( minor ).eq( matrix.view_minor( minor ) ) );
+            assert!( simple.view_minor_ascend( minor ).eq( matrix.view_minor_ascend( minor ) ) );
+            assert!( simple.view_minor_descend( minor ).eq( matrix.view_minor_descend( minor ) ) );
+        }
+
+        // round trip through the borrowing type
+        assert_eq!( simple.push_vec( vec![ (2, 7) ] ), 3 );
+        let converted: VecOfVec< (usize, i32) > =   simple.clone().into();
+        assert_eq!( VecOfVecSimple::from( converted ), simple );
+    }
+}
@@ -0,0 +1,326 @@
+//! A wrapper that records every view requested from a matrix oracle.
+//!
+//! Bugs that involve matrix oracles often show up deep inside a large pipeline, where it's hard
+//! to tell which views were requested, and in what order.  Wrapping the oracle in a
+//! [`ViewRecorder`] records each request (its key and direction) without changing the views
+//! themselves.  The resulting log can be printed, attached to a bug report, or replayed against
+//! another matrix with [`replay_major`] or [`replay`], e.g. to compare a custom oracle with a
+//! [`VecOfVec`](crate::matrices::implementors::vec_of_vec::VecOfVec) reference.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::implementors::view_recorder::{ViewRecorder, ViewDirection, replay_major};
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor, OracleMajorDescend};
+//!
+//! let matrix      =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1.) ], vec![ (0, 1.), (1, 2.) ] ] );
+//! let recorder    =   ViewRecorder::new( &matrix );
+//!
+//! // ... somewhere inside a large computation ...
+//! let _           =   recorder.view_major( 1 ).count();
+//! let _           =   recorder.view_major_descend( 0 ).count();
+//!
+//! let log         =   recorder.log();
+//! assert_eq!( log[1].key, 0 );
+//! assert_eq!( log[1].direction, ViewDirection::MajorDescend );
+//!
+//! // replay the same requests against another matrix
+//! let other       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1.) ], vec![ (1, 2.) ] ] );
+//! let views       =   replay_major( &log, &other );
+//! assert_eq!( views[0], Some( vec![ (1, 2.) ] ) );
+//! assert_eq!( views[1], Some( vec![ (0, 1.) ] ) );
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        OracleMajorDescend,
+                                        OracleMinor,
+                                        OracleMinorAscend,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::cell::RefCell;
+
+
+//  ---------------------------------------------------------------------------
+//  REQUESTS
+//  ---------------------------------------------------------------------------
+
+
+/// The kind of view requested from an oracle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ViewDirection {
+    Major,
+    MajorAscend,
+    MajorDescend,
+    Minor,
+    MinorAscend,
+    MinorDescend,
+}
+
+/// A single view request.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ViewRequest< Key > {
+    pub key:        Key,
+    pub direction:  ViewDirection,
+}
+
+
+//  ---------------------------------------------------------------------------
+//  RECORDER
+//  ---------------------------------------------------------------------------
+
+
+/// Wraps a matrix oracle, and records every view request.
+///
+/// The recorder implements each oracle trait that the wrapped matrix implements, and returns
+/// exactly the same views.
+#[derive(Debug)]
+pub struct ViewRecorder< Matrix, Key > {
+    matrix:     Matrix,
+    log:        RefCell< Vec< ViewRequest< Key > > >,
+}
+
+impl    < Matrix, Key: Clone >
+        ViewRecorder
+        < Matrix, Key >
+{
+    /// Wrap `matrix` in a recorder with an empty log.
+    pub fn new( matrix: Matrix ) -> Self { ViewRecorder{ matrix, log: RefCell::new( Vec::new() ) } }
+
+    /// A copy of the requests recorded so far, in the order they were made.
+    pub fn log( &self ) -> Vec< ViewRequest< Key > > { self.log.borrow().clone() }
+
+    /// Delete every recorded request.
+    pub fn clear_log( &self ) { self.log.borrow_mut().clear() }
+
+    /// A reference to the wrapped matrix.
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// Consume the recorder, returning the wrapped matrix and the log.
+    pub fn into_parts( self ) -> ( Matrix, Vec< ViewRequest< Key > > ) { ( self.matrix, self.log.into_inner() ) }
+
+    fn record( &self, key: &Key, direction: ViewDirection ) {
+        self.log.borrow_mut().push( ViewRequest{ key: key.clone(), direction } )
+    }
+}
+
+
+//  ---------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------
+
+
+impl     < Matrix: WhichMajor, Key >
+        WhichMajor
+        for
+        ViewRecorder < Matrix, Key >
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajor < 'a, MajKey, MinKey, SnzVal >
+        for
+        ViewRecorder < Matrix, MajKey >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    type PairMajor =   Matrix::PairMajor;
+    type ViewMajor =   Matrix::ViewMajor;
+
+    fn view_major<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajor {
+        self.record( &index, ViewDirection::Major );
+        self.matrix.view_major( index )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ViewRecorder < Matrix, MajKey >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    type PairMajorAscend =   Matrix::PairMajorAscend;
+    type ViewMajorAscend =   Matrix::ViewMajorAscend;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorAscend {
+        self.record( &index, ViewDirection::MajorAscend );
+        self.matrix.view_major_ascend( index )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMajorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ViewRecorder < Matrix, MajKey >
+
+        where   Matrix:     OracleMajorDescend< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    type PairMajorDescend =   Matrix::PairMajorDescend;
+    type ViewMajorDescend =   Matrix::ViewMajorDescend;
+
+    fn view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend {
+        self.record( &index, ViewDirection::MajorDescend );
+        self.matrix.view_major_descend( index )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinor < 'a, MajKey, MinKey, SnzVal >
+        for
+        ViewRecorder < Matrix, MajKey >
+
+        where   Matrix:     OracleMinor< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    type PairMinor =   Matrix::PairMinor;
+    type ViewMinor =   Matrix::ViewMinor;
+
+    fn view_minor<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinor {
+        self.record( &index, ViewDirection::Minor );
+        self.matrix.view_minor( index )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorAscend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ViewRecorder < Matrix, MajKey >
+
+        where   Matrix:     OracleMinorAscend< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    type PairMinorAscend =   Matrix::PairMinorAscend;
+    type ViewMinorAscend =   Matrix::ViewMinorAscend;
+
+    fn view_minor_ascend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorAscend {
+        self.record( &index, ViewDirection::MinorAscend );
+        self.matrix.view_minor_ascend( index )
+    }
+}
+
+impl     < 'a, Matrix, MajKey, MinKey, SnzVal >
+        OracleMinorDescend < 'a, MajKey, MinKey, SnzVal >
+        for
+        ViewRecorder < Matrix, MajKey >
+
+        where   Matrix:     OracleMinorDescend< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    type PairMinorDescend =   Matrix::PairMinorDescend;
+    type ViewMinorDescend =   Matrix::ViewMinorDescend;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMinorDescend {
+        self.record( &index, ViewDirection::MinorDescend );
+        self.matrix.view_minor_descend( index )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  REPLAY
+//  ---------------------------------------------------------------------------
+
+
+/// The entries of a view, collected into a vector.
+pub type CollectedView< Key, Val > = Vec< ( Key, Val ) >;
+
+/// Collect a view into a vector of `(key, val)` pairs.
+fn collect_view< I >( view: I ) -> CollectedView< < I::Item as KeyValGet >::Key, < I::Item as KeyValGet >::Val >
+    where   I:          IntoIterator,
+            I::Item:    KeyValGet,
+{
+    view.into_iter().map( |entry| ( entry.key(), entry.val() ) ).collect()
+}
+
+/// Replay the major view requests in `log` against `matrix`.
+///
+/// Returns one element for each request: `Some( view )` for major requests (with the entries of
+/// the view collected into a vector), and `None` for minor requests.
+pub fn  replay_major< 'a, Matrix, MajKey, MinKey, SnzVal >(
+            log:        &       [ ViewRequest< MajKey > ],
+            matrix:     &'a     Matrix,
+        )
+        ->
+        Vec< Option< CollectedView< MinKey, SnzVal > > >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal > +
+                            OracleMajorAscend< 'a, MajKey, MinKey, SnzVal > +
+                            OracleMajorDescend< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    log.iter()
+        .map(   |request|
+                match request.direction {
+                    ViewDirection::Major            =>  Some( collect_view( matrix.view_major(         request.key.clone() ) ) ),
+                    ViewDirection::MajorAscend      =>  Some( collect_view( matrix.view_major_ascend(  request.key.clone() ) ) ),
+                    ViewDirection::MajorDescend     =>  Some( collect_view( matrix.view_major_descend( request.key.clone() ) ) ),
+                    _                               =>  None,
+                }
+        )
+        .collect()
+}
+
+/// Replay every request in `log` against `matrix`, returning the views it produces (with the
+/// entries of each view collected into a vector).
+pub fn  replay< 'a, Matrix, MajKey, MinKey, SnzVal >(
+            log:        &       [ ViewRequest< MajKey > ],
+            matrix:     &'a     Matrix,
+        )
+        ->
+        Vec< CollectedView< MinKey, SnzVal > >
+
+        where   Matrix:     OracleMajor< 'a, MajKey, MinKey, SnzVal > +
+                            OracleMajorAscend< 'a, MajKey, MinKey, SnzVal > +
+                            OracleMajorDescend< 'a, MajKey, MinKey, SnzVal > +
+                            OracleMinor< 'a, MajKey, MinKey, SnzVal > +
+                            OracleMinorAscend< 'a, MajKey, MinKey, SnzVal > +
+                            OracleMinorDescend< 'a, MajKey, MinKey, SnzVal >,
+                MajKey:     Clone,
+{
+    log.iter()
+        .map(   |request|
+                match request.direction {
+                    ViewDirection::Major            =>  collect_view( matrix.view_major(         request.key.clone() ) ),
+                    ViewDirection::MajorAscend      =>  collect_view( matrix.view_major_ascend(  request.key.clone() ) ),
+                    ViewDirection::MajorDescend     =>  collect_view( matrix.view_major_descend( request.key.clone() ) ),
+                    ViewDirection::Minor            =>  collect_view( matrix.view_minor(         request.key.clone() ) ),
+                    ViewDirection::MinorAscend      =>  collect_view( matrix.view_minor_ascend(  request.key.clone() ) ),
+                    ViewDirection::MinorDescend     =>  collect_view( matrix.view_minor_descend( request.key.clone() ) ),
+                }
+        )
+        .collect()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::csc::Csc;
+
+    #[test]
+    fn test_record_and_replay_minor_views() {
+        let matrix      =   Csc::from_triplets( 2, 2, vec![ (0, 0, 1), (0, 1, 2), (1, 1, 3) ] );
+        let recorder    =   ViewRecorder::new( &matrix );
+
+        let _           =   recorder.view_minor_ascend( 0 ).count();
+        let _           =   recorder.view_major_descend( 1 ).count();
+        let _           =   recorder.view_minor( 1 ).count();
+
+        let directions: Vec< _ >    =   recorder.log().iter().map( |x| x.direction ).collect();
+        assert_eq!( directions, vec![ ViewDirection::MinorAscend, ViewDirection::MajorDescend, ViewDirection::Minor ] );
+
+        let views       =   replay( &recorder.log(), &matrix );
+        assert_eq!( views, vec![ vec![ (0, 1), (1, 2) ], vec![ (1, 3), (0, 2) ], vec![ (1, 3) ] ] );
+
+        recorder.clear_log();
+        assert!( recorder.log().is_empty() );
+    }
+}
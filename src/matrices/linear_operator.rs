@@ -0,0 +1,211 @@
+//! Matrix-free linear operators.
+//!
+//! A [`LinearOperator`] is anything that maps a sparse vector to a sparse vector linearly.  This
+//! lets algorithms that only ever multiply by a matrix (iterative solvers, power iteration, ...)
+//! accept user code that never materializes the matrix.
+//!
+//! - Every matrix oracle is a linear operator once it is paired with a ring operator: the tuple
+//!   `( &matrix, ring )` implements [`LinearOperator`] (see the trait documentation for the
+//!   convention).
+//! - Every closure `Fn( &[ (KeyIn, Val) ] ) -> Vec< (KeyOut, Val) >` is a linear operator.
+//! - In the other direction, an [`OperatorOracle`] exposes a linear operator as a matrix oracle,
+//!   by applying the operator to unit vectors.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::linear_operator::{LinearOperator, OperatorOracle};
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+//! use solar::rings::ring_native::NativeRing;
+//!
+//! let ring    =   NativeRing::<i64>::new();
+//!
+//! // a cyclic shift, written without ever storing a matrix
+//! let shift   =   |v: &[ (usize, i64) ]| {
+//!                     let mut w: Vec< _ > = v.iter().map( |(k, x)| ( ( k + 1 ) % 3, *x ) ).collect();
+//!                     w.sort();
+//!                     w
+//!                 };
+//! assert_eq!( shift.apply( &[ (0, 1), (2, 5) ] ), vec![ (0, 5), (1, 1) ] );
+//!
+//! // the same operator, stored as a column-major matrix
+//! let matrix  =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (1, 1) ], vec![ (2, 1) ], vec![ (0, 1) ] ] );
+//! assert_eq!( ( &matrix, ring.clone() ).apply( &[ (0, 1), (2, 5) ] ), vec![ (0, 5), (1, 1) ] );
+//!
+//! // probe the closure with unit vectors to recover the columns of the matrix
+//! let probed  =   OperatorOracle::new( shift, ring );
+//! for j in 0 .. 3 {
+//!     assert!( probed.view_major( j ).eq( matrix.view_major( j ) ) );
+//! }
+//! ```
+
+use crate::matrices::matrix_oracle::{   OracleMajor,
+                                        OracleMajorAscend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::Semiring;
+use crate::utilities::order::OrderNatural;
+use crate::vector_entries::vector_entries::KeyValGet;
+use crate::vectors::operations::sum_sparse_vectors;
+
+
+/// A linear map from sparse vectors indexed by `KeyIn` to sparse vectors indexed by `KeyOut`.
+///
+/// Input and output vectors are lists of `(key, coefficient)` pairs.  Implementations should
+/// accept any input vector that is sorted in strictly ascending order of key, and should return a
+/// vector that is sorted in strictly ascending order of key and contains no zero coefficients.
+///
+/// # Matrix oracles
+///
+/// For any oracle `matrix` that implements [`OracleMajorAscend`], and any ring operator `ring`,
+/// the pair `( &matrix, ring )` is a linear operator, which sends `v` to the linear combination
+///
+/// `sum over (k, c) in v of c * matrix.view_major_ascend( k )`.
+///
+/// If `matrix` is column-major this is the product `matrix * v`; if it is row-major it is the
+/// product `v^T * matrix`.
+pub trait LinearOperator< KeyIn, KeyOut, Val > {
+    /// Apply the operator to `vector`.
+    fn apply( &self, vector: &[ (KeyIn, Val) ] ) -> Vec< (KeyOut, Val) >;
+}
+
+impl     < KeyIn, KeyOut, Val, F >
+        LinearOperator < KeyIn, KeyOut, Val >
+        for
+        F
+
+        where   F:      Fn( &[ (KeyIn, Val) ] ) -> Vec< (KeyOut, Val) >,
+{
+    fn apply( &self, vector: &[ (KeyIn, Val) ] ) -> Vec< (KeyOut, Val) > { self( vector ) }
+}
+
+impl     < 'a, Matrix, RingOperator, MajKey, MinKey, Val >
+        LinearOperator < MajKey, MinKey, Val >
+        for
+        ( &'a Matrix, RingOperator )
+
+        where   Matrix:         OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                RingOperator:   Clone + Semiring< Val >,
+                MajKey:         Clone,
+                MinKey:         Clone + PartialOrd,
+                Val:            Clone,
+{
+    fn apply( &self, vector: &[ (MajKey, Val) ] ) -> Vec< (MinKey, Val) > {
+        let ( matrix, ring )    =   ( self.0, &self.1 );
+        let scaled_views        =   vector.iter().map(  |(key, scalar)|
+                                        matrix.view_major_ascend( key.clone() )
+                                            .into_iter()
+                                            .map( move |entry| ( entry.key(), ring.multiply( entry.val(), scalar.clone() ) ) )
+                                    );
+        sum_sparse_vectors( scaled_views, ring.clone(), OrderNatural )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  OPERATORS AS ORACLES
+//  ---------------------------------------------------------------------------
+
+
+/// Exposes a [`LinearOperator`] as a column-major matrix oracle.
+///
+/// Major view `k` is the image of the unit vector `[ (k, 1) ]`, i.e. column `k` of the matrix
+/// that represents the operator.  Each view is computed by calling the operator, so views are
+/// only as cheap as the operator itself.  If the operator follows the conventions described in
+/// [`LinearOperator`], then views are sorted in ascending order of key.
+#[derive(Clone, Debug)]
+pub struct OperatorOracle< Operator, Val > {
+    operator:   Operator,
+    one:        Val,
+}
+
+impl    < Operator, Val >
+        OperatorOracle
+        < Operator, Val >
+{
+    /// Wrap `operator`; the ring operator supplies the coefficient `1` of the unit vectors.
+    pub fn new< RingOperator >( operator: Operator, _ring: RingOperator ) -> Self
+        where   RingOperator:   Semiring< Val >,
+    {
+        OperatorOracle{ operator, one: RingOperator::one() }
+    }
+
+    /// The wrapped operator.
+    pub fn operator( &self ) -> &Operator { &self.operator }
+
+    /// Unwrap the operator.
+    pub fn into_inner( self ) -> Operator { self.operator }
+
+    fn probe< KeyIn, KeyOut >( &self, index: KeyIn ) -> Vec< (KeyOut, Val) >
+        where   Operator:   LinearOperator< KeyIn, KeyOut, Val >,
+                Val:        Clone,
+    {
+        self.operator.apply( &[ ( index, self.one.clone() ) ] )
+    }
+}
+
+impl     < Operator, Val >
+        WhichMajor
+        for
+        OperatorOracle < Operator, Val >
+{ fn major_dimension( &self ) -> MajorDimension { MajorDimension::Col } }
+
+impl     < 'a, Operator, KeyIn, KeyOut, Val >
+        OracleMajor < 'a, KeyIn, KeyOut, Val >
+        for
+        OperatorOracle < Operator, Val >
+
+        where   Operator:   LinearOperator< KeyIn, KeyOut, Val >,
+                KeyOut:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajor =   ( KeyOut, Val );
+    type ViewMajor =   std::vec::IntoIter< ( KeyOut, Val ) >;
+
+    fn view_major<'b: 'a>( &'b self, index: KeyIn ) -> Self::ViewMajor { self.probe( index ).into_iter() }
+}
+
+impl     < 'a, Operator, KeyIn, KeyOut, Val >
+        OracleMajorAscend < 'a, KeyIn, KeyOut, Val >
+        for
+        OperatorOracle < Operator, Val >
+
+        where   Operator:   LinearOperator< KeyIn, KeyOut, Val >,
+                KeyOut:     'a + Clone,
+                Val:        'a + Clone,
+{
+    type PairMajorAscend =   ( KeyOut, Val );
+    type ViewMajorAscend =   std::vec::IntoIter< ( KeyOut, Val ) >;
+
+    fn view_major_ascend<'b: 'a>( &'b self, index: KeyIn ) -> Self::ViewMajorAscend { self.probe( index ).into_iter() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::rings::field_prime::GF2;
+
+    #[test]
+    fn test_oracle_operator_round_trip() {
+        let ring        =   GF2::new();
+        let matrix      =   VecOfVec::new(
+                                MajorDimension::Col,
+                                vec![ vec![ (0, true), (1, true) ], vec![ (1, true), (2, true) ], vec![ (0, true), (2, true) ] ],
+                            );
+        let operator    =   ( &matrix, ring.clone() );
+
+        // the columns sum to zero over GF(2)
+        assert!( operator.apply( &[ (0, true), (1, true), (2, true) ] ).is_empty() );
+        assert_eq!( operator.apply( &[ (0, true), (2, true) ] ), vec![ (1, true), (2, true) ] );
+
+        let probed      =   OperatorOracle::new( operator, ring );
+        for j in 0 .. 3 {
+            assert!( probed.view_major_ascend( j ).eq( matrix.view_major_ascend( j ) ) );
+        }
+    }
+}
@@ -200,6 +200,7 @@
 //! **Note** Most functions that take matrix oracles as inputs do not require 
 //! their inputs to implement *all* of the oracle traits -- only a *subset*.
 
+use crate::rings::ring::Semiring;
 use crate::vector_entries::vector_entries::{KeyValGet};
 use std::fmt::Debug;
 use std::iter::IntoIterator;
@@ -224,7 +225,8 @@ use auto_impl::auto_impl; // auto-implement a trait on references to objects tha
 
 
 /// An enum with two values: `Row` and `Col`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MajorDimension{
     Row,
     Col
@@ -238,6 +240,39 @@ pub enum MajorDimension{
 pub trait WhichMajor{ fn major_dimension( &self ) -> MajorDimension; }
 
 
+//  ---------------------------------------------------------------------------
+//  MAJOR KEYS
+//  ---------------------------------------------------------------------------
+
+/// Matrices that can list the keys of their major views.
+///
+/// Algorithms that visit every major view (e.g. a reduction) can iterate over `major_keys()`,
+/// rather than assume that major keys are the integers `0 .. n`.  Implementors that store their
+/// views in a `Vec` list the keys `0 .. n` in order; hash-based implementors list the keys of
+/// their nonempty views, in no particular order.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, MajorKeys, OracleMajor};
+///
+/// let matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1) ], vec![], vec![ (1, 2), (2, 3) ] ] );
+/// assert_eq!( matrix.num_major_keys(), 3 );
+///
+/// let num_entries: usize  =   matrix.major_keys().map( |key| matrix.view_major( key ).count() ).sum();
+/// assert_eq!( num_entries, 3 );
+/// ```
+pub trait MajorKeys< 'a, MajKey >
+{
+    type MajorKeysIter: Iterator< Item = MajKey > + 'a;
+    /// The keys of the major views.
+    fn major_keys<'b: 'a>( &'b self ) -> Self::MajorKeysIter;
+    /// The number of keys returned by `major_keys`.
+    fn num_major_keys( &self ) -> usize;
+}
+
+
 //  ---------------------------------------------------------------------------
 //  ORACLE MAJOR
 //  ---------------------------------------------------------------------------
@@ -301,15 +336,35 @@ pub trait OracleMajorDescend< 'a, MajKey, MinKey, SnzVal>
     fn   view_major_descend<'b: 'a>( &'b self, index: MajKey ) -> Self::ViewMajorDescend;
 }
 
-// FOR FUTURE CONSIDERATION
-// pub trait OracleMajorAscendScoped< 'a, MajKey, MinKey, SnzVal>
-// {
-//     type PairMajorAscendScoped: KeyValGet< Key=MinKey, Val=SnzVal >;
-//     type ViewMajorAscendScoped: IntoIterator< Item = Self::PairMajorAscendScoped >;
-//     /// Get a major vector with entries sorted in ascending order of index, clipped to range [min,
-//     /// max).
-//     fn   view_major_ascend_scoped<'b: 'a>( &'b self, index: MajKey, min: MinKey, max: MinKey ) -> Self::ViewMajorAscendScoped;
-// }
+/// Entries appear in ascending order, according to index, and only entries with indices in a
+/// given half-open range are returned.
+///
+/// Storages that keep their major views sorted (e.g. [`VecOfVec`](crate::matrices::implementors::vec_of_vec::VecOfVec)
+/// and [`VecCsv`](crate::matrices::implementors::vec_of_csvec::VecCsv)) locate the ends of the
+/// range by binary search, so a scoped view costs `O( log n + k )` time, where `n` is the length
+/// of the major view and `k` is the number of entries returned; this is much cheaper than
+/// filtering the full ascending view when the range is narrow.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajorAscendScoped};
+///
+/// let matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (2, 2), (3, 3), (7, 4) ] ] );
+///
+/// assert_eq!( matrix.view_major_ascend_scoped( 0, 2, 7 ).collect::<Vec<_>>(), vec![ (2, 2), (3, 3) ] );
+/// assert_eq!( matrix.view_major_ascend_scoped( 0, 4, 7 ).count(), 0 );
+/// ```
+#[auto_impl(&)] 
+pub trait OracleMajorAscendScoped< 'a, MajKey, MinKey, SnzVal>
+{
+    type PairMajorAscendScoped: KeyValGet< Key=MinKey, Val=SnzVal >;
+    type ViewMajorAscendScoped: IntoIterator< Item = Self::PairMajorAscendScoped >;
+    /// Get a major vector with entries sorted in ascending order of index, clipped to range [min,
+    /// max).
+    fn   view_major_ascend_scoped<'b: 'a>( &'b self, index: MajKey, min: MinKey, max: MinKey ) -> Self::ViewMajorAscendScoped;
+}
 
 //  ---------------------------------------------------------------------------
 //  ORACLE MINOR
@@ -349,3 +404,48 @@ pub trait OracleMinorDescend< 'a, MajKey, MinKey, SnzVal>
 }
 
 
+
+//  ---------------------------------------------------------------------------
+//  WRITABLE ORACLES
+//  ---------------------------------------------------------------------------
+
+/// Matrices whose entries can be written one at a time.
+///
+/// Routines that compute a matrix entry by entry (e.g. a factorization) can take `&mut impl
+/// OracleSet< .. >`, and write their output into whichever storage the caller prefers.  Keys are
+/// given in the order `(major, minor)`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::hash_of_hash::HashOfHash;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleSet};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// // write the identity matrix, plus an entry that cancels, into any writable storage
+/// fn write< M: OracleSet< usize, usize, i64 > >( matrix: &mut M, size: usize ) {
+///     let ring    =   NativeRing::<i64>::new();
+///     for i in 0 .. size { matrix.set_entry( i, i, 1 ); }
+///     matrix.add_to_entry( 0, 1, 3, &ring );
+///     matrix.add_to_entry( 0, 1, -3, &ring );
+/// }
+///
+/// let mut vec_of_vec  =   VecOfVec::new( MajorDimension::Row, vec![] );
+/// write( &mut vec_of_vec, 2 );
+/// assert_eq!( vec_of_vec.vec_of_vec, vec![ vec![ (0, 1) ], vec![ (1, 1) ] ] );
+///
+/// let mut hash_of_hash    =   HashOfHash::new( MajorDimension::Row );
+/// write( &mut hash_of_hash, 2 );
+/// assert_eq!( hash_of_hash.num_entries(), 2 );
+/// ```
+pub trait OracleSet< MajKey, MinKey, Val >
+{
+    /// Store `val` in position `(major, minor)`, and return the coefficient previously stored
+    /// there, if any.  Explicit zeros are stored.
+    fn set_entry( &mut self, major: MajKey, minor: MinKey, val: Val ) -> Option< Val >;
+
+    /// Add `val` to the coefficient in position `(major, minor)`; if the sum is zero, no entry is
+    /// stored.
+    fn add_to_entry< RingOperator: Semiring< Val > >( &mut self, major: MajKey, minor: MinKey, val: Val, ring: &RingOperator );
+}
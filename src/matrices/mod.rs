@@ -1,6 +1,13 @@
 //! Matrix traits and some objects that implement them.
 
 pub mod matrix_oracle; 
+pub mod dyn_oracle;
 pub mod implementors;
+pub mod sampling;
+pub mod random;
+pub mod compare;
+pub mod linear_operator;
+pub mod preconditioners;
+pub mod conjugate_gradient;
 
 
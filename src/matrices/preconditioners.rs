@@ -0,0 +1,506 @@
+//! Preconditioners for iterative solvers.
+//!
+//! A preconditioner for a square matrix `A` is an easily inverted matrix `M` that approximates
+//! `A`; iterative solvers converge much faster on the system `M^{-1} A x = M^{-1} b` than on
+//! `A x = b` when `A` is ill-conditioned (as combinatorial Laplacians of large complexes often
+//! are).  Each preconditioner in this module implements [`LinearOperator`], sending a vector `r`
+//! to `M^{-1} r`, so it can be passed to any routine that accepts a linear operator.
+//!
+//! - [`JacobiPreconditioner`]: `M` is the diagonal of `A`, stored as a
+//!   [`DiagonalMatrixOracle`].
+//! - [`BlockJacobiPreconditioner`]: `M` is the block-diagonal part of `A` with respect to a
+//!   partition of the indices into blocks; each block is inverted once, densely.
+//! - [`GaussSeidelPreconditioner`]: `M` is the lower-triangular part `D + L` of `A` (a forward
+//!   sweep), or the symmetric product `(D + L) D^{-1} (D + U)` (a forward sweep followed by a
+//!   backward sweep), which is symmetric positive definite whenever `A` is.  The triangular parts
+//!   are read directly from the columns of `A`, so nothing is copied.
+//!
+//! Matrices are square, and are given as slices of sparse columns, sorted in ascending order of
+//! row index (the format returned by
+//! [`laplacian`](crate::utilities::cell_complexes::simplices_unweighted::standard_complexes::laplacian)).
+//! The Jacobi and Gauss-Seidel preconditioners divide by the diagonal entries of `A`, so they
+//! cannot be built from a matrix with a zero (or missing) diagonal entry: their `new`
+//! constructors panic, and their `try_new` constructors return a [`ZeroDiagonal`] error.
+//! Likewise, the block Jacobi preconditioner cannot be built if a diagonal block is singular;
+//! its `try_new` constructor returns a [`SingularBlock`] error.
+//!
+//! The [conjugate gradient method](crate::matrices::conjugate_gradient) accepts any of these
+//! preconditioners.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::linear_operator::LinearOperator;
+//! use solar::matrices::preconditioners::{JacobiPreconditioner, GaussSeidelPreconditioner};
+//! use solar::rings::ring_native::NativeDivisionRing;
+//!
+//! let ring        =   NativeDivisionRing::<f64>::new();
+//! let columns     =   vec![ vec![ (0, 2.), (1, -1.) ], vec![ (0, -1.), (1, 2.) ] ];
+//!
+//! let jacobi      =   JacobiPreconditioner::new( &columns, ring.clone() );
+//! assert_eq!( jacobi.apply( &[ (0, 1.), (1, 4.) ] ), vec![ (0, 0.5), (1, 2.) ] );
+//!
+//! // solve ( D + L ) x = r by forward substitution
+//! let forward     =   GaussSeidelPreconditioner::new( &columns, ring );
+//! assert_eq!( forward.apply( &[ (0, 2.), (1, 1.) ] ), vec![ (0, 1.), (1, 1.) ] );
+//! ```
+
+use crate::matrices::implementors::diagonal::DiagonalMatrixOracle;
+use crate::matrices::linear_operator::LinearOperator;
+use crate::matrices::matrix_oracle::MajorDimension;
+use crate::rings::ring::{Semiring, DivisionRing};
+use std::collections::HashMap;
+use std::fmt;
+
+
+/// The diagonal entry of `column` number `index`, or an error if it is zero or missing.
+fn diagonal_entry< Val, RingOperator >( column: &[ (usize, Val) ], index: usize, ring: &RingOperator ) -> Result< Val, ZeroDiagonal >
+    where   RingOperator:   Semiring< Val >,
+            Val:            Clone,
+{
+    column.binary_search_by_key( &index, |entry| entry.0 )
+        .ok()
+        .map( |position| column[ position ].1.clone() )
+        .filter( |val| ! ring.is_0( val.clone() ) )
+        .ok_or( ZeroDiagonal{ index } )
+}
+
+/// The diagonal entries of the square matrix with sparse columns `columns`, or an error if one
+/// of them is zero or missing.
+fn diagonal< Val, RingOperator >( columns: &[ Vec< (usize, Val) > ], ring: &RingOperator ) -> Result< Vec< Val >, ZeroDiagonal >
+    where   RingOperator:   Semiring< Val >,
+            Val:            Clone,
+{
+    columns.iter().enumerate().map( |( index, column )| diagonal_entry( column, index, ring ) ).collect()
+}
+
+/// Invert a dense square matrix (stored as a list of rows) by Gauss-Jordan elimination, or
+/// return `None` if it is singular.  Each pivot is the first nonzero entry in its column.
+fn invert_dense< Val, RingOperator >( mut matrix: Vec< Vec< Val > >, ring: &RingOperator ) -> Option< Vec< Vec< Val > > >
+    where   RingOperator:   DivisionRing< Val >,
+            Val:            Clone,
+{
+    let size                =   matrix.len();
+    let mut inverse: Vec< Vec< Val > >
+        =   ( 0 .. size )
+                .map( |i| ( 0 .. size ).map( |j| if i == j { RingOperator::one() } else { RingOperator::zero() } ).collect() )
+                .collect();
+
+    for col in 0 .. size {
+        let pivot           =   ( col .. size ).find( |&row| ! ring.is_0( matrix[ row ][ col ].clone() ) )?;
+        matrix.swap( col, pivot );
+        inverse.swap( col, pivot );
+
+        let scale           =   ring.invert( matrix[ col ][ col ].clone() );
+        for val in matrix[ col ].iter_mut().chain( inverse[ col ].iter_mut() ) {
+            *val            =   ring.multiply( val.clone(), scale.clone() );
+        }
+
+        let pivot_row       =   matrix[ col ].clone();
+        let pivot_inverse   =   inverse[ col ].clone();
+        for ( row, ( matrix_row, inverse_row ) ) in matrix.iter_mut().zip( inverse.iter_mut() ).enumerate() {
+            let factor      =   matrix_row[ col ].clone();
+            if row == col || ring.is_0( factor.clone() ) { continue }
+            for ( val, pivot_val ) in matrix_row.iter_mut().zip( pivot_row.iter() ).chain( inverse_row.iter_mut().zip( pivot_inverse.iter() ) ) {
+                *val        =   ring.subtract( val.clone(), ring.multiply( factor.clone(), pivot_val.clone() ) );
+            }
+        }
+    }
+    Some( inverse )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  ERRORS
+//  ---------------------------------------------------------------------------
+
+
+/// A preconditioner that divides by the diagonal of a matrix was given a matrix with a zero (or
+/// missing) diagonal entry.
+///
+/// Such a matrix is not positive definite, so it is not a suitable input for the
+/// [conjugate gradient method](crate::matrices::conjugate_gradient) in any case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZeroDiagonal {
+    /// The index of the zero diagonal entry.
+    pub index:      usize,
+}
+
+impl fmt::Display for ZeroDiagonal {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        write!( f, "diagonal entry {} is zero", self.index )
+    }
+}
+
+impl std::error::Error for ZeroDiagonal {}
+
+/// A [`BlockJacobiPreconditioner`] was given a matrix with a singular diagonal block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SingularBlock {
+    /// The position of the singular block in the list of blocks.
+    pub block:      usize,
+}
+
+impl fmt::Display for SingularBlock {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        write!( f, "diagonal block {} is singular", self.block )
+    }
+}
+
+impl std::error::Error for SingularBlock {}
+
+
+//  ---------------------------------------------------------------------------
+//  JACOBI
+//  ---------------------------------------------------------------------------
+
+
+/// The Jacobi (diagonal) preconditioner: `M` is the diagonal of `A`.
+///
+/// Applying the preconditioner divides each coefficient by the corresponding diagonal entry of
+/// `A`.
+#[derive(Clone, Debug)]
+pub struct JacobiPreconditioner< Val, RingOperator > {
+    inverse_diagonal:   DiagonalMatrixOracle< usize, Val >,
+    ring:               RingOperator,
+}
+
+impl    < Val, RingOperator >
+        JacobiPreconditioner
+        < Val, RingOperator >
+
+        where   RingOperator:   DivisionRing< Val >,
+                Val:            Clone,
+{
+    /// The Jacobi preconditioner of the square matrix with sparse columns `columns`.
+    ///
+    /// Panics if a diagonal entry is zero or missing; see [`try_new`](JacobiPreconditioner::try_new).
+    pub fn new( columns: &[ Vec< (usize, Val) > ], ring: RingOperator ) -> Self {
+        Self::try_new( columns, ring ).unwrap_or_else( |error| panic!( "{}", error ) )
+    }
+
+    /// The Jacobi preconditioner of the square matrix with sparse columns `columns`, or an error
+    /// if a diagonal entry is zero or missing.
+    pub fn try_new( columns: &[ Vec< (usize, Val) > ], ring: RingOperator ) -> Result< Self, ZeroDiagonal > {
+        let inverse_diagonal    =   diagonal( columns, &ring )?
+                                        .into_iter()
+                                        .map( |val| ring.invert( val ) )
+                                        .enumerate()
+                                        .collect();
+        Ok( JacobiPreconditioner{
+            inverse_diagonal:   DiagonalMatrixOracle::new( MajorDimension::Col, inverse_diagonal ),
+            ring,
+        } )
+    }
+
+    /// The inverse `M^{-1}` of the diagonal of `A`.
+    pub fn inverse_diagonal( &self ) -> &DiagonalMatrixOracle< usize, Val > { &self.inverse_diagonal }
+}
+
+impl     < Val, RingOperator >
+        LinearOperator < usize, usize, Val >
+        for
+        JacobiPreconditioner < Val, RingOperator >
+
+        where   RingOperator:   DivisionRing< Val > + Clone,
+                Val:            Clone,
+{
+    fn apply( &self, vector: &[ (usize, Val) ] ) -> Vec< (usize, Val) > {
+        ( &self.inverse_diagonal, self.ring.clone() ).apply( vector )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  BLOCK JACOBI
+//  ---------------------------------------------------------------------------
+
+
+/// The block Jacobi preconditioner: `M` is the block-diagonal part of `A` with respect to a
+/// list of disjoint blocks of indices.
+///
+/// Each diagonal block of `A` is inverted densely when the preconditioner is built, so blocks
+/// should be small.  Indices that belong to no block are treated as blocks of size one whose
+/// entry is `1` (i.e. the preconditioner leaves the corresponding coefficients unchanged).
+///
+/// Blocks are inverted by exact Gauss-Jordan elimination without pivoting for magnitude; with
+/// floating point coefficients this is reliable for blocks of symmetric positive definite
+/// matrices, but may lose accuracy for general matrices.
+#[derive(Clone, Debug)]
+pub struct BlockJacobiPreconditioner< Val, RingOperator > {
+    /// Each block, as a sorted list of indices together with the inverse of its diagonal block.
+    blocks:             Vec< ( Vec< usize >, Vec< Vec< Val > > ) >,
+    /// Maps each index to its block, and its position within the block.
+    block_of:           HashMap< usize, ( usize, usize ) >,
+    ring:               RingOperator,
+}
+
+impl    < Val, RingOperator >
+        BlockJacobiPreconditioner
+        < Val, RingOperator >
+
+        where   RingOperator:   DivisionRing< Val >,
+                Val:            Clone,
+{
+    /// The block Jacobi preconditioner of the square matrix with sparse columns `columns`, with
+    /// respect to `blocks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a diagonal block is singular (see
+    /// [`try_new`](BlockJacobiPreconditioner::try_new)), or for any of the reasons listed there.
+    pub fn new( columns: &[ Vec< (usize, Val) > ], blocks: Vec< Vec< usize > >, ring: RingOperator ) -> Self {
+        Self::try_new( columns, blocks, ring ).unwrap_or_else( |error| panic!( "{}", error ) )
+    }
+
+    /// The block Jacobi preconditioner of the square matrix with sparse columns `columns`, with
+    /// respect to `blocks`, or an error if a diagonal block is singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index appears in more than one block, or if an index is out of range.
+    pub fn try_new( columns: &[ Vec< (usize, Val) > ], blocks: Vec< Vec< usize > >, ring: RingOperator ) -> Result< Self, SingularBlock > {
+        let mut block_of    =   HashMap::new();
+        let mut inverted    =   Vec::with_capacity( blocks.len() );
+        for ( block_id, mut keys ) in blocks.into_iter().enumerate() {
+            keys.sort_unstable();
+            for ( position, key ) in keys.iter().enumerate() {
+                assert!( *key < columns.len(), "index {} is out of range", key );
+                assert!( block_of.insert( *key, ( block_id, position ) ).is_none(), "index {} appears in more than one block", key );
+            }
+            let block: Vec< Vec< Val > >
+                =   keys.iter()
+                        .map(   |row|
+                                keys.iter()
+                                    .map(   |col|
+                                            columns[ *col ].binary_search_by_key( row, |entry| entry.0 )
+                                                .map( |position| columns[ *col ][ position ].1.clone() )
+                                                .unwrap_or_else( |_| RingOperator::zero() )
+                                        )
+                                    .collect()
+                            )
+                        .collect();
+            let inverse     =   invert_dense( block, &ring ).ok_or( SingularBlock{ block: block_id } )?;
+            inverted.push( ( keys, inverse ) );
+        }
+        Ok( BlockJacobiPreconditioner{ blocks: inverted, block_of, ring } )
+    }
+
+    /// The number of blocks.
+    pub fn num_blocks( &self ) -> usize { self.blocks.len() }
+}
+
+impl     < Val, RingOperator >
+        LinearOperator < usize, usize, Val >
+        for
+        BlockJacobiPreconditioner < Val, RingOperator >
+
+        where   RingOperator:   DivisionRing< Val >,
+                Val:            Clone,
+{
+    fn apply( &self, vector: &[ (usize, Val) ] ) -> Vec< (usize, Val) > {
+        // split the vector into one dense right-hand side per block that it touches
+        let mut right_hand_sides: HashMap< usize, Vec< Val > >  =   HashMap::new();
+        let mut result          =   Vec::with_capacity( vector.len() );
+        for ( key, val ) in vector.iter() {
+            match self.block_of.get( key ) {
+                Some( &( block_id, position ) ) => {
+                    let size    =   self.blocks[ block_id ].0.len();
+                    right_hand_sides.entry( block_id ).or_insert_with( || vec![ RingOperator::zero(); size ] )[ position ] = val.clone();
+                }
+                None    =>  result.push( ( *key, val.clone() ) ),
+            }
+        }
+
+        for ( block_id, right_hand_side ) in right_hand_sides {
+            let ( keys, inverse )   =   &self.blocks[ block_id ];
+            for ( key, inverse_row ) in keys.iter().zip( inverse.iter() ) {
+                let val     =   inverse_row.iter()
+                                    .zip( right_hand_side.iter() )
+                                    .fold( RingOperator::zero(), |sum, ( a, x )| self.ring.add( sum, self.ring.multiply( a.clone(), x.clone() ) ) );
+                result.push( ( *key, val ) );
+            }
+        }
+        result.retain( |( _, val )| ! self.ring.is_0( val.clone() ) );
+        result.sort_unstable_by_key( |entry| entry.0 );
+        result
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  GAUSS-SEIDEL
+//  ---------------------------------------------------------------------------
+
+
+/// The Gauss-Seidel preconditioner, in forward or symmetric form.
+///
+/// Write `A = L + D + U`, where `L` is strictly lower triangular, `D` is diagonal, and `U` is
+/// strictly upper triangular.  The forward preconditioner is `M = D + L`, and the symmetric
+/// preconditioner is `M = (D + L) D^{-1} (D + U)`.  Use the symmetric form with solvers (such as
+/// conjugate gradient) that require a symmetric preconditioner.
+///
+/// Applying the preconditioner performs one (forward) or two (forward, then backward) sweeps of
+/// triangular substitution over the columns of `A`, and allocates one dense vector of length
+/// `columns.len()`; input keys must be smaller than `columns.len()`.
+#[derive(Clone, Debug)]
+pub struct GaussSeidelPreconditioner< 'a, Val, RingOperator > {
+    columns:            &'a [ Vec< (usize, Val) > ],
+    diagonal:           Vec< Val >,
+    inverse_diagonal:   Vec< Val >,
+    symmetric:          bool,
+    ring:               RingOperator,
+}
+
+impl    < 'a, Val, RingOperator >
+        GaussSeidelPreconditioner
+        < 'a, Val, RingOperator >
+
+        where   RingOperator:   DivisionRing< Val >,
+                Val:            Clone,
+{
+    /// The forward Gauss-Seidel preconditioner `M = D + L` of the square matrix with sparse
+    /// columns `columns`.
+    ///
+    /// Panics if a diagonal entry is zero or missing; see [`try_new`](GaussSeidelPreconditioner::try_new).
+    pub fn new( columns: &'a [ Vec< (usize, Val) > ], ring: RingOperator ) -> Self {
+        Self::try_new( columns, ring ).unwrap_or_else( |error| panic!( "{}", error ) )
+    }
+
+    /// The forward Gauss-Seidel preconditioner of the square matrix with sparse columns
+    /// `columns`, or an error if a diagonal entry is zero or missing.
+    pub fn try_new( columns: &'a [ Vec< (usize, Val) > ], ring: RingOperator ) -> Result< Self, ZeroDiagonal > {
+        let diagonal                =   diagonal( columns, &ring )?;
+        let inverse_diagonal        =   diagonal.iter().map( |val| ring.invert( val.clone() ) ).collect();
+        Ok( GaussSeidelPreconditioner{ columns, diagonal, inverse_diagonal, symmetric: false, ring } )
+    }
+
+    /// The symmetric Gauss-Seidel preconditioner `M = (D + L) D^{-1} (D + U)` of the square matrix
+    /// with sparse columns `columns`.
+    ///
+    /// Panics if a diagonal entry is zero or missing; see
+    /// [`try_symmetric`](GaussSeidelPreconditioner::try_symmetric).
+    pub fn symmetric( columns: &'a [ Vec< (usize, Val) > ], ring: RingOperator ) -> Self {
+        Self::try_symmetric( columns, ring ).unwrap_or_else( |error| panic!( "{}", error ) )
+    }
+
+    /// The symmetric Gauss-Seidel preconditioner of the square matrix with sparse columns
+    /// `columns`, or an error if a diagonal entry is zero or missing.
+    pub fn try_symmetric( columns: &'a [ Vec< (usize, Val) > ], ring: RingOperator ) -> Result< Self, ZeroDiagonal > {
+        Ok( GaussSeidelPreconditioner{ symmetric: true, ..Self::try_new( columns, ring )? } )
+    }
+
+    /// Returns `true` if this is the symmetric form of the preconditioner.
+    pub fn is_symmetric( &self ) -> bool { self.symmetric }
+}
+
+impl     < 'a, Val, RingOperator >
+        LinearOperator < usize, usize, Val >
+        for
+        GaussSeidelPreconditioner < 'a, Val, RingOperator >
+
+        where   RingOperator:   DivisionRing< Val >,
+                Val:            Clone,
+{
+    fn apply( &self, vector: &[ (usize, Val) ] ) -> Vec< (usize, Val) > {
+        let ring            =   &self.ring;
+        let mut dense       =   vec![ RingOperator::zero(); self.columns.len() ];
+        for ( key, val ) in vector.iter() { dense[ *key ] = val.clone() }
+
+        // forward sweep: solve ( D + L ) y = r
+        for ( index, ( column, inverse ) ) in self.columns.iter().zip( self.inverse_diagonal.iter() ).enumerate() {
+            let solved      =   ring.multiply( dense[ index ].clone(), inverse.clone() );
+            for ( row, val ) in column.iter().filter( |entry| entry.0 > index ) {
+                dense[ *row ]   =   ring.subtract( dense[ *row ].clone(), ring.multiply( val.clone(), solved.clone() ) );
+            }
+            dense[ index ]  =   solved;
+        }
+
+        // backward sweep: solve ( D + U ) x = D y
+        if self.symmetric {
+            for ( val, diagonal ) in dense.iter_mut().zip( self.diagonal.iter() ) {
+                *val        =   ring.multiply( val.clone(), diagonal.clone() );
+            }
+            for ( index, ( column, inverse ) ) in self.columns.iter().zip( self.inverse_diagonal.iter() ).enumerate().rev() {
+                let solved  =   ring.multiply( dense[ index ].clone(), inverse.clone() );
+                for ( row, val ) in column.iter().take_while( |entry| entry.0 < index ) {
+                    dense[ *row ]   =   ring.subtract( dense[ *row ].clone(), ring.multiply( val.clone(), solved.clone() ) );
+                }
+                dense[ index ]  =   solved;
+            }
+        }
+
+        dense.into_iter()
+            .enumerate()
+            .filter( |( _, val )| ! ring.is_0( val.clone() ) )
+            .collect()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::rings::ring_native::NativeDivisionRing;
+    use num::rational::Ratio;
+
+    type Q  =   Ratio< i64 >;
+
+    fn rational_columns( columns: Vec< Vec< (usize, i64) > > ) -> Vec< Vec< (usize, Q) > > {
+        columns.into_iter().map( |column| column.into_iter().map( |( row, val )| ( row, Q::from_integer( val ) ) ).collect() ).collect()
+    }
+
+    /// Check that `A M^{-1} r == r`, i.e. that the preconditioner inverts `A` exactly.
+    fn inverts< Operator: LinearOperator< usize, usize, Q > >( columns: &[ Vec< (usize, Q) > ], preconditioner: &Operator ) -> bool {
+        let ring        =   NativeDivisionRing::< Q >::new();
+        let matrix      =   VecOfVec::new( MajorDimension::Col, columns.to_vec() );
+        let r           =   vec![ (0, Q::from_integer( 1 )), (1, Q::new( -2, 3 )), (2, Q::from_integer( 5 )) ];
+        ( &matrix, ring ).apply( &preconditioner.apply( &r ) ) == r
+    }
+
+    #[test]
+    fn test_preconditioners_invert_matrices_of_their_own_shape() {
+        let ring        =   NativeDivisionRing::< Q >::new();
+        let spd         =   rational_columns( vec![ vec![ (0, 4), (1, 1), (2, 2) ], vec![ (0, 1), (1, 3) ], vec![ (0, 2), (2, 5) ] ] );
+        let lower       =   rational_columns( vec![ vec![ (0, 2), (1, 1), (2, -1) ], vec![ (1, 3), (2, 4) ], vec![ (2, 1) ] ] );
+        let upper       =   rational_columns( vec![ vec![ (0, 2) ], vec![ (0, 1), (1, 3) ], vec![ (0, -1), (1, 4), (2, 1) ] ] );
+        let diagonal    =   rational_columns( vec![ vec![ (0, 2) ], vec![ (1, 3) ], vec![ (2, -1) ] ] );
+
+        // a single block is the whole matrix; a missing block leaves coefficients unchanged
+        assert!( inverts( &spd, &BlockJacobiPreconditioner::new( &spd, vec![ vec![ 2, 0, 1 ] ], ring.clone() ) ) );
+        assert!( ! inverts( &diagonal, &BlockJacobiPreconditioner::new( &diagonal, vec![ vec![ 0 ], vec![ 2 ] ], ring.clone() ) ) );
+        assert!( inverts( &diagonal, &JacobiPreconditioner::new( &diagonal, ring.clone() ) ) );
+
+        // the symmetric form reduces to the forward form on lower triangular matrices
+        assert!( inverts( &lower, &GaussSeidelPreconditioner::new( &lower, ring.clone() ) ) );
+        assert!( inverts( &lower, &GaussSeidelPreconditioner::symmetric( &lower, ring.clone() ) ) );
+        assert!( inverts( &upper, &GaussSeidelPreconditioner::symmetric( &upper, ring.clone() ) ) );
+        assert!( ! inverts( &upper, &GaussSeidelPreconditioner::new( &upper, ring ) ) );
+    }
+
+    #[test]
+    fn test_zero_diagonal_is_an_error() {
+        let ring        =   NativeDivisionRing::< f64 >::new();
+        let explicit    =   vec![ vec![ (0, 1.), (1, 1.) ], vec![ (0, 1.), (1, 0.) ] ];
+        let missing     =   vec![ vec![ (0, 1.) ], vec![ (0, 1.) ] ];
+        assert_eq!( JacobiPreconditioner::try_new( &explicit, ring.clone() ).unwrap_err(), ZeroDiagonal{ index: 1 } );
+        assert_eq!( JacobiPreconditioner::try_new( &missing, ring.clone() ).unwrap_err(), ZeroDiagonal{ index: 1 } );
+        assert!( GaussSeidelPreconditioner::try_new( &missing, ring.clone() ).is_err() );
+        assert!( GaussSeidelPreconditioner::try_symmetric( &explicit, ring.clone() ).is_err() );
+        assert!( std::panic::catch_unwind( || JacobiPreconditioner::new( &missing, ring ) ).is_err() );
+        assert_eq!( ZeroDiagonal{ index: 1 }.to_string(), "diagonal entry 1 is zero" );
+    }
+
+    #[test]
+    fn test_singular_block_is_an_error() {
+        let ring        =   NativeDivisionRing::< f64 >::new();
+        // the block {1, 2} is [[1, 1], [1, 1]], although the matrix itself has no zero diagonal entry
+        let columns     =   vec![ vec![ (0, 2.) ], vec![ (1, 1.), (2, 1.) ], vec![ (1, 1.), (2, 1.) ] ];
+        assert_eq!( BlockJacobiPreconditioner::try_new( &columns, vec![ vec![ 0 ], vec![ 2, 1 ] ], ring.clone() ).unwrap_err(), SingularBlock{ block: 1 } );
+        assert_eq!( BlockJacobiPreconditioner::try_new( &columns, vec![ vec![ 0 ], vec![ 1 ], vec![ 2 ] ], ring.clone() ).unwrap().num_blocks(), 3 );
+        assert!( std::panic::catch_unwind( || BlockJacobiPreconditioner::new( &columns, vec![ vec![ 1, 2 ] ], ring ) ).is_err() );
+    }
+}
@@ -0,0 +1,276 @@
+//! Random sparse matrices, for testing and benchmarking.
+//!
+//! - [`random_sparse`] places each entry independently with a given probability, and draws its
+//!   coefficient from a caller-supplied distribution,
+//! - [`random_signs`] does the same with coefficients `1` and `-1`,
+//! - [`random_upper_unitriangular`] returns an upper triangular matrix with ones on the diagonal,
+//!   which is invertible over every ring, and
+//! - [`random_boundary_like`] returns a matrix whose columns each have the same number of
+//!   entries, with alternating signs, like the boundary matrix of a simplicial complex.
+//!
+//! Every generator takes a caller-supplied random number generator, so results can be made
+//! reproducible by passing a seeded generator.  Coefficients are drawn by a closure that receives
+//! the generator; for a ring object that implements
+//! [`SampleableRing`](crate::rings::random::SampleableRing), `|rng| ring.random_nonzero( rng )`
+//! works over any such ring.  Coefficients equal to zero are dropped, so every stored entry is
+//! nonzero.  Rows and columns are indexed by integers.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::matrices::random::random_sparse;
+//! use solar::matrices::matrix_oracle::MajorDimension;
+//! use solar::rings::ring_native::NativeRing;
+//! use rand::{Rng, SeedableRng};
+//! use rand::rngs::StdRng;
+//!
+//! let mut rng     =   StdRng::seed_from_u64( 0 );
+//! let matrix      =   random_sparse(
+//!                         MajorDimension::Row, 100, 50, 0.1, NativeRing::<i64>::new(),
+//!                         &mut rng, |rng| rng.gen_range( -5 ..= 5 ),
+//!                     );
+//! assert_eq!( matrix.vec_of_vec.len(), 100 );
+//! assert!( matrix.vec_of_vec.iter().flatten().all( |&(col, val)| col < 50 && val != 0 ) );
+//! ```
+
+use crate::matrices::implementors::vec_of_vec::VecOfVec;
+use crate::matrices::matrix_oracle::MajorDimension;
+use crate::rings::ring::{Ring, Semiring};
+use rand::Rng;
+use rand::seq::index;
+
+
+/// For each of `num_major` major views, the minor keys of its entries, in ascending order; each
+/// of the `num_minor` keys appears independently with probability `density`.
+///
+/// Skips over absent keys with geometrically distributed gaps, so the cost is proportional to the
+/// number of entries rather than to `num_major * num_minor`.
+fn random_pattern< R: Rng >( num_major: usize, num_minor: usize, density: f64, rng: &mut R ) -> Vec< Vec< usize > > {
+    assert!( ( 0. ..= 1. ).contains( &density ), "the density must lie between 0 and 1" );
+    ( 0 .. num_major )
+        .map(   |_| {
+                    if density == 0. { return Vec::new() }
+                    if density == 1. { return ( 0 .. num_minor ).collect() }
+                    let mut keys    =   Vec::new();
+                    let mut next    =   0.;
+                    loop {
+                        let gap     =   ( ( 1. - rng.gen::< f64 >() ).ln() / ( 1. - density ).ln() ).floor();
+                        next        +=  gap;
+                        if next >= num_minor as f64 { break }
+                        keys.push( next as usize );
+                        next        +=  1.;
+                    }
+                    keys
+                }
+            )
+        .collect()
+}
+
+/// The number of major and minor views of a `num_rows x num_cols` matrix.
+fn major_minor( major_dimension: &MajorDimension, num_rows: usize, num_cols: usize ) -> ( usize, usize ) {
+    match major_dimension { MajorDimension::Row => ( num_rows, num_cols ), MajorDimension::Col => ( num_cols, num_rows ) }
+}
+
+/// A random `num_rows x num_cols` matrix, in which each entry is stored independently with
+/// probability `density`, with coefficient drawn by `sample`.
+///
+/// Coefficients equal to zero are dropped, so the expected number of stored entries is at most
+/// `density * num_rows * num_cols`.  Panics if `density` does not lie between 0 and 1.
+pub fn random_sparse< 'v, Val, RingOperator, R, Sample >(
+            major_dimension:    MajorDimension,
+            num_rows:           usize,
+            num_cols:           usize,
+            density:            f64,
+            ring:               RingOperator,
+            rng:                &mut R,
+            mut sample:         Sample,
+        )
+        ->
+        VecOfVec< 'v, (usize, Val) >
+
+    where   Val:            'v + Clone,
+            RingOperator:   Semiring< Val >,
+            R:              Rng,
+            Sample:         FnMut( &mut R ) -> Val,
+{
+    let ( num_major, num_minor )    =   major_minor( &major_dimension, num_rows, num_cols );
+    let vec_of_vec  =   random_pattern( num_major, num_minor, density, rng )
+                            .into_iter()
+                            .map(   |keys|
+                                    keys.into_iter()
+                                        .map( |key| ( key, sample( rng ) ) )
+                                        .filter( |( _, val )| ! ring.is_0( val.clone() ) )
+                                        .collect()
+                                )
+                            .collect();
+    VecOfVec::new( major_dimension, vec_of_vec )
+}
+
+/// A random `num_rows x num_cols` matrix, in which each entry is stored independently with
+/// probability `density`, with coefficient `1` or `-1` (each with probability one half).
+///
+/// Panics if `density` does not lie between 0 and 1.
+pub fn random_signs< 'v, Val, RingOperator, R >(
+            major_dimension:    MajorDimension,
+            num_rows:           usize,
+            num_cols:           usize,
+            density:            f64,
+            ring:               RingOperator,
+            rng:                &mut R,
+        )
+        ->
+        VecOfVec< 'v, (usize, Val) >
+
+    where   Val:            'v + Clone,
+            RingOperator:   Ring< Val >,
+            R:              Rng,
+{
+    let minus_one   =   ring.negate( RingOperator::one() );
+    random_sparse(  major_dimension, num_rows, num_cols, density, ring, rng,
+                    |rng| if rng.gen_bool( 0.5 ) { RingOperator::one() } else { minus_one.clone() } )
+}
+
+/// A random `size x size` upper triangular matrix with ones on the diagonal, stored row-major.
+///
+/// Each entry strictly above the diagonal is stored independently with probability `density`,
+/// with coefficient drawn by `sample`; coefficients equal to zero are dropped.  The matrix is
+/// invertible over every ring, and its inverse is again upper unitriangular.  Panics if `density`
+/// does not lie between 0 and 1.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::random::random_upper_unitriangular;
+/// use solar::rings::field_prime::GF2;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let matrix  =   random_upper_unitriangular( 20, 0.2, GF2::new(), &mut StdRng::seed_from_u64( 1 ), |_| true );
+/// for ( row, view ) in matrix.vec_of_vec.iter().enumerate() {
+///     assert_eq!( view[ 0 ], ( row, true ) );
+///     assert!( view.iter().all( |&( col, _ )| col >= row ) );
+/// }
+/// ```
+pub fn random_upper_unitriangular< 'v, Val, RingOperator, R, Sample >(
+            size:               usize,
+            density:            f64,
+            ring:               RingOperator,
+            rng:                &mut R,
+            mut sample:         Sample,
+        )
+        ->
+        VecOfVec< 'v, (usize, Val) >
+
+    where   Val:            'v + Clone,
+            RingOperator:   Semiring< Val >,
+            R:              Rng,
+            Sample:         FnMut( &mut R ) -> Val,
+{
+    let vec_of_vec  =   ( 0 .. size )
+                            .map(   |row| {
+                                        // draw the pattern of the entries to the right of the diagonal
+                                        let keys    =   random_pattern( 1, size - row - 1, density, rng ).pop().unwrap();
+                                        let mut view    =   Vec::with_capacity( keys.len() + 1 );
+                                        view.push( ( row, RingOperator::one() ) );
+                                        for key in keys {
+                                            let val     =   sample( rng );
+                                            if ! ring.is_0( val.clone() ) { view.push( ( row + 1 + key, val ) ) }
+                                        }
+                                        view
+                                    }
+                                )
+                            .collect();
+    VecOfVec::new( MajorDimension::Row, vec_of_vec )
+}
+
+/// A random `num_rows x num_cols` matrix, stored column-major, in which every column has exactly
+/// `entries_per_col` entries, in distinct rows chosen uniformly at random.
+///
+/// As in the boundary of a simplex, the coefficients of each column alternate in sign, starting
+/// with `1` in the top row of the column.  Unlike a boundary matrix, the product of two such
+/// matrices need not vanish.  Panics if `entries_per_col > num_rows`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::random::random_boundary_like;
+/// use solar::rings::ring_native::NativeRing;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let matrix  =   random_boundary_like( 10, 30, 3, NativeRing::<i64>::new(), &mut StdRng::seed_from_u64( 2 ) );
+/// for view in matrix.vec_of_vec.iter() {
+///     assert_eq!( view.iter().map( |&( _, val )| val ).collect::<Vec<_>>(), vec![ 1, -1, 1 ] );
+/// }
+/// ```
+pub fn random_boundary_like< 'v, Val, RingOperator, R >(
+            num_rows:           usize,
+            num_cols:           usize,
+            entries_per_col:    usize,
+            ring:               RingOperator,
+            rng:                &mut R,
+        )
+        ->
+        VecOfVec< 'v, (usize, Val) >
+
+    where   Val:            'v + Clone,
+            RingOperator:   Ring< Val >,
+            R:              Rng,
+{
+    assert!( entries_per_col <= num_rows, "a column cannot have more entries than there are rows" );
+    let vec_of_vec  =   ( 0 .. num_cols )
+                            .map(   |_| {
+                                        let mut rows    =   index::sample( rng, num_rows, entries_per_col ).into_vec();
+                                        rows.sort_unstable();
+                                        let mut sign    =   RingOperator::one();
+                                        rows.into_iter()
+                                            .map(   |row| {
+                                                        let entry   =   ( row, sign.clone() );
+                                                        sign        =   ring.negate( sign.clone() );
+                                                        entry
+                                                    }
+                                                )
+                                            .collect()
+                                    }
+                                )
+                            .collect();
+    VecOfVec::new( MajorDimension::Col, vec_of_vec )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::matrix_oracle::OracleMajorAscend;
+    use crate::rings::ring_native::NativeRing;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_generators_are_reproducible_and_well_formed() {
+        let ring        =   NativeRing::<i64>::new();
+        let generate    =   |seed| random_signs( MajorDimension::Col, 200, 100, 0.05, ring.clone(), &mut StdRng::seed_from_u64( seed ) );
+        let matrix      =   generate( 3 );
+        assert_eq!( matrix.vec_of_vec, generate( 3 ).vec_of_vec );
+        assert_eq!( matrix.major_dimension, MajorDimension::Col );
+        assert_eq!( matrix.vec_of_vec.len(), 100 );
+        for col in 0 .. 100 {
+            let view: Vec< _ >  =   matrix.view_major_ascend( col ).collect();
+            assert!( view.windows( 2 ).all( |pair| pair[0].0 < pair[1].0 ) );
+            assert!( view.iter().all( |&( row, val )| row < 200 && ( val == 1 || val == -1 ) ) );
+        }
+
+        // the number of entries is close to its expected value, 0.05 * 200 * 100 = 1000
+        let num_entries     =   matrix.vec_of_vec.iter().map( |view| view.len() ).sum::< usize >();
+        assert!( ( 800 .. 1200 ).contains( &num_entries ), "{} entries", num_entries );
+
+        // extreme densities
+        let mut rng     =   StdRng::seed_from_u64( 4 );
+        let empty       =   random_sparse( MajorDimension::Row, 3, 4, 0., ring.clone(), &mut rng, |_| 1 );
+        assert!( empty.vec_of_vec.iter().all( |view| view.is_empty() ) );
+        let full        =   random_sparse( MajorDimension::Row, 3, 4, 1., ring, &mut rng, |_| 1 );
+        assert!( full.vec_of_vec.iter().all( |view| view.len() == 4 ) );
+    }
+}
@@ -0,0 +1,412 @@
+//! Random sampling and sketching of matrix oracles.
+//!
+//! The routines in this module extract a smaller matrix from a (possibly very large) matrix
+//! oracle:
+//!
+//! - [`sample_major_views_uniform`] samples major views uniformly at random, without replacement,
+//! - [`sample_major_views_by_norm`] samples major views with probability proportional to their
+//!   squared Euclidean norm (a cheap stand-in for leverage-score sampling), and rescales them so
+//!   that the sample is an unbiased estimator of `A^T A`,
+//! - [`sample_entries`] keeps each structural nonzero independently with a fixed probability, and
+//! - [`CountSketch`] compresses the minor dimension of a float matrix by hashing each minor key
+//!   to a random bucket with a random sign; [`estimate_rank_count_sketch`] uses it to estimate
+//!   the rank of a large matrix.
+//!
+//! Every routine takes a caller-supplied random number generator, so results can be made
+//! reproducible by passing a seeded generator.
+//!
+//! Since an oracle has no notion of "all major keys", the caller supplies the list of major keys
+//! to sample from.  In the output, major view `i` corresponds to the `i`th sampled key.
+
+use crate::matrices::implementors::vec_of_vec::VecOfVec;
+use crate::matrices::matrix_oracle::{OracleMajor, OracleMajorAscend, WhichMajor};
+use crate::vector_entries::vector_entries::KeyValGet;
+use num_traits::Float;
+use rand::Rng;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::index;
+
+
+//  ---------------------------------------------------------------------------
+//  SAMPLING
+//  ---------------------------------------------------------------------------
+
+
+/// Collect a sorted major view into a vector.
+fn collect_view< 'a, Matrix, MajKey, MinKey, Val >( matrix: &'a Matrix, key: MajKey ) -> Vec< (MinKey, Val) >
+    where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+{
+    matrix.view_major_ascend( key ).into_iter().map( |x| ( x.key(), x.val() ) ).collect()
+}
+
+/// Sample `num_samples` of the given major keys uniformly at random, without replacement.
+///
+/// Returns a `VecOfVec` whose major views are the sampled views (with the same major dimension
+/// as `matrix`), together with the sampled keys, listed in the order they were drawn.  Panics if
+/// `num_samples > keys.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::sampling::sample_major_views_uniform;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let matrix          =   VecOfVec::new(
+///                             MajorDimension::Row,
+///                             vec![ vec![ (0, 1.) ], vec![ (1, 1.) ], vec![ (2, 1.) ] ],
+///                         );
+/// let mut rng         =   StdRng::seed_from_u64( 0 );
+/// let (sample, keys)  =   sample_major_views_uniform( &matrix, &[0, 1, 2], 2, &mut rng );
+///
+/// assert_eq!( keys.len(), 2 );
+/// for (i, key) in keys.iter().enumerate() {
+///     assert_eq!( sample.vec_of_vec[ i ], matrix.vec_of_vec[ *key ] );
+/// }
+/// ```
+pub fn sample_major_views_uniform< 'a, 'v, Matrix, MajKey, MinKey, Val, R >(
+            matrix:         &'a Matrix,
+            keys:           &[ MajKey ],
+            num_samples:    usize,
+            rng:            &mut R,
+        )
+        ->
+        ( VecOfVec< 'v, (MinKey, Val) >, Vec< MajKey > )
+
+    where   Matrix:     WhichMajor + OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+            MajKey:     Clone,
+            MinKey:     Clone,
+            Val:        Clone,
+            R:          Rng,
+{
+    let sampled: Vec< MajKey >  =   index::sample( rng, keys.len(), num_samples )
+                                        .into_iter()
+                                        .map( |i| keys[ i ].clone() )
+                                        .collect();
+    let views                   =   sampled.iter().map( |key| collect_view( matrix, key.clone() ) ).collect();
+    ( VecOfVec::new( matrix.major_dimension(), views ), sampled )
+}
+
+/// Sample `num_samples` of the given major keys, with replacement, with probability proportional
+/// to the squared Euclidean norm of the corresponding major view.
+///
+/// If view `i` is drawn with probability `p_i`, then the sampled copy is rescaled by
+/// `1 / sqrt( num_samples * p_i )`; with this normalization, if `A` is the matrix and `S` the
+/// sample then `S^T S` is an unbiased estimator of `A^T A`.  This is the standard "length-squared"
+/// sampling scheme, which approximates leverage-score sampling without computing a factorization.
+///
+/// Returns the sample and the sampled keys.  If every view is zero the sample is empty.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::sampling::sample_major_views_by_norm;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// // row 1 is zero, so it is never sampled
+/// let matrix          =   VecOfVec::new(
+///                             MajorDimension::Row,
+///                             vec![ vec![ (0, 3.) ], vec![], vec![ (1, 4.) ] ],
+///                         );
+/// let mut rng         =   StdRng::seed_from_u64( 0 );
+/// let (_sample, keys) =   sample_major_views_by_norm( &matrix, &[0, 1, 2], 10, &mut rng );
+///
+/// assert_eq!( keys.len(), 10 );
+/// assert!( keys.iter().all( |k| *k != 1 ) );
+/// ```
+pub fn sample_major_views_by_norm< 'a, 'v, Matrix, MajKey, MinKey, Val, R >(
+            matrix:         &'a Matrix,
+            keys:           &[ MajKey ],
+            num_samples:    usize,
+            rng:            &mut R,
+        )
+        ->
+        ( VecOfVec< 'v, (MinKey, Val) >, Vec< MajKey > )
+
+    where   Matrix:     WhichMajor + OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+            MajKey:     Clone,
+            MinKey:     Clone,
+            Val:        Float,
+            R:          Rng,
+{
+    let views: Vec< Vec< (MinKey, Val) > >  =   keys.iter().map( |key| collect_view( matrix, key.clone() ) ).collect();
+    let weights: Vec< f64 >                 =   views.iter()
+                                                    .map( |v| v.iter().map( |x| x.1 * x.1 ).fold( Val::zero(), |a, b| a + b ) )
+                                                    .map( |w| w.to_f64().unwrap_or( 0. ) )
+                                                    .collect();
+    let total: f64                          =   weights.iter().sum();
+
+    let mut sample          =   Vec::with_capacity( num_samples );
+    let mut sampled         =   Vec::with_capacity( num_samples );
+    if let Ok( distribution ) = WeightedIndex::new( &weights ) {
+        for _ in 0 .. num_samples {
+            let i           =   distribution.sample( rng );
+            let scale       =   Val::from( 1. / ( num_samples as f64 * weights[ i ] / total ).sqrt() ).unwrap();
+            sample.push( views[ i ].iter().map( |(k, v)| ( k.clone(), *v * scale ) ).collect() );
+            sampled.push( keys[ i ].clone() );
+        }
+    }
+    ( VecOfVec::new( matrix.major_dimension(), sample ), sampled )
+}
+
+/// Keep each structural nonzero of the given major views independently with probability
+/// `probability`.
+///
+/// Major view `i` of the output is a random subset of the entries of the view with key
+/// `keys[ i ]`.  Coefficients are not rescaled.  Panics unless `0 <= probability <= 1`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::sampling::sample_entries;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let matrix      =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (1, 2), (2, 3) ] ] );
+/// let mut rng     =   StdRng::seed_from_u64( 0 );
+///
+/// assert_eq!( sample_entries( &matrix, &[0], 1.0, &mut rng ).vec_of_vec, matrix.vec_of_vec );
+/// assert!( sample_entries( &matrix, &[0], 0.0, &mut rng ).vec_of_vec[0].is_empty() );
+/// ```
+pub fn sample_entries< 'a, 'v, Matrix, MajKey, MinKey, Val, R >(
+            matrix:         &'a Matrix,
+            keys:           &[ MajKey ],
+            probability:    f64,
+            rng:            &mut R,
+        )
+        ->
+        VecOfVec< 'v, (MinKey, Val) >
+
+    where   Matrix:     WhichMajor + OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+            MajKey:     Clone,
+            MinKey:     Clone,
+            Val:        Clone,
+            R:          Rng,
+{
+    assert!( ( 0. ..= 1. ).contains( &probability ), "probability must lie in [0, 1]" );
+    let views   =   keys.iter()
+                        .map(   |key|
+                                matrix.view_major_ascend( key.clone() )
+                                    .into_iter()
+                                    .filter( |_| rng.gen_bool( probability ) )
+                                    .map( |x| ( x.key(), x.val() ) )
+                                    .collect()
+                            )
+                        .collect();
+    VecOfVec::new( matrix.major_dimension(), views )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  SKETCHING
+//  ---------------------------------------------------------------------------
+
+
+/// A CountSketch operator, which compresses vectors indexed by `0 .. num_minor` to dense vectors
+/// of length `sketch_dim`.
+///
+/// Each index `j` is assigned a random bucket `h(j)` in `0 .. sketch_dim` and a random sign
+/// `s(j) = ±1`; a sparse vector `x` is mapped to the dense vector `y` with
+/// `y[ b ] = sum over { j : h(j) = b } of s(j) * x[ j ]`.  Applying the sketch to every major view
+/// of a matrix `A` computes `A S^T`, where `S` is a random `sketch_dim x num_minor` matrix with
+/// exactly one nonzero entry per column.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::sampling::CountSketch;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let sketch  =   CountSketch::new( 100, 10, &mut StdRng::seed_from_u64( 0 ) );
+/// let y       =   sketch.sketch_view( vec![ (3, 2.0), (50, -1.0) ] );
+///
+/// assert_eq!( y.len(), 10 );
+/// assert!( y.iter().map( |x| x.abs() ).sum::<f64>() <= 3.0 );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CountSketch {
+    sketch_dim:     usize,
+    buckets:        Vec< usize >,
+    signs:          Vec< f64 >,
+}
+
+impl CountSketch {
+
+    /// Draw a random CountSketch operator from `0 .. num_minor` to `0 .. sketch_dim`.
+    ///
+    /// Panics if `sketch_dim == 0`.
+    pub fn new< R: Rng >( num_minor: usize, sketch_dim: usize, rng: &mut R ) -> Self {
+        assert!( sketch_dim > 0, "the sketch dimension must be positive" );
+        let buckets     =   ( 0 .. num_minor ).map( |_| rng.gen_range( 0 .. sketch_dim ) ).collect();
+        let signs       =   ( 0 .. num_minor ).map( |_| if rng.gen_bool( 0.5 ) { 1. } else { -1. } ).collect();
+        CountSketch{ sketch_dim, buckets, signs }
+    }
+
+    /// The length of a sketched vector.
+    pub fn sketch_dim( &self ) -> usize { self.sketch_dim }
+
+    /// The number of minor keys the sketch accepts.
+    pub fn num_minor( &self ) -> usize { self.buckets.len() }
+
+    /// Sketch a sparse vector.  Panics if a key is `>= self.num_minor()`.
+    pub fn sketch_view< I >( &self, view: I ) -> Vec< f64 >
+        where   I:          IntoIterator,
+                I::Item:    KeyValGet< Key = usize, Val = f64 >,
+    {
+        let mut sketched    =   vec![ 0.; self.sketch_dim ];
+        for entry in view {
+            let key         =   entry.key();
+            sketched[ self.buckets[ key ] ] += self.signs[ key ] * entry.val();
+        }
+        sketched
+    }
+
+    /// Sketch the major views with the given keys; entry `i` of the output is the sketch of the
+    /// view with key `keys[ i ]`.
+    pub fn sketch_major_views< 'a, Matrix, MajKey >( &self, matrix: &'a Matrix, keys: &[ MajKey ] ) -> Vec< Vec< f64 > >
+        where   Matrix:     OracleMajor< 'a, MajKey, usize, f64 >,
+                MajKey:     Clone,
+    {
+        keys.iter().map( |key| self.sketch_view( matrix.view_major( key.clone() ) ) ).collect()
+    }
+}
+
+/// The numerical rank of a dense matrix, computed by Gaussian elimination with partial pivoting.
+///
+/// A pivot is accepted only if its absolute value exceeds `tolerance`.  Pivots are compared
+/// with [`f64::total_cmp`], so `NaN` entries do not cause a panic: a `NaN` has the largest
+/// absolute value of all, and is always accepted as a pivot.
+///
+/// Panics if the rows have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::sampling::numerical_rank_dense;
+///
+/// let rows    =   vec![ vec![ 1., 2. ], vec![ 2., 4. ], vec![ 0., 1. ] ];
+/// assert_eq!( numerical_rank_dense( rows, 1e-10 ), 2 );
+/// ```
+pub fn numerical_rank_dense( mut rows: Vec< Vec< f64 > >, tolerance: f64 ) -> usize {
+    let num_cols    =   rows.first().map( |x| x.len() ).unwrap_or( 0 );
+    assert!( rows.iter().all( |x| x.len() == num_cols ), "every row of a dense matrix must have the same length" );
+    let mut rank    =   0;
+    for col in 0 .. num_cols {
+        if rank == rows.len() { break }
+        let pivot   =   ( rank .. rows.len() )
+                            .max_by( |&a, &b| rows[ a ][ col ].abs().total_cmp( &rows[ b ][ col ].abs() ) )
+                            .unwrap();
+        if rows[ pivot ][ col ].abs() <= tolerance { continue }
+        rows.swap( rank, pivot );
+        let ( head, tail )  =   rows.split_at_mut( rank + 1 );
+        let pivot_row       =   &head[ rank ];
+        for row in tail.iter_mut() {
+            let factor      =   row[ col ] / pivot_row[ col ];
+            for ( x, p ) in row.iter_mut().zip( pivot_row.iter() ).skip( col ) { *x -= factor * p }
+        }
+        rank += 1;
+    }
+    rank
+}
+
+/// Estimate the rank of a float matrix by sketching its minor dimension with a [`CountSketch`].
+///
+/// The rank of the sketched matrix `A S^T` never exceeds the rank of `A`, and equals it with high
+/// probability when `sketch_dim` is sufficiently large relative to the rank.  Only the major
+/// views with the given keys are read, and every minor key must be `< num_minor`.  See
+/// [`numerical_rank_dense`] for the meaning of `tolerance`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::sampling::estimate_rank_count_sketch;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// // a rank-1 matrix
+/// let matrix  =   VecOfVec::new(
+///                     MajorDimension::Row,
+///                     vec![ vec![ (0, 1.), (5, 2.) ], vec![ (0, -2.), (5, -4.) ] ],
+///                 );
+/// let rank    =   estimate_rank_count_sketch( &matrix, &[0, 1], 6, 4, 1e-10, &mut StdRng::seed_from_u64( 1 ) );
+/// assert!( rank <= 1 );
+/// ```
+pub fn estimate_rank_count_sketch< 'a, Matrix, MajKey, R >(
+            matrix:         &'a Matrix,
+            keys:           &[ MajKey ],
+            num_minor:      usize,
+            sketch_dim:     usize,
+            tolerance:      f64,
+            rng:            &mut R,
+        )
+        -> usize
+
+    where   Matrix:     OracleMajor< 'a, MajKey, usize, f64 >,
+            MajKey:     Clone,
+            R:          Rng,
+{
+    let sketch      =   CountSketch::new( num_minor, sketch_dim, rng );
+    numerical_rank_dense( sketch.sketch_major_views( matrix, keys ), tolerance )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::matrix_oracle::MajorDimension;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_numerical_rank_dense_edge_cases() {
+        assert_eq!( numerical_rank_dense( vec![], 1e-10 ), 0 );
+        assert_eq!( numerical_rank_dense( vec![ vec![ f64::NAN, 1. ], vec![ 1., 1. ] ], 1e-10 ), 2 );
+        assert_eq!( numerical_rank_dense( vec![ vec![ 0., 0. ], vec![ 0., 1e-12 ] ], 1e-10 ), 0 );
+        let ragged  =   std::panic::catch_unwind( || numerical_rank_dense( vec![ vec![ 1., 2. ], vec![ 1. ] ], 1e-10 ) );
+        assert!( ragged.is_err() );
+    }
+
+    #[test]
+    fn test_count_sketch_rank_of_low_rank_matrix() {
+        // the rows are linear combinations of two fixed vectors, so the matrix has rank 2
+        let u: Vec< f64 >   =   ( 0 .. 40 ).map( |j| ( j % 7 ) as f64 - 3. ).collect();
+        let v: Vec< f64 >   =   ( 0 .. 40 ).map( |j| ( ( j * j ) % 5 ) as f64 ).collect();
+        let rows            =   ( 0 .. 30 )
+                                    .map(   |i|
+                                            ( 0 .. 40 )
+                                                .map( |j| ( j, ( i % 3 ) as f64 * u[ j ] + ( i % 4 ) as f64 * v[ j ] ) )
+                                                .filter( |x| x.1 != 0. )
+                                                .collect()
+                                        )
+                                    .collect();
+        let matrix          =   VecOfVec::new( MajorDimension::Row, rows );
+        let keys: Vec< _ >  =   ( 0 .. 30 ).collect();
+
+        let exact           =   numerical_rank_dense(
+                                    matrix.vec_of_vec.iter()
+                                        .map( |row| { let mut d = vec![ 0.; 40 ]; for (j, x) in row { d[ *j ] = *x } d } )
+                                        .collect(),
+                                    1e-8,
+                                );
+        assert_eq!( exact, 2 );
+
+        let mut rng         =   StdRng::seed_from_u64( 7 );
+        for _ in 0 .. 5 {
+            let estimate    =   estimate_rank_count_sketch( &matrix, &keys, 40, 10, 1e-8, &mut rng );
+            assert!( estimate <= 2 );
+        }
+        assert_eq!( estimate_rank_count_sketch( &matrix, &keys, 40, 40, 1e-8, &mut rng ), 2 );
+    }
+}
@@ -0,0 +1,169 @@
+//! Barcodes of filtered simplicial complexes, from the output of a reduction.
+//!
+//! Reducing the boundary matrix of a complex with all dimensions concatenated (as produced by
+//! [`boundary_matrix_from_complex_facets`](crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets))
+//! yields a list of pivot pairs `(row, col)` of simplex indices.  The function
+//! [`barcode_from_pivots`] translates these pairs back into simplices, and assigns each bar its
+//! homology dimension and its birth and death filtration values.
+
+use crate::matrix_factorization::field_sweep::index_barcode;
+use crate::utilities::sequences_and_ordinals::BiMapSequential;
+use std::hash::Hash;
+
+
+/// A bar in the barcode of a filtered simplicial complex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bar< Filtration > {
+    /// The homology dimension (the dimension of the simplex that gives birth to the class).
+    pub dim:            usize,
+    /// The filtration value of the simplex that gives birth to the class.
+    pub birth:          Filtration,
+    /// The filtration value of the simplex that kills the class, or `None` if the class never
+    /// dies.
+    pub death:          Option< Filtration >,
+    /// The index (in the simplex bimap) of the simplex that gives birth to the class.
+    pub birth_index:    usize,
+    /// The index (in the simplex bimap) of the simplex that kills the class, if any.
+    pub death_index:    Option< usize >,
+}
+
+/// The barcode of a filtered simplicial complex, in every dimension.
+///
+/// Bars are sorted by dimension, then by the index of the birth simplex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Barcode< Filtration > {
+    pub bars:   Vec< Bar< Filtration > >,
+}
+
+impl < Filtration > Barcode< Filtration > {
+
+    /// The bars in dimension `dim`.
+    pub fn bars_in_dim( &self, dim: usize ) -> impl Iterator< Item = &Bar< Filtration > > {
+        self.bars.iter().filter( move |bar| bar.dim == dim )
+    }
+
+    /// The `(birth, death)` intervals in dimension `dim`.
+    pub fn intervals( &self, dim: usize ) -> Vec< ( Filtration, Option< Filtration > ) >
+        where   Filtration:     Clone,
+    {
+        self.bars_in_dim( dim ).map( |bar| ( bar.birth.clone(), bar.death.clone() ) ).collect()
+    }
+
+    /// The number of infinite bars in each dimension, from 0 up to the largest dimension of a bar.
+    pub fn betti_numbers( &self ) -> Vec< usize > {
+        let mut betti   =   vec![ 0; self.bars.iter().map( |bar| bar.dim + 1 ).max().unwrap_or( 0 ) ];
+        for bar in self.bars.iter().filter( |bar| bar.death.is_none() ) { betti[ bar.dim ] += 1 }
+        betti
+    }
+
+    /// Remove every bar whose birth and death filtration values are equal.
+    pub fn drop_zero_length( &mut self )
+        where   Filtration:     PartialEq,
+    {
+        self.bars.retain( |bar| bar.death.as_ref() != Some( &bar.birth ) );
+    }
+}
+
+/// Build the barcode of a filtered simplicial complex from the pivot pairs of its reduced
+/// boundary matrix.
+///
+/// - `pivot_pairs` contains the pairs `(row, col)` such that column `col` of the reduced
+///   boundary matrix has its pivot in row `row` (e.g. the output of
+///   [`right_reduce`](crate::matrix_factorization::vec_of_vec::right_reduce)), in any order.
+/// - `simplex_bimap` identifies simplices with row and column indices; it should list the
+///   simplices in filtration order.
+/// - `filtration` returns the filtration value of a simplex.
+///
+/// Each pair `(row, col)` yields a bar in dimension `dim( simplex row )` that is born at
+/// `filtration( simplex row )` and dies at `filtration( simplex col )`; each simplex that appears
+/// in no pair yields an infinite bar.  Bars of length zero are kept; see
+/// [`Barcode::drop_zero_length`].
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrix_factorization::barcode::barcode_from_pivots;
+/// use solar::matrix_factorization::vec_of_vec::right_reduce;
+/// use solar::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets;
+/// use solar::utilities::sequences_and_ordinals::BiMapSequential;
+/// use solar::rings::field_prime::GF2;
+///
+/// // a triangle whose vertices appear at time 0, edges at times 1, 2, 3, and face at time 4
+/// let simplices       =   vec![ vec![0], vec![1], vec![2], vec![0,1], vec![0,2], vec![1,2], vec![0,1,2] ];
+/// let filtration      =   |s: &Vec<usize>| if s.len() == 3 { 4 } else if s.len() == 2 { s[0] + s[1] } else { 0 };
+/// let bimap           =   BiMapSequential::from_vec( simplices );
+/// let mut boundary    =   boundary_matrix_from_complex_facets( &bimap, GF2::new() );
+/// let pivots          =   right_reduce( &mut boundary, GF2::new() );
+///
+/// let barcode         =   barcode_from_pivots( pivots, &bimap, filtration );
+/// assert_eq!( barcode.intervals( 0 ), vec![ (0, None), (0, Some(1)), (0, Some(2)) ] );
+/// assert_eq!( barcode.intervals( 1 ), vec![ (3, Some(4)) ] );
+/// assert_eq!( barcode.betti_numbers(), vec![ 1, 0 ] );
+/// ```
+pub fn barcode_from_pivots< Vertex, Filtration, Pairs, F >(
+            pivot_pairs:    Pairs,
+            simplex_bimap:  &BiMapSequential< Vec< Vertex > >,
+            mut filtration: F,
+        )
+        ->
+        Barcode< Filtration >
+
+    where   Vertex:     Clone + Hash + Eq,
+            Pairs:      IntoIterator< Item = ( usize, usize ) >,
+            F:          FnMut( &Vec< Vertex > ) -> Filtration,
+{
+    let simplices               =   &simplex_bimap.ord_to_val;
+    let mut pivot_pairs: Vec< _ >   =   pivot_pairs.into_iter().collect();
+    pivot_pairs.sort_unstable();
+
+    let mut bars: Vec< Bar< Filtration > >
+        =   index_barcode( simplices.len(), &pivot_pairs )
+                .into_iter()
+                .map(   |( birth_index, death_index )|
+                        Bar{
+                            dim:            simplices[ birth_index ].len() - 1,
+                            birth:          filtration( &simplices[ birth_index ] ),
+                            death:          death_index.map( |i| filtration( &simplices[ i ] ) ),
+                            birth_index,
+                            death_index,
+                        }
+                    )
+                .collect();
+    bars.sort_by_key( |bar| ( bar.dim, bar.birth_index ) );
+    Barcode{ bars }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrix_factorization::vec_of_vec::right_reduce;
+    use crate::rings::field_prime::GF2;
+    use crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets;
+    use crate::utilities::cell_complexes::simplices_unweighted::facets::ordered_subsimplices_up_thru_dim_concatenated_vec;
+
+    #[test]
+    fn test_hollow_tetrahedron_has_sphere_betti_numbers() {
+        let facets          =   vec![ vec![0,1,2], vec![0,1,3], vec![0,2,3], vec![1,2,3] ];
+        let simplices       =   ordered_subsimplices_up_thru_dim_concatenated_vec( &facets, 2 );
+        let bimap           =   BiMapSequential::from_vec( simplices );
+        let mut boundary    =   boundary_matrix_from_complex_facets( &bimap, GF2::new() );
+        let pivots          =   right_reduce( &mut boundary, GF2::new() );
+
+        // vertices and edges enter at time 0 and triangles at time 1, so the bars that pair
+        // vertices with edges have length zero
+        let mut barcode     =   barcode_from_pivots( pivots, &bimap, |s| if s.len() == 3 { 1 } else { 0 } );
+        assert_eq!( barcode.bars.len(), 4 + 6 + 4 - 2 * ( 3 + 3 ) + 2 * 3 );
+        assert_eq!( barcode.betti_numbers(), vec![ 1, 0, 1 ] );
+
+        barcode.drop_zero_length();
+        assert_eq!( barcode.intervals( 0 ), vec![ (0, None) ] );
+        assert_eq!( barcode.intervals( 1 ), vec![ (0, Some(1)); 3 ] );
+        assert_eq!( barcode.intervals( 2 ), vec![ (1, None) ] );
+        for bar in barcode.bars.iter() {
+            assert_eq!( bar.dim + 1, bimap.ord_to_val[ bar.birth_index ].len() );
+        }
+    }
+}
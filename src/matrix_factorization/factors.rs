@@ -0,0 +1,248 @@
+//! Owned factorizations, with export to standard matrix types.
+//!
+//! The function [`right_reduce`](crate::matrix_factorization::vec_of_vec::right_reduce) reduces a
+//! matrix `D` in place, but discards the column operations it performs.  The function
+//! [`right_reduce_factored`] records these operations, and returns a [`RightReduction`] that owns
+//! both factors of the decomposition
+//!
+//! ```text
+//! R = D V
+//! ```
+//!
+//! where `R` is reduced and `V` is upper unitriangular.  The factors can be exported as
+//! [`VecOfVecSimple`] matrices or as compressed sparse [`VecCsv`] matrices (both column-major), so
+//! that they can be passed to external solvers or verification scripts.
+//!
+//! **Note** the U-match factorization is still under construction; when it becomes available,
+//! its factors should be exported with the same conventions.
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use crate::matrices::implementors::vec_of_vec_simple::VecOfVecSimple;
+use crate::matrices::implementors::vec_of_csvec::VecCsv;
+use crate::matrices::matrix_oracle::MajorDimension;
+use crate::matrix_factorization::vec_of_vec::{try_right_reduce_by_order_observed, ReductionObserver, ZeroPivot};
+use crate::utilities::order::OrderNatural;
+use crate::vectors::operations::{add_assign_sorted_with_buffer};
+use std::collections::HashMap;
+
+
+/// A pair of column-major matrices `(R, V)`.
+pub type VecOfVecFactors< Val > = ( VecOfVecSimple< usize, Val >, VecOfVecSimple< usize, Val > );
+
+/// A pair of column-major compressed sparse matrices `(R, V)`.
+pub type VecCsvFactors< Val > = ( VecCsv< usize, Val >, VecCsv< usize, Val > );
+
+/// Pack a list of sorted columns into a column-major [`VecCsv`].
+fn columns_to_vec_csv< Val, Columns >( columns: Columns ) -> VecCsv< usize, Val >
+    where   Columns:    IntoIterator< Item = Vec< (usize, Val) > >,
+{
+    let mut major_ptr   =   vec![ 0 ];
+    let mut min_ind     =   Vec::new();
+    let mut snz_val     =   Vec::new();
+    for column in columns {
+        for ( row, val ) in column { min_ind.push( row ); snz_val.push( val ); }
+        major_ptr.push( min_ind.len() );
+    }
+    VecCsv::new( MajorDimension::Col, major_ptr, min_ind, snz_val )
+}
+
+/// The factors of a right reduction `R = D V`, stored as vectors of sorted sparse columns.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RightReduction< Val > {
+    reduced:    Vec< Vec< (usize, Val) > >,
+    v:          Vec< Vec< (usize, Val) > >,
+    pivots:     HashMap< usize, usize >,
+}
+
+impl < Val: Clone > RightReduction< Val > {
+
+    /// The reduced matrix `R`.
+    pub fn reduced( &self ) -> &Vec< Vec< (usize, Val) > > { &self.reduced }
+
+    /// The upper unitriangular matrix `V` such that `R = D V`.
+    pub fn v( &self ) -> &Vec< Vec< (usize, Val) > > { &self.v }
+
+    /// A hashmap sending each pivot row of `R` to the corresponding pivot column.
+    pub fn pivots( &self ) -> &HashMap< usize, usize > { &self.pivots }
+
+    /// A copy of `R`, as a column-major [`VecOfVecSimple`].
+    pub fn reduced_vec_of_vec( &self ) -> VecOfVecSimple< usize, Val > {
+        VecOfVecSimple::new( MajorDimension::Col, self.reduced.clone() )
+    }
+
+    /// A copy of `V`, as a column-major [`VecOfVecSimple`].
+    pub fn v_vec_of_vec( &self ) -> VecOfVecSimple< usize, Val > {
+        VecOfVecSimple::new( MajorDimension::Col, self.v.clone() )
+    }
+
+    /// Consume the factorization, returning `(R, V)` as column-major [`VecOfVecSimple`]s.
+    pub fn into_vec_of_vecs( self ) -> VecOfVecFactors< Val > {
+        (   VecOfVecSimple::new( MajorDimension::Col, self.reduced ),
+            VecOfVecSimple::new( MajorDimension::Col, self.v ),   )
+    }
+
+    /// A copy of `R`, as a column-major [`VecCsv`] (i.e. in compressed sparse column format).
+    pub fn reduced_vec_csv( &self ) -> VecCsv< usize, Val > { columns_to_vec_csv( self.reduced.iter().cloned() ) }
+
+    /// A copy of `V`, as a column-major [`VecCsv`] (i.e. in compressed sparse column format).
+    pub fn v_vec_csv( &self ) -> VecCsv< usize, Val > { columns_to_vec_csv( self.v.iter().cloned() ) }
+
+    /// Consume the factorization, returning `(R, V)` as column-major [`VecCsv`]s.
+    ///
+    /// With the `nalgebra-sparse` feature, each factor can then be converted to a `CscMatrix` with
+    /// `VecCsv::into_csc_matrix`.
+    pub fn into_vec_csvs( self ) -> VecCsvFactors< Val > {
+        ( columns_to_vec_csv( self.reduced ), columns_to_vec_csv( self.v ) )
+    }
+}
+
+
+/// Right-reduce a copy of `matrix`, recording the column operations.
+///
+/// The reduction is the same as the one performed by
+/// [`right_reduce`](crate::matrix_factorization::vec_of_vec::right_reduce); the entries of each
+/// column must be sorted.  Panics if the reduction needs to divide by zero; see
+/// [`try_right_reduce_factored`].
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrix_factorization::factors::right_reduce_factored;
+/// use solar::rings::ring_native::NativeDivisionRing;
+///
+/// let matrix      =   vec![
+///                         vec![ (0, 1.), (1, 1.) ],
+///                         vec![ (0, 1.), (1, 1.) ],
+///                     ];
+/// let factors     =   right_reduce_factored( &matrix, NativeDivisionRing::<f64>::new() );
+///
+/// assert_eq!( factors.reduced(), &vec![ vec![ (0, 1.), (1, 1.) ], vec![] ] );
+/// assert_eq!( factors.v(),       &vec![ vec![ (0, 1.) ], vec![ (0, -1.), (1, 1.) ] ] );
+///
+/// // export to a column-major vec-of-vec
+/// let (_r, v)     =   factors.clone().into_vec_of_vecs();
+/// assert_eq!( v.vec_of_vec[1], vec![ (0, -1.), (1, 1.) ] );
+///
+/// // export to a compressed sparse column matrix
+/// let (_r, v)     =   factors.into_vec_csvs();
+/// assert_eq!( v.major_ptr(), &vec![ 0, 1, 3 ] );
+/// assert_eq!( v.min_ind(),   &vec![ 0, 0, 1 ] );
+/// ```
+pub fn  right_reduce_factored< Val, RingOperator >(
+            matrix:     &   [ Vec< (usize, Val) > ],
+            ring:           RingOperator,
+        )
+        ->
+        RightReduction< Val >
+
+        where   RingOperator:   Semiring< Val > + Ring< Val > + DivisionRing< Val > + Clone,
+                Val:            Clone,
+{
+    try_right_reduce_factored( matrix, ring ).unwrap_or_else( |error| panic!( "{}", error ) )
+}
+
+
+/// Right-reduce a copy of `matrix`, recording the column operations, or return an error if the
+/// reduction needs to divide by zero.
+///
+/// This is the same as [`right_reduce_factored`], except that it returns [`ZeroPivot`] instead of
+/// panicking.
+pub fn  try_right_reduce_factored< Val, RingOperator >(
+            matrix:     &   [ Vec< (usize, Val) > ],
+            ring:           RingOperator,
+        )
+        ->
+        Result< RightReduction< Val >, ZeroPivot >
+
+        where   RingOperator:   Semiring< Val > + Ring< Val > + DivisionRing< Val > + Clone,
+                Val:            Clone,
+{
+    let mut reduced                                 =   matrix.to_vec();
+    let mut operations                              =   RecordColumnOperations{
+                                                            v:      ( 0 .. matrix.len() ).map( |j| vec![ ( j, RingOperator::one() ) ] ).collect(),
+                                                            ring:   ring.clone(),
+                                                            buffer: Vec::new(),
+                                                        };
+    let pivots          =   try_right_reduce_by_order_observed( &mut reduced, ring, OrderNatural, &mut operations )?;
+
+    Ok( RightReduction{ reduced, v: operations.v, pivots } )
+}
+
+
+/// Applies each column operation of a right reduction to the columns of `V`.
+struct RecordColumnOperations< Val, RingOperator > {
+    v:          Vec< Vec< (usize, Val) > >,
+    ring:       RingOperator,
+    buffer:     Vec< (usize, Val) >,
+}
+
+impl < Val, RingOperator > ReductionObserver< usize, Val > for RecordColumnOperations< Val, RingOperator >
+
+    where   RingOperator:   Semiring< Val > + Clone,
+            Val:            Clone,
+{
+    fn column_operation(
+            &mut self,
+            clearee_index:      usize,
+            clearor_index:      usize,
+            scalar:             &Val,
+            _clearee:           &[ (usize, Val) ],
+            _clearor:           &[ (usize, Val) ],
+        )
+    {
+        // columns are reduced in order, so the clearor always precedes the clearee
+        let ( done, todo )  =   self.v.split_at_mut( clearee_index );
+        add_assign_sorted_with_buffer( &mut todo[ 0 ], &done[ clearor_index ], scalar.clone(), self.ring.clone(), &mut self.buffer );
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GFp;
+    use crate::matrix_factorization::vec_of_vec::right_reduce;
+    use crate::vectors::operations::add_assign_sorted;
+    use crate::utilities::cell_complexes::simplices_unweighted::standard_complexes::{facets_torus, boundary_matrix_from_facets};
+    use crate::rings::ring_native::NativeRing;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+
+    #[test]
+    fn test_factors_multiply_to_reduced_matrix() {
+        let ring            =   GFp::new( 3 );
+        let (_, boundary)   =   boundary_matrix_from_facets( &facets_torus(), 2, NativeRing::<i64>::new() );
+        let matrix: Vec< Vec< (usize, u64) > >
+                            =   boundary.iter()
+                                    .map( |col| col.iter().map( |(i, x)| ( *i, x.rem_euclid( 3 ) as u64 ) ).collect() )
+                                    .collect();
+
+        let factors         =   right_reduce_factored( &matrix, ring.clone() );
+
+        // agrees with the in-place reduction
+        let mut reduced     =   matrix.clone();
+        let pivots          =   right_reduce( &mut reduced, ring.clone() );
+        assert_eq!( factors.reduced(), &reduced );
+        assert_eq!( factors.pivots(), &pivots );
+
+        // D V = R, and V is upper unitriangular
+        for (j, v_col) in factors.v().iter().enumerate() {
+            assert_eq!( v_col.last(), Some( &( j, 1 ) ) );
+            let mut product     =   Vec::new();
+            for (k, val) in v_col.iter() { add_assign_sorted( &mut product, &matrix[ *k ], *val, ring.clone() ) }
+            assert_eq!( product, factors.reduced()[ j ] );
+        }
+
+        // the compressed sparse export holds the same columns
+        let (r_csv, v_csv)  =   factors.clone().into_vec_csvs();
+        assert_eq!( VecOfVec::from( r_csv ).vec_of_vec, *factors.reduced() );
+        assert_eq!( VecOfVec::from( v_csv ).vec_of_vec, *factors.v() );
+        assert_eq!( factors.v_vec_csv().num_major(), matrix.len() );
+
+        // an explicit zero in a pivot position is an error, not a division by zero
+        let matrix          =   vec![ vec![ (0, 1), (1, 0) ], vec![ (1, 1) ] ];
+        assert_eq!( try_right_reduce_factored( &matrix, ring ), Err( ZeroPivot{ column: 0 } ) );
+    }
+}
@@ -0,0 +1,251 @@
+//! Run the same reduction over several coefficient fields, and compare the results.
+//!
+//! Homology with integer coefficients can have torsion, which shows up as a difference
+//! between barcodes computed over different fields (e.g. the real projective plane has
+//! different barcodes over GF2 and over the rationals).  The [`FieldSweep`] struct stores a
+//! single integer boundary matrix, reduces a copy over each field the user supplies, and
+//! reports where the resulting barcodes differ.
+//!
+//! Barcodes are reported with respect to the *index filtration*, i.e. the filtration
+//! in which column `i` of the boundary matrix enters at time `i`.
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use crate::matrix_factorization::vec_of_vec::right_reduce;
+use std::fmt::Debug;
+
+
+//  ---------------------------------------------------------------------------
+//  BARS
+//  ---------------------------------------------------------------------------
+
+/// A bar in the barcode of an index filtration.
+///
+/// The `birth` is the index of the column that creates the class; the `death` is the index
+/// of the column that kills it, or `None` if the class never dies.
+pub type IndexBar = ( usize, Option< usize > );
+
+/// The outcome of reducing the integer matrix over a single field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldSweepResult {
+    /// A user-supplied name for the field, e.g. `"GF2"`.
+    pub label:          String,
+    /// Pivot pairs `(row, col)`, sorted in ascending order.
+    pub pivot_pairs:    Vec< (usize, usize) >,
+    /// The barcode of the index filtration, sorted in ascending order.
+    pub barcode:        Vec< IndexBar >,
+}
+
+/// Records the bars that appear over one field but not over another.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDiscrepancy {
+    /// Label of the reference field (the first field added to the sweep).
+    pub label_reference:    String,
+    /// Label of the field that disagrees with the reference.
+    pub label_other:        String,
+    /// Bars that appear over the reference field, but not the other.
+    pub only_in_reference:  Vec< IndexBar >,
+    /// Bars that appear over the other field, but not the reference.
+    pub only_in_other:      Vec< IndexBar >,
+}
+
+
+//  ---------------------------------------------------------------------------
+//  SWEEP
+//  ---------------------------------------------------------------------------
+
+/// Reduces one integer boundary matrix over a sequence of fields.
+///
+/// The matrix is stored as a vector of sparse columns, each sorted in ascending order of
+/// row index (the same format accepted by [`right_reduce`]).
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrix_factorization::field_sweep::FieldSweep;
+/// use solar::rings::field_prime::GF2;
+/// use solar::rings::ring_native::NativeDivisionRing;
+/// use num::rational::Ratio;
+///
+/// // Boundary matrix of a 1-cell attached to a single vertex along a degree-2 map
+/// // (a "Moore space" M(Z/2, 1) after adding a 2-cell with boundary 2 * edge).
+/// let matrix      =   vec![
+///                         vec![],             // vertex
+///                         vec![],             // edge (a loop, so boundary is 0)
+///                         vec![ (1, 2) ],     // 2-cell, glued along the loop twice
+///                     ];
+///
+/// let mut sweep   =   FieldSweep::new( matrix );
+/// sweep.add_field( "Q",   NativeDivisionRing::< Ratio<i64> >::new(),  |x| Ratio::from_integer(x) );
+/// sweep.add_field( "GF2", GF2::new(),                                 |x| x % 2 != 0 );
+///
+/// let discrepancies = sweep.discrepancies();
+/// assert_eq!( discrepancies.len(), 1 );
+/// assert_eq!( discrepancies[0].only_in_reference, vec![ (1, Some(2)) ] );
+/// assert_eq!( discrepancies[0].only_in_other,     vec![ (1, None), (2, None) ] );
+/// ```
+#[derive(Clone, Debug)]
+pub struct FieldSweep {
+    matrix:     Vec< Vec< (usize, i64) > >,
+    results:    Vec< FieldSweepResult >,
+}
+
+impl FieldSweep {
+
+    /// Create a new sweep for the given integer matrix.
+    pub fn new( matrix: Vec< Vec< (usize, i64) > > ) -> Self {
+        FieldSweep{ matrix, results: Vec::new() }
+    }
+
+    /// The integer matrix.
+    pub fn matrix( &self ) -> &Vec< Vec< (usize, i64) > > { &self.matrix }
+
+    /// The results recorded so far, in the order the fields were added.
+    pub fn results( &self ) -> &Vec< FieldSweepResult > { &self.results }
+
+    /// Reduce a copy of the matrix over the field represented by `ring`, and record the result.
+    ///
+    /// The function `coerce` maps integers into the field (e.g. `|x| x % 2 != 0` for GF2).
+    /// Entries that coerce to zero are dropped before reduction.
+    pub fn add_field< RingOperator, Val, F >(
+                &mut self,
+                label:  &str,
+                ring:   RingOperator,
+                coerce: F
+            )
+            ->
+            &FieldSweepResult
+
+        where   RingOperator:   Semiring< Val > + Ring< Val > + DivisionRing< Val > + Clone,
+                Val:            Clone + Debug + PartialOrd,
+                F:              Fn( i64 ) -> Val,
+    {
+        let mut matrix: Vec< Vec< (usize, Val) > >  =   self.matrix
+                                                            .iter()
+                                                            .map(   |col|
+                                                                    col.iter()
+                                                                        .map( |(row, val)| ( *row, coerce( *val ) ) )
+                                                                        .filter( |x| ! ring.is_0( x.1.clone() ) )
+                                                                        .collect()
+                                                            )
+                                                            .collect();
+
+        let mut pivot_pairs: Vec<_> =   right_reduce( &mut matrix, ring )
+                                            .into_iter()
+                                            .collect();
+        pivot_pairs.sort();
+
+        let barcode     =   index_barcode( matrix.len(), &pivot_pairs );
+
+        self.results.push(
+            FieldSweepResult{ label: label.to_string(), pivot_pairs, barcode }
+        );
+        self.results.last().unwrap()
+    }
+
+    /// Compare the barcode of each field against the barcode of the first field added.
+    ///
+    /// Returns one [`FieldDiscrepancy`] for each field whose barcode differs from the reference.
+    pub fn discrepancies( &self ) -> Vec< FieldDiscrepancy > {
+        let mut discrepancies   =   Vec::new();
+        let reference           =   match self.results.first() { Some(x) => x, None => return discrepancies };
+
+        for other in self.results.iter().skip(1) {
+            if other.barcode == reference.barcode { continue }
+            discrepancies.push(
+                FieldDiscrepancy{
+                    label_reference:    reference.label.clone(),
+                    label_other:        other.label.clone(),
+                    only_in_reference:  sorted_difference( &reference.barcode, &other.barcode ),
+                    only_in_other:      sorted_difference( &other.barcode, &reference.barcode ),
+                }
+            )
+        }
+        discrepancies
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  HELPER FUNCTIONS
+//  ---------------------------------------------------------------------------
+
+/// Given the number of columns and the (sorted) pivot pairs `(row, col)` of a reduced boundary
+/// matrix, return the barcode of the index filtration.
+///
+/// A pivot pair `(row, col)` contributes a bar `(row, Some(col))`; an index that is neither a
+/// pivot row nor a pivot column contributes an infinite bar `(index, None)`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrix_factorization::field_sweep::index_barcode;
+///
+/// let barcode = index_barcode( 3, &vec![ (1, 2) ] );
+/// assert_eq!( barcode, vec![ (0, None), (1, Some(2)) ] );
+/// ```
+pub fn index_barcode( num_columns: usize, pivot_pairs: &[ (usize, usize) ] ) -> Vec< IndexBar > {
+    let mut is_paired   =   vec![ false; num_columns ];
+    let mut barcode     =   Vec::with_capacity( num_columns );
+
+    for (row, col) in pivot_pairs.iter().cloned() {
+        is_paired[ row ]    =   true;
+        is_paired[ col ]    =   true;
+        barcode.push( ( row, Some( col ) ) );
+    }
+    for (index, paired) in is_paired.iter().enumerate() {
+        if ! paired { barcode.push( ( index, None ) ) }
+    }
+    barcode.sort();
+    barcode
+}
+
+/// Elements of `a` that do not appear in `b`; both inputs are assumed to be sorted.
+fn sorted_difference< T: Ord + Clone >( a: &[T], b: &[T] ) -> Vec< T > {
+    a.iter().filter( |x| b.binary_search( x ).is_err() ).cloned().collect()
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  TESTS
+//  ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GF2;
+    use crate::rings::ring_native::{NativeRing, NativeDivisionRing};
+    use crate::utilities::sequences_and_ordinals::BiMapSequential;
+    use crate::utilities::cell_complexes::simplices_unweighted::facets::ordered_subsimplices_up_thru_dim_concatenated_vec;
+    use crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets;
+    use num::rational::Ratio;
+
+    #[test]
+    fn test_projective_plane_gf2_vs_rationals() {
+
+        // The minimal (6-vertex) triangulation of the real projective plane.
+        let facets          =   vec![
+                                    vec![0,1,2], vec![0,2,3], vec![0,3,4], vec![0,4,5], vec![0,1,5],
+                                    vec![1,2,4], vec![2,3,5], vec![1,3,4], vec![2,4,5], vec![1,3,5],
+                                ];
+        let simplices       =   ordered_subsimplices_up_thru_dim_concatenated_vec( &facets, 2 );
+        let bimap           =   BiMapSequential::from_vec( simplices );
+        let matrix          =   boundary_matrix_from_complex_facets( &bimap, NativeRing::<i64>::new() );
+
+        let mut sweep       =   FieldSweep::new( matrix );
+        sweep.add_field( "GF2", GF2::new(),                                 |x| x % 2 != 0 );
+        sweep.add_field( "Q",   NativeDivisionRing::< Ratio<i64> >::new(),  Ratio::from_integer );
+
+        // over GF2 there are infinite bars in dimensions 0, 1, and 2; over Q only in dimension 0
+        let num_infinite    =   | result: &FieldSweepResult | result.barcode.iter().filter( |x| x.1.is_none() ).count();
+        assert_eq!( num_infinite( &sweep.results()[0] ), 3 );
+        assert_eq!( num_infinite( &sweep.results()[1] ), 1 );
+
+        let discrepancies   =   sweep.discrepancies();
+        assert_eq!( discrepancies.len(), 1 );
+        assert_eq!( discrepancies[0].label_other, "Q".to_string() );
+        assert_eq!( discrepancies[0].only_in_reference.len(), 2 );
+        assert_eq!( discrepancies[0].only_in_other.len(), 1 );
+    }
+}
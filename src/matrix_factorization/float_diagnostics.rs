@@ -0,0 +1,279 @@
+//! Diagnostics for reducing matrices with floating point coefficients.
+//!
+//! Floating point reduction is fast, but round-off error can create spurious nonzero entries,
+//! or cancel entries that should be nonzero; either can change the pivots (and therefore the
+//! barcode).  The function [`right_reduce_with_diagnostics`] performs the same reduction as
+//! [`right_reduce`](crate::matrix_factorization::vec_of_vec::right_reduce), and also reports
+//!
+//! - the **pivot growth**, i.e. the largest magnitude of any entry that appears during
+//!   reduction, divided by the largest magnitude of any entry in the input,
+//! - the **minimum pivot magnitude**, and
+//! - a list of **warnings**, one for each pivot or newly created entry whose magnitude falls
+//!   below a user-supplied tolerance.
+//!
+//! Large pivot growth, tiny pivots, or a nonempty list of warnings suggest that the result
+//! should be checked with exact arithmetic, e.g. over the rationals.
+
+use crate::rings::ring_native::NativeDivisionRing;
+use crate::matrix_factorization::vec_of_vec::{try_right_reduce_by_order_observed, ReductionObserver, ZeroPivot};
+use crate::utilities::order::OrderNatural;
+use crate::vectors::operations::SortedEntrySlice;
+use num::Float;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+
+//  ---------------------------------------------------------------------------
+//  DIAGNOSTICS
+//  ---------------------------------------------------------------------------
+
+
+/// Describes why a [`FloatWarning`] was raised.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatWarningKind {
+    /// A pivot entry has magnitude below the tolerance.
+    SmallPivot,
+    /// An entry created by adding columns has magnitude below the tolerance (it is probably
+    /// round-off residue from an entry that should have cancelled).
+    SmallEntry,
+}
+
+/// A value with magnitude below the tolerance, encountered during reduction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FloatWarning< Val > {
+    /// The kind of warning.
+    pub kind:       FloatWarningKind,
+    /// The column being reduced when the value was encountered.
+    pub column:     usize,
+    /// The row of the offending entry.
+    pub row:        usize,
+    /// The offending value.
+    pub value:      Val,
+}
+
+/// Summary statistics gathered during a floating point reduction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FloatReductionDiagnostics< Val > {
+    /// The tolerance used to raise warnings.
+    pub tolerance:                  Val,
+    /// The largest magnitude of any entry in the input matrix.
+    pub max_magnitude_input:        Val,
+    /// The largest magnitude of any entry encountered during reduction (including the input).
+    pub max_magnitude_encountered:  Val,
+    /// The smallest magnitude of any pivot, or `None` if the reduced matrix has no pivots.
+    pub min_pivot_magnitude:        Option< Val >,
+    /// The values that fell below the tolerance, in the order encountered.  An entry that
+    /// several column operations leave below the tolerance raises a single
+    /// [`SmallEntry`](FloatWarningKind::SmallEntry) warning, which records its latest value.
+    pub warnings:                   Vec< FloatWarning< Val > >,
+}
+
+impl < Val: Float > FloatReductionDiagnostics< Val > {
+
+    /// The ratio `max_magnitude_encountered / max_magnitude_input`; equals 1 if the input is zero.
+    pub fn pivot_growth( &self ) -> Val {
+        if self.max_magnitude_input.is_zero() { return Val::one() }
+        self.max_magnitude_encountered / self.max_magnitude_input
+    }
+
+    /// Returns `true` if any warnings were raised, or if a non-finite value was encountered.
+    ///
+    /// In either case the user should consider switching to exact (e.g. rational) arithmetic.
+    pub fn suggests_exact_arithmetic( &self ) -> bool {
+        ! self.warnings.is_empty() || ! self.max_magnitude_encountered.is_finite()
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  REDUCTION
+//  ---------------------------------------------------------------------------
+
+
+/// Right-reduce a floating point matrix, and report diagnostics.
+///
+/// The input and output conventions are the same as for
+/// [`right_reduce`](crate::matrix_factorization::vec_of_vec::right_reduce): the entries of each
+/// column must be sorted, and the function returns a hashmap sending each pivot row to its
+/// pivot column.  Panics if the reduction needs to divide by zero; see
+/// [`try_right_reduce_with_diagnostics`].
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrix_factorization::float_diagnostics::{right_reduce_with_diagnostics, FloatWarningKind};
+///
+/// // the second column is nearly (but not exactly) equal to the first
+/// let mut matrix          =   vec![
+///                                 vec![ (0, 1.), (1, 1.)         ],
+///                                 vec![ (0, 1.), (1, 1. + 1e-12) ],
+///                             ];
+///
+/// let (pivots, diagnostics)   =   right_reduce_with_diagnostics( &mut matrix, 1e-9 );
+///
+/// assert_eq!( pivots.len(), 2 );
+/// assert_eq!( diagnostics.warnings.len(), 2 );   // one for the residue, one for the pivot
+/// assert_eq!( diagnostics.warnings[1].kind, FloatWarningKind::SmallPivot );
+/// assert!( diagnostics.min_pivot_magnitude.unwrap() < 1e-9 );
+/// assert!( diagnostics.suggests_exact_arithmetic() );
+/// ```
+pub fn  right_reduce_with_diagnostics< Val >(
+            matrix:     &mut [ Vec< (usize, Val) > ],
+            tolerance:      Val,
+        )
+        ->
+        ( HashMap< usize, usize >, FloatReductionDiagnostics< Val > )
+
+        where   Val:    Float + Debug,
+{
+    try_right_reduce_with_diagnostics( matrix, tolerance ).unwrap_or_else( |error| panic!( "{}", error ) )
+}
+
+
+/// Right-reduce a floating point matrix and report diagnostics, or return an error if the
+/// reduction needs to divide by zero.
+///
+/// This is the same as [`right_reduce_with_diagnostics`], except that it returns [`ZeroPivot`]
+/// instead of panicking; in that case `matrix` is left partially reduced.
+pub fn  try_right_reduce_with_diagnostics< Val >(
+            matrix:     &mut [ Vec< (usize, Val) > ],
+            tolerance:      Val,
+        )
+        ->
+        Result< ( HashMap< usize, usize >, FloatReductionDiagnostics< Val > ), ZeroPivot >
+
+        where   Val:    Float + Debug,
+{
+    let max_magnitude_input =   matrix.iter()
+                                    .flat_map( |col| col.iter() )
+                                    .map( |x| x.1.abs() )
+                                    .fold( Val::zero(), Val::max );
+
+    let mut diagnostics     =   FloatReductionDiagnostics{
+                                    tolerance,
+                                    max_magnitude_input,
+                                    max_magnitude_encountered:  max_magnitude_input,
+                                    min_pivot_magnitude:        None,
+                                    warnings:                   Vec::new(),
+                                };
+
+    let pivot_hash          =   try_right_reduce_by_order_observed( matrix, NativeDivisionRing::< Val >::new(), OrderNatural, &mut diagnostics )?;
+    Ok( ( pivot_hash, diagnostics ) )
+}
+
+
+impl < Val: Float > ReductionObserver< usize, Val > for FloatReductionDiagnostics< Val > {
+
+    fn column_operation(
+            &mut self,
+            clearee_index:      usize,
+            _clearor_index:     usize,
+            _scalar:            &Val,
+            clearee:            &[ (usize, Val) ],
+            clearor:            &[ (usize, Val) ],
+        )
+    {
+        // the entries that were just created or modified are those that share a row with the clearor
+        for (row, _) in clearor.iter() {
+            let val         =   match clearee.find_entry( row ) { Some( entry ) => entry.1, None => continue };
+            let magnitude   =   val.abs();
+            if magnitude > self.max_magnitude_encountered || magnitude.is_nan() {
+                self.max_magnitude_encountered = magnitude
+            }
+            if magnitude < self.tolerance {
+                // the warnings for the current column are at the end of the list
+                let repeated    =   self.warnings.iter_mut()
+                                        .rev()
+                                        .take_while( |w| w.column == clearee_index )
+                                        .find( |w| w.row == *row && w.kind == FloatWarningKind::SmallEntry );
+                match repeated {
+                    Some( warning ) =>  warning.value = val,
+                    None            =>  self.warnings.push(
+                                            FloatWarning{ kind: FloatWarningKind::SmallEntry, column: clearee_index, row: *row, value: val }
+                                        ),
+                }
+            }
+        }
+    }
+
+    fn column_reduced( &mut self, column: usize, pivot_entry: Option< &(usize, Val) > ) {
+        if let Some( pivot_entry ) = pivot_entry {
+            let magnitude   =   pivot_entry.1.abs();
+            if self.min_pivot_magnitude.map_or( true, |x| magnitude < x ) {
+                self.min_pivot_magnitude = Some( magnitude )
+            }
+            if magnitude < self.tolerance {
+                self.warnings.push(
+                    FloatWarning{ kind: FloatWarningKind::SmallPivot, column, row: pivot_entry.0, value: pivot_entry.1 }
+                );
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrix_factorization::vec_of_vec::right_reduce;
+
+    #[test]
+    fn test_diagnostics_agree_with_right_reduce() {
+        let matrix          =   vec![
+                                    vec![                   (2, 1.), (3,-1.)    ],
+                                    vec![                   (2,-1.), (3, 2.)    ],
+                                    vec![          (1, 1.), (2, 1.)             ],
+                                    vec![ (0, 1.), (1, 1.)                      ],
+                                    vec![ (0, 1.),                              ],
+                                ];
+
+        let mut reduced_plain   =   matrix.clone();
+        let mut reduced_diag    =   matrix;
+        let pivots_plain        =   right_reduce( &mut reduced_plain, NativeDivisionRing::<f64>::new() );
+        let (pivots_diag, diag) =   right_reduce_with_diagnostics( &mut reduced_diag, 1e-9 );
+
+        assert_eq!( pivots_plain, pivots_diag );
+        assert_eq!( reduced_plain, reduced_diag );
+        assert!( diag.warnings.is_empty() );
+        assert!( ! diag.suggests_exact_arithmetic() );
+        assert_eq!( diag.max_magnitude_input, 2. );
+        assert_eq!( diag.pivot_growth(), 1. );
+        assert_eq!( diag.min_pivot_magnitude, Some( 1. ) );
+    }
+
+    #[test]
+    fn test_pivot_growth() {
+        // reducing the second column by a tiny pivot creates a huge entry
+        let mut matrix      =   vec![
+                                    vec![ (0, 1.), (1, 1e-3) ],
+                                    vec![ (0, 1.), (1, 1.)   ],
+                                ];
+        let (_pivots, diag) =   right_reduce_with_diagnostics( &mut matrix, 1e-9 );
+        assert_eq!( diag.pivot_growth(), 999. );
+        assert_eq!( diag.min_pivot_magnitude, Some( 1e-3 ) );
+    }
+
+    #[test]
+    fn test_zero_pivots_are_errors() {
+        // the first column stores an explicit zero in its pivot position
+        let mut matrix      =   vec![ vec![ (0, 1.), (1, 0.) ], vec![ (1, 1.) ] ];
+        assert_eq!( try_right_reduce_with_diagnostics( &mut matrix, 1e-9 ), Err( ZeroPivot{ column: 0 } ) );
+    }
+
+    #[test]
+    fn test_repeated_small_entries_warn_once() {
+        // two column operations touch the same small residue in row 0 of the last column
+        let mut matrix      =   vec![
+                                    vec![ (0, 1.),              (2, 1.) ],
+                                    vec![ (0, 1e-13), (1, 1.)           ],
+                                    vec![ (0, 1. + 1e-12), (1, 1.), (2, 1.) ],
+                                ];
+        let (_pivots, diag) =   right_reduce_with_diagnostics( &mut matrix, 1e-9 );
+        let kinds: Vec< _ > =   diag.warnings.iter().map( |w| ( w.kind, w.column, w.row ) ).collect();
+        assert_eq!( kinds, vec![ ( FloatWarningKind::SmallEntry, 2, 0 ), ( FloatWarningKind::SmallPivot, 2, 0 ) ] );
+        // the warning records the latest value of the entry
+        assert_eq!( diag.warnings[0].value, matrix[2][0].1 );
+    }
+}
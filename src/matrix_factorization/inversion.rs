@@ -1,4 +1,344 @@
 //! Sparse matrix inversion.
-//! 
-//! Not currently implemented.
+//!
+//! The inverse of a sparse triangular matrix is triangular, but usually much denser, so it is
+//! rarely worth storing.  An [`InverseUpperTriangular`] wraps an upper triangular matrix oracle
+//! and computes views of its inverse on demand, by back substitution.  This is the basic
+//! ingredient needed to expose inverses of the triangular factors of a matrix decomposition
+//! (such as the matrices `R` and `C` of a U-match decomposition) without ever storing them.
 
+use crate::matrices::matrix_oracle::{   OracleMinor,
+                                        OracleMinorDescend,
+                                        WhichMajor,
+                                        MajorDimension};
+use crate::rings::ring::DivisionRing;
+use crate::utilities::iterators::hit_merge::{hit_bulk_insert, hit_merge_by, HitMerge};
+use crate::vector_entries::vector_entries::{KeyValGet, KeyValSet};
+use crate::utilities::order::{OrderNatural, OrderReverse};
+use crate::vectors::vector_transforms::{AssertSorted, Scale, Transforms};
+use std::fmt::{self, Debug};
+use std::iter;
+
+
+/// The inverse of an upper triangular matrix oracle with invertible diagonal, computed lazily.
+///
+/// "Upper triangular" is meant in terms of minor views: every minor view `j` of the wrapped
+/// matrix may only contain entries with keys `<= j`, and must contain an invertible entry with
+/// key `j`.  For a row-major matrix this means the matrix is upper triangular in the usual sense
+/// (minor views are columns); for a column-major matrix it means the matrix is lower triangular.
+/// The inverse has the same shape.
+///
+/// Minor view `j` of the inverse is computed by back substitution, as the unique vector `x` such
+/// that `sum_i x_i * ( minor view i )` is the unit vector with key `j`.  Its entries are computed
+/// one at a time, in descending order of key; each entry `(i, x_i)` requires one minor view of
+/// the wrapped matrix, and work proportional to the size of that view.  Nothing is stored
+/// between calls.
+///
+/// Panics (while iterating over a view) if the wrapped matrix is not upper triangular with
+/// invertible diagonal; use [`InverseUpperTriangularView::try_next`] to get a
+/// [`SingularDiagonal`] error instead.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrix_factorization::inversion::InverseUpperTriangular;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMinorDescend};
+/// use solar::rings::ring_native::NativeDivisionRing;
+///
+/// // the upper triangular matrix
+/// //  [ 1  1  0 ]
+/// //  [    2  1 ]
+/// //  [       1 ]
+/// let matrix  =   VecOfVec::new(
+///                     MajorDimension::Row,
+///                     vec![ vec![ (0, 1.), (1, 1.) ], vec![ (1, 2.), (2, 1.) ], vec![ (2, 1.) ] ],
+///                 );
+/// let inverse =   InverseUpperTriangular::new( &matrix, NativeDivisionRing::<f64>::new() );
+///
+/// // column 2 of the inverse
+/// assert_eq!( inverse.view_minor_descend( 2 ).collect::<Vec<_>>(), vec![ (2, 1.), (1, -0.5), (0, 0.5) ] );
+/// ```
+#[derive(Clone, Debug)]
+pub struct InverseUpperTriangular< Matrix, RingOperator > {
+    matrix:     Matrix,
+    ring:       RingOperator,
+}
+
+impl    < Matrix, RingOperator >
+        InverseUpperTriangular
+        < Matrix, RingOperator >
+{
+    /// Wrap `matrix`, which should be upper triangular with invertible diagonal.
+    pub fn new( matrix: Matrix, ring: RingOperator ) -> Self { InverseUpperTriangular{ matrix, ring } }
+
+    /// The wrapped matrix (the inverse of the matrix this oracle represents).
+    pub fn matrix( &self ) -> &Matrix { &self.matrix }
+
+    /// Unwrap the matrix.
+    pub fn into_inner( self ) -> Matrix { self.matrix }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  ERRORS
+//  ---------------------------------------------------------------------------
+
+
+/// The error returned by [`InverseUpperTriangularView::try_next`] when back substitution needs
+/// a diagonal entry that is missing or not invertible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SingularDiagonal< Key > {
+    /// The key of the minor view whose diagonal entry is missing or not invertible.
+    pub key:        Key,
+}
+
+impl < Key: Debug > fmt::Display for SingularDiagonal< Key > {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        write!( f, "the matrix must be upper triangular, with invertible diagonal; the diagonal entry of minor view {:?} is missing or not invertible", self.key )
+    }
+}
+
+impl < Key: Debug > std::error::Error for SingularDiagonal< Key > {}
+
+
+//  ---------------------------------------------------------------------------
+//  VIEWS
+//  ---------------------------------------------------------------------------
+
+
+/// Returns `true` if the first entry has the larger key; used to merge entries in descending
+/// order.
+pub type Descending< Pair >     =   fn( &Pair, &Pair ) -> bool;
+
+fn descending< Pair: KeyValGet >( a: &Pair, b: &Pair ) -> bool
+    where   Pair::Key:  PartialOrd,
+{
+    a.key() > b.key()
+}
+
+/// A minor view of the wrapped matrix, scaled by a coefficient of the solution (and checked for
+/// descending order, in debug builds).
+type ScaledView< 'a, Matrix, Key, Val, RingOperator >
+    =   AssertSorted< Scale< < < Matrix as OracleMinorDescend< 'a, Key, Key, Val > >::ViewMinorDescend as IntoIterator >::IntoIter, RingOperator >, OrderReverse< OrderNatural > >;
+
+/// A minor view of an [`InverseUpperTriangular`], computed lazily by back substitution.
+///
+/// Entries are `(key, coefficient)` pairs, in descending order of key.  Iterating panics if the
+/// wrapped matrix is not upper triangular with invertible diagonal; see
+/// [`try_next`](InverseUpperTriangularView::try_next).
+pub struct InverseUpperTriangularView< 'a, Matrix, Key, Val, RingOperator >
+    where   Matrix:                             'a + OracleMinorDescend< 'a, Key, Key, Val >,
+            Matrix::PairMinorDescend:           KeyValSet,
+            RingOperator:                       DivisionRing< Val >,
+            Key:                                Clone + Debug + PartialOrd,
+            Val:                                Clone + Debug,
+{
+    matrix:     &'a Matrix,
+    ring:       RingOperator,
+    /// The key of the view, until the first entry has been returned.
+    first:      Option< Key >,
+    /// The entries of `unit vector - sum of ( solved entry ) * ( minor view )` that have not yet
+    /// been eliminated, merged in descending order of key.
+    residual:   HitMerge< ScaledView< 'a, Matrix, Key, Val, RingOperator >, Descending< Matrix::PairMinorDescend > >,
+}
+
+impl    < 'a, Matrix, Key, Val, RingOperator >
+        InverseUpperTriangularView
+        < 'a, Matrix, Key, Val, RingOperator >
+
+        where   Matrix:                             'a + OracleMinorDescend< 'a, Key, Key, Val >,
+                Matrix::PairMinorDescend:           KeyValSet,
+                RingOperator:                       DivisionRing< Val > + Clone,
+                Key:                                Clone + Debug + PartialOrd,
+                Val:                                Clone + Debug,
+{
+    /// Solve for the entry with key `key`, given the residual coefficient `rhs` in that position,
+    /// and add the corresponding multiple of minor view `key` to the residual; or return an error
+    /// if the diagonal entry of minor view `key` is missing or not invertible.
+    fn try_solve( &mut self, key: Key, rhs: Val ) -> Result< ( Key, Val ), SingularDiagonal< Key > > {
+        let ring            =   &self.ring;
+        let mut view        =   self.matrix.view_minor_descend( key.clone() ).into_iter();
+        let solution        =   match   view.next()
+                                            .filter( |entry| entry.key() == key )
+                                            .and_then( |entry| ring.try_divide( rhs, entry.val() ) ) {
+                                    Some( solution )    =>  solution,
+                                    None                =>  return Err( SingularDiagonal{ key } ),
+                                };
+        let scaled          =   view.scale( ring.clone(), ring.negate( solution.clone() ) ).debug_assert_sorted( OrderReverse::new( OrderNatural ) );
+        hit_bulk_insert( &mut self.residual, iter::once( scaled ) );
+        Ok( ( key, solution ) )
+    }
+
+    /// The next entry of the view, or an error if the wrapped matrix turns out not to be upper
+    /// triangular with invertible diagonal.
+    ///
+    /// This is the same as [`next`](Iterator::next), except that it returns an error instead of
+    /// panicking.  The view should not be used after it has returned an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::matrix_factorization::inversion::{InverseUpperTriangular, SingularDiagonal};
+    /// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+    /// use solar::matrices::matrix_oracle::{MajorDimension, OracleMinorDescend};
+    /// use solar::rings::ring_native::NativeDivisionRing;
+    ///
+    /// // the second diagonal entry is missing
+    /// let matrix      =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1.), (1, 1.) ], vec![] ] );
+    /// let inverse     =   InverseUpperTriangular::new( &matrix, NativeDivisionRing::<f64>::new() );
+    ///
+    /// let mut view    =   inverse.view_minor_descend( 1 );
+    /// let column: Result< Vec< _ >, _ >   =   std::iter::from_fn( || view.try_next() ).collect();
+    /// assert_eq!( column, Err( SingularDiagonal{ key: 1 } ) );
+    /// ```
+    pub fn try_next( &mut self ) -> Option< Result< ( Key, Val ), SingularDiagonal< Key > > > {
+        if let Some( key ) = self.first.take() { return Some( self.try_solve( key, RingOperator::one() ) ) }
+        while let Some( entry ) = self.residual.next() {
+            let key         =   entry.key();
+            let mut rhs     =   entry.val();
+            while let Some( entry ) = self.residual.next_if( |entry| entry.key() == key ) {
+                rhs         =   self.ring.add( rhs, entry.val() );
+            }
+            if ! self.ring.is_0( rhs.clone() ) { return Some( self.try_solve( key, rhs ) ) }
+        }
+        None
+    }
+}
+
+impl    < 'a, Matrix, Key, Val, RingOperator >
+        Iterator
+        for
+        InverseUpperTriangularView < 'a, Matrix, Key, Val, RingOperator >
+
+        where   Matrix:                             'a + OracleMinorDescend< 'a, Key, Key, Val >,
+                Matrix::PairMinorDescend:           KeyValSet,
+                RingOperator:                       DivisionRing< Val > + Clone,
+                Key:                                Clone + Debug + PartialOrd,
+                Val:                                Clone + Debug,
+{
+    type Item = ( Key, Val );
+
+    fn next( &mut self ) -> Option< Self::Item > {
+        self.try_next().map( |result| result.unwrap_or_else( |error| panic!( "{}", error ) ) )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT IMPLEMENTATIONS
+//  ---------------------------------------------------------------------------
+
+
+impl     < Matrix, RingOperator >
+        WhichMajor
+        for
+        InverseUpperTriangular < Matrix, RingOperator >
+
+        where   Matrix:     WhichMajor,
+{ fn major_dimension( &self ) -> MajorDimension { self.matrix.major_dimension() } }
+
+impl     < 'a, Matrix, Key, Val, RingOperator >
+        OracleMinorDescend < 'a, Key, Key, Val >
+        for
+        InverseUpperTriangular < Matrix, RingOperator >
+
+        where   Matrix:                             'a + OracleMinorDescend< 'a, Key, Key, Val >,
+                Matrix::PairMinorDescend:           KeyValSet,
+                RingOperator:                       DivisionRing< Val > + Clone,
+                Key:                                Clone + Debug + PartialOrd,
+                Val:                                Clone + Debug,
+{
+    type PairMinorDescend =   ( Key, Val );
+    type ViewMinorDescend =   InverseUpperTriangularView< 'a, Matrix, Key, Val, RingOperator >;
+
+    fn view_minor_descend<'b: 'a>( &'b self, index: Key ) -> Self::ViewMinorDescend {
+        InverseUpperTriangularView{
+            matrix:     &self.matrix,
+            ring:       self.ring.clone(),
+            first:      Some( index ),
+            residual:   hit_merge_by( Vec::< ScaledView< 'a, Matrix, Key, Val, RingOperator > >::new(), descending as Descending< Matrix::PairMinorDescend > ),
+        }
+    }
+}
+
+impl     < 'a, Matrix, Key, Val, RingOperator >
+        OracleMinor < 'a, Key, Key, Val >
+        for
+        InverseUpperTriangular < Matrix, RingOperator >
+
+        where   Matrix:                             'a + OracleMinorDescend< 'a, Key, Key, Val >,
+                Matrix::PairMinorDescend:           KeyValSet,
+                RingOperator:                       DivisionRing< Val > + Clone,
+                Key:                                Clone + Debug + PartialOrd,
+                Val:                                Clone + Debug,
+{
+    type PairMinor =   ( Key, Val );
+    type ViewMinor =   InverseUpperTriangularView< 'a, Matrix, Key, Val, RingOperator >;
+
+    /// Entries appear in descending order of key.
+    fn view_minor<'b: 'a>( &'b self, index: Key ) -> Self::ViewMinor { self.view_minor_descend( index ) }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::rings::ring_native::NativeDivisionRing;
+    use num::rational::Ratio;
+
+    #[test]
+    fn test_inverse_times_matrix_is_identity() {
+        type Q          =   Ratio< i64 >;
+        let ring        =   NativeDivisionRing::< Q >::new();
+        let q           =   |n: i64| Q::from_integer( n );
+        let rows        =   vec![
+                                vec![ (0, q(2)), (1, q(1)), (3, q(-1)) ],
+                                vec![ (1, q(1)), (2, q(3)) ],
+                                vec![ (2, q(-1)), (3, q(1)) ],
+                                vec![ (3, q(5)) ],
+                            ];
+        let matrix      =   VecOfVec::new( MajorDimension::Row, rows.clone() );
+        let inverse     =   InverseUpperTriangular::new( &matrix, ring );
+
+        // row i of the matrix times column j of the inverse
+        for j in 0 .. 4 {
+            let column: Vec< _ >    =   inverse.view_minor_descend( j ).collect();
+            assert!( column.windows( 2 ).all( |pair| pair[0].0 > pair[1].0 ) );
+            assert!( column.iter().all( |( i, _ )| *i <= j ) );
+
+            let mut ascending       =   column.clone();
+            ascending.reverse();
+            let mut product         =   vec![ q(0); 4 ];
+            for ( i, row ) in rows.iter().enumerate() {
+                product[ i ]        =   row.iter().filter_map( |( k, a )| ascending.iter().find( |( l, _ )| l == k ).map( |( _, x )| a * x ) ).sum();
+            }
+            let unit: Vec< _ >      =   ( 0 .. 4 ).map( |i| if i == j { q(1) } else { q(0) } ).collect();
+            assert_eq!( product, unit );
+        }
+    }
+
+    #[test]
+    #[should_panic( expected = "upper triangular" )]
+    fn test_zero_diagonal_panics() {
+        let matrix      =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1.), (1, 1.) ], vec![] ] );
+        let inverse     =   InverseUpperTriangular::new( &matrix, NativeDivisionRing::<f64>::new() );
+        inverse.view_minor_descend( 1 ).for_each( drop );
+    }
+
+    #[test]
+    fn test_zero_diagonal_is_an_error() {
+        // the diagonal entry of column 1 is zero, and is only reached while solving for column 2
+        let matrix      =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1.) ], vec![ (1, 0.), (2, 1.) ], vec![ (2, 1.) ] ] );
+        let inverse     =   InverseUpperTriangular::new( &matrix, NativeDivisionRing::<f64>::new() );
+        let mut view    =   inverse.view_minor_descend( 2 );
+        assert_eq!( view.try_next(), Some( Ok( (2, 1.) ) ) );
+        assert_eq!( view.try_next(), Some( Err( SingularDiagonal{ key: 1 } ) ) );
+
+        // a nonsingular column is unaffected
+        let mut view    =   inverse.view_minor_descend( 0 );
+        assert_eq!( std::iter::from_fn( || view.try_next() ).collect::< Result< Vec< _ >, _ > >(), Ok( vec![ (0, 1.) ] ) );
+    }
+}
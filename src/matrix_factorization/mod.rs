@@ -5,4 +5,8 @@
 
 pub mod vec_of_vec;
 pub mod inversion;
+pub mod field_sweep;
+pub mod float_diagnostics;
+pub mod factors;
+pub mod barcode;
 // pub mod umatch;
\ No newline at end of file
@@ -2,9 +2,10 @@
 
 use crate::rings::ring::{Semiring, Ring, DivisionRing};
 use crate::vector_entries::vector_entries::{KeyValGet};
-use crate::vectors::vector_transforms::{Transforms};
+use crate::vectors::operations::{add_assign_sorted_with_buffer, add_assign_sorted_by_with_buffer, SortedEntrySlice};
+use crate::utilities::order::{OrderComparator, OrderNatural};
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 
 //  CLEAR A VECTOR
@@ -82,34 +83,18 @@ where   RingOperator: Semiring<Val> + Ring<Val> + DivisionRing<Val> + Clone,
         Val: Clone + Debug +PartialOrd
 
 {
-    let entry_to_clear_opt  =   clearee
-                                .iter()
-                                .find( |&x| x.key() == pivot_entry.key() );
+    let entry_to_clear_opt  =   clearee.find_entry( &pivot_entry.0 );
 
     if let Some(entry_to_clear) = entry_to_clear_opt 
     {
         if ring.is_0( entry_to_clear.val()) { return }              // short circuit if the entry to be cleared is zero
 
-        let scalar          =   ring.divide( 
+        let scalar          =   ring.try_divide( 
                                     ring.negate( entry_to_clear.val() ),
                                     pivot_entry.val()
-                                );
-
-        let merged          =   itertools::merge(                   // merge iterators, preserving
-                                    clearee.iter().cloned(),
-                                    clearor
-                                        .iter()
-                                        .cloned()
-                                        .scale( ring.clone(), scalar )
-                                )
-                                .peekable()                         // make peekable (necessary to gather coefficients)
-                                .gather( ring.clone() )             // gather coefficients
-                                .drop_zeros( ring );                // drop zeros
-        buffer.clear();
-        buffer.extend( merged );
-
-        clearee.clear();
-        clearee.append( buffer);    // note that buffer is already declared to be a mutable reference
+                                ).expect( "the pivot entry must be nonzero" );
+
+        add_assign_sorted_with_buffer( clearee, clearor, scalar, ring, buffer ); // add, gather, and drop zeros
     }
 }
 
@@ -146,6 +131,31 @@ pub fn clear_cols< RingOperator, Key, Val, IndexIter: IntoIterator< Item = usize
 
 
 
+//  ERRORS
+//  ------
+
+/// A reduction needed to divide by a pivot entry equal to zero.
+///
+/// This only happens if the input matrix stores an explicit zero as the last entry of a column,
+/// so that the zero is mistaken for a pivot.  Remove explicit zeros before reducing (see e.g.
+/// [`VecOfVec::validate`](crate::matrices::implementors::vec_of_vec::VecOfVec::validate)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZeroPivot {
+    /// The index of the column whose pivot entry is zero.
+    pub column:     usize,
+}
+
+impl fmt::Display for ZeroPivot {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        write!( f, "the pivot entry of column {} is zero", self.column )
+    }
+}
+
+impl std::error::Error for ZeroPivot {}
+
+
+
+
 //  RIGHT REDUCE
 //  ------------
 
@@ -157,6 +167,8 @@ type Key = usize;
 ///     * all zero entries are also structurally nonzero.
 ///     * the entries in each column are SORTED
 /// 
+/// Panics if the reduction needs to divide by zero; see [`try_right_reduce`].
+/// 
 /// # Examples
 /// 
 /// ```
@@ -210,40 +222,197 @@ pub fn right_reduce
             Val: Clone + Debug +PartialOrd
 
 {
-    let mut pivot_hash        =   HashMap::< Key, Key >::new();
+    right_reduce_by_order( matrix, ring, OrderNatural )
+}
+
+/// Compute the right-reduced matrix of input `matrix`, or return an error if the reduction
+/// needs to divide by zero.
+///
+/// This is the same as [`right_reduce`], except that it returns [`ZeroPivot`] instead of
+/// panicking; in that case `matrix` is left partially reduced.
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::ring_native::NativeDivisionRing;
+/// use solar::matrix_factorization::vec_of_vec::{try_right_reduce, ZeroPivot};
+///
+/// // the first column stores an explicit zero in its pivot position
+/// let mut matrix      =   vec![ vec![ (0, 1.), (1, 0.) ], vec![ (1, 1.) ] ];
+///
+/// let result          =   try_right_reduce( &mut matrix, NativeDivisionRing::<f64>::new() );
+/// assert_eq!( result, Err( ZeroPivot{ column: 0 } ) );
+/// ```
+pub fn try_right_reduce 
+    < Val, RingOperator > 
+    
+    ( 
+    matrix:     &mut Vec< Vec< (Key, Val) > >,
+    ring:       RingOperator
+    )
+    ->
+    Result< HashMap::<Key, Key>, ZeroPivot >
+
+    where   RingOperator: Semiring<Val> + Ring<Val> + DivisionRing<Val> + Clone,
+            Key: Clone + Debug + PartialEq + PartialOrd + Eq + std::hash::Hash,
+            Val: Clone + Debug +PartialOrd
+
+{
+    try_right_reduce_by_order( matrix, ring, OrderNatural )
+}
+
+
+/// Compute the right-reduced matrix of input `matrix`, where row keys are ordered by `order`.
+/// 
+/// This is the same as [`right_reduce`], except that
+///     * the row keys can have any type that implements `Hash` and `Eq` (e.g. simplices), and
+///     * the entries in each column must be SORTED in ascending order with respect to `order`;
+///       the pivot of a column is its greatest entry with respect to `order`.
+/// 
+/// Returns a hashmap sending each pivot row key to the index of its pivot column.  Panics if
+/// the reduction needs to divide by zero; see [`try_right_reduce_by_order`].  In debug builds,
+/// also panics if the entries of a column are not sorted.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use solar::rings::field_prime::GF2;
+/// use solar::matrix_factorization::vec_of_vec::right_reduce_by_order;
+/// 
+/// // rows are indexed by edges; order edges by their last vertex, then by their first
+/// let order           =   | a: &(usize, usize), b: &(usize, usize) | (a.1, a.0).cmp( &(b.1, b.0) );
+/// let mut matrix      =   vec![
+///                             vec![ ((0, 1), true), ((1, 2), true), ((0, 2), true) ], // not sorted by `order`
+///                         ];
+/// matrix[0].sort_by( |a, b| order( &a.0, &b.0 ) );
+/// 
+/// let pivots          =   right_reduce_by_order( &mut matrix, GF2::new(), order );
+/// assert_eq!( pivots.get( &(1, 2) ), Some( &0 ) );
+/// ```
+pub fn right_reduce_by_order 
+    < Key, Val, RingOperator, Order > 
+    
+    ( 
+    matrix:     &mut Vec< Vec< (Key, Val) > >,
+    ring:       RingOperator,
+    order:      Order,
+    )
+    ->
+    HashMap::< Key, usize >
+
+    where   RingOperator: Semiring<Val> + Ring<Val> + DivisionRing<Val> + Clone,
+            Order: OrderComparator< Key > + Clone,
+            Key: Clone + Eq + std::hash::Hash,
+            Val: Clone,
+
+{
+    try_right_reduce_by_order( matrix, ring, order ).unwrap_or_else( |error| panic!( "{}", error ) )
+}
+
+/// Compute the right-reduced matrix of input `matrix`, where row keys are ordered by `order`, or
+/// return an error if the reduction needs to divide by zero.
+/// 
+/// This is the same as [`right_reduce_by_order`], except that it returns [`ZeroPivot`] instead
+/// of panicking; in that case `matrix` is left partially reduced.
+pub fn try_right_reduce_by_order 
+    < Key, Val, RingOperator, Order > 
+    
+    ( 
+    matrix:     &mut Vec< Vec< (Key, Val) > >,
+    ring:       RingOperator,
+    order:      Order,
+    )
+    ->
+    Result< HashMap::< Key, usize >, ZeroPivot >
+
+    where   RingOperator: Semiring<Val> + Ring<Val> + DivisionRing<Val> + Clone,
+            Order: OrderComparator< Key > + Clone,
+            Key: Clone + Eq + std::hash::Hash,
+            Val: Clone,
+
+{
+    try_right_reduce_by_order_observed( matrix, ring, order, &mut () )
+}
+
+/// Compute the right-reduced matrix of input `matrix`, where row keys are ordered by `order`,
+/// reporting each step of the reduction to `observer`.
+/// 
+/// This is the same as [`try_right_reduce_by_order`], except that `observer` is notified of
+/// every column operation and every pivot; see [`ReductionObserver`].  Every right reduction
+/// in this crate runs through this function.
+///
+/// Each column operation removes the entry that it clears, even if round-off leaves a residue
+/// that `ring` does not regard as zero; this guarantees that the reduction terminates.
+///
+/// In debug builds, panics if the entries of a column are not sorted with respect to `order`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use solar::rings::field_prime::GF2;
+/// use solar::utilities::order::OrderNatural;
+/// use solar::matrix_factorization::vec_of_vec::{try_right_reduce_by_order_observed, ReductionObserver};
+/// 
+/// // count the column operations
+/// struct Count( usize );
+/// impl ReductionObserver< usize, bool > for Count {
+///     fn column_operation( &mut self, _: usize, _: usize, _: &bool, _: &[ (usize, bool) ], _: &[ (usize, bool) ] ) { self.0 += 1 }
+/// }
+/// 
+/// let mut matrix      =   vec![ vec![ (0, true), (1, true) ], vec![ (0, true), (1, true) ] ];
+/// let mut count       =   Count( 0 );
+/// try_right_reduce_by_order_observed( &mut matrix, GF2::new(), OrderNatural, &mut count ).unwrap();
+/// assert_eq!( count.0, 1 );
+/// assert!( matrix[1].is_empty() );
+/// ```
+pub fn try_right_reduce_by_order_observed 
+    < Key, Val, RingOperator, Order, Observer > 
+    
+    ( 
+    matrix:     &mut [ Vec< (Key, Val) > ],
+    ring:       RingOperator,
+    order:      Order,
+    observer:   &mut Observer,
+    )
+    ->
+    Result< HashMap::< Key, usize >, ZeroPivot >
+
+    where   RingOperator: Semiring<Val> + Ring<Val> + DivisionRing<Val> + Clone,
+            Order: OrderComparator< Key > + Clone,
+            Key: Clone + Eq + std::hash::Hash,
+            Val: Clone,
+            Observer: ReductionObserver< Key, Val > + ?Sized,
+
+{
+    let mut pivot_hash      =   HashMap::< Key, usize >::new();
     let mut buffer          =   Vec::new();
 
     for clearee_count in 0..matrix.len() {
 
-        let mut clearee     =   matrix[ clearee_count ].clone();
+        let mut clearee     =   std::mem::take( &mut matrix[ clearee_count ] );
+        debug_assert!(  ! clearee.windows( 2 ).any( |w| order.lt( &w[1].0, &w[0].0 ) ),
+                        "the entries of column {} are not sorted", clearee_count     );
         
         //  REDUCE THE CLEAREE
         while let Some( clearee_entry ) = clearee.last(){
             if let Some( clearor_index ) = pivot_hash.get( &clearee_entry.key() ) {
 
-                let  clearor        =   matrix[ clearor_index.clone() ].clone();
+                let  clearor        =   &matrix[ *clearor_index ];
                 let  clearor_entry  =   clearor.last().unwrap();
-                let  scalar         =   ring.divide( 
-                                            ring.negate(clearee_entry.val()),
-                                            clearor_entry.val()
-                                        );                                              
-
-                let merged          =   itertools::merge(                   // merge iterators, preserving
-                                            clearee.iter().cloned(),
-                                            clearor
-                                                .iter()
-                                                .cloned()
-                                                .scale( ring.clone(), scalar )
-                                        )
-                                        .peekable()                         // make peekable (necessary to gather coefficients)
-                                        .gather( ring.clone() )             // gather coefficients
-                                        .drop_zeros( ring.clone() );        // drop zeros
-
-                buffer.clear();
-                buffer.extend( merged );
-        
-                clearee.clear();
-                clearee.append( &mut buffer);
+                let  scalar         =   match ring.try_divide( ring.negate(clearee_entry.val()), clearor_entry.val() ) {
+                                            Some( scalar )  =>  scalar,
+                                            None            =>  {
+                                                matrix[ clearee_count ]     =   clearee;    // put the column back
+                                                return Err( ZeroPivot{ column: *clearor_index } )
+                                            }
+                                        };
+
+                let  pivot_key      =   clearor_entry.key();
+                add_assign_sorted_by_with_buffer( &mut clearee, clearor, scalar.clone(), ring.clone(), order.clone(), &mut buffer ); // add, gather, and drop zeros
+                // the entry in the pivot row is zero by construction, but round-off (e.g. in floating
+                // point or interval arithmetic) can leave a residue that `is_0` does not recognize
+                if clearee.last().is_some_and( |entry| entry.key() == pivot_key ) { clearee.pop(); }
+                observer.column_operation( clearee_count, *clearor_index, &scalar, &clearee, clearor );
             } else {
                 break;
             }
@@ -251,16 +420,45 @@ pub fn right_reduce
 
         //  UPDATE MATRIX + HASHMAP
 
-        matrix[ clearee_count ].clear();                             // clear this column's slot in the matrix
+        observer.column_reduced( clearee_count, clearee.last() );
         if let Some( pivot_entry ) = clearee.last() {
             pivot_hash.insert( pivot_entry.key(), clearee_count );      // update hashmap
-            matrix[ clearee_count ].append( &mut clearee );          // write in the nonzero reduced column
         } 
+        matrix[ clearee_count ]     =   clearee;                        // write in the reduced column (possibly empty)
     }
 
-    return pivot_hash
+    Ok( pivot_hash )
+}
+
+
+//  OBSERVE A REDUCTION
+//  -------------------
+
+/// Receives a callback at each step of [`try_right_reduce_by_order_observed`].
+///
+/// Both methods do nothing by default, so an implementor only overrides the steps it needs.  The
+/// unit type `()` observes nothing.
+pub trait ReductionObserver< Key, Val > {
+
+    /// Called after `scalar` times column `clearor_index` has been added to column
+    /// `clearee_index`.  Here `clearee` holds the updated (but possibly not yet fully reduced)
+    /// column, and `clearor` holds the column that was added.
+    fn column_operation(
+            &mut self,
+            _clearee_index:     usize,
+            _clearor_index:     usize,
+            _scalar:            &Val,
+            _clearee:           &[ (Key, Val) ],
+            _clearor:           &[ (Key, Val) ],
+        ) {}
+
+    /// Called once column `column` is fully reduced, with its pivot entry (or `None`, if the
+    /// reduced column is zero).
+    fn column_reduced( &mut self, _column: usize, _pivot_entry: Option< &(Key, Val) > ) {}
 }
 
+impl < Key, Val > ReductionObserver< Key, Val > for () {}
+
 
 
 
@@ -362,4 +560,73 @@ mod tests {
     
     }
 
+
+    #[test]
+    fn test_right_reduce_by_order_with_simplex_keys()
+    {
+        use crate::rings::field_prime::GF2;
+        use crate::utilities::cell_complexes::simplices_unweighted::standard_complexes::{facets_sphere, boundary_matrix_from_facets};
+        use crate::rings::ring_native::NativeRing;
+
+        // index the rows of the boundary matrix of the 2-sphere by simplices, rather than integers
+        let (bimap, boundary)   =   boundary_matrix_from_facets( &facets_sphere( 2 ), 2, NativeRing::<i64>::new() );
+        let mut matrix_usize: Vec< Vec< (usize, bool) > >
+                                =   boundary.iter().map( |col| col.iter().map( |x| ( x.0, true ) ).collect() ).collect();
+        let mut matrix_simplex: Vec< Vec< (Vec< usize >, bool) > >
+                                =   matrix_usize.iter().map( |col| col.iter().map( |x| ( bimap.ord_to_val[ x.0 ].clone(), true ) ).collect() ).collect();
+
+        // order simplices by dimension, then lexicographically (the order used by the bimap)
+        let order               =   | a: &Vec< usize >, b: &Vec< usize > | ( a.len(), a ).cmp( &( b.len(), b ) );
+        let pivots_simplex      =   right_reduce_by_order( &mut matrix_simplex, GF2::new(), order );
+        let pivots_usize        =   right_reduce( &mut matrix_usize, GF2::new() );
+
+        assert_eq!( pivots_simplex.len(), pivots_usize.len() );
+        for (row, col) in pivots_usize {
+            assert_eq!( pivots_simplex.get( &bimap.ord_to_val[ row ] ), Some( &col ) );
+        }
+    }
+
+
+    #[test]
+    fn test_zero_pivots_are_errors()
+    {
+        use crate::rings::field_prime::GFp;
+
+        // column 1 has an explicit zero in its pivot position; column 2 would have to be cleared with it
+        let matrix          =   vec![ vec![ (0, 1) ], vec![ (0, 2), (1, 0) ], vec![ (1, 1) ], vec![ (1, 1), (2, 1) ] ];
+        let mut reduced     =   matrix.clone();
+        assert_eq!( try_right_reduce( &mut reduced, GFp::new( 3 ) ), Err( ZeroPivot{ column: 1 } ) );
+        assert_eq!( reduced.len(), matrix.len() );
+        assert_eq!( reduced[ 2 ], matrix[ 2 ] );
+        assert_eq!( ZeroPivot{ column: 1 }.to_string(), "the pivot entry of column 1 is zero" );
+
+        // without explicit zeros the reduction succeeds
+        let mut reduced     =   vec![ vec![ (0, 1) ], vec![ (0, 2) ], vec![ (1, 1) ] ];
+        let pivots          =   try_right_reduce( &mut reduced, GFp::new( 3 ) ).unwrap();
+        assert_eq!( ( pivots.len(), reduced[ 1 ].is_empty() ), ( 2, true ) );
+    }
+
+    #[test]
+    fn test_keys_need_not_implement_debug()
+    {
+        use crate::rings::field_prime::GF2;
+
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        struct Opaque( usize );
+
+        let order           =   | a: &Opaque, b: &Opaque | a.0.cmp( &b.0 );
+        let mut matrix      =   vec![ vec![ ( Opaque( 0 ), true ), ( Opaque( 1 ), true ) ], vec![ ( Opaque( 0 ), true ), ( Opaque( 1 ), true ) ] ];
+        let pivots          =   right_reduce_by_order( &mut matrix, GF2::new(), order );
+        assert_eq!( pivots.get( &Opaque( 1 ) ), Some( &0 ) );
+        assert!( matrix[ 1 ].is_empty() );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic( expected = "not sorted" )]
+    fn test_unsorted_column_panics_in_debug_builds() {
+        let mut matrix      =   vec![ vec![ (0, 1.) ], vec![ (2, 1.), (0, 1.) ] ];
+        right_reduce( &mut matrix, NativeDivisionRing::<f64>::new() );
+    }
+
 }
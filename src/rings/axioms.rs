@@ -0,0 +1,282 @@
+//! Check that a ring object satisfies the axioms of a semiring, ring, or division ring.
+//!
+//! Each function takes a ring object and a collection of sample elements, and checks every axiom
+//! on every pair or triple of samples (so the cost is cubic in the number of samples).  Pass all
+//! the elements of a small ring to check it exhaustively, or a few dozen random elements (e.g.
+//! drawn with [`SampleableRing`](crate::rings::random::SampleableRing)) to check it randomly.  The
+//! functions panic with a message naming the axiom and the elements that violate it.
+//!
+//! Elements are compared with `==`.  The `_by` variants take a custom equality instead; use them
+//! when elements have several representations (as in a
+//! [`FractionField`](crate::rings::field_fraction::FractionField)), or when arithmetic is
+//! inexact (as for floating point numbers).
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::axioms::assert_division_ring_axioms;
+//! use solar::rings::field_prime::GFp;
+//! use solar::rings::random::SampleableRing;
+//! use rand::SeedableRng;
+//! use rand::rngs::StdRng;
+//!
+//! // exhaustively
+//! assert_division_ring_axioms( GFp::new( 7 ), 0 .. 7 );
+//!
+//! // randomly
+//! let field       =   GFp::new( 1_000_000_007 );
+//! let mut rng     =   StdRng::seed_from_u64( 0 );
+//! let samples: Vec< _ >   =   ( 0 .. 20 ).map( |_| field.random_element( &mut rng ) ).collect();
+//! assert_division_ring_axioms( field, samples );
+//! ```
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use std::fmt::Debug;
+
+
+//  ---------------------------------------------------------
+//  SEMIRINGS
+//  ---------------------------------------------------------
+
+/// Check the semiring axioms on all pairs and triples of `samples`, comparing elements with `==`.
+///
+/// See [`assert_semiring_axioms_by`].
+pub fn assert_semiring_axioms< RingOperator, Element, I >( ring: RingOperator, samples: I )
+    where   RingOperator:   Semiring< Element >,
+            Element:        Clone + Debug + PartialEq,
+            I:              IntoIterator< Item = Element >,
+{
+    assert_semiring_axioms_by( ring, samples, |x, y| x == y )
+}
+
+/// Check the semiring axioms on all pairs and triples of `samples`, comparing elements with `eq`.
+///
+/// The axioms checked are
+/// - `is_0` and `is_1` recognize exactly the elements equal to `zero()` and `one()`,
+/// - `0` and `1` are identities for addition and multiplication, and `0` annihilates,
+/// - addition is commutative and associative, multiplication is associative, and
+/// - multiplication distributes over addition, on both sides.
+pub fn assert_semiring_axioms_by< RingOperator, Element, I, Eq >( ring: RingOperator, samples: I, eq: Eq )
+    where   RingOperator:   Semiring< Element >,
+            Element:        Clone + Debug,
+            I:              IntoIterator< Item = Element >,
+            Eq:             Fn( &Element, &Element ) -> bool,
+{
+    check_semiring( &ring, &samples.into_iter().collect::< Vec< _ > >(), &eq )
+}
+
+fn check_semiring< RingOperator, Element, Eq >( ring: &RingOperator, samples: &[ Element ], eq: &Eq )
+    where   RingOperator:   Semiring< Element >,
+            Element:        Clone + Debug,
+            Eq:             Fn( &Element, &Element ) -> bool,
+{
+    let ( zero, one )               =   ( RingOperator::zero(), RingOperator::one() );
+    let check   =   | axiom: &str, lhs: Element, rhs: Element, elements: &[ &Element ] | {
+                        assert!( eq( &lhs, &rhs ), "{} fails for {:?}: {:?} != {:?}", axiom, elements, lhs, rhs );
+                    };
+
+    assert!( ring.is_0( zero.clone() ), "is_0( zero() ) is false" );
+    assert!( ring.is_1( one.clone() ), "is_1( one() ) is false" );
+
+    for x in samples.iter() {
+        assert_eq!( ring.is_0( x.clone() ), eq( x, &zero ), "is_0 disagrees with equality to zero for {:?}", x );
+        assert_eq!( ring.is_1( x.clone() ), eq( x, &one ), "is_1 disagrees with equality to one for {:?}", x );
+        check( "additive identity",         ring.add( x.clone(), zero.clone() ),        x.clone(),      &[ x ] );
+        check( "additive identity",         ring.add( zero.clone(), x.clone() ),        x.clone(),      &[ x ] );
+        check( "multiplicative identity",   ring.multiply( x.clone(), one.clone() ),    x.clone(),      &[ x ] );
+        check( "multiplicative identity",   ring.multiply( one.clone(), x.clone() ),    x.clone(),      &[ x ] );
+        check( "annihilation by zero",      ring.multiply( x.clone(), zero.clone() ),   zero.clone(),   &[ x ] );
+        check( "annihilation by zero",      ring.multiply( zero.clone(), x.clone() ),   zero.clone(),   &[ x ] );
+
+        for y in samples.iter() {
+            check( "commutativity of addition", ring.add( x.clone(), y.clone() ), ring.add( y.clone(), x.clone() ), &[ x, y ] );
+
+            for z in samples.iter() {
+                let elements    =   [ x, y, z ];
+                check(  "associativity of addition",
+                        ring.add( ring.add( x.clone(), y.clone() ), z.clone() ),
+                        ring.add( x.clone(), ring.add( y.clone(), z.clone() ) ),
+                        &elements );
+                check(  "associativity of multiplication",
+                        ring.multiply( ring.multiply( x.clone(), y.clone() ), z.clone() ),
+                        ring.multiply( x.clone(), ring.multiply( y.clone(), z.clone() ) ),
+                        &elements );
+                check(  "left distributivity",
+                        ring.multiply( x.clone(), ring.add( y.clone(), z.clone() ) ),
+                        ring.add( ring.multiply( x.clone(), y.clone() ), ring.multiply( x.clone(), z.clone() ) ),
+                        &elements );
+                check(  "right distributivity",
+                        ring.multiply( ring.add( x.clone(), y.clone() ), z.clone() ),
+                        ring.add( ring.multiply( x.clone(), z.clone() ), ring.multiply( y.clone(), z.clone() ) ),
+                        &elements );
+            }
+        }
+    }
+}
+
+
+//  ---------------------------------------------------------
+//  RINGS
+//  ---------------------------------------------------------
+
+/// Check the ring axioms on all pairs and triples of `samples`, comparing elements with `==`.
+///
+/// See [`assert_ring_axioms_by`].
+pub fn assert_ring_axioms< RingOperator, Element, I >( ring: RingOperator, samples: I )
+    where   RingOperator:   Ring< Element >,
+            Element:        Clone + Debug + PartialEq,
+            I:              IntoIterator< Item = Element >,
+{
+    assert_ring_axioms_by( ring, samples, |x, y| x == y )
+}
+
+/// Check the ring axioms on all pairs and triples of `samples`, comparing elements with `eq`.
+///
+/// The axioms checked are those of [`assert_semiring_axioms_by`], and
+/// - `x + negate( x ) = 0`, and
+/// - `subtract( x, y ) = x + negate( y )`.
+pub fn assert_ring_axioms_by< RingOperator, Element, I, Eq >( ring: RingOperator, samples: I, eq: Eq )
+    where   RingOperator:   Ring< Element >,
+            Element:        Clone + Debug,
+            I:              IntoIterator< Item = Element >,
+            Eq:             Fn( &Element, &Element ) -> bool,
+{
+    check_ring( &ring, &samples.into_iter().collect::< Vec< _ > >(), &eq )
+}
+
+fn check_ring< RingOperator, Element, Eq >( ring: &RingOperator, samples: &[ Element ], eq: &Eq )
+    where   RingOperator:   Ring< Element >,
+            Element:        Clone + Debug,
+            Eq:             Fn( &Element, &Element ) -> bool,
+{
+    check_semiring( ring, samples, eq );
+
+    for x in samples.iter() {
+        let sum     =   ring.add( x.clone(), ring.negate( x.clone() ) );
+        assert!( eq( &sum, &RingOperator::zero() ), "additive inverse fails for {:?}: x + negate( x ) = {:?}", x, sum );
+        for y in samples.iter() {
+            let ( lhs, rhs )    =   ( ring.subtract( x.clone(), y.clone() ), ring.add( x.clone(), ring.negate( y.clone() ) ) );
+            assert!( eq( &lhs, &rhs ), "subtraction fails for {:?}: {:?} != {:?}", [ x, y ], lhs, rhs );
+        }
+    }
+}
+
+
+//  ---------------------------------------------------------
+//  DIVISION RINGS
+//  ---------------------------------------------------------
+
+/// Check the division ring axioms on all pairs and triples of `samples`, comparing elements with
+/// `==`.
+///
+/// See [`assert_division_ring_axioms_by`].
+pub fn assert_division_ring_axioms< RingOperator, Element, I >( ring: RingOperator, samples: I )
+    where   RingOperator:   DivisionRing< Element >,
+            Element:        Clone + Debug + PartialEq,
+            I:              IntoIterator< Item = Element >,
+{
+    assert_division_ring_axioms_by( ring, samples, |x, y| x == y )
+}
+
+/// Check the division ring axioms on all pairs and triples of `samples`, comparing elements with
+/// `eq`.
+///
+/// The axioms checked are those of [`assert_ring_axioms_by`], and, for nonzero `x` and `y`,
+/// - `x * invert( x ) = invert( x ) * x = 1`, and
+/// - `divide( z, y ) = z * invert( y )`, for every `z`;
+///
+/// also, `try_divide` and `try_invert` return `None` exactly when dividing by zero.
+pub fn assert_division_ring_axioms_by< RingOperator, Element, I, Eq >( ring: RingOperator, samples: I, eq: Eq )
+    where   RingOperator:   DivisionRing< Element >,
+            Element:        Clone + Debug,
+            I:              IntoIterator< Item = Element >,
+            Eq:             Fn( &Element, &Element ) -> bool,
+{
+    let samples: Vec< Element >     =   samples.into_iter().collect();
+    check_ring( &ring, &samples, &eq );
+
+    let one     =   RingOperator::one();
+    for y in samples.iter() {
+        if ring.is_0( y.clone() ) {
+            assert!( ring.try_invert( y.clone() ).is_none(), "try_invert( {:?} ) is not None", y );
+            continue
+        }
+        let inverse     =   ring.try_invert( y.clone() ).unwrap_or_else( || panic!( "try_invert( {:?} ) is None", y ) );
+        let ( right, left )     =   ( ring.multiply( y.clone(), inverse.clone() ), ring.multiply( inverse.clone(), y.clone() ) );
+        assert!( eq( &right, &one ) && eq( &left, &one ), "multiplicative inverse fails for {:?}: invert( x ) = {:?}", y, inverse );
+        for z in samples.iter() {
+            let ( lhs, rhs )    =   ( ring.divide( z.clone(), y.clone() ), ring.multiply( z.clone(), inverse.clone() ) );
+            assert!( eq( &lhs, &rhs ), "division fails for {:?}: {:?} != {:?}", [ z, y ], lhs, rhs );
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_complex::ComplexField;
+    use crate::rings::field_fraction::FractionField;
+    use crate::rings::field_prime::{GF2, GF2Checked, GFp};
+    use crate::rings::field_rational::{CheckedRational, CheckedRationalField, RationalField};
+    use crate::rings::laurent_polynomial::LaurentPolynomialRing;
+    use crate::rings::random::SampleableRing;
+    use crate::rings::ring_native::{NativeDivisionRing, NativeRing};
+    use crate::rings::semiring_boolean::BooleanSemiring;
+    use num::Complex;
+    use num::rational::Ratio;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+
+    #[test]
+    fn test_ring_objects_satisfy_the_axioms() {
+        let mut rng     =   StdRng::seed_from_u64( 0 );
+
+        // finite rings, exhaustively
+        assert_division_ring_axioms( GF2::new(), vec![ false, true ] );
+        assert_division_ring_axioms( GF2Checked::new(), vec![ false, true ] );
+        for p in [ 2, 3, 5, 7 ].iter().cloned() { assert_division_ring_axioms( GFp::new( p ), 0 .. p ); }
+        assert_semiring_axioms( BooleanSemiring::new(), vec![ false, true ] );
+
+        // infinite or large rings, randomly
+        let field       =   GFp::new( 1_000_000_007 );
+        assert_division_ring_axioms( field.clone(), ( 0 .. 12 ).map( |_| field.random_element( &mut rng ) ) );
+        let ring        =   NativeRing::< i64 >::new();
+        assert_ring_axioms( ring.clone(), ( 0 .. 12 ).map( |_| ring.random_element( &mut rng ) ) );
+        let field       =   NativeDivisionRing::< Ratio< i64 > >::new();
+        let ratios: Vec< _ >    =   ( 0 .. 10 ).map( |_| field.random_element( &mut rng ) ).collect();
+        assert_division_ring_axioms( field, ratios.iter().cloned() );
+        assert_division_ring_axioms( RationalField::new(), ratios.iter().map( |x| CheckedRational::from( *x ).to_big() ) );
+        assert_division_ring_axioms( CheckedRationalField::new(), ratios.iter().cloned().map( CheckedRational::from ).chain( Some( CheckedRational::from( i64::MAX ) ) ) );
+        let gaussian: Vec< _ >  =   ratios.iter().zip( ratios.iter().rev() ).map( |( re, im )| Complex::new( *re, *im ) ).collect();
+        assert_division_ring_axioms( ComplexField::new( NativeDivisionRing::< Ratio< i64 > >::new() ), gaussian );
+
+        // constructions whose elements have several representations
+        let field       =   FractionField::new_reduced( NativeRing::< i64 >::new() );
+        let fractions: Vec< _ >     =   [ (0, 1), (1, 1), (-2, 3), (4, -6), (5, 7) ].iter().map( |&( n, d )| field.fraction( n, d ) ).collect();
+        assert_division_ring_axioms_by( field.clone(), fractions, |x, y| field.is_0( field.subtract( x.clone(), y.clone() ) ) );
+        let ring        =   LaurentPolynomialRing::new( GFp::new( 3 ) );
+        let polynomials =   vec![ ring.polynomial( vec![] ), ring.t_power( -1 ), ring.polynomial( vec![ (0, 1), (2, 2) ] ), ring.polynomial( vec![ (-1, 2), (1, 1) ] ) ];
+        assert_ring_axioms( ring, polynomials );
+    }
+
+    #[test]
+    #[should_panic( expected = "associativity" )]
+    fn test_floating_point_arithmetic_is_not_associative() {
+        assert_semiring_axioms( NativeDivisionRing::< f64 >::new(), vec![ 0.1, 0.2, 0.3 ] );
+    }
+
+    #[test]
+    fn test_floating_point_axioms_hold_up_to_rounding() {
+        let ring    =   NativeDivisionRing::< f64 >::new();
+        let close   =   | x: &f64, y: &f64 | ( x - y ).abs() <= 1e-12 * ( 1. + x.abs().max( y.abs() ) );
+        let mut rng =   StdRng::seed_from_u64( 1 );
+        assert_division_ring_axioms_by( ring.clone(), ( 0 .. 10 ).map( |_| ring.random_element( &mut rng ) ).chain( Some( 0. ) ), close );
+    }
+
+}
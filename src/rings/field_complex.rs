@@ -0,0 +1,156 @@
+//! Objects representing fields of complex numbers.
+//!
+//! A [`ComplexField`] performs arithmetic on [`num::Complex`] numbers, using a ring object for the
+//! real and imaginary parts.  With [`NativeDivisionRing<f64>`](crate::rings::ring_native::NativeDivisionRing)
+//! this is the usual floating point complex arithmetic; with `NativeDivisionRing<Ratio<i64>>` or
+//! [`RationalField`](crate::rings::field_rational::RationalField) it is exact arithmetic over the
+//! Gaussian rationals `Q(i)`.  The ring object also provides complex conjugation, which is needed
+//! to form adjoints (e.g. for Hodge Laplacians `d d^* + d^* d` with complex coefficients).
+//!
+//! Division computes `x / y = x * conj(y) / |y|^2`, so it requires that `a^2 + b^2` be nonzero
+//! whenever `a + bi` is nonzero; this holds for any ordered field of real parts, such as the
+//! floating point numbers or the rationals.
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use num::Complex;
+
+
+/// The field of complex numbers `a + bi`, where `a` and `b` are elements of the field represented
+/// by `RealRing`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::field_complex::ComplexField;
+/// use solar::rings::ring_native::NativeDivisionRing;
+/// use solar::rings::ring::{Semiring, DivisionRing};
+/// use num::rational::Ratio;
+/// use num::Complex;
+///
+/// // exact arithmetic over the Gaussian rationals
+/// let ring    =   ComplexField::new( NativeDivisionRing::< Ratio< i64 > >::new() );
+/// let gauss   =   | a: i64, b: i64 | Complex::new( Ratio::from_integer( a ), Ratio::from_integer( b ) );
+///
+/// // ( 1 + 2i ) / ( 3 - 4i ) = ( -1 + 2i ) / 5
+/// let quotient    =   ring.divide( gauss( 1, 2 ), gauss( 3, -4 ) );
+/// assert_eq!( quotient, Complex::new( Ratio::new( -1, 5 ), Ratio::new( 2, 5 ) ) );
+/// assert_eq!( ring.multiply( quotient, gauss( 3, -4 ) ), gauss( 1, 2 ) );
+/// assert_eq!( ring.conjugate( gauss( 1, 2 ) ), gauss( 1, -2 ) );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ComplexField< RealRing > {
+    real_ring:  RealRing,
+}
+
+impl < RealRing > ComplexField< RealRing > {
+    /// The field of complex numbers whose real and imaginary parts lie in the field represented
+    /// by `real_ring`.
+    pub fn new( real_ring: RealRing ) -> Self { ComplexField{ real_ring } }
+
+    /// The ring object for the real and imaginary parts.
+    pub fn real_ring( &self ) -> &RealRing { &self.real_ring }
+
+    /// The complex conjugate `a - bi` of `x = a + bi`.
+    pub fn conjugate< Real >( &self, x: Complex< Real > ) -> Complex< Real >
+        where   RealRing:   Ring< Real >,
+    {
+        Complex::new( x.re, self.real_ring.negate( x.im ) )
+    }
+
+    /// The squared modulus `a^2 + b^2` of `x = a + bi`.
+    pub fn norm_sqr< Real >( &self, x: Complex< Real > ) -> Real
+        where   RealRing:   Semiring< Real >,
+                Real:       Clone,
+    {
+        let ring    =   &self.real_ring;
+        ring.add( ring.multiply( x.re.clone(), x.re ), ring.multiply( x.im.clone(), x.im ) )
+    }
+}
+
+impl < RealRing, Real > Semiring< Complex< Real > > for ComplexField< RealRing >
+    where   RealRing:   Ring< Real >,
+            Real:       Clone,
+{
+    fn is_0( &self, x: Complex< Real > ) -> bool { self.real_ring.is_0( x.re ) && self.real_ring.is_0( x.im ) }
+    fn is_1( &self, x: Complex< Real > ) -> bool { self.real_ring.is_1( x.re ) && self.real_ring.is_0( x.im ) }
+    fn zero() -> Complex< Real > { Complex::new( RealRing::zero(), RealRing::zero() ) }
+    fn one()  -> Complex< Real > { Complex::new( RealRing::one(),  RealRing::zero() ) }
+
+    fn add( &self, x: Complex< Real >, y: Complex< Real > ) -> Complex< Real > {
+        Complex::new( self.real_ring.add( x.re, y.re ), self.real_ring.add( x.im, y.im ) )
+    }
+
+    /// `( a + bi )( c + di ) = ( ac - bd ) + ( ad + bc )i`
+    fn multiply( &self, x: Complex< Real >, y: Complex< Real > ) -> Complex< Real > {
+        let ring    =   &self.real_ring;
+        let re      =   ring.subtract( ring.multiply( x.re.clone(), y.re.clone() ), ring.multiply( x.im.clone(), y.im.clone() ) );
+        let im      =   ring.add( ring.multiply( x.re, y.im ), ring.multiply( x.im, y.re ) );
+        Complex::new( re, im )
+    }
+}
+
+impl < RealRing, Real > Ring< Complex< Real > > for ComplexField< RealRing >
+    where   RealRing:   Ring< Real >,
+            Real:       Clone,
+{
+    fn subtract( &self, x: Complex< Real >, y: Complex< Real > ) -> Complex< Real > {
+        Complex::new( self.real_ring.subtract( x.re, y.re ), self.real_ring.subtract( x.im, y.im ) )
+    }
+    fn negate( &self, x: Complex< Real > ) -> Complex< Real > {
+        Complex::new( self.real_ring.negate( x.re ), self.real_ring.negate( x.im ) )
+    }
+}
+
+impl < RealRing, Real > DivisionRing< Complex< Real > > for ComplexField< RealRing >
+    where   RealRing:   DivisionRing< Real >,
+            Real:       Clone,
+{
+    /// `x / y = x * conj(y) / |y|^2`; the result of dividing by zero depends on the ring object
+    /// for the real parts.
+    fn divide( &self, x: Complex< Real >, y: Complex< Real > ) -> Complex< Real > {
+        let norm_sqr    =   self.norm_sqr( y.clone() );
+        let numerator   =   self.multiply( x, self.conjugate( y ) );
+        Complex::new( self.real_ring.divide( numerator.re, norm_sqr.clone() ), self.real_ring.divide( numerator.im, norm_sqr ) )
+    }
+
+    /// `1 / x = conj(x) / |x|^2`
+    fn invert( &self, x: Complex< Real > ) -> Complex< Real > { self.divide( Self::one(), x ) }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::ring_native::NativeDivisionRing;
+
+
+    #[test]
+    fn test_complex_field_agrees_with_native_complex_arithmetic() {
+        let ring        =   ComplexField::new( NativeDivisionRing::< f64 >::new() );
+        let native      =   NativeDivisionRing::< Complex< f64 > >::new();
+        let values      =   [ Complex::new( 0.5, -2. ), Complex::new( 3., 4. ), Complex::new( 0., 1. ), Complex::new( -1.25, 0. ) ];
+        for x in values.iter().cloned() {
+            assert_eq!( ring.negate( x ), native.negate( x ) );
+            assert!( ( ring.invert( x ) - native.invert( x ) ).norm() < 1e-12 );
+            assert_eq!( ring.norm_sqr( x ), x.norm_sqr() );
+            assert_eq!( ring.conjugate( x ), x.conj() );
+            for y in values.iter().cloned() {
+                assert_eq!( ring.add( x, y ), native.add( x, y ) );
+                assert_eq!( ring.subtract( x, y ), native.subtract( x, y ) );
+                assert_eq!( ring.multiply( x, y ), native.multiply( x, y ) );
+                assert!( ( ring.divide( x, y ) - native.divide( x, y ) ).norm() < 1e-12 );
+            }
+        }
+
+        assert!( ring.is_0( ComplexField::< NativeDivisionRing< f64 > >::zero() ) );
+        assert!( ring.is_1( ComplexField::< NativeDivisionRing< f64 > >::one() ) );
+        assert!( ! ring.is_1( Complex::new( 1., 1. ) ) );
+        assert_eq!( ring.try_invert( Complex::new( 0., 0. ) ), None );
+        assert_eq!( ring.try_invert( Complex::new( 0., 2. ) ), Some( Complex::new( 0., -0.5 ) ) );
+    }
+
+}
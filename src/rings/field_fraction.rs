@@ -0,0 +1,280 @@
+//! Fields of fractions.
+//!
+//! Every integral domain `R` embeds in a field, its field of fractions, whose elements are formal
+//! fractions `a / b` with `a, b` in `R` and `b` nonzero.  A [`FractionField`] wraps a ring object
+//! for `R`, and performs arithmetic on [`Fraction`]s.  This allows exact reduction of matrices over
+//! rings which are not fields; for example, a matrix of Laurent polynomials over a finite field
+//! can be reduced over the field of rational functions.
+//!
+//! Without cancellation, numerators and denominators grow with every operation.  If the ring
+//! object implements [`GcdDomain`], build the field with [`FractionField::new_reduced`]: every
+//! result is then divided through by the greatest common divisor of its numerator and
+//! denominator.  Otherwise, build the field with [`FractionField::new`], and no cancellation is
+//! attempted.
+//!
+//! Fractions are only reduced up to units (`1/2` may be represented as `-1/-2`, for example), so
+//! two fractions should be compared with the ring object, not with `==`.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::field_fraction::FractionField;
+//! use solar::rings::ring_native::NativeRing;
+//! use solar::rings::ring::{Semiring, Ring, DivisionRing};
+//!
+//! // the rationals, as the field of fractions of the integers
+//! let field   =   FractionField::new_reduced( NativeRing::< i64 >::new() );
+//! let half    =   field.fraction( 1, 2 );
+//! let third   =   field.fraction( 2, 6 );
+//!
+//! let sum     =   field.add( half.clone(), third.clone() );
+//! assert_eq!( ( sum.numer(), sum.denom() ), ( &5, &6 ) );
+//! assert!( field.is_1( field.multiply( third.clone(), field.invert( third ) ) ) );
+//! assert!( field.is_0( field.subtract( half, field.fraction( -3, -6 ) ) ) );
+//! ```
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing, GcdDomain};
+
+
+//  ---------------------------------------------------------
+//  FRACTIONS
+//  ---------------------------------------------------------
+
+/// A formal fraction `numer / denom`, with nonzero denominator.
+#[derive(Clone, Debug)]
+pub struct Fraction< Element > {
+    numer:      Element,
+    denom:      Element,
+}
+
+impl < Element > Fraction< Element > {
+    /// The numerator.
+    pub fn numer( &self ) -> &Element { &self.numer }
+
+    /// The denominator.
+    pub fn denom( &self ) -> &Element { &self.denom }
+
+    /// Consume the fraction, returning `(numerator, denominator)`.
+    pub fn into_parts( self ) -> ( Element, Element ) { ( self.numer, self.denom ) }
+}
+
+
+//  ---------------------------------------------------------
+//  REDUCTION
+//  ---------------------------------------------------------
+
+/// A rule for putting a fraction `numer / denom` in lowest terms, used by a [`FractionField`].
+pub trait FractionReduction< RingOperator, Element > {
+    /// A fraction equal to `numer / denom`.
+    fn reduce( &self, ring: &RingOperator, numer: Element, denom: Element ) -> ( Element, Element );
+}
+
+/// Leave fractions as they are.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoReduction{}
+
+impl < RingOperator, Element > FractionReduction< RingOperator, Element > for NoReduction {
+    fn reduce( &self, _ring: &RingOperator, numer: Element, denom: Element ) -> ( Element, Element ) { ( numer, denom ) }
+}
+
+/// Divide the numerator and denominator by their greatest common divisor; zero is represented
+/// as `0 / 1`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GcdReduction{}
+
+impl < RingOperator, Element > FractionReduction< RingOperator, Element > for GcdReduction
+    where   RingOperator:   GcdDomain< Element >,
+            Element:        Clone,
+{
+    fn reduce( &self, ring: &RingOperator, numer: Element, denom: Element ) -> ( Element, Element ) {
+        if ring.is_0( numer.clone() ) { return ( RingOperator::zero(), RingOperator::one() ) }
+        let gcd     =   ring.gcd( numer.clone(), denom.clone() );
+        ( ring.divide_exact( numer, gcd.clone() ), ring.divide_exact( denom, gcd ) )
+    }
+}
+
+
+//  ---------------------------------------------------------
+//  THE FIELD OF FRACTIONS
+//  ---------------------------------------------------------
+
+/// The field of fractions of the integral domain represented by `RingOperator`.
+///
+/// The `Reduction` parameter determines how fractions are put in lowest terms; see the
+/// [module documentation](crate::rings::field_fraction).  The results are only meaningful if
+/// `RingOperator` represents an integral domain: in a ring with zero divisors, the product of two
+/// denominators may be zero.
+#[derive(Debug, Clone)]
+pub struct FractionField< RingOperator, Reduction > {
+    ring:       RingOperator,
+    reduction:  Reduction,
+}
+
+impl < RingOperator > FractionField< RingOperator, NoReduction > {
+    /// The field of fractions of `ring`, without cancellation.
+    pub fn new( ring: RingOperator ) -> Self { FractionField{ ring, reduction: NoReduction{} } }
+}
+
+impl < RingOperator > FractionField< RingOperator, GcdReduction > {
+    /// The field of fractions of `ring`, with fractions divided through by the greatest common
+    /// divisor of the numerator and denominator.
+    pub fn new_reduced( ring: RingOperator ) -> Self { FractionField{ ring, reduction: GcdReduction{} } }
+}
+
+impl < RingOperator, Reduction > FractionField< RingOperator, Reduction > {
+    /// The ring object for numerators and denominators.
+    pub fn ring( &self ) -> &RingOperator { &self.ring }
+
+    /// The fraction `numer / denom`, in lowest terms.
+    ///
+    /// Panics if `denom` is zero.
+    pub fn fraction< Element >( &self, numer: Element, denom: Element ) -> Fraction< Element >
+        where   RingOperator:   Ring< Element >,
+                Reduction:      FractionReduction< RingOperator, Element >,
+                Element:        Clone,
+    {
+        assert!( ! self.ring.is_0( denom.clone() ), "the denominator of a fraction must be nonzero" );
+        self.reduced( numer, denom )
+    }
+
+    /// The fraction `x / 1`.
+    pub fn from_element< Element >( &self, x: Element ) -> Fraction< Element >
+        where   RingOperator:   Ring< Element >,
+    {
+        Fraction{ numer: x, denom: RingOperator::one() }
+    }
+
+    /// The fraction `numer / denom`, in lowest terms; assumes `denom` is nonzero.
+    fn reduced< Element >( &self, numer: Element, denom: Element ) -> Fraction< Element >
+        where   Reduction:      FractionReduction< RingOperator, Element >,
+    {
+        let ( numer, denom )    =   self.reduction.reduce( &self.ring, numer, denom );
+        Fraction{ numer, denom }
+    }
+}
+
+impl < RingOperator, Reduction, Element > Semiring< Fraction< Element > > for FractionField< RingOperator, Reduction >
+    where   RingOperator:   Ring< Element >,
+            Reduction:      FractionReduction< RingOperator, Element >,
+            Element:        Clone,
+{
+    fn is_0( &self, x: Fraction< Element > ) -> bool { self.ring.is_0( x.numer ) }
+    fn is_1( &self, x: Fraction< Element > ) -> bool { self.ring.is_0( self.ring.subtract( x.numer, x.denom ) ) }
+    fn zero() -> Fraction< Element > { Fraction{ numer: RingOperator::zero(), denom: RingOperator::one() } }
+    fn one()  -> Fraction< Element > { Fraction{ numer: RingOperator::one(),  denom: RingOperator::one() } }
+
+    /// `a/b + c/d = ( ad + cb ) / bd`
+    fn add( &self, x: Fraction< Element >, y: Fraction< Element > ) -> Fraction< Element > {
+        let ring    =   &self.ring;
+        let numer   =   ring.add( ring.multiply( x.numer, y.denom.clone() ), ring.multiply( y.numer, x.denom.clone() ) );
+        self.reduced( numer, ring.multiply( x.denom, y.denom ) )
+    }
+
+    /// `(a/b)(c/d) = ac / bd`
+    fn multiply( &self, x: Fraction< Element >, y: Fraction< Element > ) -> Fraction< Element > {
+        self.reduced( self.ring.multiply( x.numer, y.numer ), self.ring.multiply( x.denom, y.denom ) )
+    }
+}
+
+impl < RingOperator, Reduction, Element > Ring< Fraction< Element > > for FractionField< RingOperator, Reduction >
+    where   RingOperator:   Ring< Element >,
+            Reduction:      FractionReduction< RingOperator, Element >,
+            Element:        Clone,
+{
+    fn subtract( &self, x: Fraction< Element >, y: Fraction< Element > ) -> Fraction< Element > {
+        self.add( x, self.negate( y ) )
+    }
+
+    fn negate( &self, x: Fraction< Element > ) -> Fraction< Element > {
+        Fraction{ numer: self.ring.negate( x.numer ), denom: x.denom }
+    }
+}
+
+impl < RingOperator, Reduction, Element > DivisionRing< Fraction< Element > > for FractionField< RingOperator, Reduction >
+    where   RingOperator:   Ring< Element >,
+            Reduction:      FractionReduction< RingOperator, Element >,
+            Element:        Clone,
+{
+    /// `(a/b) / (c/d) = ad / bc`; panics if `y` is zero.
+    fn divide( &self, x: Fraction< Element >, y: Fraction< Element > ) -> Fraction< Element > {
+        assert!( ! self.ring.is_0( y.numer.clone() ), "division by zero in a field of fractions" );
+        self.reduced( self.ring.multiply( x.numer, y.denom ), self.ring.multiply( x.denom, y.numer ) )
+    }
+
+    /// Panics if `x` is zero.
+    fn invert( &self, x: Fraction< Element > ) -> Fraction< Element > {
+        assert!( ! self.ring.is_0( x.numer.clone() ), "division by zero in a field of fractions" );
+        Fraction{ numer: x.denom, denom: x.numer }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GFp;
+    use crate::rings::laurent_polynomial::{LaurentPolynomial, LaurentPolynomialRing};
+    use crate::rings::ring_native::NativeRing;
+
+
+    #[test]
+    fn test_rational_functions() {
+        let polynomials     =   LaurentPolynomialRing::new( GFp::new( 5 ) );
+        let reduced         =   FractionField::new_reduced( polynomials.clone() );
+        let unreduced       =   FractionField::new( polynomials.clone() );
+
+        // ( 1 - t^2 ) / ( 1 - t ) = 1 + t
+        let a               =   polynomials.polynomial( vec![ (0, 1), (2, 4) ] );
+        let b               =   polynomials.polynomial( vec![ (0, 1), (1, 4) ] );
+        let c               =   polynomials.polynomial( vec![ (0, 1), (1, 1) ] );
+        let quotient        =   reduced.fraction( a.clone(), b.clone() );
+        assert_eq!( quotient.denom().degree(), Some( 0 ) );
+        assert!( reduced.is_0( reduced.subtract( quotient, reduced.from_element( c.clone() ) ) ) );
+
+        // Laurent monomials are units: t^{-2} ( 1 + t ) / ( t^3 + t^4 ) = t^{-5}
+        let d               =   polynomials.polynomial( vec![ (3, 1), (4, 1) ] );
+        let e               =   reduced.fraction( c.clone().shift( -2 ), d.clone() );
+        assert!( reduced.is_0( reduced.subtract( e, reduced.from_element( polynomials.t_power( -5 ) ) ) ) );
+
+        // the same computations agree with and without reduction
+        let expected    =   [   ( polynomials.add( polynomials.multiply( a.clone(), d.clone() ), polynomials.multiply( c.clone(), b.clone() ) ), polynomials.multiply( b.clone(), d.clone() ) ),
+                                ( polynomials.multiply( a.clone(), c.clone() ), polynomials.multiply( b.clone(), d.clone() ) ),
+                                ( polynomials.multiply( a.clone(), d.clone() ), polynomials.multiply( b.clone(), c.clone() ) ),   ];
+        for results in [ sum_product_ratio( &reduced, &a, &b, &c, &d ), sum_product_ratio( &unreduced, &a, &b, &c, &d ) ].iter().cloned() {
+            for ( ( numer, denom ), ( expected_numer, expected_denom ) ) in results.into_iter().zip( expected.iter().cloned() ) {
+                let cross   =   polynomials.subtract( polynomials.multiply( numer, expected_denom ), polynomials.multiply( expected_numer, denom ) );
+                assert!( polynomials.is_0( cross ) );
+            }
+        }
+    }
+
+    /// The sum, product and ratio of `a/b` and `c/d`, as `(numerator, denominator)` pairs.
+    fn sum_product_ratio< Reduction >( field: &FractionField< LaurentPolynomialRing< GFp >, Reduction >, a: &LaurentPolynomial< u64 >, b: &LaurentPolynomial< u64 >, c: &LaurentPolynomial< u64 >, d: &LaurentPolynomial< u64 > )
+            -> Vec< ( LaurentPolynomial< u64 >, LaurentPolynomial< u64 > ) >
+        where   Reduction:  FractionReduction< LaurentPolynomialRing< GFp >, LaurentPolynomial< u64 > >,
+    {
+        let ( x, y )    =   ( field.fraction( a.clone(), b.clone() ), field.fraction( c.clone(), d.clone() ) );
+        vec![   field.add( x.clone(), y.clone() ).into_parts(),
+                field.multiply( x.clone(), y.clone() ).into_parts(),
+                field.divide( x, y ).into_parts(),  ]
+    }
+
+    #[test]
+    fn test_rationals_as_fractions_of_integers() {
+        let field       =   FractionField::new_reduced( NativeRing::< i64 >::new() );
+        let x           =   field.fraction( 6, -4 );
+        assert_eq!( x.clone().into_parts(), ( 3, -2 ) );
+        assert!( field.is_1( field.divide( x.clone(), field.fraction( -3, 2 ) ) ) );
+        assert_eq!( field.subtract( x.clone(), x ).into_parts(), ( 0, 1 ) );
+        assert!( field.try_invert( FractionField::< NativeRing< i64 >, GcdReduction >::zero() ).is_none() );
+    }
+
+    #[test]
+    #[should_panic( expected = "denominator" )]
+    fn test_zero_denominators_panic() { FractionField::new( NativeRing::< i64 >::new() ).fraction( 1, 0 ); }
+
+}
@@ -0,0 +1,270 @@
+//! Objects representing the finite fields of order `2^k`.
+//!
+//! The field `GF(2^k)` is the quotient `GF(2)[x] / (f)` of the ring of polynomials with
+//! coefficients in [`GF2`](crate::rings::field_prime::GF2) by an irreducible polynomial `f` of
+//! degree `k`.  A [`GF2k`] stores each element, a polynomial of degree less than `k`, as the `u64`
+//! whose bit `i` is the coefficient of `x^i`.  Addition is then bitwise xor, and multiplication is
+//! carry-less multiplication followed by reduction modulo `f`, so arithmetic needs no lookups
+//! beyond the inverse table.  Degrees `1 ..= 63` are supported.
+//!
+//! The function [`is_irreducible`] checks whether a polynomial, stored the same way, is
+//! irreducible.
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use std::sync::Arc;
+
+
+//  ---------------------------------------------------------
+//  POLYNOMIALS OVER GF(2)
+//  ---------------------------------------------------------
+
+/// The degree of the nonzero polynomial `f`.
+fn degree( f: u64 ) -> u32 { 63 - f.leading_zeros() }
+
+/// `x * y mod f`, for `x` and `y` of degree less than `degree( f )`.
+fn multiply_mod( mut x: u64, mut y: u64, f: u64 ) -> u64 {
+    // carry-less shift-and-add, reducing the shifted copy of x as soon as it reaches degree k
+    let top         =   1u64 << degree( f );
+    let mut product =   0;
+    while y != 0 {
+        if y & 1 == 1 { product ^= x }
+        y           >>= 1;
+        x           <<= 1;
+        if x & top != 0 { x ^= f }
+    }
+    product
+}
+
+/// The quotient and remainder of polynomial division of `a` by the nonzero polynomial `b`.
+fn divide_with_remainder( mut a: u64, b: u64 ) -> ( u64, u64 ) {
+    let mut quotient    =   0;
+    while a != 0 && degree( a ) >= degree( b ) {
+        let shift       =   degree( a ) - degree( b );
+        quotient        |=  1 << shift;
+        a               ^=  b << shift;
+    }
+    ( quotient, a )
+}
+
+/// A greatest common divisor of `a` and `b`.
+fn gcd( mut a: u64, mut b: u64 ) -> u64 {
+    while b != 0 { let remainder = divide_with_remainder( a, b ).1; a = b; b = remainder }
+    a
+}
+
+/// The inverse of `x` modulo `f` (or zero, if `x` is zero), by the extended Euclidean algorithm.
+fn invert_mod( x: u64, f: u64 ) -> u64 {
+    let ( mut r0, mut r1 )  =   ( f, x );
+    let ( mut t0, mut t1 )  =   ( 0, 1 );
+    while r1 != 0 {
+        let ( quotient, remainder ) =   divide_with_remainder( r0, r1 );
+        ( r0, r1 )  =   ( r1, remainder );
+        // the Bezout coefficients have degree less than degree( f ), so this cannot overflow
+        let mut product     =   0;
+        for bit in 0 .. 64 { if quotient >> bit & 1 == 1 { product ^= t1 << bit } }
+        ( t0, t1 )  =   ( t1, t0 ^ product );
+    }
+    if r0 != 1 { return 0 } // x is zero
+    t0
+}
+
+/// Returns `true` if the polynomial `f` over `GF(2)` is irreducible, where bit `i` of `f` is the
+/// coefficient of `x^i`.
+///
+/// Uses Ben-Or's test: `f` of degree `k` is irreducible iff `x^(2^i) - x` is coprime to `f` for
+/// each `i <= k / 2`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::field_gf2k::is_irreducible;
+///
+/// assert!( is_irreducible( 0b111 ) );                 // x^2 + x + 1
+/// assert!( ! is_irreducible( 0b101 ) );               // x^2 + 1 = ( x + 1 )^2
+/// assert!( is_irreducible( 0x11B ) );                 // the AES polynomial x^8 + x^4 + x^3 + x + 1
+/// ```
+pub fn is_irreducible( f: u64 ) -> bool {
+    if f < 2 { return false }
+    let k           =   degree( f );
+    let x           =   divide_with_remainder( 0b10, f ).1;
+    let mut power   =   x;     // x^(2^i) mod f
+    for _ in 1 ..= k / 2 {
+        power       =   multiply_mod( power, power, f );
+        if gcd( f, power ^ x ) != 1 { return false }
+    }
+    true
+}
+
+
+//  ---------------------------------------------------------
+//  THE FIELD OF ORDER 2^k
+//  ---------------------------------------------------------
+
+/// The largest degree `k` for which [`GF2k::new`] precomputes an inverse table.
+pub const GF2K_TABLE_THRESHOLD: u32 = 16;
+
+/// The finite field of order `2^k`.
+///
+/// Elements are represented by integers `0 .. 2^k` of type `u64`, as described in the
+/// [module documentation](self); all inputs are assumed to lie in this range.  The constructors
+/// check that the modulus is irreducible, using [`is_irreducible`].
+///
+/// When `k <= GF2K_TABLE_THRESHOLD`, the constructors precompute a table of multiplicative
+/// inverses, so that inversion and division perform a table lookup instead of a run of the
+/// extended Euclidean algorithm.  The table is shared between clones of the ring object, so
+/// cloning remains cheap.
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::field_gf2k::GF2k;
+/// use solar::rings::ring::{Semiring, Ring, DivisionRing};
+///
+/// // the field of order 256 used by AES
+/// let ring  =  GF2k::with_modulus( 0x11B );
+///
+/// assert!( ring.uses_tables() );
+/// assert_eq!( 0x53 ^ 0xCA, ring.add( 0x53, 0xCA ) );
+/// assert_eq!( 0x53, ring.subtract( 0, 0x53 ) );
+/// assert_eq!( 0x01, ring.multiply( 0x53, 0xCA ) );
+/// assert_eq!( 0xCA, ring.invert( 0x53 ) );
+///
+/// // a field of order 2^61, with inversion by the extended Euclidean algorithm
+/// let ring  =  GF2k::new( 61 );
+/// assert!( ! ring.uses_tables() );
+/// assert_eq!( 1, ring.multiply( ring.invert( 12345 ), 12345 ) );
+/// ```
+#[derive(Debug, Clone)]
+pub struct GF2k {
+    modulus:    u64,
+    inverses:   Option< Arc< Vec< u16 > > >,
+}
+
+impl GF2k {
+    /// Create the field of order `2^k`, with modulus the least irreducible polynomial of degree
+    /// `k` (comparing polynomials as integers); an inverse table is used if
+    /// `k <= GF2K_TABLE_THRESHOLD`.
+    ///
+    /// Panics unless `1 <= k <= 63`.
+    pub fn new( k: u32 ) -> GF2k { GF2k::with_modulus( GF2k::default_modulus( k ) ) }
+
+    /// Create the field of order `2^k`, with modulus the least irreducible polynomial of degree
+    /// `k`, and without an inverse table.
+    ///
+    /// Panics unless `1 <= k <= 63`.
+    pub fn new_without_tables( k: u32 ) -> GF2k { GF2k{ modulus: GF2k::default_modulus( k ), inverses: None } }
+
+    /// Create the field `GF(2)[x] / (modulus)`, where bit `i` of `modulus` is the coefficient of
+    /// `x^i`; an inverse table is used if the degree of `modulus` is at most `GF2K_TABLE_THRESHOLD`.
+    ///
+    /// Panics if `modulus` is not irreducible.
+    pub fn with_modulus( modulus: u64 ) -> GF2k {
+        assert!( is_irreducible( modulus ), "the modulus of GF(2^k) must be irreducible, but {:#b} is not", modulus );
+        let inverses    =   if degree( modulus ) <= GF2K_TABLE_THRESHOLD {
+                                Some( Arc::new( ( 0 .. 1u64 << degree( modulus ) ).map( |x| invert_mod( x, modulus ) as u16 ).collect() ) )
+                            } else { None };
+        GF2k{ modulus, inverses }
+    }
+
+    /// The least irreducible polynomial of degree `k`.
+    fn default_modulus( k: u32 ) -> u64 {
+        assert!( ( 1 ..= 63 ).contains( &k ), "GF(2^k) is only supported for 1 <= k <= 63, not k = {}", k );
+        ( 1u64 << k .. ).find( |&f| is_irreducible( f ) ).unwrap()
+    }
+
+    /// The degree `k` of the modulus; the field has `2^k` elements.
+    pub fn degree( &self ) -> u32 { degree( self.modulus ) }
+
+    /// The modulus, an irreducible polynomial stored as a bit pattern.
+    pub fn modulus( &self ) -> u64 { self.modulus }
+
+    /// Returns `true` if this ring object performs inversion by table lookup.
+    pub fn uses_tables( &self ) -> bool { self.inverses.is_some() }
+}
+
+impl Semiring<u64> for GF2k
+{
+    fn is_0( &self, x: u64 ) -> bool { x == 0 }
+    fn is_1( &self, x: u64 ) -> bool { x == 1 }
+    fn zero() -> u64 { 0 }
+    fn one()  -> u64 { 1 }
+
+    fn add( &self, x : u64, y : u64 ) -> u64 { x ^ y }
+    fn multiply( &self, x : u64, y: u64 ) -> u64 { multiply_mod( x, y, self.modulus ) }
+}
+
+impl Ring<u64> for GF2k
+{
+    fn subtract( &self, x : u64, y: u64 ) -> u64 { x ^ y }
+    fn negate( &self, x : u64 ) -> u64 { x }
+}
+
+impl DivisionRing<u64> for GF2k
+{
+    /// NOTE: THIS DIVISION IS UNSAFE; DIVIDING BY ZERO RETURNS ZERO.  Use
+    /// [`try_divide`](DivisionRing::try_divide) to check for division by zero.
+    fn divide( &self, x : u64, y: u64 ) -> u64 { self.multiply( x, self.invert( y ) ) }
+
+    /// NOTE: THIS INVERSION IS UNSAFE; INVERTING ZERO RETURNS ZERO.  Use
+    /// [`try_invert`](DivisionRing::try_invert) to check for division by zero.
+    fn invert( &self, x : u64 ) -> u64 {
+        match &self.inverses {
+            Some( inverses )    =>  inverses[ x as usize ] as u64,
+            None                =>  invert_mod( x, self.modulus ),
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::axioms::assert_division_ring_axioms;
+    use crate::rings::field_prime::GF2;
+
+
+    #[test]
+    fn test_irreducible_polynomials() {
+        // the numbers of irreducible polynomials of degree 1, .., 8 over GF(2)
+        let counts: Vec< usize >    =   ( 1 .. 9 ).map( |k| ( 1u64 << k .. 1u64 << ( k + 1 ) ).filter( |&f| is_irreducible( f ) ).count() ).collect();
+        assert_eq!( counts, vec![ 2, 1, 2, 3, 6, 9, 18, 30 ] );
+        assert_eq!( GF2k::new( 8 ).modulus(), 0x11B );
+        assert_eq!( GF2k::new( 63 ).modulus(), ( 1 << 63 ) | 0b11 );
+    }
+
+    #[test]
+    fn test_gf2k() {
+        // GF(2) itself
+        let ring        =   GF2k::new( 1 );
+        for x in 0 .. 2 { for y in 0 .. 2 {
+            assert_eq!( ring.multiply( x, y ) == 1, GF2::new().multiply( x == 1, y == 1 ) );
+        } }
+
+        // tables agree with the extended Euclidean algorithm
+        let table       =   GF2k::new( 10 );
+        let euclid      =   GF2k::new_without_tables( 10 );
+        assert!( table.uses_tables() && ! euclid.uses_tables() );
+        for x in 0 .. 1 << 10 {
+            assert_eq!( table.invert( x ), euclid.invert( x ) );
+            if x != 0 { assert!( table.is_1( table.multiply( x, table.invert( x ) ) ) ) }
+        }
+
+        // the multiplicative group of GF(2^k) has order 2^k - 1, and Frobenius is additive
+        let ring        =   GF2k::new( 40 );
+        let x           =   0xABCDE12345;
+        assert!( ring.is_1( ring.power( x, ( 1 << 40 ) - 1 ) ) );
+        assert_eq!( ring.power( ring.add( x, 0x777 ), 2 ), ring.add( ring.power( x, 2 ), ring.power( 0x777, 2 ) ) );
+
+        assert_division_ring_axioms( GF2k::new( 4 ), 0 .. 16 );
+        assert_division_ring_axioms( GF2k::new( 63 ), vec![ 0, 1, 2, u64::MAX >> 1, 0x1234_5678_9ABC_DEF0 ] );
+    }
+
+    #[test]
+    #[should_panic( expected = "must be irreducible" )]
+    fn test_gf2k_rejects_reducible_moduli() { GF2k::with_modulus( 0b101 ); }
+
+}
@@ -1,6 +1,15 @@
 //! Objects representing prime fields.
+//!
+//! [`GF2`] is the two element field, with elements of type `bool`; [`GFp`] is the field of
+//! integers modulo an arbitrary prime `p`, with elements of type `u64`.  The function
+//! [`is_prime`] checks whether a modulus is prime.
+//!
+//! Division by zero in `GF2` panics in debug builds, and returns a meaningless value in release
+//! builds, where the check would cost time in the inner loop of every reduction.
+//! [`GF2Checked`] performs the same arithmetic, but always panics on division by zero.
 
 use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use std::sync::Arc;
 
 
 
@@ -41,18 +50,277 @@ impl Ring<bool> for GF2
 
 impl DivisionRing<bool> for GF2
 {
-    /// NOTE: THIS DIVISION IS UNSAFE; DESIGNERS MAY WANT TO ADD OPTION TO CHECK FOR DIVISION BY
-    /// ZERO
-    fn divide( &self, x : bool, _y: bool ) -> bool { x.clone() }
+    /// NOTE: IN RELEASE BUILDS THIS DIVISION IS UNSAFE; DIVIDING BY ZERO RETURNS `x`.  Debug
+    /// builds panic instead.  Use [`try_divide`](DivisionRing::try_divide) to check for division
+    /// by zero, or [`GF2Checked`] to panic in every build.
+    fn divide( &self, x : bool, y: bool ) -> bool { 
+        debug_assert!( y, "division by zero in GF2" );
+        x 
+    }
+    
+    /// NOTE: IN RELEASE BUILDS THIS INVERSION IS UNSAFE; INVERTING ZERO RETURNS ZERO.  Debug
+    /// builds panic instead.  Use [`try_invert`](DivisionRing::try_invert) to check for division
+    /// by zero, or [`GF2Checked`] to panic in every build.
+    fn invert( &self, x : bool ) -> bool { 
+        debug_assert!( x, "division by zero in GF2" );
+        x 
+    }
+}
+
+
+/// The two element field, with elements of type `bool`, which panics on division by zero.
+///
+/// This is the same as [`GF2`], except that [`divide`](DivisionRing::divide) and
+/// [`invert`](DivisionRing::invert) check for zero in release builds as well as debug builds.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use solar::rings::field_prime::GF2Checked;
+/// use solar::rings::ring::DivisionRing;
+///
+/// let field   =   GF2Checked::new();
+/// assert_eq!( field.try_divide( true, false ), None );
+/// field.divide( true, false );    // panics
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GF2Checked{}
+
+impl GF2Checked {
+    /// Create a new instance of `GF2Checked`.
+    pub fn new() -> GF2Checked { GF2Checked{} }
+}
+
+impl Semiring<bool> for GF2Checked 
+{
+    fn is_0( &self, x: bool ) -> bool { ! x }
+    fn is_1( &self, x: bool ) -> bool {   x }
+    fn zero() -> bool { false }
+    fn one()  -> bool { true  }
+
+    fn add( &self, x : bool, y : bool ) -> bool { x ^ y }
+    fn multiply( &self, x : bool, y: bool ) -> bool { x && y }
+}
+
+impl Ring<bool> for GF2Checked
+{
+    fn subtract( &self, x : bool, y: bool ) -> bool { x ^ y }
+    fn negate( &self, x : bool ) -> bool { x }
+}
+
+impl DivisionRing<bool> for GF2Checked
+{
+    /// Panics if `y` is zero.
+    fn divide( &self, x : bool, y: bool ) -> bool { 
+        assert!( y, "division by zero in GF2" );
+        x 
+    }
     
-    /// NOTE: THIS DIVISION IS UNSAFE; DESIGNERS MAY WANT TO ADD OPTION TO CHECK FOR DIVISION BY
-    /// ZERO
-    fn invert( &self, x : bool ) -> bool { x.clone() }
+    /// Panics if `x` is zero.
+    fn invert( &self, x : bool ) -> bool { 
+        assert!( x, "division by zero in GF2" );
+        x 
+    }
 }
 
 
 
 
+//  ---------------------------------------------------------
+//  PRIMALITY
+//  ---------------------------------------------------------
+
+/// `x * y mod m`, computed without overflow.
+fn multiply_mod( x: u64, y: u64, m: u64 ) -> u64 { ( ( x as u128 * y as u128 ) % m as u128 ) as u64 }
+
+/// `base ^ exponent mod m`, by repeated squaring.
+fn power_mod( mut base: u64, mut exponent: u64, m: u64 ) -> u64 {
+    let mut power   =   1 % m;
+    base            %=  m;
+    while exponent > 0 {
+        if exponent & 1 == 1 { power = multiply_mod( power, base, m ) }
+        base        =   multiply_mod( base, base, m );
+        exponent    >>= 1;
+    }
+    power
+}
+
+/// Returns `true` if `n` is prime.
+///
+/// Uses the Miller-Rabin test with the first twelve primes as witnesses, which is deterministic
+/// for every `n < 2^64`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::field_prime::is_prime;
+///
+/// assert!( is_prime( 2 ) && is_prime( 251 ) && is_prime( 1_000_000_007 ) );
+/// assert!( ! is_prime( 1 ) && ! is_prime( 561 ) );   // 561 = 3 * 11 * 17 is a Carmichael number
+/// ```
+pub fn is_prime( n: u64 ) -> bool {
+    const WITNESSES: [ u64; 12 ] = [ 2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37 ];
+    if n < 2 { return false }
+    for w in WITNESSES.iter() {
+        if n % *w == 0 { return n == *w }
+    }
+
+    // write n - 1 = d * 2^s, with d odd
+    let s           =   ( n - 1 ).trailing_zeros();
+    let d           =   ( n - 1 ) >> s;
+    'witness: for w in WITNESSES.iter() {
+        let mut x   =   power_mod( *w, d, n );
+        if x == 1 || x == n - 1 { continue }
+        for _ in 1 .. s {
+            x       =   multiply_mod( x, x, n );
+            if x == n - 1 { continue 'witness }
+        }
+        return false
+    }
+    true
+}
+
+
+
+
+//  ---------------------------------------------------------
+//  PRIME ORDER FIELDS
+//  ---------------------------------------------------------
+
+/// The largest modulus for which [`GFp::new`] precomputes lookup tables.
+pub const GFP_TABLE_THRESHOLD: u64 = 256;
+
+/// Precomputed multiplication and inverse tables for a small prime field.
+#[derive(Debug)]
+struct GFpTables {
+    /// Entry `x * p + y` holds `x * y mod p`.
+    multiply:   Vec< u8 >,
+    /// Entry `x` holds the multiplicative inverse of `x` (entry `0` holds `0`).
+    invert:     Vec< u8 >,
+}
+
+/// The field of integers modulo a prime `p`.
+///
+/// Elements are represented by integers `0 ..= p-1` of type `u64`; all inputs are assumed to
+/// lie in this range.  The constructors check that `p` is prime, using [`is_prime`].
+///
+/// When `p <= GFP_TABLE_THRESHOLD`, the constructor [`GFp::new`] precomputes tables for
+/// multiplication and inversion, so that the inner loop of a matrix reduction performs table
+/// lookups instead of modular arithmetic.  The tables are shared between clones of the ring
+/// object, so cloning remains cheap.
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::field_prime::GFp;
+/// use solar::rings::ring::{Semiring, Ring, DivisionRing};
+///
+/// let ring  =  GFp::new( 7 );
+///
+/// assert!( ring.uses_tables() );
+/// assert_eq!( 1, ring.add( 3, 5 ) );
+/// assert_eq!( 5, ring.subtract( 3, 5 ) );
+/// assert_eq!( 1, ring.multiply( 3, 5 ) );
+/// assert_eq!( 5, ring.invert( 3 ) );
+/// assert_eq!( 3, ring.divide( 1, 5 ) );
+///
+/// // larger moduli fall back to modular arithmetic
+/// let ring  =  GFp::new( 1_000_000_007 );
+/// assert!( ! ring.uses_tables() );
+/// assert_eq!( 1, ring.multiply( ring.invert( 12345 ), 12345 ) );
+/// ```
+#[derive(Debug, Clone)]
+pub struct GFp {
+    modulus:    u64,
+    tables:     Option< Arc< GFpTables > >,
+}
+
+impl GFp {
+    /// Create the field of order `p`; lookup tables are used if `p <= GFP_TABLE_THRESHOLD`.
+    ///
+    /// Panics if `p` is not prime.
+    pub fn new( p: u64 ) -> GFp {
+        if p <= GFP_TABLE_THRESHOLD { GFp::new_with_tables( p ) }
+        else                        { GFp::new_without_tables( p ) }
+    }
+
+    /// Create the field of order `p`, using modular arithmetic (no lookup tables).
+    ///
+    /// Panics if `p` is not prime.
+    pub fn new_without_tables( p: u64 ) -> GFp {
+        assert!( is_prime( p ), "the modulus of a prime field must be prime, but {} is not", p );
+        GFp{ modulus: p, tables: None }
+    }
+
+    /// Create the field of order `p`, using lookup tables for multiplication and inversion.
+    ///
+    /// Panics if `p` is not prime, or `p > GFP_TABLE_THRESHOLD`.
+    pub fn new_with_tables( p: u64 ) -> GFp {
+        assert!( p <= GFP_TABLE_THRESHOLD, "lookup tables are only available for moduli <= {}", GFP_TABLE_THRESHOLD );
+        let arithmetic  =   GFp::new_without_tables( p );
+        let multiply    =   ( 0 .. p * p ).map( |i| arithmetic.multiply( i / p, i % p ) as u8 ).collect();
+        let invert      =   ( 0 .. p ).map( |x| arithmetic.invert( x ) as u8 ).collect();
+        GFp{ modulus: p, tables: Some( Arc::new( GFpTables{ multiply, invert } ) ) }
+    }
+
+    /// The order of the field.
+    pub fn modulus( &self ) -> u64 { self.modulus }
+
+    /// Returns `true` if this ring object performs multiplication and inversion by table lookup.
+    pub fn uses_tables( &self ) -> bool { self.tables.is_some() }
+}
+
+impl Semiring<u64> for GFp
+{
+    fn is_0( &self, x: u64 ) -> bool { x == 0 }
+    fn is_1( &self, x: u64 ) -> bool { x == 1 }
+    fn zero() -> u64 { 0 }
+    fn one()  -> u64 { 1 }
+
+    fn add( &self, x : u64, y : u64 ) -> u64 {
+        // written so that no intermediate value exceeds the modulus, which may be above 2^63
+        if x >= self.modulus - y { x - ( self.modulus - y ) } else { x + y }
+    }
+    fn multiply( &self, x : u64, y: u64 ) -> u64 {
+        match &self.tables {
+            Some( tables )  =>  tables.multiply[ ( x * self.modulus + y ) as usize ] as u64,
+            None            =>  multiply_mod( x, y, self.modulus ),
+        }
+    }
+}
+
+impl Ring<u64> for GFp
+{
+    fn subtract( &self, x : u64, y: u64 ) -> u64 { self.add( x, self.negate( y ) ) }
+    fn negate( &self, x : u64 ) -> u64 { if x == 0 { 0 } else { self.modulus - x } }
+}
+
+impl DivisionRing<u64> for GFp
+{
+    /// NOTE: THIS DIVISION IS UNSAFE; DIVIDING BY ZERO RETURNS ZERO.  Use
+    /// [`try_divide`](DivisionRing::try_divide) to check for division by zero.
+    fn divide( &self, x : u64, y: u64 ) -> u64 { self.multiply( x, self.invert( y ) ) }
+
+    /// NOTE: THIS INVERSION IS UNSAFE; INVERTING ZERO RETURNS ZERO.  Use
+    /// [`try_invert`](DivisionRing::try_invert) to check for division by zero.
+    fn invert( &self, x : u64 ) -> u64 {
+        if let Some( tables ) = &self.tables { return tables.invert[ x as usize ] as u64 }
+
+        // extended Euclidean algorithm
+        let ( mut r0, mut r1 )  =   ( self.modulus as i128, x as i128 );
+        let ( mut t0, mut t1 )  =   ( 0i128, 1i128 );
+        while r1 != 0 {
+            let q   =   r0 / r1;
+            ( r0, r1 )  =   ( r1, r0 - q * r1 );
+            ( t0, t1 )  =   ( t1, t0 - q * t1 );
+        }
+        if r0 != 1 { return 0 } // x is zero
+        t0.rem_euclid( self.modulus as i128 ) as u64
+    }
+}
+
+
+
 
 
 #[cfg(test)]
@@ -90,6 +358,69 @@ mod tests {
 
     }
 
-}
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic( expected = "division by zero in GF2" )]
+    fn test_GF2_division_by_zero_panics_in_debug_builds() { GF2::new().divide( true, false ); }
+
+    #[test]
+    fn test_GF2Checked() {
+        let ring    =   GF2Checked::new();
+        for x in [ false, true ].iter().cloned() {
+            for y in [ false, true ].iter().cloned() {
+                assert_eq!( ring.add( x, y ), GF2::new().add( x, y ) );
+                assert_eq!( ring.multiply( x, y ), GF2::new().multiply( x, y ) );
+                assert_eq!( ring.try_divide( x, y ), GF2::new().try_divide( x, y ) );
+            }
+        }
+        assert!( std::panic::catch_unwind( || GF2Checked::new().invert( false ) ).is_err() );
+    }
+
+
+    #[test]
+    fn test_gfp_tables_agree_with_modular_arithmetic() {
+        for p in [ 2, 3, 5, 7, 251 ].iter().cloned() {
+            let table       =   GFp::new( p );
+            let arithmetic  =   GFp::new_without_tables( p );
+            assert!( table.uses_tables() );
+            for x in 0 .. p {
+                assert_eq!( table.invert( x ), arithmetic.invert( x ) );
+                if x != 0 { assert_eq!( table.multiply( x, table.invert( x ) ), 1 ) }
+                for y in 0 .. p {
+                    assert_eq!( table.multiply( x, y ), arithmetic.multiply( x, y ) );
+                    assert_eq!( table.add( table.subtract( x, y ), y ), x );
+                }
+            }
+        }
+    }
 
 
+
+    #[test]
+    fn test_is_prime_agrees_with_trial_division() {
+        let trial_division  =   | n: u64 | n >= 2 && ( 2 .. ).take_while( |d| d * d <= n ).all( |d| n % d != 0 );
+        for n in 0 .. 5000 { assert_eq!( is_prime( n ), trial_division( n ), "n = {}", n ) }
+
+        // strong pseudoprimes to small bases, and primes near 2^64
+        for n in [ 2047, 3215031751, 3825123056546413051 ].iter() { assert!( ! is_prime( *n ) ) }
+        assert!( is_prime( 18446744073709551557 ) );            // the largest prime below 2^64
+        assert!( ! is_prime( u64::MAX ) );
+    }
+
+    #[test]
+    fn test_gfp_addition_does_not_overflow_near_2_to_the_64() {
+        let p       =   18446744073709551557;                    // the largest prime below 2^64
+        let ring    =   GFp::new( p );
+        assert_eq!( ring.add( p - 1, p - 1 ), p - 2 );
+        assert_eq!( ring.add( p - 1, 1 ), 0 );
+        assert_eq!( ring.subtract( 0, p - 1 ), 1 );
+        assert_eq!( ring.subtract( 1, p - 1 ), 2 );
+        assert_eq!( ring.subtract( p - 2, p - 1 ), p - 1 );
+    }
+
+    #[test]
+    #[should_panic( expected = "must be prime" )]
+    fn test_gfp_rejects_composite_moduli() { GFp::new( 91 ); }
+
+}
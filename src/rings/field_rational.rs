@@ -0,0 +1,267 @@
+//! Objects representing the field of rational numbers.
+//!
+//! A [`NativeDivisionRing`](crate::rings::ring_native::NativeDivisionRing) over `Ratio<i64>`
+//! performs exact arithmetic only as long as numerators and denominators fit in an `i64`.
+//! Pivoting on a large matrix can produce much larger numbers, and then the arithmetic overflows:
+//! it panics in debug builds, and silently returns a wrong answer in release builds.
+//!
+//! This module provides two overflow-safe alternatives:
+//!
+//! - [`RationalField`], whose elements are arbitrary-precision rationals, [`BigRational`]; and
+//! - [`CheckedRationalField`], whose elements are [`CheckedRational`]s.  A `CheckedRational` is
+//!   stored as a `Ratio<i64>` when its numerator and denominator fit in an `i64`, and as a
+//!   `BigRational` otherwise; arithmetic on small values uses checked `i64` operations, and is
+//!   repeated with `BigRational`s only if these overflow.  This is much faster than `RationalField`
+//!   when most values are small, as is typical for boundary matrices.
+//!
+//! Both objects panic on division by zero.
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use num::rational::{BigRational, Ratio};
+use num::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, ToPrimitive, Zero};
+use num::BigInt;
+
+
+
+
+//  ---------------------------------------------------------
+//  ARBITRARY PRECISION RATIONALS
+//  ---------------------------------------------------------
+
+/// The field of rational numbers, with elements of type [`BigRational`].
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::field_rational::RationalField;
+/// use solar::rings::ring::{Semiring, DivisionRing};
+/// use num::BigRational;
+///
+/// let ring    =   RationalField::new();
+/// let big     =   BigRational::from_integer( i64::MAX.into() );
+///
+/// // no overflow
+/// let square  =   ring.multiply( big.clone(), big.clone() );
+/// assert_eq!( ring.divide( square, big.clone() ), big );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RationalField{}
+
+impl RationalField {
+    /// Create a new instance of `RationalField`.
+    pub fn new() -> RationalField { RationalField{} }
+}
+
+impl Semiring< BigRational > for RationalField
+{
+    fn is_0( &self, x: BigRational ) -> bool { x.is_zero() }
+    fn is_1( &self, x: BigRational ) -> bool { x.is_one() }
+    fn zero() -> BigRational { BigRational::zero() }
+    fn one()  -> BigRational { BigRational::one() }
+
+    fn add( &self, x : BigRational, y : BigRational ) -> BigRational { x + y }
+    fn multiply( &self, x : BigRational, y: BigRational ) -> BigRational { x * y }
+}
+
+impl Ring< BigRational > for RationalField
+{
+    fn subtract( &self, x : BigRational, y: BigRational ) -> BigRational { x - y }
+    fn negate( &self, x : BigRational ) -> BigRational { -x }
+}
+
+impl DivisionRing< BigRational > for RationalField
+{
+    /// Panics if `y` is zero.
+    fn divide( &self, x : BigRational, y: BigRational ) -> BigRational { x / y }
+
+    /// Panics if `x` is zero.
+    fn invert( &self, x : BigRational ) -> BigRational { x.recip() }
+}
+
+
+
+
+//  ---------------------------------------------------------
+//  RATIONALS THAT PROMOTE ON OVERFLOW
+//  ---------------------------------------------------------
+
+/// A rational number, stored as a `Ratio<i64>` if possible and as a [`BigRational`] otherwise.
+///
+/// The results of arithmetic in a [`CheckedRationalField`] are always stored as `Small` when they
+/// fit; values constructed by hand need not be, and compare equal regardless of representation.
+#[derive(Debug, Clone)]
+pub enum CheckedRational {
+    Small( Ratio< i64 > ),
+    Big( BigRational ),
+}
+
+impl CheckedRational {
+    /// Store `x` as `Small` if its numerator and denominator fit in an `i64`.
+    pub fn from_big( x: BigRational ) -> CheckedRational {
+        match ( x.numer().to_i64(), x.denom().to_i64() ) {
+            ( Some( numer ), Some( denom ) )    =>  CheckedRational::Small( Ratio::new_raw( numer, denom ) ),
+            _                                   =>  CheckedRational::Big( x ),
+        }
+    }
+
+    /// The value, as a [`BigRational`].
+    pub fn to_big( &self ) -> BigRational {
+        match self {
+            CheckedRational::Small( x )     =>  BigRational::new_raw( BigInt::from( *x.numer() ), BigInt::from( *x.denom() ) ),
+            CheckedRational::Big( x )       =>  x.clone(),
+        }
+    }
+
+    /// Returns `true` if the value is stored as a `Ratio<i64>`.
+    pub fn is_small( &self ) -> bool { matches!( self, CheckedRational::Small( _ ) ) }
+
+    /// Apply `small` if both values are small and it does not overflow; otherwise apply `big`.
+    fn combine< Small, Big >( self, other: Self, small: Small, big: Big ) -> Self
+        where   Small:  Fn( &Ratio< i64 >, &Ratio< i64 > ) -> Option< Ratio< i64 > >,
+                Big:    Fn( BigRational, BigRational ) -> BigRational,
+    {
+        if let ( CheckedRational::Small( x ), CheckedRational::Small( y ) ) = ( &self, &other ) {
+            if let Some( result ) = small( x, y ) { return CheckedRational::Small( result ) }
+        }
+        CheckedRational::from_big( big( self.to_big(), other.to_big() ) )
+    }
+}
+
+impl From< i64 > for CheckedRational {
+    fn from( x: i64 ) -> Self { CheckedRational::Small( Ratio::from_integer( x ) ) }
+}
+
+impl From< Ratio< i64 > > for CheckedRational {
+    fn from( x: Ratio< i64 > ) -> Self { CheckedRational::Small( x ) }
+}
+
+impl From< BigRational > for CheckedRational {
+    fn from( x: BigRational ) -> Self { CheckedRational::from_big( x ) }
+}
+
+impl PartialEq for CheckedRational {
+    fn eq( &self, other: &Self ) -> bool {
+        match ( self, other ) {
+            ( CheckedRational::Small( x ), CheckedRational::Small( y ) )    =>  x == y,
+            _                                                               =>  self.to_big() == other.to_big(),
+        }
+    }
+}
+
+impl Eq for CheckedRational {}
+
+
+/// The field of rational numbers, with elements of type [`CheckedRational`].
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::field_rational::{CheckedRational, CheckedRationalField};
+/// use solar::rings::ring::{Semiring, Ring, DivisionRing};
+///
+/// let ring    =   CheckedRationalField::new();
+/// let big     =   CheckedRational::from( i64::MAX );
+///
+/// // the square overflows an `i64`, so it is promoted ...
+/// let square  =   ring.multiply( big.clone(), big.clone() );
+/// assert!( ! square.is_small() );
+///
+/// // ... and results that fit are demoted again
+/// let quotient    =   ring.divide( square, big.clone() );
+/// assert!( quotient.is_small() );
+/// assert_eq!( quotient, big );
+/// assert!( ring.is_0( ring.subtract( quotient, big ) ) );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CheckedRationalField{}
+
+impl CheckedRationalField {
+    /// Create a new instance of `CheckedRationalField`.
+    pub fn new() -> CheckedRationalField { CheckedRationalField{} }
+}
+
+impl Semiring< CheckedRational > for CheckedRationalField
+{
+    fn is_0( &self, x: CheckedRational ) -> bool {
+        match x { CheckedRational::Small( x ) => x.is_zero(), CheckedRational::Big( x ) => x.is_zero() }
+    }
+    fn is_1( &self, x: CheckedRational ) -> bool {
+        match x { CheckedRational::Small( x ) => x.is_one(), CheckedRational::Big( x ) => x.is_one() }
+    }
+    fn zero() -> CheckedRational { CheckedRational::from( 0 ) }
+    fn one()  -> CheckedRational { CheckedRational::from( 1 ) }
+
+    fn add( &self, x : CheckedRational, y : CheckedRational ) -> CheckedRational {
+        x.combine( y, |x, y| x.checked_add( y ), |x, y| x + y )
+    }
+    fn multiply( &self, x : CheckedRational, y: CheckedRational ) -> CheckedRational {
+        x.combine( y, |x, y| x.checked_mul( y ), |x, y| x * y )
+    }
+}
+
+impl Ring< CheckedRational > for CheckedRationalField
+{
+    fn subtract( &self, x : CheckedRational, y: CheckedRational ) -> CheckedRational {
+        x.combine( y, |x, y| x.checked_sub( y ), |x, y| x - y )
+    }
+    fn negate( &self, x : CheckedRational ) -> CheckedRational {
+        match x {
+            // `-i64::MIN` overflows
+            CheckedRational::Small( x ) if *x.numer() != i64::MIN   =>  CheckedRational::Small( -x ),
+            x                                                       =>  CheckedRational::from_big( -x.to_big() ),
+        }
+    }
+}
+
+impl DivisionRing< CheckedRational > for CheckedRationalField
+{
+    /// Panics if `y` is zero.
+    fn divide( &self, x : CheckedRational, y: CheckedRational ) -> CheckedRational {
+        x.combine( y, |x, y| x.checked_div( y ), |x, y| x / y )
+    }
+
+    /// Panics if `x` is zero.
+    fn invert( &self, x : CheckedRational ) -> CheckedRational { self.divide( Self::one(), x ) }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+
+    #[test]
+    fn test_checked_rationals_agree_with_big_rationals() {
+        let checked     =   CheckedRationalField::new();
+        let exact       =   RationalField::new();
+        let values      =   [   Ratio::new( 0, 1 ), Ratio::new( 1, 1 ), Ratio::new( -3, 7 ),
+                                    Ratio::new( i64::MAX, 1 ), Ratio::new( i64::MIN, 1 ), Ratio::new( 1, i64::MAX ),
+                                    Ratio::new( i64::MAX - 1, i64::MAX ),  ];
+        for x in values.iter().cloned() {
+            let ( cx, bx )  =   ( CheckedRational::from( x ), CheckedRational::from( x ).to_big() );
+            assert_eq!( checked.negate( cx.clone() ).to_big(), exact.negate( bx.clone() ) );
+            for y in values.iter().cloned() {
+                let ( cy, by )  =   ( CheckedRational::from( y ), CheckedRational::from( y ).to_big() );
+                let results     =   [   ( checked.add( cx.clone(), cy.clone() ),        exact.add( bx.clone(), by.clone() ) ),
+                                            ( checked.subtract( cx.clone(), cy.clone() ),   exact.subtract( bx.clone(), by.clone() ) ),
+                                            ( checked.multiply( cx.clone(), cy.clone() ),   exact.multiply( bx.clone(), by.clone() ) ),   ];
+                for ( result, expected ) in results {
+                    assert_eq!( result.to_big(), expected );
+                    assert_eq!( result.is_small(), CheckedRational::from_big( expected ).is_small() );
+                }
+                if ! y.is_zero() {
+                    assert_eq!( checked.divide( cx.clone(), cy.clone() ).to_big(), exact.divide( bx.clone(), by.clone() ) );
+                }
+            }
+        }
+
+        // equality does not depend on the representation
+        assert_eq!( CheckedRational::Big( BigRational::from_integer( 2.into() ) ), CheckedRational::from( 2 ) );
+        assert!( checked.is_1( CheckedRational::Big( BigRational::one() ) ) );
+    }
+
+}
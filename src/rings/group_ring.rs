@@ -0,0 +1,345 @@
+//! Group rings `R[G]` of finite groups.
+//!
+//! The group ring `R[G]` has one basis element for each element `g` of a group `G`; elements of
+//! `R[G]` are finite sums `sum_g a_g g` with coefficients `a_g` in a ring `R`, and they are
+//! multiplied by extending the group operation linearly.  Chains on a space with an action of `G`
+//! form modules over `R[G]`, so group rings are the coefficient rings of equivariant homology
+//! computations over orbit complexes, with one cell per orbit.
+//!
+//! Groups are handled like rings: a *group object* implementing [`FiniteGroup`] performs the
+//! group operations on elements of some type.  This module provides group objects for the cyclic
+//! groups, [`CyclicGroup`], and the symmetric groups, [`SymmetricGroup`].  The
+//! [`LaurentPolynomialRing`](crate::rings::laurent_polynomial::LaurentPolynomialRing) is the
+//! group ring of the (infinite) group of integers.
+//!
+//! A [`GroupRing`] wraps a ring object for the coefficients and a group object, and performs
+//! arithmetic on [`GroupRingElement`]s.  Like a Laurent polynomial, a `GroupRingElement` is stored
+//! in canonical form, as a list of `(g, a_g)` pairs sorted in strictly ascending order of group
+//! element, with no zero coefficients.  Elements should therefore be constructed through the ring
+//! object, with [`GroupRing::element`] or [`GroupRing::basis_element`].
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::group_ring::{GroupRing, CyclicGroup};
+//! use solar::rings::ring_native::NativeRing;
+//! use solar::rings::ring::{Semiring, Ring};
+//!
+//! // the integral group ring of the cyclic group { 0, 1, 2 } of order 3
+//! let ring    =   GroupRing::new( NativeRing::<i64>::new(), CyclicGroup::new( 3 ) );
+//! let norm    =   ring.element( vec![ (0, 1), (1, 1), (2, 1) ] );     // 1 + g + g^2
+//! let g       =   ring.basis_element( 1 );
+//!
+//! // g permutes the terms of the norm element, so ( 1 - g ) * norm = 0
+//! assert_eq!( ring.multiply( g.clone(), norm.clone() ), norm );
+//! assert!( ring.is_0( ring.multiply( ring.subtract( GroupRing::< NativeRing<i64>, CyclicGroup >::one(), g ), norm.clone() ) ) );
+//! assert_eq!( ring.augmentation( norm ), 3 );
+//! ```
+
+use crate::rings::ring::{Semiring, Ring};
+use crate::vectors::operations::add_assign_sorted_with_buffer;
+
+
+//  ---------------------------------------------------------
+//  GROUPS
+//  ---------------------------------------------------------
+
+/// Group objects, which perform the operations of a finite group whose elements have type
+/// `Element`.
+pub trait FiniteGroup< Element > {
+
+    /// The identity element.
+    fn identity() -> Element;
+
+    /// The product `a b`.
+    fn operate( &self, a: Element, b: Element ) -> Element;
+
+    /// The inverse of `a`.
+    fn inverse( &self, a: Element ) -> Element;
+
+    /// All elements of the group, in ascending order.
+    fn elements( &self ) -> Vec< Element >;
+}
+
+/// The cyclic group `Z / nZ` of order `n`, with elements `0 .. n` and addition modulo `n` as
+/// group operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CyclicGroup {
+    order:  u64,
+}
+
+impl CyclicGroup {
+    /// The cyclic group of order `order`.
+    ///
+    /// Panics if `order` is zero.
+    pub fn new( order: u64 ) -> CyclicGroup {
+        assert!( order > 0, "a cyclic group must have positive order" );
+        CyclicGroup{ order }
+    }
+
+    /// The number of elements.
+    pub fn order( &self ) -> u64 { self.order }
+}
+
+impl FiniteGroup< u64 > for CyclicGroup {
+    fn identity() -> u64 { 0 }
+    fn operate( &self, a: u64, b: u64 ) -> u64 { ( ( a as u128 + b as u128 ) % self.order as u128 ) as u64 }
+    fn inverse( &self, a: u64 ) -> u64 { ( self.order - a ) % self.order }
+    fn elements( &self ) -> Vec< u64 > { ( 0 .. self.order ).collect() }
+}
+
+/// The symmetric group on `{ 0, .., N - 1 }`.
+///
+/// A permutation `p` is stored as the array `[ p(0), .., p(N-1) ]`, and the product `p q` is the
+/// composite `i -> p( q( i ) )`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SymmetricGroup< const N: usize > {}
+
+impl < const N: usize > SymmetricGroup< N > {
+    /// Create a new instance of `SymmetricGroup`.
+    pub fn new() -> Self { SymmetricGroup{} }
+}
+
+impl < const N: usize > FiniteGroup< [ usize; N ] > for SymmetricGroup< N > {
+    fn identity() -> [ usize; N ] { std::array::from_fn( |i| i ) }
+    fn operate( &self, a: [ usize; N ], b: [ usize; N ] ) -> [ usize; N ] { std::array::from_fn( |i| a[ b[ i ] ] ) }
+    fn inverse( &self, a: [ usize; N ] ) -> [ usize; N ] {
+        let mut inverse     =   [ 0; N ];
+        for ( i, &image ) in a.iter().enumerate() { inverse[ image ] = i }
+        inverse
+    }
+    fn elements( &self ) -> Vec< [ usize; N ] > {
+        // Heap's algorithm, followed by a sort
+        let mut elements    =   Vec::new();
+        let mut permutation =   Self::identity();
+        let mut counters    =   [ 0; N ];
+        elements.push( permutation );
+        let mut i           =   1;
+        while i < N {
+            if counters[ i ] < i {
+                if i % 2 == 0 { permutation.swap( 0, i ) } else { permutation.swap( counters[ i ], i ) }
+                elements.push( permutation );
+                counters[ i ] += 1;
+                i = 1;
+            } else {
+                counters[ i ] = 0;
+                i += 1;
+            }
+        }
+        elements.sort();
+        elements
+    }
+}
+
+
+//  ---------------------------------------------------------
+//  ELEMENTS OF GROUP RINGS
+//  ---------------------------------------------------------
+
+/// An element `sum_g a_g g` of a group ring, stored as a list of `(g, a_g)` pairs.
+///
+/// Elements built by a [`GroupRing`] have terms sorted in strictly ascending order of group
+/// element, and no zero coefficients; two such elements are equal iff their terms are.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GroupRingElement< GroupElement, Coeff > {
+    terms:      Vec< ( GroupElement, Coeff ) >,
+}
+
+impl < GroupElement: Ord, Coeff > GroupRingElement< GroupElement, Coeff > {
+    /// The `(g, a_g)` pairs of the nonzero terms, in ascending order of `g`.
+    pub fn terms( &self ) -> &[ ( GroupElement, Coeff ) ] { &self.terms }
+
+    /// Consume the element, returning its terms.
+    pub fn into_terms( self ) -> Vec< ( GroupElement, Coeff ) > { self.terms }
+
+    /// The coefficient of `g`, or `None` if it is zero.
+    pub fn coefficient( &self, g: &GroupElement ) -> Option< &Coeff > {
+        self.terms.binary_search_by( |term| term.0.cmp( g ) ).ok().map( |position| &self.terms[ position ].1 )
+    }
+}
+
+
+//  ---------------------------------------------------------
+//  THE RING OBJECT
+//  ---------------------------------------------------------
+
+/// The group ring of the group represented by `Group`, with coefficients in the ring represented
+/// by `CoeffRing`.
+///
+/// Addition and subtraction take time linear in the number of terms; multiplication of elements
+/// with `m` and `n` terms takes time `O( m n log n + m^2 n )` in the worst case.
+#[derive(Clone, Debug)]
+pub struct GroupRing< CoeffRing, Group > {
+    coefficient_ring:   CoeffRing,
+    group:              Group,
+}
+
+impl < CoeffRing, Group > GroupRing< CoeffRing, Group > {
+    /// The group ring of `group`, with coefficients in `coefficient_ring`.
+    pub fn new( coefficient_ring: CoeffRing, group: Group ) -> Self { GroupRing{ coefficient_ring, group } }
+
+    /// The ring object for the coefficients.
+    pub fn coefficient_ring( &self ) -> &CoeffRing { &self.coefficient_ring }
+
+    /// The group object.
+    pub fn group( &self ) -> &Group { &self.group }
+
+    /// The element `sum_g a_g g`, given the `(g, a_g)` pairs in any order.
+    ///
+    /// Terms with equal group elements are added, and zero coefficients are dropped.
+    pub fn element< GroupElement, Coeff, I >( &self, terms: I ) -> GroupRingElement< GroupElement, Coeff >
+        where   CoeffRing:      Semiring< Coeff >,
+                GroupElement:   Ord,
+                Coeff:          Clone,
+                I:              IntoIterator< Item = ( GroupElement, Coeff ) >,
+    {
+        let mut sorted: Vec< _ >    =   terms.into_iter().collect();
+        sorted.sort_by( |a, b| a.0.cmp( &b.0 ) );
+        let mut merged: Vec< ( GroupElement, Coeff ) >  =   Vec::with_capacity( sorted.len() );
+        for ( g, coeff ) in sorted {
+            match merged.last_mut() {
+                Some( last ) if last.0 == g     =>  { last.1 = self.coefficient_ring.add( last.1.clone(), coeff ) }
+                _                               =>  { merged.push( ( g, coeff ) ) }
+            }
+        }
+        merged.retain( |term| ! self.coefficient_ring.is_0( term.1.clone() ) );
+        GroupRingElement{ terms: merged }
+    }
+
+    /// The basis element `1 g`.
+    pub fn basis_element< GroupElement, Coeff >( &self, g: GroupElement ) -> GroupRingElement< GroupElement, Coeff >
+        where   CoeffRing:      Semiring< Coeff >,
+    {
+        GroupRingElement{ terms: vec![ ( g, CoeffRing::one() ) ] }
+    }
+
+    /// The augmentation `sum_g a_g` of `x = sum_g a_g g`, i.e. the image of `x` under the ring
+    /// homomorphism `R[G] -> R` that sends every group element to one.
+    pub fn augmentation< GroupElement, Coeff >( &self, x: GroupRingElement< GroupElement, Coeff > ) -> Coeff
+        where   CoeffRing:      Semiring< Coeff >,
+    {
+        self.coefficient_ring.sum_iter( x.terms.into_iter().map( |term| term.1 ) )
+    }
+
+    /// The element `sum_g a_g g^{-1}`, for `x = sum_g a_g g`.
+    ///
+    /// This reverses the order of products, and is used to form adjoints of equivariant maps.
+    pub fn involution< GroupElement, Coeff >( &self, x: GroupRingElement< GroupElement, Coeff > ) -> GroupRingElement< GroupElement, Coeff >
+        where   Group:          FiniteGroup< GroupElement >,
+                GroupElement:   Ord,
+    {
+        let mut terms: Vec< _ >     =   x.terms.into_iter().map( |( g, coeff )| ( self.group.inverse( g ), coeff ) ).collect();
+        terms.sort_by( |a, b| a.0.cmp( &b.0 ) );
+        GroupRingElement{ terms }
+    }
+}
+
+impl < CoeffRing, Group, GroupElement, Coeff > Semiring< GroupRingElement< GroupElement, Coeff > > for GroupRing< CoeffRing, Group >
+    where   CoeffRing:      Semiring< Coeff >,
+            Group:          FiniteGroup< GroupElement >,
+            GroupElement:   Ord + Clone,
+            Coeff:          Clone,
+{
+    fn is_0( &self, x: GroupRingElement< GroupElement, Coeff > ) -> bool { x.terms.is_empty() }
+    fn is_1( &self, x: GroupRingElement< GroupElement, Coeff > ) -> bool {
+        x.terms.len() == 1 && x.terms[0].0 == Group::identity() && self.coefficient_ring.is_1( x.terms[0].1.clone() )
+    }
+    fn zero() -> GroupRingElement< GroupElement, Coeff > { GroupRingElement{ terms: Vec::new() } }
+    fn one()  -> GroupRingElement< GroupElement, Coeff > { GroupRingElement{ terms: vec![ ( Group::identity(), CoeffRing::one() ) ] } }
+
+    fn add( &self, x: GroupRingElement< GroupElement, Coeff >, y: GroupRingElement< GroupElement, Coeff > ) -> GroupRingElement< GroupElement, Coeff > {
+        let mut sum     =   x.terms;
+        add_assign_sorted_with_buffer( &mut sum, &y.terms, CoeffRing::one(), &self.coefficient_ring, &mut Vec::new() );
+        GroupRingElement{ terms: sum }
+    }
+
+    fn multiply( &self, x: GroupRingElement< GroupElement, Coeff >, y: GroupRingElement< GroupElement, Coeff > ) -> GroupRingElement< GroupElement, Coeff > {
+        // for each term a g of x, add ( a g ) y; left multiplication by g permutes the terms of y,
+        // so they must be sorted again
+        let mut product     =   Vec::new();
+        let mut translated  =   Vec::with_capacity( y.terms.len() );
+        let mut buffer      =   Vec::new();
+        for ( g, coeff ) in x.terms {
+            translated.clear();
+            translated.extend( y.terms.iter().map( |term| ( self.group.operate( g.clone(), term.0.clone() ), self.coefficient_ring.multiply( coeff.clone(), term.1.clone() ) ) ) );
+            translated.sort_by( |a, b| a.0.cmp( &b.0 ) );
+            add_assign_sorted_with_buffer( &mut product, &translated, CoeffRing::one(), &self.coefficient_ring, &mut buffer );
+        }
+        GroupRingElement{ terms: product }
+    }
+}
+
+impl < CoeffRing, Group, GroupElement, Coeff > Ring< GroupRingElement< GroupElement, Coeff > > for GroupRing< CoeffRing, Group >
+    where   CoeffRing:      Ring< Coeff >,
+            Group:          FiniteGroup< GroupElement >,
+            GroupElement:   Ord + Clone,
+            Coeff:          Clone,
+{
+    fn subtract( &self, x: GroupRingElement< GroupElement, Coeff >, y: GroupRingElement< GroupElement, Coeff > ) -> GroupRingElement< GroupElement, Coeff > {
+        let mut difference  =   x.terms;
+        let minus_one       =   self.coefficient_ring.negate( CoeffRing::one() );
+        add_assign_sorted_with_buffer( &mut difference, &y.terms, minus_one, &self.coefficient_ring, &mut Vec::new() );
+        GroupRingElement{ terms: difference }
+    }
+
+    fn negate( &self, x: GroupRingElement< GroupElement, Coeff > ) -> GroupRingElement< GroupElement, Coeff > {
+        let terms   =   x.terms.into_iter().map( |( g, coeff )| ( g, self.coefficient_ring.negate( coeff ) ) ).collect();
+        GroupRingElement{ terms }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::axioms::assert_ring_axioms;
+    use crate::rings::field_prime::GFp;
+    use crate::rings::ring_native::NativeRing;
+
+
+    #[test]
+    fn test_groups() {
+        let group       =   CyclicGroup::new( 5 );
+        assert_eq!( group.operate( 3, 4 ), 2 );
+        assert!( group.elements().into_iter().all( |g| group.operate( g, group.inverse( g ) ) == 0 ) );
+
+        let group       =   SymmetricGroup::< 4 >::new();
+        let elements    =   group.elements();
+        assert_eq!( elements.len(), 24 );
+        assert!( elements.windows( 2 ).all( |pair| pair[0] < pair[1] ) );
+        assert!( elements.iter().all( |&p| group.operate( p, group.inverse( p ) ) == SymmetricGroup::< 4 >::identity() ) );
+        // composition is applied right to left
+        assert_eq!( group.operate( [ 1, 0, 2, 3 ], [ 0, 2, 1, 3 ] ), [ 1, 2, 0, 3 ] );
+    }
+
+    #[test]
+    fn test_group_ring() {
+        // a noncommutative group ring
+        let ring        =   GroupRing::new( NativeRing::< i64 >::new(), SymmetricGroup::< 3 >::new() );
+        let swap_01     =   ring.basis_element( [ 1, 0, 2 ] );
+        let swap_12     =   ring.basis_element( [ 0, 2, 1 ] );
+        assert_ne!( ring.multiply( swap_01.clone(), swap_12.clone() ), ring.multiply( swap_12.clone(), swap_01.clone() ) );
+        assert!( ring.is_1( ring.multiply( swap_01.clone(), swap_01.clone() ) ) );
+
+        let x           =   ring.element( vec![ ( [ 1, 0, 2 ], 2 ), ( [ 2, 0, 1 ], -1 ), ( [ 1, 0, 2 ], 1 ), ( [ 0, 1, 2 ], 0 ) ] );
+        assert_eq!( x.terms(), &[ ( [ 1, 0, 2 ], 3 ), ( [ 2, 0, 1 ], -1 ) ] );
+        assert_eq!( x.coefficient( &[ 2, 0, 1 ] ), Some( &-1 ) );
+        assert_eq!( ring.involution( x.clone() ).terms(), &[ ( [ 1, 0, 2 ], 3 ), ( [ 1, 2, 0 ], -1 ) ] );
+
+        // the augmentation is a ring homomorphism, and the involution reverses products
+        let y           =   ring.element( vec![ ( [ 0, 2, 1 ], 4 ), ( [ 1, 2, 0 ], 1 ), ( [ 0, 1, 2 ], -2 ) ] );
+        assert_eq!( ring.augmentation( ring.multiply( x.clone(), y.clone() ) ), ring.augmentation( x.clone() ) * ring.augmentation( y.clone() ) );
+        assert_eq!( ring.involution( ring.multiply( x.clone(), y.clone() ) ), ring.multiply( ring.involution( y.clone() ), ring.involution( x.clone() ) ) );
+        assert_ring_axioms( ring.clone(), vec![ x, y, swap_01, swap_12, GroupRing::< NativeRing< i64 >, SymmetricGroup< 3 > >::zero() ] );
+
+        // GF(3)[C_3], in which ( 1 - g )^3 = 1 - g^3 = 0
+        let ring        =   GroupRing::new( GFp::new( 3 ), CyclicGroup::new( 3 ) );
+        let x           =   ring.element( vec![ ( 0, 1 ), ( 1, 2 ) ] );
+        assert!( ! ring.is_0( ring.power( x.clone(), 2 ) ) && ring.is_0( ring.power( x, 3 ) ) );
+    }
+
+}
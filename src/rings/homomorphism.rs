@@ -0,0 +1,213 @@
+//! Ring homomorphisms, for changing the coefficients of vectors and matrices.
+//!
+//! A [`RingHomomorphism<Source, Target>`] sends elements of one ring to elements of another,
+//! respecting addition and multiplication.  Homomorphisms change coefficients lazily:
+//!
+//! - a sparse vector iterator can be pushed through a homomorphism with
+//!   [`Transforms::change_coefficients`](crate::vectors::vector_transforms::Transforms::change_coefficients), and
+//! - a matrix oracle can be pushed through a homomorphism with
+//!   [`MapCoefficients`](crate::matrices::implementors::map_coefficients::MapCoefficients).
+//!
+//! Every closure `Fn( Source ) -> Target` is a `RingHomomorphism< Source, Target >`; it is up to
+//! the user to check that the closure respects the ring operations.  This module also provides
+//! the homomorphisms needed most often:
+//!
+//! | homomorphism          | from                                                   | to                      |
+//! |-----------------------|--------------------------------------------------------|-------------------------|
+//! | [`ReduceModPrime`]    | `i64`, `u64`                                           | `u64` (as used by [`GFp`](crate::rings::field_prime::GFp)) |
+//! | [`ReduceModTwo`]      | `i64`, `u64`                                           | `bool` (as used by [`GF2`](crate::rings::field_prime::GF2)) |
+//! | [`IntegerToRational`] | `i64`                                                  | `Ratio<i64>`, `BigRational`, `CheckedRational` |
+//! | [`RationalToFloat`]   | `Ratio<i64>`, `BigRational`, `CheckedRational`         | `f64`                   |
+//!
+//! Strictly speaking `RationalToFloat` is not a homomorphism, since floating point arithmetic
+//! rounds; it respects the ring operations up to rounding error.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::homomorphism::{RingHomomorphism, ReduceModPrime};
+//! use solar::vectors::vector_transforms::Transforms;
+//!
+//! let mod_three   =   ReduceModPrime::new( 3 );
+//! assert_eq!( mod_three.apply( -1i64 ), 2 );
+//!
+//! // push a sparse vector through the homomorphism
+//! let vector      =   vec![ (0, 4i64), (2, -3), (5, -4) ];
+//! let reduced: Vec< _ >   =   vector.into_iter().change_coefficients( mod_three ).collect();
+//! assert_eq!( reduced, vec![ (0, 1), (2, 0), (5, 2) ] );
+//! ```
+
+use crate::rings::field_prime::is_prime;
+use crate::rings::field_rational::CheckedRational;
+use num::rational::{BigRational, Ratio};
+use num::traits::ToPrimitive;
+use num::BigInt;
+
+
+//  ---------------------------------------------------------
+//  THE TRAIT
+//  ---------------------------------------------------------
+
+/// A function from the ring of elements of type `Source` to the ring of elements of type `Target`
+/// that respects addition and multiplication, and sends one to one.
+pub trait RingHomomorphism< Source, Target > {
+    /// The image of `x`.
+    fn apply( &self, x: Source ) -> Target;
+}
+
+impl < F, Source, Target > RingHomomorphism< Source, Target > for F
+    where   F:  Fn( Source ) -> Target,
+{
+    fn apply( &self, x: Source ) -> Target { self( x ) }
+}
+
+
+//  ---------------------------------------------------------
+//  REDUCTION MODULO A PRIME
+//  ---------------------------------------------------------
+
+/// Reduction of integers modulo a prime `p`, with values in `{ 0, .., p - 1 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReduceModPrime {
+    modulus:    u64,
+}
+
+impl ReduceModPrime {
+    /// Reduction modulo `p`.
+    ///
+    /// Panics if `p` is not prime.
+    pub fn new( p: u64 ) -> ReduceModPrime {
+        assert!( is_prime( p ), "the modulus of a prime field must be prime, but {} is not", p );
+        ReduceModPrime{ modulus: p }
+    }
+
+    /// The prime `p`.
+    pub fn modulus( &self ) -> u64 { self.modulus }
+}
+
+impl RingHomomorphism< i64, u64 > for ReduceModPrime {
+    fn apply( &self, x: i64 ) -> u64 { ( x as i128 ).rem_euclid( self.modulus as i128 ) as u64 }
+}
+
+impl RingHomomorphism< u64, u64 > for ReduceModPrime {
+    fn apply( &self, x: u64 ) -> u64 { x % self.modulus }
+}
+
+
+/// Reduction of integers modulo two, with values `false` (even) and `true` (odd).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReduceModTwo{}
+
+impl ReduceModTwo {
+    /// Create a new instance of `ReduceModTwo`.
+    pub fn new() -> ReduceModTwo { ReduceModTwo{} }
+}
+
+impl RingHomomorphism< i64, bool > for ReduceModTwo {
+    fn apply( &self, x: i64 ) -> bool { x % 2 != 0 }
+}
+
+impl RingHomomorphism< u64, bool > for ReduceModTwo {
+    fn apply( &self, x: u64 ) -> bool { x % 2 != 0 }
+}
+
+
+//  ---------------------------------------------------------
+//  INTEGERS, RATIONALS, AND FLOATS
+//  ---------------------------------------------------------
+
+/// The inclusion of the integers in the rationals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntegerToRational{}
+
+impl IntegerToRational {
+    /// Create a new instance of `IntegerToRational`.
+    pub fn new() -> IntegerToRational { IntegerToRational{} }
+}
+
+impl RingHomomorphism< i64, Ratio< i64 > > for IntegerToRational {
+    fn apply( &self, x: i64 ) -> Ratio< i64 > { Ratio::from_integer( x ) }
+}
+
+impl RingHomomorphism< i64, BigRational > for IntegerToRational {
+    fn apply( &self, x: i64 ) -> BigRational { BigRational::from_integer( BigInt::from( x ) ) }
+}
+
+impl RingHomomorphism< i64, CheckedRational > for IntegerToRational {
+    fn apply( &self, x: i64 ) -> CheckedRational { CheckedRational::from( x ) }
+}
+
+
+/// The nearest floating point number to a rational number.
+///
+/// Rationals too large to represent as an `f64` are sent to `±infinity`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RationalToFloat{}
+
+impl RationalToFloat {
+    /// Create a new instance of `RationalToFloat`.
+    pub fn new() -> RationalToFloat { RationalToFloat{} }
+}
+
+impl RingHomomorphism< Ratio< i64 >, f64 > for RationalToFloat {
+    fn apply( &self, x: Ratio< i64 > ) -> f64 { x.to_f64().unwrap() }
+}
+
+impl RingHomomorphism< BigRational, f64 > for RationalToFloat {
+    fn apply( &self, x: BigRational ) -> f64 { x.to_f64().unwrap() }
+}
+
+impl RingHomomorphism< CheckedRational, f64 > for RationalToFloat {
+    fn apply( &self, x: CheckedRational ) -> f64 {
+        match x {
+            CheckedRational::Small( x )     =>  self.apply( x ),
+            CheckedRational::Big( x )       =>  self.apply( x ),
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GFp;
+    use crate::rings::ring::Semiring;
+
+
+    #[test]
+    fn test_homomorphisms_respect_ring_operations() {
+        let field       =   GFp::new( 7 );
+        let mod_seven   =   ReduceModPrime::new( 7 );
+        let values      =   [ i64::MIN, -15, -7, -1, 0, 1, 6, 9, 1 << 40 ];
+        for x in values.iter().cloned() {
+            let image: u64  =   mod_seven.apply( x );
+            assert_eq!( image as i128, ( x as i128 ).rem_euclid( 7 ) );
+            assert_eq!( ReduceModTwo::new().apply( x ), x.rem_euclid( 2 ) == 1 );
+            for y in values.iter().cloned().filter( |y| y.checked_add( x ).is_some() && y.checked_mul( x ).is_some() ) {
+                assert_eq!( mod_seven.apply( x + y ), field.add( mod_seven.apply( x ), mod_seven.apply( y ) ) );
+                assert_eq!( mod_seven.apply( x * y ), field.multiply( mod_seven.apply( x ), mod_seven.apply( y ) ) );
+            }
+        }
+        assert_eq!( mod_seven.apply( 15u64 ), 1 );
+
+        // integers to rationals to floats
+        let rational: Ratio< i64 >  =   IntegerToRational::new().apply( -3 );
+        let checked: CheckedRational    =   IntegerToRational::new().apply( -3 );
+        let big: BigRational        =   IntegerToRational::new().apply( -3 );
+        assert_eq!( RationalToFloat::new().apply( rational / 4 ), -0.75 );
+        assert_eq!( RationalToFloat::new().apply( checked ), -3. );
+        assert_eq!( RationalToFloat::new().apply( big ), -3. );
+
+        // closures are homomorphisms
+        assert_eq!( ( |x: i64| 2 * x ).apply( 3 ), 6 );
+    }
+
+    #[test]
+    #[should_panic( expected = "must be prime" )]
+    fn test_reduction_modulo_a_composite_panics() { ReduceModPrime::new( 6 ); }
+
+}
@@ -0,0 +1,377 @@
+//! Interval arithmetic, for certified computations with floating point data.
+//!
+//! An [`Interval`] `[lo, hi]` stands for an unknown real number known to lie between `lo` and
+//! `hi`.  An [`IntervalRing`] performs arithmetic on intervals with *outward rounding*: the result
+//! of every operation contains every result of the same operation on real numbers in the
+//! inputs.  Rounding errors therefore cannot go unnoticed: they widen the intervals instead.
+//!
+//! The question "is this coefficient zero?" has two answers for an interval: it is *certainly*
+//! zero if it is `[0, 0]`, and *possibly* zero if it contains zero.  An `IntervalRing` answers
+//! [`is_0`](Semiring::is_0) according to its [`ZeroTest`]:
+//!
+//! - with [`ZeroTest::Certain`] (the default), only `[0, 0]` is zero, so
+//!   [`drop_zeros`](crate::vectors::vector_transforms::Transforms::drop_zeros) never drops an
+//!   entry that might be nonzero, and a reduction may pivot on an interval that contains zero
+//!   (dividing by it gives [`Interval::entire`]);
+//! - with [`ZeroTest::Possible`], every interval that contains zero is zero, so
+//!   [`try_divide`](DivisionRing::try_divide) (and the reductions built on it) refuse to pivot on
+//!   an entry that might be zero, and entries that might be zero are dropped.
+//!
+//! Under either test, the
+//! [reductions](crate::matrix_factorization::vec_of_vec::try_right_reduce_by_order_observed)
+//! remove each entry they clear, although `x - (x / p) * p` is usually a small interval around
+//! zero rather than `[0, 0]`.
+//!
+//! Interval arithmetic is not quite a ring (`x - x` is only `[0, 0]` when `x` is a point, and
+//! multiplication is only subdistributive), so the [ring axioms](crate::rings::axioms) hold only
+//! up to enclosure.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::interval::{Interval, IntervalRing};
+//! use solar::rings::ring::{Semiring, Ring};
+//!
+//! let ring    =   IntervalRing::new();
+//! let tenth   =   Interval::point( 0.1 );
+//!
+//! // ten copies of 0.1 (which is not exactly 1/10) add up to approximately, but not exactly, 1 ...
+//! let sum     =   ring.sum_iter( vec![ tenth; 10 ] );
+//! assert!( sum.contains( 1. ) && sum.width() > 0. );
+//!
+//! // ... so the difference is possibly, but not certainly, zero
+//! let difference  =   ring.subtract( sum, IntervalRing::one() );
+//! assert!( difference.possibly_zero() && ! difference.is_certainly_zero() );
+//! assert!( ! ring.is_0( difference ) );
+//! ```
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing};
+
+
+//  ---------------------------------------------------------
+//  ROUNDING
+//  ---------------------------------------------------------
+
+/// Below this magnitude, the rounding error of a product may not be representable.
+const EXACT_ERROR_THRESHOLD: f64 = f64::MIN_POSITIVE * 9007199254740992.; // 2^53
+
+/// Round a floating point result `value` down (if `round_up` is false) or up, given the sign of
+/// the difference between the exact result and `value`; a `NaN` error is treated as unknown.
+fn round( value: f64, error: f64, round_up: bool ) -> f64 {
+    let ( below, above )    =   match error.partial_cmp( &0. ) {
+        Some( std::cmp::Ordering::Equal )   =>  ( false, false ),   // exact
+        Some( std::cmp::Ordering::Less )    =>  ( true, false ),    // exact result lies below `value`
+        Some( std::cmp::Ordering::Greater ) =>  ( false, true ),    // exact result lies above `value`
+        None                                =>  ( true, true ),
+    };
+    // an infinite result may come from overflow, in which case the exact result is finite; the
+    // largest finite value of the appropriate sign is then the tightest bound on the inner side
+    if value == f64::INFINITY       { return if round_up { value } else { f64::MAX } }
+    if value == f64::NEG_INFINITY   { return if round_up { f64::MIN } else { value } }
+    if value.is_nan() { return value }
+    if round_up { if above { next_up( value ) } else { value } }
+    else { if below { next_down( value ) } else { value } }
+}
+
+/// The least float greater than the finite float `x` (the same as `f64::next_up`, which requires
+/// a newer compiler than this crate supports).
+fn next_up( x: f64 ) -> f64 {
+    if x == 0. { return f64::from_bits( 1 ) }              // the least positive subnormal, for 0 and -0
+    let bits    =   x.to_bits();
+    if x > 0. { f64::from_bits( bits + 1 ) } else { f64::from_bits( bits - 1 ) }
+}
+
+/// The greatest float less than the finite float `x`.
+fn next_down( x: f64 ) -> f64 { - next_up( - x ) }
+
+/// `a + b`, rounded down or up.
+fn add_rounded( a: f64, b: f64, round_up: bool ) -> f64 {
+    // the TwoSum algorithm computes the rounding error exactly
+    let sum     =   a + b;
+    let b_part  =   sum - a;
+    let error   =   ( a - ( sum - b_part ) ) + ( b - b_part );
+    round( sum, error, round_up )
+}
+
+/// `a * b`, rounded down or up; `0 * inf` is taken to be `0`.
+fn multiply_rounded( a: f64, b: f64, round_up: bool ) -> f64 {
+    if a == 0. || b == 0. { return 0. }
+    let product =   a * b;
+    // a fused multiply-add computes the rounding error exactly, unless the product is tiny
+    let error   =   if product.abs() >= EXACT_ERROR_THRESHOLD { a.mul_add( b, - product ) } else { f64::NAN };
+    round( product, error, round_up )
+}
+
+/// `a / b`, rounded down or up, for nonzero `b`.
+fn divide_rounded( a: f64, b: f64, round_up: bool ) -> f64 {
+    if a == 0. { return 0. }
+    let quotient    =   a / b;
+    // `a - quotient * b` is computed exactly by a fused multiply-add, unless the quotient is tiny;
+    // the exact quotient exceeds `quotient` iff this residual has the sign of `b`
+    let error       =   if quotient.abs() >= EXACT_ERROR_THRESHOLD && b.is_finite() { ( - quotient ).mul_add( b, a ) * b.signum() } else { f64::NAN };
+    round( quotient, error, round_up )
+}
+
+
+//  ---------------------------------------------------------
+//  INTERVALS
+//  ---------------------------------------------------------
+
+/// A closed interval `[lo, hi]` of real numbers, with floating point endpoints.
+///
+/// Endpoints may be infinite, but not `NaN`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    lo:     f64,
+    hi:     f64,
+}
+
+impl Interval {
+    /// The interval `[lo, hi]`.
+    ///
+    /// Panics if `lo > hi`, or if either endpoint is `NaN`.
+    pub fn new( lo: f64, hi: f64 ) -> Interval {
+        assert!( lo <= hi, "the endpoints of an interval must satisfy lo <= hi, but [{}, {}] does not", lo, hi );
+        Interval{ lo, hi }
+    }
+
+    /// The interval `[x, x]`.
+    pub fn point( x: f64 ) -> Interval { Interval::new( x, x ) }
+
+    /// The interval `[-inf, inf]`, about which nothing is known.
+    pub fn entire() -> Interval { Interval{ lo: f64::NEG_INFINITY, hi: f64::INFINITY } }
+
+    /// The lower endpoint.
+    pub fn lo( &self ) -> f64 { self.lo }
+
+    /// The upper endpoint.
+    pub fn hi( &self ) -> f64 { self.hi }
+
+    /// The width `hi - lo` (rounded up).
+    pub fn width( &self ) -> f64 { add_rounded( self.hi, - self.lo, true ) }
+
+    /// The midpoint of the interval (rounded to nearest).
+    pub fn midpoint( &self ) -> f64 { self.lo / 2. + self.hi / 2. }
+
+    /// Returns `true` if `x` lies in the interval.
+    pub fn contains( &self, x: f64 ) -> bool { self.lo <= x && x <= self.hi }
+
+    /// Returns `true` if the interval is `[0, 0]`.
+    pub fn is_certainly_zero( &self ) -> bool { self.lo == 0. && self.hi == 0. }
+
+    /// Returns `true` if the interval contains zero.
+    pub fn possibly_zero( &self ) -> bool { self.contains( 0. ) }
+}
+
+impl From< f64 > for Interval {
+    fn from( x: f64 ) -> Interval { Interval::point( x ) }
+}
+
+
+//  ---------------------------------------------------------
+//  THE RING OBJECT
+//  ---------------------------------------------------------
+
+/// Which intervals an [`IntervalRing`] regards as zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZeroTest {
+    /// Only `[0, 0]` is zero.
+    Certain,
+    /// Every interval that contains zero is zero.
+    Possible,
+}
+
+/// Outward-rounded arithmetic on [`Interval`]s.
+///
+/// Division by an interval that contains zero returns [`Interval::entire`].
+///
+/// # Examples
+///
+/// ```
+/// use solar::rings::interval::{Interval, IntervalRing, ZeroTest};
+/// use solar::rings::ring::DivisionRing;
+/// use solar::vectors::vector_transforms::Transforms;
+///
+/// let entries     =   vec![ (0, Interval::new( -1e-12, 1e-12 )), (1, Interval::point( 0. )), (2, Interval::point( 3. )) ];
+///
+/// // only certain zeros are dropped ...
+/// let certain     =   IntervalRing::new();
+/// assert_eq!( entries.iter().cloned().drop_zeros( certain ).count(), 2 );
+///
+/// // ... unless possible zeros are treated as zeros, in which case they cannot be pivots
+/// let possible    =   IntervalRing::with_zero_test( ZeroTest::Possible );
+/// assert_eq!( entries.iter().cloned().drop_zeros( possible.clone() ).count(), 1 );
+/// assert_eq!( possible.try_invert( entries[0].1 ), None );
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntervalRing {
+    zero_test:  ZeroTest,
+}
+
+impl IntervalRing {
+    /// An interval ring that regards only `[0, 0]` as zero.
+    pub fn new() -> IntervalRing { IntervalRing{ zero_test: ZeroTest::Certain } }
+
+    /// An interval ring that regards intervals as zero according to `zero_test`.
+    pub fn with_zero_test( zero_test: ZeroTest ) -> IntervalRing { IntervalRing{ zero_test } }
+
+    /// The test used by [`is_0`](Semiring::is_0).
+    pub fn zero_test( &self ) -> ZeroTest { self.zero_test }
+}
+
+impl Default for IntervalRing {
+    fn default() -> Self { IntervalRing::new() }
+}
+
+impl Semiring< Interval > for IntervalRing
+{
+    fn is_0( &self, x: Interval ) -> bool {
+        match self.zero_test {
+            ZeroTest::Certain   =>  x.is_certainly_zero(),
+            ZeroTest::Possible  =>  x.possibly_zero(),
+        }
+    }
+    /// Only `[1, 1]` is one, regardless of the zero test.
+    fn is_1( &self, x: Interval ) -> bool { x.lo == 1. && x.hi == 1. }
+    fn zero() -> Interval { Interval::point( 0. ) }
+    fn one()  -> Interval { Interval::point( 1. ) }
+
+    fn add( &self, x: Interval, y: Interval ) -> Interval {
+        Interval{ lo: add_rounded( x.lo, y.lo, false ), hi: add_rounded( x.hi, y.hi, true ) }
+    }
+
+    fn multiply( &self, x: Interval, y: Interval ) -> Interval {
+        let pairs   =   [ ( x.lo, y.lo ), ( x.lo, y.hi ), ( x.hi, y.lo ), ( x.hi, y.hi ) ];
+        Interval{
+            lo:     pairs.iter().map( |&( a, b )| multiply_rounded( a, b, false ) ).fold( f64::INFINITY, f64::min ),
+            hi:     pairs.iter().map( |&( a, b )| multiply_rounded( a, b, true ) ).fold( f64::NEG_INFINITY, f64::max ),
+        }
+    }
+}
+
+impl Ring< Interval > for IntervalRing
+{
+    fn subtract( &self, x: Interval, y: Interval ) -> Interval { self.add( x, self.negate( y ) ) }
+    fn negate( &self, x: Interval ) -> Interval { Interval{ lo: - x.hi, hi: - x.lo } }
+}
+
+impl DivisionRing< Interval > for IntervalRing
+{
+    /// Returns [`Interval::entire`] if `y` contains zero.
+    fn divide( &self, x: Interval, y: Interval ) -> Interval {
+        if y.possibly_zero() { return Interval::entire() }
+        let pairs   =   [ ( x.lo, y.lo ), ( x.lo, y.hi ), ( x.hi, y.lo ), ( x.hi, y.hi ) ];
+        Interval{
+            lo:     pairs.iter().map( |&( a, b )| divide_rounded( a, b, false ) ).fold( f64::INFINITY, f64::min ),
+            hi:     pairs.iter().map( |&( a, b )| divide_rounded( a, b, true ) ).fold( f64::NEG_INFINITY, f64::max ),
+        }
+    }
+
+    /// Returns [`Interval::entire`] if `x` contains zero.
+    fn invert( &self, x: Interval ) -> Interval { self.divide( Self::one(), x ) }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use num::rational::BigRational;
+    use num::traits::ToPrimitive;
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+
+    #[test]
+    fn test_results_enclose_exact_results() {
+        let ring        =   IntervalRing::new();
+        let mut rng     =   StdRng::seed_from_u64( 0 );
+        let exact       =   | x: f64 | BigRational::from_float( x ).unwrap();
+        let encloses    =   | interval: Interval, value: &BigRational | exact( interval.lo ) <= *value && *value <= exact( interval.hi );
+
+        for _ in 0 .. 1000 {
+            // random points, with exponents spread over a wide range
+            let mut draw    =   || rng.gen_range( -1. .. 1. ) * 2f64.powi( rng.gen_range( -40 .. 40 ) );
+            let ( a, b )    =   ( draw(), draw() );
+            let ( x, y )    =   ( Interval::point( a ), Interval::point( b ) );
+            assert!( encloses( ring.add( x, y ), &( exact( a ) + exact( b ) ) ) );
+            assert!( encloses( ring.subtract( x, y ), &( exact( a ) - exact( b ) ) ) );
+            assert!( encloses( ring.multiply( x, y ), &( exact( a ) * exact( b ) ) ) );
+            assert!( encloses( ring.divide( x, y ), &( exact( a ) / exact( b ) ) ) );
+
+            // the enclosures are tight: at most one unit in the last place on either side
+            let sum     =   ring.add( x, y );
+            assert!( sum.lo == ( a + b ) || next_up( sum.lo ) == a + b );
+            assert!( sum.hi == ( a + b ) || next_down( sum.hi ) == a + b );
+            let product =   ring.multiply( x, y );
+            let nearest =   ( exact( a ) * exact( b ) ).to_f64().unwrap();
+            assert!( product.lo <= nearest && nearest <= product.hi && product.hi <= next_up( next_up( product.lo ) ) );
+        }
+
+        // exact operations are not widened
+        assert_eq!( ring.add( Interval::point( 0.5 ), Interval::point( 0.25 ) ), Interval::point( 0.75 ) );
+        assert_eq!( ring.multiply( Interval::new( -2., 3. ), Interval::new( 4., 5. ) ), Interval::new( -10., 15. ) );
+        assert!( ring.add( IntervalRing::zero(), IntervalRing::zero() ).is_certainly_zero() );
+        assert_eq!( ring.divide( Interval::point( 1. ), Interval::new( -1., 1. ) ), Interval::entire() );
+        assert!( ring.multiply( IntervalRing::zero(), Interval::entire() ).is_certainly_zero() );
+    }
+
+    #[test]
+    fn test_overflow_keeps_the_exact_result_enclosed() {
+        let ring        =   IntervalRing::new();
+        let big         =   Interval::point( f64::MAX );
+        assert_eq!( ring.add( big, big ), Interval::new( f64::MAX, f64::INFINITY ) );
+        assert_eq!( ring.multiply( big, Interval::point( -2. ) ), Interval::new( f64::NEG_INFINITY, f64::MIN ) );
+        assert_eq!( ring.divide( big, Interval::point( 0.5 ) ), Interval::new( f64::MAX, f64::INFINITY ) );
+
+        // infinite endpoints of the inputs stay infinite
+        assert_eq!( ring.add( Interval::entire(), Interval::point( 1. ) ), Interval::entire() );
+    }
+
+    #[test]
+    fn test_next_up_and_next_down() {
+        for x in [ 0., -0., 1., -1., 1e-310, -1e-310, f64::MIN_POSITIVE, 123.456 ].iter().cloned() {
+            assert!( next_up( x ) > x && next_down( x ) < x );
+            assert_eq!( next_down( next_up( x ) ), if x == 0. { 0. } else { x } );
+        }
+        assert_eq!( next_up( f64::MAX ), f64::INFINITY );
+        assert_eq!( next_up( - f64::from_bits( 1 ) ), 0. );
+    }
+
+    #[test]
+    fn test_zero_tests() {
+        let x           =   Interval::new( -0.5, 1. );
+        let certain     =   IntervalRing::new();
+        let possible    =   IntervalRing::with_zero_test( ZeroTest::Possible );
+        assert!( x.possibly_zero() && ! x.is_certainly_zero() );
+        assert!( ! certain.is_0( x ) && possible.is_0( x ) );
+        assert!( ! possible.is_0( Interval::new( 1e-300, 1. ) ) );
+        assert!( ! possible.is_1( Interval::new( 0.5, 1. ) ) );
+        assert!( certain.try_divide( Interval::point( 1. ), x ).is_some() );
+        assert!( possible.try_divide( Interval::point( 1. ), x ).is_none() );
+    }
+
+    #[test]
+    fn test_reduction_terminates_under_each_zero_test() {
+        use crate::matrix_factorization::vec_of_vec::right_reduce_by_order;
+        use crate::utilities::order::OrderNatural;
+
+        for zero_test in [ ZeroTest::Certain, ZeroTest::Possible ].iter().cloned() {
+            // clearing the second column leaves a residue in row 1 that is not `[0, 0]`
+            let p           =   Interval::point;
+            let mut matrix  =   vec![ vec![ (0, p( 1. )), (1, p( 3. )) ], vec![ (0, p( 0.1 )), (1, p( 0.7 )) ] ];
+            let pivots      =   right_reduce_by_order( &mut matrix, IntervalRing::with_zero_test( zero_test ), OrderNatural );
+            assert_eq!( pivots, vec![ (0, 1), (1, 0) ].into_iter().collect() );
+            assert_eq!( matrix[1].len(), 1 );
+            assert!( matrix[1][0].1.contains( 0.1 - 0.7 / 3. ) );
+        }
+    }
+
+    #[test]
+    #[should_panic( expected = "lo <= hi" )]
+    fn test_reversed_endpoints_panic() { Interval::new( 1., 0. ); }
+
+}
@@ -0,0 +1,301 @@
+//! Laurent polynomials, i.e. polynomials in which negative powers of the variable are allowed.
+//!
+//! The ring `R[t, t^{-1}]` of Laurent polynomials with coefficients in `R` is the group ring of
+//! the integers over `R`.  It is the coefficient ring needed for circle-valued persistence (where
+//! `t` records how many times a cycle winds around the circle) and for some zigzag
+//! computations.
+//!
+//! A [`LaurentPolynomialRing`] wraps a ring object for the coefficients, and performs arithmetic
+//! on [`LaurentPolynomial`]s.  Like the other ring objects in this crate, it keeps elements in a
+//! canonical form: the terms of a polynomial are sorted in strictly ascending order of exponent,
+//! and no coefficient is zero.  Polynomials should therefore be constructed through the ring
+//! object, with [`LaurentPolynomialRing::polynomial`] or [`LaurentPolynomialRing::monomial`].
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::laurent_polynomial::LaurentPolynomialRing;
+//! use solar::rings::ring_native::NativeRing;
+//! use solar::rings::ring::{Semiring, Ring};
+//!
+//! let ring        =   LaurentPolynomialRing::new( NativeRing::<i64>::new() );
+//! let x           =   ring.polynomial( vec![ (-1, 1), (0, 1) ] );    // t^{-1} + 1
+//! let y           =   ring.polynomial( vec![ (0, 1), (1, -1) ] );    // 1 - t
+//!
+//! // ( t^{-1} + 1 )( 1 - t ) = t^{-1} - t
+//! assert_eq!( ring.multiply( x.clone(), y ).terms(), &[ (-1, 1), (1, -1) ] );
+//! assert!( ring.is_0( ring.subtract( x.clone(), x ) ) );
+//! ```
+
+use crate::rings::ring::{Semiring, Ring, DivisionRing, GcdDomain};
+use crate::vectors::operations::add_assign_sorted_with_buffer;
+
+
+/// A Laurent polynomial `sum_k c_k t^k`, stored as a list of `(k, c_k)` pairs.
+///
+/// Polynomials built by a [`LaurentPolynomialRing`] have terms sorted in strictly ascending order
+/// of exponent, and no zero coefficients; two such polynomials are equal iff their terms are.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LaurentPolynomial< Coeff > {
+    terms:      Vec< ( i64, Coeff ) >,
+}
+
+impl < Coeff > LaurentPolynomial< Coeff > {
+    /// The `(exponent, coefficient)` pairs of the nonzero terms, in ascending order of exponent.
+    pub fn terms( &self ) -> &[ ( i64, Coeff ) ] { &self.terms }
+
+    /// Consume the polynomial, returning its terms.
+    pub fn into_terms( self ) -> Vec< ( i64, Coeff ) > { self.terms }
+
+    /// The largest exponent of a nonzero term, or `None` if the polynomial is zero.
+    pub fn degree( &self ) -> Option< i64 > { self.terms.last().map( |term| term.0 ) }
+
+    /// The smallest exponent of a nonzero term, or `None` if the polynomial is zero.
+    pub fn valuation( &self ) -> Option< i64 > { self.terms.first().map( |term| term.0 ) }
+
+    /// The coefficient of `t^exponent`, or `None` if it is zero.
+    pub fn coefficient( &self, exponent: i64 ) -> Option< &Coeff > {
+        self.terms.binary_search_by_key( &exponent, |term| term.0 ).ok().map( |position| &self.terms[ position ].1 )
+    }
+
+    /// Multiply by `t^shift`.
+    pub fn shift( mut self, shift: i64 ) -> Self {
+        for term in self.terms.iter_mut() { term.0 += shift }
+        self
+    }
+}
+
+
+/// The ring of Laurent polynomials with coefficients in the ring represented by `CoeffRing`.
+///
+/// Addition and subtraction take time linear in the number of terms; multiplication takes time
+/// `O( m^2 n )` in the worst case, for polynomials with `m <= n` terms.  Units other than `± t^k`
+/// are rare, so this object does not implement [`DivisionRing`]; when the coefficients form a
+/// field it implements [`GcdDomain`], so fractions of Laurent polynomials can be formed with a
+/// [`FractionField`](crate::rings::field_fraction::FractionField).
+#[derive(Clone, Debug)]
+pub struct LaurentPolynomialRing< CoeffRing > {
+    coefficient_ring:   CoeffRing,
+}
+
+impl < CoeffRing > LaurentPolynomialRing< CoeffRing > {
+    /// The ring of Laurent polynomials with coefficients in `coefficient_ring`.
+    pub fn new( coefficient_ring: CoeffRing ) -> Self { LaurentPolynomialRing{ coefficient_ring } }
+
+    /// The ring object for the coefficients.
+    pub fn coefficient_ring( &self ) -> &CoeffRing { &self.coefficient_ring }
+
+    /// The polynomial `sum_k c_k t^k`, given the `(k, c_k)` pairs in any order.
+    ///
+    /// Terms with equal exponents are added, and zero coefficients are dropped.
+    pub fn polynomial< Coeff, I >( &self, terms: I ) -> LaurentPolynomial< Coeff >
+        where   CoeffRing:  Semiring< Coeff >,
+                Coeff:      Clone,
+                I:          IntoIterator< Item = ( i64, Coeff ) >,
+    {
+        let mut sorted: Vec< _ >    =   terms.into_iter().collect();
+        sorted.sort_by_key( |term| term.0 );
+        let mut merged: Vec< ( i64, Coeff ) >   =   Vec::with_capacity( sorted.len() );
+        for ( exponent, coeff ) in sorted {
+            match merged.last_mut() {
+                Some( last ) if last.0 == exponent  =>  { last.1 = self.coefficient_ring.add( last.1.clone(), coeff ) }
+                _                                   =>  { merged.push( ( exponent, coeff ) ) }
+            }
+        }
+        merged.retain( |term| ! self.coefficient_ring.is_0( term.1.clone() ) );
+        LaurentPolynomial{ terms: merged }
+    }
+
+    /// The monomial `coeff * t^exponent` (which is zero if `coeff` is zero).
+    pub fn monomial< Coeff >( &self, exponent: i64, coeff: Coeff ) -> LaurentPolynomial< Coeff >
+        where   CoeffRing:  Semiring< Coeff >,
+                Coeff:      Clone,
+    {
+        if self.coefficient_ring.is_0( coeff.clone() ) { LaurentPolynomial{ terms: Vec::new() } }
+        else { LaurentPolynomial{ terms: vec![ ( exponent, coeff ) ] } }
+    }
+
+    /// The monomial `t^exponent`.
+    pub fn t_power< Coeff >( &self, exponent: i64 ) -> LaurentPolynomial< Coeff >
+        where   CoeffRing:  Semiring< Coeff >,
+                Coeff:      Clone,
+    {
+        self.monomial( exponent, CoeffRing::one() )
+    }
+}
+
+impl < CoeffRing, Coeff > Semiring< LaurentPolynomial< Coeff > > for LaurentPolynomialRing< CoeffRing >
+    where   CoeffRing:  Semiring< Coeff >,
+            Coeff:      Clone,
+{
+    fn is_0( &self, x: LaurentPolynomial< Coeff > ) -> bool { x.terms.is_empty() }
+    fn is_1( &self, x: LaurentPolynomial< Coeff > ) -> bool {
+        x.terms.len() == 1 && x.terms[0].0 == 0 && self.coefficient_ring.is_1( x.terms[0].1.clone() )
+    }
+    fn zero() -> LaurentPolynomial< Coeff > { LaurentPolynomial{ terms: Vec::new() } }
+    fn one()  -> LaurentPolynomial< Coeff > { LaurentPolynomial{ terms: vec![ ( 0, CoeffRing::one() ) ] } }
+
+    fn add( &self, x: LaurentPolynomial< Coeff >, y: LaurentPolynomial< Coeff > ) -> LaurentPolynomial< Coeff > {
+        let mut sum     =   x.terms;
+        add_assign_sorted_with_buffer( &mut sum, &y.terms, CoeffRing::one(), &self.coefficient_ring, &mut Vec::new() );
+        LaurentPolynomial{ terms: sum }
+    }
+
+    fn multiply( &self, x: LaurentPolynomial< Coeff >, y: LaurentPolynomial< Coeff > ) -> LaurentPolynomial< Coeff > {
+        // loop over the shorter factor, adding shifted multiples of the longer one
+        let ( short, long ) =   if x.terms.len() <= y.terms.len() { ( x, y ) } else { ( y, x ) };
+        let mut product     =   Vec::new();
+        let mut shifted     =   Vec::with_capacity( long.terms.len() );
+        let mut buffer      =   Vec::new();
+        for ( exponent, coeff ) in short.terms {
+            shifted.clear();
+            shifted.extend( long.terms.iter().map( |term| ( term.0 + exponent, term.1.clone() ) ) );
+            add_assign_sorted_with_buffer( &mut product, &shifted, coeff, &self.coefficient_ring, &mut buffer );
+        }
+        LaurentPolynomial{ terms: product }
+    }
+}
+
+impl < CoeffRing, Coeff > Ring< LaurentPolynomial< Coeff > > for LaurentPolynomialRing< CoeffRing >
+    where   CoeffRing:  Ring< Coeff >,
+            Coeff:      Clone,
+{
+    fn subtract( &self, x: LaurentPolynomial< Coeff >, y: LaurentPolynomial< Coeff > ) -> LaurentPolynomial< Coeff > {
+        let mut difference  =   x.terms;
+        let minus_one       =   self.coefficient_ring.negate( CoeffRing::one() );
+        add_assign_sorted_with_buffer( &mut difference, &y.terms, minus_one, &self.coefficient_ring, &mut Vec::new() );
+        LaurentPolynomial{ terms: difference }
+    }
+
+    fn negate( &self, x: LaurentPolynomial< Coeff > ) -> LaurentPolynomial< Coeff > {
+        let terms   =   x.terms.into_iter().map( |( exponent, coeff )| ( exponent, self.coefficient_ring.negate( coeff ) ) ).collect();
+        LaurentPolynomial{ terms }
+    }
+}
+
+impl < CoeffRing > LaurentPolynomialRing< CoeffRing > {
+    /// Divide by the unit `c t^k` that leaves a polynomial with valuation zero and leading
+    /// coefficient one.
+    fn normalize< Coeff >( &self, x: LaurentPolynomial< Coeff > ) -> LaurentPolynomial< Coeff >
+        where   CoeffRing:  DivisionRing< Coeff >,
+                Coeff:      Clone,
+    {
+        let ( valuation, leading )  =   match ( x.terms.first(), x.terms.last() ) {
+            ( Some( first ), Some( last ) )     =>  ( first.0, last.1.clone() ),
+            _                                   =>  return x,
+        };
+        let terms   =   x.terms.into_iter().map( |( exponent, coeff )| ( exponent - valuation, self.coefficient_ring.divide( coeff, leading.clone() ) ) ).collect();
+        LaurentPolynomial{ terms }
+    }
+
+    /// Polynomial long division of `x` by `y`, where both have nonnegative exponents and `y` is
+    /// nonzero: returns `(q, r)` such that `x = q y + r` and `r` has smaller degree than `y`.
+    fn divide_with_remainder< Coeff >( &self, x: LaurentPolynomial< Coeff >, y: &LaurentPolynomial< Coeff > ) -> ( LaurentPolynomial< Coeff >, LaurentPolynomial< Coeff > )
+        where   CoeffRing:  DivisionRing< Coeff >,
+                Coeff:      Clone,
+    {
+        let ( degree, leading ) =   y.terms.last().cloned().expect( "division by the zero polynomial" );
+        // the leading term of `r` is removed by hand, rather than by cancellation, so that the
+        // loop terminates even if coefficient arithmetic is inexact
+        let tail                =   LaurentPolynomial{ terms: y.terms[ .. y.terms.len() - 1 ].to_vec() };
+        let mut quotient        =   Vec::new();
+        let mut remainder       =   x;
+        while let Some( ( top, coeff ) ) = remainder.terms.last().cloned() {
+            if top < degree { break }
+            remainder.terms.pop();
+            let term    =   ( top - degree, self.coefficient_ring.divide( coeff, leading.clone() ) );
+            remainder   =   self.subtract( remainder, self.multiply( self.monomial( term.0, term.1.clone() ), tail.clone() ) );
+            quotient.push( term );
+        }
+        quotient.reverse();
+        ( LaurentPolynomial{ terms: quotient }, remainder )
+    }
+}
+
+impl < CoeffRing, Coeff > GcdDomain< LaurentPolynomial< Coeff > > for LaurentPolynomialRing< CoeffRing >
+    where   CoeffRing:  DivisionRing< Coeff >,
+            Coeff:      Clone,
+{
+    /// The greatest common divisor with valuation zero and leading coefficient one (or zero, if
+    /// `x` and `y` are both zero), computed with the Euclidean algorithm.
+    fn gcd( &self, x: LaurentPolynomial< Coeff >, y: LaurentPolynomial< Coeff > ) -> LaurentPolynomial< Coeff > {
+        let ( mut x, mut y )    =   ( self.normalize( x ), self.normalize( y ) );
+        while ! y.terms.is_empty() {
+            let remainder   =   self.divide_with_remainder( x, &y ).1;
+            x   =   y;
+            y   =   self.normalize( remainder );
+        }
+        x
+    }
+
+    /// Panics if `y` is zero.
+    fn divide_exact( &self, x: LaurentPolynomial< Coeff >, y: LaurentPolynomial< Coeff > ) -> LaurentPolynomial< Coeff > {
+        let ( x_valuation, y_valuation )    =   match ( x.valuation(), y.valuation() ) {
+            ( _, None )                 =>  panic!( "division by the zero polynomial" ),
+            ( None, _ )                 =>  return x,
+            ( Some( i ), Some( j ) )    =>  ( i, j ),
+        };
+        // powers of `t` are units, so it suffices to divide polynomials with valuation zero
+        let quotient    =   self.divide_with_remainder( x.shift( - x_valuation ), &y.shift( - y_valuation ) ).0;
+        quotient.shift( x_valuation - y_valuation )
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GFp;
+    use crate::rings::ring_native::NativeRing;
+
+
+    #[test]
+    fn test_laurent_polynomial_arithmetic() {
+        let ring    =   LaurentPolynomialRing::new( NativeRing::< i64 >::new() );
+        let t       =   ring.t_power( 1 );
+        let t_inv   =   ring.t_power( -1 );
+        assert!( ring.is_1( ring.multiply( t.clone(), t_inv.clone() ) ) );
+
+        // canonical form: unsorted input, repeated exponents, cancellation
+        let x       =   ring.polynomial( vec![ (2, 3), (-2, 1), (2, -3), (0, 5), (-2, 1) ] );
+        assert_eq!( x.terms(), &[ (-2, 2), (0, 5) ] );
+        assert_eq!( ( x.valuation(), x.degree(), x.coefficient( 0 ), x.coefficient( 1 ) ), ( Some( -2 ), Some( 0 ), Some( &5 ), None ) );
+        assert_eq!( x.clone().shift( 3 ), ring.multiply( x.clone(), ring.t_power( 3 ) ) );
+
+        // ( 1 - t )( 1 + t + t^2 ) = 1 - t^3, and multiplication commutes
+        let a       =   ring.polynomial( vec![ (0, 1), (1, -1) ] );
+        let b       =   ring.polynomial( vec![ (0, 1), (1, 1), (2, 1) ] );
+        assert_eq!( ring.multiply( a.clone(), b.clone() ).terms(), &[ (0, 1), (3, -1) ] );
+        assert_eq!( ring.multiply( a.clone(), b.clone() ), ring.multiply( b.clone(), a.clone() ) );
+
+        // distributivity, and additive inverses
+        let lhs     =   ring.multiply( x.clone(), ring.add( a.clone(), b.clone() ) );
+        let rhs     =   ring.add( ring.multiply( x.clone(), a.clone() ), ring.multiply( x.clone(), b.clone() ) );
+        assert_eq!( lhs, rhs );
+        assert!( ring.is_0( ring.add( x.clone(), ring.negate( x.clone() ) ) ) );
+        assert_eq!( ring.subtract( a.clone(), b.clone() ), ring.add( a, ring.negate( b ) ) );
+        assert!( ring.is_0( ring.multiply( x, LaurentPolynomialRing::< NativeRing< i64 > >::zero() ) ) );
+
+        // coefficients in a finite field: ( 1 + t )^3 = 1 + t^3 over GF(3)
+        let ring    =   LaurentPolynomialRing::new( GFp::new( 3 ) );
+        let c       =   ring.polynomial( vec![ (0, 1), (1, 1) ] );
+        let cube    =   ring.multiply( c.clone(), ring.multiply( c.clone(), c ) );
+        assert_eq!( cube.terms(), &[ (0, 1), (3, 1) ] );
+        assert!( ring.is_0( ring.monomial( 7, 0 ) ) );
+
+        // over a field: gcd( t^{-1} ( 1 + t )^2, 2 t^5 ( 1 + t )( 1 + 2t ) ) = 1 + t, up to a unit
+        let ring    =   LaurentPolynomialRing::new( GFp::new( 7 ) );
+        let c       =   ring.polynomial( vec![ (0, 1), (1, 1) ] );
+        let x       =   ring.multiply( ring.multiply( c.clone(), c.clone() ), ring.t_power( -1 ) );
+        let y       =   ring.multiply( c.clone(), ring.polynomial( vec![ (5, 2), (6, 4) ] ) );
+        let gcd     =   ring.gcd( x.clone(), y.clone() );
+        assert_eq!( gcd, c );
+        assert_eq!( ring.multiply( ring.divide_exact( y.clone(), gcd.clone() ), gcd.clone() ), y );
+        assert_eq!( ring.divide_exact( x, gcd ), ring.polynomial( vec![ (-1, 1), (0, 1) ] ) );
+        assert_eq!( ring.gcd( ring.polynomial( vec![] ), y ), ring.polynomial( vec![ (0, 4), (1, 5), (2, 1) ] ) );    // monic
+    }
+
+}
@@ -0,0 +1,215 @@
+//! The characteristic and cardinality of the ring represented by a ring object.
+//!
+//! Ring objects know how to compute, but not what they compute over; the [`RingMetadata`] trait
+//! fills this gap, so that e.g. a persistence pipeline can record the field a barcode was computed
+//! over, or refuse to mix results computed over fields of different characteristic.
+//!
+//! Machine integers and floating point numbers are treated as the rings they approximate: a
+//! `NativeRing<i64>` reports the characteristic and cardinality of the integers, and a
+//! `NativeDivisionRing<f64>` those of the real numbers.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::metadata::RingMetadata;
+//! use solar::rings::field_prime::GFp;
+//! use solar::rings::ring_native::NativeDivisionRing;
+//! use num::rational::Ratio;
+//! use num::BigUint;
+//!
+//! let field   =   GFp::new( 3 );
+//! assert_eq!( field.characteristic(), Some( BigUint::from( 3u32 ) ) );
+//! assert_eq!( field.cardinality(), Some( BigUint::from( 3u32 ) ) );
+//!
+//! // the rationals have characteristic zero, and infinitely many elements
+//! let field   =   NativeDivisionRing::< Ratio< i64 > >::new();
+//! assert_eq!( field.characteristic(), Some( BigUint::from( 0u32 ) ) );
+//! assert_eq!( field.cardinality(), None );
+//! ```
+
+use crate::rings::field_complex::ComplexField;
+use crate::rings::field_fraction::FractionField;
+use crate::rings::field_gf2k::GF2k;
+use crate::rings::field_prime::{GF2, GF2Checked, GFp};
+use crate::rings::field_rational::{RationalField, CheckedRationalField};
+use crate::rings::interval::IntervalRing;
+use crate::rings::laurent_polynomial::LaurentPolynomialRing;
+use crate::rings::ring_dyadic::DyadicRing;
+use crate::rings::ring_matrix::MatrixRing;
+use crate::rings::ring_native::{NativeSemiring, NativeRing, NativeDivisionRing};
+use crate::rings::semiring_boolean::BooleanSemiring;
+use num::BigUint;
+
+
+//  ---------------------------------------------------------
+//  THE TRAIT
+//  ---------------------------------------------------------
+
+/// The characteristic and cardinality of the (semi)ring represented by a ring object.
+pub trait RingMetadata {
+
+    /// The least `n > 0` such that `1 + .. + 1 = 0` (with `n` terms), or `0` if there is no such
+    /// `n`; `None` if this is undefined, as in a semiring where `1 + 1 = 1`.
+    fn characteristic( &self ) -> Option< BigUint >;
+
+    /// The number of elements, or `None` if the ring is infinite.
+    fn cardinality( &self ) -> Option< BigUint >;
+
+    /// Returns `true` if the ring has finitely many elements.
+    fn is_finite( &self ) -> bool { self.cardinality().is_some() }
+}
+
+
+//  ---------------------------------------------------------
+//  FINITE FIELDS AND SEMIRINGS
+//  ---------------------------------------------------------
+
+impl RingMetadata for GF2 {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 2u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { Some( BigUint::from( 2u32 ) ) }
+}
+
+impl RingMetadata for GF2Checked {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 2u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { Some( BigUint::from( 2u32 ) ) }
+}
+
+impl RingMetadata for GFp {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( self.modulus() ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { Some( BigUint::from( self.modulus() ) ) }
+}
+
+impl RingMetadata for GF2k {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 2u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { Some( BigUint::from( 1u32 ) << self.degree() as usize ) }
+}
+
+impl RingMetadata for BooleanSemiring {
+    /// Undefined, since `1 + 1 = 1`.
+    fn characteristic( &self ) -> Option< BigUint > { None }
+    fn cardinality( &self ) -> Option< BigUint > { Some( BigUint::from( 2u32 ) ) }
+}
+
+
+//  ---------------------------------------------------------
+//  RINGS OF CHARACTERISTIC ZERO
+//  ---------------------------------------------------------
+
+impl < Element > RingMetadata for NativeSemiring< Element >
+    where   Element:    num::traits::Zero + num::traits::One + PartialEq + Clone,
+{
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 0u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+impl < Element > RingMetadata for NativeRing< Element >
+    where   Element:    num::traits::Num + std::ops::Neg< Output = Element > + Clone,
+{
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 0u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+impl < Element > RingMetadata for NativeDivisionRing< Element >
+    where   Element:    num::traits::Num + std::ops::Neg< Output = Element > + Clone,
+{
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 0u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+impl RingMetadata for RationalField {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 0u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+impl RingMetadata for CheckedRationalField {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 0u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+impl RingMetadata for DyadicRing {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 0u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+impl RingMetadata for IntervalRing {
+    fn characteristic( &self ) -> Option< BigUint > { Some( BigUint::from( 0u32 ) ) }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+
+//  ---------------------------------------------------------
+//  CONSTRUCTIONS
+//  ---------------------------------------------------------
+
+impl < RealRing: RingMetadata > RingMetadata for ComplexField< RealRing > {
+    fn characteristic( &self ) -> Option< BigUint > { self.real_ring().characteristic() }
+    fn cardinality( &self ) -> Option< BigUint > { self.real_ring().cardinality().map( |n| &n * &n ) }
+}
+
+impl < RingOperator: RingMetadata, Reduction > RingMetadata for FractionField< RingOperator, Reduction > {
+    fn characteristic( &self ) -> Option< BigUint > { self.ring().characteristic() }
+
+    /// A finite integral domain is a field, and equal to its field of fractions.
+    fn cardinality( &self ) -> Option< BigUint > { self.ring().cardinality() }
+}
+
+impl < RingOperator: RingMetadata, const N: usize > RingMetadata for MatrixRing< RingOperator, N > {
+    /// The characteristic of the entries, except that `0 x 0` matrices form the zero ring.
+    fn characteristic( &self ) -> Option< BigUint > {
+        if N == 0 { Some( BigUint::from( 1u32 ) ) } else { self.ring().characteristic() }
+    }
+    fn cardinality( &self ) -> Option< BigUint > { self.ring().cardinality().map( |n| n.pow( ( N * N ) as u32 ) ) }
+}
+
+impl < CoeffRing: RingMetadata > RingMetadata for LaurentPolynomialRing< CoeffRing > {
+    fn characteristic( &self ) -> Option< BigUint > { self.coefficient_ring().characteristic() }
+    fn cardinality( &self ) -> Option< BigUint > { None }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use num::Complex;
+    use num::rational::Ratio;
+
+
+    #[test]
+    fn test_ring_metadata() {
+        let big     =   | n: u64 | Some( BigUint::from( n ) );
+
+        assert_eq!( ( GF2::new().characteristic(), GF2::new().cardinality() ), ( big( 2 ), big( 2 ) ) );
+        assert_eq!( ( GF2Checked::new().characteristic(), GF2Checked::new().cardinality() ), ( big( 2 ), big( 2 ) ) );
+        assert_eq!( ( GFp::new( 1_000_000_007 ).characteristic(), GFp::new( 1_000_000_007 ).cardinality() ), ( big( 1_000_000_007 ), big( 1_000_000_007 ) ) );
+        assert_eq!( ( GF2k::new( 63 ).characteristic(), GF2k::new( 63 ).cardinality() ), ( big( 2 ), big( 1 << 63 ) ) );
+        assert_eq!( ( BooleanSemiring::new().characteristic(), BooleanSemiring::new().cardinality() ), ( None, big( 2 ) ) );
+
+        assert_eq!( NativeSemiring::< usize >::new().characteristic(), big( 0 ) );
+        assert_eq!( NativeRing::< i64 >::new().characteristic(), big( 0 ) );
+        assert!( ! NativeDivisionRing::< Complex< f64 > >::new().is_finite() );
+        assert!( ! RationalField::new().is_finite() && ! CheckedRationalField::new().is_finite() );
+        assert_eq!( IntervalRing::new().characteristic(), big( 0 ) );
+        assert_eq!( ( DyadicRing::new().characteristic(), DyadicRing::new().cardinality() ), ( big( 0 ), None ) );
+
+        // constructions
+        let gaussian    =   ComplexField::new( GFp::new( 7 ) );    // GF(49), since -1 is not a square mod 7
+        assert_eq!( ( gaussian.characteristic(), gaussian.cardinality() ), ( big( 7 ), big( 49 ) ) );
+        let gaussian    =   ComplexField::new( NativeDivisionRing::< Ratio< i64 > >::new() );
+        assert_eq!( ( gaussian.characteristic(), gaussian.cardinality() ), ( big( 0 ), None ) );
+
+        let blocks      =   MatrixRing::< _, 2 >::new( GFp::new( 3 ) );
+        assert_eq!( ( blocks.characteristic(), blocks.cardinality() ), ( big( 3 ), big( 81 ) ) );
+        assert_eq!( MatrixRing::< _, 0 >::new( GF2::new() ).cardinality(), big( 1 ) );
+
+        let laurent     =   LaurentPolynomialRing::new( GFp::new( 5 ) );
+        assert_eq!( ( laurent.characteristic(), laurent.cardinality() ), ( big( 5 ), None ) );
+        let functions   =   FractionField::new_reduced( laurent );
+        assert_eq!( ( functions.characteristic(), functions.is_finite() ), ( big( 5 ), false ) );
+        assert!( FractionField::new( GF2::new() ).is_finite() );
+    }
+
+}
@@ -10,4 +10,18 @@
 // pub mod field;
 pub mod ring;
 pub mod ring_native;
+pub mod ring_dyadic;
+pub mod ring_matrix;
+pub mod axioms;
 pub mod field_prime;
+pub mod field_rational;
+pub mod field_complex;
+pub mod field_fraction;
+pub mod field_gf2k;
+pub mod group_ring;
+pub mod homomorphism;
+pub mod interval;
+pub mod laurent_polynomial;
+pub mod metadata;
+pub mod random;
+pub mod semiring_boolean;
@@ -0,0 +1,181 @@
+//! Random elements of rings, for randomized tests and random matrices.
+//!
+//! A ring object that implements [`SampleableRing`] can draw random elements of its ring from a
+//! caller-supplied random number generator; results can be made reproducible by passing a seeded
+//! generator.  The distributions are chosen to be useful in tests rather than to be uniform:
+//!
+//! | ring object                                       | distribution of `random_element`                        |
+//! |---------------------------------------------------|---------------------------------------------------------|
+//! | [`GF2`], [`GFp`], [`GF2k`]                        | uniform                                                 |
+//! | `NativeSemiring`, `NativeRing` over signed integers | uniform on `-10 ..= 10`                               |
+//! | `NativeSemiring` over unsigned integers           | uniform on `0 ..= 10`                                   |
+//! | `NativeSemiring`, `NativeRing`, `NativeDivisionRing` over `f32`, `f64` | uniform on `[-1, 1]`               |
+//! | `NativeDivisionRing` over `Ratio<i64>`            | numerator uniform on `-10 ..= 10`, denominator on `1 ..= 10` |
+//!
+//! Small integers keep products and sums of random elements far from overflow.
+//!
+//! Together with the generators in [`matrices::random`](crate::matrices::random), this makes
+//! random matrices ring-generic: pass `|rng| ring.random_nonzero( rng )` as the coefficient sampler.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::random::SampleableRing;
+//! use solar::rings::field_prime::GFp;
+//! use solar::rings::ring::{Semiring, DivisionRing};
+//! use rand::SeedableRng;
+//! use rand::rngs::StdRng;
+//!
+//! // a randomized check that every nonzero element of GF(13) is invertible
+//! let ring        =   GFp::new( 13 );
+//! let mut rng     =   StdRng::seed_from_u64( 0 );
+//! for _ in 0 .. 100 {
+//!     let x   =   ring.random_nonzero( &mut rng );
+//!     assert!( ring.is_1( ring.multiply( x, ring.invert( x ) ) ) );
+//! }
+//! ```
+
+use crate::rings::field_gf2k::GF2k;
+use crate::rings::field_prime::{GF2, GFp};
+use crate::rings::ring::Semiring;
+use crate::rings::ring_native::{NativeSemiring, NativeRing, NativeDivisionRing};
+use num::rational::Ratio;
+use rand::Rng;
+
+
+//  ---------------------------------------------------------
+//  THE TRAIT
+//  ---------------------------------------------------------
+
+/// Ring objects that can draw random elements.
+pub trait SampleableRing< Element > : Semiring< Element > {
+
+    /// A random element.
+    fn random_element< R: Rng + ?Sized >( &self, rng: &mut R ) -> Element;
+
+    /// A random nonzero element.
+    ///
+    /// By default, draws elements with [`random_element`](SampleableRing::random_element) until
+    /// one is nonzero; this never terminates if `random_element` only returns zero.
+    fn random_nonzero< R: Rng + ?Sized >( &self, rng: &mut R ) -> Element
+        where   Element:    Clone,
+    {
+        loop {
+            let x   =   self.random_element( rng );
+            if ! self.is_0( x.clone() ) { return x }
+        }
+    }
+}
+
+
+//  ---------------------------------------------------------
+//  FINITE FIELDS
+//  ---------------------------------------------------------
+
+impl SampleableRing< bool > for GF2 {
+    fn random_element< R: Rng + ?Sized >( &self, rng: &mut R ) -> bool { rng.gen() }
+    fn random_nonzero< R: Rng + ?Sized >( &self, _rng: &mut R ) -> bool { true }
+}
+
+impl SampleableRing< u64 > for GFp {
+    fn random_element< R: Rng + ?Sized >( &self, rng: &mut R ) -> u64 { rng.gen_range( 0 .. self.modulus() ) }
+    fn random_nonzero< R: Rng + ?Sized >( &self, rng: &mut R ) -> u64 { rng.gen_range( 1 .. self.modulus() ) }
+}
+
+impl SampleableRing< u64 > for GF2k {
+    fn random_element< R: Rng + ?Sized >( &self, rng: &mut R ) -> u64 { rng.gen_range( 0 .. 1 << self.degree() ) }
+    fn random_nonzero< R: Rng + ?Sized >( &self, rng: &mut R ) -> u64 { rng.gen_range( 1 .. 1 << self.degree() ) }
+}
+
+
+//  ---------------------------------------------------------
+//  NATIVE RINGS
+//  ---------------------------------------------------------
+
+/// Implement `SampleableRing< $element >` for `$ring< $element >`, drawing elements uniformly from
+/// `$low ..= $high`.
+macro_rules! sampleable_native {
+    ( $ring:ident, $low:expr, $high:expr, $( $element:ty ),* ) => {
+        $(
+            impl SampleableRing< $element > for $ring< $element > {
+                fn random_element< R: Rng + ?Sized >( &self, rng: &mut R ) -> $element { rng.gen_range( $low ..= $high ) }
+            }
+        )*
+    }
+}
+
+sampleable_native!( NativeSemiring,         -10,    10,     i8, i16, i32, i64, i128, isize );
+sampleable_native!( NativeRing,             -10,    10,     i8, i16, i32, i64, i128, isize );
+sampleable_native!( NativeSemiring,         0,      10,     u8, u16, u32, u64, u128, usize );
+sampleable_native!( NativeSemiring,         -1.,    1.,     f32, f64 );
+sampleable_native!( NativeRing,             -1.,    1.,     f32, f64 );
+sampleable_native!( NativeDivisionRing,     -1.,    1.,     f32, f64 );
+
+impl SampleableRing< Ratio< i64 > > for NativeDivisionRing< Ratio< i64 > > {
+    fn random_element< R: Rng + ?Sized >( &self, rng: &mut R ) -> Ratio< i64 > {
+        Ratio::new( rng.gen_range( -10 ..= 10 ), rng.gen_range( 1 ..= 10 ) )
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+
+    /// Draw `n` elements and `n` nonzero elements, checking that the latter are nonzero and that
+    /// the same seed gives the same elements.
+    fn draw< RingOperator, Element >( ring: &RingOperator, n: usize ) -> Vec< Element >
+        where   RingOperator:   SampleableRing< Element >,
+                Element:        Clone + PartialEq + std::fmt::Debug,
+    {
+        let sample  =   |seed| {
+                            let mut rng     =   StdRng::seed_from_u64( seed );
+                            let mut drawn   =   Vec::new();
+                            for _ in 0 .. n { drawn.push( ring.random_element( &mut rng ) ) }
+                            for _ in 0 .. n {
+                                let x   =   ring.random_nonzero( &mut rng );
+                                assert!( ! ring.is_0( x.clone() ) );
+                                drawn.push( x );
+                            }
+                            drawn
+                        };
+        let drawn   =   sample( 7 );
+        assert_eq!( drawn, sample( 7 ) );
+        drawn
+    }
+
+    #[test]
+    fn test_random_elements() {
+        let drawn   =   draw( &GF2::new(), 100 );
+        assert!( drawn[ .. 100 ].contains( &false ) && drawn[ .. 100 ].contains( &true ) );
+
+        let drawn   =   draw( &GFp::new( 5 ), 200 );
+        assert!( ( 0 .. 5 ).all( |x| drawn[ .. 200 ].contains( &x ) ) );
+        assert!( drawn.iter().all( |&x| x < 5 ) );
+
+        let drawn   =   draw( &GF2k::new( 3 ), 200 );
+        assert!( ( 0 .. 8 ).all( |x| drawn[ .. 200 ].contains( &x ) ) );
+        assert!( drawn.iter().all( |&x| x < 8 ) );
+
+        let drawn   =   draw( &NativeRing::< i64 >::new(), 200 );
+        assert!( drawn.iter().all( |x| ( -10 ..= 10 ).contains( x ) ) );
+        assert!( drawn.contains( &-10 ) && drawn.contains( &10 ) );
+
+        let drawn   =   draw( &NativeSemiring::< usize >::new(), 200 );
+        assert!( drawn.iter().all( |x| ( 0 ..= 10 ).contains( x ) ) );
+
+        let drawn   =   draw( &NativeDivisionRing::< f64 >::new(), 200 );
+        assert!( drawn.iter().all( |x| ( -1. ..= 1. ).contains( x ) ) );
+
+        let drawn   =   draw( &NativeDivisionRing::< Ratio< i64 > >::new(), 200 );
+        assert!( drawn.iter().all( |x| x.numer().abs() <= 10 && *x.denom() <= 10 ) );
+    }
+
+}
@@ -63,6 +63,76 @@ pub trait Semiring < Element > {
     /// Multiply
     fn multiply( &self, x : Element, y: Element ) -> Element;
 
+
+    // DERIVED OPERATIONS
+
+    /// Raise `x` to the power `n`, by repeated squaring; `x^0` is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::rings::ring::{Semiring, Ring};
+    /// use solar::rings::field_prime::GFp;
+    /// use solar::rings::ring_native::NativeRing;
+    ///
+    /// // Fermat's little theorem
+    /// let field   =   GFp::new( 101 );
+    /// assert_eq!( field.power( 7, 100 ), 1 );
+    ///
+    /// let ring    =   NativeRing::<i64>::new();
+    /// assert_eq!( ring.power( ring.negate( 1 ), 5 ), -1 );
+    /// assert_eq!( ring.power( 3, 0 ), 1 );
+    /// ```
+    fn power( &self, x : Element, n: usize ) -> Element
+        where   Element:    Clone,
+    {
+        let mut result  =   Self::one();
+        let mut base    =   x;
+        let mut n       =   n;
+        while n > 0 {
+            if n & 1 == 1 { result = self.multiply( result, base.clone() ) }
+            n >>= 1;
+            if n > 0 { base = self.multiply( base.clone(), base ) }
+        }
+        result
+    }
+
+    /// The sum of the elements of `iter`; the empty sum is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::rings::ring::Semiring;
+    /// use solar::rings::field_prime::GF2;
+    ///
+    /// let field   =   GF2::new();
+    /// assert!( field.sum_iter( vec![ true, true, true ] ) );
+    /// assert!( ! field.sum_iter( Vec::< bool >::new() ) );
+    /// ```
+    fn sum_iter< I >( &self, iter: I ) -> Element
+        where   I:  IntoIterator< Item = Element >,
+    {
+        iter.into_iter().fold( Self::zero(), |sum, x| self.add( sum, x ) )
+    }
+
+    /// The product of the elements of `iter`, in order; the empty product is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::rings::ring::Semiring;
+    /// use solar::rings::ring_native::NativeRing;
+    ///
+    /// let ring    =   NativeRing::<i64>::new();
+    /// assert_eq!( ring.product_iter( 1 ..= 5 ), 120 );
+    /// assert_eq!( ring.product_iter( Vec::new() ), 1 );
+    /// ```
+    fn product_iter< I >( &self, iter: I ) -> Element
+        where   I:  IntoIterator< Item = Element >,
+    {
+        iter.into_iter().fold( Self::one(), |product, x| self.multiply( product, x ) )
+    }
+
 }
 
 
@@ -96,4 +166,55 @@ pub trait DivisionRing <Element> : Ring < Element > {
     /// Invert 
     fn invert( &self, x : Element ) -> Element;
 
-}
\ No newline at end of file
+    /// Divide, or return `None` if `y` is zero.
+    ///
+    /// The behavior of [`divide`](DivisionRing::divide) on a zero denominator depends on the ring
+    /// object: some return a meaningless value (e.g. `GF2`, in release builds), some return `inf`
+    /// or `NaN` (e.g. `NativeDivisionRing::<f64>`), and some panic.  This method checks the
+    /// denominator with [`is_0`](Semiring::is_0) first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use solar::rings::ring::DivisionRing;
+    /// use solar::rings::ring_native::NativeDivisionRing;
+    ///
+    /// let ring    =   NativeDivisionRing::<f64>::new();
+    /// assert_eq!( ring.try_divide( 1., 4. ), Some( 0.25 ) );
+    /// assert_eq!( ring.try_divide( 1., 0. ), None );
+    /// ```
+    fn try_divide( &self, x : Element, y: Element ) -> Option< Element >
+        where   Element:    Clone,
+    {
+        if self.is_0( y.clone() ) { None } else { Some( self.divide( x, y ) ) }
+    }
+
+    /// Invert, or return `None` if `x` is zero.
+    ///
+    /// See [`try_divide`](DivisionRing::try_divide).
+    fn try_invert( &self, x : Element ) -> Option< Element >
+        where   Element:    Clone,
+    {
+        if self.is_0( x.clone() ) { None } else { Some( self.invert( x ) ) }
+    }
+
+}
+
+//----------------------------------------------------------
+//  THE GCD DOMAIN TRAIT 
+//----------------------------------------------------------
+
+/// Basic operations for integral domains in which every pair of elements has a greatest common
+/// divisor, such as the integers, or polynomials over a field.
+///
+/// Greatest common divisors are only defined up to multiplication by a unit; ring objects may
+/// return any of them.
+pub trait GcdDomain <Element> : Ring < Element > {
+
+    /// A greatest common divisor of `x` and `y`; this is zero iff `x` and `y` are both zero.
+    fn gcd( &self, x : Element, y: Element ) -> Element;
+
+    /// The element `q` such that `x = q * y`, assuming that `y` is nonzero and divides `x`.
+    fn divide_exact( &self, x : Element, y: Element ) -> Element;
+
+}
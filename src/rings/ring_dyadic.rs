@@ -0,0 +1,174 @@
+//! The ring of dyadic rationals, numbers of form `m * 2^e` with `m` and `e` integers.
+//!
+//! Every finite floating point number is a dyadic rational, and sums, differences and products of
+//! dyadic rationals are again dyadic rationals.  The [`DyadicRing`] therefore lets one treat
+//! floating point input data *exactly*, without rounding error.  Unlike the field of rationals,
+//! it never needs to compute a gcd: the denominator of a dyadic rational is always a power of two,
+//! which is stored as an exponent.
+//!
+//! Dyadic rationals are not closed under division, so `DyadicRing` implements [`Ring`] but not
+//! [`DivisionRing`](crate::rings::ring::DivisionRing).
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::ring_dyadic::{Dyadic, DyadicRing};
+//! use solar::rings::ring::{Semiring, Ring};
+//!
+//! let ring    =   DyadicRing::new();
+//! let tenth   =   Dyadic::from_f64( 0.1 ).unwrap();   // the float nearest to 1/10, exactly
+//!
+//! // floating point arithmetic rounds: this is 2^-54 ...
+//! assert_eq!( 0.1 + 0.2 - 0.3, 5.551115123125783e-17 );
+//!
+//! // ... but dyadic arithmetic does not
+//! let sum     =   ring.add( tenth, Dyadic::from_f64( 0.2 ).unwrap() );
+//! let error   =   ring.subtract( sum, Dyadic::from_f64( 0.3 ).unwrap() );
+//! assert_eq!( error, Dyadic::new( 1.into(), -55 ) );    // 2^-55
+//! ```
+
+use crate::rings::ring::{Semiring, Ring};
+use num::rational::BigRational;
+use num::traits::{Float, One, Pow, Signed, ToPrimitive, Zero};
+use num::BigInt;
+
+
+//  ---------------------------------------------------------
+//  DYADIC RATIONALS
+//  ---------------------------------------------------------
+
+/// A dyadic rational `mantissa * 2^exponent`.
+///
+/// The representation is normalized: the mantissa is odd, or zero with exponent zero.  Equal
+/// numbers therefore have equal representations.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Dyadic {
+    mantissa:   BigInt,
+    exponent:   i64,
+}
+
+impl Dyadic {
+    /// The number `mantissa * 2^exponent`.
+    pub fn new( mantissa: BigInt, exponent: i64 ) -> Dyadic {
+        match mantissa.trailing_zeros() {
+            None                =>  Dyadic{ mantissa, exponent: 0 },    // the mantissa is zero
+            Some( twos )        =>  Dyadic{ mantissa: mantissa >> twos, exponent: exponent + twos as i64 },
+        }
+    }
+
+    /// The exact value of `x`, or `None` if `x` is infinite or `NaN`.
+    pub fn from_f64( x: f64 ) -> Option< Dyadic > {
+        if ! x.is_finite() { return None }
+        let ( mantissa, exponent, sign ) =   x.integer_decode();
+        Some( Dyadic::new( BigInt::from( mantissa ) * sign, exponent as i64 ) )
+    }
+
+    /// The odd (or zero) mantissa of the normalized representation.
+    pub fn mantissa( &self ) -> &BigInt { &self.mantissa }
+
+    /// The exponent of the normalized representation.
+    pub fn exponent( &self ) -> i64 { self.exponent }
+
+    /// The exact value, as a rational number.
+    pub fn to_rational( &self ) -> BigRational {
+        let power   =   BigInt::from( 2 ).pow( self.exponent.unsigned_abs() );
+        if self.exponent >= 0 { BigRational::from_integer( &self.mantissa * power ) }
+        else { BigRational::new( self.mantissa.clone(), power ) }
+    }
+
+    /// The nearest floating point number (`0` or infinite if the exponent is out of range).
+    pub fn to_f64( &self ) -> f64 {
+        self.to_rational().to_f64().unwrap_or( if self.mantissa.is_negative() { f64::NEG_INFINITY } else { f64::INFINITY } )
+    }
+}
+
+impl From< i64 > for Dyadic {
+    fn from( n: i64 ) -> Dyadic { Dyadic::new( BigInt::from( n ), 0 ) }
+}
+
+impl From< BigInt > for Dyadic {
+    fn from( n: BigInt ) -> Dyadic { Dyadic::new( n, 0 ) }
+}
+
+
+//  ---------------------------------------------------------
+//  THE RING OBJECT
+//  ---------------------------------------------------------
+
+/// The ring of dyadic rationals, with elements of type [`Dyadic`].
+#[derive(Debug, Clone, Default)]
+pub struct DyadicRing{}
+
+impl DyadicRing {
+    /// Create a new instance of `DyadicRing`.
+    pub fn new() -> DyadicRing { DyadicRing{} }
+}
+
+impl Semiring< Dyadic > for DyadicRing
+{
+    fn is_0( &self, x: Dyadic ) -> bool { x.mantissa.is_zero() }
+    fn is_1( &self, x: Dyadic ) -> bool { x.mantissa.is_one() && x.exponent == 0 }
+    fn zero() -> Dyadic { Dyadic{ mantissa: BigInt::zero(), exponent: 0 } }
+    fn one()  -> Dyadic { Dyadic{ mantissa: BigInt::one(), exponent: 0 } }
+
+    fn add( &self, x: Dyadic, y: Dyadic ) -> Dyadic {
+        if x.mantissa.is_zero() { return y }
+        if y.mantissa.is_zero() { return x }
+        // shift the mantissa with the larger exponent, so that both have the smaller exponent
+        let exponent    =   x.exponent.min( y.exponent );
+        let shift       =   | z: Dyadic | z.mantissa << ( z.exponent - exponent ) as usize;
+        Dyadic::new( shift( x ) + shift( y ), exponent )
+    }
+
+    fn multiply( &self, x: Dyadic, y: Dyadic ) -> Dyadic {
+        // the product of odd mantissas is odd, so no normalization is needed
+        if x.mantissa.is_zero() || y.mantissa.is_zero() { return Self::zero() }
+        Dyadic{ mantissa: x.mantissa * y.mantissa, exponent: x.exponent + y.exponent }
+    }
+}
+
+impl Ring< Dyadic > for DyadicRing
+{
+    fn subtract( &self, x: Dyadic, y: Dyadic ) -> Dyadic { self.add( x, self.negate( y ) ) }
+    fn negate( &self, x: Dyadic ) -> Dyadic { Dyadic{ mantissa: - x.mantissa, exponent: x.exponent } }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::axioms::assert_ring_axioms;
+
+
+    #[test]
+    fn test_dyadic_ring() {
+        let ring        =   DyadicRing::new();
+        let dyadic      =   | x: f64 | Dyadic::from_f64( x ).unwrap();
+
+        // normalization
+        assert_eq!( Dyadic::new( 12.into(), -3 ), Dyadic::new( 3.into(), -1 ) );
+        assert_eq!( Dyadic::new( 0.into(), 5 ), DyadicRing::zero() );
+        assert_eq!( dyadic( -0. ), DyadicRing::zero() );
+        assert_eq!( dyadic( 6. ), Dyadic::from( 6 ) );
+        assert_eq!( ( dyadic( 0.75 ).mantissa().clone(), dyadic( 0.75 ).exponent() ), ( 3.into(), -2 ) );
+        assert_eq!( Dyadic::from_f64( f64::NAN ), None );
+
+        // round trips, and exact agreement with rational arithmetic
+        for &( a, b ) in [ ( 0.1, 0.2 ), ( -1e300, 3e-300 ), ( 5e-324, 1. ), ( 0., -2.5 ) ].iter() {
+            assert_eq!( dyadic( a ).to_f64(), a );
+            assert_eq!( dyadic( a ).to_rational(), BigRational::from_float( a ).unwrap() );
+            assert_eq!( ring.add( dyadic( a ), dyadic( b ) ).to_rational(), dyadic( a ).to_rational() + dyadic( b ).to_rational() );
+            assert_eq!( ring.subtract( dyadic( a ), dyadic( b ) ).to_rational(), dyadic( a ).to_rational() - dyadic( b ).to_rational() );
+            assert_eq!( ring.multiply( dyadic( a ), dyadic( b ) ).to_rational(), dyadic( a ).to_rational() * dyadic( b ).to_rational() );
+        }
+        assert!( ring.is_1( ring.multiply( dyadic( 0.5 ), Dyadic::from( 2 ) ) ) );
+        assert!( ring.is_0( ring.subtract( dyadic( 0.1 ), dyadic( 0.1 ) ) ) );
+
+        assert_ring_axioms( ring, [ 0., 1., -0.5, 0.1, 3e10, -7.25e-9 ].iter().map( |&x| dyadic( x ) ) );
+    }
+
+}
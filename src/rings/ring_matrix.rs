@@ -0,0 +1,143 @@
+//! Rings of small dense square matrices.
+//!
+//! A [`MatrixRing<RingOperator, N>`](MatrixRing) performs arithmetic on `N x N` matrices,
+//! represented as arrays `[[Element; N]; N]`, whose entries lie in the ring represented by
+//! `RingOperator`.  A block-structured sparse matrix can then be stored as a sparse matrix whose
+//! coefficients are blocks, and processed with all of the existing vector transforms and merges.
+//!
+//! Matrix multiplication is not commutative.  Operations that multiply a vector by a scalar, such
+//! as [`scale`](crate::vectors::vector_transforms::Transforms::scale), multiply each coefficient
+//! on the right.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::rings::ring_matrix::MatrixRing;
+//! use solar::rings::field_prime::GFp;
+//! use solar::rings::ring::{Semiring, Ring};
+//! use solar::vectors::vector_transforms::Transforms;
+//!
+//! // 2 x 2 blocks over GF(3)
+//! let ring    =   MatrixRing::< _, 2 >::new( GFp::new( 3 ) );
+//! let a       =   [ [ 1, 2 ], [ 0, 1 ] ];
+//! let b       =   [ [ 0, 1 ], [ 1, 0 ] ];
+//! assert_eq!( ring.multiply( a, b ), [ [ 2, 1 ], [ 1, 0 ] ] );
+//! assert_eq!( ring.multiply( b, a ), [ [ 0, 1 ], [ 1, 2 ] ] );
+//!
+//! // a block vector, with two blocks in position 0 that cancel
+//! let entries         =   vec![ (0, a), (0, ring.negate( a )), (1, b) ];
+//! let simplified: Vec<_>  =   entries.into_iter().gather_drop_count( ring ).collect();
+//! assert_eq!( simplified, vec![ (1, b) ] );
+//! ```
+
+use crate::rings::ring::{Semiring, Ring};
+
+
+/// The ring of `N x N` matrices with entries in the ring represented by `RingOperator`.
+#[derive(Debug, Clone, Default)]
+pub struct MatrixRing< RingOperator, const N: usize > {
+    ring:   RingOperator,
+}
+
+impl < RingOperator, const N: usize > MatrixRing< RingOperator, N > {
+    /// The ring of `N x N` matrices with entries in the ring represented by `ring`.
+    pub fn new( ring: RingOperator ) -> Self { MatrixRing{ ring } }
+
+    /// The ring object for the entries.
+    pub fn ring( &self ) -> &RingOperator { &self.ring }
+
+    /// The matrix with `x` on the diagonal and zero elsewhere.
+    pub fn scalar< Element >( &self, x: Element ) -> [ [ Element; N ]; N ]
+        where   RingOperator:   Semiring< Element >,
+                Element:        Clone,
+    {
+        std::array::from_fn( |i| std::array::from_fn( |j| if i == j { x.clone() } else { RingOperator::zero() } ) )
+    }
+
+    /// The transpose of `x`.
+    pub fn transpose< Element >( &self, x: [ [ Element; N ]; N ] ) -> [ [ Element; N ]; N ]
+        where   Element:        Clone,
+    {
+        std::array::from_fn( |i| std::array::from_fn( |j| x[ j ][ i ].clone() ) )
+    }
+}
+
+impl < RingOperator, Element, const N: usize > Semiring< [ [ Element; N ]; N ] > for MatrixRing< RingOperator, N >
+    where   RingOperator:   Semiring< Element >,
+            Element:        Clone,
+{
+    fn is_0( &self, x: [ [ Element; N ]; N ] ) -> bool {
+        x.iter().flatten().all( |entry| self.ring.is_0( entry.clone() ) )
+    }
+    fn is_1( &self, x: [ [ Element; N ]; N ] ) -> bool {
+        x.iter().enumerate().all( |( i, row )|
+            row.iter().enumerate().all( |( j, entry )|
+                if i == j { self.ring.is_1( entry.clone() ) } else { self.ring.is_0( entry.clone() ) }
+            )
+        )
+    }
+    fn zero() -> [ [ Element; N ]; N ] {
+        std::array::from_fn( |_| std::array::from_fn( |_| RingOperator::zero() ) )
+    }
+    fn one() -> [ [ Element; N ]; N ] {
+        std::array::from_fn( |i| std::array::from_fn( |j| if i == j { RingOperator::one() } else { RingOperator::zero() } ) )
+    }
+
+    fn add( &self, x: [ [ Element; N ]; N ], y: [ [ Element; N ]; N ] ) -> [ [ Element; N ]; N ] {
+        std::array::from_fn( |i| std::array::from_fn( |j| self.ring.add( x[ i ][ j ].clone(), y[ i ][ j ].clone() ) ) )
+    }
+
+    fn multiply( &self, x: [ [ Element; N ]; N ], y: [ [ Element; N ]; N ] ) -> [ [ Element; N ]; N ] {
+        std::array::from_fn( |i| std::array::from_fn( |j|
+            self.ring.sum_iter( ( 0 .. N ).map( |k| self.ring.multiply( x[ i ][ k ].clone(), y[ k ][ j ].clone() ) ) )
+        ) )
+    }
+}
+
+impl < RingOperator, Element, const N: usize > Ring< [ [ Element; N ]; N ] > for MatrixRing< RingOperator, N >
+    where   RingOperator:   Ring< Element >,
+            Element:        Clone,
+{
+    fn subtract( &self, x: [ [ Element; N ]; N ], y: [ [ Element; N ]; N ] ) -> [ [ Element; N ]; N ] {
+        std::array::from_fn( |i| std::array::from_fn( |j| self.ring.subtract( x[ i ][ j ].clone(), y[ i ][ j ].clone() ) ) )
+    }
+    fn negate( &self, x: [ [ Element; N ]; N ] ) -> [ [ Element; N ]; N ] {
+        std::array::from_fn( |i| std::array::from_fn( |j| self.ring.negate( x[ i ][ j ].clone() ) ) )
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::axioms::assert_ring_axioms;
+    use crate::rings::field_prime::GF2;
+    use crate::rings::ring_native::NativeRing;
+
+
+    #[test]
+    fn test_matrix_ring() {
+        let ring        =   MatrixRing::< _, 3 >::new( NativeRing::< i64 >::new() );
+        let x           =   [ [ 1, 2, 0 ], [ 0, -1, 3 ], [ 4, 0, 1 ] ];
+
+        assert!( ring.is_1( MatrixRing::< NativeRing< i64 >, 3 >::one() ) );
+        assert!( ring.is_0( ring.subtract( x, x ) ) );
+        assert_eq!( ring.scalar( 5 ), ring.multiply( ring.scalar( 5 ), MatrixRing::< NativeRing< i64 >, 3 >::one() ) );
+        assert_eq!( ring.transpose( ring.transpose( x ) ), x );
+        assert_eq!( ring.multiply( x, ring.transpose( x ) ), [ [ 5, -2, 4 ], [ -2, 10, 3 ], [ 4, 3, 17 ] ] );
+        assert_eq!( ring.power( x, 3 ), ring.multiply( x, ring.multiply( x, x ) ) );
+
+        let samples     =   vec![ [ [ 1, 2, 0 ], [ 0, -1, 3 ], [ 4, 0, 1 ] ], ring.scalar( -2 ), ring.transpose( x ), [ [ 0, 0, 7 ], [ 0, 0, 0 ], [ 1, 1, 1 ] ] ];
+        assert_ring_axioms( ring, samples );
+
+        // a ring with zero divisors
+        let ring        =   MatrixRing::< _, 2 >::new( GF2::new() );
+        let nilpotent   =   [ [ false, true ], [ false, false ] ];
+        assert!( ! ring.is_0( nilpotent ) && ring.is_0( ring.multiply( nilpotent, nilpotent ) ) );
+    }
+
+}
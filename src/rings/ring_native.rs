@@ -16,7 +16,7 @@
 // //! Zero-memory structs representing semirings/rings/division rings that are native to Rust.
 
 
-use crate::rings::ring::{Semiring, Ring, DivisionRing};
+use crate::rings::ring::{Semiring, Ring, DivisionRing, GcdDomain};
 use std::marker::PhantomData;
 
 //----------------------------------------------------------
@@ -234,6 +234,22 @@ impl    < Element >
     fn negate( &self, x: Element ) -> Element { - x }
 }
 
+impl    < Element > 
+        GcdDomain < Element > for NativeRing 
+        < Element >  
+    where 
+        Element:    num::traits::Num + 
+                    num::Integer +
+                    core::ops::Neg  < Output = Element > +
+                    std::clone::Clone
+{
+    /// The nonnegative greatest common divisor.
+    fn gcd( &self, x: Element, y: Element ) -> Element { x.gcd( &y ) }
+
+    /// `x/y`, rounded toward zero.
+    fn divide_exact( &self, x: Element, y: Element ) -> Element { x / y }
+}
+
 
 //----------------------------------------------------------
 //  DIVISION RINGS NATIVE TO RUST
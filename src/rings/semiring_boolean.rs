@@ -0,0 +1,101 @@
+//! The Boolean semiring.
+//!
+//! The Boolean semiring has elements `false` and `true`, with OR as addition and AND as
+//! multiplication.  It is not a ring (`true` has no additive inverse), which is what
+//! distinguishes it from [`GF2`](crate::rings::field_prime::GF2): in the Boolean semiring
+//! `true + true = true`.
+//!
+//! Matrix products over the Boolean semiring compute reachability.  If `A` is the adjacency
+//! matrix of a directed graph, then entry `(i, j)` of `A^k` is `true` iff there is a walk of
+//! length exactly `k` from `i` to `j`; so entry `(i, j)` of `( I + A )^k` is `true` iff there is a
+//! walk of length at most `k`.
+
+use crate::rings::ring::Semiring;
+
+
+/// The Boolean semiring: addition is OR, and multiplication is AND.
+///
+/// # Examples
+///
+/// ```
+/// use solar::matrices::implementors::product::ProductMatrixOracle;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+/// use solar::rings::semiring_boolean::BooleanSemiring;
+///
+/// // the path 0 -> 1 -> 2 -> 3, plus a loop at every vertex
+/// let step    =   VecOfVec::new(
+///                     MajorDimension::Row,
+///                     vec![ vec![ (0, true), (1, true) ], vec![ (1, true), (2, true) ], vec![ (2, true), (3, true) ], vec![ (3, true) ] ],
+///                 );
+///
+/// // vertices reachable from 0 in at most two steps
+/// let two_steps   =   ProductMatrixOracle::new( &step, &step, BooleanSemiring::new() );
+/// assert_eq!( two_steps.view_major( 0 ).collect::<Vec<_>>(), vec![ (0, true), (1, true), (2, true) ] );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BooleanSemiring{}
+
+impl BooleanSemiring {
+    /// Create a new instance of `BooleanSemiring`.
+    pub fn new() -> BooleanSemiring { BooleanSemiring{} }
+}
+
+impl Semiring< bool > for BooleanSemiring
+{
+    fn is_0( &self, x: bool ) -> bool { ! x }
+    fn is_1( &self, x: bool ) -> bool {   x }
+    fn zero() -> bool { false }
+    fn one()  -> bool { true  }
+
+    fn add( &self, x : bool, y : bool ) -> bool { x || y }
+    fn multiply( &self, x : bool, y: bool ) -> bool { x && y }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::implementors::product::ProductMatrixOracle;
+    use crate::matrices::implementors::vec_of_vec::VecOfVec;
+    use crate::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+
+
+    #[test]
+    fn test_transitive_closure_by_repeated_squaring() {
+        // edges of a directed graph with two strongly connected pieces, { 0, 1, 2 } and { 4, 5 }
+        let edges       =   [ (0, 1), (1, 2), (2, 0), (2, 3), (4, 5), (5, 4), (3, 6) ];
+        let n           =   7;
+
+        // ( I + A )^( 2^k ) for k = 0, 1, 2, ..; 2^3 >= n - 1, so three squarings suffice
+        let mut closure: Vec< Vec< _ > >    =   ( 0 .. n ).map( |i| vec![ ( i, true ) ] ).collect();
+        for ( i, j ) in edges.iter().cloned() { closure[ i ].push( ( j, true ) ) }
+        for view in closure.iter_mut() { view.sort() }
+        for _ in 0 .. 3 {
+            let matrix  =   VecOfVec::new( MajorDimension::Row, closure.clone() );
+            let square  =   ProductMatrixOracle::new( &matrix, &matrix, BooleanSemiring::new() );
+            closure     =   ( 0 .. n ).map( |i| square.view_major( i ).collect() ).collect();
+        }
+
+        // compare with depth-first search
+        for ( start, view ) in closure.iter().enumerate() {
+            let mut reached     =   vec![ false; n ];
+            let mut stack       =   vec![ start ];
+            while let Some( v ) = stack.pop() {
+                if reached[ v ] { continue }
+                reached[ v ]    =   true;
+                stack.extend( edges.iter().filter( |edge| edge.0 == v ).map( |edge| edge.1 ) );
+            }
+            let expected: Vec< _ >  =   ( 0 .. n ).filter( |v| reached[ *v ] ).map( |v| ( v, true ) ).collect();
+            assert_eq!( view, &expected );
+        }
+
+        // unlike GF2, true + true = true
+        assert!( BooleanSemiring::new().add( true, true ) );
+    }
+
+}
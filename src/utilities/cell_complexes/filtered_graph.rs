@@ -0,0 +1,211 @@
+//! Persistent homology of filtered graphs, in dimensions 0 and 1.
+//!
+//! Filtrations of graphs (and of the clique complexes they generate, truncated at dimension 2)
+//! are common enough to deserve a specialized pipeline.  The function [`graph_persistence`]
+//!
+//! - computes the dimension 0 barcode with a union-find structure (using the elder rule), and
+//! - computes the dimension 1 barcode by reducing the boundary matrix of the triangles over
+//!   GF2, where each edge is identified with its **rank** (its position in the filtration order)
+//!   and each triangle with its position in the sorted list of triangles.
+//!
+//! This avoids building simplex bimaps or a full boundary matrix.
+//!
+//! Bars of length zero are not reported.
+
+use crate::rings::field_prime::GF2;
+use crate::matrix_factorization::vec_of_vec::right_reduce;
+use std::collections::{HashMap, HashSet};
+
+
+//  ---------------------------------------------------------------------------
+//  BARCODE
+//  ---------------------------------------------------------------------------
+
+
+/// A bar `(birth, death)`; the death is `None` if the class never dies.
+pub type GraphBar< Filtration > = ( Filtration, Option< Filtration > );
+
+/// The barcodes of a filtered graph in dimensions 0 and 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphBarcode< Filtration > {
+    /// Bars in dimension 0 (connected components).
+    pub dim0:   Vec< GraphBar< Filtration > >,
+    /// Bars in dimension 1 (cycles).
+    pub dim1:   Vec< GraphBar< Filtration > >,
+}
+
+
+//  ---------------------------------------------------------------------------
+//  UNION FIND
+//  ---------------------------------------------------------------------------
+
+
+/// Find the root of `x`, compressing the path along the way.
+fn find_root( parent: &mut [ usize ], x: usize ) -> usize {
+    let mut root    =   x;
+    while parent[ root ] != root { root = parent[ root ] }
+    let mut x       =   x;
+    while parent[ x ] != root { let next = parent[ x ]; parent[ x ] = root; x = next; }
+    root
+}
+
+
+//  ---------------------------------------------------------------------------
+//  PERSISTENCE
+//  ---------------------------------------------------------------------------
+
+
+/// Compute the barcodes of a filtered graph in dimensions 0 and 1.
+///
+/// - `vertex_filtration[v]` is the time at which vertex `v` enters the filtration.
+/// - `edges` is a list of triples `(u, v, t)`; the edge `{u, v}` enters at time `t`, which should
+///   be no earlier than the times of `u` and `v`.  Each edge should appear at most once;
+///   self-loops are ignored.
+/// - If `fill_triangles == true`, each triangle of the graph enters at the time of its latest
+///   edge (as in a Vietoris-Rips / clique complex), and may kill a cycle.  Otherwise no
+///   triangles are added, and every cycle lives forever.
+///
+/// Bars are sorted in ascending order.  Panics if two filtration values cannot be compared
+/// (e.g. if one of them is NaN).
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::filtered_graph::graph_persistence;
+///
+/// // a square with a diagonal that appears late
+/// let vertices    =   vec![ 0., 0., 0., 0. ];
+/// let edges       =   vec![ (0, 1, 1.), (1, 2, 1.), (2, 3, 1.), (0, 3, 2.), (0, 2, 3.) ];
+///
+/// let barcode     =   graph_persistence( &vertices, &edges, true );
+/// assert_eq!( barcode.dim0, vec![ (0., Some(1.)), (0., Some(1.)), (0., Some(1.)), (0., None) ] );
+/// assert_eq!( barcode.dim1, vec![ (2., Some(3.)) ] );
+///
+/// // without triangles, the square persists forever, and the diagonal creates a second cycle
+/// let barcode     =   graph_persistence( &vertices, &edges, false );
+/// assert_eq!( barcode.dim1, vec![ (2., None), (3., None) ] );
+/// ```
+pub fn  graph_persistence< Filtration >(
+            vertex_filtration:      &   [ Filtration ],
+            edges:                  &   [ ( usize, usize, Filtration ) ],
+            fill_triangles:             bool,
+        )
+        ->
+        GraphBarcode< Filtration >
+
+        where   Filtration:     Clone + PartialOrd,
+{
+    let compare             =   | a: &Filtration, b: &Filtration | a.partial_cmp( b ).expect( "filtration values must be comparable" );
+
+    // rank the edges by filtration value (ties are broken by the order of the input)
+    let mut edges_ranked: Vec< ( usize, usize, Filtration ) >
+                            =   edges.iter()
+                                    .filter( |e| e.0 != e.1 )
+                                    .map( |e| ( e.0.min( e.1 ), e.0.max( e.1 ), e.2.clone() ) )
+                                    .collect();
+    edges_ranked.sort_by( |a, b| compare( &a.2, &b.2 ) );
+
+    //  DIMENSION 0: UNION FIND
+    //  -----------------------
+
+    let mut parent: Vec< usize >    =   ( 0 .. vertex_filtration.len() ).collect();
+    let mut dim0                    =   Vec::new();
+    let mut is_negative_edge        =   vec![ false; edges_ranked.len() ];
+
+    for (rank, (u, v, time)) in edges_ranked.iter().enumerate() {
+        let ( root_u, root_v )      =   ( find_root( &mut parent, *u ), find_root( &mut parent, *v ) );
+        if root_u == root_v { continue }
+        is_negative_edge[ rank ]    =   true;
+
+        // elder rule: the component born later dies; the root of each component is its oldest vertex
+        let ( elder, younger )      =   match compare( &vertex_filtration[ root_u ], &vertex_filtration[ root_v ] ) {
+                                            std::cmp::Ordering::Greater     =>  ( root_v, root_u ),
+                                            _                               =>  ( root_u, root_v ),
+                                        };
+        parent[ younger ]           =   elder;
+        if vertex_filtration[ younger ] < *time {
+            dim0.push( ( vertex_filtration[ younger ].clone(), Some( time.clone() ) ) );
+        }
+    }
+    for (vertex, time) in vertex_filtration.iter().enumerate() {
+        if find_root( &mut parent, vertex ) == vertex { dim0.push( ( time.clone(), None ) ) }
+    }
+
+    //  DIMENSION 1: EDGE-TRIANGLE REDUCTION
+    //  ------------------------------------
+
+    let mut triangles: Vec< ( Filtration, Vec< (usize, bool) > ) >     =   Vec::new();
+    if fill_triangles {
+        let rank_of: HashMap< (usize, usize), usize >   =   edges_ranked.iter().enumerate().map( |(rank, e)| ( ( e.0, e.1 ), rank ) ).collect();
+        let mut neighbors: Vec< HashSet< usize > >      =   vec![ HashSet::new(); vertex_filtration.len() ];
+        for (u, v, _) in edges_ranked.iter() { neighbors[ *u ].insert( *v ); }
+
+        for (u, v, _) in edges_ranked.iter() {
+            for w in neighbors[ *v ].iter().filter( |w| neighbors[ *u ].contains( w ) ) {
+                let mut boundary    =   vec![ rank_of[ &( *u, *v ) ], rank_of[ &( *u, *w ) ], rank_of[ &( *v, *w ) ] ];
+                boundary.sort_unstable();
+                let time            =   edges_ranked[ boundary[ 2 ] ].2.clone();
+                triangles.push( ( time, boundary.into_iter().map( |rank| ( rank, true ) ).collect() ) );
+            }
+        }
+        // ties are broken by reverse lexicographic order on edge ranks, so the result is deterministic
+        triangles.sort_by( |a, b| compare( &a.0, &b.0 ).then_with( || a.1.iter().rev().cmp( b.1.iter().rev() ) ) );
+    }
+
+    let death_times: Vec< Filtration >      =   triangles.iter().map( |t| t.0.clone() ).collect();
+    let mut matrix: Vec< Vec< (usize, bool) > >
+                                            =   triangles.into_iter().map( |t| t.1 ).collect();
+    let pivots                              =   right_reduce( &mut matrix, GF2::new() );
+
+    let mut dim1                            =   Vec::new();
+    for (rank, edge) in edges_ranked.iter().enumerate() {
+        if is_negative_edge[ rank ] { continue }
+        match pivots.get( &rank ) {
+            Some( col )     =>  { if edge.2 < death_times[ *col ] { dim1.push( ( edge.2.clone(), Some( death_times[ *col ].clone() ) ) ) } }
+            None            =>  dim1.push( ( edge.2.clone(), None ) ),
+        }
+    }
+
+    dim0.sort_by( |a, b| compare_bars( a, b, &compare ) );
+    dim1.sort_by( |a, b| compare_bars( a, b, &compare ) );
+    GraphBarcode{ dim0, dim1 }
+}
+
+/// Compare bars by birth, then by death (with `None` treated as infinity).
+fn compare_bars< Filtration, F >( a: &GraphBar< Filtration >, b: &GraphBar< Filtration >, compare: &F ) -> std::cmp::Ordering
+    where   F:  Fn( &Filtration, &Filtration ) -> std::cmp::Ordering,
+{
+    compare( &a.0, &b.0 ).then_with(
+        ||  match ( &a.1, &b.1 ) {
+                ( Some( x ), Some( y ) )    =>  compare( x, y ),
+                ( Some( _ ), None      )    =>  std::cmp::Ordering::Less,
+                ( None,      Some( _ ) )    =>  std::cmp::Ordering::Greater,
+                ( None,      None      )    =>  std::cmp::Ordering::Equal,
+            }
+    )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_graph_persistence_complete_graph() {
+        // the complete graph on 4 vertices, with all edges at distinct times
+        let vertices    =   vec![ 0, 0, 1, 1 ];
+        let edges       =   vec![ (0, 1, 2), (2, 3, 3), (1, 2, 4), (0, 3, 5), (0, 2, 6), (1, 3, 7) ];
+
+        let barcode     =   graph_persistence( &vertices, &edges, true );
+        assert_eq!( barcode.dim0, vec![ (0, Some(2)), (0, None), (1, Some(3)), (1, Some(4)) ] );
+        // the cycles born at times 6 and 7 die immediately, so they are not reported
+        assert_eq!( barcode.dim1, vec![ (5, Some(6)) ] );
+
+        // Euler characteristic check when triangles are omitted: #cycles = #edges - #vertices + #components
+        let barcode     =   graph_persistence( &vertices, &edges, false );
+        assert_eq!( barcode.dim1.len(), 6 - 4 + 1 );
+        assert!( barcode.dim1.iter().all( |bar| bar.1.is_none() ) );
+    }
+}
@@ -3,4 +3,6 @@
 //! 
 //! **NOTE** These tools might be moved to ExHACT in the future)
 
-pub mod simplices_unweighted;
\ No newline at end of file
+pub mod simplices_unweighted;
+pub mod filtered_graph;
+pub mod verify;
\ No newline at end of file
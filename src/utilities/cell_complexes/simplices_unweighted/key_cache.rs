@@ -0,0 +1,206 @@
+//! A memoized translation layer between simplices and integer ordinals.
+//!
+//! Oracles whose major and minor keys are simplices often need to convert those keys to
+//! integers (and back) many times during a reduction.  Hashing the same vertex vector over and
+//! over is wasteful, so [`SimplexOrdinalCache`] assigns each simplex an ordinal the first time
+//! it is seen, and remembers the assignment.  An oracle can then identify its major keys by
+//! ordinal, so that each simplex is hashed once per lookup of a facet (or cofacet), and never
+//! when it is read back from its ordinal.
+//!
+//! The cache uses interior mutability, so it can be updated through a shared reference.  To
+//! share one cache between several oracles of the same complex (e.g. a boundary oracle and a
+//! coboundary oracle), wrap it in an `Rc` and give each oracle a clone of the `Rc`.
+
+use crate::utilities::sequences_and_ordinals::BiMapSequential;
+use crate::utilities::cell_complexes::simplices_unweighted::simplex::Simplex;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+
+/// Assigns ordinals `0, 1, 2, ..` to simplices in the order they are first encountered, and
+/// caches the assignment in both directions.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::key_cache::SimplexOrdinalCache;
+/// use solar::utilities::cell_complexes::simplices_unweighted::simplex::Simplex;
+/// use std::rc::Rc;
+///
+/// // two "oracles" sharing the same cache
+/// let cache               =   Rc::new( SimplexOrdinalCache::new() );
+/// let boundary_cache      =   Rc::clone( &cache );
+/// let coboundary_cache    =   Rc::clone( &cache );
+///
+/// let edge                =   Simplex{ vertices: vec![0, 1] };
+/// assert_eq!( boundary_cache.ord( edge.clone() ), 0 );              // first encounter: assign a new ordinal
+/// assert_eq!( coboundary_cache.ord( edge.clone() ), 0 );            // second encounter: read from the cache
+/// assert_eq!( coboundary_cache.simplex( 0 ), Some( edge.clone() ) );
+///
+/// assert_eq!( cache.num_misses(), 1 );
+/// assert_eq!( cache.num_hits(),   1 );
+/// ```
+#[derive(Debug, Clone)]
+pub struct SimplexOrdinalCache< Vertex >
+    where   Vertex:     Clone + Hash + Eq
+{
+    bimap:      RefCell< BiMapSequential< Simplex< Vertex > > >,
+    hits:       Cell< usize >,
+    misses:     Cell< usize >,
+}
+
+impl    < Vertex >
+        SimplexOrdinalCache
+        < Vertex >
+    where   Vertex:     Clone + Hash + Eq
+{
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        SimplexOrdinalCache::from_bimap(
+            BiMapSequential{ ord_to_val: Vec::new(), val_to_ord: HashMap::new() }
+        )
+    }
+
+    /// Create a cache pre-populated with the assignments in `bimap`.
+    ///
+    /// Simplices not in the bimap will receive ordinals `bimap.ord_to_val.len()`,
+    /// `bimap.ord_to_val.len() + 1`, etc.
+    pub fn from_bimap( bimap: BiMapSequential< Simplex< Vertex > > ) -> Self {
+        SimplexOrdinalCache{ bimap: RefCell::new( bimap ), hits: Cell::new(0), misses: Cell::new(0) }
+    }
+
+    /// The ordinal of `simplex`; if the simplex has not been seen before, it is assigned the
+    /// next available ordinal.
+    ///
+    /// Either way, `simplex` is hashed once; it is cloned once if it receives a new ordinal, and
+    /// not at all otherwise.  Use [`SimplexOrdinalCache::ord_if_cached`] to look up a borrowed
+    /// simplex.
+    pub fn ord( &self, simplex: Simplex< Vertex > ) -> usize {
+        let mut bimap   =   self.bimap.borrow_mut();
+        let BiMapSequential{ ord_to_val, val_to_ord }  =   &mut *bimap;
+        match val_to_ord.entry( simplex ) {
+            Entry::Occupied( entry )    =>  {
+                self.hits.set( self.hits.get() + 1 );
+                *entry.get()
+            }
+            Entry::Vacant( entry )      =>  {
+                self.misses.set( self.misses.get() + 1 );
+                let ord         =   ord_to_val.len();
+                ord_to_val.push( entry.key().clone() );
+                entry.insert( ord );
+                ord
+            }
+        }
+    }
+
+    /// The ordinal of `simplex`, if it has already been assigned; never modifies the cache.
+    pub fn ord_if_cached( &self, simplex: &Simplex< Vertex > ) -> Option< usize > {
+        self.bimap.borrow().ord( simplex )
+    }
+
+    /// The simplex with ordinal `ord`, if one has been assigned.
+    pub fn simplex( &self, ord: usize ) -> Option< Simplex< Vertex > > {
+        self.bimap.borrow().val( ord )
+    }
+
+    /// The number of simplices in the cache.
+    pub fn len( &self ) -> usize { self.bimap.borrow().ord_to_val.len() }
+
+    /// Returns `true` if the cache contains no simplices.
+    pub fn is_empty( &self ) -> bool { self.len() == 0 }
+
+    /// The number of calls to [`SimplexOrdinalCache::ord`] that found the simplex in the cache.
+    pub fn num_hits( &self ) -> usize { self.hits.get() }
+
+    /// The number of calls to [`SimplexOrdinalCache::ord`] that had to assign a new ordinal.
+    pub fn num_misses( &self ) -> usize { self.misses.get() }
+
+    /// Consume the cache, returning the bijection it has accumulated.
+    pub fn into_bimap( self ) -> BiMapSequential< Simplex< Vertex > > { self.bimap.into_inner() }
+}
+
+impl    < Vertex >
+        Default for SimplexOrdinalCache
+        < Vertex >
+    where   Vertex:     Clone + Hash + Eq
+{
+    fn default() -> Self { Self::new() }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::matrices::matrix_oracle::OracleMajorAscend;
+    use crate::rings::ring_native::NativeRing;
+    use crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets;
+    use crate::utilities::cell_complexes::simplices_unweighted::facets::ordered_subsimplices_up_thru_dim_concatenated_vec;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_cache_extends_prepopulated_bimap() {
+        let simplex     =   | v: Vec< usize > | Simplex{ vertices: v };
+        let bimap       =   BiMapSequential::from_vec( vec![ simplex( vec![0] ), simplex( vec![1] ) ] );
+        let cache       =   SimplexOrdinalCache::from_bimap( bimap );
+
+        assert_eq!( cache.ord( simplex( vec![1]    ) ), 1 );
+        assert_eq!( cache.ord( simplex( vec![0, 1] ) ), 2 );
+        assert_eq!( cache.ord( simplex( vec![0, 1] ) ), 2 );
+        assert_eq!( cache.ord_if_cached( &simplex( vec![2] ) ), None );
+        assert_eq!( ( cache.num_hits(), cache.num_misses(), cache.len() ), ( 2, 1, 3 ) );
+
+        let bimap       =   cache.into_bimap();
+        assert_eq!( bimap.ord( &simplex( vec![0, 1] ) ), Some( 2 ) );
+    }
+
+    /// A boundary oracle whose major and minor keys are the ordinals assigned by a shared cache.
+    struct CachedBoundary {
+        cache:      Rc< SimplexOrdinalCache< usize > >,
+    }
+
+    impl < 'a > OracleMajorAscend< 'a, usize, usize, i64 > for CachedBoundary {
+        type PairMajorAscend = ( usize, i64 );
+        type ViewMajorAscend = Vec< ( usize, i64 ) >;
+        fn view_major_ascend<'b: 'a>( &'b self, index: usize ) -> Self::ViewMajorAscend {
+            // reading the simplex back from its ordinal involves no hashing
+            let simplex     =   self.cache.simplex( index ).unwrap();
+            if simplex.vertices.len() == 1 { return Vec::new() }
+            let mut view: Vec< _ >
+                            =   ( 0 .. simplex.vertices.len() )
+                                    .map(   |i|
+                                            {
+                                                let mut facet   =   simplex.vertices.clone();
+                                                facet.remove( i );
+                                                ( self.cache.ord( Simplex{ vertices: facet } ), if i % 2 == 0 { 1 } else { -1 } )
+                                            }
+                                        )
+                                    .collect();
+            view.sort();
+            view
+        }
+    }
+
+    #[test]
+    fn test_boundary_oracle_hashes_each_facet_once() {
+        let facets          =   vec![ vec![0, 1, 2], vec![0, 3] ];
+        let simplices       =   ordered_subsimplices_up_thru_dim_concatenated_vec( &facets, 2 );
+        let cache           =   Rc::new( SimplexOrdinalCache::from_bimap(
+                                    BiMapSequential::from_vec( simplices.iter().cloned().map( |vertices| Simplex{ vertices } ).collect() )
+                                ) );
+        let oracle          =   CachedBoundary{ cache: Rc::clone( &cache ) };
+
+        // the oracle agrees with the boundary matrix computed directly
+        let expected        =   boundary_matrix_from_complex_facets( &BiMapSequential::from_vec( simplices.clone() ), NativeRing::< i64 >::new() );
+        for ( ord, column ) in expected.iter().enumerate() {
+            assert_eq!( &oracle.view_major_ascend( ord ), column );
+        }
+
+        // every facet was hashed exactly once per lookup, and found in the cache
+        let num_facets      =   simplices.iter().filter( |x| x.len() > 1 ).map( |x| x.len() ).sum::< usize >();
+        assert_eq!( ( cache.num_hits(), cache.num_misses(), cache.len() ), ( num_facets, 0, simplices.len() ) );
+    }
+}
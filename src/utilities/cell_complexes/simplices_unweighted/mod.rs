@@ -1,3 +1,6 @@
 pub mod facets;
 pub mod simplex;
-pub mod boundary_matrices;
\ No newline at end of file
+pub mod boundary_matrices;
+pub mod relabel;
+pub mod standard_complexes;
+pub mod key_cache;
@@ -0,0 +1,208 @@
+//! Relabel the vertices of a simplicial complex, and check for isomorphisms.
+//!
+//! Vertex relabelings are represented by vectors `f = [f0, .., fn]` of type `Vec< usize >`,
+//! where `f[old_vertex] = new_vertex`; this is the same convention used by
+//! [`simplex_perm_o2n_from_vertex_perm_o2n`].
+
+use crate::utilities::sequences_and_ordinals::{BiMapSequential};
+use crate::utilities::indexing_and_bijection::{compose_f_after_g};
+use crate::utilities::cell_complexes::simplices_unweighted::facets::{ordered_subsimplices_up_thru_dim_concatenated_vec};
+use crate::utilities::cell_complexes::simplices_unweighted::simplex::{simplex_perm_o2n_from_vertex_perm_o2n};
+use crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices::{boundary_matrix_from_complex_facets};
+use crate::rings::ring::{Semiring, Ring};
+
+
+//  ---------------------------------------------------------------------------
+//  RELABEL SIMPLICES
+//  ---------------------------------------------------------------------------
+
+
+/// Apply a vertex relabeling to each simplex in a sequence; the vertices of each new simplex
+/// are sorted in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::relabel::relabel_simplices;
+///
+/// let facets      =   vec![ vec![0, 1, 2], vec![0, 3] ];
+/// let relabeled   =   relabel_simplices( &facets, &vec![3, 2, 1, 0] );
+/// assert_eq!( relabeled, vec![ vec![1, 2, 3], vec![0, 3] ] );
+/// ```
+pub fn  relabel_simplices(
+            simplices:                  &   [ Vec< usize > ],
+            vertex_perm_old_to_new:     &   Vec< usize >
+        )
+        ->
+        Vec< Vec< usize > >
+{
+    simplices
+        .iter()
+        .map(   |simplex|
+                {
+                    let mut new_simplex     =   compose_f_after_g( vertex_perm_old_to_new, simplex );
+                    new_simplex.sort();
+                    new_simplex
+                }
+        )
+        .collect()
+}
+
+
+//  ---------------------------------------------------------------------------
+//  RELABEL A WHOLE COMPLEX
+//  ---------------------------------------------------------------------------
+
+
+/// The data associated to a complex after its vertices have been relabeled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelabeledComplex< RingElt > {
+    /// The relabeled facets (vertices sorted in ascending order).
+    pub facets:             Vec< Vec< usize > >,
+    /// Bijection between the simplices of the relabeled complex and {0, .., N}; simplices
+    /// are ordered first by dimension, then lexicographically.
+    pub simplex_bimap:      BiMapSequential< Vec< usize > >,
+    /// The boundary matrix of the relabeled complex, indexed by `simplex_bimap`.
+    pub boundary:           Vec< Vec< (usize, RingElt) > >,
+    /// The function `old_simplex_number -> new_simplex_number`.
+    pub simplex_perm_o2n:   Vec< usize >,
+}
+
+/// Apply a vertex relabeling to a complex, returning the relabeled facets, simplex bimap,
+/// and boundary matrix in one call.
+///
+/// The simplices of the original complex are numbered by
+/// [`ordered_subsimplices_up_thru_dim_concatenated_vec`]`( facets, max_dim )`, i.e. first by
+/// dimension, then lexicographically.  The same convention is used for the relabeled complex.
+/// The boundary matrix is recomputed (rather than permuted) because relabeling can change the
+/// orientation of a simplex, and therefore the signs of its boundary.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::relabel::relabel_complex;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let facets      =   vec![ vec![0, 1] ];
+/// let relabeled   =   relabel_complex( &facets, &vec![1, 0], 1, NativeRing::<i64>::new() );
+///
+/// assert_eq!( relabeled.facets, vec![ vec![0, 1] ] );
+/// assert_eq!( relabeled.simplex_perm_o2n, vec![ 1, 0, 2 ] );
+/// assert_eq!( relabeled.boundary, vec![ vec![], vec![], vec![ (0, -1), (1, 1) ] ] );
+/// ```
+pub fn  relabel_complex< RingOp, RingElt >(
+            facets:                     &   Vec< Vec< usize > >,
+            vertex_perm_old_to_new:     &   Vec< usize >,
+            max_dim:                        usize,
+            ring:                           RingOp,
+        )
+        ->
+        RelabeledComplex< RingElt >
+
+        where   RingOp:     Semiring< RingElt > + Ring< RingElt >,
+{
+    let simplices_old       =   ordered_subsimplices_up_thru_dim_concatenated_vec( facets, max_dim );
+    let simplex_perm_o2n    =   simplex_perm_o2n_from_vertex_perm_o2n( &simplices_old, vertex_perm_old_to_new );
+
+    let new_facets          =   relabel_simplices( facets, vertex_perm_old_to_new );
+    let simplex_bimap       =   BiMapSequential::from_vec(
+                                    ordered_subsimplices_up_thru_dim_concatenated_vec( &new_facets, max_dim )
+                                );
+    let boundary            =   boundary_matrix_from_complex_facets( &simplex_bimap, ring );
+
+    RelabeledComplex{ facets: new_facets, simplex_bimap, boundary, simplex_perm_o2n }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  ISOMORPHISM
+//  ---------------------------------------------------------------------------
+
+
+/// Determine whether the complex generated by `facets_a` maps onto the complex generated by
+/// `facets_b` under the given vertex relabeling.
+///
+/// Two facet lists are compared by the sets of simplices they generate, so redundant facets
+/// (faces of other facets) and the order of facets do not matter.  Vertices within each facet
+/// need not be sorted.  The vertices of `facets_b` may lie outside the range of the
+/// relabeling (in which case the complexes are not isomorphic under it), but every vertex of
+/// `facets_a` must lie in the domain `0 .. vertex_perm_a_to_b.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::relabel::is_isomorphism;
+///
+/// let facets_a    =   vec![ vec![0, 1, 2], vec![2, 3]          ];
+/// let facets_b    =   vec![ vec![0, 1],    vec![1, 2, 3], vec![2, 3] ];
+///
+/// assert!(   is_isomorphism( &facets_a, &facets_b, &vec![3, 2, 1, 0] ) );
+/// assert!( ! is_isomorphism( &facets_a, &facets_b, &vec![0, 1, 2, 3] ) );
+/// ```
+pub fn  is_isomorphism(
+            facets_a:                   &   [ Vec< usize > ],
+            facets_b:                   &   [ Vec< usize > ],
+            vertex_perm_a_to_b:         &   Vec< usize >,
+        )
+        ->
+        bool
+{
+    let relabeled_a     =   relabel_simplices( facets_a, vertex_perm_a_to_b );
+    let sorted_b: Vec< Vec< usize > >
+                        =   facets_b.iter().map( |facet| { let mut facet = facet.clone(); facet.sort(); facet } ).collect();
+
+    let max_dim_a       =   relabeled_a.iter().map( |x| x.len() ).max().unwrap_or(0);
+    let max_dim_b       =   sorted_b.iter().map( |x| x.len() ).max().unwrap_or(0);
+    if max_dim_a != max_dim_b { return false }
+    if max_dim_a == 0 { return true } // both complexes are empty
+
+    ordered_subsimplices_up_thru_dim_concatenated_vec( &relabeled_a, max_dim_a - 1 )
+    ==
+    ordered_subsimplices_up_thru_dim_concatenated_vec( &sorted_b, max_dim_b - 1 )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_relabel_complex_matches_direct_construction() {
+
+        let facets          =   vec![ vec![0, 1, 2], vec![0, 3] ];
+        let perm_v_o2n      =   vec![2, 0, 3, 1];
+        let relabeled       =   relabel_complex( &facets, &perm_v_o2n, 2, NativeRing::<i64>::new() );
+
+        // the relabeled simplices agree with the simplices of the old complex, permuted
+        let simplices_old   =   ordered_subsimplices_up_thru_dim_concatenated_vec( &facets, 2 );
+        for (old_ord, old_simplex) in simplices_old.iter().enumerate() {
+            let mut new_simplex     =   compose_f_after_g( &perm_v_o2n, old_simplex );
+            new_simplex.sort();
+            assert_eq!(     relabeled.simplex_bimap.ord( &new_simplex ),
+                            Some( relabeled.simplex_perm_o2n[ old_ord ] )   );
+        }
+
+        // every boundary-of-a-boundary vanishes in the relabeled complex
+        let boundary        =   &relabeled.boundary;
+        for col in boundary.iter() {
+            let mut sum     =   vec![ 0; boundary.len() ];
+            for (row, coeff) in col.iter() {
+                for (row2, coeff2) in boundary[ *row ].iter() { sum[ *row2 ] += coeff * coeff2 }
+            }
+            assert!( sum.iter().all( |x| *x == 0 ) );
+        }
+
+        assert!( is_isomorphism( &facets, &relabeled.facets, &perm_v_o2n ) );
+    }
+
+    #[test]
+    fn test_is_isomorphism_with_mismatched_vertex_ranges() {
+        // `facets_b` uses a vertex outside the range of the relabeling
+        let facets_a        =   vec![ vec![0, 1] ];
+        assert!( ! is_isomorphism( &facets_a, &vec![ vec![1, 5] ], &vec![1, 0] ) );
+        assert!(   is_isomorphism( &facets_a, &vec![ vec![5, 1] ], &vec![5, 1] ) );
+    }
+}
@@ -24,6 +24,22 @@ use std::iter::FromIterator;
 /// This function does not assume that the `simplex_sequence` has lexicogrphic 
 /// order, but it **does** assume that the new simplex sequence has lexicographic
 /// order.
+/// 
+/// **Changed behavior:** earlier versions returned the sort permutation
+/// new_simplex_number -> old_simplex_number, i.e. the inverse of g.  Callers that relied on
+/// the old result should apply
+/// [`inverse_perm`](crate::utilities::indexing_and_bijection::inverse_perm) to the new one.
+/// The two agree when the permutation on simplices is its own inverse.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::simplex::simplex_perm_o2n_from_vertex_perm_o2n;
+/// 
+/// // the vertex relabeling 0 -> 1 -> 2 -> 0 sends simplex number i to simplex number f[i]
+/// let simplices   =   vec![ vec![0], vec![1], vec![2] ];
+/// assert_eq!( simplex_perm_o2n_from_vertex_perm_o2n( &simplices, &vec![1, 2, 0] ), vec![1, 2, 0] );
+/// ```
 pub fn  simplex_perm_o2n_from_vertex_perm_o2n( 
     simplex_sequence:           &   Vec< Vec< usize >>,
     vertex_perm_old_to_new:     &   Vec< usize >
@@ -47,8 +63,8 @@ pub fn  simplex_perm_o2n_from_vertex_perm_o2n(
     // We must remember to sort the new vertices                                    
     for simplex in new_simplex_sequence.iter_mut() { simplex.vertices.sort()}
 
-    // Obtain the sort permutation
-    sort_perm( &new_simplex_sequence )
+    // Obtain the sort permutation (new -> old), and invert it to obtain old -> new
+    inverse_perm( &sort_perm( &new_simplex_sequence ) )
 }
 
 
@@ -361,13 +377,29 @@ mod tests {
         let mut simplex_sequence_permuted_vertex_translated     =   simplex_sequence_permuted.clone();
         for i in 0..simplex_sequence_permuted_vertex_translated.len() { simplex_sequence_permuted_vertex_translated[i] = compose_f_after_g( & perm_v_o2n, & simplex_sequence_permuted[i]) };
         for i in 0..simplex_sequence_permuted_vertex_translated.len() { simplex_sequence_permuted_vertex_translated[i].sort() };        
-        
 
-        println!("sequence_old:          {:?}",     & simplex_sequence_old );
-        println!("sequence_old_permuted: {:?}",     & simplex_sequence_permuted );        
-        println!("new_sequence:          {:?}",     & simplex_sequence_permuted_vertex_translated );     
-        println!("permutation: simplex old -> new {:?}", & perm_s_o2n);           
+        simplex_sequence_new.sort();
+        assert_eq!( perm_s_o2n, vec![ 0, 1, 3, 2, 4, 6, 5, 7 ] );
+        assert_eq!( simplex_sequence_permuted_vertex_translated, simplex_sequence_new.into_iter().map( |x| x.vertices ).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn test_simplex_perm_o2n_is_not_the_sort_permutation() {
 
+        // the vertex relabeling 0 -> 1 -> 2 -> 0 induces a 3-cycle on simplices, which (unlike
+        // the permutation in the test above) differs from its inverse
+        let simplex_sequence_old    =   vec![ vec![0], vec![1], vec![2], vec![0, 1], vec![0, 2], vec![1, 2] ];
+        let perm_v_o2n              =   vec![1, 2, 0];
+        let perm_s_o2n              =   simplex_perm_o2n_from_vertex_perm_o2n( &simplex_sequence_old, &perm_v_o2n );
+
+        // [0,1] -> [1,2], [0,2] -> [0,1], [1,2] -> [0,2]
+        assert_eq!( perm_s_o2n, vec![ 1, 2, 0, 5, 3, 4 ] );
+        assert_ne!( perm_s_o2n, inverse_perm( &perm_s_o2n ) );
+        for (old_ord, old_simplex) in simplex_sequence_old.iter().enumerate() {
+            let mut new_simplex     =   compose_f_after_g( &perm_v_o2n, old_simplex );
+            new_simplex.sort();
+            assert_eq!( simplex_sequence_old[ perm_s_o2n[ old_ord ] ], new_simplex );
+        }
     }
 
 
@@ -0,0 +1,245 @@
+//! Facets, boundary matrices, and Laplacians of some standard spaces.
+//!
+//! These generators are useful for testing (e.g. the projective plane and the Klein bottle
+//! have torsion in their integral homology) and for teaching.  Every function that returns
+//! facets returns a vector of strictly ascending vertex lists, which can be passed directly
+//! to [`ordered_subsimplices_up_thru_dim_concatenated_vec`] or to [`boundary_matrix_from_facets`].
+
+use crate::utilities::sequences_and_ordinals::{BiMapSequential};
+use crate::utilities::cell_complexes::simplices_unweighted::facets::{ordered_subsimplices_up_thru_dim_concatenated_vec};
+use crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices::{boundary_matrix_from_complex_facets};
+use crate::vectors::operations::{add_assign_sorted};
+use crate::rings::ring::{Semiring, Ring};
+use itertools::Itertools;
+
+
+//  ---------------------------------------------------------------------------
+//  FACETS
+//  ---------------------------------------------------------------------------
+
+
+/// Facets of the `dim`-skeleton of the simplex on vertices `0 .. num_vertices`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::standard_complexes::facets_simplex_skeleton;
+///
+/// assert_eq!( facets_simplex_skeleton( 3, 1 ), vec![ vec![0, 1], vec![0, 2], vec![1, 2] ] );
+/// ```
+pub fn facets_simplex_skeleton( num_vertices: usize, dim: usize ) -> Vec< Vec< usize > > {
+    ( 0 .. num_vertices ).combinations( dim + 1 ).collect()
+}
+
+/// Facets of the `dim`-dimensional sphere, realized as the boundary of the `(dim+1)`-simplex.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::standard_complexes::facets_sphere;
+///
+/// // the circle is the boundary of a triangle
+/// assert_eq!( facets_sphere( 1 ), vec![ vec![0, 1], vec![0, 2], vec![1, 2] ] );
+/// ```
+pub fn facets_sphere( dim: usize ) -> Vec< Vec< usize > > {
+    facets_simplex_skeleton( dim + 2, dim )
+}
+
+/// Facets of a triangulated `m x n` grid of squares, with the opposite sides identified.
+///
+/// Grid point `(i, j)` (for `0 <= i < m`, `0 <= j < n`) is vertex `i * n + j`.  The side
+/// `i = m` is glued to the side `i = 0`, and the side `j = n` is glued to the side `j = 0`;
+/// if `twist == true` then the second gluing reverses orientation, i.e. `(i, n) ~ (m-i, 0)`.
+/// Each square is cut into two triangles along its diagonal.
+///
+/// With `twist == false` the result is a torus; with `twist == true` it is a Klein bottle.
+/// Both are valid simplicial complexes when `m, n >= 3`.
+pub fn facets_grid_quotient( m: usize, n: usize, twist: bool ) -> Vec< Vec< usize > > {
+    let label   =   | i: usize, j: usize | -> usize {
+                        let ( mut i, mut j )    =   ( i, j );
+                        if j == n { j = 0; if twist { i = ( m - i ) % m } }
+                        if i == m { i = 0 }
+                        i * n + j
+                    };
+
+    let mut facets  =   Vec::with_capacity( 2 * m * n );
+    for i in 0 .. m {
+        for j in 0 .. n {
+            for triangle in [   [ (i, j), (i+1, j),   (i+1, j+1) ],
+                                [ (i, j), (i,   j+1), (i+1, j+1) ]  ].iter() {
+                let mut facet: Vec< usize > =   triangle.iter().map( |p| label( p.0, p.1 ) ).collect();
+                facet.sort();
+                facets.push( facet );
+            }
+        }
+    }
+    facets.sort();
+    facets.dedup();
+    facets
+}
+
+/// Facets of a 9-vertex triangulation of the torus.
+pub fn facets_torus() -> Vec< Vec< usize > > { facets_grid_quotient( 3, 3, false ) }
+
+/// Facets of a 9-vertex triangulation of the Klein bottle.
+pub fn facets_klein_bottle() -> Vec< Vec< usize > > { facets_grid_quotient( 3, 3, true ) }
+
+/// Facets of the minimal (6-vertex) triangulation of the real projective plane.
+pub fn facets_projective_plane() -> Vec< Vec< usize > > {
+    vec![
+        vec![0, 1, 2], vec![0, 1, 5], vec![0, 2, 3], vec![0, 3, 4], vec![0, 4, 5],
+        vec![1, 2, 4], vec![1, 3, 4], vec![1, 3, 5], vec![2, 3, 5], vec![2, 4, 5],
+    ]
+}
+
+
+//  ---------------------------------------------------------------------------
+//  BOUNDARY MATRICES AND LAPLACIANS
+//  ---------------------------------------------------------------------------
+
+
+/// A bijection between simplices and `{0, .., N}`, together with the boundary matrix it indexes.
+pub type BimapAndBoundary< RingElt > = ( BiMapSequential< Vec< usize > >, Vec< Vec< (usize, RingElt) > > );
+
+/// Returns the simplex bimap and boundary matrix of the complex generated by `facets`,
+/// including all simplices of dimension `<= max_dim`.
+///
+/// Simplices are ordered first by dimension, then lexicographically.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::standard_complexes::{facets_sphere, boundary_matrix_from_facets};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let (bimap, boundary) = boundary_matrix_from_facets( &facets_sphere( 1 ), 1, NativeRing::<i64>::new() );
+/// assert_eq!( bimap.ord_to_val.len(), 6 );
+/// assert_eq!( boundary[3], vec![ (0, -1), (1, 1) ] );
+/// ```
+pub fn  boundary_matrix_from_facets< RingOp, RingElt >(
+            facets:     &   Vec< Vec< usize > >,
+            max_dim:        usize,
+            ring:           RingOp,
+        )
+        ->
+        BimapAndBoundary< RingElt >
+
+        where   RingOp:     Semiring< RingElt > + Ring< RingElt >,
+{
+    let bimap       =   BiMapSequential::from_vec(
+                            ordered_subsimplices_up_thru_dim_concatenated_vec( facets, max_dim )
+                        );
+    let boundary    =   boundary_matrix_from_complex_facets( &bimap, ring );
+    ( bimap, boundary )
+}
+
+/// Given a square matrix `D` stored as a vector of sorted sparse columns, returns the
+/// combinatorial Laplacian `D D^T + D^T D` (also stored as a vector of sorted sparse columns).
+///
+/// If `D` is the boundary matrix of a complex, with simplices ordered by dimension (as in
+/// [`boundary_matrix_from_facets`]), then the result is block diagonal, and the block indexed
+/// by `k`-simplices is the `k`th combinatorial Laplacian.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::simplices_unweighted::standard_complexes::laplacian;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// // boundary matrix of a single edge
+/// let boundary    =   vec![ vec![], vec![], vec![ (0, -1), (1, 1) ] ];
+/// let laplacian   =   laplacian( &boundary, NativeRing::<i64>::new() );
+///
+/// assert_eq!( laplacian, vec![ vec![ (0, 1), (1, -1) ], vec![ (0, -1), (1, 1) ], vec![ (2, 2) ] ] );
+/// ```
+pub fn  laplacian< RingOp, RingElt >(
+            matrix:     &   [ Vec< (usize, RingElt) > ],
+            ring:           RingOp
+        )
+        ->
+        Vec< Vec< (usize, RingElt) > >
+
+        where   RingOp:     Semiring< RingElt > + Clone,
+                RingElt:    Clone,
+{
+    // rows of the matrix (equivalently, columns of the transpose)
+    let mut rows: Vec< Vec< (usize, RingElt) > >    =   vec![ Vec::new(); matrix.len() ];
+    for (col, vec) in matrix.iter().enumerate() {
+        for (row, val) in vec.iter() { rows[ *row ].push( ( col, val.clone() ) ) }
+    }
+
+    let mut laplacian   =   Vec::with_capacity( matrix.len() );
+    for j in 0 .. matrix.len() {
+        let mut col     =   Vec::new();
+        // column j of D^T D is a linear combination of rows of D
+        for (k, val) in matrix[ j ].iter() {
+            add_assign_sorted( &mut col, &rows[ *k ], val.clone(), ring.clone() );
+        }
+        // column j of D D^T is a linear combination of columns of D
+        for (k, val) in rows[ j ].iter() {
+            add_assign_sorted( &mut col, &matrix[ *k ], val.clone(), ring.clone() );
+        }
+        laplacian.push( col );
+    }
+    laplacian
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GF2;
+    use crate::rings::ring_native::{NativeRing, NativeDivisionRing};
+    use crate::rings::ring::DivisionRing;
+    use crate::matrix_factorization::vec_of_vec::right_reduce;
+    use crate::matrix_factorization::field_sweep::index_barcode;
+    use num::rational::Ratio;
+    use std::fmt::Debug;
+
+    /// Betti numbers of the complex generated by `facets`, computed over the given field.
+    fn betti_numbers< RingOp, RingElt, F >( facets: &Vec< Vec< usize > >, ring: RingOp, coerce: F ) -> Vec< usize >
+        where   RingOp:     Semiring< RingElt > + Ring< RingElt > + DivisionRing< RingElt > + Clone,
+                RingElt:    Clone + Debug + PartialOrd,
+                F:          Fn( i64 ) -> RingElt,
+    {
+        let (bimap, boundary)   =   boundary_matrix_from_facets( facets, 2, NativeRing::<i64>::new() );
+        let mut matrix: Vec< Vec< (usize, RingElt) > >
+                                =   boundary.iter()
+                                        .map( |col| col.iter().map( |(i, x)| ( *i, coerce( *x ) ) ).filter( |x| ! ring.is_0( x.1.clone() ) ).collect() )
+                                        .collect();
+        let mut pairs: Vec<_>   =   right_reduce( &mut matrix, ring ).into_iter().collect();
+        pairs.sort();
+        let mut betti           =   vec![ 0; 3 ];
+        for (birth, death) in index_barcode( matrix.len(), &pairs ) {
+            if death.is_none() { betti[ bimap.ord_to_val[ birth ].len() - 1 ] += 1 }
+        }
+        betti
+    }
+
+    #[test]
+    fn test_betti_numbers_of_standard_surfaces() {
+        let rational    =   NativeDivisionRing::< Ratio<i64> >::new();
+        let gf2         =   | x: i64 | x % 2 != 0;
+
+        assert_eq!( betti_numbers( &facets_sphere( 2 ),         GF2::new(),         gf2                 ), vec![1, 0, 1] );
+        assert_eq!( betti_numbers( &facets_torus(),             GF2::new(),         gf2                 ), vec![1, 2, 1] );
+        assert_eq!( betti_numbers( &facets_torus(),             rational.clone(),   Ratio::from_integer ), vec![1, 2, 1] );
+        assert_eq!( betti_numbers( &facets_klein_bottle(),      GF2::new(),         gf2                 ), vec![1, 2, 1] );
+        assert_eq!( betti_numbers( &facets_klein_bottle(),      rational.clone(),   Ratio::from_integer ), vec![1, 1, 0] );
+        assert_eq!( betti_numbers( &facets_projective_plane(),  GF2::new(),         gf2                 ), vec![1, 1, 1] );
+        assert_eq!( betti_numbers( &facets_projective_plane(),  rational,           Ratio::from_integer ), vec![1, 0, 0] );
+    }
+
+    #[test]
+    fn test_laplacian_is_symmetric() {
+        let (_bimap, boundary)  =   boundary_matrix_from_facets( &facets_torus(), 2, NativeRing::<i64>::new() );
+        let laplacian           =   laplacian( &boundary, NativeRing::<i64>::new() );
+        for (col, vec) in laplacian.iter().enumerate() {
+            for (row, val) in vec.iter() {
+                assert!( laplacian[ *row ].contains( &( col, *val ) ) );
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Consistency checks for boundary matrices.
+//!
+//! The boundary matrix `D` of a cell complex (with all dimensions concatenated, as produced by
+//! the builders in [`simplices_unweighted::boundary_matrices`](crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices))
+//! must satisfy `D * D = 0`; equivalently, `D_{k-1} * D_k = 0` for every `k`.  A nonzero entry in
+//! `D * D` usually indicates a sign error, or a facet that was assigned the wrong key.
+//!
+//! The checks below compute `D * D` lazily, one column at a time, as the major views of a
+//! [`ProductMatrixOracle`](crate::matrices::implementors::product::ProductMatrixOracle), so they
+//! apply equally to stored and to lazily generated boundaries.
+
+use crate::matrices::implementors::product::multiply_major_view;
+use crate::matrices::implementors::vec_of_vec::VecOfVec;
+use crate::matrices::matrix_oracle::{MajorDimension, OracleMajor};
+use crate::rings::ring::Semiring;
+
+
+/// A column of `D * D` that is not zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundaryViolation< Key, Val > {
+    /// The key of the column (i.e. of the cell whose boundary of boundary is nonzero).
+    pub column:     Key,
+    /// The nonzero entries of the column, sorted in ascending order of key.
+    pub entries:    Vec< (Key, Val) >,
+}
+
+/// Return every column of `D * D` that is nonzero, where `D` is a column-major boundary oracle.
+///
+/// Only the columns with the given keys are checked.  An empty output means that the boundary
+/// is consistent on those columns.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::verify::boundary_violations;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// // boundary of a triangle with vertices 0, 1, 2, edges 3 = {0,1}, 4 = {0,2}, 5 = {1,2}, and a
+/// // 2-cell 6 whose boundary has a sign error on edge 4
+/// let boundary    =   VecOfVec::new(
+///                         MajorDimension::Col,
+///                         vec![   vec![], vec![], vec![],
+///                                 vec![ (0, -1), (1, 1) ],
+///                                 vec![ (0, -1), (2, 1) ],
+///                                 vec![ (1, -1), (2, 1) ],
+///                                 vec![ (3, 1), (4, 1), (5, 1) ], ],
+///                     );
+///
+/// let violations  =   boundary_violations( &boundary, 0 .. 7, NativeRing::<i64>::new() );
+/// assert_eq!( violations.len(), 1 );
+/// assert_eq!( violations[0].column, 6 );
+/// assert_eq!( violations[0].entries, vec![ (0, -2), (2, 2) ] );
+/// ```
+pub fn boundary_violations< 'a, Boundary, Key, Val, RingOperator, Keys >(
+            boundary:   &'a Boundary,
+            keys:       Keys,
+            ring:       RingOperator,
+        )
+        ->
+        Vec< BoundaryViolation< Key, Val > >
+
+    where   Boundary:       OracleMajor< 'a, Key, Key, Val >,
+            Keys:           IntoIterator< Item = Key >,
+            Key:            'a + Clone + PartialOrd,
+            Val:            'a + Clone,
+            RingOperator:   Semiring< Val >,
+{
+    let mut out     =   Vec::new();
+    for key in keys {
+        let entries     =   multiply_major_view( boundary, boundary, key.clone(), &ring );
+        if ! entries.is_empty() { out.push( BoundaryViolation{ column: key, entries } ) }
+    }
+    out
+}
+
+/// Returns `true` if `D * D = 0`, where `D` is a column-major boundary matrix stored as a vector
+/// of columns (the format returned by the boundary matrix builders).
+///
+/// See [`boundary_violations`] to find out which columns are inconsistent.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::cell_complexes::verify::boundary_squares_to_zero;
+/// use solar::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets;
+/// use solar::utilities::cell_complexes::simplices_unweighted::facets::ordered_subsimplices_up_thru_dim_concatenated_vec;
+/// use solar::utilities::sequences_and_ordinals::BiMapSequential;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let simplices   =   ordered_subsimplices_up_thru_dim_concatenated_vec( &vec![ vec![0, 1, 2, 3] ], 3 );
+/// let boundary    =   boundary_matrix_from_complex_facets( &BiMapSequential::from_vec( simplices ), NativeRing::<i64>::new() );
+///
+/// assert!( boundary_squares_to_zero( &boundary, NativeRing::<i64>::new() ) );
+/// ```
+pub fn boundary_squares_to_zero< Val, RingOperator >(
+            boundary:   &[ Vec< (usize, Val) > ],
+            ring:       RingOperator,
+        )
+        -> bool
+
+    where   Val:            Clone,
+            RingOperator:   Semiring< Val >,
+{
+    let matrix      =   VecOfVec::new( MajorDimension::Col, boundary.to_vec() );
+    boundary_violations( &matrix, 0 .. boundary.len(), ring ).is_empty()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::utilities::cell_complexes::simplices_unweighted::boundary_matrices::boundary_matrix_from_complex_facets;
+    use crate::utilities::cell_complexes::simplices_unweighted::facets::ordered_subsimplices_up_thru_dim_concatenated_vec;
+    use crate::utilities::sequences_and_ordinals::BiMapSequential;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_sign_error_is_detected() {
+        let ring            =   NativeRing::<i64>::new();
+        let simplices       =   ordered_subsimplices_up_thru_dim_concatenated_vec( &vec![ vec![0, 1, 2, 3] ], 3 );
+        let bimap           =   BiMapSequential::from_vec( simplices );
+        let mut boundary    =   boundary_matrix_from_complex_facets( &bimap, ring.clone() );
+        assert!( boundary_squares_to_zero( &boundary, ring.clone() ) );
+
+        // flip the sign of one facet of the first triangle
+        let triangle        =   bimap.ord( &vec![0, 1, 2] ).unwrap();
+        boundary[ triangle ][ 0 ].1 *= -1;
+        assert!( ! boundary_squares_to_zero( &boundary, ring.clone() ) );
+
+        let matrix          =   VecOfVec::new( MajorDimension::Col, boundary.clone() );
+        let violations      =   boundary_violations( &matrix, 0 .. boundary.len(), ring );
+        let tetrahedron     =   bimap.ord( &vec![0, 1, 2, 3] ).unwrap();
+        let columns: Vec< _ >   =   violations.iter().map( |x| x.column ).collect();
+        assert_eq!( columns, vec![ triangle, tetrahedron ] );
+    }
+}
@@ -0,0 +1,373 @@
+//! Human-readable printing of keys, coefficients, sparse vectors and matrices.
+//!
+//! Keys and coefficients are often of types whose `Debug` output is hard to read: simplices print
+//! as `[0, 1, 2]`, coefficients in GF(2) print as `true` and `false`, keys of block matrices print
+//! as nested tuples.  This module lets you choose how keys and coefficients print once, and reuse
+//! that choice wherever vectors and matrices are displayed.
+//!
+//! - A [`Format<T>`](Format) says how to print a value of type `T`.  It is implemented by
+//!   [`UseDebug`], [`UseDisplay`], [`Braces`] (prints sequences as sets, e.g. simplices as
+//!   `{0,1,2}`), [`Bit`] (prints booleans as `0` and `1`), and every closure
+//!   `Fn( &T, &mut std::fmt::Formatter ) -> std::fmt::Result`.
+//! - An [`EntryFormat`] pairs a format for keys with a format for coefficients.  Its methods wrap
+//!   a key, a coefficient, a sparse vector, or a list of major views of a matrix oracle in an
+//!   adapter that implements [`Display`], so the result can be passed to `format!`, `println!`,
+//!   or a panic message.
+//! - [`display_dense`] (or [`EntryFormat::dense`], to choose the formats) renders a small block
+//!   of a matrix oracle as an aligned dense table, which is often the quickest way to see what a
+//!   reduction is doing.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::utilities::fmt::{Bit, Braces, EntryFormat};
+//! use solar::matrices::implementors::vec_of_vec::VecOfVec;
+//! use solar::matrices::matrix_oracle::MajorDimension;
+//!
+//! let format  =   EntryFormat::new( Braces, Bit );
+//! let chain   =   vec![ ( vec![0, 1], true ), ( vec![1, 2], false ) ];
+//! assert_eq!( format.vector( &chain ).to_string(), "[{0,1}: 1, {1,2}: 0]" );
+//!
+//! // closures work too
+//! let format  =   EntryFormat::new( |k: &usize, f: &mut std::fmt::Formatter| write!( f, "e{}", k ), Bit );
+//! let matrix  =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, true), (2, true) ], vec![] ] );
+//! assert_eq!( format.major_views( &matrix, &[ 0, 1 ] ).to_string(), "e0: [e0: 1, e2: 1]\ne1: []" );
+//! ```
+
+use crate::matrices::matrix_oracle::{OracleMajor, OracleMajorAscend, WhichMajor, MajorDimension};
+use crate::rings::ring::Semiring;
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::fmt::{self, Debug, Display};
+use std::marker::PhantomData;
+
+
+//  ---------------------------------------------------------------------------
+//  FORMATS
+//  ---------------------------------------------------------------------------
+
+
+/// Prints values of type `T`.
+pub trait Format< T: ?Sized > {
+    /// Write `value` to `f`.
+    fn format( &self, value: &T, f: &mut fmt::Formatter<'_> ) -> fmt::Result;
+}
+
+/// Prints values with their `Debug` implementation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UseDebug;
+
+/// Prints values with their `Display` implementation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UseDisplay;
+
+/// Prints sequences as sets, e.g. the simplex `vec![0, 1, 2]` as `{0,1,2}`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Braces;
+
+/// Prints booleans (e.g. coefficients in GF(2)) as `0` and `1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bit;
+
+impl < T: Debug + ?Sized > Format< T > for UseDebug {
+    fn format( &self, value: &T, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "{:?}", value ) }
+}
+
+impl < T: Display + ?Sized > Format< T > for UseDisplay {
+    fn format( &self, value: &T, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "{}", value ) }
+}
+
+impl < T: Display > Format< [ T ] > for Braces {
+    fn format( &self, value: &[ T ], f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        write!( f, "{{" )?;
+        for ( i, element ) in value.iter().enumerate() {
+            if i > 0 { write!( f, "," )? }
+            write!( f, "{}", element )?;
+        }
+        write!( f, "}}" )
+    }
+}
+
+impl < T: Display > Format< Vec< T > > for Braces {
+    fn format( &self, value: &Vec< T >, f: &mut fmt::Formatter<'_> ) -> fmt::Result { self.format( value.as_slice(), f ) }
+}
+
+impl Format< bool > for Bit {
+    fn format( &self, value: &bool, f: &mut fmt::Formatter<'_> ) -> fmt::Result { write!( f, "{}", *value as u8 ) }
+}
+
+impl < T, F > Format< T > for F
+    where   T:  ?Sized,
+            F:  Fn( &T, &mut fmt::Formatter<'_> ) -> fmt::Result,
+{
+    fn format( &self, value: &T, f: &mut fmt::Formatter<'_> ) -> fmt::Result { self( value, f ) }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  ENTRY FORMATS
+//  ---------------------------------------------------------------------------
+
+
+/// A format for keys, together with a format for coefficients.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EntryFormat< KeyFormat, ValFormat > {
+    /// Prints keys.
+    pub key:    KeyFormat,
+    /// Prints coefficients.
+    pub val:    ValFormat,
+}
+
+impl EntryFormat< UseDebug, UseDebug > {
+    /// Print keys and coefficients with their `Debug` implementations.
+    pub fn debug() -> Self { EntryFormat{ key: UseDebug, val: UseDebug } }
+}
+
+impl    < KeyFormat, ValFormat >
+        EntryFormat
+        < KeyFormat, ValFormat >
+{
+    /// Combine a format for keys with a format for coefficients.
+    pub fn new( key: KeyFormat, val: ValFormat ) -> Self { EntryFormat{ key, val } }
+
+    /// Display a key.
+    pub fn key< 'a, Key: ?Sized >( &'a self, key: &'a Key ) -> Formatted< 'a, Key, KeyFormat > { Formatted::new( key, &self.key ) }
+
+    /// Display a coefficient.
+    pub fn val< 'a, Val: ?Sized >( &'a self, val: &'a Val ) -> Formatted< 'a, Val, ValFormat > { Formatted::new( val, &self.val ) }
+
+    /// Display a sparse vector, as `[key: val, key: val, ...]`.
+    pub fn vector< 'a, Key, Val >( &'a self, vector: &'a [ (Key, Val) ] ) -> DisplayVector< 'a, Key, Val, KeyFormat, ValFormat > {
+        DisplayVector{ vector, format: self }
+    }
+
+    /// Display the ascending major views of `matrix` with the given keys, one per line, as
+    /// `key: [key: val, ...]`.
+    pub fn major_views< 'a, Matrix, MajKey, MinKey, Val >( &'a self, matrix: &'a Matrix, keys: &'a [ MajKey ] )
+            -> DisplayMajorViews< 'a, Matrix, MajKey, MinKey, Val, KeyFormat, ValFormat >
+    {
+        DisplayMajorViews{ matrix, keys, format: self, phantom: PhantomData }
+    }
+
+    /// Render the block of `matrix` with the given major and minor keys as an aligned dense
+    /// table; see [`display_dense`].
+    pub fn dense< 'a, Matrix, MajKey, MinKey, Val, RingOperator >(
+                &self,
+                matrix:         &'a Matrix,
+                major_keys:     &[ MajKey ],
+                minor_keys:     &[ MinKey ],
+                ring:           RingOperator,
+            )
+            -> String
+
+        where   Matrix:         OracleMajor< 'a, MajKey, MinKey, Val > + WhichMajor,
+                MajKey:         Clone,
+                MinKey:         PartialEq,
+                Val:            Clone,
+                RingOperator:   Semiring< Val >,
+                KeyFormat:      Format< MajKey > + Format< MinKey >,
+                ValFormat:      Format< Val >,
+    {
+        // one row of cells per major view
+        let mut cells: Vec< Vec< String > >   =   Vec::with_capacity( major_keys.len() );
+        for major_key in major_keys {
+            let mut vals    =   vec![ RingOperator::zero(); minor_keys.len() ];
+            for entry in matrix.view_major( major_key.clone() ) {
+                if let Some( i ) = minor_keys.iter().position( |key| *key == entry.key() ) {
+                    vals[ i ]   =   ring.add( vals[ i ].clone(), entry.val() );
+                }
+            }
+            cells.push( vals.into_iter().map( |val| if ring.is_0( val.clone() ) { ".".to_string() } else { self.val( &val ).to_string() } ).collect() );
+        }
+        let major_labels: Vec< String > =   major_keys.iter().map( |key| self.key( key ).to_string() ).collect();
+        let minor_labels: Vec< String > =   minor_keys.iter().map( |key| self.key( key ).to_string() ).collect();
+
+        // lay out major views as rows of a row-major matrix, and as columns of a column-major one
+        let ( row_labels, col_labels, table )   =   match matrix.major_dimension() {
+            MajorDimension::Row     =>  ( major_labels, minor_labels, cells ),
+            MajorDimension::Col     =>  {
+                let transpose   =   ( 0 .. minor_keys.len() ).map( |i| cells.iter().map( |row| row[ i ].clone() ).collect() ).collect();
+                ( minor_labels, major_labels, transpose )
+            }
+        };
+
+        let label_width     =   row_labels.iter().map( |label| label.chars().count() ).max().unwrap_or( 0 );
+        let widths: Vec< usize >    =   col_labels.iter()
+                                            .enumerate()
+                                            .map( |( j, label )| table.iter().map( |row| row[ j ].chars().count() ).chain( Some( label.chars().count() ) ).max().unwrap() )
+                                            .collect();
+        let render_line     =   |label: &str, row: &[ String ]| -> String {
+            let mut line    =   format!( "{:<width$}", label, width = label_width );
+            for ( cell, width ) in row.iter().zip( widths.iter() ) {
+                line.push_str( &format!( "  {:>width$}", cell, width = *width ) );
+            }
+            line.trim_end().to_string()
+        };
+        let mut lines       =   vec![ render_line( "", &col_labels ) ];
+        lines.extend( row_labels.iter().zip( table.iter() ).map( |( label, row )| render_line( label, row ) ) );
+        lines.join( "\n" )
+    }
+}
+
+/// Render the block of `matrix` with the given major and minor keys as an aligned dense table,
+/// printing keys and coefficients with their `Debug` implementations.
+///
+/// The first line lists the column keys, and each following line lists a row key and the
+/// coefficients in that row.  Rows are major views if `matrix` is row-major, and minor views if it
+/// is column-major, so the table always shows the matrix itself rather than its transpose.
+/// Zero coefficients (including entries that are not stored) print as `.`; repeated entries in a
+/// major view are summed with `ring`; entries whose minor keys are not listed are ignored.
+///
+/// Each stored entry is matched against the list of minor keys by linear search, so this is meant
+/// for small matrices.  To print keys or coefficients differently, use [`EntryFormat::dense`].
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::fmt::display_dense;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// // a column-major matrix with columns 0, 1, 2
+/// let matrix  =   VecOfVec::new( MajorDimension::Col, vec![ vec![ (0, 1) ], vec![ (0, -1), (1, 10) ], vec![] ] );
+/// let table   =   display_dense( &matrix, &[ 0, 1, 2 ], &[ 0, 1 ], NativeRing::<i64>::new() );
+/// assert_eq!( table, "   0   1  2\n0  1  -1  .\n1  .  10  ." );
+/// ```
+pub fn display_dense< 'a, Matrix, MajKey, MinKey, Val, RingOperator >(
+            matrix:         &'a Matrix,
+            major_keys:     &[ MajKey ],
+            minor_keys:     &[ MinKey ],
+            ring:           RingOperator,
+        )
+        -> String
+
+    where   Matrix:         OracleMajor< 'a, MajKey, MinKey, Val > + WhichMajor,
+            MajKey:         Clone + Debug,
+            MinKey:         PartialEq + Debug,
+            Val:            Clone + Debug,
+            RingOperator:   Semiring< Val >,
+{
+    EntryFormat::debug().dense( matrix, major_keys, minor_keys, ring )
+}
+
+
+//  ---------------------------------------------------------------------------
+//  DISPLAY ADAPTERS
+//  ---------------------------------------------------------------------------
+
+
+/// Displays a value with a [`Format`].
+#[derive(Clone, Copy, Debug)]
+pub struct Formatted< 'a, T: ?Sized, F > {
+    value:      &'a T,
+    format:     &'a F,
+}
+
+impl < 'a, T: ?Sized, F > Formatted< 'a, T, F > {
+    /// Display `value` with `format`.
+    pub fn new( value: &'a T, format: &'a F ) -> Self { Formatted{ value, format } }
+}
+
+impl < 'a, T: ?Sized, F: Format< T > > Display for Formatted< 'a, T, F > {
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result { self.format.format( self.value, f ) }
+}
+
+/// Displays a sparse vector with an [`EntryFormat`]; see [`EntryFormat::vector`].
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayVector< 'a, Key, Val, KeyFormat, ValFormat > {
+    vector:     &'a [ (Key, Val) ],
+    format:     &'a EntryFormat< KeyFormat, ValFormat >,
+}
+
+impl    < 'a, Key, Val, KeyFormat, ValFormat >
+        Display
+        for
+        DisplayVector < 'a, Key, Val, KeyFormat, ValFormat >
+
+        where   KeyFormat:  Format< Key >,
+                ValFormat:  Format< Val >,
+{
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        write!( f, "[" )?;
+        for ( i, ( key, val ) ) in self.vector.iter().enumerate() {
+            if i > 0 { write!( f, ", " )? }
+            self.format.key.format( key, f )?;
+            write!( f, ": " )?;
+            self.format.val.format( val, f )?;
+        }
+        write!( f, "]" )
+    }
+}
+
+/// Displays major views of a matrix oracle with an [`EntryFormat`]; see
+/// [`EntryFormat::major_views`].
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayMajorViews< 'a, Matrix, MajKey, MinKey, Val, KeyFormat, ValFormat > {
+    matrix:     &'a Matrix,
+    keys:       &'a [ MajKey ],
+    format:     &'a EntryFormat< KeyFormat, ValFormat >,
+    phantom:    PhantomData< ( MinKey, Val ) >,
+}
+
+impl    < 'a, Matrix, MajKey, MinKey, Val, KeyFormat, ValFormat >
+        Display
+        for
+        DisplayMajorViews < 'a, Matrix, MajKey, MinKey, Val, KeyFormat, ValFormat >
+
+        where   Matrix:     OracleMajorAscend< 'a, MajKey, MinKey, Val >,
+                MajKey:     Clone,
+                KeyFormat:  Format< MajKey > + Format< MinKey >,
+                ValFormat:  Format< Val >,
+{
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        for ( i, major_key ) in self.keys.iter().enumerate() {
+            if i > 0 { writeln!( f )? }
+            self.format.key.format( major_key, f )?;
+            write!( f, ": [" )?;
+            for ( j, entry ) in self.matrix.view_major_ascend( major_key.clone() ).into_iter().enumerate() {
+                if j > 0 { write!( f, ", " )? }
+                self.format.key.format( &entry.key(), f )?;
+                write!( f, ": " )?;
+                self.format.val.format( &entry.val(), f )?;
+            }
+            write!( f, "]" )?;
+        }
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_formats_for_keys_and_values() {
+        let chain       =   vec![ ( vec![ 0, 1 ], 2 ), ( vec![ 1, 2 ], -1 ) ];
+        assert_eq!( EntryFormat::debug().vector( &chain ).to_string(), "[[0, 1]: 2, [1, 2]: -1]" );
+        assert_eq!( EntryFormat::new( Braces, UseDisplay ).vector( &chain ).to_string(), "[{0,1}: 2, {1,2}: -1]" );
+
+        let format      =   EntryFormat::new( Braces, Bit );
+        assert_eq!( format.key( &[ 3, 4 ][..] ).to_string(), "{3,4}" );
+        assert_eq!( format.val( &false ).to_string(), "0" );
+        assert_eq!( format!( "{:>3}", format.val( &true ) ), "1", "formats write directly and ignore padding" );
+        assert_eq!( EntryFormat::new( Braces, Bit ).vector::< Vec< usize >, bool >( &[] ).to_string(), "[]" );
+    }
+
+    #[test]
+    fn test_dense_table_of_row_major_matrix() {
+        use crate::matrices::implementors::vec_of_vec::VecOfVec;
+        use crate::rings::field_prime::GF2;
+
+        // rows 0 and 2 of a row-major matrix, restricted to columns 0, 1, 2; the repeated entry
+        // in position (2, 1) cancels, and column 5 is not shown
+        let matrix      =   VecOfVec::new(  MajorDimension::Row,
+                                            vec![ vec![ (0, true), (2, true), (5, true) ], vec![], vec![ (1, true), (1, true), (2, true) ] ] );
+        let table       =   EntryFormat::new( |k: &usize, f: &mut fmt::Formatter| write!( f, "e{}", k ), Bit )
+                                .dense( &matrix, &[ 0, 2 ], &[ 0, 1, 2 ], GF2::new() );
+        assert_eq!( table, "    e0  e1  e2\ne0   1   .   1\ne2   .   .   1" );
+        assert_eq!( display_dense( &matrix, &[], &[ 0 ], GF2::new() ), "  0" );
+    }
+}
@@ -0,0 +1,297 @@
+//! Compact sets of indices.
+//!
+//! Many functions in this library accept a set of keys as an `IntoIterator< Item = usize >` (for
+//! example the list of columns to clear in
+//! [`clear_cols`](crate::matrix_factorization::vec_of_vec::clear_cols)).  For banded or otherwise
+//! structured problems these sets are usually unions of a few long intervals, and an
+//! [`IndexRangeSet`] stores them as such, so that neither iteration nor membership tests pay for
+//! per-index storage.
+//!
+//! The [`KeySet`] trait abstracts over membership tests, so that functions that restrict a
+//! computation to a subset of keys (such as
+//! [`Submatrix`](crate::matrices::implementors::submatrix::Submatrix)) accept an
+//! [`IndexRangeSet`], a `HashSet`, a `BTreeSet`, or a predicate interchangeably.
+
+use std::collections::{BTreeSet, HashSet};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::ops::Range;
+use std::slice;
+use std::vec;
+
+
+/// A set of `usize` indices, stored as a sorted list of disjoint, nonadjacent, nonempty ranges.
+///
+/// Iterating over the set (by reference or by value) yields the indices in ascending order, so an
+/// `IndexRangeSet` can be passed anywhere an `IntoIterator< Item = usize >` is expected.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::index_sets::IndexRangeSet;
+///
+/// // overlapping and adjacent ranges are merged
+/// let set     =   IndexRangeSet::from_ranges( vec![ 10 .. 20, 0 .. 3, 3 .. 5, 15 .. 25 ] );
+///
+/// assert_eq!( set.ranges(), &[ 0 .. 5, 10 .. 25 ] );
+/// assert_eq!( set.len(), 20 );
+/// assert!( set.contains( 12 ) );
+/// assert!( ! set.contains( 7 ) );
+/// assert_eq!( set.iter().take( 7 ).collect::<Vec<_>>(), vec![ 0, 1, 2, 3, 4, 10, 11 ] );
+///
+/// // run-length encode an arbitrary collection of indices
+/// let set: IndexRangeSet  =   vec![ 4, 1, 2, 3, 9 ].into_iter().collect();
+/// assert_eq!( set.ranges(), &[ 1 .. 5, 9 .. 10 ] );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IndexRangeSet {
+    ranges:     Vec< Range< usize > >,
+}
+
+impl IndexRangeSet {
+
+    /// The empty set.
+    pub fn new() -> Self { IndexRangeSet{ ranges: Vec::new() } }
+
+    /// The set `range.start .. range.end`.
+    pub fn from_range( range: Range< usize > ) -> Self {
+        let mut set     =   IndexRangeSet::new();
+        set.insert_range( range );
+        set
+    }
+
+    /// The union of a collection of ranges, which may be unsorted, overlapping, or empty.
+    pub fn from_ranges< I >( ranges: I ) -> Self
+        where   I:  IntoIterator< Item = Range< usize > >,
+    {
+        let mut ranges: Vec< _ >    =   ranges.into_iter().filter( |r| r.start < r.end ).collect();
+        ranges.sort_by_key( |r| r.start );
+        let mut merged: Vec< Range< usize > >   =   Vec::with_capacity( ranges.len() );
+        for range in ranges {
+            match merged.last_mut() {
+                Some( last ) if range.start <= last.end     =>  { last.end = last.end.max( range.end ) }
+                _                                           =>  { merged.push( range ) }
+            }
+        }
+        IndexRangeSet{ ranges: merged }
+    }
+
+    /// The maximal ranges contained in the set, in ascending order.
+    pub fn ranges( &self ) -> &[ Range< usize > ] { &self.ranges }
+
+    /// The number of maximal ranges contained in the set.
+    pub fn num_ranges( &self ) -> usize { self.ranges.len() }
+
+    /// The number of indices in the set.
+    pub fn len( &self ) -> usize { self.ranges.iter().map( |r| r.end - r.start ).sum() }
+
+    /// Returns `true` if the set contains no indices.
+    pub fn is_empty( &self ) -> bool { self.ranges.is_empty() }
+
+    /// The smallest index in the set.
+    pub fn min( &self ) -> Option< usize > { self.ranges.first().map( |r| r.start ) }
+
+    /// The largest index in the set.
+    pub fn max( &self ) -> Option< usize > { self.ranges.last().map( |r| r.end - 1 ) }
+
+    /// Returns `true` if the set contains `index`.  Runs in time logarithmic in the number of
+    /// ranges.
+    pub fn contains( &self, index: usize ) -> bool {
+        // the first range that ends after `index` is the only one that can contain it
+        let position    =   self.ranges.partition_point( |r| r.end <= index );
+        self.ranges.get( position ).is_some_and( |r| r.start <= index )
+    }
+
+    /// Add a single index to the set.
+    pub fn insert( &mut self, index: usize ) { self.insert_range( index .. index + 1 ) }
+
+    /// Add every index in `range` to the set.
+    pub fn insert_range( &mut self, range: Range< usize > ) {
+        if range.start >= range.end { return }
+        // ranges strictly before `range` (and not adjacent to it) are kept as they are, as are
+        // ranges strictly after it; everything in between is merged with `range`
+        let first       =   self.ranges.partition_point( |r| r.end < range.start );
+        let last        =   self.ranges.partition_point( |r| r.start <= range.end );
+        let mut merged  =   range;
+        if first < last {
+            merged.start    =   merged.start.min( self.ranges[ first ].start );
+            merged.end      =   merged.end.max( self.ranges[ last - 1 ].end );
+        }
+        self.ranges.splice( first .. last, std::iter::once( merged ) );
+    }
+
+    /// The union of `self` and `other`.
+    pub fn union( &self, other: &IndexRangeSet ) -> IndexRangeSet {
+        IndexRangeSet::from_ranges( self.ranges.iter().chain( other.ranges.iter() ).cloned() )
+    }
+
+    /// The intersection of `self` and `other`.
+    pub fn intersection( &self, other: &IndexRangeSet ) -> IndexRangeSet {
+        let mut ranges  =   Vec::new();
+        let ( mut i, mut j )    =   ( 0, 0 );
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let ( a, b )    =   ( &self.ranges[ i ], &other.ranges[ j ] );
+            let start       =   a.start.max( b.start );
+            let end         =   a.end.min( b.end );
+            if start < end { ranges.push( start .. end ) }
+            if a.end <= b.end { i += 1 } else { j += 1 }
+        }
+        IndexRangeSet{ ranges }
+    }
+
+    /// Iterate over the indices in the set, in ascending order.
+    pub fn iter( &self ) -> IndexRangeSetIter< '_ > {
+        IndexRangeSetIter{ ranges: self.ranges.iter(), current: 0 .. 0 }
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  MEMBERSHIP
+//  ---------------------------------------------------------------------------
+
+
+/// A set of keys that supports membership tests.
+///
+/// Implemented for [`IndexRangeSet`], `HashSet`, `BTreeSet`, [`AllKeys`], and any closure
+/// `Fn( &Key ) -> bool`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::index_sets::{KeySet, IndexRangeSet, AllKeys};
+/// use std::collections::HashSet;
+///
+/// fn count_members< S: KeySet< usize > >( set: &S ) -> usize { ( 0 .. 10 ).filter( |i| set.contains_key( i ) ).count() }
+///
+/// assert_eq!( count_members( &IndexRangeSet::from_range( 2 .. 5 ) ), 3 );
+/// assert_eq!( count_members( &vec![ 1, 20 ].into_iter().collect::< HashSet< _ > >() ), 1 );
+/// assert_eq!( count_members( &|i: &usize| i % 2 == 0 ), 5 );
+/// assert_eq!( count_members( &AllKeys ), 10 );
+/// ```
+pub trait KeySet< Key > {
+    /// Returns `true` if `key` belongs to the set.
+    fn contains_key( &self, key: &Key ) -> bool;
+}
+
+/// The set of all keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AllKeys;
+
+impl < Key > KeySet< Key > for AllKeys {
+    fn contains_key( &self, _key: &Key ) -> bool { true }
+}
+
+impl KeySet< usize > for IndexRangeSet {
+    fn contains_key( &self, key: &usize ) -> bool { self.contains( *key ) }
+}
+
+impl < Key, S > KeySet< Key > for HashSet< Key, S >
+    where   Key:    Hash + Eq,
+            S:      BuildHasher,
+{
+    fn contains_key( &self, key: &Key ) -> bool { self.contains( key ) }
+}
+
+impl < Key > KeySet< Key > for BTreeSet< Key >
+    where   Key:    Ord,
+{
+    fn contains_key( &self, key: &Key ) -> bool { self.contains( key ) }
+}
+
+impl < Key, F > KeySet< Key > for F
+    where   F:      Fn( &Key ) -> bool,
+{
+    fn contains_key( &self, key: &Key ) -> bool { self( key ) }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  ITERATION
+//  ---------------------------------------------------------------------------
+
+
+/// Iterates over the indices of an [`IndexRangeSet`] in ascending order.
+#[derive(Clone, Debug)]
+pub struct IndexRangeSetIter< 'a > {
+    ranges:     slice::Iter< 'a, Range< usize > >,
+    current:    Range< usize >,
+}
+
+impl < 'a > Iterator for IndexRangeSetIter< 'a > {
+    type Item = usize;
+
+    fn next( &mut self ) -> Option< usize > {
+        loop {
+            if let Some( index ) = self.current.next() { return Some( index ) }
+            self.current    =   self.ranges.next()?.clone();
+        }
+    }
+}
+
+impl < 'a > IntoIterator for &'a IndexRangeSet {
+    type Item       =   usize;
+    type IntoIter   =   IndexRangeSetIter< 'a >;
+
+    fn into_iter( self ) -> Self::IntoIter { self.iter() }
+}
+
+impl IntoIterator for IndexRangeSet {
+    type Item       =   usize;
+    type IntoIter   =   std::iter::Flatten< vec::IntoIter< Range< usize > > >;
+
+    fn into_iter( self ) -> Self::IntoIter { self.ranges.into_iter().flatten() }
+}
+
+impl FromIterator< usize > for IndexRangeSet {
+    fn from_iter< I: IntoIterator< Item = usize > >( iter: I ) -> Self {
+        let mut indices: Vec< usize >   =   iter.into_iter().collect();
+        indices.sort_unstable();
+        let mut ranges: Vec< Range< usize > >   =   Vec::new();
+        for index in indices {
+            match ranges.last_mut() {
+                Some( last ) if index <= last.end   =>  { last.end = last.end.max( index + 1 ) }
+                _                                   =>  { ranges.push( index .. index + 1 ) }
+            }
+        }
+        IndexRangeSet{ ranges }
+    }
+}
+
+impl FromIterator< Range< usize > > for IndexRangeSet {
+    fn from_iter< I: IntoIterator< Item = Range< usize > > >( iter: I ) -> Self { IndexRangeSet::from_ranges( iter ) }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_insert_agrees_with_naive_set() {
+        let mut set         =   IndexRangeSet::new();
+        let mut naive       =   BTreeSet::new();
+        for (start, end) in vec![ (10, 12), (30, 35), (12, 14), (0, 1), (20, 20), (25, 31), (2, 9), (1, 2), (40, 60) ] {
+            set.insert_range( start .. end );
+            naive.extend( start .. end );
+        }
+        set.insert( 9 );
+        naive.insert( 9 );
+
+        let expected: Vec< usize >  =   naive.iter().cloned().collect();
+        assert_eq!( set.iter().collect::< Vec< _ > >(), expected );
+        assert_eq!( set.clone().into_iter().collect::< Vec< _ > >(), expected );
+        assert_eq!( set, expected.iter().cloned().collect() );
+        assert_eq!( set.len(), expected.len() );
+        assert_eq!( set.ranges(), &[ 0 .. 14, 25 .. 35, 40 .. 60 ] );
+        for i in 0 .. 70 { assert_eq!( set.contains( i ), naive.contains( &i ) ) }
+
+        let other           =   IndexRangeSet::from_ranges( vec![ 5 .. 27, 34 .. 41 ] );
+        assert_eq!( set.intersection( &other ).ranges(), &[ 5 .. 14, 25 .. 27, 34 .. 35, 40 .. 41 ] );
+        assert_eq!( set.union( &other ), IndexRangeSet::from_range( 0 .. 60 ) );
+        assert_eq!( ( set.min(), set.max() ), ( Some( 0 ), Some( 59 ) ) );
+    }
+}
@@ -325,8 +325,133 @@ impl<I, F> Iterator for HitMerge<I, F>
                  .map(|i| i.size_hint())
                  .fold1(hacked_size_hint_add)
                  .unwrap_or((0, Some(0)))
-    }  
-}      
+    }
+}
+
+
+//  HitMerge early termination
+//  ---------------------------------------------------------------------------
+
+impl<I, F> HitMerge<I, F>
+    where I: Iterator,
+          F: OrderingPredicate<I::Item>
+{
+    /// Returns a reference to the next item, without consuming it.
+    ///
+    /// ```
+    /// use solar::utilities::iterators::hit_merge::hit_merge_ascend;
+    ///
+    /// let mut hit = hit_merge_ascend( vec![ vec![1, 2], vec![0, 3] ] );
+    /// assert_eq!( hit.peek(), Some( &0 ) );
+    /// assert_eq!( hit.next(), Some( 0 ) );
+    /// ```
+    pub fn peek(&self) -> Option<&I::Item> {
+        self.heap.first().map(|x| &x.head)
+    }
+
+    /// Consume and return the next item if it satisfies `predicate`; otherwise leave it in place
+    /// and return `None`.
+    ///
+    /// ```
+    /// use solar::utilities::iterators::hit_merge::hit_merge_ascend;
+    ///
+    /// let mut hit = hit_merge_ascend( vec![ vec![1, 2], vec![0, 3] ] );
+    /// assert_eq!( hit.next_if( |x| *x < 1 ), Some( 0 ) );
+    /// assert_eq!( hit.next_if( |x| *x < 1 ), None );
+    /// assert_eq!( hit.next(), Some( 1 ) );
+    /// ```
+    pub fn next_if<P>(&mut self, predicate: P) -> Option<I::Item>
+        where P: FnOnce(&I::Item) -> bool
+    {
+        match self.peek() {
+            Some(item) if predicate(item) => self.next(),
+            _ => None,
+        }
+    }
+
+    /// Consume and return the next item if it comes strictly before `bound` in the merge order;
+    /// otherwise leave it in place and return `None`.
+    ///
+    /// This allows a caller to stop as soon as the merged sequence reaches `bound`, e.g. when
+    /// only the leading entries of a linear combination are needed.
+    ///
+    /// ```
+    /// use solar::utilities::iterators::hit_merge::hit_merge_ascend;
+    ///
+    /// let mut hit = hit_merge_ascend( vec![ vec![1, 4], vec![0, 3] ] );
+    /// let mut head = Vec::new();
+    /// while let Some(x) = hit.next_if_before( &3 ) { head.push(x) }
+    ///
+    /// assert_eq!( head, vec![ 0, 1 ] );
+    /// assert_eq!( hit.next(), Some( 3 ) );
+    /// ```
+    pub fn next_if_before(&mut self, bound: &I::Item) -> Option<I::Item> {
+        let less_than = &mut self.less_than;
+        match self.heap.first() {
+            Some(x) if less_than.ordering_predicate(&x.head, bound) => self.next(),
+            _ => None,
+        }
+    }
+
+    /// Discard items for as long as they satisfy `predicate`, and return the number discarded.
+    ///
+    /// The predicate should be *monotone* with respect to the merge order (if it holds for an
+    /// item, it holds for every earlier item).  Under this assumption each base iterator is
+    /// advanced independently and the heap is rebuilt once, which is cheaper than popping the
+    /// discarded items off the heap one at a time.
+    ///
+    /// ```
+    /// use solar::utilities::iterators::hit_merge::hit_merge_ascend;
+    ///
+    /// let mut hit = hit_merge_ascend( vec![ vec![1, 2, 6], vec![0, 3], vec![4] ] );
+    /// assert_eq!( hit.discard_while( |x| *x < 3 ), 3 );
+    /// assert_eq!( hit.collect::<Vec<_>>(), vec![ 3, 4, 6 ] );
+    /// ```
+    pub fn discard_while<P>(&mut self, predicate: P) -> usize
+        where P: FnMut(&I::Item) -> bool
+    {
+        let num_discarded = discard_heads_while(&mut self.heap, predicate);
+        let less_than = &mut self.less_than;
+        heapify(&mut self.heap, |a, b| less_than.ordering_predicate(&a.head, &b.head));
+        num_discarded
+    }
+
+    /// Discard every item that comes strictly before `bound` in the merge order, and return the
+    /// number discarded.
+    ///
+    /// ```
+    /// use solar::utilities::iterators::hit_merge::hit_merge_descend;
+    ///
+    /// let mut hit = hit_merge_descend( vec![ vec![9, 5, 1], vec![7, 2] ] );
+    /// assert_eq!( hit.discard_before( &5 ), 2 );
+    /// assert_eq!( hit.next(), Some( 5 ) );
+    /// ```
+    pub fn discard_before(&mut self, bound: &I::Item) -> usize {
+        let less_than = &mut self.less_than;
+        let num_discarded = discard_heads_while(&mut self.heap, |x| less_than.ordering_predicate(x, bound));
+        heapify(&mut self.heap, |a, b| less_than.ordering_predicate(&a.head, &b.head));
+        num_discarded
+    }
+}
+
+/// Advance each iterator in `heap` past the items at its head that satisfy `predicate`, removing
+/// exhausted iterators; returns the number of items discarded.  Does not restore the heap property.
+fn discard_heads_while<I, P>(heap: &mut Vec<HeadTail<I>>, mut predicate: P) -> usize
+    where I: Iterator,
+          P: FnMut(&I::Item) -> bool
+{
+    let mut num_discarded = 0;
+    let mut i = 0;
+    while i < heap.len() {
+        let mut exhausted = false;
+        while predicate(&heap[i].head) {
+            num_discarded += 1;
+            if heap[i].next().is_none() { exhausted = true; break }
+        }
+        if exhausted { heap.swap_remove(i); } else { i += 1; }
+    }
+    num_discarded
+}
 
 
 //  HitMerge makers
@@ -1,10 +1,15 @@
 //! Miscellaneous objects, traits, and functions used throughout the library.
 
 pub mod indexing_and_bijection;
+pub mod index_sets;
 pub mod sequences_and_ordinals;
 pub mod statistics;
 pub mod ring;
 pub mod combinatorics;
+pub mod order;
+pub mod reordering;
 pub mod heaps;
 pub mod iterators;
-pub mod cell_complexes;
\ No newline at end of file
+pub mod cell_complexes;
+pub mod workspace;
+pub mod fmt;
\ No newline at end of file
@@ -0,0 +1,84 @@
+//! Total orders on keys, represented by objects.
+//!
+//! Many algorithms in this library need to compare keys (e.g. to find the pivot of a sparse
+//! vector, or to merge two sorted vectors).  When the keys are integers, the natural order is
+//! usually what we want; but when the keys are simplices, or filtered simplices, we often need
+//! a different order.  Rather than require `Key: PartialOrd`, such algorithms can take an
+//! **order comparator**, i.e. an object that implements [`OrderComparator`].  This is analogous
+//! to the way we use ring objects rather than requiring coefficients to implement `Add` and `Mul`.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::utilities::order::{OrderComparator, OrderNatural, OrderReverse};
+//! use std::cmp::Ordering;
+//!
+//! assert!( OrderNatural.lt( &1, &2 ) );
+//! assert!( OrderReverse::new( OrderNatural ).lt( &2, &1 ) );
+//!
+//! // closures can also be used as comparators
+//! let by_length   =   | a: &Vec<usize>, b: &Vec<usize> | a.len().cmp( &b.len() );
+//! assert_eq!( by_length.compare( &vec![5, 6], &vec![0] ), Ordering::Greater );
+//! ```
+
+use std::cmp::Ordering;
+
+
+//  ---------------------------------------------------------------------------
+//  TRAIT
+//  ---------------------------------------------------------------------------
+
+
+/// An object that compares elements of type `T` according to a total order.
+pub trait OrderComparator< T > {
+
+    /// Compare `a` to `b`.
+    fn compare( &self, a: &T, b: &T ) -> Ordering;
+
+    /// Returns `true` iff `a < b`.
+    fn lt( &self, a: &T, b: &T ) -> bool { self.compare( a, b ) == Ordering::Less }
+
+    /// Returns `true` iff `a <= b`.
+    fn le( &self, a: &T, b: &T ) -> bool { self.compare( a, b ) != Ordering::Greater }
+
+    /// Returns `true` iff the elements of `slice` are in strictly ascending order.
+    fn is_strictly_ascending( &self, slice: &[ T ] ) -> bool {
+        slice.windows( 2 ).all( |w| self.lt( &w[0], &w[1] ) )
+    }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  IMPLEMENTORS
+//  ---------------------------------------------------------------------------
+
+
+/// The order given by `PartialOrd`.
+///
+/// Panics if it is asked to compare two incomparable elements (e.g. NaN).
+#[derive(Clone, Copy, Debug)]
+pub struct OrderNatural;
+
+impl < T: PartialOrd > OrderComparator< T > for OrderNatural {
+    fn compare( &self, a: &T, b: &T ) -> Ordering {
+        a.partial_cmp( b ).expect( "OrderNatural cannot compare incomparable elements" )
+    }
+}
+
+/// The reverse of a given order.
+#[derive(Clone, Copy, Debug)]
+pub struct OrderReverse< C > { order: C }
+
+impl < C > OrderReverse< C > {
+    /// Reverse the order represented by `order`.
+    pub fn new( order: C ) -> Self { OrderReverse{ order } }
+}
+
+impl < T, C: OrderComparator< T > > OrderComparator< T > for OrderReverse< C > {
+    fn compare( &self, a: &T, b: &T ) -> Ordering { self.order.compare( b, a ) }
+}
+
+/// Closures that return an `Ordering` are order comparators.
+impl < T, F: Fn( &T, &T ) -> Ordering > OrderComparator< T > for F {
+    fn compare( &self, a: &T, b: &T ) -> Ordering { self( a, b ) }
+}
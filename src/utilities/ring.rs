@@ -4,6 +4,10 @@ use crate::rings::ring::{Semiring, Ring};
 use num::integer::Integer;
 
 
+/// The sign `(-1)^k`, as used in boundary matrices.
+///
+/// This is the special case `self.power( self.negate( one ), k )` of [`Semiring::power`], computed
+/// from the parity of `k` alone (and without requiring `RingElt: Clone`).
 pub trait MinusOneToPower< RingElt > {
     fn minus_one_to_power( &self, k: usize ) -> RingElt;
 }
@@ -41,6 +41,22 @@ impl < T > BiMapSequential < T >
         if a < self.ord_to_val.len() { Some( self.ord_to_val[ a ].clone() ) } else { None }
     }      
     
+    /// The function S -> {0, .., N}, as a closure; for example, to reindex a sparse vector with
+    /// [`map_keys`](crate::vectors::vector_transforms::Transforms::map_keys).
+    /// 
+    /// The closure panics if its argument is not in S.
+    pub fn ord_lookup( &self ) -> impl Fn( T ) -> usize + '_ { 
+        move |a| self.ord( &a ).expect( "key not found in bimap" )
+    }
+
+    /// The function {0, .., N} -> S, as a closure; for example, to reindex a sparse vector with
+    /// [`map_keys`](crate::vectors::vector_transforms::Transforms::map_keys).
+    /// 
+    /// The closure panics if its argument is greater than N.
+    pub fn val_lookup( &self ) -> impl Fn( usize ) -> T + '_ { 
+        move |a| self.val( a ).expect( "ordinal out of range for bimap" )
+    }
+    
     /// Create sequential bimap
     pub fn from_vec( vec: Vec< T > ) -> BiMapSequential< T >
     {
@@ -0,0 +1,169 @@
+//! Reusable scratch space.
+//!
+//! Sparse linear algebra routines allocate lots of short-lived vectors: buffers for merging
+//! sorted vectors, lists of summands to be sorted and gathered, hash maps for accumulating
+//! coefficients.  When such a routine is called many times (e.g. once per column of a large
+//! matrix, possibly from many threads at once) the allocations can dominate the running time.
+//!
+//! A [`Workspace`] bundles these scratch structures so that they can be allocated once and reused.
+//! It can be passed explicitly to functions that accept one (such as
+//! [`multiply_major_view_with_workspace`](crate::matrices::implementors::product::multiply_major_view_with_workspace)),
+//! or borrowed from thread-local storage with [`with_thread_workspace`], which gives each thread
+//! its own workspace for each choice of key and coefficient type.
+//!
+//! **Note** at present the only routine that accepts a workspace is the matrix product.  The
+//! crate has no parallel reduction yet, the serial reduction
+//! ([`try_right_reduce_by_order_observed`](crate::matrix_factorization::vec_of_vec::try_right_reduce_by_order_observed))
+//! already reuses a single merge buffer for every column, and boundary matrix construction
+//! allocates nothing but its output.  Parallel versions of these routines should take their
+//! scratch space from a workspace.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+
+/// Scratch space for sparse vector operations with keys of type `Key` and coefficients of type
+/// `Val`.
+///
+/// The fields carry no meaning between calls: any function that borrows a workspace may assume
+/// nothing about their contents on entry, and may leave arbitrary data in them on exit.  Clearing
+/// a field keeps its allocated capacity.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::workspace::Workspace;
+/// use solar::vectors::operations::add_assign_sorted_with_buffer;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let ring                =   NativeRing::<i64>::new();
+/// let mut workspace       =   Workspace::new();
+/// let mut columns         =   vec![ vec![ (0, 1) ], vec![ (1, 1) ], vec![ (0, -1), (2, 1) ] ];
+///
+/// // add column 0 to every other column, reusing one buffer throughout
+/// let ( pivot, rest )     =   columns.split_first_mut().unwrap();
+/// for column in rest.iter_mut() {
+///     add_assign_sorted_with_buffer( column, pivot, 1, ring.clone(), &mut workspace.buffer );
+/// }
+/// assert_eq!( columns, vec![ vec![ (0, 1) ], vec![ (0, 1), (1, 1) ], vec![ (2, 1) ] ] );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Workspace< Key, Val > {
+    /// Scratch list of entries, e.g. summands waiting to be sorted and gathered.
+    pub entries:    Vec< (Key, Val) >,
+    /// Scratch buffer for merging sorted vectors, as in
+    /// [`add_assign_sorted_with_buffer`](crate::vectors::operations::add_assign_sorted_with_buffer).
+    pub buffer:     Vec< (Key, Val) >,
+    /// Scratch hash map, e.g. for accumulating coefficients by key.
+    pub hash:       HashMap< Key, Val >,
+}
+
+impl < Key, Val > Workspace< Key, Val > {
+
+    /// An empty workspace.  Nothing is allocated until the workspace is used.
+    pub fn new() -> Self {
+        Workspace{ entries: Vec::new(), buffer: Vec::new(), hash: HashMap::new() }
+    }
+
+    /// Clear every scratch structure, keeping its allocated capacity.
+    pub fn clear( &mut self ) {
+        self.entries.clear();
+        self.buffer.clear();
+        self.hash.clear();
+    }
+}
+
+impl < Key, Val > Default for Workspace< Key, Val > {
+    fn default() -> Self { Workspace::new() }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  THREAD-LOCAL WORKSPACES
+//  ---------------------------------------------------------------------------
+
+
+thread_local! {
+    /// One workspace per `(Key, Val)` type pair, stored as `Box< Rc< RefCell< Workspace< Key, Val > > > >`.
+    static WORKSPACES: RefCell< HashMap< TypeId, Box< dyn Any > > > = RefCell::new( HashMap::new() );
+}
+
+/// Call `f` with this thread's workspace for keys of type `Key` and coefficients of type `Val`.
+///
+/// The workspace is created the first time it is requested on each thread, and lives until the
+/// thread exits, so that its allocations are reused by every later call on the same thread.  It
+/// is cleared before being passed to `f`.
+///
+/// Panics if called recursively (from inside `f`) with the same `Key` and `Val`; pass the
+/// workspace down explicitly instead.
+///
+/// # Examples
+///
+/// ```
+/// use solar::utilities::workspace::with_thread_workspace;
+/// use solar::matrices::implementors::product::multiply_major_view_with_workspace;
+/// use solar::matrices::implementors::vec_of_vec::VecOfVec;
+/// use solar::matrices::matrix_oracle::MajorDimension;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let ring    =   NativeRing::<i64>::new();
+/// let a       =   VecOfVec::new( MajorDimension::Row, vec![ vec![ (0, 1), (1, 1) ], vec![ (1, 2) ] ] );
+///
+/// let squares: Vec< _ >   =   ( 0 .. 2 )
+///                                 .map(   |i|
+///                                         with_thread_workspace( |workspace|
+///                                             multiply_major_view_with_workspace( &a, &a, i, &ring, workspace )
+///                                         )
+///                                     )
+///                                 .collect();
+/// assert_eq!( squares, vec![ vec![ (0, 1), (1, 3) ], vec![ (1, 4) ] ] );
+/// ```
+pub fn with_thread_workspace< Key, Val, F, T >( f: F ) -> T
+    where   Key:    'static,
+            Val:    'static,
+            F:      FnOnce( &mut Workspace< Key, Val > ) -> T,
+{
+    // look up (or create) the workspace, and release the map before calling `f`, so that `f`
+    // may borrow workspaces of other types
+    let cell: Rc< RefCell< Workspace< Key, Val > > >
+        =   WORKSPACES.with(    |workspaces|
+                                workspaces.borrow_mut()
+                                    .entry( TypeId::of::< Workspace< Key, Val > >() )
+                                    .or_insert_with( || Box::new( Rc::new( RefCell::new( Workspace::< Key, Val >::new() ) ) ) )
+                                    .downcast_ref::< Rc< RefCell< Workspace< Key, Val > > > >()
+                                    .unwrap()
+                                    .clone()
+                            );
+    let mut workspace   =   cell.try_borrow_mut().expect( "the thread-local workspace for these types is already in use" );
+    workspace.clear();
+    f( &mut workspace )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_thread_workspace_is_reused_and_typed() {
+        let capacity        =   with_thread_workspace( |workspace: &mut Workspace< usize, i64 >| {
+                                    workspace.entries.extend( ( 0 .. 100 ).map( |i| ( i, 1 ) ) );
+                                    // a workspace of a different type can be borrowed at the same time
+                                    with_thread_workspace( |other: &mut Workspace< usize, bool >| other.buffer.push( ( 0, true ) ) );
+                                    workspace.entries.capacity()
+                                } );
+        with_thread_workspace( |workspace: &mut Workspace< usize, i64 >| {
+            assert!( workspace.entries.is_empty() );
+            assert_eq!( workspace.entries.capacity(), capacity );
+        } );
+
+        // each thread has its own workspace
+        std::thread::spawn( || with_thread_workspace( |workspace: &mut Workspace< usize, i64 >| assert_eq!( workspace.entries.capacity(), 0 ) ) )
+            .join()
+            .unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+//! Conversion between sparse vector iterators and dense vectors.
+//!
+//! A sparse vector with keys in `{0, .., len - 1}` corresponds to a dense vector of length `len`,
+//! whose `i`th coefficient is the coefficient of key `i` (or zero, if there is no such entry).
+//! These functions convert in either direction, e.g. to compare SOLAR's output with a dense
+//! computation, or to pass data to and from libraries that work with plain slices.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::vectors::convert::{to_dense, from_dense};
+//! use solar::rings::ring_native::NativeRing;
+//!
+//! let ring        =   NativeRing::<i64>::new();
+//! let sparse      =   vec![ (1, 2), (3, -1) ];
+//!
+//! let dense       =   to_dense( sparse.clone(), 5, ring.clone() );
+//! assert_eq!( dense, vec![ 0, 2, 0, -1, 0 ] );
+//!
+//! let round_trip : Vec<_>     =   from_dense( &dense, ring ).collect();
+//! assert_eq!( round_trip, sparse );
+//! ```
+
+use crate::rings::ring::Semiring;
+use crate::vector_entries::vector_entries::KeyValGet;
+
+
+/// Return the dense vector of length `len` represented by the sparse vector `iter`.
+///
+/// Entries may appear in any order; the coefficients of entries with equal keys are summed.
+/// Panics if a key is greater than or equal to `len`.
+pub fn to_dense< I, Val, RingOperator >( iter: I, len: usize, ring: RingOperator ) -> Vec< Val >
+    where   I:                  IntoIterator,
+            I::Item:            KeyValGet< Key = usize, Val = Val >,
+            RingOperator:       Semiring< Val >,
+            Val:                Clone,
+{
+    let mut dense   =   vec![ RingOperator::zero(); len ];
+    for entry in iter {
+        let key     =   entry.key();
+        assert!( key < len, "key {} is out of range for a dense vector of length {}", key, len );
+        dense[ key ]    =   ring.add( dense[ key ].clone(), entry.val() );
+    }
+    dense
+}
+
+/// Return an iterator over the nonzero entries of `dense`, as `(index, coefficient)` tuples in
+/// ascending order of index.
+pub fn from_dense< 'a, Val, RingOperator >( dense: &'a [ Val ], ring: RingOperator ) -> impl Iterator< Item = ( usize, Val ) > + 'a
+    where   RingOperator:       Semiring< Val > + 'a,
+            Val:                Clone,
+{
+    dense.iter()
+        .cloned()
+        .enumerate()
+        .filter( move |( _, val )| ! ring.is_0( val.clone() ) )
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GF2;
+    use crate::vector_entries::vector_entries::KeyValItem;
+
+
+    #[test]
+    fn test_round_trips() {
+        let ring        =   GF2::new();
+
+        // repeated keys are summed, and entries may come in any order
+        let sparse      =   vec![ (4, true), (0, true), (2, true), (4, true) ];
+        assert_eq!( to_dense( sparse, 5, ring.clone() ), vec![ true, false, true, false, false ] );
+
+        // other entry types
+        let items       =   vec![ KeyValItem{ key: 1, val: true } ];
+        assert_eq!( to_dense( items, 2, ring.clone() ), vec![ false, true ] );
+
+        // empty vectors
+        assert!( to_dense( Vec::< (usize, bool) >::new(), 0, ring.clone() ).is_empty() );
+        assert_eq!( from_dense( &[ false, false ], ring.clone() ).count(), 0 );
+
+        let dense       =   vec![ false, true, true, false ];
+        let sparse : Vec<_>     =   from_dense( &dense, ring.clone() ).collect();
+        assert_eq!( to_dense( sparse, 4, ring ), dense );
+    }
+
+    #[test]
+    #[should_panic( expected = "out of range" )]
+    fn test_key_out_of_range_panics() { to_dense( vec![ (3, true) ], 3, GF2::new() ); }
+
+}
@@ -32,13 +32,15 @@
 //! **TO DO LIST**
 //! 
 //! - add the following functions for convenience:
-//!     - (low priority) add vectors (with or without specifying precidence function)
 //!     - (low priority) a "tuple merge" method allowing one to merge iterators of several different types
 //! - use Rust 'Cells' to re-work the iterator heap to work by reference
 
 
 // pub mod svi;
 pub mod vector_transforms;
+pub mod operations;
+pub mod convert;
+pub mod sparse_vec;
 // pub mod svi_discussion;
 
 
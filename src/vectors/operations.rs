@@ -0,0 +1,683 @@
+//! Arithmetic operations on sparse vectors.
+//!
+//! The functions in this module operate on **owned** sparse vectors, i.e. on objects of type
+//! `Vec< (Key, Val) >` whose entries are sorted in strictly ascending order of key.  They
+//! write their output into a preallocated buffer and then swap it into place, so that a
+//! single buffer can be reused across many operations (as in matrix reduction).
+
+use crate::rings::ring::{Semiring};
+use crate::utilities::iterators::hit_merge::hit_merge_by;
+use crate::utilities::order::{OrderComparator, OrderNatural};
+use crate::vector_entries::vector_entries::KeyValGet;
+use std::cmp::Ordering;
+
+
+//  ---------------------------------------------------------------------------
+//  SEARCH
+//  ---------------------------------------------------------------------------
+
+
+/// Binary search on slices of entries `(key, val)` sorted in strictly ascending order of key.
+///
+/// Keys are compared with `PartialOrd`; like [`OrderNatural`], the methods panic if they are
+/// asked to compare two incomparable keys.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::SortedEntrySlice;
+///
+/// let entries     =   vec![ (1, 'a'), (3, 'b'), (4, 'c') ];
+///
+/// assert_eq!( entries.find_entry( &3 ), Some( &(3, 'b') ) );
+/// assert_eq!( entries.find_entry( &2 ), None );
+/// assert_eq!( entries.position_of_key( &4 ), Ok( 2 ) );
+/// assert_eq!( entries.position_of_key( &2 ), Err( 1 ) );     // where key 2 would be inserted
+///
+/// let ( below, rest ) =   entries.split_at_key( &3 );
+/// assert_eq!( ( below, rest ), ( &[ (1, 'a') ][..], &[ (3, 'b'), (4, 'c') ][..] ) );
+/// ```
+pub trait SortedEntrySlice< Key, Val > {
+    /// `Ok( i )` if the `i`th entry has key `key`, and otherwise `Err( i )`, where `i` is the
+    /// position where an entry with key `key` could be inserted to keep the slice sorted.
+    fn position_of_key( &self, key: &Key ) -> Result< usize, usize >;
+
+    /// The entry with key `key`, if there is one.
+    fn find_entry( &self, key: &Key ) -> Option< &( Key, Val ) >;
+
+    /// Split the slice into the entries with key strictly less than `key`, and the rest.
+    fn split_at_key( &self, key: &Key ) -> ( &Self, &Self );
+}
+
+impl < Key: PartialOrd, Val > SortedEntrySlice< Key, Val > for [ ( Key, Val ) ] {
+    fn position_of_key( &self, key: &Key ) -> Result< usize, usize > {
+        self.binary_search_by( |entry| OrderNatural.compare( &entry.0, key ) )
+    }
+
+    fn find_entry( &self, key: &Key ) -> Option< &( Key, Val ) > {
+        self.position_of_key( key ).ok().map( |position| &self[ position ] )
+    }
+
+    fn split_at_key( &self, key: &Key ) -> ( &Self, &Self ) {
+        let position    =   match self.position_of_key( key ) { Ok( position ) | Err( position ) => position };
+        self.split_at( position )
+    }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  ADD IN PLACE
+//  ---------------------------------------------------------------------------
+
+
+/// Push `(key, val)` onto the end of a sorted vector, adding `val` to the last coefficient if
+/// the last key equals `key`.
+fn push_gathered< Key, Val, RingOperator, Order >(
+        buffer:     &mut Vec< (Key, Val) >,
+        key:        Key,
+        val:        Val,
+        ring:       &RingOperator,
+        order:      &Order,
+    )
+    where   RingOperator:   Semiring< Val >,
+            Order:          OrderComparator< Key >,
+            Val:            Clone,
+{
+    if let Some( last ) = buffer.last_mut() {
+        if order.compare( &last.0, &key ) == Ordering::Equal { last.1 = ring.add( last.1.clone(), val ); return }
+    }
+    buffer.push( (key, val) );
+}
+
+/// Replace `target` with `target + scalar * source`, using `buffer` as scratch space.
+///
+/// Both `target` and `source` should be sorted in ascending order of key.  The result is
+/// sorted, has no repeated keys, and contains no entries equal to zero.  After the function
+/// returns, `buffer` is empty but keeps its allocated capacity, which can be reused in
+/// subsequent calls.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::add_assign_sorted_with_buffer;
+/// use solar::rings::ring_native::NativeDivisionRing;
+///
+/// let ring            =   NativeDivisionRing::<f64>::new();
+/// let mut target      =   vec![ (0, 1.), (1, 1.)          ];
+/// let source          =   vec![          (1, 1.), (2, 1.) ];
+/// let mut buffer      =   Vec::new();
+///
+/// add_assign_sorted_with_buffer( &mut target, &source, -1., ring, &mut buffer );
+/// assert_eq!( target, vec![ (0, 1.), (2, -1.) ] );
+/// ```
+pub fn add_assign_sorted_with_buffer< Key, Val, RingOperator >(
+        target:     &mut Vec< (Key, Val) >,
+        source:     &[ (Key, Val) ],
+        scalar:     Val,
+        ring:       RingOperator,
+        buffer:     &mut Vec< (Key, Val) >,
+    )
+    where   RingOperator:   Semiring< Val >,
+            Key:            Clone + PartialOrd,
+            Val:            Clone,
+{
+    add_assign_sorted_by_with_buffer( target, source, scalar, ring, OrderNatural, buffer )
+}
+
+/// Replace `target` with `target + scalar * source`, where keys are ordered by `order`.
+///
+/// This is the same as [`add_assign_sorted_with_buffer`], except that both vectors should be
+/// sorted in ascending order with respect to `order` (rather than the order given by
+/// `PartialOrd`).
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::add_assign_sorted_by_with_buffer;
+/// use solar::utilities::order::{OrderReverse, OrderNatural};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// // vectors sorted in descending order of key
+/// let mut target      =   vec![ (2, 1), (0, 1)         ];
+/// let source          =   vec![ (2, 1), (1, 1)         ];
+/// let mut buffer      =   Vec::new();
+///
+/// add_assign_sorted_by_with_buffer( &mut target, &source, -1, NativeRing::<i64>::new(), OrderReverse::new( OrderNatural ), &mut buffer );
+/// assert_eq!( target, vec![ (1, -1), (0, 1) ] );
+/// ```
+pub fn add_assign_sorted_by_with_buffer< Key, Val, RingOperator, Order >(
+        target:     &mut Vec< (Key, Val) >,
+        source:     &[ (Key, Val) ],
+        scalar:     Val,
+        ring:       RingOperator,
+        order:      Order,
+        buffer:     &mut Vec< (Key, Val) >,
+    )
+    where   RingOperator:   Semiring< Val >,
+            Order:          OrderComparator< Key >,
+            Key:            Clone,
+            Val:            Clone,
+{
+    buffer.clear();
+    buffer.reserve( target.len() + source.len() );
+
+    {
+        let mut target_iter     =   target.drain(..).peekable();
+        let mut source_iter     =   source.iter().peekable();
+
+        loop {
+            let take_target     =   match ( target_iter.peek(), source_iter.peek() ) {
+                                        ( None,     None     )  =>  break,
+                                        ( Some(_),  None     )  =>  true,
+                                        ( None,     Some(_)  )  =>  false,
+                                        ( Some(t),  Some(s)  )  =>  order.le( &t.0, &s.0 ),
+                                    };
+            if take_target {
+                let (key, val)  =   target_iter.next().unwrap();
+                push_gathered( buffer, key, val, &ring, &order );
+            } else {
+                let (key, val)  =   source_iter.next().unwrap().clone();
+                push_gathered( buffer, key, ring.multiply( val, scalar.clone() ), &ring, &order );
+            }
+        }
+    }
+
+    buffer.retain( |x| ! ring.is_0( x.1.clone() ) );
+    std::mem::swap( target, buffer );
+}
+
+/// Replace `target` with `target + scalar * source`.
+///
+/// Both vectors should be sorted in ascending order of key.  The result is sorted, has no
+/// repeated keys, and contains no entries equal to zero.  See
+/// [`add_assign_sorted_with_buffer`] for a variant that reuses a caller-supplied buffer.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::add_assign_sorted;
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let mut target      =   vec![ (0, 1), (3, 1) ];
+/// add_assign_sorted( &mut target, &vec![ (1, 1), (3, 1) ], 2, NativeRing::<i64>::new() );
+/// assert_eq!( target, vec![ (0, 1), (1, 2), (3, 3) ] );
+/// ```
+pub fn add_assign_sorted< Key, Val, RingOperator >(
+        target:     &mut Vec< (Key, Val) >,
+        source:     &[ (Key, Val) ],
+        scalar:     Val,
+        ring:       RingOperator,
+    )
+    where   RingOperator:   Semiring< Val >,
+            Key:            Clone + PartialOrd,
+            Val:            Clone,
+{
+    let mut buffer  =   Vec::with_capacity( target.len() + source.len() );
+    add_assign_sorted_with_buffer( target, source, scalar, ring, &mut buffer );
+}
+
+/// Replace `a` with the sum `a + b`.
+///
+/// Both vectors should be sorted in ascending order of key.  The result is sorted, has no
+/// repeated keys, and contains no entries equal to zero.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::merge_sorted_vecs_in_place;
+/// use solar::rings::field_prime::GF2;
+///
+/// let mut a   =   vec![ (0, true), (1, true)            ];
+/// let b       =   vec![            (1, true), (2, true) ];
+/// merge_sorted_vecs_in_place( &mut a, &b, GF2::new() );
+/// assert_eq!( a, vec![ (0, true), (2, true) ] );
+/// ```
+pub fn merge_sorted_vecs_in_place< Key, Val, RingOperator >(
+        a:          &mut Vec< (Key, Val) >,
+        b:          &[ (Key, Val) ],
+        ring:       RingOperator,
+    )
+    where   RingOperator:   Semiring< Val >,
+            Key:            Clone + PartialOrd,
+            Val:            Clone,
+{
+    add_assign_sorted( a, b, RingOperator::one(), ring );
+}
+
+/// Replace `target` with `scalar * target`, dropping any entries that become zero.
+///
+/// Coefficients are multiplied in place, so no buffer is needed; the order of the entries is
+/// unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::scale_in_place;
+/// use solar::rings::field_prime::GFp;
+///
+/// let mut target  =   vec![ (0, 1), (2, 3) ];
+/// scale_in_place( &mut target, 2, GFp::new( 5 ) );
+/// assert_eq!( target, vec![ (0, 2), (2, 1) ] );
+///
+/// scale_in_place( &mut target, 0, GFp::new( 5 ) );
+/// assert!( target.is_empty() );
+/// ```
+pub fn scale_in_place< Key, Val, RingOperator >(
+        target:     &mut Vec< (Key, Val) >,
+        scalar:     Val,
+        ring:       RingOperator,
+    )
+    where   RingOperator:   Semiring< Val >,
+            Val:            Clone,
+{
+    if ring.is_0( scalar.clone() ) { target.clear(); return }
+    for entry in target.iter_mut() {
+        entry.1 = ring.multiply( entry.1.clone(), scalar.clone() );
+    }
+    target.retain( |x| ! ring.is_0( x.1.clone() ) );
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  DENSE KERNELS
+//  ---------------------------------------------------------------------------
+
+
+/// Replace `target` with `target + alpha * source`, coefficient by coefficient.
+///
+/// This is the inner loop of a row operation between two sparse vectors with the same sparsity
+/// pattern (a dense block).  On x86-64 processors that support AVX (detected at run time) the
+/// coefficients are processed four at a time with AVX instructions; on every other target the
+/// kernel falls back to a scalar loop.  Both paths round identically, since neither fuses the
+/// multiplication with the addition.  Panics if the slices have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::axpy_f64;
+///
+/// let mut target  =   vec![ 1.; 11 ];
+/// let source: Vec< f64 >  =   ( 0 .. 11 ).map( |i| i as f64 ).collect();
+/// axpy_f64( &mut target, 2., &source );
+/// assert_eq!( target[ 10 ], 21. );
+/// ```
+pub fn axpy_f64( target: &mut [ f64 ], alpha: f64, source: &[ f64 ] ) {
+    assert_eq!( target.len(), source.len(), "axpy requires slices of equal length" );
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!( "avx" ) {
+            // SAFETY: the processor supports AVX, and the slices have equal length
+            unsafe { axpy_f64_avx( target, alpha, source ) }
+            return
+        }
+    }
+    axpy_f64_scalar( target, alpha, source )
+}
+
+/// The portable fallback for [`axpy_f64`].
+fn axpy_f64_scalar( target: &mut [ f64 ], alpha: f64, source: &[ f64 ] ) {
+    for ( t, s ) in target.iter_mut().zip( source ) { *t += alpha * s }
+}
+
+/// The AVX kernel for [`axpy_f64`].
+///
+/// # Safety
+///
+/// The processor must support AVX, and `source` must be at least as long as `target`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature( enable = "avx" )]
+unsafe fn axpy_f64_avx( target: &mut [ f64 ], alpha: f64, source: &[ f64 ] ) {
+    use std::arch::x86_64::{_mm256_add_pd, _mm256_loadu_pd, _mm256_mul_pd, _mm256_set1_pd, _mm256_storeu_pd};
+
+    const LANES: usize  =   4;
+    let num_chunks      =   target.len() / LANES;
+    let alpha_lanes     =   _mm256_set1_pd( alpha );
+    for chunk in 0 .. num_chunks {
+        let t           =   target.as_mut_ptr().add( chunk * LANES );
+        let s           =   source.as_ptr().add( chunk * LANES );
+        _mm256_storeu_pd( t, _mm256_add_pd( _mm256_loadu_pd( t ), _mm256_mul_pd( alpha_lanes, _mm256_loadu_pd( s ) ) ) );
+    }
+    let done            =   num_chunks * LANES;
+    axpy_f64_scalar( &mut target[ done .. ], alpha, &source[ done .. ] )
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  SUM OF MANY VECTORS
+//  ---------------------------------------------------------------------------
+
+
+/// Sum an arbitrary number of sparse vectors, returning a single simplified vector.
+///
+/// Each input should be sorted in ascending order of key with respect to `order`.  The inputs
+/// are merged lazily with a heap of iterators (see [`hit_merge_by`]), then entries with equal
+/// keys are summed and entries equal to zero are dropped.  The output is sorted in strictly
+/// ascending order with respect to `order`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::sum_sparse_vectors;
+/// use solar::utilities::order::{OrderNatural, OrderReverse};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let ring    =   NativeRing::<i64>::new();
+/// let vecs    =   vec![   vec![ (0, 1), (2, 1)         ],
+///                         vec![ (0, -1),        (3, 1) ],
+///                         vec![         (2, 4), (3, 1) ], ];
+///
+/// assert_eq!( sum_sparse_vectors( vecs.clone(), ring.clone(), OrderNatural ), vec![ (2, 5), (3, 2) ] );
+///
+/// // vectors sorted in descending order
+/// let reversed: Vec< Vec< _ > >   =   vecs.into_iter().map( |v| v.into_iter().rev().collect() ).collect();
+/// assert_eq!( sum_sparse_vectors( reversed, ring, OrderReverse::new( OrderNatural ) ), vec![ (3, 2), (2, 5) ] );
+/// ```
+pub fn sum_sparse_vectors< I, Key, Val, RingOperator, Order >(
+        iterables:  I,
+        ring:       RingOperator,
+        order:      Order,
+    )
+    ->
+    Vec< (Key, Val) >
+
+    where   I:                                      IntoIterator,
+            I::Item:                                IntoIterator,
+            < I::Item as IntoIterator >::Item:      KeyValGet< Key = Key, Val = Val >,
+            RingOperator:                           Semiring< Val >,
+            Order:                                  OrderComparator< Key >,
+            Val:                                    Clone,
+{
+    let merged      =   hit_merge_by( iterables, |a, b| order.lt( &a.key(), &b.key() ) );
+    let mut sum     =   Vec::new();
+    for entry in merged {
+        push_gathered( &mut sum, entry.key(), entry.val(), &ring, &order );
+    }
+    sum.retain( |x| ! ring.is_0( x.1.clone() ) );
+    sum
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  INNER PRODUCT
+//  ---------------------------------------------------------------------------
+
+
+/// Return the inner product `sum_k a[k] * b[k]` of two sparse vectors.
+///
+/// Both vectors should be sorted in ascending order of key with respect to `order`; they are
+/// walked simultaneously, so the cost is linear in the total number of entries.  Keys may be
+/// repeated, in which case their coefficients are summed (as by
+/// [`gather`](crate::vectors::vector_transforms::Transforms::gather)) before multiplying.  Each
+/// product is formed as `a[k] * b[k]`, with the coefficient of `a` on the left.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::dot;
+/// use solar::utilities::order::{OrderNatural, OrderReverse};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let ring    =   NativeRing::<i64>::new();
+/// let a       =   vec![ (0, 1), (1, 2),         (3, 4) ];
+/// let b       =   vec![         (1, 3), (2, 5), (3, -1) ];
+/// assert_eq!( dot( a.clone(), b.clone(), ring.clone(), OrderNatural ), 2 );
+///
+/// // vectors sorted in descending order
+/// let a_rev: Vec<_>   =   a.into_iter().rev().collect();
+/// let b_rev: Vec<_>   =   b.into_iter().rev().collect();
+/// assert_eq!( dot( a_rev, b_rev, ring, OrderReverse::new( OrderNatural ) ), 2 );
+/// ```
+pub fn dot< IterA, IterB, Key, Val, RingOperator, Order >(
+        a:          IterA,
+        b:          IterB,
+        ring:       RingOperator,
+        order:      Order,
+    )
+    -> Val
+
+    where   IterA:              IntoIterator,
+            IterB:              IntoIterator,
+            IterA::Item:        KeyValGet< Key = Key, Val = Val >,
+            IterB::Item:        KeyValGet< Key = Key, Val = Val >,
+            RingOperator:       Semiring< Val >,
+            Order:              OrderComparator< Key >,
+{
+    let mut a       =   a.into_iter().map( |x| ( x.key(), x.val() ) ).peekable();
+    let mut b       =   b.into_iter().map( |x| ( x.key(), x.val() ) ).peekable();
+    let mut sum     =   RingOperator::zero();
+
+    while let ( Some(x), Some(y) ) = ( a.peek(), b.peek() ) {
+        match order.compare( &x.0, &y.0 ) {
+            Ordering::Less      =>  { a.next(); }
+            Ordering::Greater   =>  { b.next(); }
+            Ordering::Equal     =>  {
+                let ( key, mut val_a )  =   a.next().unwrap();
+                let mut val_b           =   b.next().unwrap().1;
+                // gather repeated keys
+                while let Some( x ) = a.next_if( |x| order.compare( &x.0, &key ) == Ordering::Equal ) { val_a = ring.add( val_a, x.1 ) }
+                while let Some( y ) = b.next_if( |y| order.compare( &y.0, &key ) == Ordering::Equal ) { val_b = ring.add( val_b, y.1 ) }
+                sum     =   ring.add( sum, ring.multiply( val_a, val_b ) );
+            }
+        }
+    }
+    sum
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  COMPARISON
+//  ---------------------------------------------------------------------------
+
+
+/// A key where two sparse vectors differ, together with the coefficient of each vector.
+///
+/// A coefficient that is not explicitly stored is reported as zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseDiffEntry< Key, Val > {
+    pub key:    Key,
+    pub val_a:  Val,
+    pub val_b:  Val,
+}
+
+/// Return the entries where two sparse vectors differ.
+///
+/// Both vectors should be sorted in strictly ascending order of key.  A key that appears in one
+/// vector but not the other is treated as having coefficient zero in the vector where it does
+/// not appear; so explicitly stored zeros never produce a difference.  Coefficients are
+/// compared with `PartialEq`.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::{diff_sparse, SparseDiffEntry};
+/// use solar::rings::ring_native::NativeRing;
+///
+/// let a   =   vec![ (0, 1), (1, 2), (3, 0) ];
+/// let b   =   vec![ (0, 1), (1, 5), (2, 4) ];
+///
+/// let diff = diff_sparse( a, b, NativeRing::<i64>::new() );
+/// assert_eq!( diff, vec![
+///                 SparseDiffEntry{ key: 1, val_a: 2, val_b: 5 },
+///                 SparseDiffEntry{ key: 2, val_a: 0, val_b: 4 },
+///             ] );
+/// ```
+pub fn diff_sparse< IterA, IterB, Key, Val, RingOperator >(
+        a:          IterA,
+        b:          IterB,
+        ring:       RingOperator,
+    )
+    ->
+    Vec< SparseDiffEntry< Key, Val > >
+
+    where   IterA:              IntoIterator,
+            IterB:              IntoIterator,
+            IterA::Item:        KeyValGet< Key = Key, Val = Val >,
+            IterB::Item:        KeyValGet< Key = Key, Val = Val >,
+            RingOperator:       Semiring< Val >,
+            Key:                PartialOrd,
+            Val:                Clone + PartialEq,
+{
+    let mut a       =   a.into_iter().map( |x| ( x.key(), x.val() ) ).peekable();
+    let mut b       =   b.into_iter().map( |x| ( x.key(), x.val() ) ).peekable();
+    let mut diff    =   Vec::new();
+
+    loop {
+        let (key, val_a, val_b)     =   match ( a.peek(), b.peek() ) {
+                                            ( None,     None    )   =>  break,
+                                            ( Some(_),  None    )   =>  { let x = a.next().unwrap(); ( x.0, x.1, RingOperator::zero() ) },
+                                            ( None,     Some(_) )   =>  { let y = b.next().unwrap(); ( y.0, RingOperator::zero(), y.1 ) },
+                                            ( Some(x),  Some(y) )   =>  {
+                                                if x.0 < y.0        { let x = a.next().unwrap(); ( x.0, x.1, RingOperator::zero() ) }
+                                                else if y.0 < x.0   { let y = b.next().unwrap(); ( y.0, RingOperator::zero(), y.1 ) }
+                                                else                { let x = a.next().unwrap(); let y = b.next().unwrap(); ( x.0, x.1, y.1 ) }
+                                            }
+                                        };
+        let equal   =   ( ring.is_0( val_a.clone() ) && ring.is_0( val_b.clone() ) ) || val_a == val_b;
+        if ! equal { diff.push( SparseDiffEntry{ key, val_a, val_b } ) }
+    }
+    diff
+}
+
+
+/// Returns `true` if two sparse vectors are equal once explicitly stored zeros are ignored.
+///
+/// Vectors built along different paths (e.g. lazily vs. materialized) often differ only in
+/// which zero entries they store explicitly; this comparison treats such entries as absent.
+/// Both vectors should be sorted in strictly ascending order of key.  See [`diff_sparse`] to find
+/// the keys where two vectors differ.
+///
+/// # Examples
+///
+/// ```
+/// use solar::vectors::operations::equal_modulo_zeros;
+/// use solar::rings::field_prime::GF2;
+///
+/// let a   =   vec![ (0, true), (1, false), (4, true) ];
+/// let b   =   vec![ (0, true), (2, false), (4, true) ];
+///
+/// assert!( a != b );
+/// assert!( equal_modulo_zeros( a, b, GF2::new() ) );
+/// ```
+pub fn equal_modulo_zeros< IterA, IterB, Key, Val, RingOperator >(
+        a:          IterA,
+        b:          IterB,
+        ring:       RingOperator,
+    )
+    -> bool
+
+    where   IterA:              IntoIterator,
+            IterB:              IntoIterator,
+            IterA::Item:        KeyValGet< Key = Key, Val = Val >,
+            IterB::Item:        KeyValGet< Key = Key, Val = Val >,
+            RingOperator:       Semiring< Val >,
+            Key:                PartialOrd,
+            Val:                Clone + PartialEq,
+{
+    diff_sparse( a, b, ring ).is_empty()
+}
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GF2;
+    use crate::rings::ring_native::NativeRing;
+
+    #[test]
+    fn test_add_assign_sorted_reuses_buffer() {
+        let ring            =   NativeRing::<i64>::new();
+        let mut buffer      =   Vec::new();
+        let mut target      =   vec![ (0, 1), (2, 2), (4, 3) ];
+
+        add_assign_sorted_with_buffer( &mut target, &[ (1, 1), (2, -1) ], 2, ring.clone(), &mut buffer );
+        assert_eq!( target, vec![ (0, 1), (1, 2), (4, 3) ] );
+
+        add_assign_sorted_with_buffer( &mut target, &[ (0, 1), (5, 1) ], -1, ring.clone(), &mut buffer );
+        assert_eq!( target, vec![ (1, 2), (4, 3), (5, -1) ] );
+
+        add_assign_sorted_with_buffer( &mut target, &[], 7, ring, &mut buffer );
+        assert_eq!( target, vec![ (1, 2), (4, 3), (5, -1) ] );
+    }
+
+    #[test]
+    fn test_sorted_entry_slice() {
+        let entries         =   [ (0, 1), (2, 2), (4, 3), (6, 4) ];
+        for key in 0 .. 8 {
+            let linear      =   entries.iter().find( |x| x.0 == key );
+            assert_eq!( entries.find_entry( &key ), linear );
+            let ( below, rest )     =   entries.split_at_key( &key );
+            assert!( below.iter().all( |x| x.0 < key ) && rest.iter().all( |x| x.0 >= key ) );
+            assert_eq!( below.len() + rest.len(), entries.len() );
+        }
+        let empty : Vec< (usize, i64) >     =   Vec::new();
+        assert_eq!( ( empty.position_of_key( &1 ), empty.find_entry( &1 ) ), ( Err( 0 ), None ) );
+    }
+
+    #[test]
+    fn test_diff_sparse_ignores_explicit_zeros() {
+        let ring            =   NativeRing::<i64>::new();
+        let a               =   [ (0, 0), (2, 1), (4, 0) ];
+        let b               =   vec![ (1, 0), (2, 1)         ];
+        assert!( diff_sparse( a.iter().cloned(), b.iter().cloned(), ring.clone() ).is_empty() );
+
+        let c               =   vec![ (2, 1), (4, 3) ];
+        assert_eq!(     diff_sparse( b, c, ring ),
+                        vec![ SparseDiffEntry{ key: 4, val_a: 0, val_b: 3 } ]   );
+    }
+
+    #[test]
+    fn test_sum_sparse_vectors_cancels_over_gf2() {
+        let ring            =   GF2::new();
+        let vecs            =   vec![   vec![ (0, true), (1, true) ],
+                                        vec![ (1, true), (2, true) ],
+                                        vec![ (0, true), (2, true) ], ];
+        assert!( sum_sparse_vectors( vecs, ring.clone(), OrderNatural ).is_empty() );
+
+        let none: Vec< Vec< (usize, bool) > >   =   Vec::new();
+        assert!( sum_sparse_vectors( none, ring, OrderNatural ).is_empty() );
+    }
+
+    #[test]
+    fn test_dot() {
+        let ring            =   NativeRing::<i64>::new();
+        // repeated keys are gathered before multiplying
+        let a               =   vec![ (0, 1), (2, 1), (2, 2), (5, 1), (7, 3) ];
+        let b               =   vec![ (1, 4), (2, 2), (5, 0), (5, -1), (7, 1), (9, 1) ];
+        assert_eq!( dot( a.clone(), b.clone(), ring.clone(), OrderNatural ), 6 - 1 + 3 );
+        assert_eq!( dot( b, a, ring.clone(), OrderNatural ), 8 );
+        assert_eq!( dot( Vec::< (usize, i64) >::new(), vec![ (0, 1) ], ring, OrderNatural ), 0 );
+
+        // entries that cancel over GF2
+        let x               =   vec![ (0, true), (3, true) ];
+        assert!( ! dot( x.clone(), x.clone(), GF2::new(), OrderNatural ) );
+        assert!( dot( x, vec![ (3, true) ], GF2::new(), OrderNatural ) );
+    }
+
+    #[test]
+    fn test_equal_modulo_zeros() {
+        let ring            =   NativeRing::<i64>::new();
+        assert!( equal_modulo_zeros( vec![ (0, 0), (3, 2) ], vec![ (3, 2), (5, 0) ], ring.clone() ) );
+        assert!( equal_modulo_zeros( Vec::< (usize, i64) >::new(), vec![ (1, 0) ], ring.clone() ) );
+        assert!( ! equal_modulo_zeros( vec![ (0, 0), (3, 2) ], vec![ (3, 3) ], ring.clone() ) );
+        assert!( ! equal_modulo_zeros( vec![ (0, 1) ], vec![ (1, 1) ], ring ) );
+    }
+
+    #[test]
+    fn test_axpy_f64_agrees_with_scalar_loop() {
+        for len in 0 .. 19 {
+            let source: Vec< f64 >  =   ( 0 .. len ).map( |i| ( i as f64 ).sin() * 1e3 ).collect();
+            let start: Vec< f64 >   =   ( 0 .. len ).map( |i| 1. / ( i as f64 + 3. ) ).collect();
+            let ( mut fast, mut slow )  =   ( start.clone(), start );
+            axpy_f64( &mut fast, -0.3, &source );
+            axpy_f64_scalar( &mut slow, -0.3, &source );
+            assert_eq!( fast, slow, "len = {}", len );      // bitwise equal
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Owned sparse vectors, with arithmetic operators.
+//!
+//! A [`SparseVec`] stores its entries as a `Vec< (Key, Val) >`, sorted in strictly ascending
+//! order of key.  It can be built with `collect()`, iterated over like any other sparse vector,
+//! and searched for a key by binary search.
+//!
+//! Sparse vectors do not know their coefficient ring, so arithmetic needs a ring object.  The
+//! method [`with_ring`](SparseVec::with_ring) pairs a vector with a reference to a ring; the
+//! resulting [`WithRing`] supports `+`, `-` and `*` (by a scalar), and these operations return
+//! new `SparseVec`s with zero entries dropped.
+//!
+//! # Examples
+//!
+//! ```
+//! use solar::vectors::sparse_vec::SparseVec;
+//! use solar::rings::ring_native::NativeRing;
+//!
+//! let ring                            =   NativeRing::<i64>::new();
+//! let a : SparseVec< usize, i64 >     =   vec![ (3, 1), (0, 1) ].into_iter().collect();   // entries are sorted
+//! let b : SparseVec< usize, i64 >     =   vec![ (0, 1), (1, 2) ].into_iter().collect();
+//!
+//! assert_eq!( a.get( &3 ), Some( &1 ) );
+//! assert_eq!( a.get( &2 ), None );
+//!
+//! let difference  =   a.with_ring( &ring ) - &b;
+//! assert_eq!( difference.entries(), &[ (1, -2), (3, 1) ] );
+//!
+//! let scaled      =   difference.with_ring( &ring ) * 3;
+//! assert_eq!( scaled.into_iter().collect::< Vec<_> >(), vec![ (1, -6), (3, 3) ] );
+//! ```
+
+use crate::rings::ring::{Semiring, Ring};
+use crate::utilities::order::OrderNatural;
+use crate::vectors::operations::{scale_in_place, SortedEntrySlice};
+use crate::vectors::vector_transforms::Transforms;
+use std::iter::FromIterator;
+use std::ops::{Add, Mul, Sub};
+
+
+//  ---------------------------------------------------------------------------
+//  SPARSE VECTORS
+//  ---------------------------------------------------------------------------
+
+
+/// A sparse vector, stored as a list of `(key, coefficient)` entries in strictly ascending
+/// order of key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseVec< Key, Val > {
+    entries:    Vec< ( Key, Val ) >,
+}
+
+impl < Key, Val > SparseVec< Key, Val > {
+    /// The vector with no entries.
+    pub fn new() -> Self { SparseVec{ entries: Vec::new() } }
+
+    /// The entries, in ascending order of key.
+    pub fn entries( &self ) -> &[ ( Key, Val ) ] { &self.entries }
+
+    /// The entries, in ascending order of key.
+    pub fn into_entries( self ) -> Vec< ( Key, Val ) > { self.entries }
+
+    /// The number of stored entries.
+    pub fn len( &self ) -> usize { self.entries.len() }
+
+    /// Returns `true` if the vector stores no entries.
+    pub fn is_empty( &self ) -> bool { self.entries.is_empty() }
+
+    /// An iterator over the entries, in ascending order of key.
+    pub fn iter( &self ) -> std::slice::Iter< '_, ( Key, Val ) > { self.entries.iter() }
+
+    /// Pair the vector with a ring, to add, subtract, and scale it with operators.
+    pub fn with_ring< 'a, RingOperator >( &'a self, ring: &'a RingOperator ) -> WithRing< 'a, Key, Val, RingOperator > {
+        WithRing{ vec: self, ring }
+    }
+}
+
+impl < Key: PartialOrd, Val > SparseVec< Key, Val > {
+    /// The vector with entries `entries`.
+    ///
+    /// Panics if the keys are not in strictly ascending order.
+    pub fn from_sorted( entries: Vec< ( Key, Val ) > ) -> Self {
+        assert!( entries.windows( 2 ).all( |w| w[0].0 < w[1].0 ), "the keys of a sparse vector must be strictly ascending" );
+        SparseVec{ entries }
+    }
+
+    /// The coefficient of `key`, or `None` if there is no entry with key `key`; found by binary
+    /// search.
+    pub fn get( &self, key: &Key ) -> Option< &Val > {
+        self.entries.find_entry( key ).map( |entry| &entry.1 )
+    }
+}
+
+impl < Key, Val > Default for SparseVec< Key, Val > {
+    fn default() -> Self { SparseVec::new() }
+}
+
+/// Collect entries in any order; they are sorted by key.  Panics if two entries have the same
+/// key (use [`simplify`](crate::vectors::vector_transforms::Transforms::simplify) on sorted
+/// input to merge them first).
+impl < Key: PartialOrd, Val > FromIterator< ( Key, Val ) > for SparseVec< Key, Val > {
+    fn from_iter< I: IntoIterator< Item = ( Key, Val ) > >( iter: I ) -> Self {
+        let mut entries : Vec< _ >  =   iter.into_iter().collect();
+        entries.sort_by( |a, b| a.0.partial_cmp( &b.0 ).expect( "the keys of a sparse vector must be comparable" ) );
+        assert!( entries.windows( 2 ).all( |w| w[0].0 < w[1].0 ), "a sparse vector cannot have two entries with the same key" );
+        SparseVec{ entries }
+    }
+}
+
+impl < Key, Val > IntoIterator for SparseVec< Key, Val > {
+    type Item       =   ( Key, Val );
+    type IntoIter   =   std::vec::IntoIter< ( Key, Val ) >;
+    fn into_iter( self ) -> Self::IntoIter { self.entries.into_iter() }
+}
+
+impl < 'a, Key, Val > IntoIterator for &'a SparseVec< Key, Val > {
+    type Item       =   &'a ( Key, Val );
+    type IntoIter   =   std::slice::Iter< 'a, ( Key, Val ) >;
+    fn into_iter( self ) -> Self::IntoIter { self.entries.iter() }
+}
+
+
+//  ---------------------------------------------------------------------------
+//  ARITHMETIC
+//  ---------------------------------------------------------------------------
+
+
+/// A [`SparseVec`] paired with a ring, which supports `+`, `-` and `*` (by a scalar, on the
+/// right).
+#[derive(Debug)]
+pub struct WithRing< 'a, Key, Val, RingOperator > {
+    vec:        &'a SparseVec< Key, Val >,
+    ring:       &'a RingOperator,
+}
+
+impl < 'a, 'b, Key, Val, RingOperator > Add< &'b SparseVec< Key, Val > > for WithRing< 'a, Key, Val, RingOperator >
+    where   RingOperator:   Semiring< Val >,
+            Key:            Clone + PartialOrd,
+            Val:            Clone,
+{
+    type Output = SparseVec< Key, Val >;
+
+    fn add( self, other: &'b SparseVec< Key, Val > ) -> SparseVec< Key, Val > {
+        let entries     =   self.vec.iter().cloned().add( other.iter().cloned(), self.ring, OrderNatural ).collect();
+        SparseVec{ entries }
+    }
+}
+
+impl < 'a, 'b, Key, Val, RingOperator > Sub< &'b SparseVec< Key, Val > > for WithRing< 'a, Key, Val, RingOperator >
+    where   RingOperator:   Ring< Val > + Clone,
+            Key:            Clone + PartialOrd,
+            Val:            Clone,
+{
+    type Output = SparseVec< Key, Val >;
+
+    fn sub( self, other: &'b SparseVec< Key, Val > ) -> SparseVec< Key, Val > {
+        let entries     =   self.vec.iter().cloned().subtract( other.iter().cloned(), self.ring.clone(), OrderNatural ).collect();
+        SparseVec{ entries }
+    }
+}
+
+impl < 'a, Key, Val, RingOperator > Mul< Val > for WithRing< 'a, Key, Val, RingOperator >
+    where   RingOperator:   Semiring< Val >,
+            Key:            Clone,
+            Val:            Clone,
+{
+    type Output = SparseVec< Key, Val >;
+
+    fn mul( self, scalar: Val ) -> SparseVec< Key, Val > {
+        let mut entries     =   self.vec.entries.clone();
+        scale_in_place( &mut entries, scalar, self.ring );
+        SparseVec{ entries }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::rings::field_prime::GF2;
+    use crate::rings::ring_native::NativeRing;
+
+
+    #[test]
+    fn test_sparse_vec() {
+        let ring                            =   NativeRing::<i64>::new();
+        let a : SparseVec< usize, i64 >     =   SparseVec::from_sorted( vec![ (0, 1), (2, 2), (5, -1) ] );
+        let b : SparseVec< usize, i64 >     =   vec![ (5, 1), (2, -2), (1, 4) ].into_iter().collect();
+
+        assert_eq!( b.entries(), &[ (1, 4), (2, -2), (5, 1) ] );
+        assert_eq!( ( a.get( &0 ), a.get( &5 ), a.get( &6 ) ), ( Some( &1 ), Some( &-1 ), None ) );
+        assert_eq!( ( a.len(), SparseVec::< usize, i64 >::new().is_empty() ), ( 3, true ) );
+
+        // cancelling entries are dropped
+        let sum     =   a.with_ring( &ring ) + &b;
+        assert_eq!( sum.entries(), &[ (0, 1), (1, 4) ] );
+        let zero    =   a.with_ring( &ring ) - &a;
+        assert!( zero.is_empty() );
+        assert!( ( a.with_ring( &ring ) * 0 ).is_empty() );
+        assert_eq!( ( sum.with_ring( &ring ) - &b ).with_ring( &ring ) + &b, sum );
+
+        // references iterate over entries, as other sparse vectors do
+        assert_eq!( ( &a ).into_iter().map( |x| x.0 ).sum::< usize >(), 7 );
+
+        // a ring of characteristic two
+        let x : SparseVec< usize, bool >    =   vec![ (0, true), (1, true) ].into_iter().collect();
+        let y : SparseVec< usize, bool >    =   vec![ (1, true), (2, true) ].into_iter().collect();
+        assert_eq!( ( x.with_ring( &GF2::new() ) + &y ).into_entries(), vec![ (0, true), (2, true) ] );
+    }
+
+    #[test]
+    #[should_panic( expected = "same key" )]
+    fn test_repeated_keys_panic() { let _ : SparseVec< usize, i64 > = vec![ (1, 1), (1, 1) ].into_iter().collect(); }
+
+}
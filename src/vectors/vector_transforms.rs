@@ -1,12 +1,21 @@
-//! Transformations on sparse vector iterators: [`Gather`] , [`Scale`], [`DropZeros`].
+//! Transformations on sparse vector iterators: [`Gather`] , [`Scale`], [`Negate`], [`DropZeros`],
+//! [`GatherDropCount`], [`Simplify`], [`SortedSum`], [`ChangeCoefficients`], [`MapKeys`],
+//! [`FilterKeys`], [`AssertSorted`], and the linear combination builder
+//! [`LinearCombinationSimplified`].
 //!
 // //! By definition, a *sparse vector iterator* (SVI) is struct that implements `Iterator< Item = KeyValItem< Index, 
 // //! Coeff > >`.
 
-use crate::utilities::iterators::utility::{PeekUnqualified};
+use crate::utilities::iterators::hit_merge::{hit_bulk_insert, HitMerge, OrderingPredicate};
 use crate::vector_entries::vector_entries::{KeyValGet, KeyValSet};
-use crate::rings::ring::{Semiring};
-use std::fmt::{Debug};
+use crate::rings::ring::{Semiring, Ring};
+use crate::utilities::order::OrderComparator;
+use crate::rings::homomorphism::RingHomomorphism;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::iter::Peekable;
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+use std::marker::PhantomData;
 
 
 // //  ---------------------------------------------------------------------------
@@ -155,438 +164,1386 @@ impl    < Sprs, Ring >
 }
 
 
+//  ---------------------------------------------------------------------------
+//  NEGATE
+
+
+/// Iterates over the same items as `self.unnegated`, with all coefficients negated.
+#[derive(Debug, Clone)]
+pub struct Negate
+    
+    < Sprs, RingOperator > 
+    
+    where   Sprs:           Iterator,
+            Sprs::Item:     KeyValGet + KeyValSet,
+            RingOperator:   Ring< <Sprs::Item as KeyValGet>::Val >,
+{
+    unnegated:  Sprs,
+    ring:       RingOperator,
+}
+
+impl    < Sprs, RingOperator > 
+        
+        Iterator for Negate
+        
+        < Sprs, RingOperator > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet + KeyValSet,
+                RingOperator:   Ring< <Sprs::Item as KeyValGet>::Val >,
+{
+    type Item = Sprs::Item;
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        let mut x   =   self.unnegated.next()?;
+        x.set_val( self.ring.negate( x.val() ) );
+        Some( x )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.unnegated.size_hint() }
+}
+
+
 //  ---------------------------------------------------------------------------
 //  GATHER COEFFICIENTS 
 
 
-/// Iterates over the same items as `self.ungathered`, except that 
-/// consecutive entries with equal indices are merged into a single entry whose
-/// coefficient is the sum of the coefficients.
-#[derive(Debug, Clone)]
-pub struct Gather
+/// Iterates over the same items as `self.ungathered`, except that 
+/// consecutive entries with equal indices are merged into a single entry whose
+/// coefficient is the sum of the coefficients.
+/// 
+/// The struct holds its own one-entry lookahead, so `self.ungathered` can be any iterator.
+pub struct Gather
+    
+    < Sprs, Ring > 
+
+    where   Sprs:           Iterator,
+            Sprs::Item:     KeyValGet + KeyValSet,
+            Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+            // <Sprs::Item as KeyValGet>::Key: Debug + Clone,
+            // <Sprs::Item as KeyValGet>::Val: Debug + Clone,    
+
+{
+    ungathered: Peekable< Sprs >,
+    ring: Ring,
+}
+
+
+
+impl    < Sprs, Ring > 
+
+        Iterator for Gather
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+                <Sprs::Item as KeyValGet>::Key: PartialEq,
+                // <Sprs::Item as KeyValGet>::Key: Debug + Clone + PartialEq,
+                // <Sprs::Item as KeyValGet>::Val: Debug + Clone,  
+{
+    type Item = Sprs::Item;
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        if let Some( mut x ) = self.ungathered.next() {
+            while let Some( peek ) = self.ungathered.peek() {
+                if peek.key() == x.key() { 
+                    x.set_val(
+                        self.ring.add( 
+                            x.val(), 
+                            peek.val() 
+                        )
+                    );
+                    let _ = self.ungathered.next(); // we have already gotten what we need
+                }
+                else { break }
+            }
+            return Some( x )
+        }
+        else 
+        { None }
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { 
+        let ( lo, hi )  =   self.ungathered.size_hint();
+        ( lo.min( 1 ), hi )
+    }
+}
+
+impl    < Sprs, Ring > 
+
+        Clone for Gather
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:               Iterator,
+                Sprs::Item:         KeyValGet + KeyValSet,
+                Ring:               Semiring< <Sprs::Item as KeyValGet>::Val > + Clone,
+                Peekable< Sprs >:   Clone,
+{
+    fn clone( &self ) -> Self { Gather{ ungathered: self.ungathered.clone(), ring: self.ring.clone() } }
+}
+
+impl    < Sprs, Ring > 
+
+        Debug for Gather
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:               Iterator,
+                Sprs::Item:         KeyValGet + KeyValSet,
+                Ring:               Semiring< <Sprs::Item as KeyValGet>::Val > + Debug,
+                Peekable< Sprs >:   Debug,
+{
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        f.debug_struct( "Gather" ).field( "ungathered", &self.ungathered ).field( "ring", &self.ring ).finish()
+    }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  GATHER COEFFICIENTS, DROP ZEROS, AND COUNT
+
+
+/// Iterates over the same items as `self.ungathered`, except that consecutive entries with
+/// equal indices are merged into a single entry whose coefficient is the sum of the
+/// coefficients, and merged entries with coefficient zero are dropped.
+/// 
+/// The struct keeps a tally of the number of entries it has dropped, which can be read 
+/// with [`GatherDropCount::num_dropped`] (e.g. after the iterator has been consumed 
+/// with `by_ref()`).
+pub struct GatherDropCount
+    
+    < Sprs, Ring > 
+
+    where   Sprs:           Iterator,
+            Sprs::Item:     KeyValGet + KeyValSet,
+            Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+
+{
+    ungathered: Peekable< Sprs >,
+    ring: Ring,
+    num_dropped: usize,
+}
+
+impl    < Sprs, Ring > 
+
+        GatherDropCount
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+{
+    /// The number of (gathered) entries dropped so far because their coefficient was zero.
+    pub fn num_dropped( &self ) -> usize { self.num_dropped }
+}
+
+impl    < Sprs, Ring > 
+
+        Iterator for GatherDropCount
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+                <Sprs::Item as KeyValGet>::Key: PartialEq,
+{
+    type Item = Sprs::Item;
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        while let Some( mut x ) = self.ungathered.next() {
+            while let Some( peek ) = self.ungathered.peek() {
+                if peek.key() == x.key() { 
+                    x.set_val(
+                        self.ring.add( 
+                            x.val(), 
+                            peek.val() 
+                        )
+                    );
+                    let _ = self.ungathered.next(); // we have already gotten what we need
+                }
+                else { break }
+            }
+            if self.ring.is_0( x.val() ) { self.num_dropped += 1 }
+            else { return Some( x ) }
+        }
+        None
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { ( 0, self.ungathered.size_hint().1 ) }
+}
+
+impl    < Sprs, Ring > 
+
+        Clone for GatherDropCount
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:               Iterator,
+                Sprs::Item:         KeyValGet + KeyValSet,
+                Ring:               Semiring< <Sprs::Item as KeyValGet>::Val > + Clone,
+                Peekable< Sprs >:   Clone,
+{
+    fn clone( &self ) -> Self { 
+        GatherDropCount{ ungathered: self.ungathered.clone(), ring: self.ring.clone(), num_dropped: self.num_dropped } 
+    }
+}
+
+impl    < Sprs, Ring > 
+
+        Debug for GatherDropCount
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:               Iterator,
+                Sprs::Item:         KeyValGet + KeyValSet,
+                Ring:               Semiring< <Sprs::Item as KeyValGet>::Val > + Debug,
+                Peekable< Sprs >:   Debug,
+{
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        f.debug_struct( "GatherDropCount" )
+            .field( "ungathered", &self.ungathered )
+            .field( "ring", &self.ring )
+            .field( "num_dropped", &self.num_dropped )
+            .finish()
+    }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  SIMPLIFY
+
+
+/// Iterates over the same items as a [`GatherDropCount`], without exposing the number of
+/// dropped entries: consecutive entries with equal indices are merged into a single entry whose
+/// coefficient is the sum of the coefficients, and merged entries with coefficient zero are
+/// dropped.
+/// 
+/// This is the same as `.gather( ring ).drop_zeros( ring )`, in a single pass.
+pub struct Simplify
+    
+    < Sprs, Ring > 
+
+    where   Sprs:           Iterator,
+            Sprs::Item:     KeyValGet + KeyValSet,
+            Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+
+{
+    gathered: GatherDropCount< Sprs, Ring >,
+}
+
+impl    < Sprs, Ring > 
+
+        Iterator for Simplify
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+                <Sprs::Item as KeyValGet>::Key: PartialEq,
+{
+    type Item = Sprs::Item;
+
+    fn next( &mut self) -> Option< Self::Item > { self.gathered.next() }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.gathered.size_hint() }
+}
+
+impl    < Sprs, Ring > 
+
+        Clone for Simplify
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:                           Iterator,
+                Sprs::Item:                     KeyValGet + KeyValSet,
+                Ring:                           Semiring< <Sprs::Item as KeyValGet>::Val >,
+                GatherDropCount< Sprs, Ring >:  Clone,
+{
+    fn clone( &self ) -> Self { Simplify{ gathered: self.gathered.clone() } }
+}
+
+impl    < Sprs, Ring > 
+
+        Debug for Simplify
+    
+        < Sprs, Ring > 
+   
+        where   Sprs:                           Iterator,
+                Sprs::Item:                     KeyValGet + KeyValSet,
+                Ring:                           Semiring< <Sprs::Item as KeyValGet>::Val >,
+                GatherDropCount< Sprs, Ring >:  Debug,
+{
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        f.debug_struct( "Simplify" ).field( "gathered", &self.gathered ).finish()
+    }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  SUM OF TWO SORTED VECTORS
+
+
+/// Iterates over the entries of `self.a + scale * self.b`, where `self.a` and `self.b` are
+/// sorted with respect to `self.order`; `scale` is one if `self.scale` is `None`.
+/// 
+/// Entries with equal keys are merged into a single entry whose coefficient is the sum of the
+/// coefficients, and merged entries with coefficient zero are dropped; entries are returned as
+/// `(key, val)` tuples, in sorted order.  Returned by [`Transforms::add`] and
+/// [`Transforms::subtract`].
+pub struct SortedSum
+    
+    < SprsA, SprsB, Ring, Order > 
+
+    where   SprsA:          Iterator,
+            SprsB:          Iterator,
+            SprsA::Item:    KeyValGet,
+            SprsB::Item:    KeyValGet< Key = <SprsA::Item as KeyValGet>::Key, Val = <SprsA::Item as KeyValGet>::Val >,
+
+{
+    a: Peekable< SprsA >,
+    b: Peekable< SprsB >,
+    scale: Option< <SprsA::Item as KeyValGet>::Val >,
+    ring: Ring,
+    order: Order,
+}
+
+impl    < SprsA, SprsB, Ring, Order > 
+
+        SortedSum
+    
+        < SprsA, SprsB, Ring, Order > 
+   
+        where   SprsA:          Iterator,
+                SprsB:          Iterator,
+                SprsA::Item:    KeyValGet,
+                SprsB::Item:    KeyValGet< Key = <SprsA::Item as KeyValGet>::Key, Val = <SprsA::Item as KeyValGet>::Val >,
+                Ring:           Semiring< <SprsA::Item as KeyValGet>::Val >,
+                Order:          OrderComparator< <SprsA::Item as KeyValGet>::Key >,
+                <SprsA::Item as KeyValGet>::Val: Clone,
+{
+    /// The next entry of `b`, scaled.
+    fn next_b( &mut self ) -> Option< <Self as Iterator>::Item > {
+        let x       =   self.b.next()?;
+        let val     =   match &self.scale { Some( scale ) => self.ring.multiply( x.val(), scale.clone() ), None => x.val() };
+        Some( ( x.key(), val ) )
+    }
+}
+
+impl    < SprsA, SprsB, Ring, Order > 
+
+        Iterator for SortedSum
+    
+        < SprsA, SprsB, Ring, Order > 
+   
+        where   SprsA:          Iterator,
+                SprsB:          Iterator,
+                SprsA::Item:    KeyValGet,
+                SprsB::Item:    KeyValGet< Key = <SprsA::Item as KeyValGet>::Key, Val = <SprsA::Item as KeyValGet>::Val >,
+                Ring:           Semiring< <SprsA::Item as KeyValGet>::Val >,
+                Order:          OrderComparator< <SprsA::Item as KeyValGet>::Key >,
+                <SprsA::Item as KeyValGet>::Val: Clone,
+{
+    type Item = ( <SprsA::Item as KeyValGet>::Key, <SprsA::Item as KeyValGet>::Val );
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        loop {
+            // take the entry with the smaller key (from `a`, in case of a tie)
+            let take_a  =   match ( self.a.peek(), self.b.peek() ) {
+                                ( None, None )          =>  return None,
+                                ( Some( _ ), None )     =>  true,
+                                ( None, Some( _ ) )     =>  false,
+                                ( Some( x ), Some( y ) )=>  self.order.le( &x.key(), &y.key() ),
+                            };
+            let ( key, mut val )    =   if take_a { let x = self.a.next().unwrap(); ( x.key(), x.val() ) } 
+                                        else { self.next_b().unwrap() };
+
+            // gather the entries of both vectors with the same key
+            loop {
+                let a_equal     =   match self.a.peek() { Some( x ) => self.order.compare( &x.key(), &key ) == Ordering::Equal, None => false };
+                let b_equal     =   match self.b.peek() { Some( x ) => self.order.compare( &x.key(), &key ) == Ordering::Equal, None => false };
+                if a_equal {
+                    val     =   self.ring.add( val, self.a.next().unwrap().val() );
+                } else if b_equal {
+                    let x   =   self.next_b().unwrap();
+                    val     =   self.ring.add( val, x.1 );
+                } else { break }
+            }
+            if ! self.ring.is_0( val.clone() ) { return Some( ( key, val ) ) }
+        }
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { 
+        let upper   =   self.a.size_hint().1.zip( self.b.size_hint().1 ).and_then( |( a, b )| a.checked_add( b ) );
+        ( 0, upper ) 
+    }
+}
+
+impl    < SprsA, SprsB, Ring, Order > 
+
+        Clone for SortedSum
+    
+        < SprsA, SprsB, Ring, Order > 
+   
+        where   SprsA:              Iterator,
+                SprsB:              Iterator,
+                SprsA::Item:        KeyValGet,
+                SprsB::Item:        KeyValGet< Key = <SprsA::Item as KeyValGet>::Key, Val = <SprsA::Item as KeyValGet>::Val >,
+                Peekable< SprsA >:  Clone,
+                Peekable< SprsB >:  Clone,
+                Ring:               Clone,
+                Order:              Clone,
+                <SprsA::Item as KeyValGet>::Val: Clone,
+{
+    fn clone( &self ) -> Self { 
+        SortedSum{ a: self.a.clone(), b: self.b.clone(), scale: self.scale.clone(), ring: self.ring.clone(), order: self.order.clone() } 
+    }
+}
+
+impl    < SprsA, SprsB, Ring, Order > 
+
+        Debug for SortedSum
+    
+        < SprsA, SprsB, Ring, Order > 
+   
+        where   SprsA:              Iterator,
+                SprsB:              Iterator,
+                SprsA::Item:        KeyValGet,
+                SprsB::Item:        KeyValGet< Key = <SprsA::Item as KeyValGet>::Key, Val = <SprsA::Item as KeyValGet>::Val >,
+                Peekable< SprsA >:  Debug,
+                Peekable< SprsB >:  Debug,
+                Ring:               Debug,
+                Order:              Debug,
+                <SprsA::Item as KeyValGet>::Val: Debug,
+{
+    fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result {
+        f.debug_struct( "SortedSum" )
+            .field( "a", &self.a )
+            .field( "b", &self.b )
+            .field( "scale", &self.scale )
+            .field( "ring", &self.ring )
+            .field( "order", &self.order )
+            .finish()
+    }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  CHANGE COEFFICIENTS
+
+
+/// Iterates over the same indices as `self.unchanged`, with each coefficient `x` replaced by
+/// `self.homomorphism.apply( x )`.
+///
+/// Entries whose image is zero are kept; use [`Transforms::drop_zeros`] to remove them.
+#[derive(Debug, Clone)]
+pub struct ChangeCoefficients
+    
+    < Sprs, Hom, Target > 
+
+{
+    unchanged:      Sprs,
+    homomorphism:   Hom,
+    phantom:        PhantomData< Target >,
+}
+
+impl    < Sprs, Hom, Target > 
+
+        Iterator for ChangeCoefficients
+    
+        < Sprs, Hom, Target > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet,
+                Hom:            RingHomomorphism< <Sprs::Item as KeyValGet>::Val, Target >,
+{
+    type Item = ( <Sprs::Item as KeyValGet>::Key, Target );
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        self.unchanged.next().map( |x| ( x.key(), self.homomorphism.apply( x.val() ) ) )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.unchanged.size_hint() }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  MAP KEYS
+
+
+/// Iterates over the same coefficients as `self.unmapped`, with each key `k` replaced by
+/// `(self.f)( k )`.
+///
+/// The order of the entries is unchanged, so the output is sorted only if `f` preserves order.
+#[derive(Debug, Clone)]
+pub struct MapKeys
+    
+    < Sprs, F > 
+
+{
+    unmapped:       Sprs,
+    f:              F,
+}
+
+impl    < Sprs, F, NewKey > 
+
+        Iterator for MapKeys
+    
+        < Sprs, F > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet,
+                F:              FnMut( <Sprs::Item as KeyValGet>::Key ) -> NewKey,
+{
+    type Item = ( NewKey, <Sprs::Item as KeyValGet>::Val );
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        let x   =   self.unmapped.next()?;
+        Some( ( (self.f)( x.key() ), x.val() ) )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.unmapped.size_hint() }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  FILTER KEYS
+
+
+/// A condition on keys, used by [`FilterKeys`]; implemented for ranges of keys and for
+/// predicates `FnMut( &Key ) -> bool`.
+pub trait KeyFilter< Key > {
+    /// Returns `true` if entries with key `key` should be kept.
+    fn keep( &mut self, key: &Key ) -> bool;
+
+    /// Returns `true` if no key greater than or equal to `key` should be kept; this lets
+    /// [`FilterKeys`] stop early on sorted input.  The default implementation returns `false`.
+    fn beyond( &self, _key: &Key ) -> bool { false }
+}
+
+impl < Key, F: FnMut( &Key ) -> bool > KeyFilter< Key > for F {
+    fn keep( &mut self, key: &Key ) -> bool { self( key ) }
+}
+
+impl < Key: PartialOrd > KeyFilter< Key > for Range< Key > {
+    fn keep( &mut self, key: &Key ) -> bool { self.contains( key ) }
+    fn beyond( &self, key: &Key ) -> bool { *key >= self.end }
+}
+
+impl < Key: PartialOrd > KeyFilter< Key > for RangeInclusive< Key > {
+    fn keep( &mut self, key: &Key ) -> bool { self.contains( key ) }
+    fn beyond( &self, key: &Key ) -> bool { key > self.end() }
+}
+
+impl < Key: PartialOrd > KeyFilter< Key > for RangeFrom< Key > {
+    fn keep( &mut self, key: &Key ) -> bool { self.contains( key ) }
+}
+
+impl < Key: PartialOrd > KeyFilter< Key > for RangeTo< Key > {
+    fn keep( &mut self, key: &Key ) -> bool { self.contains( key ) }
+    fn beyond( &self, key: &Key ) -> bool { *key >= self.end }
+}
+
+impl < Key: PartialOrd > KeyFilter< Key > for RangeToInclusive< Key > {
+    fn keep( &mut self, key: &Key ) -> bool { self.contains( key ) }
+    fn beyond( &self, key: &Key ) -> bool { *key > self.end }
+}
+
+/// Iterates over the items of `self.unfiltered` whose keys satisfy `self.filter`.
+/// 
+/// If `self.sorted` is `true`, then the keys of `self.unfiltered` are assumed to be sorted in
+/// ascending order, and iteration stops at the first key that is
+/// [`beyond`](KeyFilter::beyond) the filter.
+#[derive(Debug, Clone)]
+pub struct FilterKeys
+    
+    < Sprs, Filter > 
+
+{
+    unfiltered:     Sprs,
+    filter:         Filter,
+    sorted:         bool,
+    done:           bool,
+}
+
+impl    < Sprs, Filter > 
+
+        Iterator for FilterKeys
+    
+        < Sprs, Filter > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet,
+                Filter:         KeyFilter< <Sprs::Item as KeyValGet>::Key >,
+{
+    type Item = Sprs::Item;
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        if self.done { return None }
+        for x in self.unfiltered.by_ref() {
+            let key     =   x.key();
+            if self.filter.keep( &key ) { return Some( x ) }
+            if self.sorted && self.filter.beyond( &key ) { break }
+        }
+        self.done   =   true;
+        None
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { 
+        if self.done { ( 0, Some( 0 ) ) } else { ( 0, self.unfiltered.size_hint().1 ) }
+    }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  ASSERT SORTED
+
+
+/// Iterates over the same items as `self.unchecked`, panicking if a key is strictly less than
+/// the key before it with respect to `self.order`.
+/// 
+/// Repeated keys are allowed.  If `self.enabled` is `false`, then no keys are checked; see
+/// [`Transforms::debug_assert_sorted`].
+pub struct AssertSorted
+    
+    < Sprs, Order > 
+
+    where   Sprs:           Iterator,
+            Sprs::Item:     KeyValGet,
+
+{
+    unchecked:      Sprs,
+    order:          Order,
+    enabled:        bool,
+    last_key:       Option< <Sprs::Item as KeyValGet>::Key >,
+}
+
+impl    < Sprs, Order > 
+
+        Iterator for AssertSorted
+    
+        < Sprs, Order > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet,
+                Order:          OrderComparator< <Sprs::Item as KeyValGet>::Key >,
+                <Sprs::Item as KeyValGet>::Key: Debug,
+{
+    type Item = Sprs::Item;
+
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        let x   =   self.unchecked.next()?;
+        if self.enabled {
+            let key     =   x.key();
+            if let Some( last_key ) = &self.last_key {
+                if self.order.lt( &key, last_key ) {
+                    panic!( "the entries of a sparse vector are not sorted: key {:?} follows key {:?}", key, last_key )
+                }
+            }
+            self.last_key   =   Some( key );
+        }
+        Some( x )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.unchecked.size_hint() }
+}
+
+
+
+//  ---------------------------------------------------------------------------
+//  SPARSE VECTOR TRAIT
+//  ---------------------------------------------------------------------------
+
+
+/// Transformations on sparse vector iterators.
+///
+/// The methods in this trait are implemented automatically for structs that
+/// implement `Iterator< Item : KeyValGet >`.  They are not complicated
+/// in general; rather, they're provided as a convenience for 
+/// chained transformations.
+pub trait Transforms
+
+    where   Self:           Iterator,
+            Self::Item:     KeyValGet,
+
+{
+
+    /// Returns an interator that iterates over the same items as `self`, 
+    /// skipping any items with coefficient 0.
+    fn  drop_zeros< Ring >
+        ( self, ring: Ring ) 
+        -> 
+        DropZeros< Self, Ring >
+        
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+                // <Self::Item as KeyValGet>::Key: Debug + Clone,
+                // <Self::Item as KeyValGet>::Val: Debug + Clone,
+
+    {
+        DropZeros{ undropped: self, ring: ring }
+    }
+
+    /// Returns an interator that iterates over the same items as `self`, 
+    /// with all coefficients scaled by `scalar`.
+    fn  scale 
+        < Ring > 
+        ( self, ring: Ring, scalar: <Self::Item as KeyValGet>::Val )
+        -> 
+        Scale< Self, Ring >
+        
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+                // <Self::Item as KeyValGet>::Key: Debug + Clone,
+                <Self::Item as KeyValGet>::Val: Debug + Clone,
+        {
+            Scale{ unscaled: self, ring: ring, scale: scalar }
+        }
+
+    /// Returns an interator that iterates over the same items as `self`, 
+    /// with all coefficients negated.
+    /// 
+    /// This is equivalent to scaling by minus one, but does not form the scalar or multiply.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::ring_native::NativeRing;
+    /// 
+    /// let entries     =   vec![ (0, 1), (2, -3) ];
+    /// let negated : Vec<_>    =   entries.into_iter().negate( NativeRing::<i64>::new() ).collect();
+    /// assert_eq!( negated, vec![ (0, -1), (2, 3) ] );
+    /// ```
+    fn  negate 
+        < RingOperator > 
+        ( self, ring: RingOperator )
+        -> 
+        Negate< Self, RingOperator >
+        
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet + KeyValSet,
+                RingOperator:   Ring< <Self::Item as KeyValGet>::Val >,
+        {
+            Negate{ unnegated: self, ring } 
+        }
+
+    /// Returns an interator that iterates over the same items as `self`, except that 
+    /// consecutive entries with equal indices are merged into a single entry whose
+    /// coefficient is the sum of the coefficients.  
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::ring_native::NativeRing;
+    /// 
+    /// let entries     =   vec![ (0, 1), (1, 1), (1, -1), (3, 2), (3, 2) ];
+    /// let gathered : Vec<_>   =   entries.into_iter().gather( NativeRing::<i64>::new() ).collect();
+    /// 
+    /// assert_eq!( gathered, vec![ (0, 1), (1, 0), (3, 4) ] );
+    /// ```
+    fn gather < Ring > ( self, ring: Ring )
+        -> Gather< Self, Ring >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+                <Self::Item as KeyValGet>::Key:PartialEq,
+                // <Self::Item as KeyValGet>::Key: Debug + Clone,
+                // <Self::Item as KeyValGet>::Val: Debug + Clone,               
+        {
+            Gather{ ungathered: self.peekable(), ring: ring  } 
+        }
+
+    /// Returns an interator that iterates over the same items as `self`, except that 
+    /// consecutive entries with equal indices are merged into a single entry whose
+    /// coefficient is the sum of the coefficients, and merged entries with coefficient
+    /// zero are dropped.  The iterator counts the number of entries it drops.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::ring_native::NativeRing;
+    /// 
+    /// let entries     =   vec![ (0, 1), (1, 1), (1, -1), (2, 0), (3, 2), (3, 2) ];
+    /// let mut iter    =   entries.into_iter().gather_drop_count( NativeRing::<i64>::new() );
+    /// let simplified  : Vec<_>    =   iter.by_ref().collect();
+    /// 
+    /// assert_eq!( simplified, vec![ (0, 1), (3, 4) ] );
+    /// assert_eq!( iter.num_dropped(), 2 );
+    /// ```
+    fn gather_drop_count < Ring > ( self, ring: Ring )
+        -> GatherDropCount< Self, Ring >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+                <Self::Item as KeyValGet>::Key:PartialEq,
+        {
+            GatherDropCount{ ungathered: self.peekable(), ring, num_dropped: 0 } 
+        }
+
+    /// Returns an interator that iterates over the same items as `self`, except that 
+    /// consecutive entries with equal indices are merged into a single entry whose
+    /// coefficient is the sum of the coefficients, and merged entries with coefficient
+    /// zero are dropped.
+    /// 
+    /// This is equivalent to `.gather( ring.clone() ).drop_zeros( ring )`, but makes a single
+    /// pass; it is also the same as [`gather_drop_count`](Transforms::gather_drop_count), without
+    /// the count.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::ring_native::NativeRing;
+    /// 
+    /// let entries     =   vec![ (0, 1), (1, 1), (1, -1), (2, 0), (3, 2), (3, 2) ];
+    /// let simplified  : Vec<_>    =   entries.into_iter().simplify( NativeRing::<i64>::new() ).collect();
+    /// 
+    /// assert_eq!( simplified, vec![ (0, 1), (3, 4) ] );
+    /// ```
+    fn simplify < Ring > ( self, ring: Ring )
+        -> Simplify< Self, Ring >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet + KeyValSet,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+                <Self::Item as KeyValGet>::Key:PartialEq,
+        {
+            Simplify{ gathered: self.gather_drop_count( ring ) } 
+        }
+
+    /// Returns an iterator over the entries of the sum of `self` and `other`, both of which
+    /// should be sorted in ascending order with respect to `order`.
+    /// 
+    /// Entries with equal keys are merged, and entries with coefficient zero are dropped;
+    /// entries are returned as `(key, val)` tuples, in sorted order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::ring_native::NativeRing;
+    /// use solar::utilities::order::{OrderNatural, OrderReverse};
+    /// 
+    /// let ring        =   NativeRing::<i64>::new();
+    /// let a           =   vec![ (0, 1), (2, 1), (3, 1) ];
+    /// let b           =   vec![ (1, 1), (2, -1), (3, 1) ];
+    /// let sum : Vec<_>    =   a.iter().cloned().add( b.iter().cloned(), ring.clone(), OrderNatural ).collect();
+    /// assert_eq!( sum, vec![ (0, 1), (1, 1), (3, 2) ] );
+    /// 
+    /// // vectors sorted in descending order
+    /// let sum : Vec<_>    =   a.into_iter().rev().add( b.into_iter().rev(), ring, OrderReverse::new( OrderNatural ) ).collect();
+    /// assert_eq!( sum, vec![ (3, 2), (1, 1), (0, 1) ] );
+    /// ```
+    fn add < Other, Ring, Order > ( self, other: Other, ring: Ring, order: Order )
+        -> SortedSum< Self, Other::IntoIter, Ring, Order >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Other:          IntoIterator,
+                Other::Item:    KeyValGet< Key = <Self::Item as KeyValGet>::Key, Val = <Self::Item as KeyValGet>::Val >,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+                Order:          OrderComparator< <Self::Item as KeyValGet>::Key >,
+        {
+            SortedSum{ a: self.peekable(), b: other.into_iter().peekable(), scale: None, ring, order } 
+        }
+
+    /// Returns an iterator over the entries of `self` minus `other`, both of which should be
+    /// sorted in ascending order with respect to `order`.
+    /// 
+    /// This is the same as [`add`](Transforms::add), except that the coefficients of `other`
+    /// are negated.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::ring_native::NativeRing;
+    /// use solar::utilities::order::OrderNatural;
+    /// 
+    /// let a           =   vec![ (0, 1), (2, 1), (3, 1) ];
+    /// let b           =   vec![ (1, 1), (2, 1), (3, 3) ];
+    /// let difference : Vec<_>     =   a.into_iter().subtract( b, NativeRing::<i64>::new(), OrderNatural ).collect();
+    /// assert_eq!( difference, vec![ (0, 1), (1, -1), (3, -2) ] );
+    /// ```
+    fn subtract < Other, RingOperator, Order > ( self, other: Other, ring: RingOperator, order: Order )
+        -> SortedSum< Self, Other::IntoIter, RingOperator, Order >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Other:          IntoIterator,
+                Other::Item:    KeyValGet< Key = <Self::Item as KeyValGet>::Key, Val = <Self::Item as KeyValGet>::Val >,
+                RingOperator:   Ring< <Self::Item as KeyValGet>::Val >,
+                Order:          OrderComparator< <Self::Item as KeyValGet>::Key >,
+        {
+            let minus_one   =   ring.negate( RingOperator::one() );
+            SortedSum{ a: self.peekable(), b: other.into_iter().peekable(), scale: Some( minus_one ), ring, order } 
+        }
+
+    /// Returns an iterator that iterates over the same indices as `self`, with each coefficient
+    /// `x` replaced by `homomorphism.apply( x )`; entries are returned as `(key, val)` tuples.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::homomorphism::ReduceModTwo;
+    /// use solar::rings::field_prime::GF2;
+    /// 
+    /// let entries     =   vec![ (0, 3i64), (1, -2), (4, -1) ];
+    /// let reduced : Vec<_>    =   entries.into_iter().change_coefficients( ReduceModTwo::new() ).drop_zeros( GF2::new() ).collect();
+    /// 
+    /// assert_eq!( reduced, vec![ (0, true), (4, true) ] );
+    /// ```
+    fn change_coefficients < Hom, Target > ( self, homomorphism: Hom )
+        -> ChangeCoefficients< Self, Hom, Target >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Hom:            RingHomomorphism< <Self::Item as KeyValGet>::Val, Target >,
+        {
+            ChangeCoefficients{ unchanged: self, homomorphism, phantom: PhantomData } 
+        }
+
+    /// Returns an iterator that iterates over the same coefficients as `self`, with each key `k`
+    /// replaced by `f( k )`; entries are returned as `(key, val)` tuples.
+    /// 
+    /// To reindex with a [`BiMapSequential`](crate::utilities::sequences_and_ordinals::BiMapSequential),
+    /// pass its [`ord_lookup`](crate::utilities::sequences_and_ordinals::BiMapSequential::ord_lookup)
+    /// or [`val_lookup`](crate::utilities::sequences_and_ordinals::BiMapSequential::val_lookup).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::utilities::sequences_and_ordinals::BiMapSequential;
+    /// 
+    /// let entries     =   vec![ (0, 1), (2, -1) ];
+    /// let shifted : Vec<_>    =   entries.iter().cloned().map_keys( |k| k + 10 ).collect();
+    /// assert_eq!( shifted, vec![ (10, 1), (12, -1) ] );
+    /// 
+    /// // from ordinals to simplices, and back
+    /// let bimap       =   BiMapSequential::from_vec( vec![ vec![0], vec![1], vec![0, 1] ] );
+    /// let simplices : Vec<_>  =   entries.into_iter().map_keys( bimap.val_lookup() ).collect();
+    /// assert_eq!( simplices, vec![ (vec![0], 1), (vec![0, 1], -1) ] );
+    /// let ordinals : Vec<_>   =   simplices.into_iter().map_keys( bimap.ord_lookup() ).collect();
+    /// assert_eq!( ordinals, vec![ (0, 1), (2, -1) ] );
+    /// ```
+    fn map_keys < F, NewKey > ( self, f: F )
+        -> MapKeys< Self, F >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                F:              FnMut( <Self::Item as KeyValGet>::Key ) -> NewKey,
+        {
+            MapKeys{ unmapped: self, f } 
+        }
+
+    /// Returns an iterator over the items of `self` whose keys satisfy `filter`, which can be a
+    /// range of keys or a predicate on keys (see [`KeyFilter`]).
+    /// 
+    /// Closures passed as predicates need an explicit argument type, e.g. `|k: &usize| k % 2 == 0`.
+    /// See [`filter_keys_sorted`](Transforms::filter_keys_sorted) for a variant that stops early
+    /// on sorted input.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// 
+    /// let entries     =   vec![ (4, 1), (1, 1), (3, 1), (2, 1) ];
+    /// let filtered : Vec<_>   =   entries.iter().cloned().filter_keys( 2 ..= 3 ).collect();
+    /// assert_eq!( filtered, vec![ (3, 1), (2, 1) ] );
+    /// 
+    /// let even : Vec<_>       =   entries.into_iter().filter_keys( |k: &usize| k % 2 == 0 ).collect();
+    /// assert_eq!( even, vec![ (4, 1), (2, 1) ] );
+    /// ```
+    fn filter_keys < Filter > ( self, filter: Filter )
+        -> FilterKeys< Self, Filter >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Filter:         KeyFilter< <Self::Item as KeyValGet>::Key >,
+        {
+            FilterKeys{ unfiltered: self, filter, sorted: false, done: false } 
+        }
+
+    /// Returns an iterator over the items of `self` whose keys satisfy `filter`, where the keys
+    /// of `self` are sorted in ascending order.
+    /// 
+    /// This is the same as [`filter_keys`](Transforms::filter_keys), except that iteration stops
+    /// (without consuming the rest of `self`) as soon as a key passes the upper bound of a range.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// 
+    /// let mut entries =   ( 0 .. ).map( |k| (k, 1) );    // an unbounded vector
+    /// let filtered : Vec<_>   =   entries.by_ref().filter_keys_sorted( 2 .. 4 ).collect();
+    /// assert_eq!( filtered, vec![ (2, 1), (3, 1) ] );
+    /// assert_eq!( entries.next(), Some( (5, 1) ) );  // the entry for key 4 was consumed
+    /// ```
+    fn filter_keys_sorted < Filter > ( self, filter: Filter )
+        -> FilterKeys< Self, Filter >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Filter:         KeyFilter< <Self::Item as KeyValGet>::Key >,
+        {
+            FilterKeys{ unfiltered: self, filter, sorted: true, done: false } 
+        }
+
+    /// Returns an iterator that iterates over the same items as `self`, and panics the first time
+    /// a key is strictly less than the key before it with respect to `order`.
+    /// 
+    /// Most operations on sparse vectors assume that entries are sorted, and return wrong
+    /// answers (without any error) if they are not; this adaptor turns such a silent violation
+    /// into a panic that names the offending keys.
+    /// 
+    /// # Examples
+    /// 
+    /// ```should_panic
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::utilities::order::OrderNatural;
+    /// 
+    /// let entries     =   vec![ (0, 1), (2, 1), (2, 1), (1, 1) ];
+    /// 
+    /// // panics: key 1 follows key 2
+    /// let checked : Vec<_>    =   entries.into_iter().assert_sorted( OrderNatural ).collect();
+    /// ```
+    fn assert_sorted < Order > ( self, order: Order )
+        -> AssertSorted< Self, Order >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Order:          OrderComparator< <Self::Item as KeyValGet>::Key >,
+        {
+            AssertSorted{ unchecked: self, order, enabled: true, last_key: None } 
+        }
+
+    /// The same as [`assert_sorted`](Transforms::assert_sorted) in debug builds; in release
+    /// builds, the keys are not checked.
+    /// 
+    /// The reduction routines in [`matrix_factorization`](crate::matrix_factorization) check
+    /// their inputs in this way.
+    fn debug_assert_sorted < Order > ( self, order: Order )
+        -> AssertSorted< Self, Order >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Order:          OrderComparator< <Self::Item as KeyValGet>::Key >,
+        {
+            AssertSorted{ unchecked: self, order, enabled: cfg!( debug_assertions ), last_key: None } 
+        }
+
+    /// Returns the sum of the coefficients of `self`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::field_prime::GFp;
+    /// 
+    /// let entries     =   vec![ (0, 1), (1, 2), (4, 2) ];
+    /// assert_eq!( entries.into_iter().sum_of_coefficients( GFp::new( 3 ) ), 2 );
+    /// ```
+    fn sum_of_coefficients < Ring > ( self, ring: Ring )
+        -> <Self::Item as KeyValGet>::Val
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+        {
+            ring.sum_iter( self.map( |x| x.val() ) )
+        }
+
+    /// Returns the number of entries of `self` with nonzero coefficient.
+    /// 
+    /// Entries are counted as they are stored; to count the size of the support of a vector with
+    /// repeated keys, [`simplify`](Transforms::simplify) it first.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// use solar::rings::ring_native::NativeRing;
+    /// 
+    /// let ring        =   NativeRing::<i64>::new();
+    /// let entries     =   vec![ (0, 1), (1, 0), (3, 2), (3, -2) ];
+    /// assert_eq!( entries.iter().cloned().count_nonzero( ring.clone() ), 3 );
+    /// assert_eq!( entries.into_iter().simplify( ring.clone() ).count_nonzero( ring ), 1 );
+    /// ```
+    fn count_nonzero < Ring > ( self, ring: Ring )
+        -> usize
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
+        {
+            self.filter( |x| ! ring.is_0( x.val() ) ).count()
+        }
+
+    /// Returns the largest absolute value of a coefficient of `self` (the L-infinity norm), or 
+    /// `None` if `self` is empty.
+    /// 
+    /// The absolute value is computed by `abs`, so that any ordered coefficient type can be used:
+    /// pass e.g. `f64::abs` or `i64::abs` for signed types, and the identity for unsigned types
+    /// (whose largest absolute value is simply the largest value).
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use solar::vectors::vector_transforms::Transforms;
+    /// 
+    /// let entries     =   vec![ (0, 1.5), (2, -4.), (3, 2.) ];
+    /// assert_eq!( entries.into_iter().max_abs( f64::abs ), Some( 4. ) );
+    /// assert_eq!( Vec::< (usize, i64) >::new().into_iter().max_abs( i64::abs ), None );
+    /// 
+    /// // unsigned coefficients
+    /// let entries     =   vec![ (0, 3u64), (1, 7u64) ];
+    /// assert_eq!( entries.into_iter().max_abs( |x| x ), Some( 7 ) );
+    /// ```
+    fn max_abs < Abs > ( self, mut abs: Abs )
+        -> Option< <Self::Item as KeyValGet>::Val >
+
+        where   Self:           Iterator + Sized,
+                Self::Item:     KeyValGet,
+                Abs:            FnMut( <Self::Item as KeyValGet>::Val ) -> <Self::Item as KeyValGet>::Val,
+                <Self::Item as KeyValGet>::Val: PartialOrd,
+        {
+            self.map( |x| abs( x.val() ) ).fold( None, |max, x| match max {
+                Some( max ) if max >= x     =>  Some( max ),
+                _                           =>  Some( x ),
+            } )
+        }
+}
+
+// We implement this trait automatically on all iterators.
+impl    < Sprs > 
+
+        Transforms 
+        
+        for Sprs  
+
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet,
+                // <Sprs::Item as KeyValGet>::Key: Debug + Clone,
+                // <Sprs::Item as KeyValGet>::Val: Debug + Clone,          
+{} // everything implemented automatically
+
+
+//  ---------------------------------------------------------------------------
+//  LINEAR COMBINATIONS
+//  ---------------------------------------------------------------------------
+
+
+/// Iterates over the entries of `scale * self.unscaled` as `(key, val)` tuples, where `scale` is
+/// one if `self.scale` is `None`; a summand of a [`LinearCombinationSimplified`].
+pub struct ScaledSvi
     
     < Sprs, Ring > 
 
-    where   Sprs:           Iterator + PeekUnqualified,
-            Sprs::Item:     KeyValGet + KeyValSet,
-            Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
-            // <Sprs::Item as KeyValGet>::Key: Debug + Clone,
-            // <Sprs::Item as KeyValGet>::Val: Debug + Clone,    
+    where   Sprs:           Iterator,
+            Sprs::Item:     KeyValGet,
 
 {
-    ungathered: Sprs,
+    unscaled: Sprs,
+    scale: Option< <Sprs::Item as KeyValGet>::Val >,
     ring: Ring,
 }
 
-
-
 impl    < Sprs, Ring > 
 
-        Iterator for Gather
+        Iterator for ScaledSvi
     
         < Sprs, Ring > 
    
-        where   Sprs:           Iterator + PeekUnqualified,
-                Sprs::Item:     KeyValGet + KeyValSet,
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet,
                 Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
-                <Sprs::Item as KeyValGet>::Key: PartialEq,
-                // <Sprs::Item as KeyValGet>::Key: Debug + Clone + PartialEq,
-                // <Sprs::Item as KeyValGet>::Val: Debug + Clone,  
+                <Sprs::Item as KeyValGet>::Val: Clone,
 {
-    type Item = Sprs::Item;
+    type Item = ( <Sprs::Item as KeyValGet>::Key, <Sprs::Item as KeyValGet>::Val );
 
     fn next( &mut self) -> Option< Self::Item > 
     {
-        if let Some( mut x ) = self.ungathered.next() {
-            while let Some( peek ) = self.ungathered.peek_unqualified() {
-                if peek.key() == x.key() { 
-                    x.set_val(
-                        self.ring.add( 
-                            x.val(), 
-                            peek.val() 
-                        )
-                    );
-                    let _ = self.ungathered.next(); // we have already gotten what we need
-                }
-                else { break }
-            }
-            return Some( x )
+        let x   =   self.unscaled.next()?;
+        match &self.scale {
+            Some( scale )   =>  Some( ( x.key(), self.ring.multiply( x.val(), scale.clone() ) ) ),
+            None            =>  Some( ( x.key(), x.val() ) ),
         }
-        else 
-        { None }
     }
-}
 
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { self.unscaled.size_hint() }
+}
 
+/// The "less than" relation on `(key, val)` tuples, comparing keys with an order comparator.
+#[derive(Debug, Clone)]
+struct KeyLessThan< Order > { order: Order }
 
-//  ---------------------------------------------------------------------------
-//  SPARSE VECTOR TRAIT
-//  ---------------------------------------------------------------------------
+impl < Key, Val, Order: OrderComparator< Key > > OrderingPredicate< ( Key, Val ) > for KeyLessThan< Order > {
+    fn ordering_predicate( &mut self, a: &( Key, Val ), b: &( Key, Val ) ) -> bool { self.order.lt( &a.0, &b.0 ) }
+}
 
 
-/// Transformations on sparse vector iterators.
-///
-/// The methods in this trait are implemented automatically for structs that
-/// implement `Iterator< Item : KeyValGet >`.  They are not complicated
-/// in general; rather, they're provided as a convenience for 
-/// chained transformations.
-pub trait Transforms
+/// A linear combination of sparse vector iterators, which iterates over the entries of the
+/// combination in sorted order.
+/// 
+/// Summands are added with [`add_svi`](LinearCombinationSimplified::add_svi), 
+/// [`add_scaled`](LinearCombinationSimplified::add_scaled) and 
+/// [`add_many`](LinearCombinationSimplified::add_many); each should be sorted in ascending
+/// order with respect to the order comparator.  The summands are merged through a heap of
+/// iterators (see [`hit_merge`](crate::utilities::iterators::hit_merge)); entries with equal 
+/// keys are merged into a single entry whose coefficient is the sum of the coefficients, and 
+/// merged entries with coefficient zero are dropped.  Entries are returned as `(key, val)` 
+/// tuples.
+/// 
+/// Summands can be added after iteration has begun, as when a column is reduced by adding 
+/// multiples of other columns; the new summands should then contain no entries that precede 
+/// the last entry returned.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use solar::vectors::vector_transforms::LinearCombinationSimplified;
+/// use solar::rings::ring_native::NativeRing;
+/// use solar::utilities::order::OrderNatural;
+/// 
+/// let ring        =   NativeRing::<i64>::new();
+/// let a           =   vec![ (0, 1), (1, 1) ];
+/// let b           =   vec![ (1, 1), (2, 1) ];
+/// let c           =   vec![ (2, 1), (3, 1) ];
+/// 
+/// // a - b + c + c
+/// let mut combination     =   LinearCombinationSimplified::new( ring, OrderNatural );
+/// combination
+///     .add_svi( a.iter().cloned() )
+///     .add_scaled( b.iter().cloned(), -1 )
+///     .add_many( vec![ c.iter().cloned(), c.iter().cloned() ] );
+/// 
+/// // the first entry is (0, 1); eliminate it by adding -a, then add a again
+/// assert_eq!( combination.next(), Some( (0, 1) ) );
+/// combination.add_scaled( a[ 1.. ].iter().cloned(), -1 ).add_svi( a[ 1.. ].iter().cloned() );
+/// 
+/// let rest : Vec<_>   =   combination.collect();
+/// assert_eq!( rest, vec![ (2, 1), (3, 2) ] );
+/// ```
+pub struct LinearCombinationSimplified
+    
+    < Sprs, Ring, Order > 
 
-    where   Self:           Iterator,
-            Self::Item:     KeyValGet,
+    where   Sprs:           Iterator,
+            Sprs::Item:     KeyValGet,
+            Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+            <Sprs::Item as KeyValGet>::Val: Clone,
 
 {
+    merged: HitMerge< ScaledSvi< Sprs, Ring >, KeyLessThan< Order > >,
+    ring: Ring,
+}
 
-    /// Returns an interator that iterates over the same items as `self`, 
-    /// skipping any items with coefficient 0.
-    fn  drop_zeros< Ring >
-        ( self, ring: Ring ) 
-        -> 
-        DropZeros< Self, Ring >
-        
-        where   Self:           Iterator + Sized,
-                Self::Item:     KeyValGet,
-                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
-                // <Self::Item as KeyValGet>::Key: Debug + Clone,
-                // <Self::Item as KeyValGet>::Val: Debug + Clone,
+impl    < Sprs, Ring, Order > 
 
-    {
-        DropZeros{ undropped: self, ring: ring }
+        LinearCombinationSimplified
+    
+        < Sprs, Ring, Order > 
+   
+        where   Sprs:           Iterator,
+                Sprs::Item:     KeyValGet,
+                Ring:           Semiring< <Sprs::Item as KeyValGet>::Val > + Clone,
+                Order:          OrderComparator< <Sprs::Item as KeyValGet>::Key >,
+                <Sprs::Item as KeyValGet>::Val: Clone,
+{
+    /// An empty linear combination, for vectors sorted with respect to `order`.
+    pub fn new( ring: Ring, order: Order ) -> Self {
+        let merged  =   HitMerge{ heap: Vec::new(), less_than: KeyLessThan{ order } };
+        LinearCombinationSimplified{ merged, ring }
     }
 
-    /// Returns an interator that iterates over the same items as `self`, 
-    /// with all coefficients scaled by `scalar`.
-    fn  scale 
-        < Ring > 
-        ( self, ring: Ring, scalar: <Self::Item as KeyValGet>::Val )
-        -> 
-        Scale< Self, Ring >
-        
-        where   Self:           Iterator + Sized,
-                Self::Item:     KeyValGet + KeyValSet,
-                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
-                // <Self::Item as KeyValGet>::Key: Debug + Clone,
-                <Self::Item as KeyValGet>::Val: Debug + Clone,
-        {
-            Scale{ unscaled: self, ring: ring, scale: scalar }
-        }
+    /// Add `svi` to the linear combination.
+    pub fn add_svi( &mut self, svi: Sprs ) -> &mut Self {
+        let summand     =   ScaledSvi{ unscaled: svi, scale: None, ring: self.ring.clone() };
+        hit_bulk_insert( &mut self.merged, std::iter::once( summand ) );
+        self
+    }
 
-    /// Returns an interator that iterates over the same items as `self`, except that 
-    /// consecutive entries with equal indices are merged into a single entry whose
-    /// coefficient is the sum of the coefficients.  
-    fn gather < Ring > ( self, ring: Ring )
-        -> Gather< Self, Ring >
+    /// Add `svi`, with coefficients multiplied by `scalar` (on the right), to the linear
+    /// combination.
+    pub fn add_scaled( &mut self, svi: Sprs, scalar: <Sprs::Item as KeyValGet>::Val ) -> &mut Self {
+        let summand     =   ScaledSvi{ unscaled: svi, scale: Some( scalar ), ring: self.ring.clone() };
+        hit_bulk_insert( &mut self.merged, std::iter::once( summand ) );
+        self
+    }
 
-        where   Self:           Iterator + Sized + PeekUnqualified,
-                Self::Item:     KeyValGet + KeyValSet,
-                Ring:           Semiring< <Self::Item as KeyValGet>::Val >,
-                <Self::Item as KeyValGet>::Key:PartialEq,
-                // <Self::Item as KeyValGet>::Key: Debug + Clone,
-                // <Self::Item as KeyValGet>::Val: Debug + Clone,               
-        {
-            Gather{ ungathered: self, ring: ring  } 
-        }
+    /// Add each of the vectors in `svis` to the linear combination.
+    pub fn add_many< I: IntoIterator< Item = Sprs > >( &mut self, svis: I ) -> &mut Self {
+        let ring        =   self.ring.clone();
+        let summands    =   svis.into_iter().map( |svi| ScaledSvi{ unscaled: svi, scale: None, ring: ring.clone() } );
+        hit_bulk_insert( &mut self.merged, summands );
+        self
+    }
 }
 
-// We implement this trait automatically on all iterators.
-impl    < Sprs > 
-
-        Transforms 
-        
-        for Sprs  
+impl    < Sprs, Ring, Order > 
 
+        Iterator for LinearCombinationSimplified
+    
+        < Sprs, Ring, Order > 
+   
         where   Sprs:           Iterator,
                 Sprs::Item:     KeyValGet,
-                // <Sprs::Item as KeyValGet>::Key: Debug + Clone,
-                // <Sprs::Item as KeyValGet>::Val: Debug + Clone,          
-{} // everything implemented automatically
+                Ring:           Semiring< <Sprs::Item as KeyValGet>::Val >,
+                Order:          OrderComparator< <Sprs::Item as KeyValGet>::Key >,
+                <Sprs::Item as KeyValGet>::Val: Clone,
+{
+    type Item = ( <Sprs::Item as KeyValGet>::Key, <Sprs::Item as KeyValGet>::Val );
 
+    fn next( &mut self) -> Option< Self::Item > 
+    {
+        while let Some( ( key, mut val ) ) = self.merged.next() {
+            loop {
+                let equal   =   match self.merged.peek() { 
+                                    Some( peek )    =>  self.merged.less_than.order.compare( &peek.0, &key ) == Ordering::Equal, 
+                                    None            =>  false,
+                                };
+                if ! equal { break }
+                val     =   self.ring.add( val, self.merged.next().unwrap().1 );
+            }
+            if ! self.ring.is_0( val.clone() ) { return Some( ( key, val ) ) }
+        }
+        None
+    }
+
+    fn size_hint( &self ) -> ( usize, Option< usize > ) { ( 0, self.merged.size_hint().1 ) }
+}
 
-//     DEFINE SPARSE VECTOR ITERATOR AS
-//
-//     struct Svi< Iter, Index, Ring, Coeff > 
-//         where   Iter: Iterator< Item = KeyValItem<Index,Coeff> >,
-//                 Ring: Semiring< Coeff >,
-// 
-//         {    
-// }
-// 
-// 
-//     svi.transform( DropZeros::y,
-//                    Scale::Coeff(3)
-//                    Merge::y,
-//                    ring
-//                 )
-// 
-// 
-//     M0 + M1 + M4 + sum(M[2..4,:]) + sum(2 * M[4..6,:]) + 
-// 
-//     R = gen_ring();
-//     C = gen_comparator();
-// 
-//     let svi0  = M.maj(majkeys[0]).simplify(&R);
-//     let svi1  = M.maj(majkeys[1]).simplify(&R);
-//     let svi2  = (2..4)
-//                     .map(|x| 
-//                          M.maj(majkeys[x].simplify(&R)
-//                     )
-//     let svi3  = (4..6)
-//                     .map(|x| 
-//                          M.maj(majkeys[x].simplify(&R)
-//                         )
-//                     .kmerge_by(&C)
-//                     .simplify(&R)
-//                     .map(|y|
-//                             (y.0, R.multiply( &y.1, &2)
-//                     )
-// 
-//     let svi4  = M.maj(majkeys[6]).simplify(&R);
-// 
-//     let agg = (svi0, svi1, svi4)
-//                 .hit_merge(             &C )
-//                 .hit_bulk_insert( svi2, &C )
-//                 .merge_by( svi3,        &C )
-//                 .simplify()
-// 
-// 
-//     !!!!!!
-//     drain_monomials
-//     drain_monomials_ordered
-// 
-//     let agg = LciSimplified::combine( ( (svi0, t0), (svi1, t1), (svi3, t3) ), R, C )
-//                 .add_combination(( (svi4, t4), (svi5, t5) ))// universal input format
-//                 .add_svi( svi4, None    )
-//                 .add_svi( svi5, Some(2) )
-//                 .add_lci( lci2, None )                            // option for other lci
-//                 .add_lci( lci2, Some(3) )                            // option for other lci
-//                 .add_sum( (svi6, svi7,  svi8 ), None    )            // avoid 1; avoid nested parentheses 
-//                 .add_sum( (svi9, svi10, svi11), Some(2) )  // option to scale
-// 
-// 
-//                 .add_svi( svi4 )                            // avoid 1; avoid nested parentheses 
-//                 .add_svi_scaled( svi5, 2 )                  // option to scale
-//               
-//                 .add_sum( (svi6, svi7, svi8) )            // avoid 1; avoid nested parentheses 
-//                 .add_sum_scaled( (svi9, svi10, svi11), 2 )  // option to scale
-//                 
-//                 .add_lci( lci2 )                            // option for other lci
-//                 .add_lci_scaled( lci3, 4 )
-// 
-//                 .add_svi( svi4 )                            // avoid 1; avoid nested parentheses 
-//                 .add_lci( lci2 )                            // option for other lci
-//                 .add_sum( (svi6, svi7, svi8) )              // avoid 1 
-//                 .add_svi_scaled( svi5, 2 )                  // option to scale
-//                 .add_lci_scaled( lci3, 4 )                  // option to scale
-//                 .add_sum_scaled( (svi9, svi10, svi11), 2 )  // option to scale
-//                 
-// 
-// 
-//                 .add_svi( svi4, R.one() )
-//                 .add_svi( svi7, 2       )
-//                 .add_lci( lci0, R.one() )
-//                 .add_lci( lci8, 3
-//                 .add_sum( (svi6, svi7,  svi8 ), R.one() )
-//                 .add_sum( (svi9, svi10, svi11), 4       )
-// 
-//                 .add( Term::Svi(svi4) )
-//                 .add( Term::Lci(lci5) )
-// 
-// 
-//                 .add( Term::Svi(svi4), STerm::Svi(svi5, 2), STerm::Lci(lci, 4) )
-//                 .add_svi(svi1).add_lci(lci2).add_sum_scaled( (svi2, svi3), 2)
-//                 .add_svi(svi1, None).add_svi(svi
-// 
-// 
-//                 .add_combination( ((svi0, 1), (svi2, 2), (svi3, 3)) ).add_sum( (svi4,) ).add
-//                 .add_lci( lci, 4 )
-//                 .add_sum(
-// 
-//                 X = LciSimplified::sum( (svi0, svi1), R, C ).add_scaled( (svi
-// 
-//                 X = LciSimplified::new().add( (
-//                         Term::Svi( svi0 ),
-//                         Term::Svi( svi1 ).x(2),
-//                         Term::Lci( lci0 ),
-//                         Term::Lci( lci1 ).x(3),
-//                         Term::Sum( (svi2, svi3, svi4) ),
-//                         Term::Sum( (svi5, svi6, svi7) ).x(4),
-//                         Term::Com( ( (svi10, 3), (svi11, 4) ),
-//                         Term::Com( ( (svi12, 3), (svi13, 4) ).x(7),
-//                         Term::Com( iter1 ).x(7),
-//                         Term::Sum( iter0 ).x(4),
-//                         )
-//                     )
-// 
-//                 X.add( Term::Sum(( svi0, svi1, svi2 )), Term::Svi( svi3 ).x(2) );
-// 
-//                 x.change_add( y, C);
-//                 x.change_add_simplify( y, C);
-// 
-// 
-//     enum LTerms< I, Index, Coeff >
-//         where I: IntoIterator< Item = KeyValItem<Index,Coeff> >
-// 
-//     {
-//         Scale(   I       ),
-//         Unscaled( I, Coeff),
-//         Combination( 
-//     }
-// 
-//     lc.add( LTerm::Unscaled( svi    ) )
-//     lc.add( LTerm::Scale(   svi, x ) )
-//    
-//     lc.add_n( LTerms::Unscaled( ( svi0, svi1, svi2 )    ) )
-//     lc.add_n( Lterms::Scale(   ( svi0, svi1, svi2 ), y ) )
-//   
-//     lc.add_n( Lterms::Combination( ( (svi0, t0), (svi1, t1), (svi2, t2) ) )
-// 
-// 
-//     
-//     lc.add(        svi0    )
-//     lc.add_scaled( svi1, 2 )
-// 
-//     lc.add_n(        (svi2, svi3)    )
-//     lc.add_n_scaled( (svi4, svi5), 2 )
-//     
-//     lc.add_n_multiscaled(( (svi6, 2), (svi7, 3) ))
-//     lc.add_lci( lci )
-// //--------------------------------------------------------------
-// 
-//     lc.add_1( svi );
-//     lc.add_n( (svi0, svi1) );
-//     lc.add_1_times( svi, 2 );
-//     lc.add_n_times( ( svi0, svi1 ), 2 )
-//     
-//     lc.add_combination( ( (svi0, 2), (svi1, 3) ) );
-// 
-// 
-//     lc.add( LTerm::times_1( svi0    ) )
-//     lc.add( LTerm::times_x( svi0, 2 ) )
-// 
-//     lc.add( LTerms::times_1( (svi0, svi1)    ) )
-//     lc.add( LTerms::times_x( (svi0, svi1), 3 ) )
-// 
-//     lc.add( LTerms::combine( ( (svi0, 2), (svi1, 3) ) )
-//     lc.add( lci.into_forgetful_terms() )
-// 
-// //--------------------------------------------------------------
-//     lc.add( svi )
-//     lc.add_scaled(svi, 2)
-// 
-// 
-//     lc.add( LTerm::new(svi).x(2) )
-//     lc.add_n( (svi0, svi1).map(|x| (x, 2) ) )
-// 
-// 
-//     lc.add( LTerm::scaled( svi, 2 ) )
-//     lc.add( LTerm::unscaled( x )
-// 
-// 
-//     lc.add( LTerm::scaled(   svi    ) )
-//     lc.add( LTerm::unscaled( svi, x ) )
-//    
-//     lc.add( LTerms::scaled(   ( svi0, svi1, svi2 )    ) )
-//     lc.add( LTerms::unscaled( ( svi0, svi1, svi2 ), y ) )
-//   
-//     lc.add( LTerms::combination( ( (svi0, t0), (svi1, t1), (svi2, t2) ) )
-//     lc.add( LTerms::lci( lci0 ) )
-// 
-//     lc.add( LTerm::scaled(svi) )
-//     lc.add( svi )
-// 
-//     lc.add_mn( ..) // FOR THESE COMPLEX LARGE SCALE OPERATIONS -- JUST EXPLAIN HOW TO DO A BULK
-//     INSERT AND HEAPIFY
-// 
-// 
-//     X.add( LTerm::Scale(   (svi0,), y )
-//     X.add( Lterm::Scale(   (svi0, svi1, svi2), y )
-//     X.add( LTerm::Unscaled( (svi0,) )
-//     X.add( LTerm::Unscaled( (svi0, svi1, svi2) ) )
-//     X.add( Lterm::Combination( (svi0, t0), (svi1, t1), (svi2, t2) )
-// 
-// 
-//     !!!!! NOPE -- JUST .merge(y, C).simplify();
-// 
-//     heter_add( svi0, svi1, C )
-//     heter_add_simplify( svi0, svi1, C, R)
-// 
-//     
-// 
-// 
-//     // Generally speaking, better to build/add in bulk.
-//     let agg = simple_lc::sum( (svi0, svi1, svi2), R, C)
-//                 .add_vector( svi3    );
-//                 .add_scaled( svi3, 2 );
-//                 .add_combination( svi4, Coeff::Scalars((3, 2, 1)) )
-//                 .add_combination( svi4, Coeff::Uniform(3) )
-//                 .add_combination( svi4, Coeff::Unit )
-//                 .add_combination( svi4, Coeff::Raw( Some(1), None, Some(2) )
-//                 .add_combination( ( (svi5, Some(3)), (svi6, Some(2)), (svi7, None) )
-// 
-//     let agg = 
-// 
-// 
-//                 .add_vector(      svi3, Coeff::unit )
-//                 .add_vector(      svi3, Coeff::scalar(3))
-//     let agg = svi2.bulk_insert( (svi0, svi1) ). 
-// 
-// (svi,)kk
-//                 .hit_merge( (svi2,), C )
-//                 .simplify(&R)
-//                 .merge(  hit_merge(svii, C).simplify(&R)  )
-// 
-//     svi
-//         .gather_terms(R.clone())
-//         .drop(R.zero())
-//         .hit_merge( 
-//     
-// 
-//     svi + svi2 + sum(svi_iter)
-//     svi 
-//         .add( svi2, &R)
-//         .gather_terms(R.clone())
-//         .drop_zeros(R.clone())
-//         .add( 
-//             hit_merge(svi_iter)
-//             .gather_terms(R.clone())
-//             );
-//         .gather()
-//         .drop_zeros(&R)
-//    
-// 
-//     spi
-//         .into_svi(R)
-//         .gather_terms()
-//         .
-// 
-//     kk
-// 
-//     svir.add( svi );
-//     svir.add_simple( svi );
-// 
-// 
-//     vec.add( vec2, ring ).drop_zero(ring).
-// 
-//     add_1_simple
-//     add_k_simple
-//     
-//     vec.add_1_simple( vec2, ring )
-//     vec.add_k_simple(
-// 
-//     simple_add
-//     simple_sum_several
-// 
-//     simple_add_k_other
-//     simple_add_k_same
-// 
-//     
-//     // UNDER CONSTRUCTION
-//    //  fn add < Ring, Svi2> (mut self, ring: Ring, svi2: Svi2) -> 
-// 
-// 
-//    //  svi.add( svi2, ring ).gather(ring)
-//    //  svi.add_several( svi_set, ring ).gather(ring)
-// 
 
 
 
@@ -666,13 +1623,115 @@ mod tests {
         // MERGE CONSECUTIVE ENTRIES THAT SHARE THE SAME INDEX
         let gathered : Vec<_> = sparse_vec
                                 .clone() // this makes a copy of the iterator, so the original stays unchanged
-                                .peekable() // this puts the iterator in a slightly different form, which is compatible with gather
                                 .gather( ring.clone() )
                                 .collect(); // this collects the entries of the iterator into a standard Rust vector
         assert_eq!( gathered, vec![ (1, 1.), (2, 2.), (3, 6.), (4, 0.) ]);        
+
+        // GATHER AND DROP ZEROS IN ONE PASS
+        let simplified : Vec<_> = sparse_vec
+                                .clone()
+                                .chain( vec![ (5, 1.), (5, -1.) ] )
+                                .simplify( ring.clone() )
+                                .collect();
+        assert_eq!( simplified, vec![ (1, 1.), (2, 2.), (3, 6.) ]);        
         
     }       
 
+    #[test]
+    fn test_add_subtract() {
+        use crate::rings::ring_native::NativeRing;
+        use crate::utilities::order::{OrderNatural, OrderReverse};
+
+        let ring    =   NativeRing::<i64>::new();
+        // repeated keys within either vector are gathered as well
+        let a       =   vec![ (0, 1), (0, 1), (2, 3), (5, 1) ];
+        let b       =   vec![ (0, -2), (1, 1), (5, 1), (5, 1) ];
+        let sum             =   a.iter().cloned().add( b.iter().cloned(), ring.clone(), OrderNatural );
+        assert!( format!( "{:?}", sum ).starts_with( "SortedSum" ) );
+        assert_eq!( sum.clone().collect::< Vec<_> >(), vec![ (1, 1), (2, 3), (5, 3) ] );
+        assert_eq!( sum.collect::< Vec<_> >(), vec![ (1, 1), (2, 3), (5, 3) ] );
+        let difference : Vec<_>     =   a.iter().cloned().subtract( b.iter().cloned(), ring.clone(), OrderNatural ).collect();
+        assert_eq!( difference, vec![ (0, 4), (1, -1), (2, 3), (5, -1) ] );
+
+        // KeyValItem entries, sorted in descending order
+        let a : Vec< KeyValItem< usize, i64 > >   =   a.iter().rev().map( |&( key, val )| KeyValItem{ key, val } ).collect();
+        let sum : Vec<_>    =   a.into_iter().add( b.into_iter().rev(), ring, OrderReverse::new( OrderNatural ) ).collect();
+        assert_eq!( sum, vec![ (5, 3), (2, 3), (1, 1) ] );
+    }
+
+    #[test]
+    fn test_filter_keys() {
+        let entries     =   vec![ (0, 1.), (1, 2.), (2, 3.), (3, 4.), (5, 5.) ];
+        let keys        =   | v: Vec< (usize, f64) > | v.into_iter().map( |x| x.0 ).collect::< Vec<_> >();
+
+        assert_eq!( keys( entries.iter().cloned().filter_keys_sorted( 1 .. 3 ).collect() ), vec![ 1, 2 ] );
+        assert_eq!( keys( entries.iter().cloned().filter_keys_sorted( 1 ..= 3 ).collect() ), vec![ 1, 2, 3 ] );
+        assert_eq!( keys( entries.iter().cloned().filter_keys_sorted( .. 2 ).collect() ), vec![ 0, 1 ] );
+        assert_eq!( keys( entries.iter().cloned().filter_keys_sorted( ..= 2 ).collect() ), vec![ 0, 1, 2 ] );
+        assert_eq!( keys( entries.iter().cloned().filter_keys_sorted( 3 .. ).collect() ), vec![ 3, 5 ] );
+        assert_eq!( keys( entries.iter().cloned().filter_keys_sorted( 6 .. 9 ).collect() ), Vec::< usize >::new() );
+
+        // without the sorted fast path, unsorted input is filtered correctly
+        let unsorted    =   entries.iter().cloned().rev();
+        assert_eq!( keys( unsorted.clone().filter_keys( 1 .. 3 ).collect() ), vec![ 2, 1 ] );
+        assert_eq!( keys( unsorted.filter_keys( |k: &usize| *k != 3 ).collect() ), vec![ 5, 2, 1, 0 ] );
+
+        // the iterator stays exhausted after stopping early
+        let mut filtered    =   entries.into_iter().chain( vec![ (1, 1.) ] ).filter_keys_sorted( .. 2 );
+        assert_eq!( filtered.by_ref().count(), 2 );
+        assert_eq!( filtered.next(), None );
+    }
+
+    #[test]
+    fn test_assert_sorted() {
+        use crate::utilities::order::{OrderNatural, OrderReverse};
+
+        let entries     =   [ (0, 1), (1, 1), (1, 1), (3, 1) ];
+        assert_eq!( entries.iter().cloned().assert_sorted( OrderNatural ).count(), 4 );
+        assert_eq!( entries.iter().cloned().rev().assert_sorted( OrderReverse::new( OrderNatural ) ).count(), 4 );
+
+        // the panic message names the keys
+        let result      =   std::panic::catch_unwind( || entries.iter().cloned().assert_sorted( OrderReverse::new( OrderNatural ) ).count() );
+        let message     =   result.unwrap_err().downcast::< String >().unwrap();
+        assert!( message.contains( "key 1 follows key 0" ) );
+
+        // checks are disabled in release builds
+        let unsorted    =   entries.iter().cloned().rev().debug_assert_sorted( OrderNatural );
+        if cfg!( debug_assertions ) { assert!( std::panic::catch_unwind( || unsorted.count() ).is_err() ) }
+        else { assert_eq!( unsorted.count(), 4 ) }
+    }
+
+    #[test]
+    fn test_linear_combination_simplified() {
+        use crate::rings::ring_native::NativeRing;
+        use crate::utilities::order::OrderNatural;
+        use rand::{Rng, SeedableRng};
+        use rand::rngs::StdRng;
+
+        let ring        =   NativeRing::<i64>::new();
+        let mut rng     =   StdRng::seed_from_u64( 0 );
+
+        // compare random linear combinations with the corresponding dense sums
+        for _ in 0 .. 100 {
+            let mut combination     =   LinearCombinationSimplified::new( ring.clone(), OrderNatural );
+            let mut dense           =   vec![ 0i64; 10 ];
+            for _ in 0 .. rng.gen_range( 0 .. 6 ) {
+                let mut svi : Vec< ( usize, i64 ) >   =   ( 0 .. rng.gen_range( 0 .. 6 ) ).map( |_| ( rng.gen_range( 0 .. 10 ), rng.gen_range( -2 .. 3 ) ) ).collect();
+                svi.sort();
+                let scalar  =   rng.gen_range( -2 .. 3 );
+                for &( key, val ) in svi.iter() { dense[ key ] += scalar * val }
+                combination.add_scaled( svi.into_iter(), scalar );
+            }
+            let expected : Vec<_>   =   dense.into_iter().enumerate().filter( |&( _, val )| val != 0 ).collect();
+            assert_eq!( combination.collect::< Vec<_> >(), expected );
+        }
+
+        // an empty combination is empty
+        let mut empty   =   LinearCombinationSimplified::< std::vec::IntoIter< ( usize, i64 ) >, _, _ >::new( ring, OrderNatural );
+        assert_eq!( empty.next(), None );
+        assert_eq!( empty.add_many( Vec::new() ).next(), None );
+    }
+
 }
 
 